@@ -0,0 +1,89 @@
+#![allow(unused_imports)]
+
+use anyhow::Result;
+use k8s_openapi::api::core::v1::{PersistentVolumeClaim, Pod};
+use kube::CustomResourceExt;
+use std::env;
+use tracing::{error, info};
+use verifiable_controllers::crds::{RabbitmqCluster, VDeployment, VReplicaSet, VStatefulSet};
+use verifiable_controllers::external_shim_layer::VoidExternalShimLayer;
+use verifiable_controllers::rabbitmq_controller::exec::reconciler::RabbitmqReconciler;
+use verifiable_controllers::shim_layer::controller_config::ControllerConfig;
+use verifiable_controllers::shim_layer::controller_manager::ControllerManager;
+use verifiable_controllers::vdeployment_controller::exec::reconciler::VDeploymentReconciler;
+use verifiable_controllers::vreplicaset_controller::exec::reconciler::VReplicaSetReconciler;
+use verifiable_controllers::vstatefulset_controller::exec::reconciler::VStatefulSetReconciler;
+
+const CONTROLLERS: [&str; 4] = ["vreplicaset", "vdeployment", "vstatefulset", "rabbitmq"];
+
+// run_manager runs the controllers listed by --controllers <c1,c2,...> (all of them by default) in one process.
+async fn run_manager(config: ControllerConfig, args: &[String]) -> Result<()> {
+    let controllers: Vec<String> = match args.iter().position(|arg| arg == "--controllers").and_then(|i| args.get(i + 1)) {
+        Some(val) => val.split(',').map(|c| c.trim().to_string()).collect(),
+        None => CONTROLLERS.iter().map(|c| c.to_string()).collect(),
+    };
+    let mut manager = ControllerManager::new(config).await?;
+    for controller in controllers {
+        match controller.as_str() {
+            "vreplicaset" => {
+                manager
+                    .register_watching_owned::<VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, Pod>(())
+                    .await?
+            }
+            "vdeployment" => {
+                manager
+                    .register_watching_owned::<VDeployment, VDeploymentReconciler, VoidExternalShimLayer, VReplicaSet>(())
+                    .await?
+            }
+            "vstatefulset" => {
+                manager
+                    .register_watching_owned::<VStatefulSet, VStatefulSetReconciler, VoidExternalShimLayer, PersistentVolumeClaim>(())
+                    .await?
+            }
+            "rabbitmq" => {
+                manager
+                    .register::<RabbitmqCluster, RabbitmqReconciler, VoidExternalShimLayer>(())
+                    .await?
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "unknown controller {}; --controllers expects a comma-separated list of {}",
+                    controller,
+                    CONTROLLERS.join(", ")
+                ))
+            }
+        }
+    }
+    manager.run().await
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+    let args: Vec<String> = env::args().collect();
+    let cmd = args.get(1).cloned().unwrap_or_default();
+    let config = ControllerConfig::from_args("controller-manager", &args)?;
+
+    if cmd == String::from("export") {
+        println!("{}", serde_yaml::to_string(&VReplicaSet::crd())?);
+        println!("---");
+        println!("{}", serde_yaml::to_string(&VDeployment::crd())?);
+        println!("---");
+        println!("{}", serde_yaml::to_string(&VStatefulSet::crd())?);
+        println!("---");
+        println!("{}", serde_yaml::to_string(&RabbitmqCluster::crd())?);
+    } else if cmd == String::from("run") {
+        info!("running controller-manager");
+        run_manager(config, &args).await?;
+    } else if cmd == String::from("crash") {
+        info!("running controller-manager in crash-testing mode");
+        let config = ControllerConfig {
+            fault_injection: true,
+            ..config
+        };
+        run_manager(config, &args).await?;
+    } else {
+        error!("wrong command; please use \"export\", \"run\" or \"crash\" with --controllers <c1,c2,...> (see ControllerConfig::from_args for the other options)");
+    }
+    Ok(())
+}
@@ -4,34 +4,69 @@ use anyhow::Result;
 use k8s_openapi::api::core::v1::Pod;
 use kube::CustomResourceExt;
 use std::env;
+use std::path::Path;
 use tracing::{error, info};
 use verifiable_controllers::crds::VReplicaSet;
 use verifiable_controllers::external_shim_layer::VoidExternalShimLayer;
+use verifiable_controllers::shim_layer::controller_config::ControllerConfig;
 use verifiable_controllers::shim_layer::controller_runtime::run_controller_watching_owned;
+use verifiable_controllers::shim_layer::plan::{format_plan, plan, plan_key};
+use verifiable_controllers::shim_layer::trace::replay_file;
 use verifiable_controllers::vreplicaset_controller::exec::reconciler::VReplicaSetReconciler;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
     let args: Vec<String> = env::args().collect();
-    let cmd = args[1].clone();
+    let cmd = args.get(1).cloned().unwrap_or_default();
+    let config = ControllerConfig::from_args("vreplicaset-controller", &args)?;
 
     if cmd == String::from("export") {
         println!("{}", serde_yaml::to_string(&VReplicaSet::crd())?);
     } else if cmd == String::from("run") {
         info!("running vreplicaset-controller");
-        run_controller_watching_owned::<VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, Pod>(
-            false,
-        )
+        run_controller_watching_owned::<
+            VReplicaSet,
+            VReplicaSetReconciler,
+            VoidExternalShimLayer,
+            Pod,
+        >(config, ())
         .await?;
     } else if cmd == String::from("crash") {
         info!("running vreplicaset-controller in crash-testing mode");
-        run_controller_watching_owned::<VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, Pod>(
-            true,
+        let config = ControllerConfig {
+            fault_injection: true,
+            ..config
+        };
+        run_controller_watching_owned::<
+            VReplicaSet,
+            VReplicaSetReconciler,
+            VoidExternalShimLayer,
+            Pod,
+        >(config, ())
+        .await?;
+    } else if cmd == String::from("replay") {
+        let trace_file = args
+            .get(2)
+            .ok_or_else(|| anyhow::anyhow!("\"replay\" expects a trace file"))?;
+        let replayed = replay_file::<VReplicaSet, VReplicaSetReconciler>(Path::new(trace_file))?;
+        info!("replayed {} reconciles from {}", replayed, trace_file);
+    } else if cmd == String::from("plan") {
+        let key = args
+            .get(2)
+            .ok_or_else(|| anyhow::anyhow!("\"plan\" expects <namespace>/<name>, or <name> for a cluster-scoped resource"))?;
+        let (namespace, name) = plan_key::<VReplicaSet>(key)?;
+        let simulate = args.iter().any(|arg| arg == "--simulate");
+        let changes = plan::<VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer>(
+            &namespace,
+            &name,
+            simulate,
+            &VoidExternalShimLayer {},
         )
         .await?;
+        print!("{}", format_plan(&changes));
     } else {
-        error!("wrong command; please use \"export\", \"run\" or \"crash\"");
+        error!("wrong command; please use \"export\", \"run\", \"crash\", \"replay <trace-file>\" or \"plan <namespace>/<name>|<name> [--simulate]\" (see ControllerConfig::from_args for the options of \"run\" and \"crash\")");
     }
     Ok(())
 }
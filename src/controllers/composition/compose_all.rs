@@ -116,6 +116,7 @@ proof fn vrs_guarantee_implies_vsts_rely(id: int)
                 APIRequest::GetThenUpdateRequest(req) => vsts_rely_get_then_update_req(req),
                 APIRequest::DeleteRequest(req) => vsts_rely_delete_req(req)(s),
                 APIRequest::GetThenDeleteRequest(req) => vsts_rely_get_then_delete_req(req),
+                APIRequest::PatchRequest(req) => vsts_rely_patch_req(req)(s),
                 _ => true,
             }) by {
             match msg.content->APIRequest_0 {
@@ -133,6 +134,59 @@ proof fn vrs_guarantee_implies_vsts_rely(id: int)
     };
 }
 
+// RMQ creates and updates the VStatefulSet of each RabbitmqCluster, which has no finalizer
+// and whose only owner reference (to the RabbitmqCluster) does not block the deletion of the owner.
+proof fn rmq_guarantee_implies_vsts_rely(id: int)
+    ensures lift_state(rmq_guarantee(id)).entails(lift_state(vsts_rely(id))),
+{
+    assert forall |s: ClusterState| #[trigger] rmq_guarantee(id)(s) implies vsts_rely(id)(s) by {
+        assert forall |msg| #[trigger] s.in_flight().contains(msg)
+            && msg.content is APIRequest
+            && msg.src.is_controller_id(id)
+            implies (match msg.content->APIRequest_0 {
+                APIRequest::CreateRequest(req) => vsts_rely_create_req(req),
+                APIRequest::UpdateRequest(req) => vsts_rely_update_req(req)(s),
+                APIRequest::GetThenUpdateRequest(req) => vsts_rely_get_then_update_req(req),
+                APIRequest::DeleteRequest(req) => vsts_rely_delete_req(req)(s),
+                APIRequest::GetThenDeleteRequest(req) => vsts_rely_get_then_delete_req(req),
+                APIRequest::PatchRequest(req) => vsts_rely_patch_req(req)(s),
+                _ => true,
+            }) by {
+            match msg.content->APIRequest_0 {
+                APIRequest::CreateRequest(r) => {
+                    assert(rmq_guarantee_create_req(r));
+                    if r.obj.kind == VStatefulSetView::kind() {
+                        rmq_vstatefulset_satisfies_vsts_rely(r.obj);
+                    }
+                }
+                APIRequest::GetThenUpdateRequest(r) => {
+                    assert(rmq_guarantee_get_then_update_req(r));
+                    if r.obj.kind == VStatefulSetView::kind() {
+                        rmq_vstatefulset_satisfies_vsts_rely(r.obj);
+                    }
+                }
+                _ => {}
+            }
+        };
+    };
+}
+
+proof fn rmq_vstatefulset_satisfies_vsts_rely(obj: DynamicObjectView)
+    requires
+        obj.metadata.finalizers is None,
+        obj.metadata.owner_references is Some,
+        exists |rabbitmq: RabbitmqClusterView| obj.metadata.owner_references->0 == seq![#[trigger] rabbitmq.controller_owner_ref()],
+    ensures vsts_rely_vsts_obj(obj),
+{
+    let owner_references = obj.metadata.owner_references->0;
+    let rabbitmq = choose |rabbitmq: RabbitmqClusterView| owner_references == seq![#[trigger] rabbitmq.controller_owner_ref()];
+    assert forall |owner_reference: OwnerReferenceView| #[trigger] obj.metadata.owner_references_contains(owner_reference)
+        implies owner_reference.block_owner_deletion != Some(true) by {
+        let i = choose |i| 0 <= i < owner_references.len() && owner_references[i] == owner_reference;
+        assert(owner_reference == rabbitmq.controller_owner_ref());
+    }
+}
+
 proof fn vsts_guarantee_implies_vrs_rely(id: int)
     ensures lift_state(vsts_guarantee(id)).entails(lift_state(vrs_rely(id))),
 {
@@ -148,6 +202,7 @@ proof fn vsts_guarantee_implies_vrs_rely(id: int)
                 APIRequest::DeleteRequest(req) => vrs_rely_delete_req(req)(s),
                 APIRequest::GetThenDeleteRequest(req) => vrs_rely_get_then_delete_req(req)(s),
                 APIRequest::GetThenUpdateStatusRequest(req) => vrs_rely_get_then_update_status_req(req)(s),
+                APIRequest::PatchRequest(req) => vrs_rely_patch_req(req)(s),
                 _ => true,
             }) by {
             match msg.content->APIRequest_0 {
@@ -163,6 +218,10 @@ proof fn vsts_guarantee_implies_vrs_rely(id: int)
                     assert(vsts_guarantee_get_then_delete_req(r));
                     vsts_prefix_not_vrs_prefix(r.key.name);
                 }
+                APIRequest::UpdateStatusRequest(r) => {
+                    assert(vsts_guarantee_update_status_req(r));
+                    kind_strings_distinct();
+                }
                 _ => {}
             }
         };
@@ -266,6 +325,7 @@ pub proof fn vsts_rmq_core_holds(cluster: CoreCluster)
         let r12_fn = |pair: (int, int)| if s1.members.contains(pair.0) && !s1.members.contains(pair.1) && s2.members.contains(pair.1) { (cluster.registry[pair.0].safety_partial_rely)(pair.1) } else { true_pred::<ClusterState>() };
         let r21_fn = |pair: (int, int)| if s2.members.contains(pair.0) && !s2.members.contains(pair.1) && s1.members.contains(pair.1) { (cluster.registry[pair.0].safety_partial_rely)(pair.1) } else { true_pred::<ClusterState>() };
 
+        rmq_guarantee_implies_vsts_rely(rmq_id());
         entails_preserved_by_always(lift_state(vsts_guarantee(vsts_id())), lift_state(rmq_rely(vsts_id())));
         entails_preserved_by_always(lift_state(rmq_guarantee(rmq_id())), lift_state(vsts_rely(rmq_id())));
 
@@ -2,3 +2,4 @@
 // SPDX-License-Identifier: MIT
 pub mod reconciler;
 pub mod resource;
+pub mod shim_hooks;
@@ -45,6 +45,14 @@ impl Reconciler for RabbitmqReconciler {
     fn reconcile_error(state: &RabbitmqReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    fn finalizer() -> Option<String> {
+        None
+    }
+
+    fn reconcile_delete(rabbitmq: &RabbitmqCluster, resp_o: Option<Response<VoidEResp>>, state: RabbitmqReconcileState) -> (RabbitmqReconcileState, Option<Request<VoidEReq>>) {
+        reconcile_core(rabbitmq, resp_o, state)
+    }
 }
 
 pub fn reconcile_init_state() -> (state: RabbitmqReconcileState)
@@ -137,6 +145,7 @@ pub fn reconcile_helper<
                                     namespace: rabbitmq.metadata().namespace().unwrap(),
                                     obj: updated_obj,
                                     owner_ref: rabbitmq.controller_owner_ref(),
+                                    predicate: KubeGetThenPredicate::OwnerRef,
                                 });
                                 let state_prime = RabbitmqReconcileState {
                                     reconcile_step: RabbitmqReconcileStep::AfterKRequestStep(ActionKind::Update, resource),
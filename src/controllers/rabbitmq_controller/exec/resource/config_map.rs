@@ -30,6 +30,7 @@ impl ResourceBuilder<RabbitmqCluster, RabbitmqReconcileState, model_resource::Se
             api_resource: ConfigMap::api_resource(),
             name: make_server_config_map_name(rabbitmq),
             namespace: rabbitmq.metadata().namespace().unwrap(),
+            read_mode: KubeReadMode::Quorum,
         }
     }
 
@@ -30,6 +30,7 @@ impl ResourceBuilder<RabbitmqCluster, RabbitmqReconcileState, model_resource::De
             api_resource: Secret::api_resource(),
             name: make_default_user_secret_name(rabbitmq),
             namespace: rabbitmq.metadata().namespace().unwrap(),
+            read_mode: KubeReadMode::Quorum,
         }
     }
 
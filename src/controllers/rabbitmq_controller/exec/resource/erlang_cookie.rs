@@ -30,6 +30,7 @@ impl ResourceBuilder<RabbitmqCluster, RabbitmqReconcileState, model_resource::Er
             api_resource: Secret::api_resource(),
             name: make_erlang_secret_name(rabbitmq),
             namespace: rabbitmq.metadata().namespace().unwrap(),
+            read_mode: KubeReadMode::Quorum,
         }
     }
 
@@ -30,6 +30,7 @@ impl ResourceBuilder<RabbitmqCluster, RabbitmqReconcileState, model_resource::He
             api_resource: Service::api_resource(),
             name: make_headless_service_name(rabbitmq),
             namespace: rabbitmq.metadata().namespace().unwrap(),
+            read_mode: KubeReadMode::Quorum,
         }
     }
 
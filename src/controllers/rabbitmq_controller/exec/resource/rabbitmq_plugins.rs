@@ -30,6 +30,7 @@ impl ResourceBuilder<RabbitmqCluster, RabbitmqReconcileState, model_resource::Pl
             api_resource: ConfigMap::api_resource(),
             name: make_plugins_config_map_name(rabbitmq),
             namespace: rabbitmq.metadata().namespace().unwrap(),
+            read_mode: KubeReadMode::Quorum,
         }
     }
 
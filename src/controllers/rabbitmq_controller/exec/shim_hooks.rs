@@ -0,0 +1,18 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::rabbitmq_controller::exec::reconciler::RabbitmqReconciler;
+use crate::rabbitmq_controller::trusted::exec_types::RabbitmqReconcileState;
+use crate::shim_layer::events::ReconcileEvents;
+use crate::shim_layer::trace::ReconcileStepName;
+
+// The hooks that the shim layer calls when running the RabbitmqCluster controller.
+// They are not verified and stay out of the exec reconciler, so that the verified code does not depend on the shim layer.
+
+// The name of a reconcile step is the Debug form of the step.
+impl ReconcileStepName for RabbitmqReconcileState {
+    fn reconcile_step_name(&self) -> String {
+        format!("{:?}", self.reconcile_step)
+    }
+}
+
+impl ReconcileEvents for RabbitmqReconciler {}
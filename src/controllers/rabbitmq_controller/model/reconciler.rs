@@ -37,6 +37,16 @@ impl Reconciler<RabbitmqReconcileState, RabbitmqClusterView, VoidEReqView, VoidE
     open spec fn reconcile_error(state: RabbitmqReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    // The objects created by the RabbitmqCluster are owned by it and removed by the garbage collector,
+    // so there is nothing to clean up and reconcile_delete is never invoked.
+    open spec fn finalizer() -> Option<StringView> {
+        None
+    }
+
+    open spec fn reconcile_delete(rabbitmq: RabbitmqClusterView, resp_o: Option<ResponseView<VoidERespView>>, state: RabbitmqReconcileState) -> (RabbitmqReconcileState, Option<RequestView<VoidEReqView>>) {
+        reconcile_core(rabbitmq, resp_o, state)
+    }
 }
 
 pub open spec fn reconcile_init_state() -> RabbitmqReconcileState {
@@ -136,6 +146,7 @@ pub open spec fn reconcile_helper<Builder: ResourceBuilder<RabbitmqClusterView,
                                     name: Builder::get_request(rabbitmq).key.name,
                                     obj: updated_obj,
                                     owner_ref: rabbitmq.controller_owner_ref(),
+                                    predicate: GetThenPredicate::OwnerRef,
                                 });
                                 let state_prime = RabbitmqReconcileState {
                                     reconcile_step: RabbitmqReconcileStep::AfterKRequestStep(ActionKind::Update, resource),
@@ -23,7 +23,7 @@ pub struct ServerConfigMapBuilder {}
 
 impl ResourceBuilder<RabbitmqClusterView, RabbitmqReconcileState> for ServerConfigMapBuilder {
     open spec fn get_request(rabbitmq: RabbitmqClusterView) -> GetRequest {
-        GetRequest { key: make_server_config_map_key(rabbitmq) }
+        GetRequest { key: make_server_config_map_key(rabbitmq), read_mode: ReadMode::Quorum }
     }
 
     open spec fn make(rabbitmq: RabbitmqClusterView, state: RabbitmqReconcileState) -> Result<DynamicObjectView, ()> {
@@ -23,7 +23,7 @@ pub struct DefaultUserSecretBuilder {}
 
 impl ResourceBuilder<RabbitmqClusterView, RabbitmqReconcileState> for DefaultUserSecretBuilder {
     open spec fn get_request(rabbitmq: RabbitmqClusterView) -> GetRequest {
-        GetRequest { key: make_default_user_secret_key(rabbitmq) }
+        GetRequest { key: make_default_user_secret_key(rabbitmq), read_mode: ReadMode::Quorum }
     }
 
     open spec fn make(rabbitmq: RabbitmqClusterView, state: RabbitmqReconcileState) -> Result<DynamicObjectView, ()> {
@@ -23,7 +23,7 @@ pub struct HeadlessServiceBuilder {}
 
 impl ResourceBuilder<RabbitmqClusterView, RabbitmqReconcileState> for HeadlessServiceBuilder {
     open spec fn get_request(rabbitmq: RabbitmqClusterView) -> GetRequest {
-        GetRequest { key: make_headless_service_key(rabbitmq) }
+        GetRequest { key: make_headless_service_key(rabbitmq), read_mode: ReadMode::Quorum }
     }
 
     open spec fn make(rabbitmq: RabbitmqClusterView, state: RabbitmqReconcileState) -> Result<DynamicObjectView, ()> {
@@ -23,7 +23,7 @@ pub struct PluginsConfigMapBuilder {}
 
 impl ResourceBuilder<RabbitmqClusterView, RabbitmqReconcileState> for PluginsConfigMapBuilder {
     open spec fn get_request(rabbitmq: RabbitmqClusterView) -> GetRequest {
-        GetRequest { key: make_plugins_config_map_key(rabbitmq) }
+        GetRequest { key: make_plugins_config_map_key(rabbitmq), read_mode: ReadMode::Quorum }
     }
 
     open spec fn make(rabbitmq: RabbitmqClusterView, state: RabbitmqReconcileState) -> Result<DynamicObjectView, ()> {
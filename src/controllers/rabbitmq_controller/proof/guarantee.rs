@@ -145,11 +145,19 @@ pub proof fn lemma_guarantee_from_reconcile_state(
                             let req = msg.content.get_get_then_update_request();
                             lemma_resource_key_has_rmq_prefix(resource, rabbitmq);
                             assert(has_rmq_prefix(req.name));
+                            if resource is VStatefulSetView {
+                                // update_stateful_set clears the finalizers
+                                assert(req.obj.metadata.finalizers is None);
+                            }
                             assert(rmq_guarantee_get_then_update_req(req));
                         } else if get_resp->Err_0 is ObjectNotFound {
                             assert(msg.content.is_create_request());
                             let req = msg.content.get_create_request();
                             lemma_resource_key_has_rmq_prefix(resource, rabbitmq);
+                            if resource is VStatefulSetView {
+                                // make_stateful_set sets no finalizer
+                                assert(req.obj.metadata.finalizers is None);
+                            }
                             assert(rmq_guarantee_create_req(req));
                         } else {
                             // Error case: no message sent (reconcile_core returns None)
@@ -149,6 +149,23 @@ pub open spec fn no_delete_resource_request_msg_from_gc_in_flight(sub_resource:
     }
 }
 
+// No update or update status request to resource_key from the builtin controllers is in flight.
+// The scheduler and the kubelet only update pods, and the garbage collector only updates an object that is being deleted
+// or whose owner is gone or being deleted. resource_key is not a pod, has no deletion timestamp, and is only owned by
+// the current rabbitmq, which is alive and not being deleted while desired_state_is(rabbitmq) holds.
+pub open spec fn no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource: SubResource, rabbitmq: RabbitmqClusterView) -> StatePred<ClusterState> {
+    let resource_key = get_request(sub_resource, rabbitmq).key;
+    |s: ClusterState| {
+        forall |msg: Message| {
+            &&& #[trigger] s.in_flight().contains(msg)
+            &&& msg.src == HostId::BuiltinController
+        } ==> {
+            &&& !resource_update_request_msg(resource_key)(msg)
+            &&& !resource_update_status_request_msg(resource_key)(msg)
+        }
+    }
+}
+
 // Self-rely-guarantee between RMQ reconciles managed by the same controller_id
 // but for different cr_keys. Says that any in-flight request issued by another
 // (cr_key', controller_id) reconcile of the same RMQ kind does not target any
@@ -170,7 +170,7 @@ pub proof fn lemma_always_resource_object_has_no_finalizers_or_timestamp_and_onl
     cluster.lemma_always_every_in_flight_req_msg_from_controller_has_valid_controller_id(spec);
     cluster.lemma_always_no_pending_request_to_api_server_from_api_server_or_external(spec);
     cluster.lemma_always_all_requests_from_pod_monkey_are_api_pod_requests(spec);
-    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_requests(spec);
+    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests(spec);
     cluster.lemma_always_cr_objects_in_reconcile_have_correct_kind::<RabbitmqClusterView>(spec, controller_id);
     cluster.lemma_always_every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(spec, controller_id);
     cluster.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
@@ -178,7 +178,7 @@ pub proof fn lemma_always_resource_object_has_no_finalizers_or_timestamp_and_onl
     always_to_always_later(spec, lift_state(rmq_guarantee(controller_id)));
     always_to_always_later(spec, lift_state(rmq_rely_conditions(cluster, controller_id)));
     always_to_always_later(spec, lift_state(Cluster::all_requests_from_pod_monkey_are_api_pod_requests()));
-    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()));
+    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
     let stronger_next = |s, s_prime| {
         &&& cluster.next()(s, s_prime)
         // &&& resource_object_create_or_update_request_msg_has_one_controller_ref_and_no_finalizers_nor_deletion_timestamp(sub_resource, rabbitmq)(s)
@@ -191,7 +191,7 @@ pub proof fn lemma_always_resource_object_has_no_finalizers_or_timestamp_and_onl
         &&& requests_from_rmq_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(controller_id, sub_resource, rabbitmq.object_ref())(s)
         &&& Cluster::no_pending_request_to_api_server_from_api_server_or_external()(s)
         &&& Cluster::all_requests_from_pod_monkey_are_api_pod_requests()(s)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s)
         &&& Cluster::cr_objects_in_reconcile_have_correct_kind::<RabbitmqClusterView>(controller_id)(s)
         &&& Cluster::every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(controller_id)(s)
         &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
@@ -211,8 +211,8 @@ pub proof fn lemma_always_resource_object_has_no_finalizers_or_timestamp_and_onl
         lift_state(Cluster::no_pending_request_to_api_server_from_api_server_or_external()),
         lift_state(Cluster::all_requests_from_pod_monkey_are_api_pod_requests()),
         later(lift_state(Cluster::all_requests_from_pod_monkey_are_api_pod_requests())),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
-        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
+        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
         lift_state(Cluster::cr_objects_in_reconcile_have_correct_kind::<RabbitmqClusterView>(controller_id)),
         lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(controller_id)),
         lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
@@ -543,6 +543,119 @@ proof fn lemma_eventually_always_no_delete_resource_request_msg_from_gc_in_fligh
     );
 }
 
+pub proof fn lemma_eventually_always_no_update_resource_request_msg_from_builtin_controllers_in_flight_forall(controller_id: int, cluster: Cluster, spec: TempPred<ClusterState>, rabbitmq: RabbitmqClusterView)
+    requires
+        cluster.type_is_installed_in_cluster::<RabbitmqClusterView>(),
+        cluster.type_is_installed_in_cluster::<VStatefulSetView>(),
+        cluster.controller_models.contains_pair(controller_id, rabbitmq_controller_model()),
+        spec.entails(always(lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<RabbitmqClusterView>()))),
+        spec.entails(always(lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()))),
+        spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
+        spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
+        spec.entails(always(lift_action(cluster.next()))),
+        spec.entails(tla_forall(|i| cluster.api_server_next().weak_fairness(i))),
+        spec.entails(always(lift_state(Cluster::desired_state_is(rabbitmq)))),
+        spec.entails(always(tla_forall(|sub_resource: SubResource| lift_state(resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq))))),
+        spec.entails(always(tla_forall(|sub_resource: SubResource| lift_state(resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq))))),
+    ensures spec.entails(true_pred().leads_to(always(tla_forall(|sub_resource: SubResource| lift_state(no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)))))),
+{
+    assert forall |sub_resource: SubResource| spec.entails(true_pred().leads_to(always(lift_state(#[trigger] no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq))))) by {
+        always_tla_forall_apply(spec, |res: SubResource| lift_state(resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(res, rabbitmq)), sub_resource);
+        always_tla_forall_apply(spec, |res: SubResource| lift_state(resource_object_only_has_owner_reference_pointing_to_current_cr(res, rabbitmq)), sub_resource);
+        lemma_eventually_always_no_update_resource_request_msg_from_builtin_controllers_in_flight(controller_id, cluster, spec, sub_resource, rabbitmq);
+    }
+    leads_to_always_tla_forall_subresource(spec, true_pred(), |sub_resource: SubResource| lift_state(no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)));
+}
+
+proof fn lemma_eventually_always_no_update_resource_request_msg_from_builtin_controllers_in_flight(controller_id: int, cluster: Cluster, spec: TempPred<ClusterState>, sub_resource: SubResource, rabbitmq: RabbitmqClusterView)
+    requires
+        cluster.type_is_installed_in_cluster::<RabbitmqClusterView>(),
+        cluster.type_is_installed_in_cluster::<VStatefulSetView>(),
+        cluster.controller_models.contains_pair(controller_id, rabbitmq_controller_model()),
+        spec.entails(always(lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<RabbitmqClusterView>()))),
+        spec.entails(always(lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()))),
+        spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
+        spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
+        spec.entails(always(lift_action(cluster.next()))),
+        spec.entails(tla_forall(|i| cluster.api_server_next().weak_fairness(i))),
+        spec.entails(always(lift_state(Cluster::desired_state_is(rabbitmq)))),
+        spec.entails(always(lift_state(resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)))),
+        spec.entails(always(lift_state(resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)))),
+    ensures spec.entails(true_pred().leads_to(always(lift_state(no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq))))),
+{
+    let key = rabbitmq.object_ref();
+    let resource_key = get_request(sub_resource, rabbitmq).key;
+    let requirements = |msg: Message, s: ClusterState| {
+        msg.src == HostId::BuiltinController
+        ==> !resource_update_request_msg(resource_key)(msg) && !resource_update_status_request_msg(resource_key)(msg)
+    };
+
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& Cluster::desired_state_is(rabbitmq)(s)
+        &&& resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)(s)
+        &&& resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)(s)
+        &&& cluster.each_custom_object_in_etcd_is_well_formed::<RabbitmqClusterView>()(s)
+        &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
+        &&& Cluster::no_pending_request_to_api_server_from_non_controllers()(s_prime)
+    };
+    assert forall |s: ClusterState, s_prime: ClusterState| #[trigger] stronger_next(s, s_prime) implies Cluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)(s, s_prime) by {
+        assert forall |msg: Message| (!s.in_flight().contains(msg) || requirements(msg, s)) && #[trigger] s_prime.in_flight().contains(msg)
+        implies requirements(msg, s_prime) by {
+            if s.in_flight().contains(msg) {
+                assert(requirements(msg, s));
+                assert(requirements(msg, s_prime));
+            } else {
+                let step = choose |step| cluster.next_step(s, s_prime, step);
+                match step {
+                    Step::BuiltinControllersStep(input) => {
+                        let target_key = input.1;
+                        // The scheduler and the kubelet only update pods, and resource_key is not a pod.
+                        lemma_resource_key_is_not_pod(sub_resource, rabbitmq);
+                        if s.resources().contains_key(target_key) {
+                            // The garbage collector updates the object at target_key, whose kind is the kind of target_key.
+                            assert(s.resources()[target_key].object_ref() == target_key);
+                        }
+                        if target_key == resource_key && s.resources().contains_key(resource_key) {
+                            // resource_key has no deletion timestamp, and its only owner is the current rabbitmq,
+                            // which exists with the same uid and has no deletion timestamp either,
+                            // so the garbage collector neither removes its finalizers nor changes its owner references.
+                            assert(cluster.etcd_object_is_well_formed(key)(s));
+                            assert(s.resources()[resource_key].metadata.deletion_timestamp is None);
+                            let owner_refs = s.resources()[resource_key].metadata.owner_references;
+                            assert(owner_refs == Some(seq![rabbitmq.controller_owner_ref()]));
+                            assert(owner_refs->0[0] == rabbitmq.controller_owner_ref());
+                            assert(owner_reference_to_object_reference(owner_refs->0[0], key.namespace) == key);
+                            assert(s.resources()[key].metadata.uid == Some(owner_refs->0[0].uid));
+                        }
+                    },
+                    _ => {
+                        assert(msg.src != HostId::BuiltinController);
+                    }, // no_pending_request_to_api_server_from_non_controllers
+                }
+            }
+        }
+    }
+    always_to_always_later(spec, lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()));
+    invariant_n!(spec,
+        lift_action(stronger_next),
+        lift_action(Cluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
+        lift_action(cluster.next()),
+        lift_state(Cluster::desired_state_is(rabbitmq)),
+        lift_state(resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)),
+        lift_state(resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)),
+        lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<RabbitmqClusterView>()),
+        lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
+        later(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))
+    );
+
+    cluster.lemma_true_leads_to_always_every_in_flight_req_msg_satisfies(spec, requirements);
+    temp_pred_equality(
+        lift_state(no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)),
+        lift_state(Cluster::every_in_flight_req_msg_satisfies(requirements))
+    );
+}
+
 // TODO: inline
 pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr_forall(controller_id: int, cluster: Cluster, spec: TempPred<ClusterState>, rabbitmq: RabbitmqClusterView)
     requires
@@ -559,7 +672,7 @@ pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_po
         spec.entails(always(lift_state(Cluster::desired_state_is(rabbitmq)))),
         spec.entails(always(lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()))),
         spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
-        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
         spec.entails(always(tla_forall(|sub_resource: SubResource| lift_state(resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq))))),
         spec.entails(always(tla_forall(|sub_resource: SubResource| lift_state(Cluster::every_create_msg_sets_owner_references_as(get_request(sub_resource, rabbitmq).key, owner_ref_is_current_cr_only(rabbitmq)))))),
         spec.entails(always(tla_forall(|sub_resource: SubResource| lift_state(Cluster::every_create_msg_with_generate_name_matching_key_set_owner_references_as(get_request(sub_resource, rabbitmq).key, owner_ref_is_current_cr_only(rabbitmq)))))),
@@ -591,7 +704,7 @@ proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointi
         spec.entails(always(lift_state(Cluster::desired_state_is(rabbitmq)))),
         spec.entails(always(lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()))),
         spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
-        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
         spec.entails(always(lift_state(resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)))),
         spec.entails(always(lift_state(Cluster::every_create_msg_sets_owner_references_as(get_request(sub_resource, rabbitmq).key, owner_ref_is_current_cr_only(rabbitmq))))),
         spec.entails(always(lift_state(Cluster::every_create_msg_with_generate_name_matching_key_set_owner_references_as(get_request(sub_resource, rabbitmq).key, owner_ref_is_current_cr_only(rabbitmq))))),
@@ -612,7 +725,7 @@ proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointi
         && cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s)
         && Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
         && Cluster::no_pending_request_to_api_server_from_non_controllers()(s)
-        && Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s)
+        && Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s)
         implies Cluster::every_valid_update_msg_sets_owner_references_as(cluster.installed_types, resource_key, eventual_owner_ref)(s) by {
         if s.resources().contains_key(resource_key) {
             let etcd_obj = s.resources()[resource_key];
@@ -675,7 +788,7 @@ proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointi
     combine_spec_entails_always_n!(spec,
         lift_state(Cluster::every_valid_update_msg_sets_owner_references_as(cluster.installed_types, resource_key, eventual_owner_ref)),
         lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
         lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
         lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()),
         lift_state(resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)),
@@ -693,7 +806,7 @@ proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointi
         && resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)(s)
     };
     invariant_n!(
-        spec, lift_state(state), lift_state(Cluster::objects_owner_references_violates(resource_key, eventual_owner_ref)).implies(lift_state(Cluster::garbage_collector_deletion_enabled(resource_key))),
+        spec, lift_state(state), lift_state(Cluster::objects_owner_references_violates(resource_key, eventual_owner_ref)).implies(lift_state(cluster.garbage_collector_deletion_enabled(resource_key))),
         lift_state(Cluster::desired_state_is(rabbitmq)),
         lift_state(resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq))
     );
@@ -834,7 +947,7 @@ pub proof fn lemma_always_sts_in_etcd_with_rmq_key_match_rmq_selector(
         &&& Cluster::cr_objects_in_reconcile_satisfy_state_validation::<RabbitmqClusterView>(controller_id)(s)
         &&& cluster.each_custom_object_in_etcd_is_well_formed::<VStatefulSetView>()(s_prime)
         &&& Cluster::all_requests_from_pod_monkey_are_api_pod_requests()(s)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s)
         &&& Cluster::no_pending_request_to_api_server_from_api_server_or_external()(s)
         &&& sts_create_request_msg_has_correct_selector_with_rabbitmq_name(rabbitmq)(s)
         &&& rmq_guarantee(controller_id)(s)
@@ -847,7 +960,7 @@ pub proof fn lemma_always_sts_in_etcd_with_rmq_key_match_rmq_selector(
     cluster.lemma_always_cr_objects_in_reconcile_satisfy_state_validation::<RabbitmqClusterView>(spec, controller_id);
     cluster.lemma_always_each_custom_object_in_etcd_is_well_formed::<VStatefulSetView>(spec);
     cluster.lemma_always_all_requests_from_pod_monkey_are_api_pod_requests(spec);
-    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_requests(spec);
+    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests(spec);
     cluster.lemma_always_no_pending_request_to_api_server_from_api_server_or_external(spec);
     guarantee_condition_holds(spec, cluster, controller_id);
     lemma_always_resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(controller_id, cluster, spec, SubResource::VStatefulSetView, rabbitmq);
@@ -863,7 +976,7 @@ pub proof fn lemma_always_sts_in_etcd_with_rmq_key_match_rmq_selector(
         lift_state(Cluster::cr_objects_in_reconcile_satisfy_state_validation::<RabbitmqClusterView>(controller_id)),
         later(lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<VStatefulSetView>())),
         lift_state(Cluster::all_requests_from_pod_monkey_are_api_pod_requests()),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
         lift_state(Cluster::no_pending_request_to_api_server_from_api_server_or_external()),
         lift_state(sts_create_request_msg_has_correct_selector_with_rabbitmq_name(rabbitmq)),
         lift_state(rmq_guarantee(controller_id)),
@@ -932,7 +1045,7 @@ pub proof fn lemma_eventually_always_create_msg_owner_refs_satisfies_for_sub_res
         spec.entails(always(lift_state(Cluster::cr_states_are_unmarshallable::<RabbitmqReconcileState, RabbitmqClusterView>(controller_id)))),
         spec.entails(always(lift_state(Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id)))),
         spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
-        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
         spec.entails(always(lift_state(rmq_guarantee(controller_id)))),
         spec.entails(always(lift_state(rmq_rely_conditions(cluster, controller_id)))),
     ensures
@@ -976,7 +1089,7 @@ pub proof fn lemma_eventually_always_create_msg_owner_refs_satisfies_for_sub_res
         spec.entails(always(lift_state(Cluster::cr_states_are_unmarshallable::<RabbitmqReconcileState, RabbitmqClusterView>(controller_id)))),
         spec.entails(always(lift_state(Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id)))),
         spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
-        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
         spec.entails(always(lift_state(rmq_guarantee(controller_id)))),
         spec.entails(always(lift_state(rmq_rely_conditions(cluster, controller_id)))),
     ensures
@@ -1004,7 +1117,7 @@ pub proof fn lemma_eventually_always_create_msg_owner_refs_satisfies_for_sub_res
         &&& cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s)
         &&& cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s_prime)
         &&& Cluster::no_pending_request_to_api_server_from_non_controllers()(s_prime)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s_prime)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s_prime)
         &&& rmq_guarantee(controller_id)(s_prime)
         &&& rmq_rely_conditions(cluster, controller_id)(s_prime)
     };
@@ -1061,7 +1174,7 @@ pub proof fn lemma_eventually_always_create_msg_owner_refs_satisfies_for_sub_res
     always_to_always_later(spec, lift_state(rmq_rely_conditions(cluster, controller_id)));
     always_to_always_later(spec, lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()));
     always_to_always_later(spec, lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()));
-    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()));
+    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
     invariant_n!(spec,
         lift_action(stronger_next),
         lift_action(Cluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
@@ -1075,7 +1188,7 @@ pub proof fn lemma_eventually_always_create_msg_owner_refs_satisfies_for_sub_res
         lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()),
         later(lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id())),
         later(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers())),
-        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())),
+        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
         later(lift_state(rmq_guarantee(controller_id))),
         later(lift_state(rmq_rely_conditions(cluster, controller_id)))
     );
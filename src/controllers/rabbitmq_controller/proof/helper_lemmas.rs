@@ -336,6 +336,21 @@ ensures
     assert(has_rmq_prefix(key_name));
 }
 
+pub proof fn lemma_resource_key_is_not_pod(sub_resource: SubResource, rabbitmq: RabbitmqClusterView)
+ensures
+    get_request(sub_resource, rabbitmq).key.kind != Kind::PodKind,
+{
+    match sub_resource {
+        SubResource::HeadlessService | SubResource::Service => {},
+        SubResource::ErlangCookieSecret | SubResource::DefaultUserSecret => {},
+        SubResource::PluginsConfigMap | SubResource::ServerConfigMap => {},
+        SubResource::ServiceAccount => {},
+        SubResource::Role => {},
+        SubResource::RoleBinding => {},
+        SubResource::VStatefulSetView => {},
+    }
+}
+
 pub proof fn make_sts_pass_state_validation(rabbitmq: RabbitmqClusterView, cm_rv: StringView) -> (sts: VStatefulSetView)
 requires
     rabbitmq.state_validation(),
@@ -446,11 +461,12 @@ requires
     Cluster::cr_states_are_unmarshallable::<RabbitmqReconcileState, RabbitmqClusterView>(controller_id)(s),
     // Cluster::cr_objects_in_reconcile_satisfy_state_validation::<RabbitmqClusterView>(controller_id)(s),
     Cluster::no_pending_request_to_api_server_from_non_controllers()(s),
-    Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s),
+    Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s),
     Cluster::each_object_in_etcd_is_weakly_well_formed()(s),
     Cluster::every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(controller_id)(s),
     resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)(s),
     no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq)(s),
+    no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)(s),
     rmq_self_rely_guarantee(controller_id, rabbitmq.object_ref())(s),
     rmq_guarantee(controller_id)(s),
     rmq_rely_conditions(cluster, controller_id)(s),
@@ -644,14 +660,43 @@ ensures
                         assert(s.resources().contains_key(resource_key) ==> s_prime.resources().contains_key(resource_key));
                     }
                 },
+                APIRequest::PatchRequest(req) => {
+                    if id == controller_id {
+                        // rmq_guarantee returns false for PatchRequest from rabbitmq controller.
+                        assert(false);
+                    } else {
+                        if s.resources().contains_key(resource_key) {
+                            if req.key() == resource_key {
+                                // Same as UpdateRequest: rmq_rely_patch_req rules out a successful patch.
+                                assert(patch_request_admission_check(cluster.installed_types, req, s.api_server) is Some);
+                                assert(s_prime.resources() == s.resources());
+                            } else {
+                                assert(req.key() != resource_key);
+                                assert(s_prime.resources().contains_key(resource_key));
+                            }
+                        }
+                        assert(s.resources().contains_key(resource_key) ==> s_prime.resources().contains_key(resource_key));
+                    }
+                },
             }
         },
         HostId::BuiltinController => {
-            // all_requests_from_builtin_controllers_are_api_delete_requests forces msg to be a delete.
-            assert(msg.content.is_delete_request());
-            assert(!resource_delete_request_msg(resource_key)(msg));
-            let req = msg.content.get_delete_request();
-            assert(req.key != resource_key);
+            // all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests forces msg to be
+            // a delete, an update or an update status, and none of them targets resource_key.
+            if msg.content.is_delete_request() {
+                assert(!resource_delete_request_msg(resource_key)(msg));
+                let req = msg.content.get_delete_request();
+                assert(req.key != resource_key);
+            } else if msg.content.is_update_request() {
+                assert(!resource_update_request_msg(resource_key)(msg));
+                let req = msg.content.get_update_request();
+                assert(req.key() != resource_key);
+            } else {
+                assert(msg.content.is_update_status_request());
+                assert(!resource_update_status_request_msg(resource_key)(msg));
+                let req = msg.content.get_update_status_request();
+                assert(req.key() != resource_key);
+            }
             assert(s.resources().contains_key(resource_key) ==> s_prime.resources().contains_key(resource_key));
         },
         _ => {
@@ -601,15 +601,18 @@ proof fn always_tla_forall_apply_for_sub_resource(controller_id: int, spec: Temp
         spec.entails(assumption_and_invariants_of_all_phases(controller_id, cluster, rabbitmq)),
     ensures
         spec.entails(always(lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq)))),
+        spec.entails(always(lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)))),
         spec.entails(always(lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)))),
         spec.entails(always(lift_state(helper_invariants::resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)))),
 {
     let stable_spec = assumption_and_invariants_of_all_phases(controller_id, cluster, rabbitmq);
     entails_trans(spec, stable_spec, always(tla_forall(|res: SubResource| lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(res, rabbitmq)))));
+    entails_trans(spec, stable_spec, always(tla_forall(|res: SubResource| lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(res, rabbitmq)))));
     entails_trans(spec, stable_spec, always(tla_forall(|res: SubResource| lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(res, rabbitmq)))));
     entails_trans(spec, stable_spec, always(tla_forall(|res: SubResource| lift_state(helper_invariants::resource_object_only_has_owner_reference_pointing_to_current_cr(res, rabbitmq)))));
 
     always_tla_forall_apply(spec, |res: SubResource| lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(res, rabbitmq)), sub_resource);
+    always_tla_forall_apply(spec, |res: SubResource| lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(res, rabbitmq)), sub_resource);
     always_tla_forall_apply(spec, |res: SubResource| lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(res, rabbitmq)), sub_resource);
     always_tla_forall_apply(spec, |res: SubResource| lift_state(helper_invariants::resource_object_only_has_owner_reference_pointing_to_current_cr(res, rabbitmq)), sub_resource);
 }
@@ -625,6 +628,9 @@ ensures
 
     assert(stable_spec.entails(always(lift_state(Cluster::crash_disabled(controller_id)))));
     assert(stable_spec.entails(always(lift_state(Cluster::req_drop_disabled()))));
+    assert(stable_spec.entails(always(lift_state(Cluster::resp_drop_disabled()))));
+    assert(stable_spec.entails(always(lift_state(Cluster::req_dup_disabled()))));
+    assert(stable_spec.entails(always(lift_state(Cluster::node_failure_disabled()))));
     assert(stable_spec.entails(always(lift_state(Cluster::pod_monkey_disabled()))));
     entails_trans(stable_spec,
         derived_invariants_since_beginning(controller_id, cluster, rabbitmq),
@@ -667,12 +673,14 @@ ensures
     assert(stable_spec.entails(always(lift_state(Cluster::every_msg_from_key_is_pending_req_msg_of(controller_id, rabbitmq.object_ref())))));
     assert(stable_spec.entails(always(lift_state(Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, rabbitmq)))));
     always_tla_forall_apply(invariants_since_phase_v(rabbitmq), |sub_resource: SubResource| lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq)), sub_resource);
+    always_tla_forall_apply(invariants_since_phase_v(rabbitmq), |sub_resource: SubResource| lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)), sub_resource);
     entails_trans(stable_spec, invariants_since_phase_v(rabbitmq), always(lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq))));
+    entails_trans(stable_spec, invariants_since_phase_v(rabbitmq), always(lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq))));
     always_tla_forall_apply(invariants_since_phase_iv(rabbitmq), |sub_resource: SubResource| lift_state(helper_invariants::resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)), sub_resource);
     entails_trans(stable_spec, invariants_since_phase_iv(rabbitmq), always(lift_state(helper_invariants::resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq))));
     always_tla_forall_apply(derived_invariants_since_beginning(controller_id, cluster, rabbitmq), |sub_resource: SubResource| lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)), sub_resource);
     entails_trans(stable_spec, derived_invariants_since_beginning(controller_id, cluster, rabbitmq), always(lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq))));
-    entails_trans(stable_spec, derived_invariants_since_beginning(controller_id, cluster, rabbitmq), always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())));
+    entails_trans(stable_spec, derived_invariants_since_beginning(controller_id, cluster, rabbitmq), always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())));
     entails_trans(stable_spec, derived_invariants_since_beginning(controller_id, cluster, rabbitmq), always(lift_state(helper_invariants::sts_in_etcd_with_rmq_key_match_rmq_selector(rabbitmq))));
     entails_trans(stable_spec, derived_invariants_since_beginning(controller_id, cluster, rabbitmq), always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(controller_id))));
     entails_trans(stable_spec, derived_invariants_since_beginning(controller_id, cluster, rabbitmq), always(lift_state(helper_invariants::rmq_self_rely_guarantee(controller_id, rabbitmq.object_ref()))));
@@ -682,6 +690,9 @@ ensures
         lift_state(rmq_guarantee(controller_id)),
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::resp_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
         lift_state(Cluster::every_in_flight_msg_has_unique_id()),
         lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()),
@@ -707,8 +718,9 @@ ensures
         lift_state(Cluster::desired_state_is(rabbitmq)),
         lift_state(Cluster::every_msg_from_key_is_pending_req_msg_of(controller_id, rabbitmq.object_ref())),
         lift_state(Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, rabbitmq)),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
         lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq)),
+        lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)),
         lift_state(helper_invariants::resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)),
         lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)),
         lift_state(helper_invariants::sts_in_etcd_with_rmq_key_match_rmq_selector(rabbitmq)),
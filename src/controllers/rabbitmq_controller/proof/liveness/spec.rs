@@ -141,6 +141,9 @@ pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(provided_
         cluster.lemma_true_leads_to_crash_always_disabled(spec, controller_id);
         cluster.lemma_true_leads_to_pod_monkey_always_disabled(spec);
         cluster.lemma_true_leads_to_req_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_resp_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_req_dup_always_disabled(spec);
+        cluster.lemma_true_leads_to_node_failure_always_disabled(spec);
         cluster.lemma_true_leads_to_always_the_object_in_schedule_has_spec_and_uid_as(spec, controller_id, rabbitmq);
         leads_to_always_and_n!(
             spec,
@@ -148,6 +151,9 @@ pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(provided_
             lift_state(Cluster::crash_disabled(controller_id)),
             lift_state(Cluster::pod_monkey_disabled()),
             lift_state(Cluster::req_drop_disabled()),
+            lift_state(Cluster::resp_drop_disabled()),
+            lift_state(Cluster::req_dup_disabled()),
+            lift_state(Cluster::node_failure_disabled()),
             lift_state(Cluster::the_object_in_schedule_has_spec_and_uid_as(controller_id, rabbitmq))
         );
     } else {
@@ -200,6 +206,12 @@ pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(provided_
             helper_invariants::lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr_forall(controller_id, cluster, spec, rabbitmq);
         } else if i == 5 {
             helper_invariants::lemma_eventually_always_no_delete_resource_request_msg_from_gc_in_flight_forall(controller_id, cluster, spec, rabbitmq);
+            helper_invariants::lemma_eventually_always_no_update_resource_request_msg_from_builtin_controllers_in_flight_forall(controller_id, cluster, spec, rabbitmq);
+            leads_to_always_and_n!(
+                spec, true_pred(),
+                tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq))),
+                tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)))
+            );
         }
     }
 }
@@ -284,6 +296,9 @@ pub open spec fn next_with_wf(cluster: Cluster, controller_id: int) -> TempPred<
     .and(cluster.disable_crash().weak_fairness(controller_id))
     .and(cluster.disable_pod_monkey().weak_fairness(()))
     .and(cluster.disable_req_drop().weak_fairness(()))
+    .and(cluster.disable_resp_drop().weak_fairness(()))
+    .and(cluster.disable_req_dup().weak_fairness(()))
+    .and(cluster.disable_node_failure().weak_fairness(()))
 }
 
 pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
@@ -300,6 +315,9 @@ pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
         always_p_is_stable::<ClusterState>(split_always);
     }
     Cluster::action_weak_fairness_is_stable(cluster.disable_req_drop());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_resp_drop());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_req_dup());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_node_failure());
     Cluster::action_weak_fairness_is_stable(cluster.disable_pod_monkey());
     stable_and_n!(
         always(lift_action(cluster.next())),
@@ -310,7 +328,10 @@ pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
         tla_forall(|input| cluster.external_next().weak_fairness((controller_id, input))),
         cluster.disable_crash().weak_fairness(controller_id),
         cluster.disable_pod_monkey().weak_fairness(()),
-        cluster.disable_req_drop().weak_fairness(())
+        cluster.disable_req_drop().weak_fairness(()),
+        cluster.disable_resp_drop().weak_fairness(()),
+        cluster.disable_req_dup().weak_fairness(()),
+        cluster.disable_node_failure().weak_fairness(())
     );
 }
 
@@ -357,7 +378,7 @@ pub open spec fn derived_invariants_since_beginning(controller_id: int, cluster:
     .and(always(lift_state(Cluster::there_is_the_controller_state(controller_id))))
     .and(always(lift_state(Cluster::there_is_no_request_msg_to_external_from_controller(controller_id))))
     .and(always(lift_state(Cluster::cr_objects_in_reconcile_satisfy_state_validation::<RabbitmqClusterView>(controller_id))))
-    .and(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())))
+    .and(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())))
     .and(always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(controller_id))))
     .and(always(lift_state(helper_invariants::rmq_self_rely_guarantee(controller_id, rabbitmq.object_ref()))))
     // Additional invariants needed by cluster_invariants_since_reconciliation
@@ -421,7 +442,7 @@ pub proof fn derived_invariants_since_beginning_is_stable(controller_id: int, cl
         lift_state(Cluster::there_is_the_controller_state(controller_id)),
         lift_state(Cluster::there_is_no_request_msg_to_external_from_controller(controller_id)),
         lift_state(Cluster::cr_objects_in_reconcile_satisfy_state_validation::<RabbitmqClusterView>(controller_id)),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
         lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(controller_id)),
         lift_state(helper_invariants::rmq_self_rely_guarantee(controller_id, rabbitmq.object_ref())),
         lift_state(Cluster::etcd_objects_have_unique_uids()),
@@ -451,6 +472,9 @@ pub open spec fn invariants_since_phase_i(controller_id: int, rabbitmq: Rabbitmq
     always(lift_state(Cluster::crash_disabled(controller_id)))
     .and(always(lift_state(Cluster::pod_monkey_disabled())))
     .and(always(lift_state(Cluster::req_drop_disabled())))
+    .and(always(lift_state(Cluster::resp_drop_disabled())))
+    .and(always(lift_state(Cluster::req_dup_disabled())))
+    .and(always(lift_state(Cluster::node_failure_disabled())))
     .and(always(lift_state(Cluster::the_object_in_schedule_has_spec_and_uid_as(controller_id, rabbitmq))))
 }
 
@@ -461,6 +485,9 @@ pub proof fn invariants_since_phase_i_is_stable(controller_id: int, rabbitmq: Ra
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::pod_monkey_disabled()),
         lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::resp_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::the_object_in_schedule_has_spec_and_uid_as(controller_id, rabbitmq))
     );
 }
@@ -523,16 +550,21 @@ pub proof fn invariants_since_phase_iv_is_stable(rabbitmq: RabbitmqClusterView)
 }
 
 // Invariants since phase V rely on the invariants since phase IV. When the objects starts to always have owner reference
-// pointing to current cr, it will never be recycled by the garbage collector. Plus, the reconciler itself never tries to
+// pointing to current cr, it will never be recycled or updated by the garbage collector. Plus, the reconciler itself never tries to
 // delete this object, so we can have the invariants saying that no delete request messages will be in flight.
+// The scheduler and the kubelet only update pods, so no update request messages from the builtin controllers will be in flight either.
 pub open spec fn invariants_since_phase_v(rabbitmq: RabbitmqClusterView) -> TempPred<ClusterState> {
     always(tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq))))
+    .and(always(tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)))))
 }
 
 pub proof fn invariants_since_phase_v_is_stable(rabbitmq: RabbitmqClusterView)
     ensures valid(stable(invariants_since_phase_v(rabbitmq))),
 {
-    always_p_is_stable(tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq))));
+    stable_and_always_n!(
+        tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq))),
+        tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)))
+    );
 }
 
 #[verifier(spinoff_prover)]
@@ -583,7 +615,7 @@ pub proof fn sm_spec_entails_all_invariants(controller_id: int, cluster: Cluster
     cluster.lemma_always_there_is_the_controller_state(spec, controller_id);
     cluster.lemma_always_there_is_no_request_msg_to_external_from_controller(spec, controller_id);
     cluster.lemma_always_cr_objects_in_reconcile_satisfy_state_validation::<RabbitmqClusterView>(spec, controller_id);
-    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_requests(spec);
+    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests(spec);
     cluster.lemma_always_every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(spec, controller_id);
     helper_invariants::lemma_always_rmq_self_rely_guarantee(spec, cluster, controller_id, rabbitmq.object_ref());
     // Additional invariants needed by cluster_invariants_since_reconciliation
@@ -661,7 +693,7 @@ pub proof fn sm_spec_entails_all_invariants(controller_id: int, cluster: Cluster
         lift_state(Cluster::there_is_the_controller_state(controller_id)),
         lift_state(Cluster::there_is_no_request_msg_to_external_from_controller(controller_id)),
         lift_state(Cluster::cr_objects_in_reconcile_satisfy_state_validation::<RabbitmqClusterView>(controller_id)),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
         lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(controller_id)),
         lift_state(helper_invariants::rmq_self_rely_guarantee(controller_id, rabbitmq.object_ref())),
         lift_state(Cluster::etcd_objects_have_unique_uids()),
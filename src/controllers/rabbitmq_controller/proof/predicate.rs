@@ -630,6 +630,9 @@ pub open spec fn cluster_invariants_since_reconciliation(cluster: Cluster, contr
         &&& rmq_guarantee(controller_id)(s)
         &&& Cluster::crash_disabled(controller_id)(s)
         &&& Cluster::req_drop_disabled()(s)
+        &&& Cluster::resp_drop_disabled()(s)
+        &&& Cluster::req_dup_disabled()(s)
+        &&& Cluster::node_failure_disabled()(s)
         &&& Cluster::pod_monkey_disabled()(s)
         &&& Cluster::every_in_flight_msg_has_unique_id()(s)
         &&& Cluster::every_in_flight_msg_has_lower_id_than_allocator()(s)
@@ -655,10 +658,11 @@ pub open spec fn cluster_invariants_since_reconciliation(cluster: Cluster, contr
         &&& Cluster::desired_state_is(rabbitmq)(s)
         &&& Cluster::every_msg_from_key_is_pending_req_msg_of(controller_id, rabbitmq.object_ref())(s)
         &&& Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, rabbitmq)(s)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s)
         &&& Cluster::every_in_flight_msg_from_controller_has_kind_as::<RabbitmqClusterView>(controller_id)(s)
         &&& rmq_self_rely_guarantee(controller_id, rabbitmq.object_ref())(s)
         &&& no_delete_resource_request_msg_from_gc_in_flight(sub_resource, rabbitmq)(s)
+        &&& no_update_resource_request_msg_from_builtin_controllers_in_flight(sub_resource, rabbitmq)(s)
         &&& resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)(s)
         &&& resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, rabbitmq)(s)
         &&& sts_in_etcd_with_rmq_key_match_rmq_selector(rabbitmq)(s)
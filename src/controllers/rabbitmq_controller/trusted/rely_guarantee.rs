@@ -31,6 +31,7 @@ pub open spec fn rmq_rely(other_id: int) -> StatePred<ClusterState> {
                 APIRequest::GetThenDeleteRequest(req) => rmq_rely_get_then_delete_req(req),
                 APIRequest::UpdateStatusRequest(req) => rmq_rely_update_status_req(req)(s),
                 APIRequest::GetThenUpdateStatusRequest(req) => rmq_rely_get_then_update_status_req(req),
+                APIRequest::PatchRequest(req) => rmq_rely_patch_req(req)(s),
                 // Get/List requests do not interfere
                 _ => true,
             }
@@ -76,6 +77,19 @@ pub open spec fn rmq_rely_get_then_update_req(req: GetThenUpdateRequest) -> bool
             ==> req.owner_ref.kind != RabbitmqClusterView::kind() // then it should not touch objects owned by rabbitmq
 }
 
+pub open spec fn rmq_rely_patch_req(req: PatchRequest) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        let etcd_obj = s.resources()[req.key()];
+        &&& is_rmq_managed_kind(req.obj.kind) && has_rmq_prefix(req.name) && s.resources().contains_key(req.key())
+        ==> req.obj.metadata.resource_version is Some // disallow unconditional patch
+            && (etcd_obj.metadata.resource_version == req.obj.metadata.resource_version // if req could succeed
+                ==> !exists |rabbitmq: RabbitmqClusterView| #[trigger] etcd_obj.metadata.owner_references_contains(rabbitmq.controller_owner_ref())) // then it should not touch objects owned by rabbitmq
+        // a server-side apply without resource_version creates an absent object, so like create it must not use the rabbitmq prefix
+        &&& is_rmq_managed_kind(req.obj.kind) && has_rmq_prefix(req.name) && req.is_apply()
+        ==> req.obj.metadata.resource_version is Some
+    }
+}
+
 // only requires for CM kind to prevent its resource version from changing
 pub open spec fn rmq_rely_update_status_req(req: UpdateStatusRequest) -> StatePred<ClusterState> {
     |s: ClusterState| {
@@ -110,7 +124,7 @@ pub open spec fn rmq_rely_get_then_delete_req(req: GetThenDeleteRequest) -> bool
 }
 
 // RMQ only creates objects of rabbitmq-managed kind with rabbitmq prefix in the name,
-// owned by exactly one RabbitmqCluster.
+// owned by exactly one RabbitmqCluster. The VStatefulSet it creates has no finalizer.
 pub open spec fn rmq_guarantee_create_req(req: CreateRequest) -> bool {
     &&& is_rmq_managed_kind(req.obj.kind)
     &&& has_rmq_prefix(req.obj.metadata.name->0)
@@ -118,10 +132,11 @@ pub open spec fn rmq_guarantee_create_req(req: CreateRequest) -> bool {
     &&& req.obj.metadata.owner_references is Some
     &&& exists |rabbitmq: RabbitmqClusterView|
         req.obj.metadata.owner_references->0 == seq![#[trigger] rabbitmq.controller_owner_ref()]
+    &&& req.obj.kind == VStatefulSetView::kind() ==> req.obj.metadata.finalizers is None
 }
 
 // RMQ only updates objects of rabbitmq-managed kind with rabbitmq prefix in the name,
-// owned by exactly one RabbitmqCluster.
+// owned by exactly one RabbitmqCluster. The VStatefulSet it updates has no finalizer.
 pub open spec fn rmq_guarantee_get_then_update_req(req: GetThenUpdateRequest) -> bool {
     &&& is_rmq_managed_kind(req.obj.kind)
     &&& has_rmq_prefix(req.name)
@@ -129,6 +144,7 @@ pub open spec fn rmq_guarantee_get_then_update_req(req: GetThenUpdateRequest) ->
     &&& req.obj.metadata.owner_references is Some
     &&& exists |rabbitmq: RabbitmqClusterView|
         req.obj.metadata.owner_references->0 == seq![#[trigger] rabbitmq.controller_owner_ref()]
+    &&& req.obj.kind == VStatefulSetView::kind() ==> req.obj.metadata.finalizers is None
 }
 
 pub open spec fn rmq_guarantee(controller_id: int) -> StatePred<ClusterState> {
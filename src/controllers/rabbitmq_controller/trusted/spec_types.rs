@@ -77,6 +77,10 @@ implement_resource_view_trait!(RabbitmqClusterView, RabbitmqClusterSpecView, Rab
 impl CustomResourceView for RabbitmqClusterView {
     proof fn kind_is_custom_resource() {}
 
+    open spec fn scope() -> ResourceScope {
+        ResourceScope::Namespaced
+    }
+
     open spec fn spec_status_validation(obj_spec: Self::Spec, obj_status: Self::Status) -> bool {
         RabbitmqClusterView {
             metadata: arbitrary(),
@@ -6,6 +6,7 @@ use crate::vstatefulset_controller::trusted::spec_types::VStatefulSetView;
 
 verus! {
 
+#[derive(Debug)]
 pub enum RabbitmqReconcileStep {
     Init,
     AfterKRequestStep(ActionKind, SubResource),
@@ -22,6 +23,7 @@ impl std::clone::Clone for RabbitmqReconcileStep {
     { *self }
 }
 
+#[derive(Debug)]
 pub enum SubResource {
     HeadlessService,
     Service,
@@ -35,6 +37,7 @@ pub enum SubResource {
     VStatefulSetView,
 }
 
+#[derive(Debug)]
 pub enum ActionKind {
     Get,
     Create,
@@ -1 +1,2 @@
-pub mod reconciler;
\ No newline at end of file
+pub mod reconciler;
+pub mod shim_hooks;
@@ -20,6 +20,7 @@ verus! {
 pub struct VDeploymentReconcileState {
     pub reconcile_step: VDeploymentReconcileStep,
     pub new_vrs: Option<VReplicaSet>,
+    pub new_vrs_spec_hash: Option<String>,
     pub old_vrs_list: Vec<VReplicaSet>,
     pub old_vrs_index: usize,
 }
@@ -31,6 +32,7 @@ impl View for VDeploymentReconcileState {
         model_reconciler::VDeploymentReconcileState {
             reconcile_step: self.reconcile_step@,
             new_vrs: self.new_vrs.deep_view(),
+            new_vrs_spec_hash: self.new_vrs_spec_hash.deep_view(),
             old_vrs_list: self.old_vrs_list.deep_view(),
             old_vrs_index: self.old_vrs_index@ as nat,
         }
@@ -61,6 +63,14 @@ impl Reconciler for VDeploymentReconciler {
     fn reconcile_error(state: &Self::S) -> bool {
         reconcile_error(state)
     }
+
+    fn finalizer() -> Option<String> {
+        Some("anvil.dev/vdeployment-finalizer".to_string())
+    }
+
+    fn reconcile_delete(vd: &Self::K, resp_o: Option<Response<Self::EResp>>, state: Self::S) -> (Self::S, Option<Request<Self::EReq>>) {
+        reconcile_delete(vd, resp_o, state)
+    }
 }
 
 pub fn reconcile_init_state() -> (state: VDeploymentReconcileState)
@@ -71,6 +81,7 @@ pub fn reconcile_init_state() -> (state: VDeploymentReconcileState)
     VDeploymentReconcileState {
         reconcile_step: VDeploymentReconcileStep::Init,
         new_vrs: None,
+        new_vrs_spec_hash: None,
         old_vrs_list: old_vrs_list,
         old_vrs_index: 0,
     }
@@ -114,12 +125,18 @@ pub fn reconcile_core(vd: &VDeployment, resp_o: Option<Response<VoidEResp>>, sta
             let req = KubeAPIRequest::ListRequest(KubeListRequest {
                 api_resource: VReplicaSet::api_resource(),
                 namespace: namespace,
+                label_selector: None,
+                field_selector: None,
+                read_mode: KubeReadMode::Quorum,
+                limit: None,
+                continue_token: None,
             });
             let old_vrs_list = Vec::<VReplicaSet>::new();
             assert(old_vrs_list.deep_view() == Seq::<VReplicaSetView>::empty());
             let state_prime = VDeploymentReconcileState {
                 reconcile_step: VDeploymentReconcileStep::AfterListVRS,
                 new_vrs: None,
+                new_vrs_spec_hash: None,
                 old_vrs_list: old_vrs_list,
                 old_vrs_index: 0,
             };
@@ -131,15 +148,21 @@ pub fn reconcile_core(vd: &VDeployment, resp_o: Option<Response<VoidEResp>>, sta
             }
             let objs = extract_some_k_list_resp!(resp_o).unwrap();
             assert(objs.deep_view() == extract_some_k_list_resp_view(resp_o.deep_view()).unwrap());
-            let vrs_list_or_none = objects_to_vrs_list(objs);
+            let vrs_list_or_none = objects_to_vrs_list(objs.clone());
             if vrs_list_or_none.is_none() {
                 return (error_state(state), None);
             }
             let (new_vrs, old_vrs_list) = filter_old_and_new_vrs(vd, filter_vrs_list(vd, vrs_list_or_none.clone().unwrap()));
+            let new_vrs_spec_hash = if new_vrs.is_some() {
+                listed_spec_hash(&objs, new_vrs.as_ref().unwrap())
+            } else {
+                None
+            };
             // no .last().cloned() in verus because "The verifier does not yet support the following Rust feature: overloaded deref"
             let state_prime = VDeploymentReconcileState{
                 reconcile_step: VDeploymentReconcileStep::AfterEnsureNewVRS,
                 new_vrs: new_vrs.clone(),
+                new_vrs_spec_hash: new_vrs_spec_hash,
                 old_vrs_index: old_vrs_list.len(),
                 old_vrs_list: old_vrs_list
             };
@@ -158,6 +181,7 @@ pub fn reconcile_core(vd: &VDeployment, resp_o: Option<Response<VoidEResp>>, sta
                 return (error_state(state), None);
             }
             let new_obj = extract_some_k_create_resp!(resp_o).unwrap();
+            let new_vrs_spec_hash = new_obj.spec_hash();
             let new_vrs_or_err = VReplicaSet::unmarshal(new_obj);
             if new_vrs_or_err.is_err() {
                 return (error_state(state), None);
@@ -165,6 +189,7 @@ pub fn reconcile_core(vd: &VDeployment, resp_o: Option<Response<VoidEResp>>, sta
             let state_prime = VDeploymentReconcileState {
                 reconcile_step: VDeploymentReconcileStep::AfterEnsureNewVRS,
                 new_vrs: Some(new_vrs_or_err.unwrap()),
+                new_vrs_spec_hash: Some(new_vrs_spec_hash),
                 ..state
             };
             return (state_prime, None);
@@ -255,6 +280,7 @@ ensures
     let state_prime = VDeploymentReconcileState {
         reconcile_step: VDeploymentReconcileStep::AfterCreateNewVRS,
         new_vrs: None,
+        new_vrs_spec_hash: state.new_vrs_spec_hash.clone(),
         old_vrs_list: state.old_vrs_list.clone(),
         old_vrs_index: state.old_vrs_index
     };
@@ -272,6 +298,13 @@ ensures
     (res.0@, res.1.deep_view()) == model_reconciler::scale_new_vrs(state@, vd@),
 {
     let mut new_vrs = state.new_vrs.clone().unwrap();
+    // only scale the new vrs if its spec (in particular, its template) has not changed since it was listed;
+    // the hash is taken on the listed object since marshalling new_vrs back is not guaranteed to give the same spec
+    let vrs_spec_hash = if state.new_vrs_spec_hash.is_some() {
+        state.new_vrs_spec_hash.clone().unwrap()
+    } else {
+        new_vrs.clone().marshal().spec_hash()
+    };
     let mut replicas = new_vrs.spec().replicas().unwrap_or(1);
     if vd.spec().replicas().unwrap_or(1) >  replicas {
         replicas = replicas + 1
@@ -286,11 +319,14 @@ ensures
         namespace: vd.metadata().namespace().unwrap(),
         name: new_vrs.metadata().name().unwrap(),
         owner_ref: vd.controller_owner_ref(),
+        predicate: KubeGetThenPredicate::SpecHashMatch(vrs_spec_hash),
         obj: new_vrs.clone().marshal()
     });
     let state_prime = VDeploymentReconcileState {
         reconcile_step: VDeploymentReconcileStep::AfterScaleNewVRS,
         new_vrs: Some(new_vrs),
+        // the updated spec is no longer the listed one
+        new_vrs_spec_hash: None,
         old_vrs_list: state.old_vrs_list.clone(),
         old_vrs_index: state.old_vrs_index
     };
@@ -316,13 +352,15 @@ ensures
         namespace: vd.metadata().namespace().unwrap(),
         name: old_vrs.metadata().name().unwrap(),
         owner_ref: vd.controller_owner_ref(),
+        predicate: KubeGetThenPredicate::OwnerRef,
         obj: old_vrs.clone().marshal()
     });
     let state_prime = VDeploymentReconcileState {
         reconcile_step: VDeploymentReconcileStep::AfterScaleDownOldVRS,
         old_vrs_index: old_vrs_index,
         old_vrs_list: state.old_vrs_list.clone(),
-        new_vrs: state.new_vrs.clone()
+        new_vrs: state.new_vrs.clone(),
+        new_vrs_spec_hash: state.new_vrs_spec_hash.clone(),
     };
     return (state_prime, Some(Request::KRequest(req)))
 }
@@ -337,6 +375,49 @@ ensures res == model_reconciler::mismatch_replicas(vd@, vrs@),
     && vrs.spec().replicas().unwrap_or(1) != vd.spec().replicas().unwrap_or(1)
 }
 
+pub fn reconcile_delete(vd: &VDeployment, resp_o: Option<Response<VoidEResp>>, state: VDeploymentReconcileState) -> (res: (VDeploymentReconcileState, Option<Request<VoidEReq>>))
+    requires vd@.well_formed(),
+    ensures (res.0@, res.1.deep_view()) == model_reconciler::reconcile_delete(vd@, resp_o.deep_view(), state@),
+{
+    match state.reconcile_step {
+        VDeploymentReconcileStep::AfterListVRS => {
+            if !(is_some_k_list_resp!(resp_o) && extract_some_k_list_resp_as_ref!(resp_o).is_ok()) {
+                return (error_state(state), None);
+            }
+            let objs = extract_some_k_list_resp!(resp_o).unwrap();
+            assert(objs.deep_view() == extract_some_k_list_resp_view(resp_o.deep_view()).unwrap());
+            let vrs_list_or_none = objects_to_vrs_list(objs);
+            if vrs_list_or_none.is_none() {
+                return (error_state(state), None);
+            }
+            let vrs_list_to_scale_down = filter_nonempty_vrs(filter_vrs_list(vd, vrs_list_or_none.unwrap()));
+            let state_prime = VDeploymentReconcileState {
+                reconcile_step: VDeploymentReconcileStep::AfterEnsureNewVRS,
+                new_vrs: None,
+                new_vrs_spec_hash: None,
+                old_vrs_index: vrs_list_to_scale_down.len(),
+                old_vrs_list: vrs_list_to_scale_down
+            };
+            return (state_prime, None);
+        },
+        VDeploymentReconcileStep::AfterScaleDownOldVRS => {
+            if state.old_vrs_index == 0 {
+                return (done_state(state), None)
+            }
+            if state.old_vrs_index > state.old_vrs_list.len() {
+                return (error_state(state), None);
+            }
+            if !valid_owned_vrs(&state.old_vrs_list[state.old_vrs_index - 1], vd) {
+                return (error_state(state), None);
+            }
+            return scale_down_old_vrs(&state, &vd);
+        },
+        _ => {
+            return reconcile_core(vd, resp_o, state);
+        }
+    }
+}
+
 fn objects_to_vrs_list(objs: Vec<DynamicObject>) -> (vrs_list_or_none: Option<Vec<VReplicaSet>>)
 ensures
     vrs_list_or_none.deep_view() == model_reconciler::objects_to_vrs_list(objs.deep_view()),
@@ -475,6 +556,78 @@ ensures
     filtered_vrs_list
 }
 
+fn filter_nonempty_vrs(vrs_list: Vec<VReplicaSet>) -> (filtered_vrs_list: Vec<VReplicaSet>)
+ensures
+    filtered_vrs_list.deep_view() == model_reconciler::filter_nonempty_vrs(vrs_list.deep_view()),
+{
+    let mut filtered_vrs_list: Vec<VReplicaSet> = Vec::new();
+    let mut idx = 0;
+    let nonempty_vrs_filter = |vrs: VReplicaSetView| vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0;
+    assert(filtered_vrs_list.deep_view() == vrs_list.deep_view().take(0).filter(nonempty_vrs_filter));
+    for idx in 0..vrs_list.len()
+    invariant
+        idx <= vrs_list.len(),
+        nonempty_vrs_filter == (|vrs: VReplicaSetView| vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0),
+        filtered_vrs_list.deep_view() == vrs_list.deep_view().take(idx as int).filter(nonempty_vrs_filter),
+    {
+        let vrs = &vrs_list[idx];
+        if vrs.spec().replicas().is_none() || vrs.spec().replicas().unwrap() > 0 {
+            filtered_vrs_list.push(vrs.clone());
+        }
+        proof {
+            let pre_filtered_vrs_list = if nonempty_vrs_filter(vrs@) {
+                filtered_vrs_list.deep_view().drop_last()
+            } else {
+                filtered_vrs_list.deep_view()
+            };
+            assert(pre_filtered_vrs_list == vrs_list.deep_view().take(idx as int).filter(nonempty_vrs_filter));
+            lemma_filter_push(vrs_list.deep_view().take(idx as int), nonempty_vrs_filter, vrs@);
+            assert(vrs_list.deep_view().take(idx as int).push(vrs@) == vrs_list.deep_view().take(idx + 1 as int));
+            assert(nonempty_vrs_filter(vrs@) ==> filtered_vrs_list.deep_view() == pre_filtered_vrs_list.push(vrs@));
+        }
+    }
+    assert(vrs_list.deep_view().take(vrs_list.len() as int) == vrs_list.deep_view());
+    filtered_vrs_list
+}
+
+fn listed_spec_hash(objs: &Vec<DynamicObject>, vrs: &VReplicaSet) -> (hash: Option<String>)
+ensures
+    hash.deep_view() == model_reconciler::listed_spec_hash(objs.deep_view(), vrs@),
+{
+    let mut listed = Vec::<DynamicObject>::new();
+    let mut idx = 0;
+    let filter = |o: DynamicObjectView| o.metadata.uid == vrs@.metadata.uid;
+    assert(listed.deep_view() == objs.deep_view().take(0).filter(filter));
+    for idx in 0..objs.len()
+    invariant
+        idx <= objs.len(),
+        filter == (|o: DynamicObjectView| o.metadata.uid == vrs@.metadata.uid),
+        listed.deep_view() == objs.deep_view().take(idx as int).filter(filter),
+    {
+        let obj = &objs[idx];
+        if obj.metadata().uid_eq(&vrs.metadata()) {
+            listed.push(obj.clone());
+        }
+        proof {
+            let pre_listed = if filter(obj@) {
+                listed.deep_view().drop_last()
+            } else {
+                listed.deep_view()
+            };
+            assert(pre_listed == objs.deep_view().take(idx as int).filter(filter));
+            lemma_filter_push(objs.deep_view().take(idx as int), filter, obj@);
+            assert(objs.deep_view().take(idx as int).push(obj@) == objs.deep_view().take(idx + 1 as int));
+            assert(filter(obj@) ==> listed.deep_view() == pre_listed.push(obj@));
+        }
+    }
+    assert(objs.deep_view().take(objs.len() as int) == objs.deep_view());
+    if listed.len() > 0 {
+        Some(listed[0].spec_hash())
+    } else {
+        None
+    }
+}
+
 fn filter_old_and_new_vrs(vd: &VDeployment, vrs_list: Vec<VReplicaSet>) -> (res: (Option<VReplicaSet>, Vec<VReplicaSet>))
 requires
     vd@.well_formed(),
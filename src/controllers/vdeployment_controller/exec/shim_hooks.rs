@@ -0,0 +1,26 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::events::{successful_event, NotableEvent, ReconcileEvents};
+use crate::shim_layer::trace::ReconcileStepName;
+use crate::vdeployment_controller::exec::reconciler::{VDeploymentReconcileState, VDeploymentReconciler};
+
+// The hooks that the shim layer calls when running the VDeployment controller.
+// They are not verified and stay out of the exec reconciler, so that the verified code does not depend on the shim layer.
+
+// The name of a reconcile step is the Debug form of the step.
+impl ReconcileStepName for VDeploymentReconcileState {
+    fn reconcile_step_name(&self) -> String {
+        format!("{:?}", self.reconcile_step)
+    }
+}
+
+// The VReplicaSets created and scaled by the controller are recorded as events of the VDeployment.
+impl ReconcileEvents for VDeploymentReconciler {
+    fn notable_event(verb: &str, kind: &str, name: &str) -> Option<NotableEvent> {
+        match (verb, kind) {
+            ("Create", "VReplicaSet") => successful_event("Create", "Created", kind, name),
+            ("GetThenUpdate", "VReplicaSet") => successful_event("Scale", "Scaled", kind, name),
+            _ => None,
+        }
+    }
+}
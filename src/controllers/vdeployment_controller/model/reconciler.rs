@@ -18,6 +18,8 @@ pub struct VDeploymentReconciler {}
 pub struct VDeploymentReconcileState {
     pub reconcile_step: VDeploymentReconcileStepView,
     pub new_vrs: Option<VReplicaSetView>,
+    // the spec_hash of the object new_vrs is read from, used to guard the scaling of new_vrs
+    pub new_vrs_spec_hash: Option<StringView>,
     // the list is written once at AfterListVRS step and read only after that
     pub old_vrs_list: Seq<VReplicaSetView>,
     pub old_vrs_index: nat,
@@ -39,12 +41,25 @@ impl Reconciler<VDeploymentReconcileState, VDeploymentView, VoidEReqView, VoidER
     open spec fn reconcile_error(state: VDeploymentReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    open spec fn finalizer() -> Option<StringView> {
+        Some(vd_finalizer())
+    }
+
+    open spec fn reconcile_delete(vd: VDeploymentView, resp_o: Option<ResponseView<VoidERespView>>, state: VDeploymentReconcileState) -> (VDeploymentReconcileState, Option<RequestView<VoidEReqView>>) {
+        reconcile_delete(vd, resp_o, state)
+    }
+}
+
+pub open spec fn vd_finalizer() -> StringView {
+    "anvil.dev/vdeployment-finalizer"@
 }
 
 pub open spec fn reconcile_init_state() -> VDeploymentReconcileState {
     VDeploymentReconcileState {
         reconcile_step: VDeploymentReconcileStepView::Init,
         new_vrs: None,
+        new_vrs_spec_hash: None,
         old_vrs_list: Seq::empty(),
         old_vrs_index: 0,
     }
@@ -71,10 +86,16 @@ pub open spec fn reconcile_core(vd: VDeploymentView, resp_o: Option<ResponseView
             let req = APIRequest::ListRequest(ListRequest {
                 kind: VReplicaSetView::kind(),
                 namespace: namespace,
+                label_selector: None,
+                field_selector: None,
+                read_mode: ReadMode::Quorum,
+                limit: None,
+                continue_token: None,
             });
             let state_prime = VDeploymentReconcileState {
                 reconcile_step: VDeploymentReconcileStepView::AfterListVRS,
                 new_vrs: None,
+                new_vrs_spec_hash: None,
                 old_vrs_list: Seq::<VReplicaSetView>::empty(),
                 old_vrs_index: 0,
             };
@@ -93,6 +114,7 @@ pub open spec fn reconcile_core(vd: VDeploymentView, resp_o: Option<ResponseView
                     let state_prime = VDeploymentReconcileState {
                         reconcile_step: VDeploymentReconcileStepView::AfterEnsureNewVRS,
                         new_vrs: new_vrs,
+                        new_vrs_spec_hash: if new_vrs is Some { listed_spec_hash(objs, new_vrs->0) } else { None },
                         old_vrs_list: old_vrs_list,
                         old_vrs_index: old_vrs_list.len()
                     };
@@ -122,6 +144,7 @@ pub open spec fn reconcile_core(vd: VDeploymentView, resp_o: Option<ResponseView
                     let state_prime = VDeploymentReconcileState {
                         reconcile_step: VDeploymentReconcileStepView::AfterEnsureNewVRS,
                         new_vrs: Some(new_vrs_or_err->Ok_0),
+                        new_vrs_spec_hash: Some(spec_hash(new_obj.spec)),
                         ..state
                     };
                     (state_prime, None)
@@ -171,6 +194,52 @@ pub open spec fn reconcile_core(vd: VDeploymentView, resp_o: Option<ResponseView
     }
 }
 
+// Before the finalizer is removed, every owned VRS is scaled down to 0 so that the pods are
+// gone before the VDeployment and its VRSs are collected. The delete path lists the VRSs like
+// reconcile_core, skips the new VRS and reuses the scale-down loop for all nonempty ones.
+// Unlike reconcile_core, a failed scale-down does not abort the loop: the VRS is removed by the
+// garbage collector together with the VDeployment anyway, and an error here would keep the
+// finalizer forever if the VRS keeps changing under the GetThenUpdate.
+pub open spec fn reconcile_delete(vd: VDeploymentView, resp_o: Option<ResponseView<VoidERespView>>, state: VDeploymentReconcileState) -> (res: (VDeploymentReconcileState, Option<RequestView<VoidEReqView>>)) {
+    match &state.reconcile_step {
+        VDeploymentReconcileStepView::AfterListVRS => {
+            if !(is_some_k_list_resp_view(resp_o) && extract_some_k_list_resp_view(resp_o) is Ok) {
+                (error_state(state), None)
+            } else {
+                let objs = extract_some_k_list_resp_view(resp_o)->Ok_0;
+                let vrs_list_or_none = objects_to_vrs_list(objs);
+                if vrs_list_or_none is None {
+                    (error_state(state), None)
+                } else {
+                    let vrs_list_to_scale_down = filter_nonempty_vrs(vrs_list_or_none->0.filter(|vrs: VReplicaSetView| esr_theorem::valid_owned_vrs(vrs, vd)));
+                    let state_prime = VDeploymentReconcileState {
+                        reconcile_step: VDeploymentReconcileStepView::AfterEnsureNewVRS,
+                        new_vrs: None,
+                        new_vrs_spec_hash: None,
+                        old_vrs_list: vrs_list_to_scale_down,
+                        old_vrs_index: vrs_list_to_scale_down.len()
+                    };
+                    (state_prime, None)
+                }
+            }
+        },
+        VDeploymentReconcileStepView::AfterScaleDownOldVRS => {
+            if state.old_vrs_index == 0 {
+                (done_state(state), None)
+            }
+            else if state.old_vrs_index > state.old_vrs_list.len() {
+                (error_state(state), None)
+            }
+            else if !esr_theorem::valid_owned_vrs(state.old_vrs_list[state.old_vrs_index - 1], vd) {
+                (error_state(state), None)
+            } else {
+                scale_down_old_vrs(state, vd)
+            }
+        },
+        _ => reconcile_core(vd, resp_o, state),
+    }
+}
+
 pub open spec fn new_vrs_ensured_state(state: VDeploymentReconcileState) -> (state_prime: VDeploymentReconcileState) {
     VDeploymentReconcileState {
         reconcile_step: VDeploymentReconcileStepView::AfterEnsureNewVRS,
@@ -256,6 +325,13 @@ pub open spec fn create_new_vrs(state: VDeploymentReconcileState, vd: VDeploymen
 //  scale new vrs to desired replicas
 pub open spec fn scale_new_vrs(state: VDeploymentReconcileState, vd: VDeploymentView) -> (res: (VDeploymentReconcileState, Option<RequestView<VoidEReqView>>)) {
     let new_vrs = state.new_vrs->0;
+    // only scale the new vrs if its spec (in particular, its template) has not changed since it was listed;
+    // the hash is taken on the listed object since marshalling new_vrs back is not guaranteed to give the same spec
+    let vrs_spec_hash = if state.new_vrs_spec_hash is Some {
+        state.new_vrs_spec_hash->0
+    } else {
+        spec_hash(new_vrs.marshal().spec)
+    };
     let updated_replicas = if vd.spec.replicas.unwrap_or(1) > new_vrs.spec.replicas.unwrap_or(1) {
         new_vrs.spec.replicas.unwrap_or(1) + 1
     } else {
@@ -272,11 +348,14 @@ pub open spec fn scale_new_vrs(state: VDeploymentReconcileState, vd: VDeployment
         name: new_vrs.metadata.name->0,
         namespace: vd.metadata.namespace->0,
         owner_ref: vd.controller_owner_ref(),
+        predicate: GetThenPredicate::SpecHashMatch(vrs_spec_hash),
         obj: new_vrs.marshal(),
     });
     let state_prime = VDeploymentReconcileState {
         reconcile_step: VDeploymentReconcileStepView::AfterScaleNewVRS,
         new_vrs: Some(new_vrs),
+        // the updated spec is no longer the listed one
+        new_vrs_spec_hash: None,
         ..state
     };
     (state_prime, Some(RequestView::KRequest(req)))
@@ -291,6 +370,7 @@ pub open spec fn scale_down_old_vrs(state: VDeploymentReconcileState, vd: VDeplo
         name: old_vrs.metadata.name->0,
         namespace: vd.metadata.namespace->0,
         owner_ref: vd.controller_owner_ref(),
+        predicate: GetThenPredicate::OwnerRef,
         obj: VReplicaSetView {
             spec: VReplicaSetSpecView {
                 replicas: Some(0 as int),
@@ -304,7 +384,8 @@ pub open spec fn scale_down_old_vrs(state: VDeploymentReconcileState, vd: VDeplo
         reconcile_step: VDeploymentReconcileStepView::AfterScaleDownOldVRS,
         old_vrs_index: old_vrs_index,
         old_vrs_list: state.old_vrs_list,
-        new_vrs: state.new_vrs
+        new_vrs: state.new_vrs,
+        new_vrs_spec_hash: state.new_vrs_spec_hash,
     };
     (state_prime, Some(RequestView::KRequest(req)))
 }
@@ -319,6 +400,16 @@ pub open spec fn objects_to_vrs_list(objs: Seq<DynamicObjectView>) -> (vrs_list:
     }
 }
 
+// listed_spec_hash is the spec_hash of the first listed object with the same uid as vrs
+pub open spec fn listed_spec_hash(objs: Seq<DynamicObjectView>, vrs: VReplicaSetView) -> Option<StringView> {
+    let listed = objs.filter(|o: DynamicObjectView| o.metadata.uid == vrs.metadata.uid);
+    if listed.len() > 0 {
+        Some(spec_hash(listed.first().spec))
+    } else {
+        None
+    }
+}
+
 pub open spec fn filter_old_and_new_vrs(vd: VDeploymentView, vrs_list: Seq<VReplicaSetView>) -> (res: (Option<VReplicaSetView>, Seq<VReplicaSetView>))
 {
     // first vrs that match template and has non-zero replicas
@@ -342,6 +433,10 @@ pub open spec fn filter_old_and_new_vrs(vd: VDeploymentView, vrs_list: Seq<VRepl
     (reusable_vrs, old_vrs_list)
 }
 
+pub open spec fn filter_nonempty_vrs(vrs_list: Seq<VReplicaSetView>) -> Seq<VReplicaSetView> {
+    vrs_list.filter(|vrs: VReplicaSetView| vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0)
+}
+
 // Strip resource_version AND status for vrs_set identity stability.
 // When VD controller changes replicas via GetThenUpdate, or VRS controller changes status,
 // the mapped set remains the same.
@@ -36,6 +36,8 @@ pub proof fn guarantee_condition_holds(spec: TempPred<ClusterState>, cluster: Cl
     helper_invariants::lemma_always_vrs_objects_in_local_reconcile_state_are_controllerly_owned_by_vd(spec, cluster, controller_id);
     cluster.lemma_always_each_object_in_etcd_has_at_most_one_controller_owner(spec);
     cluster.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
+    cluster.lemma_always_each_scheduled_object_has_consistent_key_and_valid_metadata(spec, controller_id);
+    cluster.lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec, controller_id);
     
     let stronger_next = |s, s_prime| {
         &&& cluster.next()(s, s_prime)
@@ -45,6 +47,8 @@ pub proof fn guarantee_condition_holds(spec: TempPred<ClusterState>, cluster: Cl
         &&& Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)(s)
         &&& Cluster::each_object_in_etcd_has_at_most_one_controller_owner()(s)
         &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
+        &&& Cluster::each_scheduled_object_has_consistent_key_and_valid_metadata(controller_id)(s)
+        &&& Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id)(s)
     };
 
     always_to_always_later(spec, lift_state(helper_invariants::vrs_objects_in_local_reconcile_state_are_controllerly_owned_by_vd(controller_id)));
@@ -56,7 +60,9 @@ pub proof fn guarantee_condition_holds(spec: TempPred<ClusterState>, cluster: Cl
         later(lift_state(helper_invariants::vrs_objects_in_local_reconcile_state_are_controllerly_owned_by_vd(controller_id))),
         lift_state(Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)),
         lift_state(Cluster::each_object_in_etcd_has_at_most_one_controller_owner()),
-        lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed())
+        lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
+        lift_state(Cluster::each_scheduled_object_has_consistent_key_and_valid_metadata(controller_id)),
+        lift_state(Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id))
     );
 
     assert forall |s, s_prime| invariant(s) && #[trigger] stronger_next(s, s_prime) implies invariant(s_prime) by {
@@ -78,6 +84,7 @@ pub proof fn guarantee_condition_holds(spec: TempPred<ClusterState>, cluster: Cl
                 } implies match msg.content->APIRequest_0 {
                     APIRequest::ListRequest(_) => true,
                     APIRequest::CreateRequest(req) => vd_guarantee_create_req(req)(s_prime),
+                    APIRequest::UpdateRequest(req) => vd_guarantee_update_req(req)(s_prime),
                     APIRequest::GetThenUpdateRequest(req) => vd_guarantee_get_then_update_req(req)(s_prime),
                     _ => false, 
                 } by {
@@ -95,6 +102,7 @@ pub proof fn guarantee_condition_holds(spec: TempPred<ClusterState>, cluster: Cl
                 } implies match msg.content->APIRequest_0 {
                     APIRequest::ListRequest(_) => true,
                     APIRequest::CreateRequest(req) => vd_guarantee_create_req(req)(s_prime),
+                    APIRequest::UpdateRequest(req) => vd_guarantee_update_req(req)(s_prime),
                     APIRequest::GetThenUpdateRequest(req) => vd_guarantee_get_then_update_req(req)(s_prime),
                     _ => false, 
                 } by {
@@ -102,6 +110,17 @@ pub proof fn guarantee_condition_holds(spec: TempPred<ClusterState>, cluster: Cl
 
                     if id == controller_id {
                         let new_msgs = s_prime.in_flight().sub(s.in_flight());
+                        // The only update requests are the finalizer updates sent by run_scheduled_reconcile
+                        // and end_reconcile, whose object is the scheduled or triggering VDeployment of cr_key.
+                        if new_msgs.contains(msg) && msg.content.is_update_request() {
+                            assert(cr_key.kind == VDeploymentView::kind());
+                            if s.scheduled_reconciles(controller_id).contains_key(cr_key) {
+                                assert(s.scheduled_reconciles(controller_id)[cr_key].object_ref() == cr_key);
+                            }
+                            if s.ongoing_reconciles(controller_id).contains_key(cr_key) {
+                                assert(s.ongoing_reconciles(controller_id)[cr_key].triggering_cr.object_ref() == cr_key);
+                            }
+                        }
                         if new_msgs.contains(msg) && msg.content.is_get_then_update_request() {
                             let req = msg.content.get_get_then_update_request();
                             let state = VDeploymentReconcileState::unmarshal(s.ongoing_reconciles(controller_id)[cr_key].local_state).unwrap();
@@ -149,6 +168,7 @@ pub proof fn guarantee_condition_holds(spec: TempPred<ClusterState>, cluster: Cl
                 } implies match msg.content->APIRequest_0 {
                     APIRequest::ListRequest(_) => true,
                     APIRequest::CreateRequest(req) => vd_guarantee_create_req(req)(s_prime),
+                    APIRequest::UpdateRequest(req) => vd_guarantee_update_req(req)(s_prime),
                     APIRequest::GetThenUpdateRequest(req) => vd_guarantee_get_then_update_req(req)(s_prime),
                     _ => false, 
                 } by {
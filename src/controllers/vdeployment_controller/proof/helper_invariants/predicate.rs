@@ -235,6 +235,16 @@ pub open spec fn vd_reconcile_get_then_update_request_only_interferes_with_itsel
     }
 }
 
+// The only update requests vd sends are the finalizer updates of vd itself.
+pub open spec fn vd_reconcile_update_request_only_interferes_with_itself(
+    req: UpdateRequest,
+    vd: VDeploymentView
+) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        req.key() == vd.object_ref()
+    }
+}
+
 pub open spec fn vd_reconcile_request_only_interferes_with_itself(
     controller_id: int,
     vd: VDeploymentView
@@ -247,6 +257,7 @@ pub open spec fn vd_reconcile_request_only_interferes_with_itself(
         } ==> match msg.content->APIRequest_0 {
             APIRequest::ListRequest(_) => true,
             APIRequest::CreateRequest(req) => vd_reconcile_create_request_only_interferes_with_itself(req, vd)(s),
+            APIRequest::UpdateRequest(req) => vd_reconcile_update_request_only_interferes_with_itself(req, vd)(s),
             APIRequest::GetThenUpdateRequest(req) => vd_reconcile_get_then_update_request_only_interferes_with_itself(req, vd)(s),
             _ => false, // vd doesn't send other requests (yet).
         }
@@ -290,16 +301,17 @@ pub open spec fn garbage_collector_does_not_delete_vd_vrs_objects(vd: VDeploymen
             &&& msg.content is APIRequest
         } ==> {
             let req = msg.content.get_delete_request(); 
-            &&& msg.content.is_delete_request()
-            &&& req.preconditions is Some
-            &&& req.preconditions.unwrap().uid is Some
-            &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
-            &&& s.resources().contains_key(req.key) ==> {
-                let obj = s.resources()[req.key];
-                ||| !(obj.metadata.owner_references_contains(vd.controller_owner_ref())
-                        && obj.kind == VReplicaSetView::kind()
-                        && obj.metadata.namespace == vd.metadata.namespace)
-                ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+            msg.content.is_delete_request() ==> {
+                &&& req.preconditions is Some
+                &&& req.preconditions.unwrap().uid is Some
+                &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
+                &&& s.resources().contains_key(req.key) ==> {
+                    let obj = s.resources()[req.key];
+                    ||| !(obj.metadata.owner_references_contains(vd.controller_owner_ref())
+                            && obj.kind == VReplicaSetView::kind()
+                            && obj.metadata.namespace == vd.metadata.namespace)
+                    ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+                }
             }
         }
     }
@@ -342,6 +354,11 @@ pub open spec fn vrs_objects_in_local_reconcile_state_are_controllerly_owned_by_
         &&& req_msg.content.get_list_request() == ListRequest {
             kind: VReplicaSetView::kind(),
             namespace: triggering_cr.metadata.namespace.unwrap(),
+            label_selector: None,
+            field_selector: None,
+            read_mode: ReadMode::Quorum,
+            limit: None,
+            continue_token: None,
         }
         &&& forall |msg| {
             &&& #[trigger] s.in_flight().contains(msg)
@@ -2,6 +2,7 @@
 use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::{
     api_server::{state_machine::*, types::InstalledTypes}, 
+    builtin_controllers::garbage_collector::owner_is_solid,
     cluster::*, 
     message::*,
     controller::types::*,
@@ -250,6 +251,8 @@ ensures
     cluster.lemma_always_each_object_in_etcd_has_at_most_one_controller_owner(spec);
     cluster.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
     cluster.lemma_always_each_custom_object_in_etcd_is_well_formed::<VDeploymentView>(spec);
+    cluster.lemma_always_each_scheduled_object_has_consistent_key_and_valid_metadata(spec, controller_id);
+    cluster.lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec, controller_id);
 
     let stronger_next = |s, s_prime| {
         &&& cluster.next()(s, s_prime)
@@ -260,6 +263,8 @@ ensures
         &&& Cluster::each_object_in_etcd_has_at_most_one_controller_owner()(s)
         &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
         &&& cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>()(s)
+        &&& Cluster::each_scheduled_object_has_consistent_key_and_valid_metadata(controller_id)(s)
+        &&& Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id)(s)
     };
 
     always_to_always_later(spec, lift_state(vrs_objects_in_local_reconcile_state_are_controllerly_owned_by_vd(controller_id)));
@@ -272,7 +277,9 @@ ensures
         lift_state(Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)),
         lift_state(Cluster::each_object_in_etcd_has_at_most_one_controller_owner()),
         lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
-        lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>())
+        lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>()),
+        lift_state(Cluster::each_scheduled_object_has_consistent_key_and_valid_metadata(controller_id)),
+        lift_state(Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id))
     );
 
     assert forall |s, s_prime| invariant(s) && #[trigger] stronger_next(s, s_prime) implies invariant(s_prime) by {
@@ -294,6 +301,7 @@ ensures
                 } implies match msg.content->APIRequest_0 {
                     APIRequest::ListRequest(_) => true,
                     APIRequest::CreateRequest(req) => vd_reconcile_create_request_only_interferes_with_itself(req, vd)(s),
+                    APIRequest::UpdateRequest(req) => vd_reconcile_update_request_only_interferes_with_itself(req, vd)(s),
                     APIRequest::GetThenUpdateRequest(req) => vd_reconcile_get_then_update_request_only_interferes_with_itself(req, vd)(s),
                     _ => false, // vd doesn't send other requests (yet).
                 } by {
@@ -311,6 +319,7 @@ ensures
                 } implies match msg.content->APIRequest_0 {
                     APIRequest::ListRequest(_) => true,
                     APIRequest::CreateRequest(req) => vd_reconcile_create_request_only_interferes_with_itself(req, vd)(s),
+                    APIRequest::UpdateRequest(req) => vd_reconcile_update_request_only_interferes_with_itself(req, vd)(s),
                     APIRequest::GetThenUpdateRequest(req) => vd_reconcile_get_then_update_request_only_interferes_with_itself(req, vd)(s),
                     _ => false, // vd doesn't send other requests (yet).
                 } by {
@@ -319,6 +328,16 @@ ensures
                     if id == controller_id && cr_key == vd.object_ref() {
                         let new_msgs = s_prime.in_flight().sub(s.in_flight());
 
+                        // The finalizer updates carry the scheduled or triggering vd of cr_key.
+                        if new_msgs.contains(msg) && msg.content.is_update_request() {
+                            if s.scheduled_reconciles(controller_id).contains_key(cr_key) {
+                                assert(s.scheduled_reconciles(controller_id)[cr_key].object_ref() == cr_key);
+                            }
+                            if s.ongoing_reconciles(controller_id).contains_key(cr_key) {
+                                assert(s.ongoing_reconciles(controller_id)[cr_key].triggering_cr.object_ref() == cr_key);
+                            }
+                        }
+
                         if new_msgs.contains(msg) && msg.content.is_get_then_update_request() {
                             let req = msg.content.get_get_then_update_request();
                             let state = VDeploymentReconcileState::unmarshal(s.ongoing_reconciles(controller_id)[cr_key].local_state).unwrap();
@@ -368,6 +387,7 @@ ensures
                 } implies match msg.content->APIRequest_0 {
                     APIRequest::ListRequest(_) => true,
                     APIRequest::CreateRequest(req) => vd_reconcile_create_request_only_interferes_with_itself(req, vd)(s),
+                    APIRequest::UpdateRequest(req) => vd_reconcile_update_request_only_interferes_with_itself(req, vd)(s),
                     APIRequest::GetThenUpdateRequest(req) => vd_reconcile_get_then_update_request_only_interferes_with_itself(req, vd)(s),
                     _ => false, // vd doesn't send other requests (yet).
                 } by {
@@ -500,6 +520,19 @@ ensures spec.entails(true_pred().leads_to(always(lift_state(no_pending_interferi
                         if id != controller_id {
                             assert(cluster.controller_models.remove(controller_id).contains_key(id));
                             assert(vd_rely(id)(s_prime));
+                        } else if key != vd.object_ref() {
+                            // the finalizer update of another vd targets a VDeployment, not a vrs.
+                            let havoc_vd = make_vd(); // havoc for VDeploymentView
+                            let vd_with_key = VDeploymentView {
+                                metadata: ObjectMetaView {
+                                    name: Some(key.name),
+                                    namespace: Some(key.namespace),
+                                    ..havoc_vd.metadata
+                                },
+                                ..havoc_vd
+                            };
+                            assert(vd_reconcile_request_only_interferes_with_itself(controller_id, vd_with_key)(s_prime));
+                            assert(msg.content.get_update_request().obj.kind == VDeploymentView::kind());
                         }
                     } else {
                         assert(Cluster::no_pending_request_to_api_server_from_non_controllers()(s_prime));
@@ -650,6 +683,7 @@ requires
     spec.entails(always(lift_state(Cluster::crash_disabled(controller_id)))),
     spec.entails(always(lift_state(Cluster::req_drop_disabled()))),
     spec.entails(always(lift_state(Cluster::pod_monkey_disabled()))),
+    spec.entails(always(lift_state(Cluster::node_failure_disabled()))),
     spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_unique_id()))),
     spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
     spec.entails(always(lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()))),
@@ -677,16 +711,17 @@ ensures spec.entails(true_pred().leads_to(always(lift_state(garbage_collector_do
         ==>
         ({
             let req = msg.content.get_delete_request();
-            &&& msg.content.is_delete_request()
-            &&& req.preconditions is Some
-            &&& req.preconditions.unwrap().uid is Some
-            &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
-            &&& s.resources().contains_key(req.key) ==> {
-                let obj = s.resources()[req.key];
-                ||| !(obj.metadata.owner_references_contains(vd.controller_owner_ref())
-                        && obj.kind == VReplicaSetView::kind()
-                        && obj.metadata.namespace == vd.metadata.namespace)
-                ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+            msg.content.is_delete_request() ==> {
+                &&& req.preconditions is Some
+                &&& req.preconditions.unwrap().uid is Some
+                &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
+                &&& s.resources().contains_key(req.key) ==> {
+                    let obj = s.resources()[req.key];
+                    ||| !(obj.metadata.owner_references_contains(vd.controller_owner_ref())
+                            && obj.kind == VReplicaSetView::kind()
+                            && obj.metadata.namespace == vd.metadata.namespace)
+                    ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+                }
             }
         })
     };
@@ -703,6 +738,7 @@ ensures spec.entails(true_pred().leads_to(always(lift_state(garbage_collector_do
         &&& Cluster::crash_disabled(controller_id)(s)
         &&& Cluster::req_drop_disabled()(s)
         &&& Cluster::pod_monkey_disabled()(s)
+        &&& Cluster::node_failure_disabled()(s)
         &&& Cluster::every_in_flight_msg_has_unique_id()(s)
         &&& Cluster::every_in_flight_msg_has_lower_id_than_allocator()(s)
         &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
@@ -735,18 +771,15 @@ ensures spec.entails(true_pred().leads_to(always(lift_state(garbage_collector_do
             let step = choose |step| cluster.next_step(s, s_prime, step);
             match step {
                 Step::BuiltinControllersStep(..) => {
-                    if (!s.in_flight().contains(msg) && requirements_antecedent(msg, s_prime)) {
+                    if (!s.in_flight().contains(msg) && requirements_antecedent(msg, s_prime) && msg.content.is_delete_request()) {
                         let req = msg.content.get_delete_request();
                         let key = req.key;
                         let obj = s.resources()[key];
                         let owner_references = obj.metadata.owner_references->0;
-                        assert(forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len() ==> {
-                            // the referred owner object does not exist in the cluster state
-                            ||| !s.resources().contains_key(owner_reference_to_object_reference(owner_references[i], key.namespace))
-                            // or it exists but has a different uid
-                            // (which means the actual owner was deleted and another object with the same name gets created again)
-                            ||| s.resources()[owner_reference_to_object_reference(owner_references[i], key.namespace)].metadata.uid != Some(owner_references[i].uid)
-                        });
+                        // evict_pod is disabled together with node failures, so the delete request comes from the garbage collector:
+                        // both run_garbage_collector and delete_dependent_in_foreground delete an object only if none of its owners is solid
+                        assert(forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                            ==> !owner_is_solid(cluster.installed_types, s.resources(), owner_references[i], key));
                         if obj.metadata.owner_references_contains(vd.controller_owner_ref())
                             && obj.kind == Kind::PodKind
                             && obj.metadata.namespace == vd.metadata.namespace {
@@ -834,6 +867,7 @@ ensures spec.entails(true_pred().leads_to(always(lift_state(garbage_collector_do
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::every_in_flight_msg_has_unique_id()),
         lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()),
         lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
@@ -1067,6 +1101,7 @@ ensures vrs_objects_in_local_reconcile_state_are_controllerly_owned_by_vd_with_k
                         let selector = |o: DynamicObjectView| {
                             &&& o.object_ref().namespace == req_msg.content.get_list_request().namespace
                             &&& o.object_ref().kind == req_msg.content.get_list_request().kind
+                            &&& req_msg.content.get_list_request().selects(o)
                         };
                         let selected_elements = s.resources().values().filter(selector);
                         lemma_set_to_seq_contains_all_elements(selected_elements);
@@ -1087,6 +1122,7 @@ ensures vrs_objects_in_local_reconcile_state_are_controllerly_owned_by_vd_with_k
                         let selector = |o: DynamicObjectView| {
                             &&& o.object_ref().namespace == req_msg.content.get_list_request().namespace
                             &&& o.object_ref().kind == req_msg.content.get_list_request().kind
+                            &&& req_msg.content.get_list_request().selects(o)
                         };
                         let selected_elements = s.resources().values().filter(selector);
                         assert(resp_objs.contains(resp_objs[i])); // trigger
@@ -1286,37 +1322,55 @@ ensures vrs_objects_in_local_reconcile_state_are_controllerly_owned_by_vd_with_k
                 assert(controller_owners[0] == triggering_cr.controller_owner_ref());
                 assert(controller_owners == controller_owner_singleton);
             }
-            if new_vrs is Some {
-                let owners = new_vrs->0.metadata.owner_references->0;
-                let controller_owners = owners.filter(controller_owner_filter());
-                assert(filtered_vrs_list.contains(new_vrs->0)) by {
-                    seq_filter_is_a_subset_of_original_seq(
+            if s.ongoing_reconciles(controller_id)[key].triggering_cr.metadata.deletion_timestamp is Some {
+                // reconcile_delete scales down every nonempty vrs in filtered_vrs_list.
+                let old_vrs_list = filter_nonempty_vrs(filtered_vrs_list);
+                assert forall |i| #![trigger old_vrs_list[i]] 0 <= i < old_vrs_list.len() implies {
+                    let controller_owners = old_vrs_list[i].metadata.owner_references->0.filter(controller_owner_filter());
+                    &&& old_vrs_list[i].metadata.owner_references is Some
+                    &&& old_vrs_list[i].object_ref().namespace == triggering_cr.metadata.namespace.unwrap()
+                    &&& controller_owners == controller_owner_singleton
+                } by {
+                    assert(old_vrs_list.contains(old_vrs_list[i]));
+                    seq_filter_contains_implies_seq_contains(
                         filtered_vrs_list,
-                        match_template_without_hash(triggering_cr.spec.template)
+                        |vrs: VReplicaSetView| vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0,
+                        old_vrs_list[i]
                     );
-                    let nonempty_vrs_filter = |vrs: VReplicaSetView| vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0;
-                    seq_filter_is_a_subset_of_original_seq(
-                        filtered_vrs_list.filter(match_template_without_hash(triggering_cr.spec.template)),
-                        nonempty_vrs_filter
+                }
+            } else {
+                if new_vrs is Some {
+                    let owners = new_vrs->0.metadata.owner_references->0;
+                    let controller_owners = owners.filter(controller_owner_filter());
+                    assert(filtered_vrs_list.contains(new_vrs->0)) by {
+                        seq_filter_is_a_subset_of_original_seq(
+                            filtered_vrs_list,
+                            match_template_without_hash(triggering_cr.spec.template)
+                        );
+                        let nonempty_vrs_filter = |vrs: VReplicaSetView| vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0;
+                        seq_filter_is_a_subset_of_original_seq(
+                            filtered_vrs_list.filter(match_template_without_hash(triggering_cr.spec.template)),
+                            nonempty_vrs_filter
+                        );
+                    }
+                    assert(controller_owners == controller_owner_singleton);
+                }
+                assert forall |i| #![trigger old_vrs_list[i]] 0 <= i < old_vrs_list.len() implies {
+                    let controller_owners = old_vrs_list[i].metadata.owner_references->0.filter(controller_owner_filter());
+                    &&& old_vrs_list[i].metadata.owner_references is Some
+                    &&& old_vrs_list[i].object_ref().namespace == triggering_cr.metadata.namespace.unwrap()
+                    &&& controller_owners == controller_owner_singleton
+                } by {
+                    assert(old_vrs_list.contains(old_vrs_list[i]));
+                    seq_filter_contains_implies_seq_contains(
+                        filtered_vrs_list,
+                        |vrs: VReplicaSetView| {
+                            &&& new_vrs is None || vrs.metadata.uid != new_vrs->0.metadata.uid
+                            &&& vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0
+                        },
+                        old_vrs_list[i]
                     );
                 }
-                assert(controller_owners == controller_owner_singleton);
-            }
-            assert forall |i| #![trigger old_vrs_list[i]] 0 <= i < old_vrs_list.len() implies {
-                let controller_owners = old_vrs_list[i].metadata.owner_references->0.filter(controller_owner_filter());
-                &&& old_vrs_list[i].metadata.owner_references is Some
-                &&& old_vrs_list[i].object_ref().namespace == triggering_cr.metadata.namespace.unwrap()
-                &&& controller_owners == controller_owner_singleton
-            } by {
-                assert(old_vrs_list.contains(old_vrs_list[i]));
-                seq_filter_contains_implies_seq_contains(
-                    filtered_vrs_list,
-                    |vrs: VReplicaSetView| {
-                        &&& new_vrs is None || vrs.metadata.uid != new_vrs->0.metadata.uid
-                        &&& vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0
-                    },
-                    old_vrs_list[i]
-                );
             }
         }
         if reconcile_step == VDeploymentReconcileStepView::AfterCreateNewVRS && is_ok_resp(cr_msg.content->APIResponse_0) {
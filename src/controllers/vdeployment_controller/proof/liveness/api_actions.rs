@@ -40,6 +40,7 @@ ensures
         // changing the order of fields makes a difference
         &&& o.object_ref().namespace == req.namespace
         &&& o.object_ref().kind == req.kind
+        &&& req.selects(o)
     }; 
     let resp_msg = handle_list_request_msg(req_msg, s.api_server).1;
     assert(resp_msg_is_ok_list_resp_containing_matched_vrs(vd, resp_msg, s_prime)) by {
@@ -73,6 +74,19 @@ ensures
                 }
             }
         }
+        // listed objects come from etcd, where uids are unique
+        assert forall |i: int, j: int| #![trigger resp_objs[i], resp_objs[j]] 0 <= i < resp_objs.len() && 0 <= j < resp_objs.len() && i != j
+            implies resp_objs[i].metadata.uid != resp_objs[j].metadata.uid by {
+            assert(resp_objs.contains(resp_objs[i]));
+            assert(resp_objs.contains(resp_objs[j]));
+            assert(resp_objs.map_values(|obj: DynamicObjectView| obj.object_ref())[i] != resp_objs.map_values(|obj: DynamicObjectView| obj.object_ref())[j]);
+            assert(s.resources().contains_key(resp_objs[i].object_ref()));
+            assert(s.resources().contains_key(resp_objs[j].object_ref()));
+        }
+        assert forall |obj: DynamicObjectView| #[trigger] resp_objs.contains(obj) && valid_owned_vrs(VReplicaSetView::unmarshal(obj)->Ok_0, vd) implies {
+            &&& s_prime.resources().contains_key(obj.object_ref())
+            &&& s_prime.resources()[obj.object_ref()].spec == obj.spec
+        } by {}
         let vrs_list = objects_to_vrs_list(resp_objs)->0;
         assert(vrs_list == resp_objs.map_values(|o: DynamicObjectView| VReplicaSetView::unmarshal(o)->Ok_0));
         let managed_vrs_list = vrs_list.filter(|vrs| valid_owned_vrs(vrs, vd));
@@ -175,6 +189,7 @@ ensures
         // changing the order of fields makes a difference
         &&& o.object_ref().namespace == req.namespace
         &&& o.object_ref().kind == req.kind
+        &&& req.selects(o)
     }; 
     let resp_msg = handle_list_request_msg(req_msg, s.api_server).1;
     assert(ru_resp_msg_is_ok_list_resp_containing_matched_vrs(vd, resp_msg, s_prime, new_vrs.object_ref())) by {
@@ -208,6 +223,19 @@ ensures
                 }
             }
         }
+        // listed objects come from etcd, where uids are unique
+        assert forall |i: int, j: int| #![trigger resp_objs[i], resp_objs[j]] 0 <= i < resp_objs.len() && 0 <= j < resp_objs.len() && i != j
+            implies resp_objs[i].metadata.uid != resp_objs[j].metadata.uid by {
+            assert(resp_objs.contains(resp_objs[i]));
+            assert(resp_objs.contains(resp_objs[j]));
+            assert(resp_objs.map_values(|obj: DynamicObjectView| obj.object_ref())[i] != resp_objs.map_values(|obj: DynamicObjectView| obj.object_ref())[j]);
+            assert(s.resources().contains_key(resp_objs[i].object_ref()));
+            assert(s.resources().contains_key(resp_objs[j].object_ref()));
+        }
+        assert forall |obj: DynamicObjectView| #[trigger] resp_objs.contains(obj) && valid_owned_vrs(VReplicaSetView::unmarshal(obj)->Ok_0, vd) implies {
+            &&& s_prime.resources().contains_key(obj.object_ref())
+            &&& s_prime.resources()[obj.object_ref()].spec == obj.spec
+        } by {}
         let vrs_list = objects_to_vrs_list(resp_objs)->0;
         assert(vrs_list == resp_objs.map_values(|o: DynamicObjectView| VReplicaSetView::unmarshal(o)->Ok_0));
         let managed_vrs_list = vrs_list.filter(|vrs| valid_owned_vrs(vrs, vd));
@@ -440,6 +468,7 @@ ensures
     let req = req_msg.content.get_get_then_update_request();
     let etcd_obj = s.resources()[req.key()];
     // update can succeed
+    assert(req.predicate.holds_on(etcd_obj));
     assert(etcd_obj.metadata.owner_references_contains(req.owner_ref));
     assert(req.owner_ref == vd.controller_owner_ref());
     assert(s.resources().contains_key(req.key()));
@@ -835,6 +864,8 @@ ensures
         &&& VReplicaSetView::unmarshal(s_prime.resources()[k]) is Ok
         &&& vrs.metadata.without_resource_version() == vrs_prime.metadata.without_resource_version()
         &&& vrs.spec == vrs_prime.spec
+        // the raw spec is kept as well, which is what the SpecHashMatch guard of scale_new_vrs hashes
+        &&& s.resources()[k].spec == s_prime.resources()[k].spec
         // convenient post condition
         &&& valid_owned_obj_key(vd, s_prime)(k) == valid_owned_obj_key(vd, s)(k)
     },
@@ -852,6 +883,7 @@ ensures
         &&& VReplicaSetView::unmarshal(s.resources()[k]) is Ok
         &&& vrs.metadata.without_resource_version() == vrs_prime.metadata.without_resource_version()
         &&& vrs.spec == vrs_prime.spec
+        &&& s.resources()[k].spec == s_prime.resources()[k].spec
         &&& valid_owned_obj_key(vd, s_prime)(k) == valid_owned_obj_key(vd, s)(k)
     },
 {
@@ -869,6 +901,7 @@ ensures
         &&& VReplicaSetView::unmarshal(s_prime.resources()[k]) is Ok
         &&& vrs.metadata.without_resource_version() == vrs_prime.metadata.without_resource_version()
         &&& vrs.spec == vrs_prime.spec
+        &&& s.resources()[k].spec == s_prime.resources()[k].spec
         &&& valid_owned_obj_key(vd, s_prime)(k) == valid_owned_obj_key(vd, s)(k)
     } by {
         // ==>
@@ -981,6 +1014,7 @@ ensures
         &&& VReplicaSetView::unmarshal(s.resources()[k]) is Ok
         &&& vrs.metadata.without_resource_version() == vrs_prime.metadata.without_resource_version()
         &&& vrs.spec == vrs_prime.spec
+        &&& s.resources()[k].spec == s_prime.resources()[k].spec
         &&& valid_owned_obj_key(vd, s_prime)(k) == valid_owned_obj_key(vd, s)(k)
     } by {
         let obj = s_prime.resources()[k];
@@ -1082,6 +1116,62 @@ ensures
     }
 }
 
+// the raw spec half of resp_msg_is_ok_list_resp_containing_matched_vrs, kept by other requests
+pub proof fn lemma_api_request_other_than_pending_req_msg_maintains_listed_spec_of_objects_owned_by_vd(
+    s: ClusterState, s_prime: ClusterState, vd: VDeploymentView, cluster: Cluster, controller_id: int, msg: Message, resp_objs: Seq<DynamicObjectView>
+)
+requires
+    cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+    cluster.next_step(s, s_prime, Step::APIServerStep(Some(msg))),
+    cluster_invariants_since_reconciliation(cluster, vd, controller_id)(s),
+    forall |vd| helper_invariants::vd_reconcile_request_only_interferes_with_itself(controller_id, vd)(s),
+    vd_rely_condition(cluster, controller_id)(s),
+    msg.src != HostId::Controller(controller_id, vd.object_ref()),
+    objects_to_vrs_list(resp_objs) is Some,
+    forall |obj: DynamicObjectView| #[trigger] resp_objs.contains(obj) && valid_owned_vrs(VReplicaSetView::unmarshal(obj)->Ok_0, vd) ==> {
+        &&& s.resources().contains_key(obj.object_ref())
+        &&& s.resources()[obj.object_ref()].spec == obj.spec
+    },
+    forall |vrs: VReplicaSetView| #[trigger] objects_to_vrs_list(resp_objs)->0.filter(|vrs| valid_owned_vrs(vrs, vd)).contains(vrs) ==> {
+        let etcd_vrs = VReplicaSetView::unmarshal(s.resources()[vrs.object_ref()])->Ok_0;
+        &&& VReplicaSetView::unmarshal(s.resources()[vrs.object_ref()]) is Ok
+        &&& etcd_vrs.metadata.without_resource_version() == vrs.metadata.without_resource_version()
+    },
+ensures
+    forall |obj: DynamicObjectView| #[trigger] resp_objs.contains(obj) && valid_owned_vrs(VReplicaSetView::unmarshal(obj)->Ok_0, vd) ==> {
+        &&& s_prime.resources().contains_key(obj.object_ref())
+        &&& s_prime.resources()[obj.object_ref()].spec == obj.spec
+    },
+{
+    lemma_api_request_other_than_pending_req_msg_maintains_object_owned_by_vd(
+        s, s_prime, vd, cluster, controller_id, msg
+    );
+    assert forall |obj: DynamicObjectView| #[trigger] resp_objs.contains(obj) && valid_owned_vrs(VReplicaSetView::unmarshal(obj)->Ok_0, vd) implies {
+        &&& s_prime.resources().contains_key(obj.object_ref())
+        &&& s_prime.resources()[obj.object_ref()].spec == obj.spec
+    } by {
+        VReplicaSetView::marshal_preserves_integrity();
+        VReplicaSetView::marshal_preserves_metadata();
+        let k = obj.object_ref();
+        let vrs = VReplicaSetView::unmarshal(obj)->Ok_0;
+        let etcd_obj = s.resources()[k];
+        let vrs_list = objects_to_vrs_list(resp_objs)->0;
+        assert(vrs_list.filter(|vrs| valid_owned_vrs(vrs, vd)).contains(vrs)) by {
+            broadcast use group_seq_properties;
+            let i = choose |i| 0 <= i < resp_objs.len() && resp_objs[i] == obj;
+            assert(vrs_list[i] == vrs);
+            assert(vrs_list.contains(vrs));
+        }
+        // the owner references of the listed object are still those in etcd
+        assert(etcd_obj.metadata.owner_references == vrs.metadata.owner_references);
+        assert(etcd_obj.metadata.namespace == vd.metadata.namespace);
+        assert(etcd_obj.metadata.owner_references->0.filter(controller_owner_filter()) == seq![vd.controller_owner_ref()]) by {
+            broadcast use group_seq_properties;
+            assert(etcd_obj.metadata.owner_references->0.filter(controller_owner_filter()).contains(vd.controller_owner_ref()));
+        }
+    }
+}
+
 // Havoc function for VDeploymentView.
 uninterp spec fn make_vd() -> VDeploymentView;
 
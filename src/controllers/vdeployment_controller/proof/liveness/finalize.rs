@@ -0,0 +1,1311 @@
+#![allow(unused_imports)]
+use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::kubernetes_cluster::spec::{
+    api_server::{state_machine::*, types::*},
+    cluster::*,
+    controller::{state_machine::*, types::*},
+    message::*,
+};
+use crate::kubernetes_cluster::proof::{controller_runtime_liveness::*, network_liveness::*};
+use crate::reconciler::spec::io::*;
+use verus_temporal_logic::{defs::*, rules::*};
+use crate::vdeployment_controller::{
+    model::{install::*, reconciler::*},
+    proof::{helper_invariants::*, helper_lemmas::*, liveness::{spec::*, terminate}, predicate::*},
+    trusted::{liveness_theorem::*, rely_guarantee::*, spec_types::*, step::*},
+};
+use crate::vdeployment_controller::trusted::step::VDeploymentReconcileStepView::*; // shortcut for steps
+use crate::vreplicaset_controller::trusted::spec_types::*;
+use crate::vstd_ext::{seq_lib::*, set_lib::*, string_view::*};
+use vstd::{prelude::*, seq_lib::*};
+
+verus! {
+
+// The deletion liveness of vd: once vd is being deleted, the vd controller eventually removes vd_finalizer() from vd.
+//
+// The proof follows the same structure as the ESR proof: after a few phases, each of which establishes
+// some invariants that hold until the end of the execution, the finalizer removal request is always in flight
+// whenever vd is not being reconciled, and the reconcile of vd always terminates. Unlike the ESR proof,
+// the condition conjoined to the spec in the phases (deletion_is_requested) is not an assumption on the environment,
+// so we first show that it holds until the finalizer is removed.
+pub proof fn eventually_finalized_holds_per_cr(spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int)
+    requires
+        spec.entails(lift_state(cluster.init())),
+        // The cluster always takes an action, and the relevant actions satisfy weak fairness.
+        spec.entails(next_with_wf(cluster, controller_id)),
+        // The vd type is installed in the cluster.
+        cluster.type_is_installed_in_cluster::<VDeploymentView>(),
+        // The vrs type is installed in the cluster.
+        cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+        // The vd controller runs in the cluster.
+        cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+        // No other controllers interfere with the vd controller.
+        forall |other_id| cluster.controller_models.remove(controller_id).contains_key(other_id)
+            ==> spec.entails(always(lift_state(#[trigger] vd_rely(other_id)))),
+    ensures
+        spec.entails(vd_eventually_finalized_per_cr(vd, controller_id)),
+{
+    let stable_spec = stable_spec(cluster, controller_id);
+    let deletion_is_requested = Cluster::deletion_is_requested(vd, vd_finalizer());
+    let finalizer_is_removed = Cluster::finalizer_is_removed(vd, vd_finalizer());
+    let not_interfered = always(lift_state(vd_deletion_is_not_interfered(vd, controller_id)));
+    stable_spec_is_stable(cluster, controller_id);
+    finalize_spec_before_phase_n_is_stable(vd, cluster, controller_id);
+    assert forall |i: nat| 1 <= i <= 7 implies valid(stable(#[trigger] stable_spec.and(finalize_spec_before_phase_n(i, vd, cluster, controller_id)))) by {
+        stable_and_n!(stable_spec, finalize_spec_before_phase_n(i, vd, cluster, controller_id));
+    }
+
+    lemma_true_leads_to_finalizer_is_removed(stable_spec, vd, cluster, controller_id);
+    reveal_with_fuel(finalize_spec_before_phase_n, 7);
+
+    finalize_spec_before_phase_n_entails_true_leads_to_finalizer_is_removed(6, stable_spec, vd, cluster, controller_id);
+    finalize_spec_before_phase_n_entails_true_leads_to_finalizer_is_removed(5, stable_spec, vd, cluster, controller_id);
+    finalize_spec_before_phase_n_entails_true_leads_to_finalizer_is_removed(4, stable_spec, vd, cluster, controller_id);
+    finalize_spec_before_phase_n_entails_true_leads_to_finalizer_is_removed(3, stable_spec, vd, cluster, controller_id);
+    finalize_spec_before_phase_n_entails_true_leads_to_finalizer_is_removed(2, stable_spec, vd, cluster, controller_id);
+    finalize_spec_before_phase_n_entails_true_leads_to_finalizer_is_removed(1, stable_spec, vd, cluster, controller_id);
+
+    // stable_spec /\ invariants /\ []not_interfered /\ []deletion_is_requested |= true ~> finalizer_is_removed
+    let base = stable_spec.and(invariants(vd, cluster, controller_id)).and(not_interfered);
+    temp_pred_equality(
+        stable_spec.and(finalize_spec_before_phase_n(1, vd, cluster, controller_id)),
+        base.and(always(lift_state(deletion_is_requested)))
+    );
+
+    // deletion_is_requested is only falsified by the removal of the finalizer,
+    // so base |= deletion_is_requested ~> finalizer_is_removed.
+    invariants_is_stable(vd, cluster, controller_id);
+    always_p_is_stable(lift_state(vd_deletion_is_not_interfered(vd, controller_id)));
+    stable_and_n!(stable_spec, invariants(vd, cluster, controller_id), not_interfered);
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& vd_deletion_is_not_interfered(vd, controller_id)(s)
+        &&& Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id)(s)
+        &&& forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s)
+    };
+    only_interferes_with_itself_equivalent_to_lifted_only_interferes_with_itself(base, cluster, controller_id);
+    combine_spec_entails_always_n!(
+        base, lift_action(stronger_next),
+        lift_action(cluster.next()),
+        lift_state(vd_deletion_is_not_interfered(vd, controller_id)),
+        lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id)),
+        lifted_vd_reconcile_request_only_interferes_with_itself(controller_id)
+    );
+    assert forall |s, s_prime| deletion_is_requested(s) && #[trigger] stronger_next(s, s_prime) && !finalizer_is_removed(s_prime)
+    implies deletion_is_requested(s_prime) by {
+        lemma_deletion_is_requested_is_kept_until_finalizer_is_removed(s, s_prime, vd, cluster, controller_id);
+    }
+    lemma_leads_to_by_keeping_p_until_q(base, stronger_next, deletion_is_requested, finalizer_is_removed);
+
+    // Unpack []not_interfered from the spec.
+    temp_pred_equality(
+        base,
+        stable_spec.and(invariants(vd, cluster, controller_id)).and(not_interfered)
+    );
+    unpack_conditions_from_spec(
+        stable_spec.and(invariants(vd, cluster, controller_id)), not_interfered,
+        lift_state(deletion_is_requested), lift_state(finalizer_is_removed)
+    );
+    temp_pred_equality(
+        lift_state(deletion_is_requested).and(not_interfered),
+        lift_state(Cluster::deletion_is_requested(vd, vd_finalizer()))
+            .and(always(lift_state(vd_deletion_is_not_interfered(vd, controller_id))))
+    );
+
+    // Annoying non-automatic unpacking of the spec for one precondition.
+    entails_trans(
+        spec,
+        next_with_wf(cluster, controller_id),
+        always(lift_action(cluster.next()))
+    );
+    spec_entails_all_invariants(spec, vd, cluster, controller_id);
+    simplify_predicate(spec, derived_invariants_since_beginning(vd, cluster, controller_id));
+
+    spec_and_invariants_entails_stable_spec_and_invariants(spec, vd, cluster, controller_id);
+    entails_trans(
+        spec.and(derived_invariants_since_beginning(vd, cluster, controller_id)),
+        stable_spec.and(invariants(vd, cluster, controller_id)),
+        vd_eventually_finalized_per_cr(vd, controller_id)
+    );
+}
+
+// Wrapper: quantifies eventually_finalized_holds_per_cr over vd.
+pub proof fn lemma_vd_eventually_finalized(spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int)
+    requires
+        spec.entails(lift_state(cluster.init())),
+        spec.entails(next_with_wf(cluster, controller_id)),
+        cluster.type_is_installed_in_cluster::<VDeploymentView>(),
+        cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+        cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+        forall |other_id| cluster.controller_models.remove(controller_id).contains_key(other_id)
+            ==> spec.entails(always(lift_state(#[trigger] vd_rely(other_id)))),
+    ensures
+        spec.entails(tla_forall(|vd: VDeploymentView| vd_eventually_finalized_per_cr(vd, controller_id))),
+{
+    assert forall |vd: VDeploymentView| #[trigger] spec.entails(vd_eventually_finalized_per_cr(vd, controller_id)) by {
+        eventually_finalized_holds_per_cr(spec, vd, cluster, controller_id);
+    }
+    spec_entails_tla_forall(spec, |vd: VDeploymentView| vd_eventually_finalized_per_cr(vd, controller_id));
+}
+
+// If p can only be falsified by q, and spec /\ []p |= true ~> q, then spec |= p ~> q.
+//
+// Intuitively, if q never happens after p holds, then p holds forever from then on, and thus q eventually happens.
+proof fn lemma_leads_to_by_keeping_p_until_q(spec: TempPred<ClusterState>, next: ActionPred<ClusterState>, p: StatePred<ClusterState>, q: StatePred<ClusterState>)
+    requires
+        valid(stable(spec)),
+        spec.entails(always(lift_action(next))),
+        forall |s, s_prime| p(s) && #[trigger] next(s, s_prime) && !q(s_prime) ==> p(s_prime),
+        spec.and(always(lift_state(p))).entails(true_pred().leads_to(lift_state(q))),
+    ensures spec.entails(lift_state(p).leads_to(lift_state(q))),
+{
+    assert forall |ex: Execution<ClusterState>| #[trigger] spec.satisfied_by(ex)
+    implies lift_state(p).leads_to(lift_state(q)).satisfied_by(ex) by {
+        assert forall |i: nat| #[trigger] lift_state(p).satisfied_by(ex.suffix(i))
+        implies eventually(lift_state(q)).satisfied_by(ex.suffix(i)) by {
+            if !eventually(lift_state(q)).satisfied_by(ex.suffix(i)) {
+                assert(always(lift_action(next)).satisfied_by(ex));
+                assert forall |j: nat| #[trigger] lift_state(p).satisfied_by(ex.suffix(i).suffix(j)) by {
+                    assert forall |k: nat| next((ex.nat_to_state)(i + k), (ex.nat_to_state)(i + k + 1)) by {
+                        assert(lift_action(next).satisfied_by(ex.suffix(i + k)));
+                    }
+                    assert forall |k: nat| !q((ex.nat_to_state)(i + k)) by {
+                        assert(!lift_state(q).satisfied_by(ex.suffix(i).suffix(k)));
+                    }
+                    lemma_p_is_kept_until_q(ex, next, p, q, i, j);
+                }
+                assert(stable(spec).satisfied_by(ex));
+                assert(spec.satisfied_by(ex.suffix(i)));
+                assert(spec.and(always(lift_state(p))).satisfied_by(ex.suffix(i)));
+                assert(true_pred().leads_to(lift_state(q)).satisfied_by(ex.suffix(i)));
+                assert(true_pred::<ClusterState>().satisfied_by(ex.suffix(i).suffix(0)));
+                let k = choose |k: nat| lift_state(q).satisfied_by(#[trigger] ex.suffix(i).suffix(0).suffix(k));
+                assert(lift_state(q).satisfied_by(ex.suffix(i).suffix(k)));
+            }
+        }
+    }
+}
+
+proof fn lemma_p_is_kept_until_q(ex: Execution<ClusterState>, next: ActionPred<ClusterState>, p: StatePred<ClusterState>, q: StatePred<ClusterState>, i: nat, j: nat)
+    requires
+        p((ex.nat_to_state)(i)),
+        forall |s, s_prime| p(s) && #[trigger] next(s, s_prime) && !q(s_prime) ==> p(s_prime),
+        forall |k: nat| next((ex.nat_to_state)(i + k), (ex.nat_to_state)(i + k + 1)),
+        forall |k: nat| !q((ex.nat_to_state)(i + k)),
+    ensures p((ex.nat_to_state)(i + j)),
+    decreases j,
+{
+    if j > 0 {
+        lemma_p_is_kept_until_q(ex, next, p, q, i, (j - 1) as nat);
+        assert(next((ex.nat_to_state)(i + (j - 1) as nat), (ex.nat_to_state)(i + (j - 1) as nat + 1)));
+        assert(!q((ex.nat_to_state)(i + j)));
+    }
+}
+
+pub open spec fn finalize_assumption(vd: VDeploymentView, controller_id: int) -> TempPred<ClusterState> {
+    always(lift_state(vd_deletion_is_not_interfered(vd, controller_id)))
+    .and(always(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer()))))
+}
+
+pub open spec fn finalize_invariants_since_phase_n(n: nat, vd: VDeploymentView, cluster: Cluster, controller_id: int) -> TempPred<ClusterState> {
+    if n == 1 {
+        invariants_since_phase_i(controller_id, vd)
+    } else if n == 2 {
+        always(lift_state(vd_in_schedule_has_deletion_timestamp(vd, controller_id)))
+    } else if n == 3 {
+        always(lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))))
+    } else if n == 4 {
+        always(lift_state(vd_in_schedule_is_vd_in_etcd(vd, controller_id)))
+    } else if n == 5 {
+        always(lift_state(Cluster::every_ongoing_reconcile_satisfies(controller_id, vd_deletion_reconcile_is_on_track(vd, controller_id))))
+    } else if n == 6 {
+        always(lift_state(vd_is_in_reconcile_or_finalizer_removal_is_in_flight(vd, cluster, controller_id)))
+    } else {
+        true_pred()
+    }
+}
+
+pub open spec fn finalize_spec_before_phase_n(n: nat, vd: VDeploymentView, cluster: Cluster, controller_id: int) -> TempPred<ClusterState>
+    decreases n,
+{
+    if n == 1 {
+        invariants(vd, cluster, controller_id).and(finalize_assumption(vd, controller_id))
+    } else if 2 <= n <= 7 {
+        finalize_spec_before_phase_n((n-1) as nat, vd, cluster, controller_id).and(finalize_invariants_since_phase_n((n-1) as nat, vd, cluster, controller_id))
+    } else {
+        true_pred()
+    }
+}
+
+pub proof fn finalize_spec_before_phase_n_is_stable(vd: VDeploymentView, cluster: Cluster, controller_id: int)
+    ensures forall |i: nat| 1 <= i <= 7 ==> valid(stable(#[trigger] finalize_spec_before_phase_n(i, vd, cluster, controller_id))),
+{
+    reveal_with_fuel(finalize_spec_before_phase_n, 7);
+    invariants_is_stable(vd, cluster, controller_id);
+    invariants_since_phase_i_is_stable(controller_id, vd);
+    stable_and_always_n!(
+        lift_state(vd_deletion_is_not_interfered(vd, controller_id)),
+        lift_state(Cluster::deletion_is_requested(vd, vd_finalizer()))
+    );
+    always_p_is_stable(lift_state(vd_in_schedule_has_deletion_timestamp(vd, controller_id)));
+    always_p_is_stable(lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))));
+    always_p_is_stable(lift_state(vd_in_schedule_is_vd_in_etcd(vd, controller_id)));
+    always_p_is_stable(lift_state(Cluster::every_ongoing_reconcile_satisfies(controller_id, vd_deletion_reconcile_is_on_track(vd, controller_id))));
+    always_p_is_stable(lift_state(vd_is_in_reconcile_or_finalizer_removal_is_in_flight(vd, cluster, controller_id)));
+    stable_and_n!(invariants(vd, cluster, controller_id), finalize_assumption(vd, controller_id));
+    assert forall |i: nat| 2 <= i <= 7 && valid(stable(finalize_spec_before_phase_n((i - 1) as nat, vd, cluster, controller_id)))
+    implies valid(stable(#[trigger] finalize_spec_before_phase_n(i, vd, cluster, controller_id))) by {
+        stable_and_n!(
+            finalize_spec_before_phase_n((i - 1) as nat, vd, cluster, controller_id),
+            finalize_invariants_since_phase_n((i - 1) as nat, vd, cluster, controller_id)
+        );
+    }
+    assert(valid(stable(finalize_spec_before_phase_n(2, vd, cluster, controller_id))));
+    assert(valid(stable(finalize_spec_before_phase_n(3, vd, cluster, controller_id))));
+    assert(valid(stable(finalize_spec_before_phase_n(4, vd, cluster, controller_id))));
+    assert(valid(stable(finalize_spec_before_phase_n(5, vd, cluster, controller_id))));
+    assert(valid(stable(finalize_spec_before_phase_n(6, vd, cluster, controller_id))));
+    assert(valid(stable(finalize_spec_before_phase_n(7, vd, cluster, controller_id))));
+}
+
+proof fn finalize_spec_before_phase_n_entails_true_leads_to_finalizer_is_removed(i: nat, spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int)
+    requires
+        1 <= i <= 6,
+        valid(stable(spec.and(finalize_spec_before_phase_n(i, vd, cluster, controller_id)))),
+        spec.and(finalize_spec_before_phase_n(i + 1, vd, cluster, controller_id)).entails(true_pred().leads_to(lift_state(Cluster::finalizer_is_removed(vd, vd_finalizer())))),
+        cluster.type_is_installed_in_cluster::<VDeploymentView>(),
+        cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+        cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+    ensures spec.and(finalize_spec_before_phase_n(i, vd, cluster, controller_id)).entails(true_pred().leads_to(lift_state(Cluster::finalizer_is_removed(vd, vd_finalizer())))),
+{
+    reveal_with_fuel(finalize_spec_before_phase_n, 7);
+    temp_pred_equality(
+        spec.and(finalize_spec_before_phase_n(i + 1, vd, cluster, controller_id)),
+        spec.and(finalize_spec_before_phase_n(i, vd, cluster, controller_id))
+            .and(finalize_invariants_since_phase_n(i, vd, cluster, controller_id))
+    );
+    finalize_spec_of_previous_phases_entails_eventually_new_invariants(spec, vd, cluster, controller_id, i);
+    unpack_conditions_from_spec(spec.and(finalize_spec_before_phase_n(i, vd, cluster, controller_id)), finalize_invariants_since_phase_n(i, vd, cluster, controller_id), true_pred(), lift_state(Cluster::finalizer_is_removed(vd, vd_finalizer())));
+    temp_pred_equality(
+        true_pred().and(finalize_invariants_since_phase_n(i, vd, cluster, controller_id)),
+        finalize_invariants_since_phase_n(i, vd, cluster, controller_id)
+    );
+    leads_to_trans(spec.and(finalize_spec_before_phase_n(i, vd, cluster, controller_id)), true_pred(), finalize_invariants_since_phase_n(i, vd, cluster, controller_id), lift_state(Cluster::finalizer_is_removed(vd, vd_finalizer())));
+}
+
+pub proof fn finalize_spec_of_previous_phases_entails_eventually_new_invariants(provided_spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int, i: nat)
+    requires
+        1 <= i <= 6,
+        cluster.type_is_installed_in_cluster::<VDeploymentView>(),
+        cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+        cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+    ensures provided_spec.and(finalize_spec_before_phase_n(i, vd, cluster, controller_id)).entails(true_pred().leads_to(finalize_invariants_since_phase_n(i, vd, cluster, controller_id))),
+{
+    let spec = provided_spec.and(finalize_spec_before_phase_n(i, vd, cluster, controller_id));
+    reveal_with_fuel(finalize_spec_before_phase_n, 7);
+    if i == 1 {
+        cluster.lemma_true_leads_to_crash_always_disabled(spec, controller_id);
+        cluster.lemma_true_leads_to_req_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_resp_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_req_dup_always_disabled(spec);
+        cluster.lemma_true_leads_to_node_failure_always_disabled(spec);
+        cluster.lemma_true_leads_to_pod_monkey_always_disabled(spec);
+        leads_to_always_and_n!(
+            spec,
+            true_pred(),
+            lift_state(Cluster::crash_disabled(controller_id)),
+            lift_state(Cluster::req_drop_disabled()),
+            lift_state(Cluster::resp_drop_disabled()),
+            lift_state(Cluster::req_dup_disabled()),
+            lift_state(Cluster::node_failure_disabled()),
+            lift_state(Cluster::pod_monkey_disabled())
+        );
+    } else {
+        terminate::reconcile_eventually_terminates(spec, cluster, controller_id);
+        spec_entails_tla_forall_apply(
+            spec,
+            |key: ObjectRef|
+                true_pred().leads_to(lift_state(|s: ClusterState| !s.ongoing_reconciles(controller_id).contains_key(key))),
+            vd.object_ref()
+        );
+        spec_entails_tla_forall_apply(
+            spec,
+            |input| cluster.schedule_controller_reconcile().weak_fairness((controller_id, input)),
+            vd.object_ref()
+        );
+        only_interferes_with_itself_equivalent_to_lifted_only_interferes_with_itself(spec, cluster, controller_id);
+        if i == 2 {
+            lemma_eventually_always_vd_in_schedule_has_deletion_timestamp(spec, vd, cluster, controller_id);
+        } else if i == 3 {
+            lemma_eventually_always_finalizer_is_not_added_back(spec, vd, cluster, controller_id);
+        } else if i == 4 {
+            lemma_eventually_always_vd_in_schedule_is_vd_in_etcd(spec, vd, cluster, controller_id);
+        } else if i == 5 {
+            lemma_eventually_always_vd_deletion_reconcile_is_on_track(spec, vd, cluster, controller_id);
+        } else if i == 6 {
+            lemma_eventually_always_vd_is_in_reconcile_or_finalizer_removal_is_in_flight(spec, vd, cluster, controller_id);
+        }
+    }
+}
+
+// The last phase: the reconcile of vd terminates, after which the finalizer removal request is in flight,
+// and the API server eventually handles it.
+proof fn lemma_true_leads_to_finalizer_is_removed(provided_spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int)
+    requires
+        provided_spec.entails(next_with_wf(cluster, controller_id)),
+        cluster.type_is_installed_in_cluster::<VDeploymentView>(),
+        cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+        cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+    ensures
+        provided_spec.and(finalize_spec_before_phase_n(7, vd, cluster, controller_id)).entails(true_pred().leads_to(lift_state(Cluster::finalizer_is_removed(vd, vd_finalizer())))),
+{
+    let spec = provided_spec.and(finalize_spec_before_phase_n(7, vd, cluster, controller_id));
+    reveal_with_fuel(finalize_spec_before_phase_n, 7);
+    let key = vd.object_ref();
+    let reconcile_idle = |s: ClusterState| !s.ongoing_reconciles(controller_id).contains_key(key);
+    let removal_in_flight = |msg: Message| lift_state(finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg));
+    let some_removal_in_flight = |s: ClusterState| exists |msg: Message| #[trigger] finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg)(s);
+    let finalizer_is_removed = Cluster::finalizer_is_removed(vd, vd_finalizer());
+
+    // true ~> reconcile_idle
+    terminate::reconcile_eventually_terminates(spec, cluster, controller_id);
+    spec_entails_tla_forall_apply(
+        spec,
+        |key: ObjectRef| true_pred().leads_to(lift_state(|s: ClusterState| !s.ongoing_reconciles(controller_id).contains_key(key))),
+        key
+    );
+
+    // reconcile_idle ~> some_removal_in_flight
+    assert(spec.entails(lift_state(reconcile_idle).leads_to(lift_state(some_removal_in_flight)))) by {
+        let idle_implies_removal_in_flight = |s: ClusterState| reconcile_idle(s) ==> some_removal_in_flight(s);
+        always_weaken(spec, lift_state(vd_is_in_reconcile_or_finalizer_removal_is_in_flight(vd, cluster, controller_id)), lift_state(idle_implies_removal_in_flight));
+        temp_pred_equality(lift_state(idle_implies_removal_in_flight), lift_state(reconcile_idle).implies(lift_state(some_removal_in_flight)));
+        always_implies_to_leads_to(spec, lift_state(reconcile_idle), lift_state(some_removal_in_flight));
+    }
+
+    // some_removal_in_flight ~> finalizer_is_removed
+    assert forall |msg: Message| spec.entails(#[trigger] removal_in_flight(msg).leads_to(lift_state(finalizer_is_removed))) by {
+        let pre = finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg);
+        let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+            &&& cluster.next()(s, s_prime)
+            &&& vd_is_being_deleted_without_interference(vd, controller_id)(s)
+            &&& vd_is_being_deleted_without_interference(vd, controller_id)(s_prime)
+            &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
+            &&& cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>()(s)
+            &&& Cluster::each_object_in_etcd_has_at_most_one_controller_owner()(s)
+            &&& Cluster::req_drop_disabled()(s)
+            &&& Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))(s)
+            &&& forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s)
+        };
+        lemma_spec_entails_always_vd_is_being_deleted_without_interference(spec, vd, controller_id);
+        always_to_always_later(spec, lift_state(vd_is_being_deleted_without_interference(vd, controller_id)));
+        only_interferes_with_itself_equivalent_to_lifted_only_interferes_with_itself(spec, cluster, controller_id);
+        combine_spec_entails_always_n!(
+            spec, lift_action(stronger_next),
+            lift_action(cluster.next()),
+            lift_state(vd_is_being_deleted_without_interference(vd, controller_id)),
+            later(lift_state(vd_is_being_deleted_without_interference(vd, controller_id))),
+            lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
+            lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>()),
+            lift_state(Cluster::each_object_in_etcd_has_at_most_one_controller_owner()),
+            lift_state(Cluster::req_drop_disabled()),
+            lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))),
+            lifted_vd_reconcile_request_only_interferes_with_itself(controller_id)
+        );
+        assert forall |s, s_prime| pre(s) && #[trigger] stronger_next(s, s_prime) implies pre(s_prime) || finalizer_is_removed(s_prime) by {
+            lemma_vd_in_etcd_is_unchanged(s, s_prime, vd, cluster, controller_id);
+            let step = choose |step| cluster.next_step(s, s_prime, step);
+            match step {
+                Step::APIServerStep(input) => {
+                    if input->0 == msg {
+                        lemma_finalizer_removal_request_removes_finalizer(s, s_prime, vd, cluster, controller_id, msg);
+                    }
+                },
+                _ => {}
+            }
+        }
+        assert forall |s, s_prime| pre(s) && #[trigger] stronger_next(s, s_prime) && cluster.api_server_next().forward(Some(msg))(s, s_prime)
+        implies finalizer_is_removed(s_prime) by {
+            lemma_finalizer_removal_request_removes_finalizer(s, s_prime, vd, cluster, controller_id, msg);
+        }
+        cluster.lemma_pre_leads_to_post_by_api_server(spec, Some(msg), stronger_next, APIServerStep::HandleRequest, pre, finalizer_is_removed);
+    }
+    leads_to_exists_intro(spec, removal_in_flight, lift_state(finalizer_is_removed));
+    assert_by(
+        tla_exists(removal_in_flight) == lift_state(some_removal_in_flight),
+        {
+            assert forall |ex| #[trigger] lift_state(some_removal_in_flight).satisfied_by(ex)
+            implies tla_exists(removal_in_flight).satisfied_by(ex) by {
+                let msg = choose |msg: Message| #[trigger] finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg)(ex.head());
+                assert(removal_in_flight(msg).satisfied_by(ex));
+            }
+            temp_pred_equality(tla_exists(removal_in_flight), lift_state(some_removal_in_flight));
+        }
+    );
+
+    leads_to_trans_n!(
+        spec,
+        true_pred(),
+        lift_state(reconcile_idle),
+        lift_state(some_removal_in_flight),
+        lift_state(finalizer_is_removed)
+    );
+}
+
+// Phase II: the vd in schedule is the one being deleted.
+
+pub open spec fn vd_in_schedule_has_deletion_timestamp(vd: VDeploymentView, controller_id: int) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        s.scheduled_reconciles(controller_id).contains_key(vd.object_ref())
+        ==> s.scheduled_reconciles(controller_id)[vd.object_ref()].metadata.deletion_timestamp is Some
+    }
+}
+
+pub proof fn lemma_eventually_always_vd_in_schedule_has_deletion_timestamp(
+    spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    spec.entails(always(lift_action(cluster.next()))),
+    spec.entails(always(lift_state(Cluster::there_is_the_controller_state(controller_id)))),
+    spec.entails(always(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())))),
+    spec.entails(cluster.schedule_controller_reconcile().weak_fairness((controller_id, vd.object_ref()))),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    spec.entails(true_pred().leads_to(always(lift_state(vd_in_schedule_has_deletion_timestamp(vd, controller_id))))),
+{
+    let p_prime = |s: ClusterState| Cluster::deletion_is_requested(vd, vd_finalizer())(s);
+    let q = vd_in_schedule_has_deletion_timestamp(vd, controller_id);
+
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& Cluster::there_is_the_controller_state(controller_id)(s)
+        &&& Cluster::deletion_is_requested(vd, vd_finalizer())(s)
+        &&& Cluster::deletion_is_requested(vd, vd_finalizer())(s_prime)
+    };
+    always_to_always_later(spec, lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())));
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(cluster.next()),
+        lift_state(Cluster::there_is_the_controller_state(controller_id)),
+        lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())),
+        later(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())))
+    );
+
+    cluster.schedule_controller_reconcile().wf1(
+        (controller_id, vd.object_ref()),
+        spec,
+        stronger_next,
+        p_prime,
+        q
+    );
+    leads_to_stable(spec, lift_action(stronger_next), lift_state(p_prime), lift_state(q));
+
+    temp_pred_equality(
+        true_pred().and(lift_state(p_prime)),
+        lift_state(p_prime)
+    );
+    pack_conditions_to_spec(spec, lift_state(p_prime), true_pred(), always(lift_state(q)));
+    temp_pred_equality(
+        lift_state(p_prime),
+        lift_state(Cluster::deletion_is_requested(vd, vd_finalizer()))
+    );
+    simplify_predicate(spec, always(lift_state(p_prime)));
+}
+
+// Phase III: the vd controller never adds vd_finalizer() back to vd.
+
+pub open spec fn finalizer_is_not_added_back(vd: VDeploymentView, controller_id: int) -> spec_fn(Message, ClusterState) -> bool {
+    |msg: Message, s: ClusterState| {
+        msg.src == HostId::Controller(controller_id, vd.object_ref()) && msg.content.is_update_request()
+        ==> !msg.content.get_update_request().obj.metadata.finalizers_as_set().contains(vd_finalizer())
+    }
+}
+
+pub proof fn lemma_eventually_always_finalizer_is_not_added_back(
+    spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    spec.entails(always(lift_action(cluster.next()))),
+    spec.entails(tla_forall(|i| cluster.api_server_next().weak_fairness(i))),
+    spec.entails(always(lift_state(Cluster::there_is_the_controller_state(controller_id)))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
+    spec.entails(always(lift_state(Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)))),
+    spec.entails(always(lift_state(vd_in_schedule_has_deletion_timestamp(vd, controller_id)))),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    spec.entails(true_pred().leads_to(always(lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id)))))),
+{
+    let requirements = finalizer_is_not_added_back(vd, controller_id);
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& Cluster::there_is_the_controller_state(controller_id)(s)
+        &&& Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)(s)
+        &&& vd_in_schedule_has_deletion_timestamp(vd, controller_id)(s)
+    };
+    assert forall |s: ClusterState, s_prime: ClusterState| #[trigger] stronger_next(s, s_prime)
+    implies Cluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)(s, s_prime) by {
+        assert forall |msg: Message| (!s.in_flight().contains(msg) || requirements(msg, s)) && #[trigger] s_prime.in_flight().contains(msg) && msg.dst is APIServer && msg.content is APIRequest
+        implies requirements(msg, s_prime) by {
+            if !s.in_flight().contains(msg) && msg.src == HostId::Controller(controller_id, vd.object_ref()) && msg.content.is_update_request() {
+                let step = choose |step| cluster.next_step(s, s_prime, step);
+                // Only the vd controller sends requests on behalf of the reconcile of vd.
+                let input = step->ControllerStep_0;
+                let cr_key = input.2->0;
+                assert(step is ControllerStep && input.0 == controller_id && cr_key == vd.object_ref());
+                let model = cluster.reconcile_model(controller_id);
+                if s.ongoing_reconciles(controller_id).contains_key(cr_key) {
+                    let reconcile = s.ongoing_reconciles(controller_id)[cr_key];
+                    if (model.done)(reconcile.local_state) || (model.error)(reconcile.local_state) {
+                        // end_reconcile only sends the request that filters out the finalizer.
+                        let cr = reconcile.triggering_cr;
+                        let finalizers = if cr.metadata.finalizers is Some { cr.metadata.finalizers->0 } else { Seq::empty() };
+                        let filtered = finalizers.filter(|f: StringView| f != vd_finalizer());
+                        assert(!filtered.contains(vd_finalizer())) by {
+                            if filtered.contains(vd_finalizer()) {
+                                seq_filter_contains_implies_seq_contains(finalizers, |f: StringView| f != vd_finalizer(), vd_finalizer());
+                                let i = choose |i: int| 0 <= i < filtered.len() && filtered[i] == vd_finalizer();
+                                finalizers.filter_lemma(|f: StringView| f != vd_finalizer());
+                            }
+                        }
+                    } else {
+                        // continue_reconcile only sends the requests of reconcile_core or reconcile_delete,
+                        // none of which is an update request.
+                        VDeploymentReconcileState::marshal_preserves_integrity();
+                        let triggering_vd = VDeploymentView::unmarshal(reconcile.triggering_cr)->Ok_0;
+                        let state = VDeploymentReconcileState::unmarshal(reconcile.local_state)->Ok_0;
+                        let resp_o = unmarshal_resp_o::<VoidERespView>(
+                            if input.1 is Some {
+                                if input.1->0.content is APIResponse {
+                                    Some(ResponseContent::KubernetesResponse(input.1->0.content->APIResponse_0))
+                                } else {
+                                    Some(ResponseContent::ExternalResponse(input.1->0.content->ExternalResponse_0))
+                                }
+                            } else {
+                                None
+                            }
+                        );
+                        lemma_vd_reconcile_does_not_send_update_request(triggering_vd, resp_o, state);
+                    }
+                } else {
+                    // run_scheduled_reconcile only sends the request that adds the finalizer to the vd in schedule,
+                    // which is not sent since the vd in schedule has a deletion timestamp.
+                    assert(s.scheduled_reconciles(controller_id)[cr_key].metadata.deletion_timestamp is Some);
+                    assert(finalizer_update_req(model, s.scheduled_reconciles(controller_id)[cr_key], true) is None);
+                }
+            }
+        }
+    }
+    invariant_n!(
+        spec, lift_action(stronger_next),
+        lift_action(Cluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
+        lift_action(cluster.next()),
+        lift_state(Cluster::there_is_the_controller_state(controller_id)),
+        lift_state(Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)),
+        lift_state(vd_in_schedule_has_deletion_timestamp(vd, controller_id))
+    );
+    cluster.lemma_true_leads_to_always_every_in_flight_req_msg_satisfies(spec, requirements);
+}
+
+// Neither reconcile_core nor reconcile_delete sends update requests; vd only updates itself through
+// the finalizer updates sent by the controller runtime.
+proof fn lemma_vd_reconcile_does_not_send_update_request(vd: VDeploymentView, resp_o: Option<ResponseView<VoidERespView>>, state: VDeploymentReconcileState)
+    ensures
+        reconcile_core(vd, resp_o, state).1 is Some && reconcile_core(vd, resp_o, state).1->0 is KRequest
+            ==> reconcile_core(vd, resp_o, state).1->0->KRequest_0 !is UpdateRequest,
+        reconcile_delete(vd, resp_o, state).1 is Some && reconcile_delete(vd, resp_o, state).1->0 is KRequest
+            ==> reconcile_delete(vd, resp_o, state).1->0->KRequest_0 !is UpdateRequest,
+{}
+
+// Phase IV: the vd in schedule is the vd in etcd, which does not change until the finalizer is removed.
+
+pub open spec fn vd_in_schedule_is_vd_in_etcd(vd: VDeploymentView, controller_id: int) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        s.scheduled_reconciles(controller_id).contains_key(vd.object_ref())
+        ==> s.scheduled_reconciles(controller_id)[vd.object_ref()] == s.resources()[vd.object_ref()]
+    }
+}
+
+pub proof fn lemma_eventually_always_vd_in_schedule_is_vd_in_etcd(
+    spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    spec.entails(always(lift_action(cluster.next()))),
+    spec.entails(always(lift_state(Cluster::there_is_the_controller_state(controller_id)))),
+    spec.entails(always(lift_state(Cluster::crash_disabled(controller_id)))),
+    spec.entails(always(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())))),
+    spec.entails(always(lift_state(vd_deletion_is_not_interfered(vd, controller_id)))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id)))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))))),
+    spec.entails(always(lifted_vd_reconcile_request_only_interferes_with_itself(controller_id))),
+    spec.entails(cluster.schedule_controller_reconcile().weak_fairness((controller_id, vd.object_ref()))),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    spec.entails(true_pred().leads_to(always(lift_state(vd_in_schedule_is_vd_in_etcd(vd, controller_id))))),
+{
+    let p = |s| Cluster::deletion_is_requested(vd, vd_finalizer())(s);
+    let q = vd_in_schedule_is_vd_in_etcd(vd, controller_id);
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& Cluster::there_is_the_controller_state(controller_id)(s)
+        &&& Cluster::crash_disabled(controller_id)(s)
+        &&& vd_is_being_deleted_without_interference(vd, controller_id)(s)
+        &&& vd_is_being_deleted_without_interference(vd, controller_id)(s_prime)
+        &&& Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))(s)
+        &&& forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s)
+    };
+    lemma_spec_entails_always_vd_is_being_deleted_without_interference(spec, vd, controller_id);
+    always_to_always_later(spec, lift_state(vd_is_being_deleted_without_interference(vd, controller_id)));
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(cluster.next()),
+        lift_state(Cluster::there_is_the_controller_state(controller_id)),
+        lift_state(Cluster::crash_disabled(controller_id)),
+        lift_state(vd_is_being_deleted_without_interference(vd, controller_id)),
+        later(lift_state(vd_is_being_deleted_without_interference(vd, controller_id))),
+        lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))),
+        lifted_vd_reconcile_request_only_interferes_with_itself(controller_id)
+    );
+    assert forall |s, s_prime| #[trigger] stronger_next(s, s_prime) && cluster.schedule_controller_reconcile().forward((controller_id, vd.object_ref()))(s, s_prime)
+    implies q(s_prime) by {
+        lemma_vd_in_etcd_is_unchanged(s, s_prime, vd, cluster, controller_id);
+    }
+    assert forall |s, s_prime| q(s) && #[trigger] stronger_next(s, s_prime) implies q(s_prime) by {
+        lemma_vd_in_etcd_is_unchanged(s, s_prime, vd, cluster, controller_id);
+    }
+    cluster.lemma_pre_leads_to_post_by_schedule_controller_reconcile(spec, controller_id, vd.object_ref(), stronger_next, p, q);
+    temp_pred_equality(true_pred().and(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer()))), lift_state(p));
+    leads_to_by_borrowing_inv(spec, true_pred(), lift_state(q), lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())));
+    leads_to_stable(spec, lift_action(stronger_next), true_pred(), lift_state(q));
+}
+
+// Phase V: the reconcile of vd goes through the delete path without errors,
+// so it always ends up sending the finalizer removal request.
+
+pub open spec fn resp_msg_is_ok_list_resp_of_vrs(resp_msg: Message) -> bool {
+    &&& resp_msg.content.is_list_response()
+    &&& resp_msg.content.get_list_response().res is Ok
+    &&& objects_to_vrs_list(resp_msg.content.get_list_response().res->Ok_0) is Some
+}
+
+pub open spec fn vd_deletion_reconcile_is_on_track(vd: VDeploymentView, controller_id: int) -> spec_fn(ObjectRef, ClusterState) -> bool {
+    |key: ObjectRef, s: ClusterState| key == vd.object_ref() ==> {
+        let reconcile = s.ongoing_reconciles(controller_id)[key];
+        let triggering_vd = VDeploymentView::unmarshal(reconcile.triggering_cr)->Ok_0;
+        let state = VDeploymentReconcileState::unmarshal(reconcile.local_state)->Ok_0;
+        &&& reconcile.triggering_cr == s.resources()[key]
+        &&& match state.reconcile_step {
+            Init => reconcile.pending_req_msg is None,
+            AfterListVRS => {
+                let req_msg = reconcile.pending_req_msg->0;
+                &&& reconcile.pending_req_msg is Some
+                &&& req_msg_is_list_vrs_req(triggering_vd, controller_id, req_msg, s)
+                &&& forall |msg: Message| #[trigger] s.in_flight().contains(msg) && resp_msg_matches_req_msg(msg, req_msg)
+                    ==> resp_msg_is_ok_list_resp_of_vrs(msg)
+            },
+            AfterEnsureNewVRS | AfterScaleDownOldVRS => {
+                &&& state.old_vrs_index <= state.old_vrs_list.len()
+                &&& forall |i: int| #![trigger state.old_vrs_list[i]] 0 <= i < state.old_vrs_list.len()
+                    ==> valid_owned_vrs(state.old_vrs_list[i], triggering_vd)
+            },
+            Done => true,
+            _ => false,
+        }
+    }
+}
+
+pub proof fn lemma_eventually_always_vd_deletion_reconcile_is_on_track(
+    spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    spec.entails(always(lift_action(cluster.next()))),
+    spec.entails(tla_forall(|i: (Option<Message>, Option<ObjectRef>)| cluster.controller_next().weak_fairness((controller_id, i.0, i.1)))),
+    spec.entails(tla_forall(|key: ObjectRef| true_pred().leads_to(lift_state(|s: ClusterState| !s.ongoing_reconciles(controller_id).contains_key(key))))),
+    spec.entails(always(lift_state(Cluster::every_ongoing_reconcile_has_lower_id_than_allocator(controller_id)))),
+    spec.entails(always(lift_state(Cluster::there_is_the_controller_state(controller_id)))),
+    spec.entails(always(lift_state(Cluster::crash_disabled(controller_id)))),
+    spec.entails(always(lift_state(Cluster::req_drop_disabled()))),
+    spec.entails(always(lift_state(Cluster::req_dup_disabled()))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_req_msg_has_different_id_from_pending_req_msg_of_every_ongoing_reconcile(controller_id)))),
+    spec.entails(always(lift_state(Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)))),
+    spec.entails(always(lift_state(Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id)))),
+    spec.entails(always(lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<VReplicaSetView>()))),
+    spec.entails(always(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())))),
+    spec.entails(always(lift_state(vd_deletion_is_not_interfered(vd, controller_id)))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id)))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))))),
+    spec.entails(always(lift_state(vd_in_schedule_is_vd_in_etcd(vd, controller_id)))),
+    spec.entails(always(lifted_vd_reconcile_request_only_interferes_with_itself(controller_id))),
+    cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    spec.entails(true_pred().leads_to(always(lift_state(Cluster::every_ongoing_reconcile_satisfies(controller_id, vd_deletion_reconcile_is_on_track(vd, controller_id)))))),
+{
+    let requirements = vd_deletion_reconcile_is_on_track(vd, controller_id);
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& vd_deletion_reconcile_is_on_track_conditions(vd, cluster, controller_id)(s)
+        &&& vd_is_being_deleted_without_interference(vd, controller_id)(s_prime)
+        &&& forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s)
+    };
+    lemma_spec_entails_always_vd_is_being_deleted_without_interference(spec, vd, controller_id);
+    always_to_always_later(spec, lift_state(vd_is_being_deleted_without_interference(vd, controller_id)));
+    combine_spec_entails_always_n!(
+        spec, lift_state(vd_deletion_reconcile_is_on_track_conditions(vd, cluster, controller_id)),
+        lift_state(Cluster::there_is_the_controller_state(controller_id)),
+        lift_state(Cluster::crash_disabled(controller_id)),
+        lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()),
+        lift_state(Cluster::every_in_flight_req_msg_has_different_id_from_pending_req_msg_of_every_ongoing_reconcile(controller_id)),
+        lift_state(Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)),
+        lift_state(Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id)),
+        lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<VReplicaSetView>()),
+        lift_state(vd_is_being_deleted_without_interference(vd, controller_id)),
+        lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))),
+        lift_state(vd_in_schedule_is_vd_in_etcd(vd, controller_id))
+    );
+    assert forall |s: ClusterState, s_prime: ClusterState| #[trigger] stronger_next(s, s_prime)
+    implies Cluster::every_new_ongoing_reconcile_satisfies(controller_id, requirements)(s, s_prime) by {
+        lemma_vd_deletion_reconcile_is_on_track_is_preserved(s, s_prime, vd, cluster, controller_id);
+    }
+    invariant_n!(
+        spec, lift_action(stronger_next),
+        lift_action(Cluster::every_new_ongoing_reconcile_satisfies(controller_id, requirements)),
+        lift_action(cluster.next()),
+        lift_state(vd_deletion_reconcile_is_on_track_conditions(vd, cluster, controller_id)),
+        later(lift_state(vd_is_being_deleted_without_interference(vd, controller_id))),
+        lifted_vd_reconcile_request_only_interferes_with_itself(controller_id)
+    );
+    cluster.lemma_true_leads_to_always_every_ongoing_reconcile_satisfies(spec, controller_id, requirements);
+}
+
+pub open spec fn vd_deletion_reconcile_is_on_track_conditions(vd: VDeploymentView, cluster: Cluster, controller_id: int) -> StatePred<ClusterState> {
+    and!(
+        Cluster::there_is_the_controller_state(controller_id),
+        Cluster::crash_disabled(controller_id),
+        Cluster::req_drop_disabled(),
+        Cluster::req_dup_disabled(),
+        Cluster::every_in_flight_msg_has_lower_id_than_allocator(),
+        Cluster::every_in_flight_req_msg_has_different_id_from_pending_req_msg_of_every_ongoing_reconcile(controller_id),
+        Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id),
+        Cluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata(controller_id),
+        cluster.each_custom_object_in_etcd_is_well_formed::<VReplicaSetView>(),
+        vd_is_being_deleted_without_interference(vd, controller_id),
+        Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id)),
+        vd_in_schedule_is_vd_in_etcd(vd, controller_id)
+    )
+}
+
+#[verifier(rlimit(100))]
+#[verifier(spinoff_prover)]
+pub proof fn lemma_vd_deletion_reconcile_is_on_track_is_preserved(
+    s: ClusterState, s_prime: ClusterState, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    cluster.next()(s, s_prime),
+    vd_deletion_reconcile_is_on_track_conditions(vd, cluster, controller_id)(s),
+    vd_is_being_deleted_without_interference(vd, controller_id)(s_prime),
+    forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s),
+    cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    Cluster::every_new_ongoing_reconcile_satisfies(controller_id, vd_deletion_reconcile_is_on_track(vd, controller_id))(s, s_prime),
+{
+    let key = vd.object_ref();
+    let requirements = vd_deletion_reconcile_is_on_track(vd, controller_id);
+    VDeploymentReconcileState::marshal_preserves_integrity();
+    VDeploymentView::marshal_preserves_integrity();
+    lemma_vd_in_etcd_is_unchanged(s, s_prime, vd, cluster, controller_id);
+    assert forall |k: ObjectRef| (!s.ongoing_reconciles(controller_id).contains_key(k) || requirements(k, s))
+        && #[trigger] s_prime.ongoing_reconciles(controller_id).contains_key(k)
+    implies requirements(k, s_prime) by {
+        if k == key {
+            let step = choose |step| cluster.next_step(s, s_prime, step);
+            let triggering_cr = s_prime.ongoing_reconciles(controller_id)[key].triggering_cr;
+            let triggering_vd = VDeploymentView::unmarshal(triggering_cr)->Ok_0;
+            match step {
+                Step::ControllerStep(input) => {
+                    if input.0 == controller_id && input.2 == Some(key) {
+                        if !s.ongoing_reconciles(controller_id).contains_key(key) {
+                            // run_scheduled_reconcile starts from the vd in schedule, which is the vd in etcd.
+                            assert(triggering_cr == s.scheduled_reconciles(controller_id)[key]);
+                            assert(triggering_cr == s_prime.resources()[key]);
+                        } else {
+                            // continue_reconcile runs reconcile_delete since the triggering vd is being deleted.
+                            let reconcile = s.ongoing_reconciles(controller_id)[key];
+                            let state = VDeploymentReconcileState::unmarshal(reconcile.local_state)->Ok_0;
+                            assert(triggering_cr == reconcile.triggering_cr);
+                            assert(triggering_cr.metadata.deletion_timestamp is Some);
+                            assert(cluster.reconcile_model(controller_id).finalizer is Some);
+                            let state_prime = VDeploymentReconcileState::unmarshal(s_prime.ongoing_reconciles(controller_id)[key].local_state)->Ok_0;
+                            match state.reconcile_step {
+                                AfterListVRS => {
+                                    // The response is the ok list response to the pending request.
+                                    let resp_msg = input.1->0;
+                                    assert(s.in_flight().contains(resp_msg));
+                                    assert(resp_msg_is_ok_list_resp_of_vrs(resp_msg));
+                                    let objs = resp_msg.content.get_list_response().res->Ok_0;
+                                    let vrs_list = objects_to_vrs_list(objs)->0;
+                                    let valid_owned_vrs_list = vrs_list.filter(|vrs: VReplicaSetView| valid_owned_vrs(vrs, triggering_vd));
+                                    let nonempty_vrs_list = filter_nonempty_vrs(valid_owned_vrs_list);
+                                    assert(state_prime.old_vrs_list == nonempty_vrs_list);
+                                    assert forall |i: int| #![trigger nonempty_vrs_list[i]] 0 <= i < nonempty_vrs_list.len()
+                                    implies valid_owned_vrs(nonempty_vrs_list[i], triggering_vd) by {
+                                        assert(nonempty_vrs_list.contains(nonempty_vrs_list[i]));
+                                        seq_filter_contains_implies_seq_contains(
+                                            valid_owned_vrs_list,
+                                            |vrs: VReplicaSetView| vrs.spec.replicas is None || vrs.spec.replicas.unwrap() > 0,
+                                            nonempty_vrs_list[i]
+                                        );
+                                        valid_owned_vrs_list.filter_lemma(|vrs: VReplicaSetView| valid_owned_vrs(vrs, triggering_vd));
+                                    }
+                                },
+                                AfterEnsureNewVRS | AfterScaleDownOldVRS => {
+                                    assert(state_prime.old_vrs_list == state.old_vrs_list);
+                                },
+                                _ => {},
+                            }
+                            // The newly sent request has a fresh rpc id, so no response matches it yet.
+                            if s_prime.ongoing_reconciles(controller_id)[key].pending_req_msg is Some {
+                                let req_msg = s_prime.ongoing_reconciles(controller_id)[key].pending_req_msg->0;
+                                assert(forall |msg| #[trigger] s.in_flight().contains(msg) ==> msg.rpc_id != req_msg.rpc_id);
+                                assert(forall |msg| #[trigger] s_prime.in_flight().contains(msg) && msg != req_msg ==> s.in_flight().contains(msg));
+                            }
+                        }
+                    }
+                },
+                Step::APIServerStep(input) => {
+                    let req_msg = input->0;
+                    let state = VDeploymentReconcileState::unmarshal(s.ongoing_reconciles(controller_id)[key].local_state)->Ok_0;
+                    if state.reconcile_step == AfterListVRS {
+                        let pending_req_msg = s.ongoing_reconciles(controller_id)[key].pending_req_msg->0;
+                        if req_msg == pending_req_msg {
+                            let resp_msg = lemma_list_vrs_request_returns_ok_vrs_list(s, s_prime, triggering_vd, cluster, controller_id, req_msg);
+                            assert(forall |msg| #[trigger] s_prime.in_flight().contains(msg) && msg != resp_msg ==> s.in_flight().contains(msg));
+                        } else {
+                            // The response to any other request has a different rpc id.
+                            assert(req_msg.rpc_id != pending_req_msg.rpc_id);
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+pub proof fn lemma_list_vrs_request_returns_ok_vrs_list(
+    s: ClusterState, s_prime: ClusterState, vd: VDeploymentView, cluster: Cluster, controller_id: int, req_msg: Message,
+) -> (resp_msg: Message)
+requires
+    cluster.type_is_installed_in_cluster::<VReplicaSetView>(),
+    cluster.next_step(s, s_prime, Step::APIServerStep(Some(req_msg))),
+    req_msg_is_list_vrs_req(vd, controller_id, req_msg, s),
+    cluster.each_custom_object_in_etcd_is_well_formed::<VReplicaSetView>()(s),
+ensures
+    resp_msg == handle_list_request_msg(req_msg, s.api_server).1,
+    s_prime.in_flight().contains(resp_msg),
+    resp_msg_matches_req_msg(resp_msg, req_msg),
+    resp_msg_is_ok_list_resp_of_vrs(resp_msg),
+{
+    broadcast use group_seq_properties;
+    VReplicaSetView::marshal_preserves_integrity();
+    let req = req_msg.content.get_list_request();
+    let list_req_filter = |o: DynamicObjectView| {
+        // changing the order of fields makes a difference
+        &&& o.object_ref().namespace == req.namespace
+        &&& o.object_ref().kind == req.kind
+        &&& req.selects(o)
+    };
+    let resp_msg = handle_list_request_msg(req_msg, s.api_server).1;
+    let resp_objs = resp_msg.content.get_list_response().res.unwrap();
+    assert(resp_objs == s.resources().values().filter(list_req_filter).to_seq());
+    assert forall |o| #[trigger] resp_objs.contains(o) implies VReplicaSetView::unmarshal(o) is Ok by {
+        assert(s.resources().values().filter(list_req_filter).contains(o)) by {
+            lemma_set_to_seq_contains_all_elements(s.resources().values().filter(list_req_filter));
+        }
+    }
+    seq_pred_false_on_all_elements_is_equivalent_to_empty_filter(resp_objs, |o: DynamicObjectView| VReplicaSetView::unmarshal(o).is_err());
+    resp_msg
+}
+
+// Phase VI: vd is either being reconciled, or its finalizer removal request is in flight.
+
+pub open spec fn finalizer_removal_request_is_in_flight(vd: VDeploymentView, cluster: Cluster, controller_id: int, msg: Message) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        let req_o = finalizer_update_req(cluster.reconcile_model(controller_id), s.resources()[vd.object_ref()], false);
+        &&& s.resources().contains_key(vd.object_ref())
+        &&& req_o is Some
+        &&& s.in_flight().contains(msg)
+        &&& msg == controller_req_msg(controller_id, vd.object_ref(), msg.rpc_id, APIRequest::UpdateRequest(req_o->0))
+    }
+}
+
+pub open spec fn vd_is_in_reconcile_or_finalizer_removal_is_in_flight(vd: VDeploymentView, cluster: Cluster, controller_id: int) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        ||| s.ongoing_reconciles(controller_id).contains_key(vd.object_ref())
+        ||| exists |msg: Message| #[trigger] finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg)(s)
+    }
+}
+
+pub proof fn lemma_eventually_always_vd_is_in_reconcile_or_finalizer_removal_is_in_flight(
+    spec: TempPred<ClusterState>, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    spec.entails(always(lift_action(cluster.next()))),
+    spec.entails(tla_forall(|i: (Option<Message>, Option<ObjectRef>)| cluster.controller_next().weak_fairness((controller_id, i.0, i.1)))),
+    spec.entails(cluster.schedule_controller_reconcile().weak_fairness((controller_id, vd.object_ref()))),
+    spec.entails(always(lift_state(Cluster::there_is_the_controller_state(controller_id)))),
+    spec.entails(always(lift_state(Cluster::crash_disabled(controller_id)))),
+    spec.entails(always(lift_state(Cluster::req_drop_disabled()))),
+    spec.entails(always(lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()))),
+    spec.entails(always(lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>()))),
+    spec.entails(always(lift_state(Cluster::each_object_in_etcd_has_at_most_one_controller_owner()))),
+    spec.entails(always(lift_state(Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)))),
+    spec.entails(always(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())))),
+    spec.entails(always(lift_state(vd_deletion_is_not_interfered(vd, controller_id)))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id)))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))))),
+    spec.entails(always(lift_state(Cluster::every_ongoing_reconcile_satisfies(controller_id, vd_deletion_reconcile_is_on_track(vd, controller_id))))),
+    spec.entails(always(lifted_vd_reconcile_request_only_interferes_with_itself(controller_id))),
+    cluster.type_is_installed_in_cluster::<VDeploymentView>(),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    spec.entails(true_pred().leads_to(always(lift_state(vd_is_in_reconcile_or_finalizer_removal_is_in_flight(vd, cluster, controller_id))))),
+{
+    let key = vd.object_ref();
+    let inv = vd_is_in_reconcile_or_finalizer_removal_is_in_flight(vd, cluster, controller_id);
+    let in_reconcile = |s: ClusterState| s.ongoing_reconciles(controller_id).contains_key(key);
+    let not_scheduled_or_reconcile = |s: ClusterState| {
+        &&& !s.ongoing_reconciles(controller_id).contains_key(key)
+        &&& !s.scheduled_reconciles(controller_id).contains_key(key)
+    };
+    let scheduled_and_not_reconcile = |s: ClusterState| {
+        &&& !s.ongoing_reconciles(controller_id).contains_key(key)
+        &&& s.scheduled_reconciles(controller_id).contains_key(key)
+    };
+    // not_scheduled_or_reconcile ~> scheduled_and_not_reconcile
+    assert(spec.entails(lift_state(not_scheduled_or_reconcile).leads_to(lift_state(scheduled_and_not_reconcile)))) by {
+        let (pre, post) = (not_scheduled_or_reconcile, scheduled_and_not_reconcile);
+        let stronger_next = |s, s_prime| {
+            &&& cluster.next()(s, s_prime)
+            &&& Cluster::deletion_is_requested(vd, vd_finalizer())(s)
+            &&& Cluster::deletion_is_requested(vd, vd_finalizer())(s_prime)
+            &&& Cluster::there_is_the_controller_state(controller_id)(s)
+        };
+        always_to_always_later(spec, lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())));
+        combine_spec_entails_always_n!(
+            spec, lift_action(stronger_next),
+            lift_action(cluster.next()),
+            lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())),
+            later(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer()))),
+            lift_state(Cluster::there_is_the_controller_state(controller_id))
+        );
+        let stronger_pre = and!(pre, Cluster::deletion_is_requested(vd, vd_finalizer()));
+        cluster.lemma_pre_leads_to_post_by_schedule_controller_reconcile(spec, controller_id, key, stronger_next, stronger_pre, post);
+        temp_pred_equality(lift_state(pre).and(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer()))), lift_state(stronger_pre));
+        leads_to_by_borrowing_inv(spec, lift_state(pre), lift_state(post), lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())));
+    }
+    // scheduled_and_not_reconcile ~> in_reconcile
+    assert(spec.entails(lift_state(scheduled_and_not_reconcile).leads_to(lift_state(in_reconcile)))) by {
+        cluster.lemma_reconcile_idle_and_scheduled_leads_to_reconcile_init(spec, controller_id, key);
+        leads_to_weaken(
+            spec,
+            lift_state(scheduled_and_not_reconcile), lift_state(cluster.reconciler_init_and_no_pending_req(controller_id, key)),
+            lift_state(scheduled_and_not_reconcile), lift_state(in_reconcile)
+        );
+    }
+    leads_to_trans(spec, lift_state(not_scheduled_or_reconcile), lift_state(scheduled_and_not_reconcile), lift_state(in_reconcile));
+    entails_implies_leads_to(spec, lift_state(in_reconcile), lift_state(in_reconcile));
+    or_leads_to_combine_and_equality!(
+        spec, true_pred(),
+        lift_state(in_reconcile), lift_state(scheduled_and_not_reconcile), lift_state(not_scheduled_or_reconcile);
+        lift_state(in_reconcile)
+    );
+    leads_to_weaken(
+        spec,
+        true_pred(), lift_state(in_reconcile),
+        true_pred(), lift_state(inv)
+    );
+
+    // inv is preserved until the end of the execution.
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& Cluster::there_is_the_controller_state(controller_id)(s)
+        &&& Cluster::crash_disabled(controller_id)(s)
+        &&& Cluster::req_drop_disabled()(s)
+        &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
+        &&& cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>()(s)
+        &&& Cluster::each_object_in_etcd_has_at_most_one_controller_owner()(s)
+        &&& Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)(s)
+        &&& vd_is_being_deleted_without_interference(vd, controller_id)(s)
+        &&& vd_is_being_deleted_without_interference(vd, controller_id)(s_prime)
+        &&& Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))(s)
+        &&& Cluster::every_ongoing_reconcile_satisfies(controller_id, vd_deletion_reconcile_is_on_track(vd, controller_id))(s)
+        &&& forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s)
+    };
+    lemma_spec_entails_always_vd_is_being_deleted_without_interference(spec, vd, controller_id);
+    always_to_always_later(spec, lift_state(vd_is_being_deleted_without_interference(vd, controller_id)));
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(cluster.next()),
+        lift_state(Cluster::there_is_the_controller_state(controller_id)),
+        lift_state(Cluster::crash_disabled(controller_id)),
+        lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
+        lift_state(cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>()),
+        lift_state(Cluster::each_object_in_etcd_has_at_most_one_controller_owner()),
+        lift_state(Cluster::cr_states_are_unmarshallable::<VDeploymentReconcileState, VDeploymentView>(controller_id)),
+        lift_state(vd_is_being_deleted_without_interference(vd, controller_id)),
+        later(lift_state(vd_is_being_deleted_without_interference(vd, controller_id))),
+        lift_state(Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))),
+        lift_state(Cluster::every_ongoing_reconcile_satisfies(controller_id, vd_deletion_reconcile_is_on_track(vd, controller_id))),
+        lifted_vd_reconcile_request_only_interferes_with_itself(controller_id)
+    );
+    assert forall |s, s_prime| inv(s) && #[trigger] stronger_next(s, s_prime) implies inv(s_prime) by {
+        VDeploymentReconcileState::marshal_preserves_integrity();
+        lemma_vd_in_etcd_is_unchanged(s, s_prime, vd, cluster, controller_id);
+        let model = cluster.reconcile_model(controller_id);
+        assert(model.finalizer == Some(vd_finalizer()));
+        if !s_prime.ongoing_reconciles(controller_id).contains_key(key) {
+            let step = choose |step| cluster.next_step(s, s_prime, step);
+            if s.ongoing_reconciles(controller_id).contains_key(key) {
+                // The reconcile of vd ends at Done, sending the finalizer removal request.
+                let input = step->ControllerStep_0;
+                assert(step is ControllerStep && input.0 == controller_id && input.2 == Some(key));
+                let reconcile = s.ongoing_reconciles(controller_id)[key];
+                assert(vd_deletion_reconcile_is_on_track(vd, controller_id)(key, s));
+                assert((model.done)(reconcile.local_state));
+                let req = finalizer_update_req(model, reconcile.triggering_cr, false)->0;
+                let rpc_id = s.rpc_id_allocator.allocate().1;
+                let msg = controller_req_msg(controller_id, key, rpc_id, APIRequest::UpdateRequest(req));
+                assert(s_prime.in_flight().contains(msg));
+                assert(finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg)(s_prime));
+            } else {
+                // The removal request is still in flight, or it is handled, which removes the finalizer.
+                let msg = choose |msg: Message| #[trigger] finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg)(s);
+                match step {
+                    Step::APIServerStep(input) => {
+                        if input->0 == msg {
+                            lemma_finalizer_removal_request_removes_finalizer(s, s_prime, vd, cluster, controller_id, msg);
+                            assert(false);
+                        }
+                    },
+                    _ => {},
+                }
+                assert(finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg)(s_prime));
+            }
+        }
+    }
+    leads_to_stable(spec, lift_action(stronger_next), true_pred(), lift_state(inv));
+}
+
+// Lemmas on the object of vd in etcd during its deletion.
+
+pub open spec fn vd_is_being_deleted_without_interference(vd: VDeploymentView, controller_id: int) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        &&& Cluster::deletion_is_requested(vd, vd_finalizer())(s)
+        &&& vd_deletion_is_not_interfered(vd, controller_id)(s)
+        &&& Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id)(s)
+    }
+}
+
+pub proof fn lemma_spec_entails_always_vd_is_being_deleted_without_interference(
+    spec: TempPred<ClusterState>, vd: VDeploymentView, controller_id: int
+)
+requires
+    spec.entails(always(lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())))),
+    spec.entails(always(lift_state(vd_deletion_is_not_interfered(vd, controller_id)))),
+    spec.entails(always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id)))),
+ensures
+    spec.entails(always(lift_state(vd_is_being_deleted_without_interference(vd, controller_id)))),
+{
+    combine_spec_entails_always_n!(
+        spec, lift_state(vd_is_being_deleted_without_interference(vd, controller_id)),
+        lift_state(Cluster::deletion_is_requested(vd, vd_finalizer())),
+        lift_state(vd_deletion_is_not_interfered(vd, controller_id)),
+        lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id))
+    );
+}
+
+// Only the update requests sent by the vd controller on behalf of vd itself can change vd in etcd:
+// other hosts do not write to vd (vd_deletion_is_not_interfered), and the vd controller only sends
+// list requests, and create and get-then-update requests of vrs, on behalf of other vds.
+pub proof fn lemma_only_vd_controller_changes_vd_in_etcd(
+    s: ClusterState, s_prime: ClusterState, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    cluster.next()(s, s_prime),
+    vd_is_being_deleted_without_interference(vd, controller_id)(s),
+    forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    ({
+        let step = choose |step| cluster.next_step(s, s_prime, step);
+        let msg = step->APIServerStep_0->0;
+        ||| s_prime.resources().contains_key(vd.object_ref()) && s_prime.resources()[vd.object_ref()] == s.resources()[vd.object_ref()]
+        ||| {
+            &&& step is APIServerStep
+            &&& s.in_flight().contains(msg)
+            &&& msg.src == HostId::Controller(controller_id, vd.object_ref())
+            &&& msg.content.is_update_request_with_key(vd.object_ref())
+        }
+    }),
+{
+    let key = vd.object_ref();
+    let step = choose |step| cluster.next_step(s, s_prime, step);
+    match step {
+        Step::APIServerStep(input) => {
+            let msg = input->0;
+            if msg.content is APIRequest {
+                if msg.src.is_controller_id(controller_id) {
+                    let cr_key = msg.src->Controller_1;
+                    if cr_key == key {
+                        assert(vd_reconcile_request_only_interferes_with_itself(controller_id, vd)(s));
+                    } else {
+                        let havoc_vd = make_vd(); // havoc for VDeploymentView
+                        let vd_with_key = VDeploymentView {
+                            metadata: ObjectMetaView {
+                                name: Some(cr_key.name),
+                                namespace: Some(cr_key.namespace),
+                                ..havoc_vd.metadata
+                            },
+                            ..havoc_vd
+                        };
+                        assert(cr_key == vd_with_key.object_ref());
+                        assert(vd_reconcile_request_only_interferes_with_itself(controller_id, vd_with_key)(s));
+                    }
+                } else {
+                    // A create request cannot override vd, which is still in etcd.
+                    assert(vd_deletion_is_not_interfered(vd, controller_id)(s));
+                }
+            }
+        },
+        _ => {}
+    }
+}
+
+// While vd is being deleted, vd in etcd does not change: the only possible change is
+// the update sent by the vd controller, which does not carry vd_finalizer(), so any effective update removes it.
+pub proof fn lemma_vd_in_etcd_is_unchanged(
+    s: ClusterState, s_prime: ClusterState, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    cluster.next()(s, s_prime),
+    vd_is_being_deleted_without_interference(vd, controller_id)(s),
+    vd_is_being_deleted_without_interference(vd, controller_id)(s_prime),
+    Cluster::every_in_flight_req_msg_satisfies(finalizer_is_not_added_back(vd, controller_id))(s),
+    forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    s_prime.resources().contains_key(vd.object_ref()),
+    s_prime.resources()[vd.object_ref()] == s.resources()[vd.object_ref()],
+{
+    let key = vd.object_ref();
+    lemma_only_vd_controller_changes_vd_in_etcd(s, s_prime, vd, cluster, controller_id);
+    if !(s_prime.resources().contains_key(key) && s_prime.resources()[key] == s.resources()[key]) {
+        let step = choose |step| cluster.next_step(s, s_prime, step);
+        let msg = step->APIServerStep_0->0;
+        let req = msg.content.get_update_request();
+        assert(finalizer_is_not_added_back(vd, controller_id)(msg, s));
+        assert(!req.obj.metadata.finalizers_as_set().contains(vd_finalizer()));
+        // The update succeeds and changes vd, so vd either carries the finalizers in req or is deleted.
+        let updated_obj = updated_object(req, s.resources()[key]);
+        assert(updated_obj.metadata.finalizers == req.obj.metadata.finalizers);
+        assert(!Cluster::deletion_is_requested(vd, vd_finalizer())(s_prime));
+    }
+}
+
+// If vd is still being deleted after a step, it is because the step does not remove vd_finalizer() from vd.
+pub proof fn lemma_deletion_is_requested_is_kept_until_finalizer_is_removed(
+    s: ClusterState, s_prime: ClusterState, vd: VDeploymentView, cluster: Cluster, controller_id: int
+)
+requires
+    cluster.next()(s, s_prime),
+    Cluster::deletion_is_requested(vd, vd_finalizer())(s),
+    vd_deletion_is_not_interfered(vd, controller_id)(s),
+    Cluster::every_in_flight_msg_from_controller_has_kind_as::<VDeploymentView>(controller_id)(s),
+    forall |other_vd: VDeploymentView| #[trigger] vd_reconcile_request_only_interferes_with_itself(controller_id, other_vd)(s),
+    !Cluster::finalizer_is_removed(vd, vd_finalizer())(s_prime),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    Cluster::deletion_is_requested(vd, vd_finalizer())(s_prime),
+{
+    let key = vd.object_ref();
+    lemma_only_vd_controller_changes_vd_in_etcd(s, s_prime, vd, cluster, controller_id);
+    if !(s_prime.resources().contains_key(key) && s_prime.resources()[key] == s.resources()[key]) {
+        // The update keeps the deletion timestamp of vd.
+        let step = choose |step| cluster.next_step(s, s_prime, step);
+        let msg = step->APIServerStep_0->0;
+        let req = msg.content.get_update_request();
+        let updated_obj = updated_object(req, s.resources()[key]);
+        assert(updated_obj.metadata.deletion_timestamp == s.resources()[key].metadata.deletion_timestamp);
+        assert(updated_obj.metadata.uid == s.resources()[key].metadata.uid);
+    }
+}
+
+// The finalizer removal request sent by the vd controller passes all the checks of the API server:
+// it only differs from vd in etcd in the finalizers, and it only removes vd_finalizer().
+pub proof fn lemma_finalizer_removal_request_removes_finalizer(
+    s: ClusterState, s_prime: ClusterState, vd: VDeploymentView, cluster: Cluster, controller_id: int, msg: Message
+)
+requires
+    cluster.next_step(s, s_prime, Step::APIServerStep(Some(msg))),
+    finalizer_removal_request_is_in_flight(vd, cluster, controller_id, msg)(s),
+    Cluster::each_object_in_etcd_is_weakly_well_formed()(s),
+    cluster.each_custom_object_in_etcd_is_well_formed::<VDeploymentView>()(s),
+    Cluster::each_object_in_etcd_has_at_most_one_controller_owner()(s),
+    cluster.type_is_installed_in_cluster::<VDeploymentView>(),
+    cluster.controller_models.contains_pair(controller_id, vd_controller_model()),
+ensures
+    Cluster::finalizer_is_removed(vd, vd_finalizer())(s_prime),
+{
+    let key = vd.object_ref();
+    let model = cluster.reconcile_model(controller_id);
+    assert(model.finalizer == Some(vd_finalizer()));
+    let old_obj = s.resources()[key];
+    let req = finalizer_update_req(model, old_obj, false)->0;
+    let finalizers = old_obj.metadata.finalizers->0;
+    let filter_pred = |f: StringView| f != vd_finalizer();
+    let filtered = finalizers.filter(filter_pred);
+    assert(req.obj == old_obj.with_metadata(old_obj.metadata.with_finalizers(filtered)));
+    assert(!filtered.contains(vd_finalizer())) by {
+        if filtered.contains(vd_finalizer()) {
+            finalizers.filter_lemma(filter_pred);
+        }
+    }
+    assert(filtered.to_set().subset_of(finalizers.to_set())) by {
+        seq_filter_is_a_subset_of_original_seq(finalizers, filter_pred);
+    }
+
+    // Admission: req.obj has the same key, rv and uid as vd in etcd, and its spec and status are unchanged.
+    assert(old_obj.object_ref() == key);
+    assert(req.key() == key);
+    assert(unmarshallable_object(req.obj, cluster.installed_types));
+    assert(update_request_admission_check(cluster.installed_types, req, s.api_server) is None);
+
+    // Validity: only the finalizers change, and they shrink.
+    let updated_obj = updated_object(req, old_obj);
+    assert(updated_obj.metadata.finalizers == Some(filtered));
+    assert(updated_obj != old_obj) by {
+        assert(old_obj.metadata.finalizers_as_set().contains(vd_finalizer()));
+        assert(!updated_obj.metadata.finalizers_as_set().contains(vd_finalizer()));
+    }
+    let updated_obj_with_new_rv = updated_obj.with_resource_version(s.api_server.resource_version_counter);
+    assert(updated_obj_with_new_rv.metadata.owner_references == old_obj.metadata.owner_references);
+    assert(metadata_validity_check(updated_obj_with_new_rv) is None);
+    assert(metadata_transition_validity_check(updated_obj_with_new_rv, old_obj) is None);
+    assert(updated_obj_with_new_rv.spec == old_obj.spec && updated_obj_with_new_rv.status == old_obj.status);
+    assert(object_validity_check(updated_obj_with_new_rv, cluster.installed_types) is None);
+    assert(object_transition_validity_check(updated_obj_with_new_rv, old_obj, cluster.installed_types) is None);
+
+    // So vd in etcd either no longer carries vd_finalizer(), or is deleted.
+    if s_prime.resources().contains_key(key) {
+        assert(s_prime.resources()[key] == updated_obj_with_new_rv);
+    }
+}
+
+uninterp spec fn make_vd() -> VDeploymentView;
+
+}
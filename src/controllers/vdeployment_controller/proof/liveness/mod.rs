@@ -3,4 +3,5 @@ pub mod rolling_update;
 pub mod spec;
 pub mod terminate;
 pub mod resource_match;
-pub mod api_actions;
\ No newline at end of file
+pub mod api_actions;
+pub mod finalize;
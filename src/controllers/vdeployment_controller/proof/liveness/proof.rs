@@ -115,6 +115,9 @@ ensures
     }
     entails_trans(spec, assumption_and_invariants_of_all_phases(vd, cluster, controller_id), always(lift_state(Cluster::crash_disabled(controller_id))));
     entails_trans(spec, assumption_and_invariants_of_all_phases(vd, cluster, controller_id), always(lift_state(Cluster::req_drop_disabled())));
+    entails_trans(spec, assumption_and_invariants_of_all_phases(vd, cluster, controller_id), always(lift_state(Cluster::resp_drop_disabled())));
+    entails_trans(spec, assumption_and_invariants_of_all_phases(vd, cluster, controller_id), always(lift_state(Cluster::req_dup_disabled())));
+    entails_trans(spec, assumption_and_invariants_of_all_phases(vd, cluster, controller_id), always(lift_state(Cluster::node_failure_disabled())));
     entails_trans(spec, assumption_and_invariants_of_all_phases(vd, cluster, controller_id), always(lift_state(Cluster::pod_monkey_disabled())));
     entails_trans(spec, assumption_and_invariants_of_all_phases(vd, cluster, controller_id), always(lift_state(Cluster::every_in_flight_msg_has_unique_id())));
     entails_trans(spec, assumption_and_invariants_of_all_phases(vd, cluster, controller_id), always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator())));
@@ -148,6 +151,9 @@ ensures
         lift_state(cluster_invariants_since_reconciliation(cluster, vd, controller_id)),
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::resp_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
         lift_state(Cluster::every_in_flight_msg_has_unique_id()),
         lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()),
@@ -786,6 +786,27 @@ ensures
         implies managed_vrs_list.contains(vds_prime.old_vrs_list[i]) by {
         assert(old_vrs_list.contains(vds_prime.old_vrs_list[i])); // trigger
     }
+
+    // the spec hash guarding the scale request is the one of the new vrs in etcd
+    if new_vrs is Some {
+        let nv = new_vrs->0;
+        VReplicaSetView::marshal_preserves_metadata();
+        let i = choose |i: int| 0 <= i < vrs_list.len() && vrs_list[i] == nv;
+        let listed = resp_objs.filter(|o: DynamicObjectView| o.metadata.uid == nv.metadata.uid);
+        assert(resp_objs.contains(resp_objs[i])); // trigger
+        assert(VReplicaSetView::unmarshal(resp_objs[i])->Ok_0 == nv);
+        assert(listed.contains(resp_objs[i]));
+        assert(listed.first() == resp_objs[i]) by {
+            assert(listed.contains(listed.first()));
+            seq_filter_contains_implies_seq_contains(resp_objs, |o: DynamicObjectView| o.metadata.uid == nv.metadata.uid, listed.first());
+            let j = choose |j: int| 0 <= j < resp_objs.len() && resp_objs[j] == listed.first();
+            // uids of the listed objects are unique
+            assert(resp_objs[j].metadata.uid == resp_objs[i].metadata.uid);
+            assert(j == i);
+        }
+        assert(s.resources()[nv.object_ref()].spec == resp_objs[i].spec);
+        assert(listed_spec_hash(resp_objs, nv) == Some(spec_hash(s.resources()[nv.object_ref()].spec)));
+    }
 }
 
 pub proof fn lemma_from_after_receive_list_vrs_resp_to_after_ensure_new_vrs(
@@ -874,6 +895,9 @@ ensures
                         s, s_prime, vd, cluster, controller_id, msg
                     );
                 }
+                lemma_api_request_other_than_pending_req_msg_maintains_listed_spec_of_objects_owned_by_vd(
+                    s, s_prime, vd, cluster, controller_id, msg, resp_objs
+                );
             },
             Step::ControllerStep(input) => {
                 if input.0 == controller_id && input.1 == Some(resp_msg) && input.2 == Some(vd.object_ref()) {
@@ -976,6 +1000,9 @@ ensures
                         s, s_prime, vd, cluster, controller_id, msg
                     );
                 }
+                lemma_api_request_other_than_pending_req_msg_maintains_listed_spec_of_objects_owned_by_vd(
+                    s, s_prime, vd, cluster, controller_id, msg, resp_objs
+                );
             },
             Step::ControllerStep(input) => {
                 if input.0 == controller_id && input.1 == Some(resp_msg) && input.2 == Some(vd.object_ref()) {
@@ -1263,6 +1290,9 @@ ensures
                         s, s_prime, vd, cluster, controller_id, msg
                     );
                 }
+                lemma_api_request_other_than_pending_req_msg_maintains_listed_spec_of_objects_owned_by_vd(
+                    s, s_prime, vd, cluster, controller_id, msg, resp_objs
+                );
             },
             Step::ControllerStep(input) => {
                 if input.0 == controller_id && input.1 == Some(resp_msg) && input.2 == Some(vd.object_ref()) {
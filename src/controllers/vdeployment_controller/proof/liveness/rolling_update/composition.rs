@@ -229,6 +229,9 @@ ensures
                             s, s_prime, vd, cluster, controller_id, msg
                         );
                     }
+                    lemma_api_request_other_than_pending_req_msg_maintains_listed_spec_of_objects_owned_by_vd(
+                        s, s_prime, vd, cluster, controller_id, msg, resp_objs
+                    );
                 }
             }
         } else {
@@ -67,6 +67,12 @@ pub open spec fn ru_resp_msg_is_ok_list_resp_containing_matched_vrs(
         &&& obj.metadata.name is Some
         &&& obj.metadata.uid is Some
     }
+    &&& forall |i: int, j: int| #![trigger resp_objs[i], resp_objs[j]] 0 <= i < resp_objs.len() && 0 <= j < resp_objs.len() && i != j
+        ==> resp_objs[i].metadata.uid != resp_objs[j].metadata.uid
+    &&& forall |obj: DynamicObjectView| #[trigger] resp_objs.contains(obj) && valid_owned_vrs(VReplicaSetView::unmarshal(obj)->Ok_0, vd) ==> {
+        &&& s.resources().contains_key(obj.object_ref())
+        &&& s.resources()[obj.object_ref()].spec == obj.spec
+    }
     &&& forall |vrs: VReplicaSetView| #[trigger] managed_vrs_list.contains(vrs) ==> {
         let key = vrs.object_ref();
         let etcd_obj = s.resources()[key];
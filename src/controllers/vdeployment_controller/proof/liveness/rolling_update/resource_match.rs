@@ -306,6 +306,9 @@ ensures
                         s, s_prime, vd, cluster, controller_id, msg
                     );
                 }
+                lemma_api_request_other_than_pending_req_msg_maintains_listed_spec_of_objects_owned_by_vd(
+                    s, s_prime, vd, cluster, controller_id, msg, resp_objs
+                );
                 assert(filter_obj_keys_managed_by_vd(vd, s) == filter_obj_keys_managed_by_vd(vd, s_prime)) by {
                     lemma_api_request_other_than_pending_req_msg_maintains_objects_owned_by_vd(
                         s, s_prime, vd, cluster, controller_id, msg, None // just need the first post condition
@@ -123,6 +123,9 @@ pub open spec fn spec_before_phase_n(n: nat, vd: VDeploymentView, cluster: Clust
 pub open spec fn invariants_since_phase_i(controller_id: int, vd: VDeploymentView) -> TempPred<ClusterState> {
     always(lift_state(Cluster::crash_disabled(controller_id)))
     .and(always(lift_state(Cluster::req_drop_disabled())))
+    .and(always(lift_state(Cluster::resp_drop_disabled())))
+    .and(always(lift_state(Cluster::req_dup_disabled())))
+    .and(always(lift_state(Cluster::node_failure_disabled())))
     .and(always(lift_state(Cluster::pod_monkey_disabled())))
 }
 
@@ -132,6 +135,9 @@ pub proof fn invariants_since_phase_i_is_stable(controller_id: int, vd: VDeploym
     stable_and_always_n!(
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::resp_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::pod_monkey_disabled())
     );
 }
@@ -236,6 +242,9 @@ pub proof fn spec_entails_always_cluster_invariants_since_reconciliation_holds_p
         lift_state(cluster_invariants_since_reconciliation(cluster, vd, controller_id)),
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::resp_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
         lift_state(Cluster::every_in_flight_msg_has_unique_id()),
         lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()),
@@ -473,12 +482,18 @@ pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(provided_
     if i == 1 {
         cluster.lemma_true_leads_to_crash_always_disabled(spec, controller_id);
         cluster.lemma_true_leads_to_req_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_resp_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_req_dup_always_disabled(spec);
+        cluster.lemma_true_leads_to_node_failure_always_disabled(spec);
         cluster.lemma_true_leads_to_pod_monkey_always_disabled(spec);
         leads_to_always_and_n!(
             spec,
             true_pred(),
             lift_state(Cluster::crash_disabled(controller_id)),
             lift_state(Cluster::req_drop_disabled()),
+            lift_state(Cluster::resp_drop_disabled()),
+            lift_state(Cluster::req_dup_disabled()),
+            lift_state(Cluster::node_failure_disabled()),
             lift_state(Cluster::pod_monkey_disabled())
         );
     } else {
@@ -635,6 +650,9 @@ pub open spec fn next_with_wf(cluster: Cluster, controller_id: int) -> TempPred<
     .and(tla_forall(|input| cluster.external_next().weak_fairness((controller_id, input))))
     .and(cluster.disable_crash().weak_fairness(controller_id))
     .and(cluster.disable_req_drop().weak_fairness(()))
+    .and(cluster.disable_resp_drop().weak_fairness(()))
+    .and(cluster.disable_req_dup().weak_fairness(()))
+    .and(cluster.disable_node_failure().weak_fairness(()))
     .and(cluster.disable_pod_monkey().weak_fairness(()))
 }
 
@@ -652,6 +670,9 @@ pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
         always_p_is_stable::<ClusterState>(split_always);
     }
     Cluster::action_weak_fairness_is_stable(cluster.disable_req_drop());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_resp_drop());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_req_dup());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_node_failure());
     Cluster::action_weak_fairness_is_stable(cluster.disable_pod_monkey());
     stable_and_n!(
         always(lift_action(cluster.next())),
@@ -662,6 +683,9 @@ pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
         tla_forall(|input| cluster.external_next().weak_fairness((controller_id, input))),
         cluster.disable_crash().weak_fairness(controller_id),
         cluster.disable_req_drop().weak_fairness(()),
+        cluster.disable_resp_drop().weak_fairness(()),
+        cluster.disable_req_dup().weak_fairness(()),
+        cluster.disable_node_failure().weak_fairness(()),
         cluster.disable_pod_monkey().weak_fairness(())
     );
 }
@@ -326,6 +326,8 @@ ensures
     // 0 ~> Done | Error ~> idle
     assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))] at_step_or![(AfterScaleDownOldVRS, old_vrs_list_len(zero))](s)
         ==> at_step_or![Error, Done]((cluster.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0));
+    assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))] at_step_or![(AfterScaleDownOldVRS, old_vrs_list_len(zero))](s)
+        ==> at_step_or![Error, Done]((cluster.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0));
     cluster.lemma_from_some_state_to_arbitrary_next_state_to_reconcile_idle(
         spec, controller_id, vd.object_ref(),
         at_step_or![(AfterScaleDownOldVRS, old_vrs_list_len(zero))],
@@ -357,6 +359,10 @@ ensures
             lemma_from_pending_req_in_flight_or_resp_in_flight_at_step_to_at_step_and_pred(
                 spec, vd, controller_id, AfterScaleDownOldVRS, old_vrs_list_len(n)
             );
+            assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))] at_step_or![(AfterScaleDownOldVRS, old_vrs_list_len(n))](s)
+                ==> scale_down_old_vrs_rank_n((n - 1) as nat)((cluster.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0));
+            assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))] at_step_or![(AfterScaleDownOldVRS, old_vrs_list_len(n))](s)
+                ==> scale_down_old_vrs_rank_n((n - 1) as nat)((cluster.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0));
             cluster.lemma_from_some_state_to_arbitrary_next_state(
                 spec, controller_id, vd.object_ref(),
                 at_step_or![(AfterScaleDownOldVRS, old_vrs_list_len(n))],
@@ -428,6 +434,9 @@ ensures
     assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))]
         at_step_or![AfterEnsureNewVRS](s) ==> at_step_or![AfterScaleDownOldVRS, Error, Done]
                                              ((cluster.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0));
+    assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))]
+        at_step_or![AfterEnsureNewVRS](s) ==> at_step_or![AfterScaleDownOldVRS, Error, Done]
+                                             ((cluster.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0));
     // AfterEnsureNewVRS is similar to init on no pending req/resp is needed for the transition to next step
     // let me borrow this lemma for init here
     cluster.lemma_from_init_state_to_next_state_to_reconcile_idle(
@@ -445,6 +454,9 @@ ensures
     assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))] 
         at_step_or![AfterScaleNewVRS](s) ==> at_step_or![AfterEnsureNewVRS, Error]
                                              ((cluster.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0));
+    assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))] 
+        at_step_or![AfterScaleNewVRS](s) ==> at_step_or![AfterEnsureNewVRS, Error]
+                                             ((cluster.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0));
     cluster.lemma_from_some_state_to_arbitrary_next_state_to_reconcile_idle(
         spec, controller_id, vd.object_ref(),
         at_step_or![AfterScaleNewVRS],
@@ -454,12 +466,17 @@ ensures
     assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))]
         at_step_or![AfterCreateNewVRS](s) ==> at_step_or![AfterEnsureNewVRS, Error]
                                               ((cluster.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0));
+    assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))]
+        at_step_or![AfterCreateNewVRS](s) ==> at_step_or![AfterEnsureNewVRS, Error]
+                                              ((cluster.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0));
     cluster.lemma_from_some_state_to_arbitrary_next_state_to_reconcile_idle(
         spec, controller_id, vd.object_ref(),
         at_step_or![AfterCreateNewVRS],
         at_step_or![AfterEnsureNewVRS, Error]
     );
     // 6, AfterListVRS ~> idle
+    // reconcile_delete differs from reconcile_core at AfterListVRS, where it goes to AfterEnsureNewVRS or Error,
+    // and at AfterScaleDownOldVRS, where it ignores the response
     or_leads_to_combine_and_equality!(spec,
         lift_state(lift_local(controller_id, vd, at_step_or![AfterCreateNewVRS, AfterScaleNewVRS, AfterEnsureNewVRS, Error])),
         lift_state(lift_local(controller_id, vd, at_step_or![AfterCreateNewVRS])),
@@ -470,12 +487,17 @@ ensures
     assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))]
         at_step_or![AfterListVRS](s) ==> at_step_or![AfterCreateNewVRS, AfterScaleNewVRS, AfterEnsureNewVRS, Error]
                                          ((cluster.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0));
+    assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))]
+        at_step_or![AfterListVRS](s) ==> at_step_or![AfterCreateNewVRS, AfterScaleNewVRS, AfterEnsureNewVRS, Error]
+                                         ((cluster.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0));
     cluster.lemma_from_some_state_to_arbitrary_next_state_to_reconcile_idle(
         spec, controller_id, vd.object_ref(),
         at_step_or![AfterListVRS],
         at_step_or![AfterCreateNewVRS, AfterScaleNewVRS, AfterEnsureNewVRS, Error]
     );
     // 7, Init ~> idle
+    assert(forall |input_cr, resp_o, s| #![trigger dummy((input_cr, resp_o, s))]
+        at_step_or![Init](s) ==> at_step_or![AfterListVRS]((cluster.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0));
     cluster.lemma_from_init_state_to_next_state_to_reconcile_idle(
         spec, controller_id, vd.object_ref(),
         at_step_or![Init],
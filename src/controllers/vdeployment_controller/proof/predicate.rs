@@ -63,6 +63,11 @@ pub open spec fn req_msg_is_list_vrs_req(
     &&& req->ListRequest_0 == ListRequest {
         kind: VReplicaSetView::kind(),
         namespace: vd.metadata.namespace.unwrap(),
+        label_selector: None,
+        field_selector: None,
+        read_mode: ReadMode::Quorum,
+        limit: None,
+        continue_token: None,
     }
 }
 
@@ -146,6 +151,14 @@ pub open spec fn resp_msg_is_ok_list_resp_containing_matched_vrs(
         &&& obj.metadata.name is Some
         &&& obj.metadata.uid is Some
     }
+    // listed_spec_hash picks the listed object by uid
+    &&& forall |i: int, j: int| #![trigger resp_objs[i], resp_objs[j]] 0 <= i < resp_objs.len() && 0 <= j < resp_objs.len() && i != j
+        ==> resp_objs[i].metadata.uid != resp_objs[j].metadata.uid
+    // the raw spec of a managed object, which the SpecHashMatch guard of scale_new_vrs hashes, is the one in etcd
+    &&& forall |obj: DynamicObjectView| #[trigger] resp_objs.contains(obj) && valid_owned_vrs(VReplicaSetView::unmarshal(obj)->Ok_0, vd) ==> {
+        &&& s.resources().contains_key(obj.object_ref())
+        &&& s.resources()[obj.object_ref()].spec == obj.spec
+    }
     &&& forall |vrs: VReplicaSetView| #[trigger] managed_vrs_list.contains(vrs) ==> {
         let key = vrs.object_ref();
         let etcd_obj = s.resources()[key];
@@ -375,6 +388,8 @@ pub open spec fn req_msg_is_scale_new_vrs_req(
             replicas: Some(req_vrs_replicas),
             ..etcd_vrs.spec
         }
+        // the guard hashes the spec still in etcd, so the update goes through
+        &&& req.predicate == GetThenPredicate::SpecHashMatch(spec_hash(etcd_obj.spec))
         // owned by vd
         &&& req_vrs.metadata.owner_references is Some
         &&& req_vrs.metadata.owner_references->0.filter(controller_owner_filter()) == seq![vd.controller_owner_ref()]
@@ -680,6 +695,9 @@ pub open spec fn cluster_invariants_since_reconciliation(cluster: Cluster, vd: V
     and!(
         Cluster::crash_disabled(controller_id),
         Cluster::req_drop_disabled(),
+        Cluster::resp_drop_disabled(),
+        Cluster::req_dup_disabled(),
+        Cluster::node_failure_disabled(),
         Cluster::pod_monkey_disabled(),
         Cluster::every_in_flight_msg_has_unique_id(),
         Cluster::every_in_flight_msg_has_lower_id_than_allocator(),
@@ -3,7 +3,7 @@ use crate::kubernetes_cluster::spec::{cluster::*, message::*, controller::types:
 use verus_temporal_logic::defs::*;
 use crate::vreplicaset_controller::trusted::{spec_types::*, liveness_theorem as vrs_liveness};
 use crate::vdeployment_controller::{
-    model::reconciler::VDeploymentReconcileState,
+    model::reconciler::{VDeploymentReconcileState, vd_finalizer},
     trusted::{spec_types::*, step::VDeploymentReconcileStepView::*},
     proof::{predicate::*, liveness::rolling_update::predicate::*},
 };
@@ -25,6 +25,36 @@ pub open spec fn vd_eventually_stable_reconciliation_per_cr(vd: VDeploymentView,
     always(lift_state(desired_state_is(vd))).leads_to(tla_exists(|new_vrs_key: ObjectRef| always(lift_state(inductive_current_state_matches(vd, controller_id, new_vrs_key)))))
 }
 
+// Deletion liveness: once vd is being deleted, the vd controller eventually scales down the vrs owned by vd
+// and removes vd_finalizer() from vd, so that the API server (and then the garbage collector) can clean up.
+pub open spec fn vd_eventually_finalized_per_cr(vd: VDeploymentView, controller_id: int) -> TempPred<ClusterState> {
+    lift_state(Cluster::deletion_is_requested(vd, vd_finalizer()))
+        .and(always(lift_state(vd_deletion_is_not_interfered(vd, controller_id))))
+        .leads_to(lift_state(Cluster::finalizer_is_removed(vd, vd_finalizer())))
+}
+
+// The environment assumption of the deletion liveness, playing the role of desired_state_is in ESR:
+// no one other than the vd controller writes to vd, so that the finalizer removal sent by the vd controller
+// does not keep failing on a conflict. Note that this does not restrict the requests to the vrs owned by vd.
+pub open spec fn vd_deletion_is_not_interfered(vd: VDeploymentView, controller_id: int) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        forall |msg| {
+            &&& #[trigger] s.in_flight().contains(msg)
+            &&& msg.content is APIRequest
+            &&& !msg.src.is_controller_id(controller_id)
+        } ==> match msg.content->APIRequest_0 {
+            APIRequest::DeleteRequest(req) => req.key() != vd.object_ref(),
+            APIRequest::UpdateRequest(req) => req.key() != vd.object_ref(),
+            APIRequest::UpdateStatusRequest(req) => req.key() != vd.object_ref(),
+            APIRequest::GetThenDeleteRequest(req) => req.key() != vd.object_ref(),
+            APIRequest::GetThenUpdateRequest(req) => req.key() != vd.object_ref(),
+            APIRequest::GetThenUpdateStatusRequest(req) => req.key() != vd.object_ref(),
+            APIRequest::PatchRequest(req) => req.key() != vd.object_ref(),
+            _ => true,
+        }
+    }
+}
+
 pub open spec fn desired_state_is(vd: VDeploymentView) -> StatePred<ClusterState> {
     |s: ClusterState| {
         &&& Cluster::desired_state_is(vd)(s)
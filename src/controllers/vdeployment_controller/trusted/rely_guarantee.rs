@@ -46,6 +46,28 @@ pub open spec fn vd_rely_update_req(req: UpdateRequest) -> StatePred<ClusterStat
     }
 }
 
+// Patch requests are handled as updates of the patched object, so they are restricted
+// in the same way as update requests.
+pub open spec fn vd_rely_patch_req(req: PatchRequest) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        req.obj.kind == VReplicaSetView::kind() ==>
+            req.obj.metadata.resource_version is Some
+            && !{
+                let etcd_obj = s.resources()[req.key()];
+                let owner_references = etcd_obj.metadata.owner_references->0;
+                &&& s.resources().contains_key(req.key())
+                &&& etcd_obj.metadata.resource_version is Some
+                &&& etcd_obj.metadata.resource_version == req.obj.metadata.resource_version
+                &&& etcd_obj.metadata.owner_references is Some
+                &&& exists |vd: VDeploymentView|
+                    #[trigger] owner_references.contains(vd.controller_owner_ref())
+            }
+            && (req.obj.metadata.owner_references is Some ==>
+                    forall |vd: VDeploymentView|
+                        ! #[trigger] req.obj.metadata.owner_references->0.contains(vd.controller_owner_ref()))
+    }
+}
+
 // Update requests to vrs must carry a resource version on them
 // and cannot 1) update vrs owned by a VDeployment
 //         or 2) update vrs to become owned
@@ -130,6 +152,7 @@ pub open spec fn vd_rely(other_id: int) -> StatePred<ClusterState> {
             APIRequest::UpdateStatusRequest(req) => vd_rely_update_status_req(req)(s),
             APIRequest::DeleteRequest(req) => vd_rely_delete_req(req)(s),
             APIRequest::GetThenDeleteRequest(req) => vd_rely_get_then_delete_req(req)(s),
+            APIRequest::PatchRequest(req) => vd_rely_patch_req(req)(s),
             _ => true,
         }
     }
@@ -157,6 +180,13 @@ pub open spec fn vd_guarantee_get_then_update_req(req: GetThenUpdateRequest) ->
     }
 }
 
+// VD only updates VDeployments, to add or remove its finalizer.
+pub open spec fn vd_guarantee_update_req(req: UpdateRequest) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        req.obj.kind == VDeploymentView::kind()
+    }
+}
+
 // TODO: support delete requests (not yet implemented in the controller)
 // This should be the same as vrs_guarantee_delete_req
 // plus vd will not delete new vrs (has same template as vd)
@@ -176,6 +206,7 @@ pub open spec fn vd_guarantee(controller_id: int) -> StatePred<ClusterState> {
         } ==> match msg.content->APIRequest_0 {
             APIRequest::ListRequest(_) => true,
             APIRequest::CreateRequest(req) => vd_guarantee_create_req(req)(s),
+            APIRequest::UpdateRequest(req) => vd_guarantee_update_req(req)(s),
             APIRequest::GetThenUpdateRequest(req) => vd_guarantee_get_then_update_req(req)(s),
             APIRequest::GetThenDeleteRequest(req) => vd_guarantee_get_then_delete_req(req)(s),
             _ => false, // vd doesn't send other requests
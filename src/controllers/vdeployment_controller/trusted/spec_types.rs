@@ -107,6 +107,10 @@ implement_resource_view_trait!(VDeploymentView, VDeploymentSpecView, VDeployment
 impl CustomResourceView for VDeploymentView {
     proof fn kind_is_custom_resource() {}
 
+    open spec fn scope() -> ResourceScope {
+        ResourceScope::Namespaced
+    }
+
     open spec fn spec_status_validation(obj_spec: Self::Spec, obj_status: Self::Status) -> bool {
         VDeploymentView {
             metadata: arbitrary(),
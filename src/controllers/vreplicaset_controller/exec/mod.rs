@@ -1,3 +1,4 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
-pub mod reconciler;
\ No newline at end of file
+pub mod reconciler;
+pub mod shim_hooks;
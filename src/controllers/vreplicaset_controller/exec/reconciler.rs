@@ -60,6 +60,14 @@ impl Reconciler for VReplicaSetReconciler {
     fn reconcile_error(state: &Self::S) -> bool {
         reconcile_error(state)
     }
+
+    fn finalizer() -> Option<String> {
+        None
+    }
+
+    fn reconcile_delete(vrs: &Self::K, resp_o: Option<Response<Self::EResp>>, state: Self::S) -> (Self::S, Option<Request<Self::EReq>>) {
+        reconcile_core(vrs, resp_o, state)
+    }
 }
 
 pub fn reconcile_init_state() -> (state: VReplicaSetReconcileState)
@@ -106,6 +114,11 @@ pub fn reconcile_core(vrs: &VReplicaSet, resp_o: Option<Response<VoidEResp>>, st
                 let req = KubeAPIRequest::ListRequest(KubeListRequest {
                     api_resource: Pod::api_resource(),
                     namespace: namespace,
+                    label_selector: Some(vrs.spec().selector()),
+                    field_selector: None,
+                    read_mode: KubeReadMode::Quorum,
+                    limit: None,
+                    continue_token: None,
                 });
                 let state_prime = VReplicaSetReconcileState {
                     reconcile_step: VReplicaSetReconcileStep::AfterListPods,
@@ -157,6 +170,7 @@ pub fn reconcile_core(vrs: &VReplicaSet, resp_o: Option<Response<VoidEResp>>, st
                     name: pod_name_or_none.unwrap(),
                     namespace: namespace,
                     owner_ref: vrs.controller_owner_ref(),
+                    predicate: KubeGetThenPredicate::OwnerRef,
                 });
                 let state_prime = VReplicaSetReconcileState {
                     reconcile_step: VReplicaSetReconcileStep::AfterDeletePod(diff - 1),
@@ -210,6 +224,7 @@ pub fn reconcile_core(vrs: &VReplicaSet, resp_o: Option<Response<VoidEResp>>, st
                     name: pod_name_or_none.unwrap(),
                     namespace: namespace,
                     owner_ref: vrs.controller_owner_ref(),
+                    predicate: KubeGetThenPredicate::OwnerRef,
                 });
                 let state_prime = VReplicaSetReconcileState {
                     reconcile_step: VReplicaSetReconcileStep::AfterDeletePod(diff - 1),
@@ -457,6 +472,9 @@ fn update_vrs_replicas(state: VReplicaSetReconcileState, vrs: &VReplicaSet) -> (
     if vrs.metadata().owner_references().is_some() && filter_by_controller_owner(vrs.metadata().owner_references().unwrap()).len() == 1 {
         let mut new_status = VReplicaSetStatus::default();
         new_status.set_replicas(vrs.spec().replicas().unwrap_or(1));
+        if let Some(generation) = vrs.metadata().generation() {
+            new_status.set_observed_generation(generation);
+        }
         let mut new_vrs = vrs.clone();
         new_vrs.set_status(new_status);
         let state_prime = VReplicaSetReconcileState {
@@ -470,6 +488,7 @@ fn update_vrs_replicas(state: VReplicaSetReconcileState, vrs: &VReplicaSet) -> (
             name: vrs.metadata().name().unwrap(),
             namespace: vrs.metadata().namespace().unwrap(),
             owner_ref: owner_ref,
+            predicate: KubeGetThenPredicate::OwnerRef,
             obj: new_vrs.marshal(),
         });
         return (state_prime, Some(Request::KRequest(req)));
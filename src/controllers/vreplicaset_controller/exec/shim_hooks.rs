@@ -0,0 +1,26 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::events::{successful_event, NotableEvent, ReconcileEvents};
+use crate::shim_layer::trace::ReconcileStepName;
+use crate::vreplicaset_controller::exec::reconciler::{VReplicaSetReconcileState, VReplicaSetReconciler};
+
+// The hooks that the shim layer calls when running the VReplicaSet controller.
+// They are not verified and stay out of the exec reconciler, so that the verified code does not depend on the shim layer.
+
+// The name of a reconcile step is the Debug form of the step.
+impl ReconcileStepName for VReplicaSetReconcileState {
+    fn reconcile_step_name(&self) -> String {
+        format!("{:?}", self.reconcile_step)
+    }
+}
+
+// The pods created and deleted by the controller are recorded as events of the VReplicaSet.
+impl ReconcileEvents for VReplicaSetReconciler {
+    fn notable_event(verb: &str, kind: &str, name: &str) -> Option<NotableEvent> {
+        match (verb, kind) {
+            ("Create", "Pod") => successful_event("Create", "Created", kind, name),
+            ("GetThenDelete", "Pod") => successful_event("Delete", "Deleted", kind, name),
+            _ => None,
+        }
+    }
+}
@@ -30,6 +30,16 @@ impl Reconciler<VReplicaSetReconcileState, VReplicaSetView, VoidEReqView, VoidER
     open spec fn reconcile_error(state: VReplicaSetReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    // The objects created by the VReplicaSet are owned by it and removed by the garbage collector,
+    // so there is nothing to clean up and reconcile_delete is never invoked.
+    open spec fn finalizer() -> Option<StringView> {
+        None
+    }
+
+    open spec fn reconcile_delete(vrs: VReplicaSetView, resp_o: Option<ResponseView<VoidERespView>>, state: VReplicaSetReconcileState) -> (VReplicaSetReconcileState, Option<RequestView<VoidEReqView>>) {
+        reconcile_core(vrs, resp_o, state)
+    }
 }
 
 pub open spec fn reconcile_init_state() -> VReplicaSetReconcileState {
@@ -67,6 +77,11 @@ pub open spec fn reconcile_core(vrs: VReplicaSetView, resp_o: Option<ResponseVie
                 let req = APIRequest::ListRequest(ListRequest {
                     kind: PodView::kind(),
                     namespace: namespace,
+                    label_selector: Some(vrs.spec.selector),
+                    field_selector: None,
+                    read_mode: ReadMode::Quorum,
+                    limit: None,
+                    continue_token: None,
                 });
                 let state_prime = VReplicaSetReconcileState {
                     reconcile_step: VReplicaSetRecStepView::AfterListPods,
@@ -118,6 +133,7 @@ pub open spec fn reconcile_core(vrs: VReplicaSetView, resp_o: Option<ResponseVie
                                         namespace: namespace,
                                     },
                                     owner_ref: vrs.controller_owner_ref(),
+                                    predicate: GetThenPredicate::OwnerRef,
                                 });
                                 let state_prime = VReplicaSetReconcileState {
                                     reconcile_step: VReplicaSetRecStepView::AfterDeletePod((diff - 1) as nat),
@@ -173,6 +189,7 @@ pub open spec fn reconcile_core(vrs: VReplicaSetView, resp_o: Option<ResponseVie
                                 namespace: namespace,
                             },
                             owner_ref: vrs.controller_owner_ref(),
+                            predicate: GetThenPredicate::OwnerRef,
                         });
                         let state_prime = VReplicaSetReconcileState {
                             reconcile_step: VReplicaSetRecStepView::AfterDeletePod((diff - 1) as nat),
@@ -274,6 +291,7 @@ pub open spec fn update_vrs_replicas(
         let vrs_with_new_status = VReplicaSetView {
             status: Some(VReplicaSetStatusView {
                 replicas: vrs.spec.replicas.unwrap_or(1),
+                observed_generation: vrs.metadata.generation,
             }),
             ..vrs
         };
@@ -285,6 +303,7 @@ pub open spec fn update_vrs_replicas(
             name: vrs.metadata.name.unwrap(),
             namespace: vrs.metadata.namespace.unwrap(),
             owner_ref: vrs.metadata.owner_references.unwrap().filter(controller_owner_filter())[0],
+            predicate: GetThenPredicate::OwnerRef,
             obj: vrs_with_new_status.marshal(),
         });
         (state_prime, Some(RequestView::KRequest(req)))
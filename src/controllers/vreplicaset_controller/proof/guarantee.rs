@@ -50,10 +50,11 @@ pub open spec fn local_pods_are_bound_to_vrs_with_key(controller_id: int, cr_key
         &&& s.ongoing_reconciles(controller_id)[cr_key].pending_req_msg is Some
         &&& req_msg.dst is APIServer
         &&& req_msg.content.is_list_request()
-        &&& req_msg.content.get_list_request() == ListRequest {
-            kind: Kind::PodKind,
-            namespace: cr_key.namespace,
-        }
+        &&& req_msg.content.get_list_request().kind == Kind::PodKind
+        &&& req_msg.content.get_list_request().namespace == cr_key.namespace
+        // The label selector is not pinned: this invariant only needs the listed pods to come from etcd and the namespace,
+        // which holds for any selector. The reconciler sends vrs.spec.selector, which only drops the pods that filter_pods drops.
+        &&& req_msg.content.get_list_request().is_full_quorum_list()
         &&& forall |msg| {
             &&& #[trigger] s.in_flight().contains(msg)
             &&& s.ongoing_reconciles(controller_id)[cr_key].pending_req_msg is Some
@@ -135,6 +136,7 @@ ensures
                                 let selector = |o: DynamicObjectView| {
                                     &&& o.object_ref().namespace == req_msg.content.get_list_request().namespace
                                     &&& o.object_ref().kind == req_msg.content.get_list_request().kind
+                                    &&& req_msg.content.get_list_request().selects(o)
                                 };
                                 let selected_elements = s.resources().values().filter(selector);
                                 assert(resp_objs.contains(resp_objs[i])); // trigger
@@ -65,6 +65,29 @@ pub open spec fn no_other_pending_update_request_interferes_with_vrs_reconcile(
     }
 }
 
+pub open spec fn no_other_pending_patch_request_interferes_with_vrs_reconcile(
+    req: PatchRequest,
+    vrs: VReplicaSetView
+) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        (req.obj.kind == Kind::PodKind
+            && req.key().namespace == vrs.metadata.namespace.unwrap()) ==>
+            req.obj.metadata.resource_version is Some
+            && !{
+                let etcd_obj = s.resources()[req.key()];
+                let owner_references = etcd_obj.metadata.owner_references->0;
+                &&& s.resources().contains_key(req.key())
+                &&& etcd_obj.metadata.namespace == vrs.metadata.namespace
+                &&& etcd_obj.metadata.resource_version is Some
+                &&& etcd_obj.metadata.resource_version == req.obj.metadata.resource_version
+                &&& etcd_obj.metadata.owner_references is Some
+                &&& owner_references.contains(vrs.controller_owner_ref())
+            }
+            && (req.obj.metadata.owner_references is Some ==>
+                        ! req.obj.metadata.owner_references->0.contains(vrs.controller_owner_ref()))
+    }
+}
+
 pub open spec fn no_other_pending_update_status_request_interferes_with_vrs_reconcile(
     req: UpdateStatusRequest,
     vrs: VReplicaSetView
@@ -192,6 +215,7 @@ pub open spec fn no_other_pending_request_interferes_with_vrs_reconcile(
                 APIRequest::DeleteRequest(req) => no_other_pending_delete_request_interferes_with_vrs_reconcile(req, vrs)(s),
                 APIRequest::GetThenDeleteRequest(req) => no_other_pending_get_then_delete_request_interferes_with_vrs_reconcile(req, vrs)(s),
                 APIRequest::GetThenUpdateStatusRequest(req) => no_other_pending_get_then_update_status_request_interferes_with_vrs_reconcile(req, vrs)(s),
+                APIRequest::PatchRequest(req) => no_other_pending_patch_request_interferes_with_vrs_reconcile(req, vrs)(s),
                 _ => true,
             }
         }
@@ -322,16 +346,20 @@ pub open spec fn garbage_collector_does_not_delete_vrs_pods(vrs: VReplicaSetView
             &&& msg.content is APIRequest
         } ==> {
             let req = msg.content.get_delete_request(); 
-            &&& msg.content.is_delete_request()
-            &&& req.preconditions is Some
-            &&& req.preconditions.unwrap().uid is Some
-            &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
-            &&& s.resources().contains_key(req.key) ==> {
-                let obj = s.resources()[req.key];
-                ||| !(obj.metadata.owner_references_contains(vrs.controller_owner_ref())
-                        && obj.kind == Kind::PodKind 
-                        && obj.metadata.namespace == vrs.metadata.namespace)
-                ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+            // Update requests are not restricted: the garbage collector updates pods to drop the owner references
+            // to deleted owners, and the kubelet updates the status of every scheduled pod, including the ones owned by vrs.
+            &&& msg.content.is_delete_request() || msg.content.is_update_request() || msg.content.is_update_status_request()
+            &&& msg.content.is_delete_request() ==> {
+                &&& req.preconditions is Some
+                &&& req.preconditions.unwrap().uid is Some
+                &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
+                &&& s.resources().contains_key(req.key) ==> {
+                    let obj = s.resources()[req.key];
+                    ||| !(obj.metadata.owner_references_contains(vrs.controller_owner_ref())
+                            && obj.kind == Kind::PodKind 
+                            && obj.metadata.namespace == vrs.metadata.namespace)
+                    ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+                }
             }
         }
     }
@@ -385,6 +413,11 @@ pub open spec fn filtered_pods_invariant_matrix(controller_id: int, key: ObjectR
         &&& req_msg.content.get_list_request() == ListRequest {
             kind: PodView::kind(),
             namespace: triggering_cr.metadata.namespace.unwrap(),
+            label_selector: Some(triggering_cr.spec.selector),
+            field_selector: None,
+            read_mode: ReadMode::Quorum,
+            limit: None,
+            continue_token: None,
         }
         &&& forall |msg| {
             let req_msg = s.ongoing_reconciles(controller_id)[triggering_cr.object_ref()].pending_req_msg->0;
@@ -4,7 +4,7 @@
 use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::{
     api_server::state_machine::*, cluster::*, controller::types::*, message::*,
-    builtin_controllers::types::*,
+    builtin_controllers::{garbage_collector::owner_is_solid, types::*},
 };
 use crate::reconciler::spec::io::*;
 use verus_temporal_logic::{defs::*, rules::*};
@@ -70,6 +70,7 @@ pub proof fn lemma_eventually_always_no_other_pending_request_interferes_with_vr
             APIRequest::DeleteRequest(req) => no_other_pending_delete_request_interferes_with_vrs_reconcile(req, vrs)(s),
             APIRequest::GetThenDeleteRequest(req) => no_other_pending_get_then_delete_request_interferes_with_vrs_reconcile(req, vrs)(s),
             APIRequest::GetThenUpdateStatusRequest(req) => no_other_pending_get_then_update_status_request_interferes_with_vrs_reconcile(req, vrs)(s),
+            APIRequest::PatchRequest(req) => no_other_pending_patch_request_interferes_with_vrs_reconcile(req, vrs)(s),
             _ => true,
         }
     };
@@ -514,6 +515,7 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
         spec.entails(always(lift_state(Cluster::crash_disabled(controller_id)))),
         spec.entails(always(lift_state(Cluster::req_drop_disabled()))),
         spec.entails(always(lift_state(Cluster::pod_monkey_disabled()))),
+        spec.entails(always(lift_state(Cluster::node_failure_disabled()))),
         spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()))),
@@ -522,6 +524,7 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
         spec.entails(always(lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()))),
         spec.entails(always(lift_state(Cluster::pending_req_of_key_is_unique_with_unique_id(controller_id, vrs.object_ref())))),
         spec.entails(always(lift_state(no_pending_interfering_update_request()))),
+        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
         forall |other_id| cluster.controller_models.remove(controller_id).contains_key(other_id)
             ==> spec.entails(always(lift_state(#[trigger] vrs_rely(other_id)))),
     ensures spec.entails(true_pred().leads_to(always(lift_state(garbage_collector_does_not_delete_vrs_pods(vrs))))),
@@ -535,16 +538,18 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
         ==>
         ({
             let req = msg.content.get_delete_request();
-            &&& msg.content.is_delete_request()
-            &&& req.preconditions is Some
-            &&& req.preconditions.unwrap().uid is Some
-            &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
-            &&& s.resources().contains_key(req.key) ==> {
-                let obj = s.resources()[req.key];
-                ||| !(obj.metadata.owner_references_contains(vrs.controller_owner_ref())
-                        && obj.kind == Kind::PodKind
-                        && obj.metadata.namespace == vrs.metadata.namespace)
-                ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+            &&& msg.content.is_delete_request() || msg.content.is_update_request() || msg.content.is_update_status_request()
+            &&& msg.content.is_delete_request() ==> {
+                &&& req.preconditions is Some
+                &&& req.preconditions.unwrap().uid is Some
+                &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
+                &&& s.resources().contains_key(req.key) ==> {
+                    let obj = s.resources()[req.key];
+                    ||| !(obj.metadata.owner_references_contains(vrs.controller_owner_ref())
+                            && obj.kind == Kind::PodKind
+                            && obj.metadata.namespace == vrs.metadata.namespace)
+                    ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+                }
             }
         })
     };
@@ -561,6 +566,7 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
         &&& Cluster::crash_disabled(controller_id)(s)
         &&& Cluster::req_drop_disabled()(s)
         &&& Cluster::pod_monkey_disabled()(s)
+        &&& Cluster::node_failure_disabled()(s)
         &&& Cluster::every_in_flight_msg_has_unique_id()(s)
         &&& Cluster::every_in_flight_msg_has_lower_id_than_allocator()(s)
         &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
@@ -569,6 +575,7 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
         &&& cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s)
         &&& Cluster::pending_req_of_key_is_unique_with_unique_id(controller_id, vrs.object_ref())(s)
         &&& no_pending_interfering_update_request()(s)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s_prime)
         &&& forall |other_id| cluster.controller_models.remove(controller_id).contains_key(other_id)
                 ==> #[trigger] vrs_rely(other_id)(s)
         &&& forall |other_id| cluster.controller_models.remove(controller_id).contains_key(other_id)
@@ -582,16 +589,13 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
             let key = msg.content.get_delete_request().key;
             match step {
                 Step::BuiltinControllersStep(..) => {
-                    if (!s.in_flight().contains(msg) && requirements_antecedent(msg, s_prime)) {
+                    if (!s.in_flight().contains(msg) && requirements_antecedent(msg, s_prime) && msg.content.is_delete_request()) {
                         let obj = s.resources()[key];
                         let owner_references = obj.metadata.owner_references->0;
-                        assert(forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len() ==> {
-                            // the referred owner object does not exist in the cluster state
-                            ||| !s.resources().contains_key(owner_reference_to_object_reference(owner_references[i], key.namespace))
-                            // or it exists but has a different uid
-                            // (which means the actual owner was deleted and another object with the same name gets created again)
-                            ||| s.resources()[owner_reference_to_object_reference(owner_references[i], key.namespace)].metadata.uid != Some(owner_references[i].uid)
-                        });
+                        // evict_pod is disabled together with node failures, so the delete request comes from the garbage collector:
+                        // both run_garbage_collector and delete_dependent_in_foreground delete an object only if none of its owners is solid
+                        assert(forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                            ==> !owner_is_solid(cluster.installed_types, s.resources(), owner_references[i], key));
                         if obj.metadata.owner_references_contains(vrs.controller_owner_ref())
                             && obj.kind == Kind::PodKind
                             && obj.metadata.namespace == vrs.metadata.namespace {
@@ -620,6 +624,7 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
         }
     }
 
+    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
     helper_lemmas::vrs_rely_condition_equivalent_to_lifted_vrs_rely_condition_action(
         spec, cluster, controller_id
     );
@@ -632,6 +637,7 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::every_in_flight_msg_has_unique_id()),
         lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()),
         lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
@@ -640,6 +646,7 @@ pub proof fn lemma_eventually_always_garbage_collector_does_not_delete_vrs_pods(
         lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()),
         lift_state(Cluster::pending_req_of_key_is_unique_with_unique_id(controller_id, vrs.object_ref())),
         lift_state(no_pending_interfering_update_request()),
+        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
         lifted_vrs_rely_condition_action(cluster, controller_id)
     );
 
@@ -168,6 +168,7 @@ pub proof fn lemma_list_pods_request_returns_ok_list_resp_containing_matching_po
         let selector = |o: DynamicObjectView| {
             &&& o.object_ref().namespace == vrs.metadata.namespace.unwrap()
             &&& o.object_ref().kind == PodView::kind()
+            &&& msg.content.get_list_request().selects(o)
         };
         let selected_elements = s.resources().values().filter(selector);
         assert(selected_elements.contains(o));
@@ -182,6 +183,7 @@ pub proof fn lemma_list_pods_request_returns_ok_list_resp_containing_matching_po
         let selector = |o: DynamicObjectView| {
             &&& o.object_ref().namespace == vrs.metadata.namespace.unwrap()
             &&& o.object_ref().kind == PodView::kind()
+            &&& msg.content.get_list_request().selects(o)
         };
         let selected_elements = s.resources().values().filter(selector);
         lemma_set_to_seq_contains_all_elements(selected_elements);
@@ -195,6 +197,7 @@ pub proof fn lemma_list_pods_request_returns_ok_list_resp_containing_matching_po
         let selector = |o: DynamicObjectView| {
             &&& o.object_ref().namespace == vrs.metadata.namespace.unwrap()
             &&& o.object_ref().kind == PodView::kind()
+            &&& msg.content.get_list_request().selects(o)
         };
         let selected_elements = s.resources().values().filter(selector);
         lemma_set_to_seq_has_no_duplicates(selected_elements);
@@ -238,6 +241,7 @@ pub proof fn lemma_list_pods_request_returns_ok_list_resp_containing_matching_po
         let selector = |o: DynamicObjectView| {
             &&& o.object_ref().namespace == msg.content.get_list_request().namespace
             &&& o.object_ref().kind == msg.content.get_list_request().kind
+            &&& msg.content.get_list_request().selects(o)
         };
         let selected_elements = s.resources().values().filter(selector);
         lemma_set_to_seq_has_no_duplicates(selected_elements);
@@ -266,6 +270,7 @@ pub proof fn lemma_list_pods_request_returns_ok_list_resp_containing_matching_po
         let selector = |o: DynamicObjectView| {
             &&& o.object_ref().namespace == vrs.metadata.namespace.unwrap()
             &&& o.object_ref().kind == PodView::kind()
+            &&& msg.content.get_list_request().selects(o)
         };
         assert(resp_objs == s.resources().values().filter(selector).to_seq());
         lemma_set_to_seq_has_no_duplicates(s.resources().values().filter(selector));
@@ -133,6 +133,9 @@ pub open spec fn spec_before_phase_n(n: nat, vrs: VReplicaSetView, cluster: Clus
 pub open spec fn invariants_since_phase_i(controller_id: int, vrs: VReplicaSetView) -> TempPred<ClusterState> {
     always(lift_state(Cluster::crash_disabled(controller_id)))
     .and(always(lift_state(Cluster::req_drop_disabled())))
+    .and(always(lift_state(Cluster::resp_drop_disabled())))
+    .and(always(lift_state(Cluster::req_dup_disabled())))
+    .and(always(lift_state(Cluster::node_failure_disabled())))
     .and(always(lift_state(Cluster::pod_monkey_disabled())))
     .and(always(lift_state(vrs_in_schedule_has_spec_and_uid_as(controller_id, vrs))))
 }
@@ -143,6 +146,9 @@ pub proof fn invariants_since_phase_i_is_stable(controller_id: int, vrs: VReplic
     stable_and_always_n!(
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::resp_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
         lift_state(vrs_in_schedule_has_spec_and_uid_as(controller_id, vrs))
     );
@@ -252,6 +258,9 @@ pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(provided_
         spec_entails_tla_forall_apply(spec, |input| cluster.disable_crash().weak_fairness(input), controller_id);
         cluster.lemma_true_leads_to_crash_always_disabled(spec, controller_id);
         cluster.lemma_true_leads_to_req_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_resp_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_req_dup_always_disabled(spec);
+        cluster.lemma_true_leads_to_node_failure_always_disabled(spec);
         cluster.lemma_true_leads_to_pod_monkey_always_disabled(spec);
         lemma_true_leads_to_always_vrs_in_schedule_has_spec_and_uid_as(cluster, spec, controller_id, vrs);
         leads_to_always_and_n!(
@@ -259,6 +268,9 @@ pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(provided_
             true_pred(),
             lift_state(Cluster::crash_disabled(controller_id)),
             lift_state(Cluster::req_drop_disabled()),
+            lift_state(Cluster::resp_drop_disabled()),
+            lift_state(Cluster::req_dup_disabled()),
+            lift_state(Cluster::node_failure_disabled()),
             lift_state(Cluster::pod_monkey_disabled()),
             lift_state(vrs_in_schedule_has_spec_and_uid_as(controller_id, vrs))
         );
@@ -395,6 +407,9 @@ pub open spec fn next_with_wf(cluster: Cluster, controller_id: int) -> TempPred<
     .and(tla_forall(|input| cluster.disable_crash().weak_fairness(input)))
     .and(tla_forall(|input| cluster.external_next().weak_fairness((controller_id, input))))
     .and(cluster.disable_req_drop().weak_fairness(()))
+    .and(cluster.disable_resp_drop().weak_fairness(()))
+    .and(cluster.disable_req_dup().weak_fairness(()))
+    .and(cluster.disable_node_failure().weak_fairness(()))
     .and(cluster.disable_pod_monkey().weak_fairness(()))
 }
 
@@ -409,6 +424,9 @@ pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
     cluster.tla_forall_external_next_weak_fairness_is_stable(controller_id);
     Cluster::tla_forall_action_weak_fairness_is_stable(cluster.disable_crash());
     Cluster::action_weak_fairness_is_stable(cluster.disable_req_drop());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_resp_drop());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_req_dup());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_node_failure());
     Cluster::action_weak_fairness_is_stable(cluster.disable_pod_monkey());
     stable_and_n!(
         always(lift_action(cluster.next())),
@@ -419,6 +437,9 @@ pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
         tla_forall(|input| cluster.disable_crash().weak_fairness(input)),
         tla_forall(|input| cluster.external_next().weak_fairness((controller_id, input))),
         cluster.disable_req_drop().weak_fairness(()),
+        cluster.disable_resp_drop().weak_fairness(()),
+        cluster.disable_req_dup().weak_fairness(()),
+        cluster.disable_node_failure().weak_fairness(()),
         cluster.disable_pod_monkey().weak_fairness(())
     );
 }
@@ -485,6 +506,7 @@ pub open spec fn derived_invariants_since_beginning(vrs: VReplicaSetView, cluste
     ))))
     .and(always(tla_forall(|vrs: VReplicaSetView| lift_state(vrs_reconcile_request_only_interferes_with_itself(controller_id, vrs)))))
     .and(always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VReplicaSetView>(controller_id))))
+    .and(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())))
 }
 
 pub proof fn derived_invariants_since_beginning_is_stable(vrs: VReplicaSetView, cluster: Cluster, controller_id: int)
@@ -530,6 +552,7 @@ pub proof fn derived_invariants_since_beginning_is_stable(vrs: VReplicaSetView,
     )));
     always_p_is_stable(tla_forall(|vrs: VReplicaSetView| lift_state(vrs_reconcile_request_only_interferes_with_itself(controller_id, vrs))));
     always_p_is_stable(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VReplicaSetView>(controller_id)));
+    always_p_is_stable(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
 
     stable_and_n!(
         always(lift_state(Cluster::every_in_flight_msg_has_unique_id())),
@@ -571,7 +594,8 @@ pub proof fn derived_invariants_since_beginning_is_stable(vrs: VReplicaSetView,
             cluster.reconcile_model(controller_id).error
         ))),
         always(tla_forall(|vrs: VReplicaSetView| lift_state(vrs_reconcile_request_only_interferes_with_itself(controller_id, vrs)))),
-        always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VReplicaSetView>(controller_id)))
+        always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VReplicaSetView>(controller_id))),
+        always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))
     );
 }
 
@@ -645,6 +669,7 @@ pub proof fn spec_entails_all_invariants(spec: TempPred<ClusterState>, vrs: VRep
         spec, |vrs: VReplicaSetView| lift_state(vrs_reconcile_request_only_interferes_with_itself(controller_id, vrs))
     );
     cluster.lemma_always_every_in_flight_msg_from_controller_has_kind_as::<VReplicaSetView>(spec, controller_id);
+    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests(spec);
     entails_always_and_n!(
         spec,
         lift_state(Cluster::every_in_flight_msg_has_unique_id()),
@@ -686,7 +711,8 @@ pub proof fn spec_entails_all_invariants(spec: TempPred<ClusterState>, vrs: VRep
             cluster.reconcile_model(controller_id).error
         )),
         tla_forall(|vrs: VReplicaSetView| lift_state(vrs_reconcile_request_only_interferes_with_itself(controller_id, vrs))),
-        lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VReplicaSetView>(controller_id))
+        lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VReplicaSetView>(controller_id)),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())
     );
 }
 }
\ No newline at end of file
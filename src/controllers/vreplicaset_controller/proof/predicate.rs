@@ -174,6 +174,11 @@ pub open spec fn req_msg_is_list_pods_req(
     &&& request->ListRequest_0 == ListRequest {
         kind: PodView::kind(),
         namespace: vrs.metadata.namespace.unwrap(),
+        label_selector: Some(vrs.spec.selector),
+        field_selector: None,
+        read_mode: ReadMode::Quorum,
+        limit: None,
+        continue_token: None,
     }
 }
 
@@ -186,6 +186,13 @@ impl VReplicaSetStatus {
     {
         self.inner.replicas = replicas;
     }
+
+    #[verifier(external_body)]
+    pub fn set_observed_generation(&mut self, observed_generation: i64)
+        ensures final(self)@ == old(self)@.with_observed_generation(observed_generation as int),
+    {
+        self.inner.observed_generation = Some(observed_generation);
+    }
 }
 
 }
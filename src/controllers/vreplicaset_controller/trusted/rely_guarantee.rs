@@ -57,6 +57,29 @@ pub open spec fn vrs_rely_update_req(req: UpdateRequest) -> StatePred<ClusterSta
     }
 }
 
+// Patch requests to pods are restricted in the same way as update requests
+// since a patch is handled as an update of the patched object.
+pub open spec fn vrs_rely_patch_req(req: PatchRequest) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        req.obj.kind == Kind::PodKind ==>
+            req.obj.metadata.resource_version is Some
+            && !{
+                let etcd_obj = s.resources()[req.key()];
+                let owner_references = etcd_obj.metadata.owner_references->0;
+                &&& s.resources().contains_key(req.key())
+                &&& etcd_obj.metadata.resource_version is Some
+                &&& etcd_obj.metadata.resource_version == req.obj.metadata.resource_version
+                &&& etcd_obj.metadata.owner_references is Some
+                &&& exists |vrs: VReplicaSetView|
+                    #[trigger] owner_references.contains(vrs.controller_owner_ref())
+            }
+            && (req.obj.metadata.owner_references is Some ==>
+                    forall |vrs: VReplicaSetView|
+                        ! #[trigger] req.obj.metadata.owner_references->0.contains(vrs.controller_owner_ref()))
+            && !has_vrs_prefix(req.key().name)
+    }
+}
+
 // Update requests to pods must carry a resource version on them
 // and cannot 1) update pods owned by a VReplicaSet
 //         or 2) update pod to become owned
@@ -162,6 +185,7 @@ pub open spec fn vrs_rely(other_id: int) -> StatePred<ClusterState> {
             APIRequest::DeleteRequest(req) => vrs_rely_delete_req(req)(s),
             APIRequest::GetThenDeleteRequest(req) => vrs_rely_get_then_delete_req(req)(s),
             APIRequest::GetThenUpdateStatusRequest(req) => vrs_rely_get_then_update_status_req(req)(s), // treat get-then-update-status as update-status for rely
+            APIRequest::PatchRequest(req) => vrs_rely_patch_req(req)(s),
             _ => true,
         }
     }
@@ -83,6 +83,10 @@ implement_resource_view_trait!(VReplicaSetView, VReplicaSetSpecView, VReplicaSet
 impl CustomResourceView for VReplicaSetView {
     proof fn kind_is_custom_resource() {}
 
+    open spec fn scope() -> ResourceScope {
+        ResourceScope::Namespaced
+    }
+
     open spec fn spec_status_validation(obj_spec: Self::Spec, obj_status: Self::Status) -> bool {
         VReplicaSetView {
             metadata: arbitrary(),
@@ -142,18 +146,28 @@ impl VReplicaSetSpecView {
 
 pub struct VReplicaSetStatusView {
     pub replicas: int,
+    pub observed_generation: Option<int>,
 }
 
 impl VReplicaSetStatusView {
     pub open spec fn default() -> VReplicaSetStatusView {
         VReplicaSetStatusView {
             replicas: 0,
+            observed_generation: None,
         }
     }
 
     pub open spec fn with_replicas(self, replicas: int) -> VReplicaSetStatusView {
         VReplicaSetStatusView {
             replicas: replicas,
+            ..self
+        }
+    }
+
+    pub open spec fn with_observed_generation(self, observed_generation: int) -> VReplicaSetStatusView {
+        VReplicaSetStatusView {
+            observed_generation: Some(observed_generation),
+            ..self
         }
     }
 }
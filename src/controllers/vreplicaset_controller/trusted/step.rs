@@ -2,6 +2,7 @@ use vstd::prelude::*;
 
 verus! {
 
+#[derive(Debug)]
 pub enum VReplicaSetReconcileStep {
     Init,
     AfterListPods,
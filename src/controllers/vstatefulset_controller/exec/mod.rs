@@ -1,2 +1,3 @@
-pub mod validation;
 pub mod reconciler;
+pub mod shim_hooks;
+pub mod validation;
@@ -4,7 +4,7 @@ use crate::kubernetes_api_objects::spec::{prelude::*, volume::*};
 use crate::reconciler::exec::{io::*, reconciler::*};
 use crate::reconciler::spec::io::*;
 use crate::vstatefulset_controller::model::reconciler::*;
-use crate::vstatefulset_controller::trusted::exec_types::VStatefulSet;
+use crate::vstatefulset_controller::trusted::exec_types::{VStatefulSet, VStatefulSetStatus};
 use crate::vstatefulset_controller::trusted::spec_types::VStatefulSetView;
 use crate::vstatefulset_controller::trusted::reconciler::get_ordinal;
 use crate::vstatefulset_controller::trusted::reconciler::sort_pods_by_ord;
@@ -76,6 +76,16 @@ impl Reconciler for VStatefulSetReconciler {
         reconcile_error(state)
     }
 
+    fn finalizer() -> Option<String>
+    {
+        None
+    }
+
+    fn reconcile_delete(cr: &Self::K, resp_o: Option<Response<Self::EResp>>, state: Self::S) -> (Self::S, Option<Request<Self::EReq>>)
+    {
+        reconcile_core(cr, resp_o, state)
+    }
+
 }
 
 pub fn reconcile_init_state() -> (state: VStatefulSetReconcileState)
@@ -162,6 +172,9 @@ pub fn reconcile_core(
         VStatefulSetReconcileStep::AfterDeleteOutdated => {
             handle_after_delete_outdated(vsts, resp_o, state)
         },
+        VStatefulSetReconcileStep::AfterUpdateStatus => {
+            handle_after_update_status(vsts, resp_o, state)
+        },
         _ => { (state, None) },
     }
 }
@@ -191,6 +204,12 @@ pub fn handle_init(
             KubeListRequest {
                 api_resource: Pod::api_resource(),
                 namespace: vsts.metadata().namespace().unwrap(),
+                // Not vsts.spec().selector(): pod_filter does not check the selector (see the model)
+                label_selector: None,
+                field_selector: None,
+                read_mode: KubeReadMode::Quorum,
+                limit: None,
+                continue_token: None,
             },
         );
         let state_prime = VStatefulSetReconcileState {
@@ -337,6 +356,7 @@ pub fn handle_get_pvc(
                 api_resource: PersistentVolumeClaim::api_resource(),
                 name: state.pvcs[state.pvc_index].metadata().name().unwrap(),
                 namespace: vsts.metadata().namespace().unwrap(),
+                read_mode: KubeReadMode::Quorum,
             },
         );
         let state_prime = VStatefulSetReconcileState {
@@ -568,6 +588,7 @@ pub fn handle_update_needed(
                 name: new_pod.metadata().name().unwrap(),
                 namespace: vsts.metadata().namespace().unwrap(),
                 owner_ref: vsts.controller_owner_ref(),
+                predicate: KubeGetThenPredicate::OwnerRef,
                 obj: new_pod.marshal(),
             },
         );
@@ -637,6 +658,7 @@ pub fn handle_delete_condemned(
                 name: condemned_pod.metadata().name().unwrap(),
                 namespace: vsts.metadata().namespace().unwrap(),
                 owner_ref: vsts.controller_owner_ref(),
+                predicate: KubeGetThenPredicate::OwnerRef,
             },
         );
 
@@ -718,6 +740,7 @@ pub fn handle_delete_outdated(
             name: pod.metadata().name().unwrap(),
             namespace: vsts.metadata().namespace().unwrap(),
             owner_ref: vsts.controller_owner_ref(),
+            predicate: KubeGetThenPredicate::OwnerRef,
         });
         let state_prime = VStatefulSetReconcileState {
             reconcile_step: VStatefulSetReconcileStep::AfterDeleteOutdated,
@@ -725,7 +748,18 @@ pub fn handle_delete_outdated(
         };
         (state_prime, Some(Request::KRequest(req)))
     } else {
-        (done_state(state), None)
+        // All the pods and pvcs are reconciled, so report the generation observed by this round
+        let req = KubeAPIRequest::UpdateStatusRequest(KubeUpdateStatusRequest {
+            api_resource: VStatefulSet::api_resource(),
+            name: vsts.metadata().name().unwrap(),
+            namespace: vsts.metadata().namespace().unwrap(),
+            obj: updated_vsts_status(vsts).marshal(),
+        });
+        let state_prime = VStatefulSetReconcileState {
+            reconcile_step: VStatefulSetReconcileStep::AfterUpdateStatus,
+            ..state
+        };
+        (state_prime, Some(Request::KRequest(req)))
     }
 }
 
@@ -761,6 +795,44 @@ pub fn handle_after_delete_outdated(
     }
 }
 
+pub fn handle_after_update_status(
+    vsts: &VStatefulSet,
+    resp_o: Option<Response<VoidEResp>>,
+    state: VStatefulSetReconcileState,
+) -> (res: (VStatefulSetReconcileState, Option<Request<VoidEReq>>))
+    requires
+        vsts@.well_formed(),
+    ensures
+        (res.0@, res.1.deep_view()) == model_reconciler::handle_after_update_status(
+            vsts@,
+            resp_o.deep_view(),
+            state@,
+        ),
+{
+    if is_some_k_update_status_resp!(resp_o) {
+        // The pods and pvcs are already reconciled at this point, so a failed status update
+        // (e.g., a conflict because the object changed after this round read it) does not fail
+        // the reconcile; the next round reports the observed generation again
+        (done_state(state), None)
+    } else {
+        // This should be unreachable
+        (error_state(state), None)
+    }
+}
+
+pub fn updated_vsts_status(vsts: &VStatefulSet) -> (new_vsts: VStatefulSet)
+    ensures
+        new_vsts@ == model_reconciler::updated_vsts_status(vsts@),
+{
+    let mut new_status = VStatefulSetStatus::default();
+    if let Some(generation) = vsts.metadata().generation() {
+        new_status.set_observed_generation(generation);
+    }
+    let mut new_vsts = vsts.clone();
+    new_vsts.set_status(new_status);
+    new_vsts
+}
+
 pub fn handle_after_create_or_skip_pvc_helper(
     vsts: &VStatefulSet,
     state: VStatefulSetReconcileState,
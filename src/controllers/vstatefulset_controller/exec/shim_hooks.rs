@@ -0,0 +1,27 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::events::{successful_event, NotableEvent, ReconcileEvents};
+use crate::shim_layer::trace::ReconcileStepName;
+use crate::vstatefulset_controller::exec::reconciler::{VStatefulSetReconcileState, VStatefulSetReconciler};
+
+// The hooks that the shim layer calls when running the VStatefulSet controller.
+// They are not verified and stay out of the exec reconciler, so that the verified code does not depend on the shim layer.
+
+// The name of a reconcile step is the Debug form of the step.
+impl ReconcileStepName for VStatefulSetReconcileState {
+    fn reconcile_step_name(&self) -> String {
+        format!("{:?}", self.reconcile_step)
+    }
+}
+
+// The pods and claims created by the controller and the pods it deletes (the condemned and the outdated ones)
+// are recorded as events of the VStatefulSet.
+impl ReconcileEvents for VStatefulSetReconciler {
+    fn notable_event(verb: &str, kind: &str, name: &str) -> Option<NotableEvent> {
+        match (verb, kind) {
+            ("Create", "Pod") | ("Create", "PersistentVolumeClaim") => successful_event("Create", "Created", kind, name),
+            ("GetThenDelete", "Pod") => successful_event("Delete", "Deleted", kind, name),
+            _ => None,
+        }
+    }
+}
@@ -49,6 +49,16 @@ impl Reconciler<VStatefulSetReconcileState, VStatefulSetView, VoidEReqView, Void
     open spec fn reconcile_error(state: VStatefulSetReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    // The objects created by the VStatefulSet are owned by it and removed by the garbage collector,
+    // so there is nothing to clean up and reconcile_delete is never invoked.
+    open spec fn finalizer() -> Option<StringView> {
+        None
+    }
+
+    open spec fn reconcile_delete(vsts: VStatefulSetView, resp_o: DefaultResp, state: VStatefulSetReconcileState) -> (VStatefulSetReconcileState, DefaultReq) {
+        reconcile_core(vsts, resp_o, state)
+    }
 }
 
 pub open spec fn reconcile_init_state() -> VStatefulSetReconcileState {
@@ -96,6 +106,8 @@ pub open spec fn reconcile_error(state: VStatefulSetReconcileState) -> bool {
 // state will not be achieved in just one round of reconciliation. The remaining job will
 // be done by the next round of reconcile---the controller will get [pod-0, ...pod-3] by list,
 // and then it will create a new pod-4 with the new template, and then delete the next outdated pod.
+// Once no pod is outdated, the controller reports the generation it has reconciled
+// in status.observedGeneration and ends reconcile().
 pub open spec fn reconcile_core(vsts: VStatefulSetView, resp_o: DefaultResp, state: VStatefulSetReconcileState) -> (VStatefulSetReconcileState, DefaultReq) {
     match state.reconcile_step {
         VStatefulSetReconcileStepView::Init => {
@@ -146,6 +158,9 @@ pub open spec fn reconcile_core(vsts: VStatefulSetView, resp_o: DefaultResp, sta
         VStatefulSetReconcileStepView::AfterDeleteOutdated => {
             handle_after_delete_outdated(vsts, resp_o, state)
         },
+        VStatefulSetReconcileStepView::AfterUpdateStatus => {
+            handle_after_update_status(vsts, resp_o, state)
+        },
         _ => {
             (state, None)
         }
@@ -163,6 +178,13 @@ pub open spec fn handle_init(vsts: VStatefulSetView, resp_o: DefaultResp, state:
         let req = APIRequest::ListRequest(ListRequest {
             kind: PodView::kind(),
             namespace: vsts.metadata.namespace->0,
+            // List all the pods in the namespace: pod_filter does not check the selector,
+            // so listing with the selector would hide the owned pods whose labels no longer match it
+            label_selector: None,
+            field_selector: None,
+            read_mode: ReadMode::Quorum,
+            limit: None,
+            continue_token: None,
         });
         let state_prime = VStatefulSetReconcileState {
             reconcile_step: VStatefulSetReconcileStepView::AfterListPod,
@@ -254,7 +276,8 @@ pub open spec fn handle_get_pvc(vsts: VStatefulSetView, resp_o: DefaultResp, sta
                 kind: PersistentVolumeClaimView::kind(),
                 name: state.pvcs[state.pvc_index as int].metadata.name->0,
                 namespace: vsts.metadata.namespace->0,
-            }
+            },
+            read_mode: ReadMode::Quorum,
         });
         let state_prime = VStatefulSetReconcileState {
             reconcile_step: VStatefulSetReconcileStepView::AfterGetPVC,
@@ -414,6 +437,7 @@ pub open spec fn handle_update_needed(vsts: VStatefulSetView, resp_o: DefaultRes
                 name: new_pod.metadata.name->0,
                 namespace: vsts.metadata.namespace->0,
                 owner_ref: vsts.controller_owner_ref(),
+                predicate: GetThenPredicate::OwnerRef,
                 obj: new_pod.marshal(),
             });
             let state_prime = VStatefulSetReconcileState {
@@ -497,6 +521,7 @@ pub open spec fn handle_delete_condemned(vsts: VStatefulSetView, resp_o: Default
                     namespace: vsts.metadata.namespace->0,
                 },
                 owner_ref: vsts.controller_owner_ref(),
+                predicate: GetThenPredicate::OwnerRef,
             });
             let state_prime = VStatefulSetReconcileState {
                 reconcile_step: VStatefulSetReconcileStepView::AfterDeleteCondemned,
@@ -544,6 +569,7 @@ pub open spec fn handle_delete_outdated(vsts: VStatefulSetView, resp_o: DefaultR
                     namespace: vsts.metadata.namespace->0,
                 },
                 owner_ref: vsts.controller_owner_ref(),
+                predicate: GetThenPredicate::OwnerRef,
             });
             let state_prime = VStatefulSetReconcileState {
                 reconcile_step: VStatefulSetReconcileStepView::AfterDeleteOutdated,
@@ -554,7 +580,17 @@ pub open spec fn handle_delete_outdated(vsts: VStatefulSetView, resp_o: DefaultR
             (error_state(state), None)
         }
     } else {
-        (done_state(state), None)
+        // All the pods and pvcs are reconciled, so report the generation observed by this round
+        let req = APIRequest::UpdateStatusRequest(UpdateStatusRequest {
+            namespace: vsts.metadata.namespace->0,
+            name: vsts.metadata.name->0,
+            obj: updated_vsts_status(vsts).marshal(),
+        });
+        let state_prime = VStatefulSetReconcileState {
+            reconcile_step: VStatefulSetReconcileStepView::AfterUpdateStatus,
+            ..state
+        };
+        (state_prime, Some(RequestView::KRequest(req)))
     }
 }
 
@@ -572,6 +608,26 @@ pub open spec fn handle_after_delete_outdated(vsts: VStatefulSetView, resp_o: De
     }
 }
 
+pub open spec fn handle_after_update_status(vsts: VStatefulSetView, resp_o: DefaultResp, state: VStatefulSetReconcileState) -> (VStatefulSetReconcileState, DefaultReq) {
+    if is_some_k_update_status_resp_view(resp_o) {
+        // The pods and pvcs are already reconciled at this point, so a failed status update
+        // (e.g., a conflict because the object changed after this round read it) does not fail
+        // the reconcile; the next round reports the observed generation again
+        (done_state(state), None)
+    } else {
+        // This should be unreachable
+        (error_state(state), None)
+    }
+}
+
+pub open spec fn updated_vsts_status(vsts: VStatefulSetView) -> VStatefulSetView {
+    let status = StatefulSetStatusView {
+        observed_generation: vsts.metadata.generation,
+        ..StatefulSetStatusView::default()
+    };
+    vsts.with_status(status)
+}
+
 pub open spec fn delete_outdated_state(state: VStatefulSetReconcileState) -> VStatefulSetReconcileState {
     VStatefulSetReconcileState {
         reconcile_step: VStatefulSetReconcileStepView::DeleteOutdated,
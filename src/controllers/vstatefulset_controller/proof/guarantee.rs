@@ -31,6 +31,7 @@ pub proof fn lemma_guarantee_from_reconcile_state(
             APIRequest::CreateRequest(req) => vsts_guarantee_create_req(req),
             APIRequest::GetThenUpdateRequest(req) => vsts_guarantee_get_then_update_req(req),
             APIRequest::GetThenDeleteRequest(req) => vsts_guarantee_get_then_delete_req(req),
+            APIRequest::UpdateStatusRequest(req) => vsts_guarantee_update_status_req(req),
             _ => false,
         }
 {
@@ -79,6 +80,11 @@ pub proof fn lemma_guarantee_from_reconcile_state(
                 assert(req.key.name == pod.metadata.name->0);
                 assert(has_vsts_prefix(req.key.name));
                 assert(req.owner_ref == vsts.controller_owner_ref());
+            } else {
+                assert(msg.content.is_update_status_request());
+                let req = msg.content.get_update_status_request();
+                assert(req.obj == updated_vsts_status(vsts).marshal());
+                assert(req.obj.kind == VStatefulSetView::kind());
             }
         }
         VStatefulSetReconcileStepView::Init | VStatefulSetReconcileStepView::GetPVC => {},
@@ -149,6 +155,7 @@ pub proof fn guarantee_condition_holds(spec: TempPred<ClusterState>, cluster: Cl
             APIRequest::CreateRequest(req) => vsts_guarantee_create_req(req),
             APIRequest::GetThenUpdateRequest(req) => vsts_guarantee_get_then_update_req(req),
             APIRequest::GetThenDeleteRequest(req) => vsts_guarantee_get_then_delete_req(req),
+            APIRequest::UpdateStatusRequest(req) => vsts_guarantee_update_status_req(req),
             _ => false,
         } by {
             let step = choose |step| cluster.next_step(s, s_prime, step);
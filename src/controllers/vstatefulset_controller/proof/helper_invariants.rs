@@ -3,6 +3,7 @@ use crate::kubernetes_cluster::{
     proof::api_server::*,
     spec::{
         api_server::{state_machine::*, types::InstalledTypes},
+        builtin_controllers::{garbage_collector::{owner_is_deleting_with, owner_is_solid, owner_reference_key}, types::BuiltinControllerChoice},
         cluster::*,
         controller::types::ControllerStep,
         message::*,
@@ -72,6 +73,53 @@ pub open spec fn is_vsts_pod_key(vsts: VStatefulSetView) -> spec_fn(ObjectRef) -
     |key: ObjectRef| key.kind == Kind::PodKind && key.namespace == vsts.object_ref().namespace && pod_name_match(key.name, vsts.object_ref().name)
 }
 
+// Only the vsts controller makes a pod owned by vsts, and it only does so for the pods matching the name of vsts
+pub open spec fn all_pods_in_etcd_owned_by_vsts_match_vsts(vsts: VStatefulSetView) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        forall |pod_key: ObjectRef| {
+            &&& #[trigger] s.resources().contains_key(pod_key)
+            &&& pod_key.kind == Kind::PodKind
+            &&& pod_key.namespace == vsts.object_ref().namespace
+            &&& s.resources()[pod_key].metadata.owner_references_contains(vsts.controller_owner_ref())
+        } ==> pod_name_match(pod_key.name, vsts.object_ref().name)
+    }
+}
+
+// The builtin controllers only delete the pods matching vsts in background, and only update them with the uid,
+// the owner references and the finalizers (none) of the pods in etcd:
+// the garbage collector never updates them since their only owner is a VSTS (see no_vsts_carries_gc_finalizer_or_blocks_owner_deletion),
+// and the scheduler only sets the node of the pod in etcd.
+pub open spec fn builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts: VStatefulSetView) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        forall |msg: Message| {
+            &&& #[trigger] s.in_flight().contains(msg)
+            &&& msg.src is BuiltinController
+        } ==> builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, msg, s)
+    }
+}
+
+pub open spec fn builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts: VStatefulSetView, msg: Message, s: ClusterState) -> bool {
+    let update_req = msg.content.get_update_request();
+    &&& msg.content.is_delete_request() && is_vsts_pod_key(vsts)(msg.content.get_delete_request().key) ==> {
+        ||| msg.content.get_delete_request().propagation_policy is None
+        ||| msg.content.get_delete_request().propagation_policy == Some(PropagationPolicy::Background)
+    }
+    &&& msg.content.is_update_request() && update_req.obj.kind == Kind::PodKind && update_req.namespace == vsts.object_ref().namespace ==> {
+        // a pod owned by vsts keeps matching vsts
+        &&& update_req.obj.metadata.owner_references_contains(vsts.controller_owner_ref())
+            ==> pod_name_match(update_req.name, vsts.object_ref().name)
+        &&& pod_name_match(update_req.name, vsts.object_ref().name) ==> {
+            &&& update_req.obj.metadata.finalizers is None
+            &&& update_req.obj.metadata.uid is Some
+            &&& update_req.obj.metadata.uid->0 < s.api_server.uid_counter
+            // the update only succeeds on the pod it is sent for, which still has the same owner references
+            &&& s.resources().contains_key(update_req.key())
+                && s.resources()[update_req.key()].metadata.uid == update_req.obj.metadata.uid
+                ==> s.resources()[update_req.key()].metadata.owner_references == update_req.obj.metadata.owner_references
+        }
+    }
+}
+
 pub proof fn lemma_always_all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(
     spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int, vsts: VStatefulSetView
 )
@@ -84,8 +132,15 @@ requires
     cluster.controller_models.contains_pair(controller_id, vsts_controller_model()),
 ensures
     spec.entails(always(lift_state(all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)))),
+    spec.entails(always(lift_state(all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)))),
+    spec.entails(always(lift_state(builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)))),
 {
-    let inv = all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts);
+    // The three invariants depend on each other, so they are proved together.
+    let inv = |s: ClusterState| {
+        &&& all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)(s)
+        &&& all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)(s)
+        &&& builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)(s)
+    };
     let stronger_next = |s: ClusterState, s_prime: ClusterState| {
         &&& cluster.next()(s, s_prime)
         &&& rely_guarantee::vsts_rely_conditions(cluster, controller_id)(s)
@@ -93,20 +148,26 @@ ensures
         &&& Cluster::there_is_the_controller_state(controller_id)(s)
         &&& Cluster::no_pending_request_to_api_server_from_api_server_or_external()(s)
         &&& Cluster::all_requests_from_pod_monkey_are_api_pod_requests()(s)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s)
+        &&& Cluster::all_requests_from_node_monkey_are_api_node_delete_requests()(s)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s)
         &&& Cluster::each_object_in_etcd_has_at_most_one_controller_owner()(s)
+        &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
         &&& cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s)
         &&& internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)(s)
         &&& every_msg_from_vsts_controller_carries_vsts_key(controller_id)(s)
+        &&& no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()(s)
     };
     cluster.lemma_always_there_is_the_controller_state(spec, controller_id);
     cluster.lemma_always_no_pending_request_to_api_server_from_api_server_or_external(spec);
     cluster.lemma_always_all_requests_from_pod_monkey_are_api_pod_requests(spec);
-    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_requests(spec);
+    cluster.lemma_always_all_requests_from_node_monkey_are_api_node_delete_requests(spec);
+    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests(spec);
     cluster.lemma_always_each_object_in_etcd_has_at_most_one_controller_owner(spec);
+    cluster.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
     cluster.lemma_always_every_in_flight_req_msg_from_controller_has_valid_controller_id(spec);
     internal_rely_guarantee::internal_guarantee_condition_holds_on_all_vsts(spec, cluster, controller_id);
     lemma_always_every_msg_from_vsts_controller_carries_vsts_key(spec, cluster, controller_id);
+    lemma_always_no_vsts_carries_gc_finalizer_or_blocks_owner_deletion(spec, cluster, controller_id);
 
     assert forall |s, s_prime: ClusterState| inv(s) && #[trigger] stronger_next(s, s_prime)
         implies inv(s_prime) by {
@@ -196,7 +257,11 @@ ensures
                                     assert(internal_rely_guarantee::no_interfering_request_between_vsts(controller_id, vsts)(s));
                                 }
                             },
-                            HostId::BuiltinController => {}, // must be delete requests
+                            HostId::BuiltinController => {
+                                // The builtin controllers create no objects, only delete the pod in background, which removes it
+                                // since it has no finalizers, and only update it with its own uid, owner references and (no) finalizers.
+                                assert(builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, msg, s));
+                            },
                             HostId::PodMonkey => {
                                 assert(rely_guarantee::vsts_rely_conditions_pod_monkey()(s));
                                 if msg.content.is_create_request() {
@@ -217,6 +282,7 @@ ensures
                                     }
                                 } else {} // Deletion/Update/UpdateStatus are not possible
                             }, // must be pod requests
+                            HostId::NodeMonkey => {}, // only deletes nodes
                             _ => {}
                         }
                     }
@@ -226,6 +292,48 @@ ensures
             },
             _ => {}
         }
+        assert forall |pod_key: ObjectRef| {
+            &&& #[trigger] s_prime.resources().contains_key(pod_key)
+            &&& pod_key.kind == Kind::PodKind
+            &&& pod_key.namespace == vsts.object_ref().namespace
+            &&& s_prime.resources()[pod_key].metadata.owner_references_contains(vsts.controller_owner_ref())
+        } implies pod_name_match(pod_key.name, vsts.object_ref().name) by {
+            match step {
+                Step::APIServerStep(input) => {
+                    lemma_api_request_keeps_pods_owned_by_vsts_matching_vsts(cluster, controller_id, vsts, s, s_prime, input->0, pod_key);
+                },
+                _ => {} // only the API server changes etcd
+            }
+        }
+        assert forall |msg: Message| {
+            &&& #[trigger] s_prime.in_flight().contains(msg)
+            &&& msg.src is BuiltinController
+        } implies builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, msg, s_prime) by {
+            if s.in_flight().contains(msg) {
+                let update_req = msg.content.get_update_request();
+                assert(builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, msg, s));
+                if msg.content.is_update_request() && is_vsts_pod_key(vsts)(update_req.key()) {
+                    match step {
+                        Step::APIServerStep(input) => {
+                            // A pod created at the key gets a new uid, which is different from the uid in the update.
+                            lemma_api_request_keeps_uid_and_owner_references_of_pod_matching_vsts(cluster, controller_id, vsts, s, s_prime, input->0, update_req.key());
+                        },
+                        _ => {}
+                    }
+                }
+            } else {
+                match step {
+                    Step::BuiltinControllersStep(input) => {
+                        lemma_builtin_controllers_keep_owner_references_of_pods_matching_vsts(cluster, vsts, s, s_prime, input, msg);
+                    },
+                    Step::DuplicateReqStep(req_msg) => {
+                        // The copy has the same src and content as the request in flight.
+                        assert(s.in_flight().contains(req_msg));
+                    },
+                    _ => {}
+                }
+            }
+        }
     };
     combine_spec_entails_always_n!(
         spec, lift_action(stronger_next),
@@ -235,15 +343,507 @@ ensures
         lift_state(Cluster::there_is_the_controller_state(controller_id)),
         lift_state(Cluster::no_pending_request_to_api_server_from_api_server_or_external()),
         lift_state(Cluster::all_requests_from_pod_monkey_are_api_pod_requests()),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
+        lift_state(Cluster::all_requests_from_node_monkey_are_api_node_delete_requests()),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
         lift_state(Cluster::each_object_in_etcd_has_at_most_one_controller_owner()),
+        lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
         lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()),
         lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)),
-        lift_state(every_msg_from_vsts_controller_carries_vsts_key(controller_id))
+        lift_state(every_msg_from_vsts_controller_carries_vsts_key(controller_id)),
+        lift_state(no_vsts_carries_gc_finalizer_or_blocks_owner_deletion())
+    );
+    init_invariant(spec, cluster.init(), stronger_next, inv);
+    always_weaken(spec, lift_state(inv), lift_state(all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)));
+    always_weaken(spec, lift_state(inv), lift_state(all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)));
+    always_weaken(spec, lift_state(inv), lift_state(builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)));
+}
+
+// Only the vsts controller sets owner references to vsts when creating or updating a pod (other controllers and the pod monkey
+// are ruled out by the rely conditions), and it only does so for the pods matching the name of vsts.
+// The builtin controllers only set owner references to vsts on the pods matching vsts, and
+// deletions and status updates keep the owner references.
+proof fn lemma_api_request_keeps_pods_owned_by_vsts_matching_vsts(
+    cluster: Cluster, controller_id: int, vsts: VStatefulSetView, s: ClusterState, s_prime: ClusterState, msg: Message, pod_key: ObjectRef
+)
+requires
+    cluster.next_step(s, s_prime, Step::APIServerStep(Some(msg))),
+    all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)(s),
+    builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)(s),
+    rely_guarantee::vsts_rely_conditions(cluster, controller_id)(s),
+    rely_guarantee::vsts_rely_conditions_pod_monkey()(s),
+    internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)(s),
+    Cluster::no_pending_request_to_api_server_from_api_server_or_external()(s),
+    Cluster::all_requests_from_pod_monkey_are_api_pod_requests()(s),
+    Cluster::all_requests_from_node_monkey_are_api_node_delete_requests()(s),
+    Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s),
+    cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s),
+    s_prime.resources().contains_key(pod_key),
+    pod_key.kind == Kind::PodKind,
+    pod_key.namespace == vsts.object_ref().namespace,
+    s_prime.resources()[pod_key].metadata.owner_references_contains(vsts.controller_owner_ref()),
+ensures
+    pod_name_match(pod_key.name, vsts.object_ref().name),
+{
+    assert(s.in_flight().contains(msg));
+    assert(msg.content is APIRequest);
+    let owner_ref = vsts.controller_owner_ref();
+    let obj = s.resources()[pod_key];
+    let obj_prime = s_prime.resources()[pod_key];
+    if s.resources().contains_key(pod_key) && obj_prime.metadata.owner_references == obj.metadata.owner_references {
+        assert(obj.metadata.owner_references_contains(owner_ref));
+        return;
+    }
+    match msg.src {
+        HostId::Controller(other_id, cr_key) => {
+            if other_id != controller_id {
+                assert(cluster.controller_models.remove(controller_id).contains_key(other_id));
+                assert(rely_guarantee::vsts_rely(other_id)(s));
+                match msg.content->APIRequest_0 {
+                    APIRequest::CreateRequest(req) => {
+                        assert(!req.obj.metadata.owner_references_contains(owner_ref));
+                    },
+                    APIRequest::UpdateRequest(req) => {
+                        assert(!req.obj.metadata.owner_references_contains(owner_ref));
+                    },
+                    APIRequest::GetThenUpdateRequest(req) => {
+                        assert(!req.obj.metadata.owner_references_contains(owner_ref));
+                    },
+                    APIRequest::PatchRequest(req) => {
+                        assert(!req.obj.metadata.owner_references_contains(owner_ref));
+                    },
+                    _ => {}
+                }
+            } else {
+                let vsts_with_key = VStatefulSetView {
+                    metadata: ObjectMetaView {
+                        name: Some(cr_key.name),
+                        namespace: Some(cr_key.namespace),
+                        ..ObjectMetaView::default()
+                    },
+                    ..VStatefulSetView::default()
+                };
+                assert(vsts_with_key.object_ref() == cr_key);
+                assert(internal_rely_guarantee::no_interfering_request_between_vsts(controller_id, vsts_with_key)(s));
+                // The only owner reference set by the vsts controller refers to the vsts being reconciled,
+                // so owner_ref refers to it as well, and the pod matches its name.
+                if msg.content.is_create_request() {
+                    let req = msg.content.get_create_request();
+                    assert(internal_rely_guarantee::vsts_internal_guarantee_create_req(req, vsts_with_key));
+                    let owner_reference = choose |owner_reference: OwnerReferenceView| {
+                        &&& req.obj.metadata.owner_references == Some(Seq::empty().push(owner_reference))
+                        &&& #[trigger] owner_reference_eq_without_uid(owner_reference, vsts_with_key.controller_owner_ref())
+                    };
+                    assert(Seq::empty().push(owner_reference).contains(owner_ref));
+                    assert(owner_ref == owner_reference);
+                } else if msg.content.is_get_then_update_request() {
+                    let req = msg.content.get_get_then_update_request();
+                    assert(internal_rely_guarantee::vsts_internal_guarantee_get_then_update_req(req, vsts_with_key));
+                    assert(Seq::empty().push(req.owner_ref).contains(owner_ref));
+                    assert(owner_ref == req.owner_ref);
+                }
+                assert(cr_key.name == vsts.object_ref().name);
+            }
+        },
+        HostId::BuiltinController => {
+            assert(builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, msg, s));
+        },
+        HostId::PodMonkey => {
+            if msg.content.is_create_request() {
+                assert(!msg.content.get_create_request().obj.metadata.owner_references_contains(owner_ref));
+            } else if msg.content.is_update_request() {
+                assert(!msg.content.get_update_request().obj.metadata.owner_references_contains(owner_ref));
+            }
+        },
+        _ => {}
+    }
+}
+
+// A pod matching vsts keeps its uid and owner references: other controllers and the pod monkey cannot write it
+// by the rely conditions, the vsts controller keeps its only owner reference since get_then_update only succeeds
+// if the pod is owned by the owner reference in the request, and the builtin controllers only update it with
+// its own owner references. A pod created at the key gets a new uid.
+proof fn lemma_api_request_keeps_uid_and_owner_references_of_pod_matching_vsts(
+    cluster: Cluster, controller_id: int, vsts: VStatefulSetView, s: ClusterState, s_prime: ClusterState, msg: Message, pod_key: ObjectRef
+)
+requires
+    cluster.next_step(s, s_prime, Step::APIServerStep(Some(msg))),
+    all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)(s),
+    builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)(s),
+    rely_guarantee::vsts_rely_conditions(cluster, controller_id)(s),
+    rely_guarantee::vsts_rely_conditions_pod_monkey()(s),
+    internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)(s),
+    Cluster::no_pending_request_to_api_server_from_api_server_or_external()(s),
+    Cluster::all_requests_from_pod_monkey_are_api_pod_requests()(s),
+    Cluster::all_requests_from_node_monkey_are_api_node_delete_requests()(s),
+    Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s),
+    cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s),
+    is_vsts_pod_key(vsts)(pod_key),
+ensures
+    s.api_server.uid_counter <= s_prime.api_server.uid_counter,
+    s_prime.resources().contains_key(pod_key) ==> {
+        ||| {
+            &&& s.resources().contains_key(pod_key)
+            &&& s_prime.resources()[pod_key].metadata.uid == s.resources()[pod_key].metadata.uid
+            &&& s_prime.resources()[pod_key].metadata.owner_references == s.resources()[pod_key].metadata.owner_references
+        }
+        ||| s_prime.resources()[pod_key].metadata.uid == Some(s.api_server.uid_counter)
+    },
+{
+    assert(s.in_flight().contains(msg));
+    assert(msg.content is APIRequest);
+    if !s.resources().contains_key(pod_key) {
+        return;
+    }
+    let obj = s.resources()[pod_key];
+    assert(pod_name_match(pod_key.name, vsts.object_ref().name));
+    pod_name_match_implies_has_vsts_prefix(pod_key.name);
+    match msg.src {
+        HostId::Controller(other_id, cr_key) => {
+            if other_id != controller_id {
+                // Other controllers never write objects with the vsts prefix.
+                assert(cluster.controller_models.remove(controller_id).contains_key(other_id));
+                assert(rely_guarantee::vsts_rely(other_id)(s));
+            } else {
+                let vsts_with_key = VStatefulSetView {
+                    metadata: ObjectMetaView {
+                        name: Some(cr_key.name),
+                        namespace: Some(cr_key.namespace),
+                        ..ObjectMetaView::default()
+                    },
+                    ..VStatefulSetView::default()
+                };
+                assert(vsts_with_key.object_ref() == cr_key);
+                assert(internal_rely_guarantee::no_interfering_request_between_vsts(controller_id, vsts_with_key)(s));
+                if msg.content.is_get_then_update_request() {
+                    let req = msg.content.get_get_then_update_request();
+                    assert(internal_rely_guarantee::vsts_internal_guarantee_get_then_update_req(req, vsts_with_key));
+                    if req.key() == pod_key && obj.metadata.owner_references_contains(req.owner_ref) {
+                        // The pod only has one owner reference, which is the one in the request.
+                        let owner_reference = choose |owner_reference: OwnerReferenceView| {
+                            &&& obj.metadata.owner_references == Some(Seq::empty().push(owner_reference))
+                            &&& #[trigger] owner_reference_eq_without_uid(owner_reference, vsts.controller_owner_ref())
+                        };
+                        assert(Seq::empty().push(owner_reference).contains(req.owner_ref));
+                        assert(req.owner_ref == owner_reference);
+                    }
+                }
+            }
+        },
+        HostId::BuiltinController => {
+            assert(builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, msg, s));
+        },
+        HostId::PodMonkey => {
+            // The pod monkey never updates objects with the vsts prefix.
+            assert(rely_guarantee::vsts_rely_conditions_pod_monkey()(s));
+        },
+        _ => {}
+    }
+}
+
+// The only owner of a pod matching vsts is a VSTS, which is never deleted in foreground or with the orphan policy.
+// So the garbage collector never updates the pod: the pod is not being deleted, its owner is not orphaning it,
+// and the owner cannot be solid and dangling (or blocked by the pod) at the same time. For the same reason,
+// the garbage collector only deletes the pod in background.
+proof fn lemma_only_owner_of_pod_matching_vsts_is_not_deleting_with_gc_finalizer(
+    cluster: Cluster, vsts: VStatefulSetView, s: ClusterState, key: ObjectRef
+)
+requires
+    all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)(s),
+    no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()(s),
+    Cluster::each_object_in_etcd_is_weakly_well_formed()(s),
+    s.resources().contains_key(key),
+    is_vsts_pod_key(vsts)(key),
+ensures
+    s.resources()[key].metadata.owner_references is Some,
+    s.resources()[key].metadata.owner_references->0.len() == 1,
+    !owner_is_deleting_with(cluster.installed_types, s.resources(), s.resources()[key].metadata.owner_references->0[0], key, foreground_deletion_finalizer()),
+    !owner_is_deleting_with(cluster.installed_types, s.resources(), s.resources()[key].metadata.owner_references->0[0], key, orphan_finalizer()),
+{
+    let obj = s.resources()[key];
+    let owner_reference = choose |owner_reference: OwnerReferenceView| {
+        &&& obj.metadata.owner_references == Some(Seq::empty().push(owner_reference))
+        &&& #[trigger] owner_reference_eq_without_uid(owner_reference, vsts.controller_owner_ref())
+    };
+    let owner_key = owner_reference_key(cluster.installed_types, owner_reference, key);
+    assert(obj.metadata.owner_references->0[0] == owner_reference);
+    if s.resources().contains_key(owner_key) {
+        assert(owner_key.kind == VStatefulSetView::kind());
+        assert(rely_guarantee::vsts_rely_vsts_obj(s.resources()[owner_key]));
+    }
+}
+
+// The garbage collector only deletes a pod matching vsts in background, and never updates it.
+// The scheduler only sets the node of the pod, so the update carries the metadata of the pod in etcd.
+// An update from the garbage collector keeps the owner references that block the deletion of the owners,
+// so it never makes a pod owned by vsts unless the pod is already owned by vsts.
+proof fn lemma_builtin_controllers_keep_owner_references_of_pods_matching_vsts(
+    cluster: Cluster, vsts: VStatefulSetView, s: ClusterState, s_prime: ClusterState, input: (BuiltinControllerChoice, ObjectRef), msg: Message
+)
+requires
+    cluster.next_step(s, s_prime, Step::BuiltinControllersStep(input)),
+    all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)(s),
+    all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)(s),
+    no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()(s),
+    Cluster::each_object_in_etcd_is_weakly_well_formed()(s),
+    !s.in_flight().contains(msg),
+    s_prime.in_flight().contains(msg),
+ensures
+    builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, msg, s_prime),
+{
+    let key = input.1;
+    let obj = s.resources()[key];
+    if is_vsts_pod_key(vsts)(key) && s.resources().contains_key(key) {
+        lemma_only_owner_of_pod_matching_vsts_is_not_deleting_with_gc_finalizer(cluster, vsts, s, key);
+    }
+    if msg.content.is_update_request() {
+        cluster.lemma_builtin_controllers_update_comes_from_etcd(s, s_prime, input, msg);
+        if msg.content.get_update_request().obj.metadata.owner_references_contains(vsts.controller_owner_ref()) {
+            assert(obj.metadata.owner_references_contains(vsts.controller_owner_ref()));
+        }
+    }
+}
+
+// Once the pod monkey is disabled, the scheduler stops binding pods, so no builtin controller updates a pod matching vsts.
+proof fn lemma_builtin_controllers_do_not_update_pods_matching_vsts(
+    cluster: Cluster, vsts: VStatefulSetView, s: ClusterState, s_prime: ClusterState, input: (BuiltinControllerChoice, ObjectRef), msg: Message
+)
+requires
+    cluster.next_step(s, s_prime, Step::BuiltinControllersStep(input)),
+    Cluster::pod_monkey_disabled()(s),
+    all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)(s),
+    no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()(s),
+    Cluster::each_object_in_etcd_is_weakly_well_formed()(s),
+    !s.in_flight().contains(msg),
+    s_prime.in_flight().contains(msg),
+ensures
+    !(msg.content.is_update_request() && is_vsts_pod_key(vsts)(msg.content.get_update_request().key())),
+{
+    if msg.content.is_update_request() {
+        cluster.lemma_builtin_controllers_update_comes_from_etcd(s, s_prime, input, msg);
+        if is_vsts_pod_key(vsts)(input.1) {
+            lemma_only_owner_of_pod_matching_vsts_is_not_deleting_with_gc_finalizer(cluster, vsts, s, input.1);
+        }
+    }
+}
+
+// No VSTS in etcd carries the foregroundDeletion or orphan finalizer or blocks the deletion of its owners,
+// so the garbage collector never deletes the pods of a VSTS in foreground or orphans them.
+// Other controllers keep this by the rely condition, and the builtin controllers never add these finalizers
+// to a VSTS or make it block the deletion of an owner.
+pub open spec fn no_vsts_carries_gc_finalizer_or_blocks_owner_deletion() -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        &&& forall |key: ObjectRef| {
+            &&& #[trigger] s.resources().contains_key(key)
+            &&& key.kind == VStatefulSetView::kind()
+        } ==> rely_guarantee::vsts_rely_vsts_obj(s.resources()[key])
+        &&& forall |msg: Message| {
+            &&& #[trigger] s.in_flight().contains(msg)
+            &&& msg.src is BuiltinController
+        } ==> {
+            &&& msg.content.is_delete_request() && msg.content.get_delete_request().key.kind == VStatefulSetView::kind() ==> {
+                ||| msg.content.get_delete_request().propagation_policy is None
+                ||| msg.content.get_delete_request().propagation_policy == Some(PropagationPolicy::Background)
+            }
+            &&& msg.content.is_update_request() && msg.content.get_update_request().obj.kind == VStatefulSetView::kind()
+                ==> rely_guarantee::vsts_rely_vsts_obj(msg.content.get_update_request().obj)
+        }
+    }
+}
+
+pub proof fn lemma_always_no_vsts_carries_gc_finalizer_or_blocks_owner_deletion(
+    spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int
+)
+requires
+    spec.entails(lift_state(cluster.init())),
+    spec.entails(always(lift_action(cluster.next()))),
+    spec.entails(always(lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)))),
+    cluster.type_is_installed_in_cluster::<VStatefulSetView>(),
+    cluster.controller_models.contains_pair(controller_id, vsts_controller_model()),
+ensures
+    spec.entails(always(lift_state(no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()))),
+{
+    let inv = no_vsts_carries_gc_finalizer_or_blocks_owner_deletion();
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& rely_guarantee::vsts_rely_conditions(cluster, controller_id)(s)
+        &&& rely_guarantee::vsts_guarantee(controller_id)(s)
+        &&& Cluster::no_pending_request_to_api_server_from_api_server_or_external()(s)
+        &&& Cluster::all_requests_from_pod_monkey_are_api_pod_requests()(s)
+        &&& Cluster::all_requests_from_node_monkey_are_api_node_delete_requests()(s)
+        &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
+        &&& cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s)
+    };
+    guarantee::guarantee_condition_holds(spec, cluster, controller_id);
+    cluster.lemma_always_no_pending_request_to_api_server_from_api_server_or_external(spec);
+    cluster.lemma_always_all_requests_from_pod_monkey_are_api_pod_requests(spec);
+    cluster.lemma_always_all_requests_from_node_monkey_are_api_node_delete_requests(spec);
+    cluster.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
+    cluster.lemma_always_every_in_flight_req_msg_from_controller_has_valid_controller_id(spec);
+
+    assert forall |s, s_prime: ClusterState| inv(s) && #[trigger] stronger_next(s, s_prime)
+        implies inv(s_prime) by {
+        let step = choose |step| cluster.next_step(s, s_prime, step);
+        assert forall |key: ObjectRef| {
+            &&& #[trigger] s_prime.resources().contains_key(key)
+            &&& key.kind == VStatefulSetView::kind()
+        } implies rely_guarantee::vsts_rely_vsts_obj(s_prime.resources()[key]) by {
+            match step {
+                Step::APIServerStep(input) => {
+                    lemma_api_request_keeps_vsts_rely_vsts_obj(cluster, controller_id, s, s_prime, input->0, key);
+                },
+                _ => {} // only the API server changes etcd
+            }
+        }
+        assert forall |msg: Message| {
+            &&& #[trigger] s_prime.in_flight().contains(msg)
+            &&& msg.src is BuiltinController
+        } implies {
+            &&& msg.content.is_delete_request() && msg.content.get_delete_request().key.kind == VStatefulSetView::kind() ==> {
+                ||| msg.content.get_delete_request().propagation_policy is None
+                ||| msg.content.get_delete_request().propagation_policy == Some(PropagationPolicy::Background)
+            }
+            &&& msg.content.is_update_request() && msg.content.get_update_request().obj.kind == VStatefulSetView::kind()
+                ==> rely_guarantee::vsts_rely_vsts_obj(msg.content.get_update_request().obj)
+        } by {
+            if !s.in_flight().contains(msg) {
+                match step {
+                    Step::BuiltinControllersStep(input) => {
+                        lemma_builtin_controllers_keep_vsts_rely_vsts_obj(cluster, s, s_prime, input, msg);
+                    },
+                    Step::DuplicateReqStep(req_msg) => {
+                        // The copy has the same src and content as the request in flight.
+                        assert(s.in_flight().contains(req_msg));
+                    },
+                    _ => {}
+                }
+            }
+        }
+    };
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(cluster.next()),
+        lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)),
+        lift_state(rely_guarantee::vsts_guarantee(controller_id)),
+        lift_state(Cluster::no_pending_request_to_api_server_from_api_server_or_external()),
+        lift_state(Cluster::all_requests_from_pod_monkey_are_api_pod_requests()),
+        lift_state(Cluster::all_requests_from_node_monkey_are_api_node_delete_requests()),
+        lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
+        lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id())
     );
     init_invariant(spec, cluster.init(), stronger_next, inv);
 }
 
+// The API server only changes the finalizers and owner references of a VSTS as requested by a create, update or patch,
+// or removes the foregroundDeletion and orphan finalizers on a background deletion.
+proof fn lemma_api_request_keeps_vsts_rely_vsts_obj(
+    cluster: Cluster, controller_id: int, s: ClusterState, s_prime: ClusterState, msg: Message, key: ObjectRef
+)
+requires
+    cluster.next_step(s, s_prime, Step::APIServerStep(Some(msg))),
+    no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()(s),
+    rely_guarantee::vsts_rely_conditions(cluster, controller_id)(s),
+    rely_guarantee::vsts_guarantee(controller_id)(s),
+    Cluster::no_pending_request_to_api_server_from_api_server_or_external()(s),
+    Cluster::all_requests_from_pod_monkey_are_api_pod_requests()(s),
+    Cluster::all_requests_from_node_monkey_are_api_node_delete_requests()(s),
+    Cluster::each_object_in_etcd_is_weakly_well_formed()(s),
+    cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s),
+    s_prime.resources().contains_key(key),
+    key.kind == VStatefulSetView::kind(),
+ensures
+    rely_guarantee::vsts_rely_vsts_obj(s_prime.resources()[key]),
+{
+    assert(s.in_flight().contains(msg));
+    assert(msg.content is APIRequest);
+    let obj = s.resources()[key];
+    let obj_prime = s_prime.resources()[key];
+    if s.resources().contains_key(key) && obj_prime == obj {
+        return;
+    }
+    if msg.src is Controller {
+        let other_id = msg.src->Controller_0;
+        if other_id == controller_id {
+            // The VSTS controller only creates, updates and deletes pods and PVCs.
+            assert(rely_guarantee::vsts_guarantee(controller_id)(s));
+        } else {
+            assert(cluster.controller_models.remove(controller_id).contains_key(other_id));
+            assert(rely_guarantee::vsts_rely(other_id)(s));
+        }
+    }
+    match msg.content->APIRequest_0 {
+        APIRequest::DeleteRequest(req) => {
+            // Only the finalizers change, and they never gain the foregroundDeletion or orphan finalizer
+            // with no propagation policy or the background policy.
+            assert(req.key == key);
+            assert(obj.object_ref() == key);
+            if req.propagation_policy == Some(PropagationPolicy::Background) && obj.metadata.finalizers is Some {
+                let pred = |f: StringView| f != foreground_deletion_finalizer() && f != orphan_finalizer();
+                lemma_filter_to_set_eq_to_set_filter(obj.metadata.finalizers->0, pred);
+                assert(obj_prime.metadata.finalizers == Some(obj.metadata.finalizers->0.filter(pred)));
+            }
+            assert(obj_prime.metadata.owner_references == obj.metadata.owner_references);
+        },
+        APIRequest::UpdateStatusRequest(req) => {
+            assert(obj_prime.metadata.finalizers == obj.metadata.finalizers);
+            assert(obj_prime.metadata.owner_references == obj.metadata.owner_references);
+        },
+        APIRequest::PatchRequest(req) => {
+            // The finalizers and owner references in the patch (if any) replace the existing ones.
+            if s.resources().contains_key(key) {
+                assert(obj.object_ref() == key);
+            }
+        },
+        _ => {}
+    }
+}
+
+// The builtin controllers never delete a VSTS in foreground or with the orphan policy, and the garbage collector
+// only removes finalizers and owner references from a VSTS (and stops it from blocking the deletion of its owners).
+proof fn lemma_builtin_controllers_keep_vsts_rely_vsts_obj(
+    cluster: Cluster, s: ClusterState, s_prime: ClusterState, input: (BuiltinControllerChoice, ObjectRef), msg: Message
+)
+requires
+    cluster.next_step(s, s_prime, Step::BuiltinControllersStep(input)),
+    no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()(s),
+    Cluster::each_object_in_etcd_is_weakly_well_formed()(s),
+    !s.in_flight().contains(msg),
+    s_prime.in_flight().contains(msg),
+ensures
+    msg.content.is_delete_request() && msg.content.get_delete_request().key.kind == VStatefulSetView::kind() ==> {
+        ||| msg.content.get_delete_request().propagation_policy is None
+        ||| msg.content.get_delete_request().propagation_policy == Some(PropagationPolicy::Background)
+    },
+    msg.content.is_update_request() && msg.content.get_update_request().obj.kind == VStatefulSetView::kind()
+        ==> rely_guarantee::vsts_rely_vsts_obj(msg.content.get_update_request().obj),
+{
+    let key = input.1;
+    if msg.content.is_update_request() {
+        // The update keeps the kind of the object at key (the scheduler only updates pods).
+        cluster.lemma_builtin_controllers_update_comes_from_etcd(s, s_prime, input, msg);
+    }
+    if s.resources().contains_key(key) && key.kind == VStatefulSetView::kind() {
+        let obj = s.resources()[key];
+        let owner_references = obj.metadata.owner_references->0;
+        assert(obj.object_ref() == key);
+        assert(rely_guarantee::vsts_rely_vsts_obj(obj));
+        // delete_dependent_in_foreground only deletes obj in foreground if obj blocks the deletion of an owner,
+        // and the finalizers of obj rule out remove_foreground_deletion_finalizer and remove_orphan_finalizer,
+        // so the garbage collector only removes owner references from obj, or stops them from blocking the deletion.
+        assert forall |i| 0 <= i < owner_references.len() implies #[trigger] owner_references[i].block_owner_deletion != Some(true) by {
+            assert(obj.metadata.owner_references_contains(owner_references[i]));
+        }
+        if msg.content.is_update_request() {
+            let req_obj = msg.content.get_update_request().obj;
+            assert forall |o: OwnerReferenceView| #[trigger] req_obj.metadata.owner_references_contains(o)
+                implies o.block_owner_deletion != Some(true) by {
+                if o.block_owner_deletion == Some(true) {
+                    assert(obj.metadata.owner_references_contains(o));
+                }
+            }
+        }
+    }
+}
+
 // only contains one owner_ref, so GC can delete it when the vsts is deleted
 pub open spec fn vsts_pods_only_have_one_vsts_owner_ref() -> StatePred<ClusterState> {
     |s: ClusterState| {
@@ -273,6 +873,7 @@ pub open spec fn all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref
             APIRequest::GetThenUpdateRequest(req) =>
                 req.obj.kind == Kind::PodKind && req.obj.metadata.owner_references == Some(seq![vsts.controller_owner_ref()]),
             APIRequest::GetThenDeleteRequest(_) => true,
+            APIRequest::UpdateStatusRequest(req) => req.obj.kind == VStatefulSetView::kind(),
             _ => false,
         }
     }
@@ -355,7 +956,7 @@ requires
     spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
     spec.entails(always(lift_state(Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)))),
     spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
-    spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+    spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
     spec.entails(always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)))),
     spec.entails(always(lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)))),
     spec.entails(tla_forall(|i| cluster.api_server_next().weak_fairness(i))),
@@ -376,7 +977,7 @@ ensures
         &&& cluster.next()(s, s_prime)
         &&& Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)(s)
         &&& Cluster::no_pending_request_to_api_server_from_non_controllers()(s_prime)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s_prime)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s_prime)
         &&& internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)(s_prime)
         &&& rely_guarantee::vsts_rely_conditions(cluster, controller_id)(s_prime)
     };
@@ -410,7 +1011,7 @@ ensures
         }
     }
     always_to_always_later(spec, lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()));
-    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()));
+    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
     always_to_always_later(spec, lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)));
     always_to_always_later(spec, lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)));
     invariant_n!(
@@ -419,7 +1020,7 @@ ensures
         lift_action(cluster.next()),
         lift_state(Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)),
         later(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers())),
-        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())),
+        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
         later(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id))),
         later(lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)))
     );
@@ -467,7 +1068,7 @@ requires
     spec.entails(always(lift_state(Cluster::desired_state_is(vsts)))),
     spec.entails(always(lift_state(Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)))),
     spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
-    spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+    spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
     spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
     spec.entails(always(lift_state(Cluster::every_in_flight_req_msg_satisfies(all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref(vsts, controller_id))))),
     spec.entails(always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)))),
@@ -494,7 +1095,7 @@ ensures
         &&& Cluster::desired_state_is(vsts)(s)
         &&& Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)(s)
         &&& Cluster::no_pending_request_to_api_server_from_non_controllers()(s_prime)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s_prime)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s_prime)
         &&& Cluster::every_in_flight_req_msg_satisfies(all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref(vsts, controller_id))(s_prime)
         &&& internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)(s_prime)
         &&& rely_guarantee::vsts_rely_conditions(cluster, controller_id)(s_prime)
@@ -538,7 +1139,7 @@ ensures
         }
     };
     always_to_always_later(spec, lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()));
-    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()));
+    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
     always_to_always_later(spec, lift_state(Cluster::every_in_flight_req_msg_satisfies(all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref(vsts, controller_id))));
     always_to_always_later(spec, lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)));
     always_to_always_later(spec, lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)));
@@ -549,7 +1150,7 @@ ensures
         lift_state(Cluster::desired_state_is(vsts)),
         lift_state(Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)),
         later(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers())),
-        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())),
+        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
         later(lift_state(Cluster::every_in_flight_req_msg_satisfies(all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref(vsts, controller_id)))),
         later(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id))),
         later(lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)))
@@ -567,9 +1168,13 @@ pub proof fn lemma_eventually_always_every_valid_update_msg_sets_owner_reference
 requires
     spec.entails(always(lift_action(cluster.next()))),
     spec.entails(always(lift_state(Cluster::desired_state_is(vsts)))),
+    spec.entails(always(lift_state(Cluster::pod_monkey_disabled()))),
+    spec.entails(always(lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()))),
+    spec.entails(always(lift_state(all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)))),
+    spec.entails(always(lift_state(no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()))),
     spec.entails(always(lift_state(Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)))),
     spec.entails(always(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()))),
-    spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+    spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
     spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
     spec.entails(always(lift_state(Cluster::every_in_flight_req_msg_satisfies(all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref(vsts, controller_id))))),
     spec.entails(always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)))),
@@ -600,8 +1205,12 @@ ensures
         &&& cluster.next()(s, s_prime)
         &&& Cluster::desired_state_is(vsts)(s)
         &&& Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)(s)
+        &&& Cluster::pod_monkey_disabled()(s)
+        &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
+        &&& all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)(s)
+        &&& no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()(s)
         &&& Cluster::no_pending_request_to_api_server_from_non_controllers()(s_prime)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s_prime)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s_prime)
         &&& Cluster::every_in_flight_req_msg_satisfies(all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref(vsts, controller_id))(s_prime)
         &&& internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)(s_prime)
         &&& rely_guarantee::vsts_rely_conditions(cluster, controller_id)(s_prime)
@@ -641,6 +1250,18 @@ ensures
                                 assert(rely_guarantee::vsts_rely(id)(s_prime));
                             }
                         },
+                        HostId::BuiltinController => {
+                            let step = choose |step| cluster.next_step(s, s_prime, step);
+                            match step {
+                                Step::BuiltinControllersStep(input) => {
+                                    lemma_builtin_controllers_do_not_update_pods_matching_vsts(cluster, vsts, s, s_prime, input, msg);
+                                },
+                                Step::DuplicateReqStep(req_msg) => {
+                                    assert(s.in_flight().contains(req_msg));
+                                },
+                                _ => {}
+                            }
+                        },
                         _ => {}
                     }
                 }
@@ -648,7 +1269,7 @@ ensures
         }
     };
     always_to_always_later(spec, lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers()));
-    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()));
+    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
     always_to_always_later(spec, lift_state(Cluster::every_in_flight_req_msg_satisfies(all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref(vsts, controller_id))));
     always_to_always_later(spec, lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)));
     always_to_always_later(spec, lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)));
@@ -658,8 +1279,12 @@ ensures
         lift_action(cluster.next()),
         lift_state(Cluster::desired_state_is(vsts)),
         lift_state(Cluster::the_object_in_reconcile_has_spec_and_uid_as(controller_id, vsts)),
+        lift_state(Cluster::pod_monkey_disabled()),
+        lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
+        lift_state(all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)),
+        lift_state(no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()),
         later(lift_state(Cluster::no_pending_request_to_api_server_from_non_controllers())),
-        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())),
+        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
         later(lift_state(Cluster::every_in_flight_req_msg_satisfies(all_pod_requests_from_vsts_controller_carry_only_vsts_owner_ref(vsts, controller_id)))),
         later(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id))),
         later(lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)))
@@ -751,7 +1376,7 @@ ensures
         Cluster::every_create_msg_with_generate_name_matching_key_set_owner_references_as_for_all(cond, req)
     ));
     invariant_n!(spec, partial_spec,
-        lift_state(Cluster::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, req)),
+        lift_state(cluster.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, req)),
         lift_state(Cluster::desired_state_is(vsts)),
         lift_state(all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)),
         lift_state(Cluster::every_create_msg_sets_owner_references_as_for_all(cond, req)),
@@ -791,8 +1416,9 @@ ensures
     );
 }
 
-// stronger version of all_requests_from_builtin_controllers_are_api_delete_requests
+// stronger version of all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests
 // as guaranteed by rely_guarantee condition, PVC's owner_references remains None
+// (pvcs are not owned, so the garbage collector never deletes them, and the node lifecycle controller only deletes pods)
 pub open spec fn buildin_controllers_do_not_delete_pvcs_owned_by_vsts() -> StatePred<ClusterState> {
     |s: ClusterState| {
         forall |msg: Message| {
@@ -801,8 +1427,8 @@ pub open spec fn buildin_controllers_do_not_delete_pvcs_owned_by_vsts() -> State
         } ==> {
             let key = msg.content.get_delete_request().key;
             &&& msg.dst is APIServer
-            &&& msg.content.is_delete_request()
-            &&& !(key.kind == Kind::PersistentVolumeClaimKind
+            &&& msg.content.is_delete_request() || msg.content.is_update_request() || msg.content.is_update_status_request()
+            &&& msg.content.is_delete_request() ==> !(key.kind == Kind::PersistentVolumeClaimKind
                 && exists |vsts_name: StringView| pvc_name_match(key.name, vsts_name))
         }
     }
@@ -817,19 +1443,24 @@ pub open spec fn buildin_controllers_do_not_delete_pods_owned_by_vsts(vsts: VSta
             &&& msg.content is APIRequest
         } ==> {
             let req = msg.content.get_delete_request();
-            &&& msg.content.is_delete_request()
-            &&& req.preconditions is Some
-            &&& req.preconditions.unwrap().uid is Some
-            &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
-            &&& s.resources().contains_key(req.key) ==> {
-                let obj = s.resources()[req.key];
-                // this object is not owned by vsts
-                ||| !(obj.metadata.owner_references_contains(vsts.controller_owner_ref())
-                        && obj.kind == PodView::kind()
-                        && obj.metadata.namespace == vsts.metadata.namespace)
-                // this object is created later with different uid, deletion fails
-                ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+            // update status requests (from the kubelet) keep the metadata and spec of the pod
+            &&& msg.content.is_delete_request() || msg.content.is_update_request() || msg.content.is_update_status_request()
+            &&& msg.content.is_delete_request() ==> {
+                &&& req.preconditions is Some
+                &&& req.preconditions.unwrap().uid is Some
+                &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
+                &&& s.resources().contains_key(req.key) ==> {
+                    let obj = s.resources()[req.key];
+                    // this object is not owned by vsts
+                    ||| !(obj.metadata.owner_references_contains(vsts.controller_owner_ref())
+                            && obj.kind == PodView::kind()
+                            && obj.metadata.namespace == vsts.metadata.namespace)
+                    // this object is created later with different uid, deletion fails
+                    ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+                }
             }
+            // the scheduler is disabled together with the pod monkey, and the garbage collector never updates the pods matching vsts
+            &&& msg.content.is_update_request() ==> !is_vsts_pod_key(vsts)(msg.content.get_update_request().key())
         }
     }
 }
@@ -842,6 +1473,7 @@ requires
     spec.entails(always(lift_state(Cluster::crash_disabled(controller_id)))),
     spec.entails(always(lift_state(Cluster::req_drop_disabled()))),
     spec.entails(always(lift_state(Cluster::pod_monkey_disabled()))),
+    spec.entails(always(lift_state(Cluster::node_failure_disabled()))),
     spec.entails(always(lift_state(Cluster::desired_state_is(vsts)))),
     spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_unique_id()))),
     spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
@@ -852,7 +1484,10 @@ requires
     spec.entails(always(lift_state(Cluster::pending_req_of_key_is_unique_with_unique_id(controller_id, vsts.object_ref())))),
     spec.entails(always(lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)))),
     spec.entails(always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)))),
-    spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+    spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
+    spec.entails(always(lift_state(all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)))),
+    spec.entails(always(lift_state(no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()))),
+    spec.entails(always(lift_state(builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)))),
     spec.entails(tla_forall(|i| cluster.api_server_next().weak_fairness(i))),
     cluster.type_is_installed_in_cluster::<VStatefulSetView>(),
     cluster.controller_models.contains_pair(controller_id, vsts_controller_model()),
@@ -866,19 +1501,22 @@ ensures
         &&& msg.content is APIRequest
     } ==> {
         let req = msg.content.get_delete_request();
-        &&& msg.content.is_delete_request()
-        &&& req.preconditions is Some
-        &&& req.preconditions.unwrap().uid is Some
-        &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
-        &&& s.resources().contains_key(req.key) ==> {
-            let obj = s.resources()[req.key];
-            // this object is not owned by vsts
-            ||| !(obj.metadata.owner_references_contains(vsts.controller_owner_ref())
-                    && obj.kind == PodView::kind()
-                    && obj.metadata.namespace == vsts.metadata.namespace)
-            // this object is created later with different uid, deletion fails
-            ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+        &&& msg.content.is_delete_request() || msg.content.is_update_request() || msg.content.is_update_status_request()
+        &&& msg.content.is_delete_request() ==> {
+            &&& req.preconditions is Some
+            &&& req.preconditions.unwrap().uid is Some
+            &&& req.preconditions.unwrap().uid.unwrap() < s.api_server.uid_counter
+            &&& s.resources().contains_key(req.key) ==> {
+                let obj = s.resources()[req.key];
+                // this object is not owned by vsts
+                ||| !(obj.metadata.owner_references_contains(vsts.controller_owner_ref())
+                        && obj.kind == PodView::kind()
+                        && obj.metadata.namespace == vsts.metadata.namespace)
+                // this object is created later with different uid, deletion fails
+                ||| obj.metadata.uid.unwrap() > req.preconditions.unwrap().uid.unwrap()
+            }
         }
+        &&& msg.content.is_update_request() ==> !is_vsts_pod_key(vsts)(msg.content.get_update_request().key())
     };
     let requirements_antecedent = |msg: Message| {
         &&& msg.src is BuiltinController
@@ -890,6 +1528,7 @@ ensures
         &&& Cluster::crash_disabled(controller_id)(s)
         &&& Cluster::req_drop_disabled()(s)
         &&& Cluster::pod_monkey_disabled()(s)
+        &&& Cluster::node_failure_disabled()(s)
         &&& Cluster::desired_state_is(vsts)(s)
         &&& Cluster::every_in_flight_msg_has_unique_id()(s)
         &&& Cluster::every_in_flight_msg_has_lower_id_than_allocator()(s)
@@ -898,7 +1537,11 @@ ensures
         &&& Cluster::each_object_in_etcd_is_weakly_well_formed()(s)
         &&& Cluster::every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(controller_id)(s)
         &&& Cluster::pending_req_of_key_is_unique_with_unique_id(controller_id, vsts.object_ref())(s)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s_prime)
+        &&& all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)(s)
+        &&& no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()(s)
+        &&& builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)(s)
         &&& forall |vsts| internal_rely_guarantee::no_interfering_request_between_vsts(controller_id, vsts)(s)
         &&& forall |other_id: int| #[trigger] cluster.controller_models.remove(controller_id).contains_key(other_id)
             ==> #[trigger] rely_guarantee::vsts_rely(other_id)(s)
@@ -909,17 +1552,17 @@ ensures
             let step = choose |step| cluster.next_step(s, s_prime, step);
             let key = msg.content.get_delete_request().key;
             match step {
-                Step::BuiltinControllersStep(..) => {
-                    if (!s.in_flight().contains(msg) && requirements_antecedent(msg)) {
+                Step::BuiltinControllersStep(input) => {
+                    if !s.in_flight().contains(msg) && requirements_antecedent(msg) {
+                        lemma_builtin_controllers_do_not_update_pods_matching_vsts(cluster, vsts, s, s_prime, input, msg);
+                    }
+                    if (!s.in_flight().contains(msg) && requirements_antecedent(msg) && msg.content.is_delete_request()) {
                         let obj = s.resources()[key];
                         let owner_references = obj.metadata.owner_references->0;
-                        assert(forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len() ==> {
-                            // the referred owner object does not exist in the cluster state
-                            ||| !s.resources().contains_key(owner_reference_to_object_reference(owner_references[i], key.namespace))
-                            // or it exists but has a different uid
-                            // (which means the actual owner was deleted and another object with the same name gets created again)
-                            ||| s.resources()[owner_reference_to_object_reference(owner_references[i], key.namespace)].metadata.uid != Some(owner_references[i].uid)
-                        });
+                        // evict_pod is disabled together with node failures, so the delete request comes from the garbage collector:
+                        // both run_garbage_collector and delete_dependent_in_foreground delete an object only if none of its owners is solid
+                        assert(forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                            ==> !owner_is_solid(cluster.installed_types, s.resources(), owner_references[i], key));
                         if obj.metadata.owner_references_contains(vsts.controller_owner_ref())
                             && obj.kind == Kind::PodKind
                             && obj.metadata.namespace == vsts.metadata.namespace {
@@ -928,6 +1571,12 @@ ensures
                         }
                     }
                 },
+                Step::DuplicateReqStep(req_msg) => {
+                    // The copy has the same src and content as the request in flight.
+                    if !s.in_flight().contains(msg) {
+                        assert(s.in_flight().contains(req_msg));
+                    }
+                },
                 Step::APIServerStep(req_msg_opt) => {
                     let req_msg = req_msg_opt.unwrap();
                     if s.in_flight().contains(msg) && requirements(msg, s) && s_prime.resources().contains_key(key) {
@@ -973,7 +1622,11 @@ ensures
                                                 }
                                             },
                                             HostId::BuiltinController => {
-                                                assert(req_msg.content.is_delete_request());
+                                                // Deletions and status updates keep the owner references, and an update that sets
+                                                // the owner reference to vsts is sent for a pod matching vsts, which keeps its owner references
+                                                assert(req_msg.content.is_delete_request() || req_msg.content.is_update_request()
+                                                    || req_msg.content.is_update_status_request());
+                                                assert(builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, req_msg, s));
                                             },
                                             _ => { // no_pending_request_to_api_server_from_non_controllers
                                                 assert(false);
@@ -989,6 +1642,7 @@ ensures
             }
         }
     };
+    always_to_always_later(spec, lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
     invariant_n!(
         spec, lift_action(stronger_next),
         lift_action(Cluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
@@ -996,6 +1650,7 @@ ensures
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::desired_state_is(vsts)),
         lift_state(Cluster::every_in_flight_msg_has_unique_id()),
         lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()),
@@ -1004,7 +1659,11 @@ ensures
         lift_state(Cluster::each_object_in_etcd_is_weakly_well_formed()),
         lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(controller_id)),
         lift_state(Cluster::pending_req_of_key_is_unique_with_unique_id(controller_id, vsts.object_ref())),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
+        later(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
+        lift_state(all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)),
+        lift_state(no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()),
+        lift_state(builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)),
         lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)),
         lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id))
     );
@@ -1047,8 +1706,8 @@ ensures
         } implies {
             let key = msg.content.get_delete_request().key;
             &&& msg.dst is APIServer
-            &&& msg.content.is_delete_request()
-            &&& !(key.kind == Kind::PersistentVolumeClaimKind
+            &&& msg.content.is_delete_request() || msg.content.is_update_request() || msg.content.is_update_status_request()
+            &&& msg.content.is_delete_request() ==> !(key.kind == Kind::PersistentVolumeClaimKind
                 && exists |vsts_name: StringView| pvc_name_match(key.name, vsts_name))
         } by {
             if s.in_flight().contains(msg) {} else {}
@@ -1097,7 +1756,7 @@ ensures
         &&& rely_guarantee::vsts_rely_conditions_pod_monkey()(s)
         &&& Cluster::no_pending_request_to_api_server_from_api_server_or_external()(s)
         &&& Cluster::all_requests_from_pod_monkey_are_api_pod_requests()(s)
-        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()(s)
+        &&& Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()(s)
         &&& cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()(s)
         &&& internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)(s)
         &&& every_msg_from_vsts_controller_carries_vsts_key(controller_id)(s)
@@ -1105,7 +1764,7 @@ ensures
     cluster.lemma_always_there_is_the_controller_state(spec, controller_id);
     cluster.lemma_always_no_pending_request_to_api_server_from_api_server_or_external(spec);
     cluster.lemma_always_all_requests_from_pod_monkey_are_api_pod_requests(spec);
-    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_requests(spec);
+    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests(spec);
     cluster.lemma_always_every_in_flight_req_msg_from_controller_has_valid_controller_id(spec);
     internal_rely_guarantee::internal_guarantee_condition_holds_on_all_vsts(spec, cluster, controller_id);
     lemma_always_every_msg_from_vsts_controller_carries_vsts_key(spec, cluster, controller_id);
@@ -1183,7 +1842,7 @@ ensures
                                 assert(s.in_flight().contains(msg)); // trigger
                             }
                         },
-                        HostId::BuiltinController => {}, // must be delete requests
+                        HostId::BuiltinController => {}, // must be delete, update or update status requests, which create no objects
                         HostId::PodMonkey => {}, // must be pod requests
                         _ => {}
                     }
@@ -1202,7 +1861,7 @@ ensures
         lift_state(rely_guarantee::vsts_rely_conditions_pod_monkey()),
         lift_state(Cluster::no_pending_request_to_api_server_from_api_server_or_external()),
         lift_state(Cluster::all_requests_from_pod_monkey_are_api_pod_requests()),
-        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()),
+        lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()),
         lift_state(cluster.every_in_flight_req_msg_from_controller_has_valid_controller_id()),
         lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)),
         lift_state(every_msg_from_vsts_controller_carries_vsts_key(controller_id))
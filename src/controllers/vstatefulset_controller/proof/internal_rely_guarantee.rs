@@ -58,6 +58,8 @@ requires
     helper_invariants::all_pods_in_etcd_matching_vsts_have_correct_owner_ref_and_no_deletion_timestamp(vsts)(s),
     helper_invariants::all_pods_in_etcd_matching_vsts_have_correct_owner_ref_and_no_deletion_timestamp(vsts)(s_prime),
     helper_invariants::buildin_controllers_do_not_delete_pods_owned_by_vsts(vsts)(s),
+    helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)(s),
+    helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)(s),
     // 1. rely conditions for other controllers
     forall |other_id| #[trigger] cluster.controller_models.remove(controller_id).contains_key(other_id)
         ==> vsts_rely(other_id)(s),
@@ -139,7 +141,7 @@ ensures
                                 },
                                 ..make_vsts()
                             };
-                            // so msg can only be list, create, get_then_delete and get_then_update
+                            // so msg can only be list, create, get_then_delete, get_then_update and update_status of other_vsts
                             assert(no_interfering_request_between_vsts(controller_id, other_vsts)(s));
                             if msg.content.is_get_then_delete_request() || msg.content.is_get_then_update_request() {
                                 let req_owner_ref = match msg.content->APIRequest_0 {
@@ -195,7 +197,13 @@ ensures
                             assert(obj.metadata.owner_references_contains(vsts.controller_owner_ref())
                                 && obj.kind == PodView::kind()
                                 && obj.metadata.namespace == vsts.metadata.namespace);
+                        } else if resource_update_request_msg(k)(msg) {
+                            // by all_pods_in_etcd_owned_by_vsts_match_vsts, and the builtin controllers do not update the pods matching vsts
+                            assert(pod_name_match(k.name, vsts.metadata.name->0));
+                            assert(helper_invariants::is_vsts_pod_key(vsts)(k));
+                            assert(false);
                         }
+                        // The kubelet only updates the status of the pod, which keeps its metadata and spec
                     }, // buildin_controllers_do_not_delete_pods_owned_by_vsts
                     _ => {
                         assert(post);
@@ -314,6 +322,11 @@ ensures
                             }
                         }
                     },
+                    HostId::BuiltinController => {
+                        // The builtin controllers create no objects, and only set the owner reference to vsts on the pods
+                        // matching vsts, which they never update, so the pod is covered by the ==> direction
+                        assert(helper_invariants::builtin_req_keeps_owner_references_of_pods_matching_vsts(vsts, msg, s));
+                    },
                     _ => {},
                 }
             }
@@ -552,7 +565,8 @@ pub open spec fn no_interfering_request_between_vsts(controller_id: int, vsts: V
             APIRequest::CreateRequest(req) => vsts_internal_guarantee_create_req(req, vsts),
             APIRequest::GetThenDeleteRequest(req) => vsts_internal_guarantee_get_then_delete_req(req, vsts),
             APIRequest::GetThenUpdateRequest(req) => vsts_internal_guarantee_get_then_update_req(req, vsts),
-            // VSTS controller will not issue DeleteRequest, UpdateRequest and UpdateStatusRequest
+            APIRequest::UpdateStatusRequest(req) => vsts_internal_guarantee_update_status_req(req, vsts),
+            // VSTS controller will not issue DeleteRequest and UpdateRequest
             _ => false
         }
     }
@@ -597,6 +611,11 @@ pub open spec fn vsts_internal_guarantee_get_then_update_req(req: GetThenUpdateR
     &&& req.obj.metadata.deletion_timestamp is None
     &&& req.obj.metadata.finalizers is None
 }
+
+// VSTS controller only updates the status of the specific vsts instance
+pub open spec fn vsts_internal_guarantee_update_status_req(req: UpdateStatusRequest, vsts: VStatefulSetView) -> bool {
+    req.key() == vsts.object_ref()
+}
 // similar to local_pods_and_pvcs_are_bound_to_vsts
 // helper invariant to prove both (external) guarantee conditions and internal guarantee conditions
 
@@ -645,10 +664,10 @@ pub open spec fn local_pods_and_pvcs_are_bound_to_vsts_with_key(controller_id: i
         &&& s.ongoing_reconciles(controller_id)[cr_key].pending_req_msg is Some
         &&& req_msg.dst is APIServer
         &&& req_msg.content.is_list_request()
-        &&& req_msg.content.get_list_request() == ListRequest {
-            kind: Kind::PodKind,
-            namespace: cr_key.namespace,
-        }
+        &&& req_msg.content.get_list_request().kind == Kind::PodKind
+        &&& req_msg.content.get_list_request().namespace == cr_key.namespace
+        &&& req_msg.content.get_list_request().label_selector is None
+        &&& req_msg.content.get_list_request().is_full_quorum_list()
         &&& forall |msg| {
             &&& #[trigger] s.in_flight().contains(msg)
             &&& s.ongoing_reconciles(controller_id)[cr_key].pending_req_msg is Some
@@ -737,10 +756,10 @@ ensures
                         let req_msg = s_prime.ongoing_reconciles(controller_id)[cr_key].pending_req_msg->0;
                         assert(req_msg.dst is APIServer);
                         assert(req_msg.content.is_list_request());
-                        assert(req_msg.content.get_list_request() == ListRequest {
-                            kind: Kind::PodKind,
-                            namespace: cr_key.namespace,
-                        });
+                        assert(req_msg.content.get_list_request().kind == Kind::PodKind);
+                        assert(req_msg.content.get_list_request().namespace == cr_key.namespace);
+                        assert(req_msg.content.get_list_request().label_selector is None);
+                        assert(req_msg.content.get_list_request().is_full_quorum_list());
                         assert(forall |msg| {
                             &&& #[trigger] s_prime.in_flight().contains(msg)
                             &&& msg.src is APIServer
@@ -887,6 +906,7 @@ ensures
                                 let selector = |o: DynamicObjectView| {
                                     &&& o.object_ref().namespace == req_msg.content.get_list_request().namespace
                                     &&& o.object_ref().kind == req_msg.content.get_list_request().kind
+                                    &&& req_msg.content.get_list_request().selects(o)
                                 };
                                 let selected_elements = s.resources().values().filter(selector);
                                 assert(resp_objs.contains(resp_objs[i])); // trigger
@@ -1072,6 +1092,7 @@ pub proof fn internal_guarantee_condition_holds(
                     APIRequest::CreateRequest(req) => vsts_internal_guarantee_create_req(req, vsts),
                     APIRequest::GetThenDeleteRequest(req) => vsts_internal_guarantee_get_then_delete_req(req, vsts),
                     APIRequest::GetThenUpdateRequest(req) => vsts_internal_guarantee_get_then_update_req(req, vsts),
+                    APIRequest::UpdateStatusRequest(req) => vsts_internal_guarantee_update_status_req(req, vsts),
                     _ => false,
                 } by {
                     if s.in_flight().contains(msg) {} // used to instantiate invariant's trigger.
@@ -1090,6 +1111,7 @@ pub proof fn internal_guarantee_condition_holds(
                     APIRequest::CreateRequest(req) => vsts_internal_guarantee_create_req(req, vsts),
                     APIRequest::GetThenDeleteRequest(req) => vsts_internal_guarantee_get_then_delete_req(req, vsts),
                     APIRequest::GetThenUpdateRequest(req) => vsts_internal_guarantee_get_then_update_req(req, vsts),
+                    APIRequest::UpdateStatusRequest(req) => vsts_internal_guarantee_update_status_req(req, vsts),
                     _ => false,
                 } by {
                     if s.in_flight().contains(msg) {} // used to instantiate invariant's trigger.
@@ -1147,9 +1169,9 @@ pub proof fn internal_guarantee_condition_holds(
                                     assert(vsts_internal_guarantee_get_then_delete_req(req, vsts));
                                 },
                                 DeleteOutdated => {
-                                    assert(msg.content.is_get_then_delete_request());
-                                    let req = msg.content.get_get_then_delete_request();
                                     if let Some(pod) = get_largest_unmatched_pods(triggering_vsts, state.needed) {
+                                        assert(msg.content.is_get_then_delete_request());
+                                        let req = msg.content.get_get_then_delete_request();
                                         seq_filter_contains_implies_seq_contains(state.needed, outdated_pod_filter(triggering_vsts), Some(pod));
                                         // trigger for local_pods_and_pvcs_are_bound_to_vsts_with_key_in_local_state
                                         assert(exists |i: int| 0 <= i < state.needed.len() && #[trigger] state.needed[i] == Some(pod));
@@ -1157,10 +1179,15 @@ pub proof fn internal_guarantee_condition_holds(
                                         let ord = get_ordinal(vsts.metadata.name->0, pod.metadata.name->0)->0;
                                         get_ordinal_eq_pod_name(vsts.metadata.name->0, ord, pod.metadata.name->0);
                                         assert(req.key().name == pod.metadata.name->0);
+                                        assert(vsts_internal_guarantee_get_then_delete_req(req, vsts));
                                     } else {
-                                        assert(false); // should not have any new message
+                                        // the status update targets the vsts under reconciliation
+                                        assert(msg.content.is_update_status_request());
+                                        let req = msg.content.get_update_status_request();
+                                        assert(req.obj == updated_vsts_status(triggering_vsts).marshal());
+                                        assert(triggering_vsts.object_ref() == cr_key);
+                                        assert(vsts_internal_guarantee_update_status_req(req, vsts));
                                     }
-                                    assert(vsts_internal_guarantee_get_then_delete_req(req, vsts));
                                 },
                                 _ => {
                                     // other cases are trivial
@@ -1182,6 +1209,7 @@ pub proof fn internal_guarantee_condition_holds(
                     APIRequest::CreateRequest(req) => vsts_internal_guarantee_create_req(req, vsts),
                     APIRequest::GetThenDeleteRequest(req) => vsts_internal_guarantee_get_then_delete_req(req, vsts),
                     APIRequest::GetThenUpdateRequest(req) => vsts_internal_guarantee_get_then_update_req(req, vsts),
+                    APIRequest::UpdateStatusRequest(req) => vsts_internal_guarantee_update_status_req(req, vsts),
                     _ => false,
                 } by {
                     if s.in_flight().contains(msg) {} // used to instantiate invariant's trigger.
@@ -43,6 +43,7 @@ ensures
         // changing the order of fields makes a difference
         &&& o.object_ref().namespace == vsts.metadata.namespace->0
         &&& o.object_ref().kind == Kind::PodKind
+        &&& req.selects(o)
     }; 
     let resp_msg = handle_list_request_msg(req_msg, s.api_server).1;
     assert(s_prime.in_flight().contains(resp_msg));
@@ -323,6 +324,30 @@ ensures
     return resp_msg;
 }
 
+pub proof fn lemma_update_status_vsts_request_returns_resp_and_only_changes_vsts(
+    s: ClusterState, s_prime: ClusterState, vsts: VStatefulSetView, cluster: Cluster, controller_id: int, req_msg: Message,
+) -> (resp_msg: Message)
+requires
+    cluster.type_is_installed_in_cluster::<VStatefulSetView>(),
+    cluster.next_step(s, s_prime, Step::APIServerStep(Some(req_msg))),
+    Cluster::pending_req_msg_is(controller_id, s, vsts.object_ref(), req_msg),
+    cluster_invariants_since_reconciliation(cluster, vsts, controller_id)(s),
+    req_msg_is_update_status_vsts_req(vsts.object_ref(), controller_id, req_msg),
+ensures
+    s_prime.in_flight().contains(resp_msg),
+    resp_msg_matches_req_msg(resp_msg, req_msg),
+    resp_msg.content.is_update_status_response(),
+    // no side effect on objects other than vsts
+    forall |key: ObjectRef| key != vsts.object_ref() ==> {
+        &&& s_prime.resources().contains_key(key) == s.resources().contains_key(key)
+        &&& s_prime.resources()[key] == s.resources()[key]
+    },
+{
+    let resp_msg = handle_update_status_request_msg(cluster.installed_types, req_msg, s.api_server).1;
+    assert(s_prime.in_flight().contains(resp_msg));
+    return resp_msg;
+}
+
 // *** shield lemma is heavily abused below this line *** //
 pub proof fn lemma_api_request_other_than_pending_req_msg_maintains_local_state_coherence(
     s: ClusterState, s_prime: ClusterState, vsts: VStatefulSetView, cluster: Cluster, controller_id: int, req_msg: Message
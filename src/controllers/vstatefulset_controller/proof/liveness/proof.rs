@@ -194,6 +194,9 @@ pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(provided_
     if i == 1 {
         cluster.lemma_true_leads_to_crash_always_disabled(spec, controller_id);
         cluster.lemma_true_leads_to_req_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_resp_drop_always_disabled(spec);
+        cluster.lemma_true_leads_to_req_dup_always_disabled(spec);
+        cluster.lemma_true_leads_to_node_failure_always_disabled(spec);
         cluster.lemma_true_leads_to_pod_monkey_always_disabled(spec);
         cluster.lemma_true_leads_to_always_the_object_in_schedule_has_spec_and_uid_as(spec, controller_id, vsts);
         helper_invariants::lemma_eventually_always_vsts_in_schedule_has_the_same_name_and_namespace_as_vsts(spec, vsts, cluster, controller_id);
@@ -203,6 +206,9 @@ pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(provided_
             true_pred(),
             lift_state(Cluster::crash_disabled(controller_id)),
             lift_state(Cluster::req_drop_disabled()),
+            lift_state(Cluster::resp_drop_disabled()),
+            lift_state(Cluster::req_dup_disabled()),
+            lift_state(Cluster::node_failure_disabled()),
             lift_state(Cluster::pod_monkey_disabled()),
             lift_state(Cluster::the_object_in_schedule_has_spec_and_uid_as(controller_id, vsts)),
             lift_state(helper_invariants::vsts_in_schedule_has_the_same_name_and_namespace_as_vsts(vsts, controller_id)),
@@ -639,6 +645,7 @@ pub proof fn spec_entails_pending_request_invariants_part8(spec: TempPred<Cluste
     ensures
         spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteCondemned]))))),
         spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated]))))),
+        spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]))))),
 {
     cluster.lemma_always_there_is_the_controller_state(spec, controller_id);
     cluster.lemma_always_there_is_no_request_msg_to_external_from_controller(spec, controller_id);
@@ -654,6 +661,11 @@ pub proof fn spec_entails_pending_request_invariants_part8(spec: TempPred<Cluste
         cluster.lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_state(spec, controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated]);
     }
     spec_entails_always_tla_forall_equality(spec, |vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated])));
+    assert forall |vsts: VStatefulSetView| spec.entails(always(lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, #[trigger] vsts.object_ref(), at_step_or![AfterUpdateStatus])))) by {
+        cluster.lemma_always_pending_req_of_key_is_unique_with_unique_id(spec, controller_id, vsts.object_ref());
+        cluster.lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_state(spec, controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]);
+    }
+    spec_entails_always_tla_forall_equality(spec, |vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus])));
 }
 
 pub proof fn spec_entails_pending_request_invariants(spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int)
@@ -682,6 +694,7 @@ pub proof fn spec_entails_pending_request_invariants(spec: TempPred<ClusterState
         spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateNeeded]))))),
         spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteCondemned]))))),
         spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated]))))),
+        spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]))))),
 {
     // due to the complexity of transitions
     spec_entails_pending_request_invariants_part1(spec, cluster, controller_id);
@@ -741,14 +754,15 @@ pub proof fn spec_entails_all_invariants(spec: TempPred<ClusterState>, vsts: VSt
     internal_rely_guarantee::internal_guarantee_condition_holds_on_all_vsts(spec, cluster, controller_id);
 
     // Additional invariants needed by cluster_invariants_since_reconciliation
-    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_requests(spec);
+    cluster.lemma_always_all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests(spec);
     helper_invariants::lemma_always_all_pvcs_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_or_owner_ref(spec, cluster, controller_id);
     helper_invariants::lemma_always_buildin_controllers_do_not_delete_pvcs_owned_by_vsts(spec, cluster, controller_id);
     helper_invariants::lemma_always_every_msg_from_vsts_controller_carries_vsts_key(spec, cluster, controller_id);
     cluster.lemma_always_every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(spec, controller_id);
     helper_invariants::lemma_always_all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(spec, cluster, controller_id, vsts);
+    helper_invariants::lemma_always_no_vsts_carries_gc_finalizer_or_blocks_owner_deletion(spec, cluster, controller_id);
 
-    // Combine the 17 pending request invariants into pending_request_invariants
+    // Combine the 18 pending request invariants into pending_request_invariants
     spec_entails_pending_request_invariants_combine(spec, cluster, controller_id);
 
     // Combine all into derived_invariants_since_beginning
@@ -769,6 +783,9 @@ ensures
     // Extract from invariants_since_phase_i
     assert(stable_spec.entails(always(lift_state(Cluster::crash_disabled(controller_id)))));
     assert(stable_spec.entails(always(lift_state(Cluster::req_drop_disabled()))));
+    assert(stable_spec.entails(always(lift_state(Cluster::resp_drop_disabled()))));
+    assert(stable_spec.entails(always(lift_state(Cluster::req_dup_disabled()))));
+    assert(stable_spec.entails(always(lift_state(Cluster::node_failure_disabled()))));
     assert(stable_spec.entails(always(lift_state(Cluster::pod_monkey_disabled()))));
 
     // Extract from derived_invariants_since_beginning
@@ -850,7 +867,7 @@ ensures
     );
     entails_trans(stable_spec,
         derived_invariants_since_beginning(vsts, cluster, controller_id),
-        always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))
+        always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))
     );
     entails_trans(stable_spec,
         derived_invariants_since_beginning(vsts, cluster, controller_id),
@@ -868,6 +885,14 @@ ensures
         derived_invariants_since_beginning(vsts, cluster, controller_id),
         always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(controller_id)))
     );
+    entails_trans(stable_spec,
+        derived_invariants_since_beginning(vsts, cluster, controller_id),
+        always(lift_state(helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)))
+    );
+    entails_trans(stable_spec,
+        derived_invariants_since_beginning(vsts, cluster, controller_id),
+        always(lift_state(helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)))
+    );
 
     // Extract pending_req_of_key for specific vsts from the forall version
     entails_trans(stable_spec,
@@ -931,6 +956,9 @@ ensures
         lift_state(cluster_invariants_since_reconciliation(cluster, vsts, controller_id)),
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::resp_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
         lift_state(Cluster::every_in_flight_msg_has_unique_id()),
         lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()),
@@ -960,6 +988,8 @@ ensures
         lift_state(helper_invariants::vsts_in_ongoing_reconciles_has_no_deletion_timestamp(vsts, controller_id)),
         lift_state(helper_invariants::buildin_controllers_do_not_delete_pvcs_owned_by_vsts()),
         lift_state(helper_invariants::buildin_controllers_do_not_delete_pods_owned_by_vsts(vsts)),
+        lift_state(helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)),
+        lift_state(helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)),
         lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)),
         lift_state(internal_rely_guarantee::every_msg_from_vsts_controller_carries_vsts_key(controller_id)),
         lift_state(vsts_rely_conditions(cluster, controller_id))
@@ -995,6 +1025,9 @@ pub proof fn eventually_stable_reconciliation_holds_per_cr(spec: TempPred<Cluste
     entails_trans(stable_spec, next_with_wf(cluster, controller_id), tla_forall(|i| cluster.schedule_controller_reconcile().weak_fairness((controller_id, i))));
     entails_trans(stable_spec, invariants_since_phase_i(controller_id, vsts), always(lift_state(Cluster::crash_disabled(controller_id))));
     entails_trans(stable_spec, invariants_since_phase_i(controller_id, vsts), always(lift_state(Cluster::req_drop_disabled())));
+    entails_trans(stable_spec, invariants_since_phase_i(controller_id, vsts), always(lift_state(Cluster::resp_drop_disabled())));
+    entails_trans(stable_spec, invariants_since_phase_i(controller_id, vsts), always(lift_state(Cluster::req_dup_disabled())));
+    entails_trans(stable_spec, invariants_since_phase_i(controller_id, vsts), always(lift_state(Cluster::node_failure_disabled())));
     entails_trans(stable_spec, invariants_since_phase_i(controller_id, vsts), always(lift_state(Cluster::pod_monkey_disabled())));
     entails_trans(stable_spec, derived_invariants_since_beginning(vsts, cluster, controller_id), always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id))));
     entails_trans(stable_spec, derived_invariants_since_beginning(vsts, cluster, controller_id), always(lift_state(Cluster::there_is_no_request_msg_to_external_from_controller(controller_id))));
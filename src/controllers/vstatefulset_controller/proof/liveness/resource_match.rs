@@ -3024,7 +3024,7 @@ ensures
                     },
                     Step::ControllerStep(input) => {
                         if input.0 == controller_id && input.2 == Some(vsts.object_ref()) {
-                            lemma_from_delete_outdated_to_after_delete_outdated_or_done(s, s_prime, vsts, cluster, controller_id, condemned_len, outdated_len);
+                            lemma_from_delete_outdated_to_after_delete_outdated_or_after_update_status(s, s_prime, vsts, cluster, controller_id, condemned_len, outdated_len);
                         }
                     },
                     Step::BuiltinControllersStep(_) => {},
@@ -3034,7 +3034,7 @@ ensures
             let input = (None, Some(vsts.object_ref()));
             assert forall |s, s_prime| delete_outdated_state(s) && #[trigger] stronger_next(s, s_prime) && cluster.controller_next().forward((controller_id, input.0, input.1))(s, s_prime)
                 implies after_delete_outdated_state_with_request(s_prime) by {
-                lemma_from_delete_outdated_to_after_delete_outdated_or_done(s, s_prime, vsts, cluster, controller_id, condemned_len, outdated_len);
+                lemma_from_delete_outdated_to_after_delete_outdated_or_after_update_status(s, s_prime, vsts, cluster, controller_id, condemned_len, outdated_len);
             }
             cluster.lemma_pre_leads_to_post_by_controller(
                 spec, controller_id, input, stronger_next, ControllerStep::ContinueReconcile, delete_outdated_state, after_delete_outdated_state_with_request
@@ -3115,16 +3115,32 @@ ensures
             lift_state(done_state)
         );
     } else {
-        assert(spec.entails(lift_state(delete_outdated_state).leads_to(lift_state(done_state)))) by {
-            lemma_spec_entails_delete_outdated_leads_to_after_delete_outdated_or_done(
+        let after_update_status_state_with_request = and!(
+            at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus]),
+            local_state_is_valid_and_coherent(vsts, controller_id),
+            pending_update_status_req_in_flight(vsts, controller_id),
+            pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
+        );
+        assert(spec.entails(lift_state(delete_outdated_state).leads_to(lift_state(after_update_status_state_with_request)))) by {
+            lemma_spec_entails_delete_outdated_leads_to_after_delete_outdated_or_after_update_status(
+                vsts, spec, cluster, controller_id, condemned_len, outdated_len
+            );
+        }
+        assert(spec.entails(lift_state(after_update_status_state_with_request).leads_to(lift_state(done_state)))) by {
+            lemma_spec_entails_after_update_status_leads_to_done(
                 vsts, spec, cluster, controller_id, condemned_len, outdated_len
             );
         }
+        leads_to_trans(spec,
+            lift_state(delete_outdated_state),
+            lift_state(after_update_status_state_with_request),
+            lift_state(done_state)
+        );
     }
 }
 
 #[verifier(spinoff_prover)]
-pub proof fn lemma_spec_entails_delete_outdated_leads_to_after_delete_outdated_or_done(
+pub proof fn lemma_spec_entails_delete_outdated_leads_to_after_delete_outdated_or_after_update_status(
     vsts: VStatefulSetView, spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int, condemned_len: nat, outdated_len: nat
 )
 requires
@@ -3154,9 +3170,9 @@ ensures
         no_pending_req_in_cluster(vsts, controller_id),
         pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
     )).leads_to(lift_state(and!(
-        at_vsts_step(vsts, controller_id, at_step![Done]),
+        at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus]),
         local_state_is_valid_and_coherent(vsts, controller_id),
-        no_pending_req_in_cluster(vsts, controller_id),
+        pending_update_status_req_in_flight(vsts, controller_id),
         pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
     )))),
 {
@@ -3187,9 +3203,9 @@ ensures
         )
     } else {
         and!(
-            at_vsts_step(vsts, controller_id, at_step![Done]),
+            at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus]),
             local_state_is_valid_and_coherent(vsts, controller_id),
-            no_pending_req_in_cluster(vsts, controller_id),
+            pending_update_status_req_in_flight(vsts, controller_id),
             pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
         )
     };
@@ -3203,7 +3219,7 @@ ensures
                 },
                 Step::ControllerStep(input) => {
                     if input.0 == controller_id && input.2 == Some(vsts.object_ref()) {
-                        lemma_from_delete_outdated_to_after_delete_outdated_or_done(s, s_prime, vsts, cluster, controller_id, condemned_len, outdated_len);
+                        lemma_from_delete_outdated_to_after_delete_outdated_or_after_update_status(s, s_prime, vsts, cluster, controller_id, condemned_len, outdated_len);
                     }
                 },
                 Step::BuiltinControllersStep(_) => {},
@@ -3213,7 +3229,7 @@ ensures
         let input = (None, Some(vsts.object_ref()));
         assert forall |s, s_prime| delete_outdated_state(s) && #[trigger] stronger_next(s, s_prime) && cluster.controller_next().forward((controller_id, input.0, input.1))(s, s_prime)
             implies next_state(s_prime) by {
-            lemma_from_delete_outdated_to_after_delete_outdated_or_done(s, s_prime, vsts, cluster, controller_id, condemned_len, outdated_len);
+            lemma_from_delete_outdated_to_after_delete_outdated_or_after_update_status(s, s_prime, vsts, cluster, controller_id, condemned_len, outdated_len);
         }
         cluster.lemma_pre_leads_to_post_by_controller(
             spec, controller_id, input, stronger_next, ControllerStep::ContinueReconcile, delete_outdated_state, next_state
@@ -3351,6 +3367,188 @@ ensures
     );
 }
 
+#[verifier(rlimit(200))]
+#[verifier(spinoff_prover)]
+pub proof fn lemma_spec_entails_after_update_status_leads_to_done(
+    vsts: VStatefulSetView, spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int, condemned_len: nat, outdated_len: nat
+)
+requires
+    cluster.type_is_installed_in_cluster::<VStatefulSetView>(),
+    cluster.controller_models.contains_pair(controller_id, vsts_controller_model()),
+    spec.entails(always(lift_state(cluster_invariants_since_reconciliation(cluster, vsts, controller_id)))),
+    spec.entails(always(lift_action(cluster.next()))),
+    spec.entails(tla_forall(|i| cluster.api_server_next().weak_fairness(i))),
+    spec.entails(tla_forall(|i: (Option<Message>, Option<ObjectRef>)| cluster.controller_next().weak_fairness((controller_id, i.0, i.1)))),
+    spec.entails(always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)))),
+    spec.entails(always(lift_state(rely_guarantee::vsts_rely_conditions(cluster, controller_id)))),
+    outdated_len == 0,
+ensures
+    spec.entails(lift_state(and!(
+        at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus]),
+        local_state_is_valid_and_coherent(vsts, controller_id),
+        pending_update_status_req_in_flight(vsts, controller_id),
+        pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
+    )).leads_to(lift_state(and!(
+        at_vsts_step(vsts, controller_id, at_step![Done]),
+        local_state_is_valid_and_coherent(vsts, controller_id),
+        no_pending_req_in_cluster(vsts, controller_id),
+        pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
+    )))),
+{
+    let stronger_next = |s, s_prime: ClusterState| {
+        &&& cluster.next()(s, s_prime)
+        &&& cluster_invariants_since_reconciliation(cluster, vsts, controller_id)(s)
+        &&& cluster_invariants_since_reconciliation(cluster, vsts, controller_id)(s_prime)
+    };
+    always_to_always_later(spec, lift_state(cluster_invariants_since_reconciliation(cluster, vsts, controller_id)));
+    combine_spec_entails_always_n!(spec,
+        lift_action(stronger_next),
+        lift_action(cluster.next()),
+        lift_state(cluster_invariants_since_reconciliation(cluster, vsts, controller_id)),
+        later(lift_state(cluster_invariants_since_reconciliation(cluster, vsts, controller_id)))
+    );
+    let after_update_status_state_with_request = and!(
+        at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus]),
+        local_state_is_valid_and_coherent(vsts, controller_id),
+        pending_update_status_req_in_flight(vsts, controller_id),
+        pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
+    );
+    let req_msg_is_pending_msg_at_after_update_status_state = |msg| and!(
+        at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus]),
+        local_state_is_valid_and_coherent(vsts, controller_id),
+        pending_update_status_req_in_flight(vsts, controller_id),
+        pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len),
+        req_msg_is(msg, vsts.object_ref(), controller_id)
+    );
+    let after_update_status_state_with_response = and!(
+        at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus]),
+        local_state_is_valid_and_coherent(vsts, controller_id),
+        pending_update_status_resp_in_flight(vsts, controller_id),
+        pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
+    );
+    let resp_msg_is_pending_msg_at_after_update_status_state = |msg| and!(
+        at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus]),
+        local_state_is_valid_and_coherent(vsts, controller_id),
+        resp_msg_is_pending_update_status_resp_in_flight(vsts, controller_id, msg),
+        pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
+    );
+    let done_state = and!(
+        at_vsts_step(vsts, controller_id, at_step![Done]),
+        local_state_is_valid_and_coherent(vsts, controller_id),
+        no_pending_req_in_cluster(vsts, controller_id),
+        pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)
+    );
+    // the API server handles the status update, which only touches the vsts object
+    assert(spec.entails(lift_state(after_update_status_state_with_request).leads_to(lift_state(after_update_status_state_with_response)))) by {
+        assert forall |ex: Execution<ClusterState>| #[trigger] lift_state(after_update_status_state_with_request).satisfied_by(ex) implies
+            tla_exists(|msg| lift_state(req_msg_is_pending_msg_at_after_update_status_state(msg))).satisfied_by(ex) by {
+            let s = ex.head();
+            let req_msg = s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0;
+            assert((|msg| lift_state(req_msg_is_pending_msg_at_after_update_status_state(msg)))(req_msg).satisfied_by(ex));
+        }
+        entails_implies_leads_to(spec,
+            lift_state(after_update_status_state_with_request),
+            tla_exists(|msg| lift_state(req_msg_is_pending_msg_at_after_update_status_state(msg)))
+        );
+        assert forall |msg| spec.entails(lift_state(#[trigger] req_msg_is_pending_msg_at_after_update_status_state(msg)).leads_to(lift_state(after_update_status_state_with_response))) by {
+            assert forall |s, s_prime| req_msg_is_pending_msg_at_after_update_status_state(msg)(s) && #[trigger] stronger_next(s, s_prime) implies
+                req_msg_is_pending_msg_at_after_update_status_state(msg)(s_prime) || after_update_status_state_with_response(s_prime) by {
+                let step = choose |step| cluster.next_step(s, s_prime, step);
+                match step {
+                    Step::APIServerStep(input) => {
+                        if input == Some(msg) {
+                            lemma_from_after_send_update_status_req_to_receive_update_status_resp(s, s_prime, vsts, cluster, controller_id, msg, condemned_len, outdated_len);
+                        } else {
+                            lemma_api_request_other_than_pending_req_msg_maintains_local_state_coherence(s, s_prime, vsts, cluster, controller_id, input->0);
+                        }
+                    },
+                    _ => {
+                        assert(s_prime.in_flight().contains(msg));
+                        assert(s_prime.resources() == s.resources());
+                    }
+                }
+            }
+            let input = Some(msg);
+            assert forall |s, s_prime| req_msg_is_pending_msg_at_after_update_status_state(msg)(s) && #[trigger] stronger_next(s, s_prime) && cluster.api_server_next().forward(input)(s, s_prime)
+                implies after_update_status_state_with_response(s_prime) by {
+                lemma_from_after_send_update_status_req_to_receive_update_status_resp(s, s_prime, vsts, cluster, controller_id, msg, condemned_len, outdated_len);
+            }
+            cluster.lemma_pre_leads_to_post_by_api_server(
+                spec, input, stronger_next, APIServerStep::HandleRequest, req_msg_is_pending_msg_at_after_update_status_state(msg), after_update_status_state_with_response
+            );
+        }
+        leads_to_exists_intro(spec,
+            |msg| lift_state(req_msg_is_pending_msg_at_after_update_status_state(msg)),
+            lift_state(after_update_status_state_with_response)
+        );
+        leads_to_trans(spec,
+            lift_state(after_update_status_state_with_request),
+            tla_exists(|msg| lift_state(req_msg_is_pending_msg_at_after_update_status_state(msg))),
+            lift_state(after_update_status_state_with_response)
+        );
+    }
+    // the controller receives the response and finishes this round regardless of the result
+    assert(spec.entails(lift_state(after_update_status_state_with_response).leads_to(lift_state(done_state)))) by {
+        assert forall |ex| #[trigger] lift_state(after_update_status_state_with_response).satisfied_by(ex) implies
+            tla_exists(|msg| lift_state(resp_msg_is_pending_msg_at_after_update_status_state(msg))).satisfied_by(ex) by {
+            let s = ex.head();
+            let req_msg = s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0;
+            let resp_msg = choose |resp_msg: Message| {
+                &&& #[trigger] s.in_flight().contains(resp_msg)
+                &&& resp_msg_matches_req_msg(resp_msg, req_msg)
+                &&& resp_msg.content.is_update_status_response()
+            };
+            assert((|msg| lift_state(resp_msg_is_pending_msg_at_after_update_status_state(msg)))(resp_msg).satisfied_by(ex));
+        }
+        entails_implies_leads_to(spec,
+            lift_state(after_update_status_state_with_response),
+            tla_exists(|msg| lift_state(resp_msg_is_pending_msg_at_after_update_status_state(msg)))
+        );
+        assert forall |msg| spec.entails(lift_state(#[trigger] resp_msg_is_pending_msg_at_after_update_status_state(msg)).leads_to(lift_state(done_state))) by {
+            assert forall |s, s_prime| resp_msg_is_pending_msg_at_after_update_status_state(msg)(s) && #[trigger] stronger_next(s, s_prime) implies
+                resp_msg_is_pending_msg_at_after_update_status_state(msg)(s_prime) || done_state(s_prime) by {
+                let step = choose |step| cluster.next_step(s, s_prime, step);
+                match step {
+                    Step::ControllerStep(input) => {
+                        if input.0 == controller_id && input.2 == Some(vsts.object_ref()) {
+                            lemma_from_after_update_status_to_done(s, s_prime, vsts, cluster, controller_id, msg, condemned_len, outdated_len);
+                        }
+                    },
+                    Step::APIServerStep(input) => {
+                        lemma_api_request_other_than_pending_req_msg_maintains_local_state_coherence(s, s_prime, vsts, cluster, controller_id, input->0);
+                    },
+                    _ => {
+                        assert(s_prime.in_flight().contains(msg));
+                        assert(s_prime.resources() == s.resources());
+                    }
+                }
+            }
+            let input = (Some(msg), Some(vsts.object_ref()));
+            assert forall |s, s_prime| resp_msg_is_pending_msg_at_after_update_status_state(msg)(s) && #[trigger] stronger_next(s, s_prime) && cluster.controller_next().forward((controller_id, input.0, input.1))(s, s_prime)
+                implies done_state(s_prime) by {
+                lemma_from_after_update_status_to_done(s, s_prime, vsts, cluster, controller_id, msg, condemned_len, outdated_len);
+            }
+            cluster.lemma_pre_leads_to_post_by_controller(
+                spec, controller_id, input, stronger_next, ControllerStep::ContinueReconcile, resp_msg_is_pending_msg_at_after_update_status_state(msg), done_state
+            );
+        }
+        leads_to_exists_intro(spec,
+            |msg| lift_state(resp_msg_is_pending_msg_at_after_update_status_state(msg)),
+            lift_state(done_state)
+        );
+        leads_to_trans(spec,
+            lift_state(after_update_status_state_with_response),
+            tla_exists(|msg| lift_state(resp_msg_is_pending_msg_at_after_update_status_state(msg))),
+            lift_state(done_state)
+        );
+    }
+    leads_to_trans(spec,
+        lift_state(after_update_status_state_with_request),
+        lift_state(after_update_status_state_with_response),
+        lift_state(done_state)
+    );
+}
+
 pub proof fn lemma_spec_entails_done_leads_to_reconcile_idle(
     vsts: VStatefulSetView, spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int, outdated_len: nat
 )
@@ -4333,7 +4531,7 @@ ensures
     VStatefulSetReconcileState::marshal_preserves_integrity();
 }
 
-pub proof fn lemma_from_delete_outdated_to_after_delete_outdated_or_done(
+pub proof fn lemma_from_delete_outdated_to_after_delete_outdated_or_after_update_status(
     s: ClusterState, s_prime: ClusterState, vsts: VStatefulSetView, cluster: Cluster, controller_id: int, condemned_len: nat, outdated_len: nat
 )
 requires
@@ -4352,8 +4550,8 @@ ensures
         pending_get_then_delete_outdated_pod_req_in_flight(vsts, controller_id)(s_prime) &&
         at_vsts_step(vsts, controller_id, at_step![AfterDeleteOutdated])(s_prime),
     outdated_len == 0 ==>
-        no_pending_req_in_cluster(vsts, controller_id)(s_prime) &&
-        at_vsts_step(vsts, controller_id, at_step![Done])(s_prime),
+        pending_update_status_req_in_flight(vsts, controller_id)(s_prime) &&
+        at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus])(s_prime),
 {
     VStatefulSetReconcileState::marshal_preserves_integrity();
     let local_state = VStatefulSetReconcileState::unmarshal(s.ongoing_reconciles(controller_id)[vsts.object_ref()].local_state).unwrap();
@@ -4378,6 +4576,9 @@ ensures
         }
     } else {
         assert(get_largest_unmatched_pods(triggering_cr, local_state.needed) is None);
+        let req = s_prime.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0.content.get_update_status_request();
+        assert(req.obj.kind == VStatefulSetView::kind());
+        assert(req.key() == vsts.object_ref());
     }
 }
 
@@ -4493,6 +4694,70 @@ ensures
     }
 }
 
+pub proof fn lemma_from_after_send_update_status_req_to_receive_update_status_resp(
+    s: ClusterState, s_prime: ClusterState, vsts: VStatefulSetView, cluster: Cluster, controller_id: int, req_msg: Message, condemned_len: nat, outdated_len: nat
+)
+requires
+    cluster.type_is_installed_in_cluster::<VStatefulSetView>(),
+    cluster.controller_models.contains_pair(controller_id, vsts_controller_model()),
+    cluster.next_step(s, s_prime, Step::APIServerStep(Some(req_msg))),
+    cluster_invariants_since_reconciliation(cluster, vsts, controller_id)(s),
+    at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus])(s),
+    local_state_is_valid_and_coherent(vsts, controller_id)(s),
+    pending_update_status_req_in_flight(vsts, controller_id)(s),
+    req_msg_is(req_msg, vsts.object_ref(), controller_id)(s),
+    pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)(s),
+    outdated_len == 0,
+ensures
+    local_state_is_valid_and_coherent(vsts, controller_id)(s_prime),
+    at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus])(s_prime),
+    pending_update_status_resp_in_flight(vsts, controller_id)(s_prime),
+    pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)(s_prime),
+{
+    VStatefulSetReconcileState::marshal_preserves_integrity();
+    let resp_msg = lemma_update_status_vsts_request_returns_resp_and_only_changes_vsts(
+        s, s_prime, vsts, cluster, controller_id, req_msg
+    );
+    // pods and pvcs are not touched by the status update
+    assert(outdated_obj_keys_in_etcd(s_prime, vsts) == outdated_obj_keys_in_etcd(s, vsts)) by {
+        assert forall |key: ObjectRef| #[trigger] outdated_obj_keys_in_etcd(s_prime, vsts).contains(key)
+            == outdated_obj_keys_in_etcd(s, vsts).contains(key) by {
+            if key == vsts.object_ref() {
+                assert(!outdated_obj_key_filter(s, vsts)(key));
+                assert(!outdated_obj_key_filter(s_prime, vsts)(key));
+            }
+        }
+        assert(outdated_obj_keys_in_etcd(s_prime, vsts) =~= outdated_obj_keys_in_etcd(s, vsts));
+    }
+    let local_state = VStatefulSetReconcileState::unmarshal(s_prime.ongoing_reconciles(controller_id)[vsts.object_ref()].local_state)->Ok_0;
+    assert(local_state_is_coherent_with_etcd(vsts, local_state)(s_prime));
+    assert(s_prime.in_flight().contains(resp_msg)); // trigger
+}
+
+pub proof fn lemma_from_after_update_status_to_done(
+    s: ClusterState, s_prime: ClusterState, vsts: VStatefulSetView, cluster: Cluster, controller_id: int, msg: Message, condemned_len: nat, outdated_len: nat
+)
+requires
+    cluster.type_is_installed_in_cluster::<VStatefulSetView>(),
+    cluster.controller_models.contains_pair(controller_id, vsts_controller_model()),
+    cluster.next_step(s, s_prime, Step::ControllerStep((controller_id, resp_msg_or_none(s, vsts.object_ref(), controller_id), Some(vsts.object_ref())))),
+    cluster_invariants_since_reconciliation(cluster, vsts, controller_id)(s),
+    at_vsts_step(vsts, controller_id, at_step![AfterUpdateStatus])(s),
+    local_state_is_valid_and_coherent(vsts, controller_id)(s),
+    resp_msg_is_pending_update_status_resp_in_flight(vsts, controller_id, msg)(s),
+    pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)(s),
+    outdated_len == 0,
+ensures
+    local_state_is_valid_and_coherent(vsts, controller_id)(s_prime),
+    at_vsts_step(vsts, controller_id, at_step![Done])(s_prime),
+    no_pending_req_in_cluster(vsts, controller_id)(s_prime),
+    pvc_needed_condemned_index_condemned_len_and_outdated_len_are(vsts, controller_id, pvc_cnt(vsts), replicas(vsts), condemned_len, condemned_len, outdated_len)(s_prime),
+{
+    VStatefulSetReconcileState::marshal_preserves_integrity();
+    let local_state = VStatefulSetReconcileState::unmarshal(s.ongoing_reconciles(controller_id)[vsts.object_ref()].local_state)->Ok_0;
+    assert(get_largest_unmatched_pods(vsts, local_state.needed) is None);
+}
+
 pub proof fn lemma_done_with_outdated_len_reflects_outdated_pods_in_etcd(
     s: ClusterState, vsts: VStatefulSetView, cluster: Cluster, controller_id: int, condemned_len: nat, outdated_len: nat
 )
@@ -4777,6 +5042,13 @@ ensures
                     }
                     assert(inductive_current_state_matches(vsts, controller_id)(s_prime));
                 },
+                AfterUpdateStatus => {
+                    // the status update only touches the vsts object, so pods and pvcs are not affected
+                    lemma_update_status_vsts_request_returns_resp_and_only_changes_vsts(
+                        s, s_prime, vsts, cluster, controller_id, req_msg
+                    );
+                    assert(current_state_matches(vsts)(s_prime));
+                },
                 _ => {}
             }
             assert(inductive_current_state_matches(vsts, controller_id)(s_prime));
@@ -4847,7 +5119,12 @@ ensures
                         );
                         assert(false);
                     }
-                    assert(next_local_state.reconcile_step == Error || next_local_state.reconcile_step == Done);
+                    assert(next_local_state.reconcile_step == Error || next_local_state.reconcile_step == AfterUpdateStatus);
+                    if next_local_state.reconcile_step == AfterUpdateStatus {
+                        let req = s_prime.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0.content.get_update_status_request();
+                        assert(req.obj.kind == VStatefulSetView::kind());
+                        assert(req.key() == vsts.object_ref());
+                    }
                 },
                 _ => {}
             }
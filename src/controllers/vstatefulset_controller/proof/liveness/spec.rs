@@ -38,6 +38,9 @@ pub open spec fn next_with_wf(cluster: Cluster, controller_id: int) -> TempPred<
     .and(tla_forall(|input| cluster.external_next().weak_fairness((controller_id, input))))
     .and(cluster.disable_crash().weak_fairness(controller_id))
     .and(cluster.disable_req_drop().weak_fairness(()))
+    .and(cluster.disable_resp_drop().weak_fairness(()))
+    .and(cluster.disable_req_dup().weak_fairness(()))
+    .and(cluster.disable_node_failure().weak_fairness(()))
     .and(cluster.disable_pod_monkey().weak_fairness(()))
 }
 
@@ -55,6 +58,9 @@ pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
         always_p_is_stable::<ClusterState>(split_always);
     }
     Cluster::action_weak_fairness_is_stable(cluster.disable_req_drop());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_resp_drop());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_req_dup());
+    Cluster::action_weak_fairness_is_stable(cluster.disable_node_failure());
     Cluster::action_weak_fairness_is_stable(cluster.disable_pod_monkey());
     stable_and_n!(
         always(lift_action(cluster.next())),
@@ -65,6 +71,9 @@ pub proof fn next_with_wf_is_stable(cluster: Cluster, controller_id: int)
         tla_forall(|input| cluster.external_next().weak_fairness((controller_id, input))),
         cluster.disable_crash().weak_fairness(controller_id),
         cluster.disable_req_drop().weak_fairness(()),
+        cluster.disable_resp_drop().weak_fairness(()),
+        cluster.disable_req_dup().weak_fairness(()),
+        cluster.disable_node_failure().weak_fairness(()),
         cluster.disable_pod_monkey().weak_fairness(())
     );
 }
@@ -89,6 +98,7 @@ pub open spec fn pending_request_invariants(cluster: Cluster, controller_id: int
     .and(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateNeeded]))))
     .and(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteCondemned]))))
     .and(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated]))))
+    .and(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]))))
 }
 
 pub proof fn spec_entails_pending_request_invariants_combine(spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int)
@@ -110,6 +120,7 @@ pub proof fn spec_entails_pending_request_invariants_combine(spec: TempPred<Clus
         spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateNeeded]))))),
         spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteCondemned]))))),
         spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated]))))),
+        spec.entails(always(tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]))))),
     ensures
         spec.entails(always(pending_request_invariants(cluster, controller_id))),
 {
@@ -131,6 +142,7 @@ pub proof fn spec_entails_pending_request_invariants_combine(spec: TempPred<Clus
     let a_to_p_15 = tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateNeeded])));
     let a_to_p_16 = tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteCondemned])));
     let a_to_p_17 = tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated])));
+    let a_to_p_18 = tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus])));
     combine_spec_entails_always_n!(spec,
         pending_request_invariants(cluster, controller_id),
         a_to_p_1,
@@ -149,7 +161,8 @@ pub proof fn spec_entails_pending_request_invariants_combine(spec: TempPred<Clus
         a_to_p_14,
         a_to_p_15,
         a_to_p_16,
-        a_to_p_17
+        a_to_p_17,
+        a_to_p_18
     );
 }
 
@@ -178,12 +191,15 @@ pub open spec fn derived_invariants_since_beginning(vsts: VStatefulSetView, clus
     .and(always(pending_request_invariants(cluster, controller_id)))
     .and(always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id))))
     // Additional invariants needed by cluster_invariants_since_reconciliation
-    .and(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())))
+    .and(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())))
     .and(always(lift_state(helper_invariants::all_pvcs_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_or_owner_ref())))
     .and(always(lift_state(helper_invariants::buildin_controllers_do_not_delete_pvcs_owned_by_vsts())))
     .and(always(lift_state(helper_invariants::every_msg_from_vsts_controller_carries_vsts_key(controller_id))))
     .and(always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(controller_id))))
     .and(always(lift_state(helper_invariants::all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts))))
+    .and(always(lift_state(helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts))))
+    .and(always(lift_state(helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts))))
+    .and(always(lift_state(helper_invariants::no_vsts_carries_gc_finalizer_or_blocks_owner_deletion())))
 }
 
 pub proof fn spec_entails_derived_invariants_combine(spec: TempPred<ClusterState>, vsts: VStatefulSetView, cluster: Cluster, controller_id: int)
@@ -210,12 +226,15 @@ pub proof fn spec_entails_derived_invariants_combine(spec: TempPred<ClusterState
         spec.entails(always(lift_state(Cluster::cr_states_are_unmarshallable::<VStatefulSetReconcileState, VStatefulSetView>(controller_id)))),
         spec.entails(always(pending_request_invariants(cluster, controller_id))),
         spec.entails(always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)))),
-        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+        spec.entails(always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
         spec.entails(always(lift_state(helper_invariants::all_pvcs_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_or_owner_ref()))),
         spec.entails(always(lift_state(helper_invariants::buildin_controllers_do_not_delete_pvcs_owned_by_vsts()))),
         spec.entails(always(lift_state(helper_invariants::every_msg_from_vsts_controller_carries_vsts_key(controller_id)))),
         spec.entails(always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(controller_id)))),
         spec.entails(always(lift_state(helper_invariants::all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)))),
+        spec.entails(always(lift_state(helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)))),
+        spec.entails(always(lift_state(helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)))),
+        spec.entails(always(lift_state(helper_invariants::no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()))),
     ensures
         spec.entails(derived_invariants_since_beginning(vsts, cluster, controller_id)),
 {
@@ -243,12 +262,15 @@ pub proof fn spec_entails_derived_invariants_combine(spec: TempPred<ClusterState
         always(lift_state(Cluster::cr_states_are_unmarshallable::<VStatefulSetReconcileState, VStatefulSetView>(controller_id))),
         always(pending_request_invariants(cluster, controller_id)),
         always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id))),
-        always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())),
+        always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
         always(lift_state(helper_invariants::all_pvcs_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_or_owner_ref())),
         always(lift_state(helper_invariants::buildin_controllers_do_not_delete_pvcs_owned_by_vsts())),
         always(lift_state(helper_invariants::every_msg_from_vsts_controller_carries_vsts_key(controller_id))),
         always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(controller_id))),
-        always(lift_state(helper_invariants::all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)))
+        always(lift_state(helper_invariants::all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts))),
+        always(lift_state(helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts))),
+        always(lift_state(helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts))),
+        always(lift_state(helper_invariants::no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()))
     );
 }
 
@@ -277,12 +299,15 @@ pub proof fn derived_invariants_since_beginning_is_stable(vsts: VStatefulSetView
     always_p_is_stable(lift_state(Cluster::cr_states_are_unmarshallable::<VStatefulSetReconcileState, VStatefulSetView>(controller_id)));
     always_p_is_stable(pending_request_invariants(cluster, controller_id));
     always_p_is_stable(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id)));
-    always_p_is_stable(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests()));
+    always_p_is_stable(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()));
     always_p_is_stable(lift_state(helper_invariants::all_pvcs_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_or_owner_ref()));
     always_p_is_stable(lift_state(helper_invariants::buildin_controllers_do_not_delete_pvcs_owned_by_vsts()));
     always_p_is_stable(lift_state(helper_invariants::every_msg_from_vsts_controller_carries_vsts_key(controller_id)));
     always_p_is_stable(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(controller_id)));
     always_p_is_stable(lift_state(helper_invariants::all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)));
+    always_p_is_stable(lift_state(helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts)));
+    always_p_is_stable(lift_state(helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts)));
+    always_p_is_stable(lift_state(helper_invariants::no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()));
     stable_and_n!(
         always(lift_state(Cluster::every_in_flight_msg_has_unique_id())),
         always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator())),
@@ -306,12 +331,15 @@ pub proof fn derived_invariants_since_beginning_is_stable(vsts: VStatefulSetView
         always(lift_state(Cluster::cr_states_are_unmarshallable::<VStatefulSetReconcileState, VStatefulSetView>(controller_id))),
         always(pending_request_invariants(cluster, controller_id)),
         always(lift_state(internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id))),
-        always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_requests())),
+        always(lift_state(Cluster::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests())),
         always(lift_state(helper_invariants::all_pvcs_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_or_owner_ref())),
         always(lift_state(helper_invariants::buildin_controllers_do_not_delete_pvcs_owned_by_vsts())),
         always(lift_state(helper_invariants::every_msg_from_vsts_controller_carries_vsts_key(controller_id))),
         always(lift_state(Cluster::every_in_flight_msg_from_controller_has_kind_as::<VStatefulSetView>(controller_id))),
-        always(lift_state(helper_invariants::all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts)))
+        always(lift_state(helper_invariants::all_pods_in_etcd_matching_vsts_have_no_finalizer_or_deletion_timestamp_and_one_owner_ref(vsts))),
+        always(lift_state(helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts))),
+        always(lift_state(helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts))),
+        always(lift_state(helper_invariants::no_vsts_carries_gc_finalizer_or_blocks_owner_deletion()))
     );
 }
 
@@ -331,10 +359,13 @@ pub proof fn invariants_is_stable(vsts: VStatefulSetView, cluster: Cluster, cont
     );
 }
 
-// Phase I: crash, req_drop, pod_monkey disabled, schedule_has_spec, schedule_has_name_namespace, schedule_has_no_deletion_timestamp
+// Phase I: crash, req_drop, resp_drop, req_dup, node_failure, pod_monkey disabled, schedule_has_spec, schedule_has_name_namespace, schedule_has_no_deletion_timestamp
 pub open spec fn invariants_since_phase_i(controller_id: int, vsts: VStatefulSetView) -> TempPred<ClusterState> {
     always(lift_state(Cluster::crash_disabled(controller_id)))
     .and(always(lift_state(Cluster::req_drop_disabled())))
+    .and(always(lift_state(Cluster::resp_drop_disabled())))
+    .and(always(lift_state(Cluster::req_dup_disabled())))
+    .and(always(lift_state(Cluster::node_failure_disabled())))
     .and(always(lift_state(Cluster::pod_monkey_disabled())))
     .and(always(lift_state(Cluster::the_object_in_schedule_has_spec_and_uid_as(controller_id, vsts))))
     .and(always(lift_state(helper_invariants::vsts_in_schedule_has_the_same_name_and_namespace_as_vsts(vsts, controller_id))))
@@ -347,6 +378,9 @@ pub proof fn invariants_since_phase_i_is_stable(controller_id: int, vsts: VState
     stable_and_always_n!(
         lift_state(Cluster::crash_disabled(controller_id)),
         lift_state(Cluster::req_drop_disabled()),
+        lift_state(Cluster::resp_drop_disabled()),
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::node_failure_disabled()),
         lift_state(Cluster::pod_monkey_disabled()),
         lift_state(Cluster::the_object_in_schedule_has_spec_and_uid_as(controller_id, vsts)),
         lift_state(helper_invariants::vsts_in_schedule_has_the_same_name_and_namespace_as_vsts(vsts, controller_id)),
@@ -50,10 +50,10 @@ pub open spec fn req_msg_is_list_pod_req(
     &&& req_msg.dst == HostId::APIServer
     &&& req_msg.content is APIRequest
     &&& req is ListRequest
-    &&& req->ListRequest_0 == ListRequest {
-        kind: Kind::PodKind,
-        namespace: vsts_key.namespace,
-    }
+    &&& req->ListRequest_0.kind == Kind::PodKind
+    &&& req->ListRequest_0.namespace == vsts_key.namespace
+    &&& req->ListRequest_0.label_selector is None
+    &&& req->ListRequest_0.is_full_quorum_list()
 }
 
 pub open spec fn pending_list_pod_req_in_flight(
@@ -239,13 +239,14 @@ pub open spec fn local_state_is_valid(vsts: VStatefulSetView, state: VStatefulSe
     &&& state.reconcile_step == DeleteCondemned ==> state.condemned_index < state.condemned.len()
     &&& state.reconcile_step == AfterDeleteCondemned ==> state.condemned_index > 0
     &&& state.reconcile_step == AfterDeleteOutdated ==> get_largest_unmatched_pods(vsts, state.needed) is Some
+    &&& state.reconcile_step == AfterUpdateStatus ==> get_largest_unmatched_pods(vsts, state.needed) is None
     &&& locally_at_step_or!(state, AfterCreateNeeded, AfterUpdateNeeded) ==> state.needed_index > 0
     // in these states pvc index is strictly less than pvc count
     &&& locally_at_step_or!(state, GetPVC, AfterGetPVC, CreatePVC, SkipPVC) ==> state.pvc_index < pvc_cnt
     // precondition to transit to CreateNeeded or UpdateNeeded
     &&& locally_at_step_or!(state, CreateNeeded, UpdateNeeded) ==> state.pvc_index == pvc_cnt
     // before reaching condemned step the index is 0
-    &&& !locally_at_step_or!(state, DeleteCondemned, AfterDeleteCondemned, DeleteOutdated, AfterDeleteOutdated, Done) ==> state.condemned_index == 0
+    &&& !locally_at_step_or!(state, DeleteCondemned, AfterDeleteCondemned, DeleteOutdated, AfterDeleteOutdated, AfterUpdateStatus, Done) ==> state.condemned_index == 0
 }
 
 // coherence between local state and etcd state
@@ -849,6 +850,56 @@ pub open spec fn resp_msg_is_pending_get_then_delete_outdated_pod_resp_in_flight
     }
 }
 
+pub open spec fn req_msg_is_update_status_vsts_req(
+    vsts_key: ObjectRef, controller_id: int, req_msg: Message
+) -> bool {
+    let req = req_msg.content.get_update_status_request();
+    &&& req_msg.src == HostId::Controller(controller_id, vsts_key)
+    &&& req_msg.dst == HostId::APIServer
+    &&& req_msg.content.is_update_status_request()
+    &&& req.key() == vsts_key
+}
+
+pub open spec fn pending_update_status_req_in_flight(
+    vsts: VStatefulSetView, controller_id: int
+) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        let req_msg = s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0;
+        &&& Cluster::pending_req_msg_is(controller_id, s, vsts.object_ref(), req_msg)
+        &&& s.in_flight().contains(req_msg)
+        &&& req_msg_is_update_status_vsts_req(vsts.object_ref(), controller_id, req_msg)
+    }
+}
+
+// the status update may fail (e.g., with a conflict), which does not matter as the controller tolerates it
+pub open spec fn pending_update_status_resp_in_flight(
+    vsts: VStatefulSetView, controller_id: int
+) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        let req_msg = s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0;
+        &&& Cluster::pending_req_msg_is(controller_id, s, vsts.object_ref(), req_msg)
+        &&& req_msg_is_update_status_vsts_req(vsts.object_ref(), controller_id, req_msg)
+        &&& exists |resp_msg: Message| {
+            &&& #[trigger] s.in_flight().contains(resp_msg)
+            &&& resp_msg_matches_req_msg(resp_msg, req_msg)
+            &&& resp_msg.content.is_update_status_response()
+        }
+    }
+}
+
+pub open spec fn resp_msg_is_pending_update_status_resp_in_flight(
+    vsts: VStatefulSetView, controller_id: int, resp_msg: Message
+) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        let req_msg = s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0;
+        &&& Cluster::pending_req_msg_is(controller_id, s, vsts.object_ref(), req_msg)
+        &&& req_msg_is_update_status_vsts_req(vsts.object_ref(), controller_id, req_msg)
+        &&& s.in_flight().contains(resp_msg)
+        &&& resp_msg_matches_req_msg(resp_msg, req_msg)
+        &&& resp_msg.content.is_update_status_response()
+    }
+}
+
 pub open spec fn n_outdated_pods_in_etcd(vsts: VStatefulSetView, n: nat) -> StatePred<ClusterState> {
     |s: ClusterState| {
         outdated_obj_keys_in_etcd(s, vsts).len() == n
@@ -954,7 +1005,7 @@ pub open spec fn inductive_current_state_matches(vsts: VStatefulSetView, control
                 &&& local_state.condemned.len() == 0
                 &&& !locally_at_step_or!(local_state, Init, AfterListPod) ==> local_state.needed.len() == replicas(vsts)
             }
-            &&& at_vsts_step(vsts, controller_id, at_step_or![Init, AfterListPod, GetPVC, AfterGetPVC, CreatePVC, AfterCreatePVC, SkipPVC, UpdateNeeded, AfterUpdateNeeded, DeleteOutdated, Done, Error])(s)
+            &&& at_vsts_step(vsts, controller_id, at_step_or![Init, AfterListPod, GetPVC, AfterGetPVC, CreatePVC, AfterCreatePVC, SkipPVC, UpdateNeeded, AfterUpdateNeeded, DeleteOutdated, AfterUpdateStatus, Done, Error])(s)
             &&& match local_state.reconcile_step {
                 AfterListPod => {
                     let req_msg = s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0;
@@ -986,6 +1037,11 @@ pub open spec fn inductive_current_state_matches(vsts: VStatefulSetView, control
                     &&& req_msg.content.is_create_request()
                     &&& req.key().kind == Kind::PersistentVolumeClaimKind
                 },
+                AfterUpdateStatus => {
+                    let req_msg = s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg->0;
+                    &&& s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg is Some
+                    &&& req_msg_is_update_status_vsts_req(vsts.object_ref(), controller_id, req_msg)
+                },
                 _ => {
                     s.ongoing_reconciles(controller_id)[vsts.object_ref()].pending_req_msg is None
                 }
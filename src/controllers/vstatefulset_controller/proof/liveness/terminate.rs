@@ -59,6 +59,7 @@ pub open spec fn vsts_all_states(vsts: VStatefulSetView, controller_id: int) ->
     .or(lift_at_step_or![AfterDeleteCondemned])
     .or(lift_at_step_or![DeleteOutdated])
     .or(lift_at_step_or![AfterDeleteOutdated])
+    .or(lift_at_step_or![AfterUpdateStatus])
     .or(lift_at_step_or![Done])
     .or(lift_at_step_or![Error])
 }
@@ -102,6 +103,7 @@ pub open spec fn vsts_terminate_invariants(
     &&& spec.entails(always(lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateNeeded]))))
     &&& spec.entails(always(lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteCondemned]))))
     &&& spec.entails(always(lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated]))))
+    &&& spec.entails(always(lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]))))
 }
 
 pub proof fn reconcile_eventually_terminates(spec: TempPred<ClusterState>, cluster: Cluster, controller_id: int)
@@ -147,6 +149,7 @@ pub proof fn reconcile_eventually_terminates(spec: TempPred<ClusterState>, clust
     always_weaken(spec, pending_request_invariants(cluster, controller_id), tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateNeeded]))));
     always_weaken(spec, pending_request_invariants(cluster, controller_id), tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteCondemned]))));
     always_weaken(spec, pending_request_invariants(cluster, controller_id), tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated]))));
+    always_weaken(spec, pending_request_invariants(cluster, controller_id), tla_forall(|vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]))));
 
     
     let post = |key: ObjectRef|
@@ -389,6 +392,19 @@ pub proof fn reconcile_eventually_terminates(spec: TempPred<ClusterState>, clust
                 vsts
             );
         }
+
+        assert forall |vsts: VStatefulSetView| #![auto]
+        spec.entails(always(lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+            controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]
+        )))) by {
+            always_tla_forall_apply::<ClusterState, VStatefulSetView>(
+                spec,
+                |vsts: VStatefulSetView| lift_state(Cluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+                    controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus]
+                )),
+                vsts
+            );
+        }
         // End unwrapping foralls.
 
         if key.kind == VStatefulSetView::kind() {
@@ -481,8 +497,9 @@ ensures
     temp_pred_equality(lift_at_step_or![Error], lift_state(reconcile_error));
     entails_implies_leads_to(spec, lift_state(reconcile_idle), lift_state(reconcile_idle));
 
-    // Prove AfterDeleteOutdated -> Idle first
+    // Prove AfterDeleteOutdated -> Idle and AfterUpdateStatus -> Idle first
     cluster.lemma_from_some_state_to_arbitrary_next_state(spec, controller_id, vsts.object_ref(), at_step_or![AfterDeleteOutdated], at_step_or![Error, Done]);
+    cluster.lemma_from_some_state_to_arbitrary_next_state(spec, controller_id, vsts.object_ref(), at_step_or![AfterUpdateStatus], at_step_or![Error, Done]);
 
     or_leads_to_combine_and_equality!(spec,
         lift_at_step_or![Error, Done],
@@ -497,24 +514,30 @@ ensures
         lift_at_step_or![Error, Done],
         lift_state(reconcile_idle)
     );
+    leads_to_trans_n!(
+        spec,
+        lift_at_step_or![AfterUpdateStatus],
+        lift_at_step_or![Error, Done],
+        lift_state(reconcile_idle)
+    );
 
-    // Prove DeleteOutdated -> Idle (goes to AfterDeleteOutdated, Error, or Done)
+    // Prove DeleteOutdated -> Idle (goes to AfterDeleteOutdated, AfterUpdateStatus or Error)
     or_leads_to_n!(
         spec,
         lift_at_step_or![AfterDeleteOutdated],
-        lift_at_step_or![Error],
-        lift_at_step_or![Done];
+        lift_at_step_or![AfterUpdateStatus],
+        lift_at_step_or![Error];
         lift_state(reconcile_idle)
     );
     temp_pred_equality(
-        lift_at_step_or![AfterDeleteOutdated, Error, Done],
-        lift_at_step_or![AfterDeleteOutdated].or(lift_at_step_or![Error]).or(lift_at_step_or![Done])
+        lift_at_step_or![AfterDeleteOutdated, AfterUpdateStatus, Error],
+        lift_at_step_or![AfterDeleteOutdated].or(lift_at_step_or![AfterUpdateStatus]).or(lift_at_step_or![Error])
     );
     
     cluster.lemma_from_init_state_to_next_state_to_reconcile_idle(
         spec, controller_id, vsts.object_ref(),
         at_step_or![DeleteOutdated],
-        at_step_or![AfterDeleteOutdated, Error, Done]
+        at_step_or![AfterDeleteOutdated, AfterUpdateStatus, Error]
     );
 
     lemma_after_delete_condemned_leads_to_idle(spec, vsts, cluster, controller_id);
@@ -771,6 +794,7 @@ ensures
         lift_at_step_or![AfterDeleteCondemned],
         lift_at_step_or![DeleteOutdated],
         lift_at_step_or![AfterDeleteOutdated],
+        lift_at_step_or![AfterUpdateStatus],
         lift_at_step_or![Done],
         lift_at_step_or![Error];
         lift_state(reconcile_idle)
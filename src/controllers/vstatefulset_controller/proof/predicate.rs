@@ -97,6 +97,9 @@ pub open spec fn cluster_invariants_since_reconciliation(cluster: Cluster, vsts:
     and!(
         Cluster::crash_disabled(controller_id),
         Cluster::req_drop_disabled(),
+        Cluster::resp_drop_disabled(),
+        Cluster::req_dup_disabled(),
+        Cluster::node_failure_disabled(),
         Cluster::pod_monkey_disabled(),
         Cluster::every_in_flight_msg_has_unique_id(),
         Cluster::every_in_flight_msg_has_lower_id_than_allocator(),
@@ -126,6 +129,8 @@ pub open spec fn cluster_invariants_since_reconciliation(cluster: Cluster, vsts:
         helper_invariants::vsts_in_ongoing_reconciles_has_no_deletion_timestamp(vsts, controller_id),
         helper_invariants::buildin_controllers_do_not_delete_pvcs_owned_by_vsts(),
         helper_invariants::buildin_controllers_do_not_delete_pods_owned_by_vsts(vsts),
+        helper_invariants::all_pods_in_etcd_owned_by_vsts_match_vsts(vsts),
+        helper_invariants::builtin_controllers_keep_owner_references_of_pods_matching_vsts(vsts),
         internal_rely_guarantee::vsts_internal_guarantee_conditions(controller_id),
         internal_rely_guarantee::every_msg_from_vsts_controller_carries_vsts_key(controller_id),
         rely_guarantee::vsts_rely_conditions(cluster, controller_id)
@@ -6,7 +6,7 @@ use crate::kubernetes_api_objects::exec::{
     prelude::*, resource::*,
 };
 use crate::kubernetes_api_objects::spec::{
-    persistent_volume_claim::*, resource::*, volume_resource_requirements::*,
+    persistent_volume_claim::*, resource::*, stateful_set::*, volume_resource_requirements::*,
 };
 use crate::vstatefulset_controller::trusted::spec_types;
 use crate::vstd_ext::{string_map::*, string_view::*};
@@ -42,6 +42,13 @@ impl VStatefulSet {
         self.inner.spec = spec.into_kube()
     }
 
+    #[verifier(external_body)]
+    pub fn set_status(&mut self, status: VStatefulSetStatus)
+        ensures final(self)@ == old(self)@.with_status(status@),
+    {
+        self.inner.status = Some(status.into_kube());
+    }
+
     // TODO: move controller_owner_ref to implement_object_wrapper_type
     #[verifier(external_body)]
     pub fn controller_owner_ref(&self) -> (owner_reference: OwnerReference)
@@ -58,6 +65,12 @@ implement_field_wrapper_type!(
     spec_types::VStatefulSetSpecView
 );
 
+implement_field_wrapper_type!(
+    VStatefulSetStatus,
+    crate::crds::VStatefulSetStatus,
+    StatefulSetStatusView
+);
+
 // NOTE: updates to statefulset spec for fields other than 'replicas', 'ordinals', 'template', 
 // 'updateStrategy', 'persistentVolumeClaimRetentionPolicy' and 'minReadySeconds' should not be made
 
@@ -207,6 +220,15 @@ impl VStatefulSetSpec {
     }
 }
 
+impl VStatefulSetStatus {
+    #[verifier(external_body)]
+    pub fn set_observed_generation(&mut self, observed_generation: i64)
+        ensures final(self)@ == old(self)@.with_observed_generation(observed_generation as int),
+    {
+        self.inner.observed_generation = Some(observed_generation);
+    }
+}
+
 // Other internal fields are defined in kubernetes_api_objects::exec::stateful_set
 
 }
@@ -48,6 +48,7 @@ pub open spec fn vsts_rely(other_id: int) -> StatePred<ClusterState> {
             APIRequest::GetThenUpdateRequest(req) => vsts_rely_get_then_update_req(req),
             APIRequest::DeleteRequest(req) => vsts_rely_delete_req(req)(s),
             APIRequest::GetThenDeleteRequest(req) => vsts_rely_get_then_delete_req(req),
+            APIRequest::PatchRequest(req) => vsts_rely_patch_req(req)(s),
             // Get/List/UpdateStatus requests are unconstrained
             _ => true,
         }
@@ -68,7 +69,7 @@ pub open spec fn vsts_rely_create_req(req: CreateRequest) -> bool {
             &&& !exists |vsts: VStatefulSetView|
                 #[trigger] req.obj.metadata.owner_references_contains(vsts.controller_owner_ref())
         },
-        _ => true,
+        _ => req.obj.kind == VStatefulSetView::kind() ==> vsts_rely_vsts_obj(req.obj),
     }
 }
 
@@ -84,7 +85,24 @@ pub open spec fn vsts_rely_update_req(req: UpdateRequest) -> StatePred<ClusterSt
                 // Prevents 2): where other controllers update pod so they become owned by a VSTS.
                 &&& !exists |vsts: VStatefulSetView| #[trigger] req.obj.metadata.owner_references_contains(vsts.controller_owner_ref()) 
             }
-            _ => true,
+            _ => req.obj.kind == VStatefulSetView::kind() ==> vsts_rely_vsts_obj(req.obj),
+        }
+    }
+}
+
+// A patch is handled as an update of the patched object, and owner_references in the patch
+// replace the existing ones, so it is restricted in the same way as update.
+pub open spec fn vsts_rely_patch_req(req: PatchRequest) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        match req.obj.kind {
+            Kind::PodKind | Kind::PersistentVolumeClaimKind => {
+                &&& !has_vsts_prefix(req.name)
+                &&& !exists |vsts: VStatefulSetView|
+                    #[trigger] s.resources()[req.key()].metadata.owner_references_contains(vsts.controller_owner_ref())
+                &&& !exists |vsts: VStatefulSetView| #[trigger] req.obj.metadata.owner_references_contains(vsts.controller_owner_ref())
+            }
+            // The finalizers and owner_references in the patch (if any) replace the existing ones
+            _ => req.obj.kind == VStatefulSetView::kind() ==> vsts_rely_vsts_obj(req.obj),
         }
     }
 }
@@ -98,7 +116,7 @@ pub open spec fn vsts_rely_get_then_update_req(req: GetThenUpdateRequest) -> boo
             // Prevents 2): where other controllers update pod so they become owned by a VSTS.
             &&& !exists |vsts: VStatefulSetView| #[trigger] req.obj.metadata.owner_references_contains(vsts.controller_owner_ref()) 
         }
-        _ => true,
+        _ => req.obj.kind == VStatefulSetView::kind() ==> vsts_rely_vsts_obj(req.obj),
     }
 }
 
@@ -110,7 +128,11 @@ pub open spec fn vsts_rely_delete_req(req: DeleteRequest) -> StatePred<ClusterSt
                 // Prevents 1): where other controllers delete pod already owned by a VSTS.
                 &&& !exists |vsts: VStatefulSetView| #[trigger] s.resources()[req.key()].metadata.owner_references_contains(vsts.controller_owner_ref())
             }
-            _ => true,
+            // Deleting a VSTS in foreground or with the orphan policy adds the foregroundDeletion or orphan finalizer to it
+            _ => req.key.kind == VStatefulSetView::kind() ==> {
+                ||| req.propagation_policy is None
+                ||| req.propagation_policy == Some(PropagationPolicy::Background)
+            },
         }
     }
 }
@@ -128,6 +150,17 @@ pub open spec fn vsts_rely_get_then_delete_req(req: GetThenDeleteRequest) -> boo
     }
 }
 
+// Other controllers must not make the garbage collector delete the pods of a VSTS in foreground, which sets their
+// deletion timestamp, or orphan the pods, which removes their owner references to the VSTS.
+// So a VSTS object never carries the foregroundDeletion or orphan finalizer, and never blocks the deletion of its owners
+// (otherwise the garbage collector deletes the VSTS in foreground when its owner is deleted in foreground).
+pub open spec fn vsts_rely_vsts_obj(obj: DynamicObjectView) -> bool {
+    &&& !obj.metadata.finalizers_as_set().contains(foreground_deletion_finalizer())
+    &&& !obj.metadata.finalizers_as_set().contains(orphan_finalizer())
+    &&& forall |owner_reference: OwnerReferenceView| #[trigger] obj.metadata.owner_references_contains(owner_reference)
+        ==> owner_reference.block_owner_deletion != Some(true)
+}
+
 // VSTS Guarantee Condition (for other controllers)
 
 pub open spec fn vsts_guarantee(controller_id: int) -> StatePred<ClusterState> {
@@ -141,7 +174,8 @@ pub open spec fn vsts_guarantee(controller_id: int) -> StatePred<ClusterState> {
             APIRequest::CreateRequest(req) => vsts_guarantee_create_req(req),
             APIRequest::GetThenUpdateRequest(req) => vsts_guarantee_get_then_update_req(req),
             APIRequest::GetThenDeleteRequest(req) => vsts_guarantee_get_then_delete_req(req),
-            // No Update, UpdateStatus and Delete requests submitted
+            APIRequest::UpdateStatusRequest(req) => vsts_guarantee_update_status_req(req),
+            // No Update and Delete requests submitted
             _ => false,
         }
     }
@@ -179,4 +213,9 @@ pub open spec fn vsts_guarantee_get_then_delete_req(req: GetThenDeleteRequest) -
     &&& exists |vsts: VStatefulSetView| req.owner_ref == #[trigger] vsts.controller_owner_ref()
 }
 
-}
\ No newline at end of file
+// VSTS controller only updates the status of VSTS
+pub open spec fn vsts_guarantee_update_status_req(req: UpdateStatusRequest) -> bool {
+    req.obj.kind == VStatefulSetView::kind()
+}
+
+}
@@ -45,6 +45,13 @@ impl VStatefulSetView {
         }
     }
 
+    pub open spec fn with_status(self, status: StatefulSetStatusView) -> VStatefulSetView {
+        VStatefulSetView {
+            status: Some(status),
+            ..self
+        }
+    }
+
     #[verifier(inline)]
     pub open spec fn _kind() -> Kind { Kind::CustomResourceKind("vstatefulset"@) }
 
@@ -151,6 +158,10 @@ implement_resource_view_trait!(VStatefulSetView, VStatefulSetSpecView, VStateful
 impl CustomResourceView for VStatefulSetView {
     proof fn kind_is_custom_resource() {}
 
+    open spec fn scope() -> ResourceScope {
+        ResourceScope::Namespaced
+    }
+
     open spec fn spec_status_validation(obj_spec: Self::Spec, obj_status: Self::Status) -> bool {
         VStatefulSetView {
             metadata: arbitrary(),
@@ -1,7 +1,7 @@
 use vstd::prelude::*;
 
 verus! {
-    #[derive(Copy)]
+    #[derive(Copy, Debug)]
     pub enum VStatefulSetReconcileStep {
         Init,
         AfterListPod,
@@ -18,6 +18,7 @@ verus! {
         AfterDeleteCondemned,
         DeleteOutdated,
         AfterDeleteOutdated,
+        AfterUpdateStatus,
         Done,
         Error,
     }
@@ -48,6 +49,7 @@ verus! {
                 VStatefulSetReconcileStep::AfterDeleteCondemned => VStatefulSetReconcileStepView::AfterDeleteCondemned,
                 VStatefulSetReconcileStep::DeleteOutdated => VStatefulSetReconcileStepView::DeleteOutdated,
                 VStatefulSetReconcileStep::AfterDeleteOutdated => VStatefulSetReconcileStepView::AfterDeleteOutdated,
+                VStatefulSetReconcileStep::AfterUpdateStatus => VStatefulSetReconcileStepView::AfterUpdateStatus,
                 VStatefulSetReconcileStep::Done => VStatefulSetReconcileStepView::Done,
                 VStatefulSetReconcileStep::Error => VStatefulSetReconcileStepView::Error,
             }
@@ -70,6 +72,7 @@ verus! {
         AfterDeleteCondemned,
         DeleteOutdated,
         AfterDeleteOutdated,
+        AfterUpdateStatus,
         Done,
         Error,
     }
@@ -29,6 +29,8 @@ pub struct VReplicaSetSpec {
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
 pub struct VReplicaSetStatus {
     pub replicas: i32,
+    #[serde(rename = "observedGeneration")]
+    pub observed_generation: Option<i64>,
 }
 
 impl Default for VReplicaSet {
@@ -2,41 +2,44 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 use crate::executable_model::{
-    api_server_state::ApiServerState, common::*, object_map::ObjectMap,
-    object_ref_set::ObjectRefSet,
+    api_server_state::*, common::*, installed_types::*, object_map::ObjectMap,
 };
-use crate::kubernetes_api_objects::{error::*, exec::prelude::*, spec::prelude::*};
+use crate::kubernetes_api_objects::{error::*, exec::{api_resource::ApiResource, prelude::*}, spec::prelude::*};
 use crate::kubernetes_cluster::spec::{
-    api_server::state_machine as model, api_server::types as model_types,
+    api_server::state_machine as model, api_server::types as model_types, message::*,
 };
-use vstd::{multiset::*, prelude::*};
+use crate::vstd_ext::string_view::*;
+use vstd::prelude::*;
 
 verus! {
 
 // The ExecutableApiServerModel is an executable version of the spec-level ApiServerModel
 // defined in crate::kubernetes_cluster::spec::api_server.
-// The main purpose of creating this exec model is to validate the trusted ApiServerModel
-// which our verification depends on.
-// The validation is done in two steps: (1) we prove ExecutableApiServerModel conforms to the ApiServerModel,
-// and (2) we run conformance tests by comparing the external behavior of ExecutableApiServerModel and the real
-// Kubernetes implementation (in a kind cluster).
-// The conformance tests can be found in crate::conformance_tests.
-pub struct ExecutableApiServerModel<K> where K: View + CustomResource, K::V: CustomResourceView {
-    k: K,
-}
+// Each handle_*_request below is proved to conform to its correspondence in the spec:
+// it changes the state and returns the response in the same way as the spec does,
+// given the installed types that the spec is instantiated with.
+// The exec model is used by crate::shim_layer::in_memory_backend to handle the requests from the controllers
+// in tests, so the tests exercise the same request handling logic that the controllers are verified against.
+//
+// The model relies on a few trusted functions whose ensures are not proved:
+// the installed types (see ExecutableInstalledTypes), the getters and setters of DynamicObject,
+// the generated names, the continue tokens, and the merge of patches (which is uninterpreted in the spec).
+pub struct ExecutableApiServerModel {}
 
-pub struct ExecutableApiServerState {
-    pub resources: StoredState,
+// stored_object_well_formed says that the object stored at key (if any) can be updated by the model:
+// it is unmarshallable, passes state validation, has the right key (name, namespace, kind),
+// and its generation can be incremented without overflow.
+// All but the last one are proved by the invariant lemma_always_each_object_in_etcd_is_well_formed.
+pub open spec fn stored_object_well_formed(installed_types: InstalledTypes, s: model_types::APIServerState, key: ObjectRef) -> bool {
+    s.resources.contains_key(key) ==> {
+        &&& model::unmarshallable_object(s.resources[key], installed_types)
+        &&& model::valid_object(s.resources[key], installed_types)
+        &&& s.resources[key].object_ref() == key
+        &&& s.resources[key].metadata.generation is Some ==> s.resources[key].metadata.generation->0 < i64::MAX
+    }
 }
 
-pub type SimpleExecutableApiServerModel = ExecutableApiServerModel<SimpleCR>;
-
-impl <K> ExecutableApiServerModel<K> where K: View + CustomResource, K::V: CustomResourceView {
-
-#[verifier(external_body)]
-fn unmarshallable_object(obj: &DynamicObject) -> (b: bool)
-    ensures b == model::unmarshallable_object::<K::V>(obj@),
-{ true }
+impl ExecutableApiServerModel {
 
 fn metadata_validity_check(obj: &DynamicObject) -> (ret: Option<APIError>)
     ensures ret == model::metadata_validity_check(obj@)
@@ -62,15 +65,17 @@ fn metadata_transition_validity_check(obj: &DynamicObject, old_obj: &DynamicObje
     }
 }
 
-fn valid_object(obj: &DynamicObject) -> (ret: bool)
+fn valid_object(t: &ExecutableInstalledTypes, obj: &DynamicObject) -> (ret: bool)
     requires
-        model::unmarshallable_object::<K::V>(obj@),
-        obj@.kind is CustomResourceKind ==> obj@.kind == K::V::kind(),
-    ensures ret == model::valid_object::<K::V>(obj@)
+        installed_kind(t@, obj@.kind),
+        model::unmarshallable_object(obj@, t@),
+    ensures ret == model::valid_object(obj@, t@)
 {
-    match obj.kind() {
+    match t.kind(obj) {
         KindExec::ConfigMapKind => ConfigMap::unmarshal(obj.clone()).unwrap().state_validation(),
         KindExec::DaemonSetKind => DaemonSet::unmarshal(obj.clone()).unwrap().state_validation(),
+        KindExec::EventKind => Event::unmarshal(obj.clone()).unwrap().state_validation(),
+        KindExec::NodeKind => Node::unmarshal(obj.clone()).unwrap().state_validation(),
         KindExec::PersistentVolumeClaimKind => PersistentVolumeClaim::unmarshal(obj.clone()).unwrap().state_validation(),
         KindExec::PodKind => Pod::unmarshal(obj.clone()).unwrap().state_validation(),
         KindExec::RoleBindingKind => RoleBinding::unmarshal(obj.clone()).unwrap().state_validation(),
@@ -79,42 +84,38 @@ fn valid_object(obj: &DynamicObject) -> (ret: bool)
         KindExec::ServiceKind => Service::unmarshal(obj.clone()).unwrap().state_validation(),
         KindExec::StatefulSetKind => StatefulSet::unmarshal(obj.clone()).unwrap().state_validation(),
         KindExec::ServiceAccountKind => ServiceAccount::unmarshal(obj.clone()).unwrap().state_validation(),
-        KindExec::CustomResourceKind(_) => {
-            proof {
-                K::V::unmarshal_result_determined_by_unmarshal_spec_and_status();
-                K::V::kind_is_custom_resource();
-            }
-            K::unmarshal(obj.clone()).unwrap().state_validation()
-        },
+        KindExec::CustomResourceKind(_) => t.valid_custom_object(obj),
     }
 }
 
-fn object_validity_check(obj: &DynamicObject) -> (ret: Option<APIError>)
+fn object_validity_check(t: &ExecutableInstalledTypes, obj: &DynamicObject) -> (ret: Option<APIError>)
     requires
-        model::unmarshallable_object::<K::V>(obj@),
-        obj@.kind is CustomResourceKind ==> obj@.kind == K::V::kind(),
-    ensures ret == model::object_validity_check::<K::V>(obj@)
+        installed_kind(t@, obj@.kind),
+        model::unmarshallable_object(obj@, t@),
+    ensures ret == model::object_validity_check(obj@, t@)
 {
-    if !Self::valid_object(obj) {
+    if !Self::valid_object(t, obj) {
         Some(APIError::Invalid)
     } else {
         None
     }
 }
 
-fn valid_transition(obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: bool)
+fn valid_transition(t: &ExecutableInstalledTypes, obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: bool)
     requires
-        model::unmarshallable_object::<K::V>(obj@),
-        model::unmarshallable_object::<K::V>(old_obj@),
+        installed_kind(t@, obj@.kind),
+        model::unmarshallable_object(obj@, t@),
+        model::unmarshallable_object(old_obj@, t@),
         old_obj@.kind == obj@.kind,
-        model::valid_object::<K::V>(obj@),
-        model::valid_object::<K::V>(old_obj@),
-        obj@.kind is CustomResourceKind ==> obj@.kind == K::V::kind(),
-    ensures ret == model::valid_transition::<K::V>(obj@, old_obj@)
+        model::valid_object(obj@, t@),
+        model::valid_object(old_obj@, t@),
+    ensures ret == model::valid_transition(obj@, old_obj@, t@)
 {
-    match obj.kind() {
+    match t.kind(obj) {
         KindExec::ConfigMapKind => ConfigMap::unmarshal(obj.clone()).unwrap().transition_validation(&ConfigMap::unmarshal(old_obj.clone()).unwrap()),
         KindExec::DaemonSetKind => DaemonSet::unmarshal(obj.clone()).unwrap().transition_validation(&DaemonSet::unmarshal(old_obj.clone()).unwrap()),
+        KindExec::EventKind => Event::unmarshal(obj.clone()).unwrap().transition_validation(&Event::unmarshal(old_obj.clone()).unwrap()),
+        KindExec::NodeKind => Node::unmarshal(obj.clone()).unwrap().transition_validation(&Node::unmarshal(old_obj.clone()).unwrap()),
         KindExec::PersistentVolumeClaimKind => PersistentVolumeClaim::unmarshal(obj.clone()).unwrap().transition_validation(&PersistentVolumeClaim::unmarshal(old_obj.clone()).unwrap()),
         KindExec::PodKind => Pod::unmarshal(obj.clone()).unwrap().transition_validation(&Pod::unmarshal(old_obj.clone()).unwrap()),
         KindExec::RoleBindingKind => RoleBinding::unmarshal(obj.clone()).unwrap().transition_validation(&RoleBinding::unmarshal(old_obj.clone()).unwrap()),
@@ -123,38 +124,32 @@ fn valid_transition(obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: bool)
         KindExec::ServiceKind => Service::unmarshal(obj.clone()).unwrap().transition_validation(&Service::unmarshal(old_obj.clone()).unwrap()),
         KindExec::StatefulSetKind => StatefulSet::unmarshal(obj.clone()).unwrap().transition_validation(&StatefulSet::unmarshal(old_obj.clone()).unwrap()),
         KindExec::ServiceAccountKind => ServiceAccount::unmarshal(obj.clone()).unwrap().transition_validation(&ServiceAccount::unmarshal(old_obj.clone()).unwrap()),
-        KindExec::CustomResourceKind(_) => {
-            proof {
-                K::V::unmarshal_result_determined_by_unmarshal_spec_and_status();
-                K::V::kind_is_custom_resource();
-            }
-            K::unmarshal(obj.clone()).unwrap().transition_validation(&K::unmarshal(old_obj.clone()).unwrap())
-        },
+        KindExec::CustomResourceKind(_) => t.valid_custom_transition(obj, old_obj),
     }
 }
 
-fn object_transition_validity_check(obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: Option<APIError>)
+fn object_transition_validity_check(t: &ExecutableInstalledTypes, obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: Option<APIError>)
     requires
-        model::unmarshallable_object::<K::V>(obj@),
-        model::unmarshallable_object::<K::V>(old_obj@),
+        installed_kind(t@, obj@.kind),
+        model::unmarshallable_object(obj@, t@),
+        model::unmarshallable_object(old_obj@, t@),
         old_obj@.kind == obj@.kind,
-        model::valid_object::<K::V>(obj@),
-        model::valid_object::<K::V>(old_obj@),
-        obj@.kind is CustomResourceKind ==> obj@.kind == K::V::kind(),
-    ensures ret == model::object_transition_validity_check::<K::V>(obj@, old_obj@)
+        model::valid_object(obj@, t@),
+        model::valid_object(old_obj@, t@),
+    ensures ret == model::object_transition_validity_check(obj@, old_obj@, t@)
 {
-    if !Self::valid_transition(obj, old_obj) {
+    if !Self::valid_transition(t, obj, old_obj) {
         Some(APIError::Invalid)
     } else {
         None
     }
 }
 
-pub fn handle_get_request(req: &KubeGetRequest, s: &ApiServerState) -> (ret: KubeGetResponse)
+pub fn handle_get_request(t: &ExecutableInstalledTypes, req: &KubeGetRequest, s: &ApiServerState) -> (ret: KubeGetResponse)
     ensures ret@ == model::handle_get_request(req@, s@)
 {
     let req_key = KubeObjectRef {
-        kind: req.api_resource.kind(),
+        kind: t.api_resource_kind(&req.api_resource),
         name: req.name.clone(),
         namespace: req.namespace.clone(),
     };
@@ -165,17 +160,106 @@ pub fn handle_get_request(req: &KubeGetRequest, s: &ApiServerState) -> (ret: Kub
     }
 }
 
-fn create_request_admission_check(req: &KubeCreateRequest, s: &ApiServerState) -> (ret: Option<APIError>)
-    ensures ret == model::create_request_admission_check::<K::V>(req@, s@),
+// The continue token is "<resource version>/<offset>".
+#[verifier(external_body)] // TRUSTED
+fn continue_token(resource_version: i64, offset: usize) -> (ret: String)
+    ensures ret@ == model::continue_token(resource_version as int, offset as nat)
 {
-    if req.obj.metadata().name().is_none() && req.obj.metadata().generate_name().is_none() {
+    format!("{}/{}", resource_version, offset)
+}
+
+#[verifier(external_body)] // TRUSTED
+fn parse_continue_token(token: &String) -> (ret: Option<(i64, usize)>)
+    ensures
+        ret is Some == model::parse_continue_token(token@) is Some,
+        ret is Some ==> (ret->0.0 as int, ret->0.1 as nat) == model::parse_continue_token(token@)->0,
+{
+    let (resource_version, offset) = token.split_once('/')?;
+    Some((resource_version.parse().ok()?, offset.parse().ok()?))
+}
+
+fn list_page(req: &KubeListRequest, objs: Vec<DynamicObject>, resource_version: i64, offset: usize) -> (ret: KubeListResponse)
+    ensures ret@ == model::list_page(req@, objs.deep_view(), resource_version as int, offset as nat)
+{
+    let len = objs.len();
+    let start = if offset <= len { offset } else { len };
+    // start + limit < len is checked as limit < len - start to avoid overflow
+    let end = if req.limit.is_some() && req.limit.unwrap() > 0 && (req.limit.unwrap() as usize) < len - start {
+        start + req.limit.unwrap() as usize
+    } else {
+        len
+    };
+    let mut page = Vec::new();
+    let mut i = start;
+    while i < end
+        invariant
+            start <= i <= end <= objs.len(),
+            page.deep_view() == objs.deep_view().subrange(start as int, i as int),
+        decreases end - i,
+    {
+        page.push(objs[i].clone());
+        proof {
+            assert(objs.deep_view().subrange(start as int, i + 1) == objs.deep_view().subrange(start as int, i as int).push(objs[i as int]@));
+        }
+        i = i + 1;
+    }
+    KubeListResponse {
+        res: Ok(page),
+        continue_token: if end < len { Some(Self::continue_token(resource_version, end)) } else { None },
+    }
+}
+
+pub fn handle_list_request(t: &ExecutableInstalledTypes, req: &KubeListRequest, s: &mut ApiServerState) -> (ret: KubeListResponse)
+    ensures (final(s)@, ret@) == model::handle_list_request(req@, old(s)@)
+{
+    let kind = t.api_resource_kind(&req.api_resource);
+    let cached = match req.read_mode {
+        KubeReadMode::Cached => true,
+        KubeReadMode::Quorum => false,
+    };
+    if !req.field_selector_is_supported() {
+        KubeListResponse{res: Err(APIError::BadRequest), continue_token: None}
+    } else if cached && req.continue_token.is_some() {
+        KubeListResponse{res: Err(APIError::BadRequest), continue_token: None}
+    } else if cached || !req.is_paginated() {
+        KubeListResponse{res: Ok(s.resources.listed_objects(&kind, req)), continue_token: None}
+    } else if req.continue_token.is_none() {
+        let resp = Self::list_page(req, s.resources.listed_objects(&kind, req), s.resource_version_counter, 0);
+        if resp.continue_token.is_some() {
+            // Keep the snapshot for the later pages
+            s.list_snapshots.insert(s.resource_version_counter, s.resources.clone());
+        }
+        resp
+    } else {
+        let position = Self::parse_continue_token(req.continue_token.as_ref().unwrap());
+        if position.is_none() {
+            KubeListResponse{res: Err(APIError::BadRequest), continue_token: None}
+        } else {
+            let (resource_version, offset) = position.unwrap();
+            if s.list_snapshots.get(resource_version).is_none() || list_snapshot_compacted(s, resource_version) {
+                KubeListResponse{res: Err(APIError::Expired), continue_token: None}
+            } else {
+                let objs = s.list_snapshots.get(resource_version).unwrap().listed_objects(&kind, req);
+                Self::list_page(req, objs, resource_version, offset)
+            }
+        }
+    }
+}
+
+fn create_request_admission_check(t: &ExecutableInstalledTypes, req: &KubeCreateRequest, s: &ApiServerState) -> (ret: Option<APIError>)
+    requires installed_kind(t@, req@.obj.kind),
+    ensures ret == model::create_request_admission_check(t@, req@, s@),
+{
+    if req.obj.metadata().name().is_none() && req.obj.metadata().generated_name().is_none() {
         Some(APIError::Invalid)
     } else if req.obj.metadata().namespace().is_some() && !req.namespace.eq(&req.obj.metadata().namespace().unwrap()) {
         Some(APIError::BadRequest)
-    } else if !Self::unmarshallable_object(&req.obj) {
+    } else if !t.kind_is_namespaced(&t.kind(&req.obj)) && !string_equal(&req.namespace, "") {
+        Some(APIError::BadRequest)
+    } else if !t.unmarshallable_object(&req.obj) {
         Some(APIError::BadRequest)
     } else if req.obj.metadata().name().is_some() && s.resources.contains_key(&KubeObjectRef {
-        kind: req.obj.kind(),
+        kind: t.kind(&req.obj),
         name: req.obj.metadata().name().unwrap(),
         namespace: req.namespace.clone(),
     }) {
@@ -185,73 +269,93 @@ fn create_request_admission_check(req: &KubeCreateRequest, s: &ApiServerState) -
     }
 }
 
-fn created_object_validity_check(created_obj: &DynamicObject) -> (ret: Option<APIError>)
+fn created_object_validity_check(t: &ExecutableInstalledTypes, created_obj: &DynamicObject) -> (ret: Option<APIError>)
     requires
-        model::unmarshallable_object::<K::V>(created_obj@),
-        created_obj@.kind is CustomResourceKind ==> created_obj@.kind == K::V::kind(),
-    ensures ret == model::created_object_validity_check::<K::V>(created_obj@)
+        installed_kind(t@, created_obj@.kind),
+        model::unmarshallable_object(created_obj@, t@),
+    ensures ret == model::created_object_validity_check(created_obj@, t@)
 {
     if Self::metadata_validity_check(created_obj).is_some() {
         Self::metadata_validity_check(created_obj)
-    } else if Self::object_validity_check(created_obj).is_some() {
-        Self::object_validity_check(created_obj)
+    } else if Self::object_validity_check(t, created_obj).is_some() {
+        Self::object_validity_check(t, created_obj)
     } else {
         None
     }
 }
 
-// No plan to run conformance test on create requests without names
-// so just panic here
-#[verifier(external_body)]
-fn generated_name(s: &ApiServerState) -> (ret: String)
-    ensures ret@ == model::generated_name(s@)
+// The generated name is "<generate_name><n>" where n is the smallest number, starting from the uid counter,
+// that makes the name different from all the existing ones (see generated_name_spec).
+#[verifier(external_body)] // TRUSTED
+fn generated_name(s: &ApiServerState, generate_name: &String) -> (ret: String)
+    ensures ret@ == model::generated_name(s@, generate_name@)
 {
-    panic!()
+    let mut suffix = s.uid_counter;
+    loop {
+        let name = format!("{}{}", generate_name, suffix);
+        if !s.resources.contains_name(&name) {
+            return name;
+        }
+        suffix += 1;
+    }
 }
 
-pub fn handle_create_request(req: &KubeCreateRequest, s: &mut ApiServerState) -> (ret: KubeCreateResponse)
+pub fn handle_create_request(t: &ExecutableInstalledTypes, req: &KubeCreateRequest, s: &mut ApiServerState) -> (ret: KubeCreateResponse)
     requires
         // No integer overflow
         old(s).resource_version_counter < i64::MAX,
         old(s).uid_counter < i64::MAX,
-        req@.obj.kind is CustomResourceKind ==> req@.obj.kind == K::V::kind(),
-    ensures (s@, ret@) == model::handle_create_request::<K::V>(req@, old(s)@)
+        installed_kind(t@, req@.obj.kind),
+    ensures (final(s)@, ret@) == model::handle_create_request(t@, req@, old(s)@)
 {
-    // TODO: use if-let?
-    let request_check_error = Self::create_request_admission_check(req, s);
+    let request_check_error = Self::create_request_admission_check(t, req, s);
     if request_check_error.is_some() {
         KubeCreateResponse{res: Err(request_check_error.unwrap())}
     } else {
+        let kind = t.kind(&req.obj);
         let mut created_obj = req.obj.clone();
         if req.obj.metadata().name().is_none() {
-            created_obj.set_name(Self::generated_name(s));
+            created_obj.set_name(Self::generated_name(s, &req.obj.metadata().generated_name().unwrap()));
+        }
+        if t.kind_is_namespaced(&kind) {
+            created_obj.set_namespace(req.namespace.clone());
+        } else {
+            created_obj.unset_namespace();
         }
-        created_obj.set_namespace(req.namespace.clone());
         created_obj.set_resource_version(s.resource_version_counter);
         created_obj.set_uid(s.uid_counter);
+        created_obj.set_generation(1);
         created_obj.unset_deletion_timestamp();
-        created_obj.set_default_status::<K::V>();
-        let object_check_error = Self::created_object_validity_check(&created_obj);
-        if s.resources.contains_key(&created_obj.object_ref()) {
+        t.set_default_status(&mut created_obj);
+        // A cluster-scoped object is only created with the empty namespace (see create_request_admission_check),
+        // so the key of the created object always has the namespace of the request.
+        let created_obj_key = KubeObjectRef {
+            kind: kind,
+            name: created_obj.metadata().name().unwrap(),
+            namespace: req.namespace.clone(),
+        };
+        if s.resources.contains_key(&created_obj_key) {
             KubeCreateResponse{res: Err(APIError::ObjectAlreadyExists)}
-        } else if object_check_error.is_some() {
-            KubeCreateResponse{res: Err(object_check_error.unwrap())}
         } else {
-            s.resources.insert(created_obj.object_ref(), created_obj.clone());
-            s.stable_resources.remove(&created_obj.object_ref());
-            s.uid_counter = s.uid_counter + 1;
-            s.resource_version_counter = s.resource_version_counter + 1;
-            KubeCreateResponse{res: Ok(created_obj)}
+            let object_check_error = Self::created_object_validity_check(t, &created_obj);
+            if object_check_error.is_some() {
+                KubeCreateResponse{res: Err(object_check_error.unwrap())}
+            } else {
+                s.resources.insert(created_obj_key, created_obj.clone());
+                s.uid_counter = s.uid_counter + 1;
+                s.resource_version_counter = s.resource_version_counter + 1;
+                KubeCreateResponse{res: Ok(created_obj)}
+            }
         }
     }
 }
 
-pub fn handle_delete_request(req: &KubeDeleteRequest, s: &mut ApiServerState) -> (ret: KubeDeleteResponse)
+pub fn handle_delete_request(t: &ExecutableInstalledTypes, req: &KubeDeleteRequest, s: &mut ApiServerState) -> (ret: KubeDeleteResponse)
     requires old(s).resource_version_counter < i64::MAX // No integer overflow
-    ensures (s@, ret@) == model::handle_delete_request(req@, old(s)@)
+    ensures (final(s)@, ret@) == model::handle_delete_request(req@, old(s)@)
 {
     let req_key = KubeObjectRef {
-        kind: req.api_resource.kind(),
+        kind: t.api_resource_kind(&req.api_resource),
         name: req.name.clone(),
         namespace: req.namespace.clone(),
     };
@@ -259,21 +363,29 @@ pub fn handle_delete_request(req: &KubeDeleteRequest, s: &mut ApiServerState) ->
         KubeDeleteResponse{res: Err(APIError::ObjectNotFound)}
     } else {
         let mut obj = s.resources.get(&req_key).unwrap();
-        if obj.metadata().finalizers().is_some() && obj.metadata().finalizers().unwrap().len() > 0 {
-            if obj.metadata().has_deletion_timestamp() {
-                KubeDeleteResponse{res: Ok(())}
+        if req.preconditions.is_some() && preconditions_conflict(req.preconditions.as_ref().unwrap(), &obj) {
+            KubeDeleteResponse{res: Err(APIError::Conflict)}
+        } else {
+            let finalizers = finalizers_for_propagation_policy(&obj, req.propagation_policy);
+            if finalizers.is_some() && finalizers.as_ref().unwrap().len() > 0 {
+                if obj.metadata().has_deletion_timestamp() && finalizers_eq(&finalizers, &obj.metadata().finalizers()) {
+                    KubeDeleteResponse{res: Ok(())}
+                } else {
+                    if !obj.metadata().has_deletion_timestamp() {
+                        obj.set_current_deletion_timestamp();
+                    }
+                    obj.overwrite_finalizers(finalizers);
+                    obj.set_resource_version(s.resource_version_counter);
+                    let stamped_obj_with_new_rv = obj; // This renaming is just to stay consistent with the model
+                    s.resources.insert(req_key, stamped_obj_with_new_rv);
+                    s.resource_version_counter = s.resource_version_counter + 1;
+                    KubeDeleteResponse{res: Ok(())}
+                }
             } else {
-                obj.set_current_deletion_timestamp();
-                obj.set_resource_version(s.resource_version_counter);
-                let stamped_obj_with_new_rv = obj; // This renaming is just to stay consistent with the model
-                s.resources.insert(req_key, stamped_obj_with_new_rv);
+                s.resources.remove(&req_key);
                 s.resource_version_counter = s.resource_version_counter + 1;
                 KubeDeleteResponse{res: Ok(())}
             }
-        } else {
-            s.resources.remove(&req_key);
-            s.resource_version_counter = s.resource_version_counter + 1;
-            KubeDeleteResponse{res: Ok(())}
         }
     }
 }
@@ -287,11 +399,12 @@ fn allow_unconditional_update(kind: &KindExec) -> (ret: bool)
     }
 }
 
-fn update_request_admission_check_helper(name: &String, namespace: &String, obj: &DynamicObject, s: &ApiServerState) -> (ret: Option<APIError>)
-    ensures ret == model::update_request_admission_check_helper::<K::V>(name@, namespace@, obj@, s@)
+fn update_request_admission_check_helper(t: &ExecutableInstalledTypes, name: &String, namespace: &String, obj: &DynamicObject, s: &ApiServerState) -> (ret: Option<APIError>)
+    requires installed_kind(t@, obj@.kind),
+    ensures ret == model::update_request_admission_check_helper(t@, name@, namespace@, obj@, s@)
 {
     let key = KubeObjectRef {
-        kind: obj.kind(),
+        kind: t.kind(obj),
         namespace: namespace.clone(),
         name: name.clone(),
     };
@@ -302,7 +415,7 @@ fn update_request_admission_check_helper(name: &String, namespace: &String, obj:
     } else if obj.metadata().namespace().is_some()
     && !namespace.eq(&obj.metadata().namespace().unwrap()) {
         Some(APIError::BadRequest)
-    } else if !Self::unmarshallable_object(&obj) {
+    } else if !t.unmarshallable_object(obj) {
         Some(APIError::BadRequest)
     } else if !s.resources.contains_key(&key) {
         Some(APIError::ObjectNotFound)
@@ -314,83 +427,86 @@ fn update_request_admission_check_helper(name: &String, namespace: &String, obj:
         Some(APIError::Conflict)
     } else if obj.metadata().has_some_uid()
     && !obj.metadata().uid_eq(&s.resources.get(&key).unwrap().metadata()) {
-        Some(APIError::InternalError)
+        Some(APIError::Conflict)
     } else {
         None
     }
 }
 
-fn update_request_admission_check(req: &KubeUpdateRequest, s: &ApiServerState) -> (ret: Option<APIError>)
-    ensures ret == model::update_request_admission_check::<K::V>(req@, s@)
+fn update_request_admission_check(t: &ExecutableInstalledTypes, req: &KubeUpdateRequest, s: &ApiServerState) -> (ret: Option<APIError>)
+    requires installed_kind(t@, req@.obj.kind),
+    ensures ret == model::update_request_admission_check(t@, req@, s@)
 {
-    Self::update_request_admission_check_helper(&req.name, &req.namespace, &req.obj, s)
+    Self::update_request_admission_check_helper(t, &req.name, &req.namespace, &req.obj, s)
 }
 
 fn updated_object(req: &KubeUpdateRequest, old_obj: &DynamicObject) -> (ret: DynamicObject)
+    requires old_obj@.metadata.generation is Some ==> old_obj@.metadata.generation->0 < i64::MAX,
     ensures ret@ == model::updated_object(req@, old_obj@)
 {
     let mut updated_obj = req.obj.clone();
-    updated_obj.set_namespace(req.namespace.clone());
+    updated_obj.set_kind_from(old_obj);
+    updated_obj.set_namespace_from(old_obj);
     updated_obj.set_resource_version_from(old_obj);
     updated_obj.set_uid_from(old_obj);
+    if !req.obj.spec_eq(old_obj) {
+        let generation = if old_obj.metadata().generation().is_some() { old_obj.metadata().generation().unwrap() } else { 0 };
+        updated_obj.set_generation(generation + 1);
+    } else {
+        updated_obj.set_generation_from(old_obj);
+    }
     updated_obj.set_deletion_timestamp_from(old_obj);
     updated_obj.set_status_from(old_obj);
     updated_obj
 }
 
-fn updated_object_validity_check(updated_obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: Option<APIError>)
+fn updated_object_validity_check(t: &ExecutableInstalledTypes, updated_obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: Option<APIError>)
     requires
-        model::unmarshallable_object::<K::V>(updated_obj@),
-        model::unmarshallable_object::<K::V>(old_obj@),
+        installed_kind(t@, updated_obj@.kind),
+        model::unmarshallable_object(updated_obj@, t@),
+        model::unmarshallable_object(old_obj@, t@),
         old_obj@.kind == updated_obj@.kind,
-        model::valid_object::<K::V>(old_obj@),
-        updated_obj@.kind is CustomResourceKind ==> updated_obj@.kind == K::V::kind(),
-    ensures ret == model::updated_object_validity_check::<K::V>(updated_obj@, old_obj@)
+        model::valid_object(old_obj@, t@),
+    ensures ret == model::updated_object_validity_check(updated_obj@, old_obj@, t@)
 {
     if Self::metadata_validity_check(updated_obj).is_some() {
         Self::metadata_validity_check(updated_obj)
     } else if Self::metadata_transition_validity_check(updated_obj, old_obj).is_some() {
         Self::metadata_transition_validity_check(updated_obj, old_obj)
-    } else if Self::object_validity_check(updated_obj).is_some() {
-        Self::object_validity_check(updated_obj)
-    } else if Self::object_transition_validity_check(updated_obj, old_obj).is_some() {
-        Self::object_transition_validity_check(updated_obj, old_obj)
+    } else if Self::object_validity_check(t, updated_obj).is_some() {
+        Self::object_validity_check(t, updated_obj)
+    } else if Self::object_transition_validity_check(t, updated_obj, old_obj).is_some() {
+        Self::object_transition_validity_check(t, updated_obj, old_obj)
     } else {
         None
     }
 }
 
-pub fn handle_update_request(req: &KubeUpdateRequest, s: &mut ApiServerState) -> (ret: KubeUpdateResponse)
+pub fn handle_update_request(t: &ExecutableInstalledTypes, req: &KubeUpdateRequest, s: &mut ApiServerState) -> (ret: KubeUpdateResponse)
     requires
         // No integer overflow
         old(s).resource_version_counter < i64::MAX,
-        // The old version is marshallable
-        old(s)@.resources.contains_key(req@.key()) ==> model::unmarshallable_object::<K::V>(old(s)@.resources[req@.key()]),
-        // The old version passes state validation
-        old(s)@.resources.contains_key(req@.key()) ==> model::valid_object::<K::V>(old(s)@.resources[req@.key()]),
-        // The old version has the right key (name, namespace, kind)
-        old(s)@.resources.contains_key(req@.key()) ==> old(s)@.resources[req@.key()].object_ref() == req@.key(),
-        // All the three preconditions above are proved by the invariant lemma_always_each_object_in_etcd_is_well_formed
-        req@.obj.kind is CustomResourceKind ==> req@.obj.kind == K::V::kind(),
-    ensures (s@, ret@) == model::handle_update_request::<K::V>(req@, old(s)@)
-{
-    let request_check_error = Self::update_request_admission_check(req, s);
+        installed_kind(t@, req@.obj.kind),
+        stored_object_well_formed(t@, old(s)@, req@.key()),
+    ensures (final(s)@, ret@) == model::handle_update_request(t@, req@, old(s)@)
+{
+    let request_check_error = Self::update_request_admission_check(t, req, s);
     if request_check_error.is_some() {
         KubeUpdateResponse{res: Err(request_check_error.unwrap())}
     } else {
         let req_key = KubeObjectRef {
-            kind: req.obj.kind(),
+            kind: t.kind(&req.obj),
             namespace: req.namespace.clone(),
             name: req.name.clone(),
         };
         let old_obj = s.resources.get(&req_key).unwrap();
-        let mut updated_obj = Self::updated_object(&req, &old_obj);
+        let mut updated_obj = Self::updated_object(req, &old_obj);
         if updated_obj.eq(&old_obj) {
             KubeUpdateResponse{res: Ok(old_obj)}
         } else {
             updated_obj.set_resource_version(s.resource_version_counter);
             let updated_obj_with_new_rv = updated_obj; // This renaming is just to stay consistent with the model
-            let object_check_error = Self::updated_object_validity_check(&updated_obj_with_new_rv, &old_obj);
+            let object_check_error = Self::updated_object_validity_check(t, &updated_obj_with_new_rv, &old_obj);
             if object_check_error.is_some() {
                 KubeUpdateResponse{res: Err(object_check_error.unwrap())}
             } else {
@@ -398,12 +514,12 @@ pub fn handle_update_request(req: &KubeUpdateRequest, s: &mut ApiServerState) ->
                     || (updated_obj_with_new_rv.metadata().finalizers().is_some()
                         && updated_obj_with_new_rv.metadata().finalizers().unwrap().len() > 0)
                 {
-                    s.stable_resources.remove(&req_key);
                     s.resources.insert(req_key, updated_obj_with_new_rv.clone());
                     s.resource_version_counter = s.resource_version_counter + 1;
                     KubeUpdateResponse{res: Ok(updated_obj_with_new_rv)}
                 } else {
-                    s.resources.remove(&updated_obj_with_new_rv.object_ref());
+                    // The stored object has the right key, so req_key is also the key of the updated object
+                    s.resources.remove(&req_key);
                     s.resource_version_counter = s.resource_version_counter + 1;
                     KubeUpdateResponse{res: Ok(updated_obj_with_new_rv)}
                 }
@@ -412,52 +528,48 @@ pub fn handle_update_request(req: &KubeUpdateRequest, s: &mut ApiServerState) ->
     }
 }
 
-fn update_status_request_admission_check(req: &KubeUpdateStatusRequest, s: &ApiServerState) -> (ret: Option<APIError>)
-    ensures ret == model::update_status_request_admission_check::<K::V>(req@, s@)
+fn update_status_request_admission_check(t: &ExecutableInstalledTypes, req: &KubeUpdateStatusRequest, s: &ApiServerState) -> (ret: Option<APIError>)
+    requires installed_kind(t@, req@.obj.kind),
+    ensures ret == model::update_status_request_admission_check(t@, req@, s@)
 {
-    Self::update_request_admission_check_helper(&req.name, &req.namespace, &req.obj, s)
+    Self::update_request_admission_check_helper(t, &req.name, &req.namespace, &req.obj, s)
 }
 
 fn status_updated_object(req: &KubeUpdateStatusRequest, old_obj: &DynamicObject) -> (ret: DynamicObject)
     ensures ret@ == model::status_updated_object(req@, old_obj@)
 {
     let mut status_updated_object = req.obj.clone();
+    status_updated_object.set_kind_from(old_obj);
     status_updated_object.set_metadata_from(old_obj);
     status_updated_object.set_spec_from(old_obj);
     status_updated_object
 }
 
-pub fn handle_update_status_request(req: &KubeUpdateStatusRequest, s: &mut ApiServerState) -> (ret: KubeUpdateStatusResponse)
+pub fn handle_update_status_request(t: &ExecutableInstalledTypes, req: &KubeUpdateStatusRequest, s: &mut ApiServerState) -> (ret: KubeUpdateStatusResponse)
     requires
         // No integer overflow
         old(s).resource_version_counter < i64::MAX,
-        // The old version is marshallable
-        old(s)@.resources.contains_key(req@.key()) ==> model::unmarshallable_object::<K::V>(old(s)@.resources[req@.key()]),
-        // The old version passes state validation
-        old(s)@.resources.contains_key(req@.key()) ==> model::valid_object::<K::V>(old(s)@.resources[req@.key()]),
-        // The old version has the right key (name, namespace, kind)
-        old(s)@.resources.contains_key(req@.key()) ==> old(s)@.resources[req@.key()].object_ref() == req@.key(),
-        // All the three preconditions above are proved by the invariant lemma_always_each_object_in_etcd_is_well_formed
-        req@.obj.kind is CustomResourceKind ==> req@.obj.kind == K::V::kind(),
-    ensures (s@, ret@) == model::handle_update_status_request::<K::V>(req@, old(s)@)
-{
-    let request_check_error = Self::update_status_request_admission_check(req, s);
+        installed_kind(t@, req@.obj.kind),
+        stored_object_well_formed(t@, old(s)@, req@.key()),
+    ensures (final(s)@, ret@) == model::handle_update_status_request(t@, req@, old(s)@)
+{
+    let request_check_error = Self::update_status_request_admission_check(t, req, s);
     if request_check_error.is_some() {
         KubeUpdateStatusResponse{res: Err(request_check_error.unwrap())}
     } else {
         let req_key = KubeObjectRef {
-            kind: req.obj.kind(),
+            kind: t.kind(&req.obj),
             namespace: req.namespace.clone(),
             name: req.name.clone(),
         };
         let old_obj = s.resources.get(&req_key).unwrap();
-        let mut status_updated_obj = Self::status_updated_object(&req, &old_obj);
+        let mut status_updated_obj = Self::status_updated_object(req, &old_obj);
         if status_updated_obj.eq(&old_obj) {
             KubeUpdateStatusResponse{res: Ok(old_obj)}
         } else {
             status_updated_obj.set_resource_version(s.resource_version_counter);
             let status_updated_obj_with_new_rv = status_updated_obj; // This renaming is just to stay consistent with the model
-            let object_check_error = Self::updated_object_validity_check(&status_updated_obj_with_new_rv, &old_obj);
+            let object_check_error = Self::updated_object_validity_check(t, &status_updated_obj_with_new_rv, &old_obj);
             if object_check_error.is_some() {
                 KubeUpdateStatusResponse{res: Err(object_check_error.unwrap())}
             } else {
@@ -469,6 +581,222 @@ pub fn handle_update_status_request(req: &KubeUpdateStatusRequest, s: &mut ApiSe
     }
 }
 
+fn apply_creates_object(t: &ExecutableInstalledTypes, req: &KubePatchRequest, s: &ApiServerState) -> (ret: bool)
+    ensures ret == model::apply_creates_object(req@, s@)
+{
+    let key = KubeObjectRef {
+        kind: t.kind(&req.obj),
+        namespace: req.namespace.clone(),
+        name: req.name.clone(),
+    };
+    req.field_manager.is_some()
+    && !s.resources.contains_key(&key)
+    && !req.obj.metadata().has_some_resource_version()
+    && !req.obj.metadata().has_some_uid()
+}
+
+fn patch_request_admission_check(t: &ExecutableInstalledTypes, req: &KubePatchRequest, s: &ApiServerState) -> (ret: Option<APIError>)
+    ensures ret == model::patch_request_admission_check(t@, req@, s@)
+{
+    let key = KubeObjectRef {
+        kind: t.kind(&req.obj),
+        namespace: req.namespace.clone(),
+        name: req.name.clone(),
+    };
+    if req.obj.metadata().name().is_some() && !req.name.eq(&req.obj.metadata().name().unwrap()) {
+        Some(APIError::BadRequest)
+    } else if req.obj.metadata().namespace().is_some() && !req.namespace.eq(&req.obj.metadata().namespace().unwrap()) {
+        Some(APIError::BadRequest)
+    } else if req.field_manager.is_some() && req.obj.metadata().name().is_none() {
+        Some(APIError::BadRequest)
+    } else if !s.resources.contains_key(&key) && !Self::apply_creates_object(t, req, s) {
+        Some(APIError::ObjectNotFound)
+    } else if !s.resources.contains_key(&key) {
+        // The patch is a server-side apply that creates the object, which carries neither rv nor uid
+        None
+    } else if req.obj.metadata().has_some_resource_version()
+    && !req.obj.metadata().resource_version_eq(&s.resources.get(&key).unwrap().metadata()) {
+        Some(APIError::Conflict)
+    } else if req.obj.metadata().has_some_uid()
+    && !req.obj.metadata().uid_eq(&s.resources.get(&key).unwrap().metadata()) {
+        Some(APIError::Conflict)
+    } else {
+        None
+    }
+}
+
+// patched_object is trusted because merge_patch is uninterpreted in the spec
+// (see DynamicObject::merge_patch_from for the implementation of the merge).
+#[verifier(external_body)] // TRUSTED
+fn patched_object(req: &KubePatchRequest, old_obj: &DynamicObject) -> (ret: DynamicObject)
+    ensures ret@ == model::patched_object(req@, old_obj@)
+{
+    let mut patched_obj = old_obj.clone();
+    patched_obj.merge_patch_from(&req.obj);
+    patched_obj
+}
+
+pub fn handle_patch_request(t: &ExecutableInstalledTypes, req: &KubePatchRequest, s: &mut ApiServerState) -> (ret: KubePatchResponse)
+    requires
+        // No integer overflow
+        old(s).resource_version_counter < i64::MAX,
+        old(s).uid_counter < i64::MAX,
+        installed_kind(t@, req@.obj.kind),
+        stored_object_well_formed(t@, old(s)@, req@.key()),
+    ensures (final(s)@, ret@) == model::handle_patch_request(t@, req@, old(s)@)
+{
+    let request_check_error = Self::patch_request_admission_check(t, req, s);
+    if request_check_error.is_some() {
+        KubePatchResponse{res: Err(request_check_error.unwrap())}
+    } else if Self::apply_creates_object(t, req, s) {
+        let create_req = KubeCreateRequest {
+            api_resource: req.api_resource.clone(),
+            namespace: req.namespace.clone(),
+            obj: req.obj.clone(),
+        };
+        let create_resp = Self::handle_create_request(t, &create_req, s);
+        KubePatchResponse{res: create_resp.res}
+    } else {
+        let key = KubeObjectRef {
+            kind: t.kind(&req.obj),
+            namespace: req.namespace.clone(),
+            name: req.name.clone(),
+        };
+        let update_req = KubeUpdateRequest {
+            api_resource: req.api_resource.clone(),
+            name: req.name.clone(),
+            namespace: req.namespace.clone(),
+            obj: Self::patched_object(req, &s.resources.get(&key).unwrap()),
+        };
+        let update_resp = Self::handle_update_request(t, &update_req, s);
+        KubePatchResponse{res: update_resp.res}
+    }
+}
+
+// The get-then requests are only specified as a whole by the message handlers of the spec,
+// so the ensures below say that the response forms the same message as the one returned by the spec.
+
+pub fn handle_get_then_delete_request(t: &ExecutableInstalledTypes, req: &KubeGetThenDeleteRequest, s: &mut ApiServerState) -> (ret: KubeGetThenDeleteResponse)
+    requires old(s).resource_version_counter < i64::MAX // No integer overflow
+    ensures
+        forall |msg: Message| #![trigger model::handle_get_then_delete_request_msg(msg, old(s)@)]
+            msg.content.is_get_then_delete_request() && msg.content.get_get_then_delete_request() == req@
+            ==> model::handle_get_then_delete_request_msg(msg, old(s)@) == (final(s)@, form_get_then_delete_resp_msg(msg, ret@)),
+{
+    if !req.well_formed() {
+        KubeGetThenDeleteResponse{res: Err(APIError::BadRequest)}
+    } else {
+        let req_key = KubeObjectRef {
+            kind: t.api_resource_kind(&req.api_resource),
+            name: req.name.clone(),
+            namespace: req.namespace.clone(),
+        };
+        if !s.resources.contains_key(&req_key) {
+            KubeGetThenDeleteResponse{res: Err(APIError::ObjectNotFound)}
+        } else {
+            let current_obj = s.resources.get(&req_key).unwrap();
+            if current_obj.metadata().owner_references_contains(&req.owner_ref) && req.predicate.holds_on_object(&current_obj) {
+                let delete_req = KubeDeleteRequest {
+                    api_resource: req.api_resource.clone(),
+                    name: req.name.clone(),
+                    namespace: req.namespace.clone(),
+                    preconditions: None,
+                    propagation_policy: None,
+                };
+                let delete_resp = Self::handle_delete_request(t, &delete_req, s);
+                KubeGetThenDeleteResponse{res: delete_resp.res}
+            } else {
+                KubeGetThenDeleteResponse{res: Err(APIError::TransactionAbort)}
+            }
+        }
+    }
+}
+
+pub fn handle_get_then_update_request(t: &ExecutableInstalledTypes, req: &KubeGetThenUpdateRequest, s: &mut ApiServerState) -> (ret: KubeGetThenUpdateResponse)
+    requires
+        // No integer overflow
+        old(s).resource_version_counter < i64::MAX,
+        installed_kind(t@, req@.obj.kind),
+        stored_object_well_formed(t@, old(s)@, req@.key()),
+    ensures
+        forall |msg: Message| #![trigger model::handle_get_then_update_request_msg(t@, msg, old(s)@)]
+            msg.content.is_get_then_update_request() && msg.content.get_get_then_update_request() == req@
+            ==> model::handle_get_then_update_request_msg(t@, msg, old(s)@) == (final(s)@, form_get_then_update_resp_msg(msg, ret@)),
+{
+    if !req.well_formed() {
+        KubeGetThenUpdateResponse{res: Err(APIError::BadRequest)}
+    } else {
+        let req_key = KubeObjectRef {
+            kind: t.kind(&req.obj),
+            name: req.name.clone(),
+            namespace: req.namespace.clone(),
+        };
+        if !s.resources.contains_key(&req_key) {
+            KubeGetThenUpdateResponse{res: Err(APIError::ObjectNotFound)}
+        } else {
+            let current_obj = s.resources.get(&req_key).unwrap();
+            if current_obj.metadata().owner_references_contains(&req.owner_ref) && req.predicate.holds_on_object(&current_obj) {
+                // Note that resource_version and uid comes from the current object to avoid conflict error
+                let mut new_obj = req.obj.clone();
+                new_obj.set_resource_version_from(&current_obj);
+                new_obj.set_uid_from(&current_obj);
+                let update_req = KubeUpdateRequest {
+                    api_resource: req.api_resource.clone(),
+                    name: req.name.clone(),
+                    namespace: req.namespace.clone(),
+                    obj: new_obj,
+                };
+                let update_resp = Self::handle_update_request(t, &update_req, s);
+                KubeGetThenUpdateResponse{res: update_resp.res}
+            } else {
+                KubeGetThenUpdateResponse{res: Err(APIError::TransactionAbort)}
+            }
+        }
+    }
+}
+
+pub fn handle_get_then_update_status_request(t: &ExecutableInstalledTypes, req: &KubeGetThenUpdateStatusRequest, s: &mut ApiServerState) -> (ret: KubeGetThenUpdateStatusResponse)
+    requires
+        // No integer overflow
+        old(s).resource_version_counter < i64::MAX,
+        installed_kind(t@, req@.obj.kind),
+        stored_object_well_formed(t@, old(s)@, req@.key()),
+    ensures
+        forall |msg: Message| #![trigger model::handle_get_then_update_status_request_msg(t@, msg, old(s)@)]
+            msg.content.is_get_then_update_status_request() && msg.content.get_get_then_update_status_request() == req@
+            ==> model::handle_get_then_update_status_request_msg(t@, msg, old(s)@) == (final(s)@, form_get_then_update_status_resp_msg(msg, ret@)),
+{
+    if !req.well_formed() {
+        KubeGetThenUpdateStatusResponse{res: Err(APIError::BadRequest)}
+    } else {
+        let req_key = KubeObjectRef {
+            kind: t.kind(&req.obj),
+            name: req.name.clone(),
+            namespace: req.namespace.clone(),
+        };
+        if !s.resources.contains_key(&req_key) {
+            KubeGetThenUpdateStatusResponse{res: Err(APIError::ObjectNotFound)}
+        } else {
+            let current_obj = s.resources.get(&req_key).unwrap();
+            if current_obj.metadata().owner_references_contains(&req.owner_ref) && req.predicate.holds_on_object(&current_obj) {
+                // Only the status of the new object matters
+                let mut new_obj = current_obj.clone();
+                new_obj.set_status_from(&req.obj);
+                let update_status_req = KubeUpdateStatusRequest {
+                    api_resource: req.api_resource.clone(),
+                    name: req.name.clone(),
+                    namespace: req.namespace.clone(),
+                    obj: new_obj,
+                };
+                let update_status_resp = Self::handle_update_status_request(t, &update_status_req, s);
+                KubeGetThenUpdateStatusResponse{res: update_status_resp.res}
+            } else {
+                KubeGetThenUpdateStatusResponse{res: Err(APIError::TransactionAbort)}
+            }
+        }
+    }
+}
+
 }
 
 }
@@ -1,45 +1,99 @@
-use crate::executable_model::{object_map::ObjectMap, object_ref_set::ObjectRefSet};
-use crate::kubernetes_api_objects::exec::dynamic::DynamicObject;
+use crate::executable_model::object_map::ObjectMap;
 use crate::kubernetes_api_objects::spec::{
-    common::{Kind, ObjectRef},
-    dynamic::{DynamicObjectView, StoredState},
+    common::ResourceVersion,
+    dynamic::StoredState,
 };
-use crate::kubernetes_cluster::spec::api_server::types as model_types;
+use crate::kubernetes_cluster::spec::api_server::{state_machine as model, types as model_types};
 use vstd::prelude::*;
-use vstd::string::*;
 
 verus! {
 
-// This is the exec version of crate::kubernetes_cluster::spec::api_server::types::ApiServerState
+// This is the exec version of crate::kubernetes_cluster::spec::api_server::types::APIServerState
 // and is used as the "state" of the exec API server model.
 pub struct ApiServerState {
     pub resources: ObjectMap,
     pub uid_counter: i64,
     pub resource_version_counter: i64,
-    pub stable_resources: ObjectRefSet,
+    pub list_snapshots: ListSnapshots,
 }
 
 impl ApiServerState {
-    pub fn new() -> ApiServerState {
+    pub fn new() -> (s: ApiServerState)
+        ensures
+            s@.resources == StoredState::empty(),
+            s@.uid_counter == 0,
+            s@.resource_version_counter == 0,
+            s@.list_snapshots == Map::<ResourceVersion, StoredState>::empty(),
+    {
         ApiServerState {
             resources: ObjectMap::new(),
             uid_counter: 0,
             resource_version_counter: 0,
-            stable_resources: ObjectRefSet::new(),
+            list_snapshots: ListSnapshots::new(),
         }
     }
 }
 
 impl View for ApiServerState {
-    type V = model_types::ApiServerState;
-    open spec fn view(&self) -> model_types::ApiServerState {
-        model_types::ApiServerState {
+    type V = model_types::APIServerState;
+    open spec fn view(&self) -> model_types::APIServerState {
+        model_types::APIServerState {
             resources: self.resources@,
             uid_counter: self.uid_counter as int,
             resource_version_counter: self.resource_version_counter as int,
-            stable_resources: self.stable_resources@,
+            list_snapshots: self.list_snapshots@,
         }
     }
 }
 
+// This is the exec version of the snapshots in crate::kubernetes_cluster::spec::api_server::types::APIServerState
+// that paginated lists are served from.
+#[verifier(external_body)]
+pub struct ListSnapshots {
+    inner: std::collections::BTreeMap<i64, ObjectMap>,
+}
+
+impl ListSnapshots {
+    pub uninterp spec fn view(&self) -> Map<ResourceVersion, StoredState>;
+
+    #[verifier(external_body)]
+    pub fn new() -> (m: Self)
+        ensures m@ == Map::<ResourceVersion, StoredState>::empty(),
+    {
+        ListSnapshots { inner: std::collections::BTreeMap::new() }
+    }
+
+    #[verifier(external_body)]
+    pub fn insert(&mut self, resource_version: i64, resources: ObjectMap)
+        ensures final(self)@ == old(self)@.insert(resource_version as int, resources@),
+    {
+        self.inner.insert(resource_version, resources);
+    }
+
+    #[verifier(external_body)]
+    pub fn get(&self, resource_version: i64) -> (v: Option<&ObjectMap>)
+        ensures
+            self@.contains_key(resource_version as int) == v is Some,
+            v is Some ==> v->0@ == self@[resource_version as int],
+    {
+        self.inner.get(&resource_version)
+    }
+
+    // clear drops all the snapshots, like a compaction of etcd. Compaction is not a step of the model
+    // (see list_snapshot_compacted), so this is only called from outside the model.
+    #[verifier(external)]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
+
+// The exec model never compacts a snapshot while handling a request: the snapshots are only dropped by
+// ListSnapshots::clear, after which they are gone from list_snapshots.
+#[verifier(external_body)] // TRUSTED
+pub fn list_snapshot_compacted(s: &ApiServerState, resource_version: i64) -> (b: bool)
+    ensures b == model::list_snapshot_compacted(s@, resource_version as int),
+{
+    false
+}
+
 }
@@ -1,18 +1,15 @@
 use crate::executable_model::string_set::*;
-use crate::kubernetes_api_objects::error::UnmarshalError;
-use crate::kubernetes_api_objects::exec::{api_resource::ApiResource, prelude::*};
-use crate::kubernetes_api_objects::spec::prelude::*;
-use crate::kubernetes_cluster::spec::{
-    api_server::state_machine as model, api_server::types as model_types,
-};
-use crate::vstd_ext::string_view::StringView;
+use crate::kubernetes_api_objects::exec::prelude::*;
+use crate::kubernetes_api_objects::spec::{api_method as spec_api_method, prelude::*};
+use crate::kubernetes_cluster::spec::api_server::state_machine as model;
+use crate::vstd_ext::string_view::*;
+use std::collections::BTreeMap;
 use vstd::prelude::*;
-use vstd::string::*;
 
 // We use ExternalObjectRef, instead of KubeObjectRef, as the key of the ObjectMap
 // because the key has to implement a few traits including Ord and PartialOrd.
 // It's easy to implement such traits for ExternalObjectRef but hard for KubeObjectRef
-// because it internally uses vstd::string::String, which does not implement such traits.
+// because it is defined inside verus!.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq)]
 pub struct ExternalObjectRef {
     pub kind: KindExec,
@@ -30,6 +27,39 @@ impl KubeObjectRef {
     }
 }
 
+// selects mirrors ListRequest::selects: only the match labels of the label selector are considered,
+// and the field selector only supports metadata.name and metadata.namespace.
+pub fn selects(
+    obj: &kube::api::DynamicObject, label_selector: Option<&BTreeMap<std::string::String, std::string::String>>,
+    field_selector: Option<&BTreeMap<std::string::String, std::string::String>>,
+) -> bool {
+    let labels = obj.metadata.labels.clone().unwrap_or_default();
+    let labels_match = label_selector.map_or(true, |selector| {
+        selector.iter().all(|(k, v)| labels.get(k) == Some(v))
+    });
+    let fields_match = field_selector.map_or(true, |selector| {
+        selector.iter().all(|(path, v)| match path.as_str() {
+            "metadata.name" => obj.metadata.name.as_ref() == Some(v),
+            "metadata.namespace" => obj.metadata.namespace.as_ref() == Some(v),
+            _ => false,
+        })
+    });
+    labels_match && fields_match
+}
+
+// spec_of returns everything in the object other than its metadata and status, which is what the model
+// treats as the spec of the object (e.g., the data of a ConfigMap).
+pub fn spec_of(obj: &kube::api::DynamicObject) -> serde_json::Value {
+    let mut data = obj.data.clone();
+    if let Some(data) = data.as_object_mut() {
+        data.remove("status");
+    }
+    data
+}
+
+pub const FOREGROUND_DELETION_FINALIZER: &str = "foregroundDeletion";
+pub const ORPHAN_FINALIZER: &str = "orphan";
+
 verus! {
 
 pub struct KubeObjectRef {
@@ -61,28 +91,6 @@ impl std::clone::Clone for KubeObjectRef {
     }
 }
 
-impl ApiResource {
-    // This kind() is not a perfect implementation but it is sufficient for conformance tests.
-    #[verifier(external_body)]
-    pub fn kind(&self) -> (kind: KindExec)
-        ensures kind@ == self@.kind,
-    {
-        match self.as_kube_ref().kind.as_str() {
-            "ConfigMap" => KindExec::ConfigMapKind,
-            "DaemonSet" => KindExec::DaemonSetKind,
-            "PersistentVolumeClaim" => KindExec::PersistentVolumeClaimKind,
-            "Pod" => KindExec::PodKind,
-            "Role" => KindExec::RoleKind,
-            "RoleBinding" => KindExec::RoleBindingKind,
-            "StatefulSet" => KindExec::StatefulSetKind,
-            "Service" => KindExec::ServiceKind,
-            "ServiceAccount" => KindExec::ServiceAccountKind,
-            "Secret" => KindExec::SecretKind,
-            _ => panic!(), // We assume the DynamicObject won't be a custom object
-        }
-    }
-}
-
 impl ObjectMeta {
     pub fn finalizers_as_set(&self) -> (ret: StringSet)
         ensures ret@ == self@.finalizers_as_set()
@@ -96,6 +104,13 @@ impl ObjectMeta {
 }
 
 impl DynamicObjectView {
+    pub open spec fn with_kind(self, kind: Kind) -> DynamicObjectView {
+        DynamicObjectView {
+            kind: kind,
+            ..self
+        }
+    }
+
     pub open spec fn without_deletion_timestamp(self) -> DynamicObjectView {
         DynamicObjectView {
             metadata: ObjectMetaView {
@@ -116,6 +131,16 @@ impl DynamicObjectView {
         }
     }
 
+    pub open spec fn overwrite_namespace(self, namespace: Option<StringView>) -> DynamicObjectView {
+        DynamicObjectView {
+            metadata: ObjectMetaView {
+                namespace: namespace,
+                ..self.metadata
+            },
+            ..self
+        }
+    }
+
     pub open spec fn overwrite_uid(self, uid: Option<int>) -> DynamicObjectView {
         DynamicObjectView {
             metadata: ObjectMetaView {
@@ -136,6 +161,26 @@ impl DynamicObjectView {
         }
     }
 
+    pub open spec fn overwrite_generation(self, generation: Option<int>) -> DynamicObjectView {
+        DynamicObjectView {
+            metadata: ObjectMetaView {
+                generation: generation,
+                ..self.metadata
+            },
+            ..self
+        }
+    }
+
+    pub open spec fn overwrite_finalizers(self, finalizers: Option<Seq<StringView>>) -> DynamicObjectView {
+        DynamicObjectView {
+            metadata: ObjectMetaView {
+                finalizers: finalizers,
+                ..self.metadata
+            },
+            ..self
+        }
+    }
+
     pub open spec fn with_spec(self, spec: Value) -> DynamicObjectView {
         DynamicObjectView {
             spec: spec,
@@ -151,111 +196,117 @@ impl DynamicObjectView {
     }
 }
 
+// We implement getter and setter functions of the DynamicObject
+// which are used by the exec API server model.
+// Like the other wrappers of kube-rs types, they are trusted to do what their ensures say on the kube-rs object.
+// The spec of an object is everything other than its metadata and status (see spec_of).
+
 impl DynamicObject {
-    // This kind() is not a perfect implementation but it is sufficient for conformance tests.
     #[verifier(external_body)]
-    pub fn kind(&self) -> (kind: KindExec)
-        ensures kind@ == self@.kind,
+    pub fn set_kind_from(&mut self, other: &DynamicObject)
+        ensures final(self)@ == old(self)@.with_kind(other@.kind),
     {
-        if self.as_kube_ref().types.is_none() {
-            panic!();
-        }
-        match self.as_kube_ref().types.as_ref().unwrap().kind.as_str() {
-            "ConfigMap" => KindExec::ConfigMapKind,
-            "DaemonSet" => KindExec::DaemonSetKind,
-            "PersistentVolumeClaim" => KindExec::PersistentVolumeClaimKind,
-            "Pod" => KindExec::PodKind,
-            "Role" => KindExec::RoleKind,
-            "RoleBinding" => KindExec::RoleBindingKind,
-            "StatefulSet" => KindExec::StatefulSetKind,
-            "Service" => KindExec::ServiceKind,
-            "ServiceAccount" => KindExec::ServiceAccountKind,
-            "Secret" => KindExec::SecretKind,
-            _ => panic!(), // We assume the DynamicObject won't be a custom object
-        }
+        self.as_kube_mut_ref().types = other.as_kube_ref().types.clone();
     }
 
-    // We implement getter and setter functions of the DynamicObject
-    // which are used by the exec API server model.
+    #[verifier(external_body)]
+    pub fn set_name(&mut self, name: String)
+        ensures final(self)@ == old(self)@.with_name(name@),
+    {
+        self.as_kube_mut_ref().metadata.name = Some(name);
+    }
 
     #[verifier(external_body)]
-    pub fn object_ref(&self) -> (object_ref: KubeObjectRef)
-        requires
-            self@.metadata.name is Some,
-            self@.metadata.namespace is Some,
-        ensures object_ref@ == self@.object_ref(),
+    pub fn set_namespace(&mut self, namespace: String)
+        ensures final(self)@ == old(self)@.with_namespace(namespace@),
     {
-        KubeObjectRef {
-            kind: self.kind(),
-            name: self.metadata().name().unwrap(),
-            namespace: self.metadata().namespace().unwrap(),
-        }
+        self.as_kube_mut_ref().metadata.namespace = Some(namespace);
     }
 
     #[verifier(external_body)]
-    pub fn set_name(&mut self, name: String)
-        ensures self@ == old(self)@.with_name(name@),
+    pub fn unset_namespace(&mut self)
+        ensures final(self)@ == old(self)@.overwrite_namespace(None),
     {
-        self.as_kube_mut_ref().metadata.name = Some(name);
+        self.as_kube_mut_ref().metadata.namespace = None;
     }
 
     #[verifier(external_body)]
-    pub fn set_namespace(&mut self, namespace: String)
-        ensures self@ == old(self)@.with_namespace(namespace@),
+    pub fn set_namespace_from(&mut self, other: &DynamicObject)
+        ensures final(self)@ == old(self)@.overwrite_namespace(other@.metadata.namespace),
     {
-        self.as_kube_mut_ref().metadata.namespace = Some(namespace);
+        self.as_kube_mut_ref().metadata.namespace = other.as_kube_ref().metadata.namespace.clone();
     }
 
     #[verifier(external_body)]
     pub fn set_resource_version(&mut self, resource_version: i64)
-        ensures self@ == old(self)@.with_resource_version(resource_version as int),
+        ensures final(self)@ == old(self)@.with_resource_version(resource_version as int),
     {
         self.as_kube_mut_ref().metadata.resource_version = Some(resource_version.to_string());
     }
 
     #[verifier(external_body)]
     pub fn set_resource_version_from(&mut self, other: &DynamicObject)
-        ensures self@ == old(self)@.overwrite_resource_version(other@.metadata.resource_version),
+        ensures final(self)@ == old(self)@.overwrite_resource_version(other@.metadata.resource_version),
     {
         self.as_kube_mut_ref().metadata.resource_version = other.as_kube_ref().metadata.resource_version.clone();
     }
 
     #[verifier(external_body)]
     pub fn set_uid(&mut self, uid: i64)
-        ensures self@ == old(self)@.with_uid(uid as int),
+        ensures final(self)@ == old(self)@.with_uid(uid as int),
     {
         self.as_kube_mut_ref().metadata.uid = Some(uid.to_string());
     }
 
     #[verifier(external_body)]
     pub fn set_uid_from(&mut self, other: &DynamicObject)
-        ensures self@ == old(self)@.overwrite_uid(other@.metadata.uid),
+        ensures final(self)@ == old(self)@.overwrite_uid(other@.metadata.uid),
     {
         self.as_kube_mut_ref().metadata.uid = other.as_kube_ref().metadata.uid.clone();
     }
 
+    #[verifier(external_body)]
+    pub fn set_generation(&mut self, generation: i64)
+        ensures final(self)@ == old(self)@.overwrite_generation(Some(generation as int)),
+    {
+        self.as_kube_mut_ref().metadata.generation = Some(generation);
+    }
+
+    #[verifier(external_body)]
+    pub fn set_generation_from(&mut self, other: &DynamicObject)
+        ensures final(self)@ == old(self)@.overwrite_generation(other@.metadata.generation),
+    {
+        self.as_kube_mut_ref().metadata.generation = other.as_kube_ref().metadata.generation;
+    }
+
+    #[verifier(external_body)]
+    pub fn overwrite_finalizers(&mut self, finalizers: Option<Vec<String>>)
+        ensures final(self)@ == old(self)@.overwrite_finalizers(finalizers.deep_view()),
+    {
+        self.as_kube_mut_ref().metadata.finalizers = finalizers;
+    }
+
     #[verifier(external_body)]
     pub fn unset_deletion_timestamp(&mut self)
-        ensures self@ == old(self)@.without_deletion_timestamp(),
+        ensures final(self)@ == old(self)@.without_deletion_timestamp(),
     {
         self.as_kube_mut_ref().metadata.deletion_timestamp = None;
     }
 
     #[verifier(external_body)]
     pub fn set_deletion_timestamp_from(&mut self, other: &DynamicObject)
-        ensures self@ == old(self)@.overwrite_deletion_stamp(other@.metadata.deletion_timestamp),
+        ensures final(self)@ == old(self)@.overwrite_deletion_stamp(other@.metadata.deletion_timestamp),
     {
         self.as_kube_mut_ref().metadata.deletion_timestamp = other.as_kube_ref().metadata.deletion_timestamp.clone();
     }
 
-
     // This function sets the deletion timestamp to the current time.
     // This seems a bit inconsistent with the model's behavior which
     // always sets it to the return value of deletion_timestamp().
     // However, this function is actually closer to Kubernetes' real behavior.
     #[verifier(external_body)]
     pub fn set_current_deletion_timestamp(&mut self)
-        ensures self@ == old(self)@.with_deletion_timestamp(model::deletion_timestamp()),
+        ensures final(self)@ == old(self)@.with_deletion_timestamp(model::deletion_timestamp()),
     {
         self.as_kube_mut_ref().metadata.deletion_timestamp = Some(k8s_openapi::apimachinery::pkg::apis::meta::v1::Time(chrono::Utc::now()));
     }
@@ -267,37 +318,125 @@ impl DynamicObject {
         self.as_kube_ref() == other.as_kube_ref()
     }
 
+    #[verifier(external_body)]
+    pub fn spec_eq(&self, other: &DynamicObject) -> (ret: bool)
+        ensures ret == (self@.spec == other@.spec)
+    {
+        spec_of(self.as_kube_ref()) == spec_of(other.as_kube_ref())
+    }
+
     #[verifier(external_body)]
     pub fn set_metadata_from(&mut self, other: &DynamicObject)
-        ensures self@ == old(self)@.with_metadata(other@.metadata)
+        ensures final(self)@ == old(self)@.with_metadata(other@.metadata)
     {
         self.as_kube_mut_ref().metadata = other.as_kube_ref().metadata.clone()
     }
 
-    // We intentionally leave set_spec_from overly sets the data and
-    // set_status_from does not set any data because they are rather
-    // difficult to implement: we'll have to unmarshal other.inner and extract
-    // the spec/status part from the json representation.
-    // Since these two are left empty, the conformance test should not check
-    // the content of the spec and status.
     #[verifier(external_body)]
     pub fn set_spec_from(&mut self, other: &DynamicObject)
-        ensures self@ == old(self)@.with_spec(other@.spec)
+        ensures final(self)@ == old(self)@.with_spec(other@.spec)
     {
-        self.as_kube_mut_ref().data = other.as_kube_ref().data.clone()
+        let status = self.as_kube_ref().data.get("status").cloned();
+        let mut data = spec_of(other.as_kube_ref());
+        if let (Some(status), Some(data)) = (status, data.as_object_mut()) {
+            data.insert("status".to_string(), status);
+        }
+        self.as_kube_mut_ref().data = data;
     }
 
     #[verifier(external_body)]
     pub fn set_status_from(&mut self, other: &DynamicObject)
-        ensures self@ == old(self)@.with_status(other@.status)
-    {}
+        ensures final(self)@ == old(self)@.with_status(other@.status)
+    {
+        let status = other.as_kube_ref().data.get("status").cloned();
+        let inner = self.as_kube_mut_ref();
+        if !inner.data.is_object() {
+            inner.data = serde_json::Value::Object(serde_json::Map::new());
+        }
+        let data = inner.data.as_object_mut().unwrap();
+        match status {
+            Some(status) => data.insert("status".to_string(), status),
+            None => data.remove("status"),
+        };
+    }
+}
+
+impl KubeListRequest {
+    #[verifier(external_body)]
+    pub fn field_selector_is_supported(&self) -> (ret: bool)
+        ensures ret == self@.field_selector_is_supported()
+    {
+        match &self.field_selector {
+            Some(field_selector) => field_selector.clone().into_rust_map().keys()
+                .all(|path| path == "metadata.name" || path == "metadata.namespace"),
+            None => true,
+        }
+    }
 
+    pub fn is_paginated(&self) -> (ret: bool)
+        ensures ret == self@.is_paginated()
+    {
+        (self.limit.is_some() && self.limit.unwrap() > 0) || self.continue_token.is_some()
+    }
+}
+
+impl KubeGetThenPredicate {
     #[verifier(external_body)]
-    pub fn set_default_status<K: CustomResourceView>(&mut self)
-        ensures
-            self@ == old(self)@.with_status(model::marshalled_default_status::<K>(self@.kind)),
-            model::unmarshallable_status::<K>(self@),
-    {}
+    pub fn holds_on_object(&self, obj: &DynamicObject) -> (ret: bool)
+        ensures ret == self@.holds_on(obj@)
+    {
+        self.holds_on(obj.as_kube_ref())
+    }
+}
+
+// finalizers_for_propagation_policy mirrors finalizers_for_propagation_policy in the spec:
+// the propagation policy is recorded as the foregroundDeletion or orphan finalizer.
+#[verifier(external_body)]
+pub fn finalizers_for_propagation_policy(obj: &DynamicObject, propagation_policy: Option<KubePropagationPolicy>) -> (ret: Option<Vec<String>>)
+    ensures
+        ret.deep_view() == spec_api_method::finalizers_for_propagation_policy(obj@, match propagation_policy {
+            Some(policy) => Some(policy@),
+            None => None,
+        }),
+{
+    let finalizers = &obj.as_kube_ref().metadata.finalizers;
+    let policy = match propagation_policy {
+        None => return finalizers.clone(),
+        Some(policy) => policy,
+    };
+    let mut other_finalizers: Vec<std::string::String> = finalizers.clone().unwrap_or_default().into_iter()
+        .filter(|f| f != FOREGROUND_DELETION_FINALIZER && f != ORPHAN_FINALIZER)
+        .collect();
+    match policy {
+        KubePropagationPolicy::Background if finalizers.is_none() => return None,
+        KubePropagationPolicy::Background => {}
+        KubePropagationPolicy::Foreground => other_finalizers.push(FOREGROUND_DELETION_FINALIZER.to_string()),
+        KubePropagationPolicy::Orphan => other_finalizers.push(ORPHAN_FINALIZER.to_string()),
+    }
+    Some(other_finalizers)
+}
+
+// preconditions_conflict says whether the object fails the uid or the resource version in the preconditions
+// of a delete request (see delete_request_admission_check).
+#[verifier(external_body)]
+pub fn preconditions_conflict(preconditions: &Preconditions, obj: &DynamicObject) -> (ret: bool)
+    ensures
+        ret == {
+            ||| preconditions@.uid is Some && preconditions@.uid != obj@.metadata.uid
+            ||| preconditions@.resource_version is Some && preconditions@.resource_version != obj@.metadata.resource_version
+        },
+{
+    let preconditions = preconditions.as_kube_ref();
+    let metadata = &obj.as_kube_ref().metadata;
+    (preconditions.uid.is_some() && preconditions.uid != metadata.uid)
+    || (preconditions.resource_version.is_some() && preconditions.resource_version != metadata.resource_version)
+}
+
+#[verifier(external_body)]
+pub fn finalizers_eq(finalizers: &Option<Vec<String>>, other: &Option<Vec<String>>) -> (ret: bool)
+    ensures ret == (finalizers.deep_view() == other.deep_view()),
+{
+    finalizers == other
 }
 
 // We implement the validation logic in exec code for different k8s object types below
@@ -329,6 +468,26 @@ impl DaemonSet {
     }
 }
 
+impl Event {
+    pub fn state_validation(&self) -> (ret: bool)
+        ensures ret == self@.state_validation()
+    { true }
+
+    pub fn transition_validation(&self, old_obj: &Event) -> (ret: bool)
+        ensures ret == self@.transition_validation(old_obj@)
+    { true }
+}
+
+impl Node {
+    pub fn state_validation(&self) -> (ret: bool)
+        ensures ret == self@.state_validation()
+    { true }
+
+    pub fn transition_validation(&self, old_obj: &Node) -> (ret: bool)
+        ensures ret == self@.transition_validation(old_obj@)
+    { true }
+}
+
 impl Pod {
     pub fn state_validation(&self) -> (ret: bool)
         ensures ret == self@.state_validation()
@@ -368,14 +527,12 @@ impl Role {
         if self.rules().is_some() {
             let policy_rules = self.rules().unwrap();
             let mut all_valid = true;
-            let mut i = 0;
             for i in 0..policy_rules.len()
                 invariant
                     all_valid == (forall |j| #![trigger policy_rules[j]] 0 <= j < i ==> policy_rules@.map_values(|policy_rule: PolicyRule| policy_rule@)[j].state_validation()),
                     i <= policy_rules.len(),
             {
                 all_valid = all_valid && policy_rules[i].state_validation();
-                i += 1;
             }
             all_valid
         } else {
@@ -392,9 +549,9 @@ impl RoleBinding {
     pub fn state_validation(&self) -> (ret: bool)
         ensures ret == self@.state_validation()
     {
-        self.role_ref().api_group().eq(&"rbac.authorization.k8s.io".to_string())
-        && (self.role_ref().kind().eq(&"Role".to_string())
-            || self.role_ref().kind().eq(&"ClusterRole".to_string()))
+        string_equal(&self.role_ref().api_group(), "rbac.authorization.k8s.io")
+        && (string_equal(&self.role_ref().kind(), "Role")
+            || string_equal(&self.role_ref().kind(), "ClusterRole"))
     }
 
     pub fn transition_validation(&self, old_obj: &RoleBinding) -> (ret: bool)
@@ -409,7 +566,6 @@ impl Secret {
         ensures ret == self@.state_validation()
     { true }
 
-
     pub fn transition_validation(&self, old_obj: &Secret) -> (ret: bool)
         ensures ret == self@.transition_validation(old_obj@)
     { true }
@@ -446,208 +602,24 @@ impl StatefulSet {
         }
     }
 
+    // All the fields of the spec other than replicas, template and persistent_volume_claim_retention_policy
+    // are immutable. Each field of StatefulSetSpec is modeled in StatefulSetSpecView, so comparing the rest
+    // of the kube-rs spec is the same as comparing the rest of the spec view.
+    #[verifier(external_body)]
     pub fn transition_validation(&self, old_obj: &StatefulSet) -> (ret: bool)
         requires
             self@.state_validation(),
             old_obj@.state_validation(),
         ensures ret == self@.transition_validation(old_obj@)
     {
-        self.spec().unwrap().selector().eq(&old_obj.spec().unwrap().selector())
-        && self.spec().unwrap().service_name().eq(&old_obj.spec().unwrap().service_name())
-        && (self.spec().unwrap().pod_management_policy().is_none() == old_obj.spec().unwrap().pod_management_policy().is_none()
-            && if self.spec().unwrap().pod_management_policy().is_some() {
-                self.spec().unwrap().pod_management_policy().unwrap().eq(&old_obj.spec().unwrap().pod_management_policy().unwrap())
-            } else {
-                true
-            }
-        )
-        && (self.spec().unwrap().volume_claim_templates().is_none() == old_obj.spec().unwrap().volume_claim_templates().is_none()
-            && if self.spec().unwrap().volume_claim_templates().is_some() {
-                let new_volume_claim_templates = self.spec().unwrap().volume_claim_templates().unwrap();
-                let old_volume_claim_templates = old_obj.spec().unwrap().volume_claim_templates().unwrap();
-                let mut all_equal = true;
-                let mut i = 0;
-                if new_volume_claim_templates.len() != old_volume_claim_templates.len() {
-                    proof { assert(self@.spec->0.volume_claim_templates->0.len() != old_obj@.spec->0.volume_claim_templates->0.len()) }
-                    proof { assert(!(self@.spec->0.volume_claim_templates->0 =~= old_obj@.spec->0.volume_claim_templates->0)) }
-                    false
-                } else {
-                    for i in 0..new_volume_claim_templates.len()
-                        invariant
-                            all_equal == (forall |j| #![trigger new_volume_claim_templates[j]]
-                                0 <= j < i
-                                    ==> new_volume_claim_templates@.map_values(|p: PersistentVolumeClaim| p@)[j] == old_volume_claim_templates@.map_values(|p: PersistentVolumeClaim| p@)[j]
-                            ),
-                            i <= new_volume_claim_templates.len(),
-                            new_volume_claim_templates.len() == old_volume_claim_templates.len(),
-                    {
-                        all_equal = all_equal && new_volume_claim_templates[i].eq(&old_volume_claim_templates[i]);
-                    }
-                    proof { assert(all_equal == (self@.spec->0.volume_claim_templates =~= old_obj@.spec->0.volume_claim_templates)) }
-                    all_equal
-                }
-            } else {
-                true
-            }
-        )
-    }
-}
-
-// CustomResource is the trait that associates the exec methods (e.g., unmarshal)
-// with their spec correspondences, and is only used for proving the executable API server model
-// conforms to the spec model.
-pub trait CustomResource: View
-where Self::V: CustomResourceView, Self: std::marker::Sized
-{
-    fn unmarshal(obj: DynamicObject) -> (res: Result<Self, UnmarshalError>)
-        ensures
-            res is Ok == Self::V::unmarshal(obj@) is Ok,
-            res is Ok ==> res->Ok_0@ == Self::V::unmarshal(obj@)->Ok_0;
-
-    fn state_validation(&self) -> (ret: bool)
-        ensures ret == self@.state_validation();
-
-    fn transition_validation(&self, old_obj: &Self) -> (ret: bool)
-        ensures ret == self@.transition_validation(old_obj@);
-}
-
-// SimpleCRView and SimpleCR are types only used for instantiating the executable API server model,
-// which is only used for conformance tests, so we keep the two types minimal.
-
-pub struct SimpleCRView {
-    pub metadata: ObjectMetaView,
-    pub spec: SimpleCRSpecView,
-    pub status: Option<SimpleCRStatusView>,
-}
-
-pub struct SimpleCRSpecView {}
-
-pub struct SimpleCRStatusView {}
-
-impl ResourceView for SimpleCRView {
-    type Spec = SimpleCRSpecView;
-    type Status = Option<SimpleCRStatusView>;
-
-    open spec fn default() -> SimpleCRView {
-        SimpleCRView {
-            metadata: ObjectMetaView::default(),
-            spec: arbitrary(),
-            status: None,
-        }
-    }
-
-    open spec fn metadata(self) -> ObjectMetaView { self.metadata }
-
-    open spec fn kind() -> Kind { Kind::CustomResourceKind("simple"@) }
-
-    open spec fn object_ref(self) -> ObjectRef {
-        ObjectRef {
-            kind: Self::kind(),
-            name: self.metadata.name->0,
-            namespace: self.metadata.namespace->0,
-        }
-    }
-
-    proof fn object_ref_is_well_formed() {}
-
-    open spec fn spec(self) -> SimpleCRSpecView { self.spec }
-
-    open spec fn status(self) -> Option<SimpleCRStatusView> { self.status }
-
-    open spec fn marshal(self) -> DynamicObjectView {
-        DynamicObjectView {
-            kind: Self::kind(),
-            metadata: self.metadata,
-            spec: SimpleCRView::marshal_spec(self.spec),
-            status: SimpleCRView::marshal_status(self.status),
-        }
-    }
-
-    open spec fn unmarshal(obj: DynamicObjectView) -> Result<SimpleCRView, UnmarshalError> {
-        if obj.kind != Self::kind() {
-            Err(())
-        } else if !(SimpleCRView::unmarshal_spec(obj.spec) is Ok) {
-            Err(())
-        } else if !(SimpleCRView::unmarshal_status(obj.status) is Ok) {
-            Err(())
-        } else {
-            Ok(SimpleCRView {
-                metadata: obj.metadata,
-                spec: SimpleCRView::unmarshal_spec(obj.spec)->Ok_0,
-                status: SimpleCRView::unmarshal_status(obj.status)->Ok_0,
-            })
-        }
-    }
-
-    proof fn marshal_preserves_integrity() {
-        SimpleCRView::marshal_spec_preserves_integrity();
-        SimpleCRView::marshal_status_preserves_integrity();
-    }
-
-    proof fn marshal_preserves_metadata() {}
-
-    proof fn marshal_preserves_kind() {}
-
-    uninterp spec fn marshal_spec(s: SimpleCRSpecView) -> Value;
-
-    uninterp spec fn unmarshal_spec(v: Value) -> Result<SimpleCRSpecView, UnmarshalError>;
-
-    uninterp spec fn marshal_status(s: Option<SimpleCRStatusView>) -> Value;
-
-    uninterp spec fn unmarshal_status(v: Value) -> Result<Option<SimpleCRStatusView>, UnmarshalError>;
-
-    #[verifier(external_body)]
-    proof fn marshal_spec_preserves_integrity() {}
-
-    #[verifier(external_body)]
-    proof fn marshal_status_preserves_integrity() {}
-
-    proof fn unmarshal_result_determined_by_unmarshal_spec_and_status() {}
-
-    open spec fn state_validation(self) -> bool { true }
-
-    open spec fn transition_validation(self, old_obj: SimpleCRView) -> bool { true }
-}
-
-impl CustomResourceView for SimpleCRView {
-    proof fn kind_is_custom_resource() {}
-
-    open spec fn spec_status_validation(obj_spec: Self::Spec, obj_status: Self::Status) -> bool { true }
-
-    proof fn validation_result_determined_by_spec_and_status()
-        ensures forall |obj: Self| #[trigger] obj.state_validation() == Self::spec_status_validation(obj.spec(), obj.status())
-    {}
-}
-
-#[verifier(external_body)]
-pub struct SimpleCR {}
-
-impl View for SimpleCR {
-    type V = SimpleCRView;
-
-    uninterp spec fn view(&self) -> SimpleCRView;
-}
-
-impl CustomResource for SimpleCR {
-    #[verifier(external_body)]
-    fn unmarshal(obj: DynamicObject) -> (res: Result<SimpleCR, UnmarshalError>)
-        ensures
-            res is Ok == SimpleCRView::unmarshal(obj@) is Ok,
-            res is Ok ==> res->Ok_0@ == SimpleCRView::unmarshal(obj@)->Ok_0,
-    {
-        Ok(SimpleCR {})
-    }
-
-    fn state_validation(&self) -> (ret: bool)
-        ensures ret == self@.state_validation()
-    {
-        true
-    }
-
-    fn transition_validation(&self, old_obj: &Self) -> (ret: bool)
-        ensures ret == self@.transition_validation(old_obj@)
-    {
-        true
+        let old_spec = old_obj.as_kube_ref().spec.clone().unwrap();
+        let new_spec = k8s_openapi::api::apps::v1::StatefulSetSpec {
+            replicas: old_spec.replicas,
+            template: old_spec.template.clone(),
+            persistent_volume_claim_retention_policy: old_spec.persistent_volume_claim_retention_policy.clone(),
+            ..self.as_kube_ref().spec.clone().unwrap()
+        };
+        old_spec == new_spec
     }
 }
 
@@ -655,9 +627,7 @@ impl CustomResource for SimpleCR {
 pub fn filter_controller_references(owner_references: Vec<OwnerReference>) -> (ret: Vec<OwnerReference>)
     ensures ret@.map_values(|o: OwnerReference| o@) == owner_references@.map_values(|o: OwnerReference| o@).filter(|o: OwnerReferenceView| o.controller is Some && o.controller->0)
 {
-    // TODO: is there a way to prove postconditions involving filter?
-    // TODO: clone the entire Vec instead of clone in map()
-    owner_references.iter().map(|o: &OwnerReference| o.clone()).filter(|o: &OwnerReference| o.controller().is_some() && o.controller().unwrap()).collect()
+    owner_references.into_iter().filter(|o: &OwnerReference| o.controller().is_some() && o.controller().unwrap()).collect()
 }
 
 #[verifier(external_body)]
@@ -0,0 +1,228 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::exec::{api_resource::ApiResource, prelude::*};
+use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::kubernetes_cluster::spec::api_server::{state_machine as model, types::*};
+use kube::api::Resource;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use vstd::prelude::*;
+
+// InstalledTypeExec is what the exec API server model knows about one installed custom resource type:
+// its kind in the spec (the string in Kind::CustomResourceKind), its scope, and its validation.
+#[derive(Clone)]
+pub struct InstalledTypeExec {
+    pub name: std::string::String,
+    pub namespaced: bool,
+    pub unmarshallable: Arc<dyn Fn(&kube::api::DynamicObject) -> bool + Send + Sync>,
+    pub valid_object: Arc<dyn Fn(&kube::api::DynamicObject) -> bool + Send + Sync>,
+    pub valid_transition: Arc<dyn Fn(&kube::api::DynamicObject, &kube::api::DynamicObject) -> bool + Send + Sync>,
+}
+
+verus! {
+
+// ExecutableInstalledTypes is the exec version of crate::kubernetes_cluster::spec::api_server::types::InstalledTypes.
+// It is keyed by the kind of the custom resource in kube-rs (e.g., VReplicaSet) so that the kind of an object
+// can be looked up from its type meta.
+//
+// The view is uninterpreted: we trust that each type installed here behaves like the InstalledType
+// that the cluster installs for it (see Cluster::installed_type), which is what the ensures below say.
+#[verifier(external_body)]
+pub struct ExecutableInstalledTypes {
+    inner: BTreeMap<std::string::String, InstalledTypeExec>,
+}
+
+// installed_kind says that the objects of kind can be handled: builtin kinds are always supported,
+// and a custom resource kind has to be installed.
+pub open spec fn installed_kind(installed_types: InstalledTypes, kind: Kind) -> bool {
+    kind is CustomResourceKind ==> installed_types.contains_key(kind->CustomResourceKind_0)
+}
+
+impl ExecutableInstalledTypes {
+    pub uninterp spec fn view(&self) -> InstalledTypes;
+
+    #[verifier(external_body)]
+    pub fn new() -> (t: Self)
+        ensures t@ == InstalledTypes::empty(),
+    {
+        ExecutableInstalledTypes { inner: BTreeMap::new() }
+    }
+
+    #[verifier(external_body)]
+    pub fn kind(&self, obj: &DynamicObject) -> (kind: KindExec)
+        ensures kind@ == obj@.kind,
+    {
+        self.kind_of_kube_kind(&kube_kind_of(obj.as_kube_ref()))
+    }
+
+    #[verifier(external_body)]
+    pub fn api_resource_kind(&self, api_resource: &ApiResource) -> (kind: KindExec)
+        ensures kind@ == api_resource@.kind,
+    {
+        self.kind_of_kube_kind(&api_resource.as_kube_ref().kind)
+    }
+
+    #[verifier(external_body)]
+    pub fn is_installed(&self, kind: &KindExec) -> (b: bool)
+        ensures b == installed_kind(self@, kind@),
+    {
+        match kind {
+            KindExec::CustomResourceKind(name) => self.get(name).is_some(),
+            _ => true,
+        }
+    }
+
+    #[verifier(external_body)]
+    pub fn kind_is_namespaced(&self, kind: &KindExec) -> (b: bool)
+        requires installed_kind(self@, kind@),
+        ensures b == model::kind_is_namespaced(self@, kind@),
+    {
+        match kind {
+            KindExec::CustomResourceKind(name) => self.get(name).unwrap().namespaced,
+            KindExec::NodeKind => false,
+            _ => true,
+        }
+    }
+
+    #[verifier(external_body)]
+    pub fn unmarshallable_object(&self, obj: &DynamicObject) -> (b: bool)
+        requires installed_kind(self@, obj@.kind),
+        ensures b == model::unmarshallable_object(obj@, self@),
+    {
+        let obj = obj.as_kube_ref();
+        match self.kind_of_kube_kind(&kube_kind_of(obj)) {
+            KindExec::ConfigMapKind => parses::<k8s_openapi::api::core::v1::ConfigMap>(obj),
+            KindExec::DaemonSetKind => parses::<k8s_openapi::api::apps::v1::DaemonSet>(obj),
+            KindExec::EventKind => parses::<k8s_openapi::api::core::v1::Event>(obj),
+            KindExec::NodeKind => parses::<k8s_openapi::api::core::v1::Node>(obj),
+            KindExec::PersistentVolumeClaimKind => parses::<k8s_openapi::api::core::v1::PersistentVolumeClaim>(obj),
+            KindExec::PodKind => parses::<k8s_openapi::api::core::v1::Pod>(obj),
+            KindExec::RoleBindingKind => parses::<k8s_openapi::api::rbac::v1::RoleBinding>(obj),
+            KindExec::RoleKind => parses::<k8s_openapi::api::rbac::v1::Role>(obj),
+            KindExec::SecretKind => parses::<k8s_openapi::api::core::v1::Secret>(obj),
+            KindExec::ServiceKind => parses::<k8s_openapi::api::core::v1::Service>(obj),
+            KindExec::StatefulSetKind => parses::<k8s_openapi::api::apps::v1::StatefulSet>(obj),
+            KindExec::ServiceAccountKind => parses::<k8s_openapi::api::core::v1::ServiceAccount>(obj),
+            KindExec::CustomResourceKind(name) => (self.get(&name).unwrap().unmarshallable)(obj),
+        }
+    }
+
+    #[verifier(external_body)]
+    pub fn valid_custom_object(&self, obj: &DynamicObject) -> (b: bool)
+        requires
+            obj@.kind is CustomResourceKind,
+            installed_kind(self@, obj@.kind),
+            model::unmarshallable_object(obj@, self@),
+        ensures b == model::valid_object(obj@, self@),
+    {
+        match self.kind(obj) {
+            KindExec::CustomResourceKind(name) => (self.get(&name).unwrap().valid_object)(obj.as_kube_ref()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[verifier(external_body)]
+    pub fn valid_custom_transition(&self, obj: &DynamicObject, old_obj: &DynamicObject) -> (b: bool)
+        requires
+            obj@.kind is CustomResourceKind,
+            old_obj@.kind == obj@.kind,
+            installed_kind(self@, obj@.kind),
+            model::unmarshallable_object(obj@, self@),
+            model::unmarshallable_object(old_obj@, self@),
+            model::valid_object(obj@, self@),
+            model::valid_object(old_obj@, self@),
+        ensures b == model::valid_transition(obj@, old_obj@, self@),
+    {
+        match self.kind(obj) {
+            KindExec::CustomResourceKind(name) => (self.get(&name).unwrap().valid_transition)(obj.as_kube_ref(), old_obj.as_kube_ref()),
+            _ => unreachable!(),
+        }
+    }
+
+    // set_default_status overwrites the status of obj with the default one,
+    // which is no status at all for each kind supported so far.
+    #[verifier(external_body)]
+    pub fn set_default_status(&self, obj: &mut DynamicObject)
+        requires installed_kind(self@, old(obj)@.kind),
+        ensures final(obj)@ == old(obj)@.with_status(model::marshalled_default_status(old(obj)@.kind, self@)),
+    {
+        if let Some(data) = obj.as_kube_mut_ref().data.as_object_mut() {
+            data.remove("status");
+        }
+    }
+
+    // install installs the custom resource type K as the kind name in the spec, with the validation of K.
+    // valid_object and valid_transition are expected to be the exec versions of state_validation and
+    // transition_validation of the spec type of K; the objects passed to them are unmarshallable to K.
+    #[verifier(external)]
+    pub fn install<K>(
+        &mut self, name: &str, namespaced: bool, valid_object: fn(&K) -> bool, valid_transition: fn(&K, &K) -> bool,
+    ) where
+        K: Resource + DeserializeOwned + 'static,
+        K::DynamicType: Default,
+    {
+        self.inner.insert(K::kind(&K::DynamicType::default()).to_string(), InstalledTypeExec {
+            name: name.to_string(),
+            namespaced: namespaced,
+            unmarshallable: Arc::new(|obj| parses::<K>(obj)),
+            valid_object: Arc::new(move |obj| valid_object(&obj.clone().try_parse::<K>().unwrap())),
+            valid_transition: Arc::new(move |obj, old_obj| {
+                valid_transition(&obj.clone().try_parse::<K>().unwrap(), &old_obj.clone().try_parse::<K>().unwrap())
+            }),
+        });
+    }
+
+    // install_without_validation installs K for a kind that has no validation (e.g., Namespace and Lease,
+    // which are not modeled as builtin kinds).
+    #[verifier(external)]
+    pub fn install_without_validation<K>(&mut self, name: &str, namespaced: bool)
+    where
+        K: Resource + DeserializeOwned + 'static,
+        K::DynamicType: Default,
+    {
+        self.install::<K>(name, namespaced, |_obj| true, |_obj, _old_obj| true);
+    }
+
+    // kind_of_kube_kind returns the kind in the spec for the kind in kube-rs. A kind that is neither builtin
+    // nor installed is returned as a custom resource kind that is not installed.
+    #[verifier(external)]
+    pub fn kind_of_kube_kind(&self, kube_kind: &str) -> KindExec {
+        match kube_kind {
+            "ConfigMap" => KindExec::ConfigMapKind,
+            "DaemonSet" => KindExec::DaemonSetKind,
+            "Event" => KindExec::EventKind,
+            "Node" => KindExec::NodeKind,
+            "PersistentVolumeClaim" => KindExec::PersistentVolumeClaimKind,
+            "Pod" => KindExec::PodKind,
+            "RoleBinding" => KindExec::RoleBindingKind,
+            "Role" => KindExec::RoleKind,
+            "Secret" => KindExec::SecretKind,
+            "Service" => KindExec::ServiceKind,
+            "StatefulSet" => KindExec::StatefulSetKind,
+            "ServiceAccount" => KindExec::ServiceAccountKind,
+            _ => match self.inner.get(kube_kind) {
+                Some(installed_type) => KindExec::CustomResourceKind(installed_type.name.clone()),
+                None => KindExec::CustomResourceKind(kube_kind.to_string()),
+            },
+        }
+    }
+
+    #[verifier(external)]
+    fn get(&self, name: &str) -> Option<&InstalledTypeExec> {
+        self.inner.values().find(|installed_type| installed_type.name == name)
+    }
+}
+
+// An object without type meta has no kind, which is not the kind of any installed type.
+#[verifier(external)]
+fn kube_kind_of(obj: &kube::api::DynamicObject) -> std::string::String {
+    obj.types.as_ref().map(|t| t.kind.clone()).unwrap_or_default()
+}
+
+#[verifier(external)]
+fn parses<K: Resource + DeserializeOwned>(obj: &kube::api::DynamicObject) -> bool {
+    obj.clone().try_parse::<K>().is_ok()
+}
+
+}
@@ -3,7 +3,7 @@
 pub mod api_server;
 pub mod api_server_state;
 pub mod common;
+pub mod installed_types;
 pub mod object_map;
-pub mod object_ref_set;
 pub mod prelude;
 pub mod string_set;
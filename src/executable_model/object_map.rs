@@ -1,9 +1,10 @@
 use crate::executable_model::common::*;
-use crate::kubernetes_api_objects::exec::dynamic::DynamicObject;
+use crate::kubernetes_api_objects::exec::{api_method::KubeListRequest, common::KindExec, dynamic::DynamicObject, resource::*};
 use crate::kubernetes_api_objects::spec::{
     common::{Kind, ObjectRef},
     dynamic::{DynamicObjectView, StoredState},
 };
+use crate::kubernetes_cluster::spec::api_server::state_machine as model;
 use vstd::prelude::*;
 use vstd::string::*;
 
@@ -49,7 +50,7 @@ impl ObjectMap {
     #[verifier(external_body)]
     pub fn insert(&mut self, key: KubeObjectRef, value: DynamicObject) -> (old_v: Option<DynamicObject>)
         ensures
-            self@ == old(self)@.insert(key@, value@),
+            final(self)@ == old(self)@.insert(key@, value@),
             old(self)@.contains_key(key@) == old_v is Some,
             old_v is Some ==> old_v->0@ == old(self)@[key@],
     {
@@ -62,7 +63,7 @@ impl ObjectMap {
     #[verifier(external_body)]
     pub fn remove(&mut self, key: &KubeObjectRef) -> (old_v: Option<DynamicObject>)
         ensures
-            self@ == old(self)@.remove(key@),
+            final(self)@ == old(self)@.remove(key@),
             old(self)@.contains_key(key@) == old_v is Some,
             old_v is Some ==> old_v->0@ == old(self)@[key@],
     {
@@ -89,6 +90,30 @@ impl ObjectMap {
         self.inner.contains_key(&key.clone().into_external_object_ref())
     }
 
+    // listed_objects returns the objects of kind selected by req, in the order of their keys.
+    // NOTE: we trust that the order of the keys is the order that to_seq picks in listed_objects,
+    // which is the same assumption that the continue token makes on the order of the objects.
+    #[verifier(external_body)] // TRUSTED
+    pub fn listed_objects(&self, kind: &KindExec, req: &KubeListRequest) -> (objs: Vec<DynamicObject>)
+        requires kind@ == req@.kind,
+        ensures objs.deep_view() == model::listed_objects(req@, self@),
+    {
+        let label_selector = req.label_selector.as_ref().and_then(|selector| selector.as_kube_ref().match_labels.clone());
+        let field_selector = req.field_selector.as_ref().map(|selector| selector.clone().into_rust_map());
+        self.inner
+            .iter()
+            .filter(|(key, _)| &key.kind == kind && key.namespace == req.namespace)
+            .map(|(_, obj)| obj)
+            .filter(|obj| selects(obj.as_kube_ref(), label_selector.as_ref(), field_selector.as_ref()))
+            .cloned()
+            .collect()
+    }
+
+    #[verifier(external)]
+    pub fn contains_name(&self, name: &str) -> bool {
+        self.inner.keys().any(|key| key.name == name)
+    }
+
     #[verifier(external)]
     pub fn from_rust_map(inner: std::collections::BTreeMap<ExternalObjectRef, DynamicObject>) -> ObjectMap { ObjectMap { inner: inner } }
 
@@ -3,3 +3,4 @@
 pub use crate::executable_model::api_server::*;
 pub use crate::executable_model::api_server_state::*;
 pub use crate::executable_model::common::*;
+pub use crate::executable_model::installed_types::*;
@@ -1,15 +1,42 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::ExternalError;
 use crate::reconciler::exec::io::{VoidEReq, VoidEResp};
+use futures::Future;
+use std::time::Duration;
+use tracing::error;
 
-// A trait for the external api of a reconciler, whose core is a transition method, and the developer should wrap all
+// A trait for the external api of a reconciler, whose core is an async call method, and the developer should wrap all
 // possible operations they may need in the function.
-// Input is the input type of the external api and also the ? of Request<?> of the reconciler, i.e., it completes the
+// EReq is the input type of the external api and also the ? of Request<?> of the reconciler, i.e., it completes the
 // request type of a reconciler.
-// Similarly, Output is the output type of the external api, which composes the Response<?> type of a reconciler.
+// Similarly, EResp is the output type of the external api, which composes the Response<?> type of a reconciler.
 // Note that we can encapsulate all the required libraries here, so each reconciler only has one ExternalAPI type.
-pub trait ExternalShimLayer<EReq, EResp> {
-    fn external_call(req: EReq) -> EResp;
+//
+// The shim layer creates one instance by init when the controller starts, with the Config given to run_controller
+// (e.g., the endpoint and the credentials of the external system), and shares it across all the reconciles,
+// so the instance is also where to keep the state shared by the external calls (e.g., a connection pool).
+// external_call should return ExternalError::TransportError if the request cannot reach the external system;
+// the shim layer cancels the call and returns ExternalError::Timeout if it takes longer than
+// ControllerConfig::external_call_timeout (see call_with_timeout).
+pub trait ExternalShimLayer<EReq, EResp>: Sized + Send + Sync + 'static {
+    type Config: Send;
+
+    fn init(config: Self::Config) -> impl Future<Output = anyhow::Result<Self>> + Send;
+
+    fn external_call(&self, req: EReq) -> impl Future<Output = Result<EResp, ExternalError>> + Send;
+}
+
+// call_with_timeout sends req to the external system via external and gives up after timeout.
+// The abandoned call might have already taken effect on the external system.
+pub async fn call_with_timeout<E, EReq, EResp>(external: &E, req: EReq, timeout: Duration) -> Result<EResp, ExternalError>
+where
+    E: ExternalShimLayer<EReq, EResp>,
+{
+    match tokio::time::timeout(timeout, external.external_call(req)).await {
+        Ok(resp) => resp,
+        Err(_elapsed) => Err(ExternalError::Timeout),
+    }
 }
 
 // An empty library that implements External Library.
@@ -17,7 +44,15 @@ pub trait ExternalShimLayer<EReq, EResp> {
 pub struct VoidExternalShimLayer {}
 
 impl ExternalShimLayer<VoidEReq, VoidEResp> for VoidExternalShimLayer {
-    fn external_call(_input: VoidEReq) -> VoidEResp {
-        panic!("This should not be visited");
+    type Config = ();
+
+    async fn init(_config: ()) -> anyhow::Result<VoidExternalShimLayer> {
+        Ok(VoidExternalShimLayer {})
+    }
+
+    async fn external_call(&self, _req: VoidEReq) -> Result<VoidEResp, ExternalError> {
+        // There is no external system behind it, so the request can never be delivered
+        error!("VoidExternalShimLayer receives an external request");
+        Err(ExternalError::TransportError)
     }
 }
@@ -17,6 +17,7 @@ pub enum APIError {
     Timeout,
     ServerTimeout,
     TransactionAbort,
+    Expired,
     Other
 }
 
@@ -35,6 +36,7 @@ impl std::fmt::Debug for APIError {
             APIError::Timeout => write!(f, "Timeout"),
             APIError::ServerTimeout => write!(f, "ServerTimeout"),
             APIError::TransactionAbort => write!(f, "TransactionAbort"),
+            APIError::Expired => write!(f, "Expired"),
             APIError::Other => write!(f, "Other"),
         }
     }
@@ -51,6 +53,25 @@ impl APIError {
     }
 }
 
+// ExternalError is the error of a request to an external system (see ExternalShimLayer)
+// that fails before the external system gives any response.
+pub enum ExternalError {
+    // The external system does not respond in time
+    Timeout,
+    // The request cannot be sent to the external system or the response cannot be received
+    TransportError,
+}
+
+#[verifier(external)]
+impl std::fmt::Debug for ExternalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ExternalError::Timeout => write!(f, "Timeout"),
+            ExternalError::TransportError => write!(f, "TransportError"),
+        }
+    }
+}
+
 pub type UnmarshalError = ();
 
 
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: MIT
 use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::exec::{
-    api_resource::*, dynamic::*, owner_reference::*, preconditions::*, resource::*,
+    api_resource::*, dynamic::*, label_selector::*, owner_reference::*, preconditions::*, resource::*,
 };
 use crate::kubernetes_api_objects::spec::{api_method::*, common::ObjectRef};
+use crate::vstd_ext::string_map::*;
 use vstd::prelude::*;
 
 use vstd::pervasive::unreached;
@@ -32,14 +33,17 @@ pub enum KubeAPIRequest {
     GetThenDeleteRequest(KubeGetThenDeleteRequest),
     GetThenUpdateRequest(KubeGetThenUpdateRequest),
     GetThenUpdateStatusRequest(KubeGetThenUpdateStatusRequest),
+    PatchRequest(KubePatchRequest),
 }
 
 // KubeGetRequest has the name as the parameter of Api.get(), and namespace to instantiate an Api.
+// read_mode decides whether the shim layer may serve it from the watch caches.
 
 pub struct KubeGetRequest {
     pub api_resource: ApiResource,
     pub name: String,
     pub namespace: String,
+    pub read_mode: KubeReadMode,
 }
 
 impl KubeGetRequest {
@@ -58,15 +62,25 @@ impl View for KubeGetRequest {
                 name: self.name@,
                 namespace: self.namespace@,
             },
+            read_mode: self.read_mode@,
         }
     }
 }
 
-// KubeListRequest has the namespace to instantiate an Api.
+// KubeListRequest has the namespace to instantiate an Api,
+// and the label selector and field selector as the parameters of Api.list().
+// read_mode decides whether the shim layer may serve it from the watch caches.
+// limit and continue_token are the limit and continue parameters of Api.list();
+// if limit is None, the shim layer pages through the list by itself and returns all the objects at once.
 
 pub struct KubeListRequest {
     pub api_resource: ApiResource,
     pub namespace: String,
+    pub label_selector: Option<LabelSelector>,
+    pub field_selector: Option<StringMap>,
+    pub read_mode: KubeReadMode,
+    pub limit: Option<u32>,
+    pub continue_token: Option<String>,
 }
 
 impl KubeListRequest {
@@ -74,6 +88,48 @@ impl KubeListRequest {
     pub fn key(&self) -> std::string::String {
         format!("{}/{}", self.api_resource.as_kube_ref().kind, self.namespace)
     }
+
+    // label_selector_string renders the match labels of the label selector in the form of "k1=v1,k2=v2".
+    #[verifier(external)]
+    pub fn label_selector_string(&self) -> Option<std::string::String> {
+        self.label_selector.as_ref().and_then(|selector| {
+            selector.as_kube_ref().match_labels.as_ref().map(|match_labels| {
+                match_labels
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+        })
+    }
+
+    // field_selector_string renders the field selector in the form of "path1=v1,path2=v2".
+    // The values are escaped like fields.EscapeValue so that a value containing '\', ',' or '=' stays one value.
+    #[verifier(external)]
+    pub fn field_selector_string(&self) -> Option<std::string::String> {
+        self.field_selector.as_ref().map(|selector| {
+            selector
+                .clone()
+                .into_rust_map()
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, escape_field_selector_value(v)))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+    }
+}
+
+// See https://github.com/kubernetes/kubernetes/blob/v1.30.0/staging/src/k8s.io/apimachinery/pkg/fields/selector.go#L206
+#[verifier(external)]
+pub fn escape_field_selector_value(value: &str) -> std::string::String {
+    let mut escaped = std::string::String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || c == ',' || c == '=' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 impl View for KubeListRequest {
@@ -82,6 +138,35 @@ impl View for KubeListRequest {
         ListRequest {
             kind: self.api_resource@.kind,
             namespace: self.namespace@,
+            label_selector: self.label_selector.deep_view(),
+            field_selector: self.field_selector.deep_view(),
+            read_mode: self.read_mode@,
+            limit: match self.limit {
+                Some(limit) => Some(limit as nat),
+                None => None,
+            },
+            continue_token: match self.continue_token {
+                Some(continue_token) => Some(continue_token@),
+                None => None,
+            },
+        }
+    }
+}
+
+// KubeReadMode is the ReadMode of a Get or List request.
+
+#[derive(Clone, Copy, Debug)]
+pub enum KubeReadMode {
+    Quorum,
+    Cached,
+}
+
+impl View for KubeReadMode {
+    type V = ReadMode;
+    open spec fn view(&self) -> ReadMode {
+        match self {
+            KubeReadMode::Quorum => ReadMode::Quorum,
+            KubeReadMode::Cached => ReadMode::Cached,
         }
     }
 }
@@ -112,12 +197,14 @@ impl View for KubeCreateRequest {
 }
 
 // KubeDeleteRequest has the name as the parameter of Api.delete(), and namespace to instantiate an Api.
+// preconditions and propagation_policy are passed to Api.delete() in DeleteParams.
 
 pub struct KubeDeleteRequest {
     pub api_resource: ApiResource,
     pub name: String,
     pub namespace: String,
     pub preconditions: Option<Preconditions>,
+    pub propagation_policy: Option<KubePropagationPolicy>,
 }
 
 impl KubeDeleteRequest {
@@ -137,6 +224,41 @@ impl View for KubeDeleteRequest {
                 namespace: self.namespace@,
             },
             preconditions: self.preconditions.deep_view(),
+            propagation_policy: match self.propagation_policy {
+                Some(policy) => Some(policy@),
+                None => None,
+            },
+        }
+    }
+}
+
+// KubePropagationPolicy is the propagationPolicy in DeleteParams.
+
+#[derive(Clone, Copy, Debug)]
+pub enum KubePropagationPolicy {
+    Background,
+    Foreground,
+    Orphan,
+}
+
+impl KubePropagationPolicy {
+    #[verifier(external)]
+    pub fn into_kube(&self) -> kube::api::PropagationPolicy {
+        match self {
+            KubePropagationPolicy::Background => kube::api::PropagationPolicy::Background,
+            KubePropagationPolicy::Foreground => kube::api::PropagationPolicy::Foreground,
+            KubePropagationPolicy::Orphan => kube::api::PropagationPolicy::Orphan,
+        }
+    }
+}
+
+impl View for KubePropagationPolicy {
+    type V = PropagationPolicy;
+    open spec fn view(&self) -> PropagationPolicy {
+        match self {
+            KubePropagationPolicy::Background => PropagationPolicy::Background,
+            KubePropagationPolicy::Foreground => PropagationPolicy::Foreground,
+            KubePropagationPolicy::Orphan => PropagationPolicy::Orphan,
         }
     }
 }
@@ -195,15 +317,54 @@ impl View for KubeUpdateStatusRequest {
     }
 }
 
+// KubeGetThenPredicate is the predicate checked by the shim layer on the object returned by Api.get()
+// before the delete or the update of a get-then request.
+// The ownership check on owner_ref is performed by the shim layer for every predicate,
+// so OwnerRef adds no check of its own (see GetThenPredicate::OwnerRef).
+
+pub enum KubeGetThenPredicate {
+    OwnerRef,
+    LabelsMatch(StringMap),
+    ResourceVersionMatch(String),
+    SpecHashMatch(String),
+}
+
+impl KubeGetThenPredicate {
+    #[verifier(external)]
+    pub fn holds_on(&self, obj: &kube::api::DynamicObject) -> bool {
+        match self {
+            KubeGetThenPredicate::OwnerRef => true,
+            KubeGetThenPredicate::LabelsMatch(labels) => {
+                let obj_labels = obj.metadata.labels.clone().unwrap_or_default();
+                labels.clone().into_rust_map().iter().all(|(k, v)| obj_labels.get(k) == Some(v))
+            },
+            KubeGetThenPredicate::ResourceVersionMatch(rv) => obj.metadata.resource_version.as_ref() == Some(rv),
+            KubeGetThenPredicate::SpecHashMatch(hash) => &spec_hash_of(obj) == hash,
+        }
+    }
+}
+
+impl View for KubeGetThenPredicate {
+    type V = GetThenPredicate;
+    open spec fn view(&self) -> GetThenPredicate {
+        match self {
+            KubeGetThenPredicate::OwnerRef => GetThenPredicate::OwnerRef,
+            KubeGetThenPredicate::LabelsMatch(labels) => GetThenPredicate::LabelsMatch(labels@),
+            KubeGetThenPredicate::ResourceVersionMatch(rv) => GetThenPredicate::ResourceVersionMatch(rv@),
+            KubeGetThenPredicate::SpecHashMatch(hash) => GetThenPredicate::SpecHashMatch(hash@),
+        }
+    }
+}
+
 // KubeGetThenDeleteRequest has the name as the parameter of Api.get() and Api.delete(), and namespace to instantiate an Api.
-//
-// TODO: KubeGetThenDeleteRequest should carry a Box<dyn Fn(DynamicObject) -> bool> when Verus supports dyn
+// The object is deleted only if it is owned by owner_ref and predicate holds on it.
 
 pub struct KubeGetThenDeleteRequest {
     pub api_resource: ApiResource,
     pub name: String,
     pub namespace: String,
     pub owner_ref: OwnerReference,
+    pub predicate: KubeGetThenPredicate,
 }
 
 impl KubeGetThenDeleteRequest {
@@ -230,19 +391,20 @@ impl View for KubeGetThenDeleteRequest {
                 namespace: self.namespace@,
             },
             owner_ref: self.owner_ref@,
+            predicate: self.predicate@,
         }
     }
 }
 
 // KubeGetThenUpdateRequest has the name as the parameter of Api.get() and the obj as the parameter of Api.replace().
-//
-// TODO: KubeGetThenUpdateRequest should carry a Box<dyn Fn(DynamicObject) -> Option<DynamicObject>> when Verus supports dyn
+// The object is replaced only if it is owned by owner_ref and predicate holds on it.
 
 pub struct KubeGetThenUpdateRequest {
     pub api_resource: ApiResource,
     pub name: String,
     pub namespace: String,
     pub owner_ref: OwnerReference,
+    pub predicate: KubeGetThenPredicate,
     pub obj: DynamicObject,
 }
 
@@ -267,20 +429,21 @@ impl View for KubeGetThenUpdateRequest {
             name: self.name@,
             namespace: self.namespace@,
             owner_ref: self.owner_ref@,
+            predicate: self.predicate@,
             obj: self.obj@,
         }
     }
 }
 
 // KubeGetThenUpdateStatusRequest has the name as the parameter of Api.get() and the obj as the parameter of Api.replace_status().
-//
-// TODO: KubeGetThenUpdateStatusRequest should carry a Box<dyn Fn(DynamicObject) -> Option<DynamicObject>> when Verus supports dyn
+// The status is replaced only if the object is owned by owner_ref and predicate holds on it.
 
 pub struct KubeGetThenUpdateStatusRequest {
     pub api_resource: ApiResource,
     pub name: String,
     pub namespace: String,
     pub owner_ref: OwnerReference,
+    pub predicate: KubeGetThenPredicate,
     pub obj: DynamicObject,
 }
 
@@ -305,7 +468,42 @@ impl View for KubeGetThenUpdateStatusRequest {
             name: self.name@,
             namespace: self.namespace@,
             owner_ref: self.owner_ref@,
+            predicate: self.predicate@,
+            obj: self.obj@,
+        }
+    }
+}
+
+// KubePatchRequest has the obj as the patch sent to Api.patch().
+// If field_manager is None, obj is sent as a JSON merge patch; otherwise obj is
+// server-side applied with field_manager as the field manager.
+
+pub struct KubePatchRequest {
+    pub api_resource: ApiResource,
+    pub name: String,
+    pub namespace: String,
+    pub obj: DynamicObject,
+    pub field_manager: Option<String>,
+}
+
+impl KubePatchRequest {
+    #[verifier(external)]
+    pub fn key(&self) -> std::string::String {
+        format!("{}/{}/{}", self.api_resource.as_kube_ref().kind, self.namespace, self.name)
+    }
+}
+
+impl View for KubePatchRequest {
+    type V = PatchRequest;
+    open spec fn view(&self) -> PatchRequest {
+        PatchRequest {
+            name: self.name@,
+            namespace: self.namespace@,
             obj: self.obj@,
+            field_manager: match self.field_manager {
+                Some(field_manager) => Some(field_manager@),
+                None => None,
+            },
         }
     }
 }
@@ -324,6 +522,7 @@ impl View for KubeAPIRequest {
             KubeAPIRequest::GetThenDeleteRequest(req) => APIRequest::GetThenDeleteRequest(req@),
             KubeAPIRequest::GetThenUpdateRequest(req) => APIRequest::GetThenUpdateRequest(req@),
             KubeAPIRequest::GetThenUpdateStatusRequest(req) => APIRequest::GetThenUpdateStatusRequest(req@),
+            KubeAPIRequest::PatchRequest(req) => APIRequest::PatchRequest(req@),
         }
     }
 }
@@ -350,6 +549,7 @@ pub enum KubeAPIResponse {
     GetThenDeleteResponse(KubeGetThenDeleteResponse),
     GetThenUpdateResponse(KubeGetThenUpdateResponse),
     GetThenUpdateStatusResponse(KubeGetThenUpdateStatusResponse),
+    PatchResponse(KubePatchResponse),
 }
 
 // KubeGetResponse has the object returned by KubeGetRequest.
@@ -368,18 +568,24 @@ impl View for KubeGetResponse {
     }
 }
 
-// KubeListResponse has the sequence of objects returned by KubeListRequest.
+// KubeListResponse has the sequence of objects returned by KubeListRequest,
+// and the continue token of the next page if KubeListRequest asks for one page of the list.
 
 pub struct KubeListResponse {
     pub res: Result<Vec<DynamicObject>, APIError>,
+    pub continue_token: Option<String>,
 }
 
 impl View for KubeListResponse {
     type V = ListResponse;
     open spec fn view(&self) -> ListResponse {
+        let continue_token = match self.continue_token {
+            Some(continue_token) => Some(continue_token@),
+            None => None,
+        };
         match self.res {
-            Ok(l) => ListResponse { res: Ok(l.deep_view()) },
-            Err(e) => ListResponse { res: Err(e) },
+            Ok(l) => ListResponse { res: Ok(l.deep_view()), continue_token: continue_token },
+            Err(e) => ListResponse { res: Err(e), continue_token: continue_token },
         }
     }
 }
@@ -496,6 +702,22 @@ impl View for KubeGetThenUpdateStatusResponse {
     }
 }
 
+// KubePatchResponse has the object patched by KubePatchRequest.
+
+pub struct KubePatchResponse {
+    pub res: Result<DynamicObject, APIError>,
+}
+
+impl View for KubePatchResponse {
+    type V = PatchResponse;
+    open spec fn view(&self) -> PatchResponse {
+        match self.res {
+            Ok(o) => PatchResponse { res: Ok(o@) },
+            Err(e) => PatchResponse { res: Err(e) },
+        }
+    }
+}
+
 impl View for KubeAPIResponse {
     type V = APIResponse;
     open spec fn view(&self) -> APIResponse {
@@ -509,6 +731,7 @@ impl View for KubeAPIResponse {
             KubeAPIResponse::GetThenDeleteResponse(resp) => APIResponse::GetThenDeleteResponse(resp@),
             KubeAPIResponse::GetThenUpdateResponse(resp) => APIResponse::GetThenUpdateResponse(resp@),
             KubeAPIResponse::GetThenUpdateStatusResponse(resp) => APIResponse::GetThenUpdateStatusResponse(resp@),
+            KubeAPIResponse::PatchResponse(resp) => APIResponse::PatchResponse(resp@),
         }
     }
 }
@@ -634,3 +857,12 @@ declare_kube_api_response_helper_methods!(
     KubeGetThenUpdateStatusResponse,
     arrow_GetThenUpdateStatusResponse_0
 );
+
+declare_kube_api_response_helper_methods!(
+    is_patch_response,
+    as_patch_response_ref,
+    into_patch_response,
+    PatchResponse,
+    KubePatchResponse,
+    arrow_PatchResponse_0
+);
@@ -19,6 +19,7 @@ pub struct ApiResource {
 
 implement_view_trait!(ApiResource, ApiResourceView);
 implement_deep_view_trait!(ApiResource, ApiResourceView);
+implement_clone_trait!(ApiResource);
 implement_resource_wrapper_trait!(ApiResource, kube::api::ApiResource);
 
 }
@@ -10,6 +10,8 @@ pub enum KindExec {
     ConfigMapKind,
     CustomResourceKind(String),
     DaemonSetKind,
+    EventKind,
+    NodeKind,
     PersistentVolumeClaimKind,
     PodKind,
     RoleKind,
@@ -27,6 +29,8 @@ impl View for KindExec {
         match self {
             KindExec::ConfigMapKind => Kind::ConfigMapKind,
             KindExec::DaemonSetKind => Kind::DaemonSetKind,
+            KindExec::EventKind => Kind::EventKind,
+            KindExec::NodeKind => Kind::NodeKind,
             KindExec::PersistentVolumeClaimKind => Kind::PersistentVolumeClaimKind,
             KindExec::PodKind => Kind::PodKind,
             KindExec::RoleBindingKind => Kind::RoleBindingKind,
@@ -48,6 +52,8 @@ impl std::clone::Clone for KindExec {
         match self {
             KindExec::ConfigMapKind => KindExec::ConfigMapKind,
             KindExec::DaemonSetKind => KindExec::DaemonSetKind,
+            KindExec::EventKind => KindExec::EventKind,
+            KindExec::NodeKind => KindExec::NodeKind,
             KindExec::PersistentVolumeClaimKind => KindExec::PersistentVolumeClaimKind,
             KindExec::PodKind => KindExec::PodKind,
             KindExec::RoleBindingKind => KindExec::RoleBindingKind,
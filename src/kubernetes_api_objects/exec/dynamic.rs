@@ -1,7 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 use crate::kubernetes_api_objects::exec::{object_meta::*, resource::*};
-use crate::kubernetes_api_objects::spec::dynamic::*;
+use crate::kubernetes_api_objects::spec::{api_method::spec_hash, dynamic::*};
 use vstd::prelude::*;
 
 verus! {
@@ -27,6 +27,87 @@ impl DynamicObject {
     {
         ObjectMeta::from_kube(self.inner.metadata.clone())
     }
+
+    // spec_hash is used by the controllers to build a GetThenPredicate::SpecHashMatch.
+    // NOTE: we trust that spec_hash_of is a function of the spec only, which is what the uninterpreted spec_hash
+    // stands for. The hash is SHA-256 over the canonical JSON of the spec, so it is the same across builds and
+    // Rust releases, and a predicate built by one version of the controller matches under another.
+    #[verifier(external_body)] // TRUSTED
+    pub fn spec_hash(&self) -> (hash: String)
+        ensures hash@ == spec_hash(self@.spec),
+    {
+        spec_hash_of(&self.inner)
+    }
+
+    // merge_patch_from merges the patch into self following patched_object in
+    // crate::kubernetes_cluster::spec::api_server::state_machine:
+    // labels and annotations are merged, finalizers and owner_references are replaced,
+    // the spec is merged by JSON merge patch (RFC 7386), and status and the other metadata are untouched.
+    // Note that merge_patch in the model is uninterpreted, and merge_patch_spec accounts for the deletion of
+    // fields by null (implemented here) by only assuming merge_patch(value, value) == value for values without null.
+    #[verifier(external)]
+    pub fn merge_patch_from(&mut self, patch: &DynamicObject) {
+        let patch = &patch.inner;
+        if let Some(labels) = &patch.metadata.labels {
+            self.inner.metadata.labels.get_or_insert_with(Default::default).extend(labels.clone());
+        }
+        if let Some(annotations) = &patch.metadata.annotations {
+            self.inner.metadata.annotations.get_or_insert_with(Default::default).extend(annotations.clone());
+        }
+        if patch.metadata.finalizers.is_some() {
+            self.inner.metadata.finalizers = patch.metadata.finalizers.clone();
+        }
+        if patch.metadata.owner_references.is_some() {
+            self.inner.metadata.owner_references = patch.metadata.owner_references.clone();
+        }
+        // All the fields other than metadata and status (e.g., spec, or data of a ConfigMap) belong to the spec
+        if let (Some(data), Some(patch_data)) = (self.inner.data.as_object_mut(), patch.data.as_object()) {
+            for (k, v) in patch_data.iter().filter(|(k, _)| k.as_str() != "status") {
+                if v.is_null() {
+                    data.remove(k);
+                } else {
+                    merge_patch_value(data.entry(k.as_str()).or_insert(serde_json::Value::Null), v);
+                }
+            }
+        }
+    }
+}
+
+// merge_patch_value merges patch into value following JSON merge patch (RFC 7386):
+// objects are merged field by field, a null field in the patch removes the field,
+// and any other value (including arrays) replaces the old one.
+#[verifier(external)]
+pub fn merge_patch_value(value: &mut serde_json::Value, patch: &serde_json::Value) {
+    match patch {
+        serde_json::Value::Object(patch) => {
+            if !value.is_object() {
+                *value = serde_json::Value::Object(serde_json::Map::new());
+            }
+            let value = value.as_object_mut().unwrap();
+            for (k, v) in patch {
+                if v.is_null() {
+                    value.remove(k);
+                } else {
+                    merge_patch_value(value.entry(k.as_str()).or_insert(serde_json::Value::Null), v);
+                }
+            }
+        }
+        _ => *value = patch.clone(),
+    }
+}
+
+// spec_hash_of hashes all the fields other than metadata and status (e.g., spec, or data of a ConfigMap)
+// with SHA-256 and returns the hex digest.
+// The input is the canonical JSON of the fields: serde_json::Map is ordered by key and to_string adds no whitespace,
+// so the same spec always has the same hash. Unlike DefaultHasher, SHA-256 is specified and stable across releases.
+#[verifier(external)]
+pub fn spec_hash_of(obj: &kube::api::DynamicObject) -> std::string::String {
+    use sha2::{Digest, Sha256};
+    let mut data = obj.data.clone();
+    if let Some(data) = data.as_object_mut() {
+        data.remove("status");
+    }
+    Sha256::digest(data.to_string().as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
 }
 
 #[verifier(external)]
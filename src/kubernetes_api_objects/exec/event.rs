@@ -0,0 +1,122 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::UnmarshalError;
+use crate::kubernetes_api_objects::exec::{
+    api_resource::*, dynamic::*, object_meta::*, resource::*,
+};
+use crate::kubernetes_api_objects::spec::{event::*, resource::*};
+use vstd::prelude::*;
+
+verus! {
+
+// An Event is a report of something that happened to an object (e.g., a custom resource) in the cluster,
+// such as a Pod being created by the controller or a reconcile failing.
+// Events are informational: the controllers never read them back.
+//
+// This definition is a wrapper of Event defined at
+// https://github.com/Arnavion/k8s-openapi/blob/v0.17.0/src/v1_26/api/core/v1/event.rs.
+// It is supposed to be used in exec controller code.
+//
+// More detailed information: https://kubernetes.io/docs/reference/kubernetes-api/cluster-resources/event-v1/.
+
+implement_object_wrapper_type!(
+    Event,
+    k8s_openapi::api::core::v1::Event,
+    EventView
+);
+
+implement_field_wrapper_type!(
+    ObjectReference,
+    k8s_openapi::api::core::v1::ObjectReference,
+    ObjectReferenceView
+);
+
+impl Event {
+    #[verifier(external_body)]
+    pub fn involved_object(&self) -> (involved_object: ObjectReference)
+        ensures involved_object@ == self@.involved_object
+    {
+        ObjectReference::from_kube(self.inner.involved_object.clone())
+    }
+
+    #[verifier(external_body)]
+    pub fn reason(&self) -> (reason: Option<String>)
+        ensures self@.reason == reason.deep_view()
+    {
+        self.inner.reason.clone()
+    }
+
+    #[verifier(external_body)]
+    pub fn message(&self) -> (message: Option<String>)
+        ensures self@.message == message.deep_view()
+    {
+        self.inner.message.clone()
+    }
+
+    #[verifier(external_body)]
+    pub fn type_(&self) -> (type_: Option<String>)
+        ensures self@.type_ == type_.deep_view()
+    {
+        self.inner.type_.clone()
+    }
+
+    #[verifier(external_body)]
+    pub fn set_involved_object(&mut self, involved_object: ObjectReference)
+        ensures final(self)@ == old(self)@.with_involved_object(involved_object@),
+    {
+        self.inner.involved_object = involved_object.into_kube();
+    }
+
+    #[verifier(external_body)]
+    pub fn set_reason(&mut self, reason: String)
+        ensures final(self)@ == old(self)@.with_reason(reason@),
+    {
+        self.inner.reason = Some(reason);
+    }
+
+    #[verifier(external_body)]
+    pub fn set_message(&mut self, message: String)
+        ensures final(self)@ == old(self)@.with_message(message@),
+    {
+        self.inner.message = Some(message);
+    }
+
+    #[verifier(external_body)]
+    pub fn set_type(&mut self, type_: String)
+        ensures final(self)@ == old(self)@.with_type(type_@),
+    {
+        self.inner.type_ = Some(type_);
+    }
+}
+
+impl ObjectReference {
+    #[verifier(external_body)]
+    pub fn set_api_version(&mut self, api_version: String)
+        ensures final(self)@ == old(self)@.with_api_version(api_version@),
+    {
+        self.inner.api_version = Some(api_version);
+    }
+
+    #[verifier(external_body)]
+    pub fn set_kind(&mut self, kind: String)
+        ensures final(self)@ == old(self)@.with_kind(kind@),
+    {
+        self.inner.kind = Some(kind);
+    }
+
+    #[verifier(external_body)]
+    pub fn set_name(&mut self, name: String)
+        ensures final(self)@ == old(self)@.with_name(name@),
+    {
+        self.inner.name = Some(name);
+    }
+
+    #[verifier(external_body)]
+    pub fn set_namespace(&mut self, namespace: String)
+        ensures final(self)@ == old(self)@.with_namespace(namespace@),
+    {
+        self.inner.namespace = Some(namespace);
+    }
+}
+
+}
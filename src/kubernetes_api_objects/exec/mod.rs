@@ -8,7 +8,9 @@ pub mod config_map;
 pub mod container;
 pub mod daemon_set;
 pub mod dynamic;
+pub mod event;
 pub mod label_selector;
+pub mod node;
 pub mod object_meta;
 pub mod owner_reference;
 pub mod persistent_volume_claim;
@@ -0,0 +1,61 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::UnmarshalError;
+use crate::kubernetes_api_objects::exec::{
+    api_resource::*, dynamic::*, object_meta::*, resource::*,
+};
+use crate::kubernetes_api_objects::spec::{node::*, resource::*};
+use vstd::prelude::*;
+
+verus! {
+
+// Node is a type of API object representing a worker machine that runs pods.
+// Unlike most of the other objects, a Node is cluster-scoped and does not belong to any namespace.
+//
+// This definition is a wrapper of Node defined at
+// https://github.com/Arnavion/k8s-openapi/blob/v0.17.0/src/v1_26/api/core/v1/node.rs.
+// It is supposed to be used in exec controller code.
+//
+// More detailed information: https://kubernetes.io/docs/concepts/architecture/nodes/.
+
+implement_object_wrapper_type!(
+    Node,
+    k8s_openapi::api::core::v1::Node,
+    NodeView
+);
+
+implement_field_wrapper_type!(
+    NodeSpec,
+    k8s_openapi::api::core::v1::NodeSpec,
+    NodeSpecView
+);
+
+impl Node {
+    #[verifier(external_body)]
+    pub fn spec(&self) -> (spec: Option<NodeSpec>)
+        ensures self@.spec == spec.deep_view()
+    {
+        match &self.inner.spec {
+            Some(s) => Some(NodeSpec::from_kube(s.clone())),
+            None => None,
+        }
+    }
+
+    #[verifier(external_body)]
+    pub fn set_spec(&mut self, spec: NodeSpec)
+        ensures final(self)@ == old(self)@.with_spec(spec@),
+    {
+        self.inner.spec = Some(spec.into_kube());
+    }
+}
+
+impl NodeSpec {
+    #[verifier(external_body)]
+    pub fn set_unschedulable(&mut self, unschedulable: bool)
+        ensures final(self)@ == old(self)@.with_unschedulable(unschedulable),
+    {
+        self.inner.unschedulable = Some(unschedulable);
+    }
+}
+
+}
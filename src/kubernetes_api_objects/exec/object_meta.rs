@@ -139,6 +139,15 @@ impl ObjectMeta {
         self.inner.resource_version == other.inner.resource_version
     }
 
+    #[verifier(external_body)]
+    pub fn generation(&self) -> (generation: Option<i64>)
+        ensures
+            self@.generation is Some == generation is Some,
+            generation is Some ==> generation->0 as int == self@.generation->0,
+    {
+        self.inner.generation
+    }
+
     #[verifier(external_body)]
     pub fn has_some_uid(&self) -> (b: bool)
         ensures self@.uid is Some == b,
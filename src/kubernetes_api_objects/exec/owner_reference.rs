@@ -30,6 +30,13 @@ impl OwnerReference {
             None => None,
         }
     }
+
+    #[verifier(external_body)]
+    pub fn block_owner_deletion(&self) -> (block_owner_deletion: Option<bool>)
+        ensures self@.block_owner_deletion == block_owner_deletion.deep_view()
+    {
+        self.inner.block_owner_deletion
+    }
 }
 
 }
@@ -36,6 +36,12 @@ implement_field_wrapper_type!(
     PodSpecView
 );
 
+implement_field_wrapper_type!(
+    PodStatus,
+    k8s_openapi::api::core::v1::PodStatus,
+    PodStatusView
+);
+
 implement_field_wrapper_type!(
     PodSecurityContext,
     k8s_openapi::api::core::v1::PodSecurityContext,
@@ -59,6 +65,16 @@ impl Pod {
         }
     }
 
+    #[verifier(external_body)]
+    pub fn status(&self) -> (status: Option<PodStatus>)
+        ensures self@.status == status.deep_view(),
+    {
+        match &self.inner.status {
+            Some(s) => Some(PodStatus::from_kube(s.clone())),
+            None => None,
+        }
+    }
+
     #[verifier(external_body)]
     pub fn set_spec(&mut self, spec: PodSpec)
         ensures final(self)@ == old(self)@.with_spec(spec@),
@@ -67,6 +83,15 @@ impl Pod {
     }
 }
 
+impl PodStatus {
+    #[verifier(external_body)]
+    pub fn phase(&self) -> (phase: Option<String>)
+        ensures phase.deep_view() == self@.phase,
+    {
+        self.inner.phase.clone()
+    }
+}
+
 #[verifier::external]
 pub fn normalize_resources(r: &k8s_types::ResourceRequirements) -> k8s_types::ResourceRequirements {
     let limits = r.limits.clone().unwrap_or_default();
@@ -329,6 +354,20 @@ impl PodSpec {
         self.inner.subdomain = None;
     }
 
+    #[verifier(external_body)]
+    pub fn node_name(&self) -> (node_name: Option<String>)
+        ensures node_name.deep_view() == self@.node_name
+    {
+        self.inner.node_name.clone()
+    }
+
+    #[verifier(external_body)]
+    pub fn unset_node_name(&mut self)
+        ensures final(self)@ == old(self)@.without_node_name()
+    {
+        self.inner.node_name = None;
+    }
+
     #[verifier(external_body)]
     pub fn eq_spec(&self, other: &Self) -> (res: bool)
         ensures res == (self@ == other@)
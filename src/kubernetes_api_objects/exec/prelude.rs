@@ -5,6 +5,8 @@ pub use crate::kubernetes_api_objects::exec::common::*;
 pub use crate::kubernetes_api_objects::exec::config_map::*;
 pub use crate::kubernetes_api_objects::exec::daemon_set::*;
 pub use crate::kubernetes_api_objects::exec::dynamic::*;
+pub use crate::kubernetes_api_objects::exec::event::*;
+pub use crate::kubernetes_api_objects::exec::node::*;
 pub use crate::kubernetes_api_objects::exec::object_meta::*;
 pub use crate::kubernetes_api_objects::exec::owner_reference::*;
 pub use crate::kubernetes_api_objects::exec::persistent_volume_claim::*;
@@ -206,6 +206,8 @@ pub trait ResourceWrapper<T>: Sized {
     fn into_kube(self) -> T;
 
     fn as_kube_ref(&self) -> &T;
+
+    fn as_kube_mut_ref(&mut self) -> &mut T;
 }
 
 #[macro_export]
@@ -226,6 +228,10 @@ macro_rules! implement_resource_wrapper_trait {
             fn as_kube_ref(&self) -> &$it {
                 &self.inner
             }
+
+            fn as_kube_mut_ref(&mut self) -> &mut $it {
+                &mut self.inner
+            }
         }
 
         }
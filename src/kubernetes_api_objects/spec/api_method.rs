@@ -4,8 +4,10 @@ use crate::kubernetes_api_objects::error::APIError;
 use crate::kubernetes_api_objects::spec::{
     common::{Kind, ObjectRef},
     dynamic::*,
+    label_selector::*,
     owner_reference::*,
     preconditions::*,
+    resource::*,
 };
 use crate::vstd_ext::string_view::*;
 use vstd::prelude::*;
@@ -18,7 +20,6 @@ verus! {
 // Each variant in APIRequest represents on type of request.
 // For now we do not consider Watch.
 
-// TODO: implement Update and Patch request.
 pub enum APIRequest {
     GetRequest(GetRequest),
     ListRequest(ListRequest),
@@ -29,12 +30,15 @@ pub enum APIRequest {
     GetThenDeleteRequest(GetThenDeleteRequest),
     GetThenUpdateRequest(GetThenUpdateRequest),
     GetThenUpdateStatusRequest(GetThenUpdateStatusRequest),
+    PatchRequest(PatchRequest),
 }
 
 // GetRequest gets an object with the key (kind, name and namespace).
+// read_mode decides whether the object is read from the API server or from the controller's cache (see ReadMode).
 
 pub struct GetRequest {
     pub key: ObjectRef,
+    pub read_mode: ReadMode,
 }
 
 impl GetRequest {
@@ -44,10 +48,65 @@ impl GetRequest {
 }
 
 // ListRequest lists all the objects of kind in namespace.
+// If label_selector is provided, only the objects whose labels match the selector are listed.
+// If field_selector is provided, only the objects whose fields match all the (field path, value)
+// pairs in the selector are listed.
+// read_mode decides whether the objects are read from the API server or from the controller's cache (see ReadMode).
+// If limit is provided (and is not zero), at most limit objects are listed in one page, and the response carries
+// a continue_token if there are more objects; the next page is listed by sending the same request with the
+// continue_token (see handle_list_request in api_server::state_machine).
 
 pub struct ListRequest {
     pub kind: Kind,
     pub namespace: StringView,
+    pub label_selector: Option<LabelSelectorView>,
+    pub field_selector: Option<Map<StringView, StringView>>,
+    pub read_mode: ReadMode,
+    pub limit: Option<nat>,
+    pub continue_token: Option<StringView>,
+}
+
+impl ListRequest {
+    // The API server only supports metadata.name and metadata.namespace
+    // as field paths for all kinds of objects.
+    pub open spec fn field_selector_is_supported(self) -> bool {
+        self.field_selector is Some ==> forall |path: StringView| #[trigger] self.field_selector->0.contains_key(path)
+            ==> path == "metadata.name"@ || path == "metadata.namespace"@
+    }
+
+    pub open spec fn is_paginated(self) -> bool {
+        ||| self.limit is Some && self.limit->0 > 0
+        ||| self.continue_token is Some
+    }
+
+    // is_full_quorum_list says that the request lists all the objects that match the label selector
+    // from the latest state in etcd, in one response.
+    pub open spec fn is_full_quorum_list(self) -> bool {
+        &&& self.field_selector is None
+        &&& self.read_mode is Quorum
+        &&& self.limit is None
+        &&& self.continue_token is None
+    }
+
+    pub open spec fn selects(self, obj: DynamicObjectView) -> bool {
+        &&& self.label_selector is Some ==> self.label_selector->0.matches(obj.metadata.labels.unwrap_or(Map::empty()))
+        &&& self.field_selector is Some ==> {
+            let field_selector = self.field_selector->0;
+            &&& field_selector.contains_key("metadata.name"@) ==> obj.metadata.name == Some(field_selector["metadata.name"@])
+            &&& field_selector.contains_key("metadata.namespace"@) ==> obj.metadata.namespace == Some(field_selector["metadata.namespace"@])
+        }
+    }
+}
+
+// ReadMode is how a Get or List request is served:
+// * Quorum: the request is served by the API server with the latest state in etcd.
+// * Cached: the request may be served from the cache kept by the watches of the controller that sends it,
+//   which lags behind etcd (see Cluster::cached_read). If the controller has no cache, the request is
+//   served by the API server as a Quorum read.
+
+pub enum ReadMode {
+    Quorum,
+    Cached,
 }
 
 // CreateRequest creates the obj.
@@ -69,10 +128,14 @@ impl CreateRequest {
 }
 
 // DeleteRequest deletes the object with the key.
+// propagation_policy decides how the garbage collector deletes the dependents of the object
+// (see PropagationPolicy). None keeps the policy already recorded in the object's finalizers,
+// which is background deletion unless the object has been deleted with another policy before.
 
 pub struct DeleteRequest {
     pub key: ObjectRef,
     pub preconditions: Option<PreconditionsView>,
+    pub propagation_policy: Option<PropagationPolicy>,
 }
 
 impl DeleteRequest {
@@ -81,6 +144,43 @@ impl DeleteRequest {
     }
 }
 
+// PropagationPolicy is the propagationPolicy of a delete request.
+// The API server records the policy as a finalizer of the object (see finalizers_for_propagation_policy),
+// and the garbage collector deletes the dependents accordingly:
+// * Background: the object is deleted right away, and the dependents are deleted afterwards.
+// * Foreground: the object is kept (with the foregroundDeletion finalizer) until its dependents are deleted.
+// * Orphan: the object is kept (with the orphan finalizer) until its dependents no longer refer to it,
+//   and the dependents are not deleted.
+// See https://kubernetes.io/docs/concepts/architecture/garbage-collection/#cascading-deletion.
+
+pub enum PropagationPolicy {
+    Background,
+    Foreground,
+    Orphan,
+}
+
+pub open spec fn foreground_deletion_finalizer() -> StringView { "foregroundDeletion"@ }
+
+pub open spec fn orphan_finalizer() -> StringView { "orphan"@ }
+
+// finalizers_for_propagation_policy returns the finalizers of obj after the API server handles a delete request
+// with propagation_policy: Foreground adds foregroundDeletion and removes orphan, Orphan adds orphan and removes
+// foregroundDeletion, Background removes both, and None leaves the finalizers unchanged.
+// See https://github.com/kubernetes/kubernetes/blob/v1.30.0/staging/src/k8s.io/apiserver/pkg/registry/generic/registry/store.go#L875.
+pub open spec fn finalizers_for_propagation_policy(obj: DynamicObjectView, propagation_policy: Option<PropagationPolicy>) -> Option<Seq<StringView>> {
+    if propagation_policy is None {
+        obj.metadata.finalizers
+    } else {
+        let finalizers = if obj.metadata.finalizers is Some { obj.metadata.finalizers->0 } else { Seq::empty() };
+        let other_finalizers = finalizers.filter(|f: StringView| f != foreground_deletion_finalizer() && f != orphan_finalizer());
+        match propagation_policy->0 {
+            PropagationPolicy::Background => if obj.metadata.finalizers is None { None } else { Some(other_finalizers) },
+            PropagationPolicy::Foreground => Some(other_finalizers.push(foreground_deletion_finalizer())),
+            PropagationPolicy::Orphan => Some(other_finalizers.push(orphan_finalizer())),
+        }
+    }
+}
+
 // UpdateRequest replaces the existing obj with a new one.
 // TODO: remove name/namespace as they should always be consistent with the object in payload
 
@@ -118,16 +218,54 @@ impl UpdateStatusRequest {
     }
 }
 
-// GetThenDeleteRequest deletes the object with the key only when it's owned by owner_ref and avoids conflicts caused by
-// version race.
+// GetThenPredicate is the check that a get-then request performs on the current object before deleting or updating it,
+// in addition to the ownership check on owner_ref which is always performed.
+// The set of predicates is closed so that the API server model (and the rely conditions) can reason about each of them.
+
+pub enum GetThenPredicate {
+    // No check other than the current object being owned by owner_ref.
+    // The ownership check is performed for every predicate, so OwnerRef is not a check of its own:
+    // it is the trivial predicate (holds_on is always true) for the requests that need nothing more.
+    OwnerRef,
+    // The labels of the current object include all the given labels
+    LabelsMatch(Map<StringView, StringView>),
+    // The current object has the given resource version (as a string, like what the client sees)
+    ResourceVersionMatch(StringView),
+    // The spec of the current object hashes to the given value
+    SpecHashMatch(StringView),
+}
+
+impl GetThenPredicate {
+    pub open spec fn holds_on(self, obj: DynamicObjectView) -> bool {
+        match self {
+            GetThenPredicate::OwnerRef => true,
+            GetThenPredicate::LabelsMatch(labels) => {
+                let obj_labels = if obj.metadata.labels is Some { obj.metadata.labels->0 } else { Map::empty() };
+                labels.submap_of(obj_labels)
+            },
+            GetThenPredicate::ResourceVersionMatch(rv) => {
+                &&& obj.metadata.resource_version is Some
+                &&& int_to_string_view(obj.metadata.resource_version->0) == rv
+            },
+            GetThenPredicate::SpecHashMatch(hash) => spec_hash(obj.spec) == hash,
+        }
+    }
+}
+
+// spec_hash is the hash of the spec used by GetThenPredicate::SpecHashMatch.
+// The exec counterpart is DynamicObject::spec_hash.
+pub uninterp spec fn spec_hash(spec: Value) -> StringView;
+
+// GetThenDeleteRequest deletes the object with the key only when it's owned by owner_ref, predicate holds on it,
+// and avoids conflicts caused by version race.
 //
-// TODO: GetThenUpdateRequest should carry a spec_fn(DynamicObjectView) -> bool
 // TODO: Add a type invariant by #[verifier::type_invariant].
 //       Skipped because it requires fields to be private, and Deref is not supported so we have to add getter/setter
 
 pub struct GetThenDeleteRequest {
     pub key: ObjectRef,
     pub owner_ref: OwnerReferenceView,
+    pub predicate: GetThenPredicate,
 }
 
 impl GetThenDeleteRequest {
@@ -141,16 +279,16 @@ impl GetThenDeleteRequest {
     }
 }
 
-// GetThenUpdateRequest replaces the existing obj with a new one only when it's owned by owner_ref and avoids
-// conflicts caused by version race.
+// GetThenUpdateRequest replaces the existing obj with a new one only when it's owned by owner_ref, predicate holds
+// on it, and avoids conflicts caused by version race.
 //
-// TODO: GetThenUpdateRequest should carry a spec_fn(DynamicObjectView) -> Option<DynamicObjectView>
 // TODO: remove name/namespace as they should always be consistent with the object in payload
 
 pub struct GetThenUpdateRequest {
     pub namespace: StringView,
     pub name: StringView,
     pub owner_ref: OwnerReferenceView,
+    pub predicate: GetThenPredicate,
     pub obj: DynamicObjectView,
 }
 
@@ -173,6 +311,7 @@ pub struct GetThenUpdateStatusRequest {
     pub namespace: StringView,
     pub name: StringView,
     pub owner_ref: OwnerReferenceView,
+    pub predicate: GetThenPredicate,
     pub obj: DynamicObjectView,
 }
 
@@ -191,6 +330,33 @@ impl GetThenUpdateStatusRequest {
     }
 }
 
+// PatchRequest patches the existing obj with the (partial) object in obj.
+// If field_manager is None, the patch is a JSON merge patch (RFC 7386);
+// otherwise it is a server-side apply patch sent by field_manager.
+// See patched_object in the API server model for the fields that a patch can change.
+// TODO: remove name/namespace as they should always be consistent with the object in payload
+
+pub struct PatchRequest {
+    pub namespace: StringView,
+    pub name: StringView,
+    pub obj: DynamicObjectView,
+    pub field_manager: Option<StringView>,
+}
+
+impl PatchRequest {
+    pub open spec fn key(self) -> ObjectRef {
+        ObjectRef {
+            kind: self.obj.kind,
+            namespace: self.namespace,
+            name: self.name,
+        }
+    }
+
+    pub open spec fn is_apply(self) -> bool {
+        self.field_manager is Some
+    }
+}
+
 // APIResponse represents API responses sent from the Kubernetes API for specifications.
 
 pub enum APIResponse {
@@ -203,6 +369,7 @@ pub enum APIResponse {
     GetThenDeleteResponse(GetThenDeleteResponse),
     GetThenUpdateResponse(GetThenUpdateResponse),
     GetThenUpdateStatusResponse(GetThenUpdateStatusResponse),
+    PatchResponse(PatchResponse),
 }
 
 // GetResponse has the object returned by GetRequest.
@@ -212,9 +379,11 @@ pub struct GetResponse {
 }
 
 // ListResponse has the sequence of objects returned by ListRequest.
+// continue_token is Some if the objects are one page of a paginated list and there are more pages.
 
 pub struct ListResponse {
     pub res: Result<Seq<DynamicObjectView>, APIError>,
+    pub continue_token: Option<StringView>,
 }
 
 // CreateResponse has the object created by CreateRequest.
@@ -257,4 +426,10 @@ pub struct GetThenUpdateStatusResponse {
     pub res: Result<DynamicObjectView, APIError>,
 }
 
+// PatchResponse has the object patched by PatchRequest.
+
+pub struct PatchResponse {
+    pub res: Result<DynamicObjectView, APIError>,
+}
+
 }
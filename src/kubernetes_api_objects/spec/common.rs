@@ -20,6 +20,8 @@ pub enum Kind {
     ConfigMapKind,
     CustomResourceKind(StringView),
     DaemonSetKind,
+    EventKind,
+    NodeKind,
     PersistentVolumeClaimKind,
     PodKind,
     RoleKind,
@@ -30,10 +32,24 @@ pub enum Kind {
     SecretKind,
 }
 
+// ResourceScope tells whether the objects of a kind live in a namespace or in the cluster.
+pub enum ResourceScope {
+    Namespaced,
+    Cluster,
+}
+
+// ObjectRef is the key of an object in the cluster state.
+// A cluster-scoped object has no namespace, and its key has the empty namespace (see cluster_scope_namespace).
 pub struct ObjectRef {
     pub kind: Kind,
     pub name: StringView,
     pub namespace: StringView,
 }
 
+// cluster_scope_namespace is the namespace in the keys of cluster-scoped objects,
+// the same as how Kubernetes keys the cluster-scoped objects in its storage and caches.
+pub open spec fn cluster_scope_namespace() -> StringView {
+    ""@
+}
+
 }
@@ -16,15 +16,15 @@ pub struct DynamicObjectView {
 }
 
 impl DynamicObjectView {
+    // A cluster-scoped object has no namespace, so its object_ref has cluster_scope_namespace().
     pub open spec fn object_ref(self) -> ObjectRef
         recommends
             self.metadata.name is Some,
-            self.metadata.namespace is Some,
     {
         ObjectRef {
             kind: self.kind,
             name: self.metadata.name->0,
-            namespace: self.metadata.namespace->0,
+            namespace: self.metadata.namespace.unwrap_or(cluster_scope_namespace()),
         }
     }
 
@@ -0,0 +1,152 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::spec::{common::*, dynamic::*, object_meta::*, resource::*};
+use crate::vstd_ext::string_view::*;
+use vstd::prelude::*;
+
+verus! {
+
+// EventView is the ghost type of Event.
+//
+// Like ConfigMap, an Event does not have a spec field, so we use a tuple to wrap around
+// the involved_object, reason, message and type_ fields as its spec.
+
+pub struct EventView {
+    pub metadata: ObjectMetaView,
+    pub involved_object: ObjectReferenceView,
+    pub reason: Option<StringView>,
+    pub message: Option<StringView>,
+    pub type_: Option<StringView>,
+}
+
+type EventSpecView = (ObjectReferenceView, Option<StringView>, Option<StringView>, Option<StringView>);
+
+impl EventView {
+    pub open spec fn with_metadata(self, metadata: ObjectMetaView) -> EventView {
+        EventView {
+            metadata: metadata,
+            ..self
+        }
+    }
+
+    pub open spec fn with_involved_object(self, involved_object: ObjectReferenceView) -> EventView {
+        EventView {
+            involved_object: involved_object,
+            ..self
+        }
+    }
+
+    pub open spec fn with_reason(self, reason: StringView) -> EventView {
+        EventView {
+            reason: Some(reason),
+            ..self
+        }
+    }
+
+    pub open spec fn with_message(self, message: StringView) -> EventView {
+        EventView {
+            message: Some(message),
+            ..self
+        }
+    }
+
+    pub open spec fn with_type(self, type_: StringView) -> EventView {
+        EventView {
+            type_: Some(type_),
+            ..self
+        }
+    }
+
+    #[verifier(inline)]
+    pub open spec fn _default() -> EventView {
+        EventView {
+            metadata: ObjectMetaView::default(),
+            involved_object: ObjectReferenceView::default(),
+            reason: None,
+            message: None,
+            type_: None,
+        }
+    }
+
+    #[verifier(inline)]
+    pub open spec fn _spec(self) -> EventSpecView {
+        (self.involved_object, self.reason, self.message, self.type_)
+    }
+
+    #[verifier(inline)]
+    pub open spec fn _status(self) -> EmptyStatusView {
+        empty_status()
+    }
+
+    #[verifier(inline)]
+    pub open spec fn _unmarshal_helper(obj: DynamicObjectView) -> EventView {
+        EventView {
+            metadata: obj.metadata,
+            involved_object: EventView::unmarshal_spec(obj.spec)->Ok_0.0,
+            reason: EventView::unmarshal_spec(obj.spec)->Ok_0.1,
+            message: EventView::unmarshal_spec(obj.spec)->Ok_0.2,
+            type_: EventView::unmarshal_spec(obj.spec)->Ok_0.3,
+        }
+    }
+
+    #[verifier(inline)]
+    pub open spec fn _state_validation(self) -> bool { true }
+
+    #[verifier(inline)]
+    pub open spec fn _transition_validation(self, old_obj: EventView) -> bool { true }
+}
+
+implement_resource_view_trait!(EventView, EventSpecView, EmptyStatusView, _default, Kind::EventKind, _spec,
+    _status, _unmarshal_helper, _state_validation, _transition_validation);
+
+// ObjectReferenceView is the ghost type of ObjectReference,
+// which an Event uses to refer to the object that the Event is about.
+
+pub struct ObjectReferenceView {
+    pub api_version: Option<StringView>,
+    pub kind: Option<StringView>,
+    pub name: Option<StringView>,
+    pub namespace: Option<StringView>,
+}
+
+impl ObjectReferenceView {
+    pub open spec fn default() -> ObjectReferenceView {
+        ObjectReferenceView {
+            api_version: None,
+            kind: None,
+            name: None,
+            namespace: None,
+        }
+    }
+
+    pub open spec fn with_api_version(self, api_version: StringView) -> ObjectReferenceView {
+        ObjectReferenceView {
+            api_version: Some(api_version),
+            ..self
+        }
+    }
+
+    pub open spec fn with_kind(self, kind: StringView) -> ObjectReferenceView {
+        ObjectReferenceView {
+            kind: Some(kind),
+            ..self
+        }
+    }
+
+    pub open spec fn with_name(self, name: StringView) -> ObjectReferenceView {
+        ObjectReferenceView {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    pub open spec fn with_namespace(self, namespace: StringView) -> ObjectReferenceView {
+        ObjectReferenceView {
+            namespace: Some(namespace),
+            ..self
+        }
+    }
+}
+
+}
@@ -0,0 +1,79 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::spec::{common::*, dynamic::*, object_meta::*, resource::*};
+use crate::vstd_ext::string_view::*;
+use vstd::prelude::*;
+
+verus! {
+
+// NodeView is the ghost type of Node.
+// Node is cluster-scoped, so a node has no namespace (see kind_is_namespaced).
+
+pub struct NodeView {
+    pub metadata: ObjectMetaView,
+    pub spec: Option<NodeSpecView>,
+    pub status: Option<NodeStatusView>,
+}
+
+pub type NodeStatusView = EmptyStatusView;
+
+impl NodeView {
+    pub open spec fn with_metadata(self, metadata: ObjectMetaView) -> NodeView {
+        NodeView {
+            metadata: metadata,
+            ..self
+        }
+    }
+
+    pub open spec fn with_spec(self, spec: NodeSpecView) -> NodeView {
+        NodeView {
+            spec: Some(spec),
+            ..self
+        }
+    }
+
+    // is_schedulable says that the scheduler can bind new pods to the node.
+    pub open spec fn is_schedulable(self) -> bool {
+        self.spec is None || self.spec->0.unschedulable != Some(true)
+    }
+
+    #[verifier(inline)]
+    pub open spec fn _state_validation(self) -> bool { true }
+
+    #[verifier(inline)]
+    pub open spec fn _transition_validation(self, old_obj: NodeView) -> bool { true }
+}
+
+implement_resource_view_trait!(NodeView, Option<NodeSpecView>, None, Option<NodeStatusView>, None,
+    Kind::NodeKind, _state_validation, _transition_validation);
+
+pub struct NodeSpecView {
+    pub unschedulable: Option<bool>,
+}
+
+impl NodeSpecView {
+    pub open spec fn default() -> NodeSpecView {
+        NodeSpecView {
+            unschedulable: None,
+        }
+    }
+
+    pub open spec fn with_unschedulable(self, unschedulable: bool) -> NodeSpecView {
+        NodeSpecView {
+            unschedulable: Some(unschedulable),
+            ..self
+        }
+    }
+}
+
+// node_key returns the key of the node with the name.
+pub open spec fn node_key(name: StringView) -> ObjectRef {
+    ObjectRef {
+        kind: Kind::NodeKind,
+        name: name,
+        namespace: cluster_scope_namespace(),
+    }
+}
+
+}
@@ -15,6 +15,7 @@ pub struct ObjectMetaView {
     pub namespace: Option<StringView>,
     pub resource_version: Option<ResourceVersion>,
     pub uid: Option<Uid>,
+    pub generation: Option<int>,
     pub labels: Option<Map<StringView, StringView>>,
     pub annotations: Option<Map<StringView, StringView>>,
     pub owner_references: Option<Seq<OwnerReferenceView>>,
@@ -30,6 +31,7 @@ impl ObjectMetaView {
             namespace: None,
             resource_version: None,
             uid: None,
+            generation: None,
             labels: None,
             annotations: None,
             owner_references: None,
@@ -150,6 +152,13 @@ impl ObjectMetaView {
         }
     }
 
+    pub open spec fn with_generation(self, generation: int) -> ObjectMetaView {
+        ObjectMetaView {
+            generation: Some(generation),
+            ..self
+        }
+    }
+
     pub open spec fn with_owner_references(self, owner_references: Seq<OwnerReferenceView>) -> ObjectMetaView {
         ObjectMetaView {
             owner_references: Some(owner_references),
@@ -19,8 +19,6 @@ pub struct PodView {
     pub status: Option<PodStatusView>,
 }
 
-pub type PodStatusView = EmptyStatusView;
-
 impl PodView {
     pub open spec fn with_metadata(self, metadata: ObjectMetaView) -> PodView {
         PodView {
@@ -36,6 +34,37 @@ impl PodView {
         }
     }
 
+    pub open spec fn with_status(self, status: PodStatusView) -> PodView {
+        PodView {
+            status: Some(status),
+            ..self
+        }
+    }
+
+    // is_scheduled says that the pod has been bound to a node.
+    pub open spec fn is_scheduled(self) -> bool {
+        &&& self.spec is Some
+        &&& self.spec->0.node_name is Some
+    }
+
+    // phase returns the phase of the pod, where a pod without a phase is pending.
+    pub open spec fn phase(self) -> StringView {
+        if self.status is Some && self.status->0.phase is Some {
+            self.status->0.phase->0
+        } else {
+            pod_pending_phase()
+        }
+    }
+
+    // is_ready says that the pod is running and its Ready condition is true.
+    pub open spec fn is_ready(self) -> bool {
+        &&& self.phase() == pod_running_phase()
+        &&& self.status->0.conditions is Some
+        &&& exists |i| #![trigger self.status->0.conditions->0[i]] 0 <= i < self.status->0.conditions->0.len()
+            && self.status->0.conditions->0[i].type_ == pod_ready_condition()
+            && self.status->0.conditions->0[i].status == "True"@
+    }
+
     #[verifier(inline)]
     pub open spec fn _state_validation(self) -> bool {
         self.spec is Some
@@ -66,6 +95,7 @@ pub struct PodSpecView {
     pub image_pull_secrets: Option<Seq<LocalObjectReferenceView>>,
     pub hostname: Option<StringView>,
     pub subdomain: Option<StringView>,
+    pub node_name: Option<StringView>,
 }
 
 impl PodSpecView {
@@ -88,6 +118,7 @@ impl PodSpecView {
             image_pull_secrets: None,
             hostname: None,
             subdomain: None,
+            node_name: None,
         }
     }
 
@@ -244,8 +275,83 @@ impl PodSpecView {
             ..self
         }
     }
+
+    pub open spec fn with_node_name(self, node_name: StringView) -> PodSpecView {
+        PodSpecView {
+            node_name: Some(node_name),
+            ..self
+        }
+    }
+
+    pub open spec fn without_node_name(self) -> PodSpecView {
+        PodSpecView {
+            node_name: None,
+            ..self
+        }
+    }
+}
+
+// PodStatusView only models the fields set by the scheduler and kubelet in
+// crate::kubernetes_cluster::spec::builtin_controllers::pod_lifecycle.
+pub struct PodStatusView {
+    pub phase: Option<StringView>,
+    pub conditions: Option<Seq<PodConditionView>>,
+    pub container_statuses: Option<Seq<ContainerStatusView>>,
+}
+
+impl PodStatusView {
+    pub open spec fn default() -> PodStatusView {
+        PodStatusView {
+            phase: None,
+            conditions: None,
+            container_statuses: None,
+        }
+    }
+
+    pub open spec fn with_phase(self, phase: StringView) -> PodStatusView {
+        PodStatusView {
+            phase: Some(phase),
+            ..self
+        }
+    }
+
+    pub open spec fn with_conditions(self, conditions: Seq<PodConditionView>) -> PodStatusView {
+        PodStatusView {
+            conditions: Some(conditions),
+            ..self
+        }
+    }
+
+    pub open spec fn with_container_statuses(self, container_statuses: Seq<ContainerStatusView>) -> PodStatusView {
+        PodStatusView {
+            container_statuses: Some(container_statuses),
+            ..self
+        }
+    }
+}
+
+pub struct PodConditionView {
+    pub type_: StringView,
+    pub status: StringView,
+}
+
+pub struct ContainerStatusView {
+    pub name: StringView,
+    pub ready: bool,
+    pub started: Option<bool>,
+    pub restart_count: int,
 }
 
+pub open spec fn pod_pending_phase() -> StringView { "Pending"@ }
+
+pub open spec fn pod_running_phase() -> StringView { "Running"@ }
+
+pub open spec fn pod_failed_phase() -> StringView { "Failed"@ }
+
+pub open spec fn pod_succeeded_phase() -> StringView { "Succeeded"@ }
+
+pub open spec fn pod_ready_condition() -> StringView { "Ready"@ }
+
 pub struct PodSecurityContextView {}
 
 impl PodSecurityContextView {
@@ -50,7 +50,7 @@ pub trait ResourceView: Sized {
                 #[trigger] o.object_ref() == (ObjectRef {
                     kind: Self::kind(),
                     name: o.metadata().name->0,
-                    namespace: o.metadata().namespace->0,
+                    namespace: o.metadata().namespace.unwrap_or(cluster_scope_namespace()),
                 });
 
     // Get the spec of the object
@@ -122,6 +122,9 @@ pub trait CustomResourceView: ResourceView {
     proof fn kind_is_custom_resource()
         ensures Self::kind() is CustomResourceKind;
 
+    // The scope of the custom resource, which is set in its CRD.
+    spec fn scope() -> ResourceScope;
+
     // The following spec and proof state that validation is only determined by spec and status.
     // That is, validation is not affected by the metadata.
     // TODO: promote this to ResourceView.
@@ -161,7 +164,7 @@ macro_rules! implement_resource_view_trait {
                 ObjectRef {
                     kind: Self::kind(),
                     name: self.metadata().name->0,
-                    namespace: self.metadata().namespace->0,
+                    namespace: self.metadata().namespace.unwrap_or(cluster_scope_namespace()),
                 }
             }
 
@@ -259,7 +262,7 @@ macro_rules! implement_resource_view_trait {
                 ObjectRef {
                     kind: Self::kind(),
                     name: self.metadata().name->0,
-                    namespace: self.metadata().namespace->0,
+                    namespace: self.metadata().namespace.unwrap_or(cluster_scope_namespace()),
                 }
             }
 
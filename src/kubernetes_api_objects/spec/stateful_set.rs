@@ -260,12 +260,21 @@ impl RollingUpdateStatefulSetStrategyView {
 
 pub struct StatefulSetStatusView {
     pub ready_replicas: Option<int>,
+    pub observed_generation: Option<int>,
 }
 
 impl StatefulSetStatusView {
     pub open spec fn default() -> StatefulSetStatusView {
         StatefulSetStatusView {
             ready_replicas: None,
+            observed_generation: None,
+        }
+    }
+
+    pub open spec fn with_observed_generation(self, observed_generation: int) -> StatefulSetStatusView {
+        StatefulSetStatusView {
+            observed_generation: Some(observed_generation),
+            ..self
         }
     }
 }
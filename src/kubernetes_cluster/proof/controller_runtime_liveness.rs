@@ -290,8 +290,10 @@ pub proof fn lemma_from_some_state_to_arbitrary_next_state_to_reconcile_idle(sel
         self.controller_models[controller_id].external_model is Some ==> spec.entails(always(lift_state(Self::there_is_the_external_state(controller_id)))),
         // Current state is not the terminating state (done or error), meaning that reconcile will continue.
         forall |s| (#[trigger] current_state(s)) ==> !(self.reconcile_model(controller_id).error)(s) && !(self.reconcile_model(controller_id).done)(s),
-        // Given any input cr, resp_o and local state s, current state will transition to next state.
+        // Given any input cr, resp_o and local state s, current state will transition to next state,
+        // with either transition or (if the controller has a finalizer) delete_transition.
         forall |input_cr, resp_o, s| current_state(s) ==> #[trigger] next_state((self.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0),
+        self.reconcile_model(controller_id).finalizer is Some ==> forall |input_cr, resp_o, s| current_state(s) ==> #[trigger] next_state((self.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0),
         // Next state leads to idle.
         spec.entails(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, next_state)).leads_to(lift_state(Self::reconcile_idle(controller_id, cr_key)))),
     ensures spec.entails(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, current_state)).leads_to(lift_state(Self::reconcile_idle(controller_id, cr_key)))),
@@ -323,6 +325,7 @@ pub proof fn lemma_from_some_state_to_arbitrary_next_state(self, spec: TempPred<
         self.controller_models[controller_id].external_model is Some ==> spec.entails(always(lift_state(Self::there_is_the_external_state(controller_id)))),
         forall |s| (#[trigger] current_state(s)) ==> !(self.reconcile_model(controller_id).error)(s) && !(self.reconcile_model(controller_id).done)(s),
         forall |input_cr, resp_o, s| current_state(s) ==> #[trigger] next_state((self.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0),
+        self.reconcile_model(controller_id).finalizer is Some ==> forall |input_cr, resp_o, s| current_state(s) ==> #[trigger] next_state((self.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0),
     ensures spec.entails(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, current_state)).leads_to(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, next_state)))),
 {
     let at_some_state_and_pending_req_in_flight_or_resp_in_flight = |s: ClusterState| {
@@ -366,6 +369,7 @@ pub proof fn lemma_from_init_state_to_next_state_to_reconcile_idle(self, spec: T
         spec.entails(always(lift_state(Self::there_is_the_controller_state(controller_id)))),
         forall |s| (#[trigger] init_state(s)) ==> !(self.reconcile_model(controller_id).error)(s) && !(self.reconcile_model(controller_id).done)(s),
         forall |input_cr, resp_o, s| init_state(s) ==> next_state(#[trigger] (self.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0),
+        self.reconcile_model(controller_id).finalizer is Some ==> forall |input_cr, resp_o, s| init_state(s) ==> next_state(#[trigger] (self.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0),
         spec.entails(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, next_state)).leads_to(lift_state(Self::reconcile_idle(controller_id, cr_key)))),
     ensures spec.entails(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, init_state)).leads_to(lift_state(Self::reconcile_idle(controller_id, cr_key)))),
 {
@@ -408,6 +412,7 @@ pub proof fn lemma_from_no_pending_req_in_flight_at_some_state_to_next_state(sel
         spec.entails(always(lift_state(Self::there_is_the_controller_state(controller_id)))),
         forall |s| (#[trigger] init_state(s)) ==> !(self.reconcile_model(controller_id).error)(s) && !(self.reconcile_model(controller_id).done)(s),
         forall |input_cr, resp_o, s| init_state(s) ==> next_state(#[trigger] (self.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0),
+        self.reconcile_model(controller_id).finalizer is Some ==> forall |input_cr, resp_o, s| init_state(s) ==> next_state(#[trigger] (self.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0),
     ensures spec.entails(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, init_state)).leads_to(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, next_state)))),
 {
     let no_pending_req = |s| {
@@ -453,6 +458,7 @@ pub proof fn lemma_from_pending_req_in_flight_at_some_state_to_next_state(self,
         self.controller_models[controller_id].external_model is Some ==> spec.entails(always(lift_state(Self::there_is_the_external_state(controller_id)))),
         forall |s| (#[trigger] current_state(s)) ==> !(self.reconcile_model(controller_id).error)(s) && !(self.reconcile_model(controller_id).done)(s),
         forall |input_cr, resp_o, s| current_state(s) ==> #[trigger] next_state((self.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0),
+        self.reconcile_model(controller_id).finalizer is Some ==> forall |input_cr, resp_o, s| current_state(s) ==> #[trigger] next_state((self.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0),
     ensures spec.entails(lift_state(Self::pending_req_in_flight_at_reconcile_state(controller_id, cr_key, current_state)).leads_to(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, next_state)))),
 {
     self.lemma_from_pending_req_in_flight_at_some_state_to_in_flight_resp_matches_pending_req_at_some_state(spec, controller_id, cr_key, current_state);
@@ -477,6 +483,7 @@ pub proof fn lemma_from_in_flight_resp_matches_pending_req_at_some_state_to_next
         spec.entails(always(lift_state(Self::there_is_the_controller_state(controller_id)))),
         forall |s| (#[trigger] current_state(s)) ==> !(self.reconcile_model(controller_id).error)(s) && !(self.reconcile_model(controller_id).done)(s),
         forall |input_cr, resp_o, s| current_state(s) ==> #[trigger] next_state((self.reconcile_model(controller_id).transition)(input_cr, resp_o, s).0),
+        self.reconcile_model(controller_id).finalizer is Some ==> forall |input_cr, resp_o, s| current_state(s) ==> #[trigger] next_state((self.reconcile_model(controller_id).delete_transition)(input_cr, resp_o, s).0),
     ensures spec.entails(lift_state(Self::resp_in_flight_matches_pending_req_at_reconcile_state(controller_id, cr_key, current_state)).leads_to(lift_state(Self::at_expected_reconcile_states(controller_id, cr_key, next_state)))),
 {
     let pre = Self::resp_in_flight_matches_pending_req_at_reconcile_state(controller_id, cr_key, current_state);
@@ -633,6 +640,15 @@ pub proof fn lemma_from_pending_req_in_flight_at_some_state_to_in_flight_resp_ma
                                 assert(pre_1(s_prime));
                             }
                         }
+                        Step::ExternalFailureStep(input) => {
+                            if input.0 == req_msg {
+                                let resp_msg = form_external_resp_msg(req_msg, Err(input.1));
+                                assert(s_prime.in_flight().contains(resp_msg));
+                                assert(post_1(s_prime));
+                            } else {
+                                assert(pre_1(s_prime));
+                            }
+                        }
                         Step::ControllerStep(input) => { assert(pre_1(s_prime)); },
                         _ => { assert(pre_1(s_prime)); }
                     }
@@ -1,6 +1,7 @@
+use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::{
-    api_server::state_machine::*, cluster::*,
+    api_server::state_machine::*, api_server::types::*, cluster::*,
     builtin_controllers::types::*, controller::types::*,
     external::state_machine::*, message::*,
 };
@@ -90,6 +91,8 @@ pub proof fn lemma_always_triggering_cr_has_lower_uid_than_uid_counter(self, spe
 pub open spec fn state_comes_with_a_pending_request(self, controller_id: int, state: spec_fn(ReconcileLocalState) -> bool) -> bool {
     &&& forall |s| #[trigger] state(s) ==> s != (self.controller_models[controller_id].reconcile_model.init)()
     &&& forall |cr, resp_o, pre_state| #[trigger] state((self.controller_models[controller_id].reconcile_model.transition)(cr, resp_o, pre_state).0) ==> (self.controller_models[controller_id].reconcile_model.transition)(cr, resp_o, pre_state).1 is Some
+    &&& self.controller_models[controller_id].reconcile_model.finalizer is Some ==>
+        forall |cr, resp_o, pre_state| #[trigger] state((self.controller_models[controller_id].reconcile_model.delete_transition)(cr, resp_o, pre_state).0) ==> (self.controller_models[controller_id].reconcile_model.delete_transition)(cr, resp_o, pre_state).1 is Some
 }
 
 // TODO: Investigate flaky proof.
@@ -145,6 +148,38 @@ pub proof fn lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_s
                         }
                     }
                 }
+                Step::CachedReadStep(input) => {
+                    if input == pending_req_msg {
+                        let cached_api_server_state = APIServerState {
+                            resources: s.cache(input.src->Controller_0)->0,
+                            ..s.api_server
+                        };
+                        let resp_msg = transition_by_etcd(self.installed_types, pending_req_msg, cached_api_server_state).1;
+                        assert(s_prime.in_flight().contains(resp_msg));
+                    } else {
+                        if !s.in_flight().contains(pending_req_msg) {
+                            assert(s_prime.in_flight().contains(resp));
+                        }
+                    }
+                }
+                Step::DropRespStep(input) => {
+                    if s.in_flight().contains(pending_req_msg) {
+                        assert(s_prime.in_flight().contains(pending_req_msg));
+                    } else if input == resp {
+                        let err_resp_msg = form_err_resp_msg_replacing(resp, APIError::Timeout);
+                        assert(resp_msg_matches_req_msg(err_resp_msg, pending_req_msg));
+                        assert(s_prime.in_flight().contains(err_resp_msg));
+                    } else {
+                        assert(s_prime.in_flight().contains(resp));
+                    }
+                }
+                Step::DuplicateReqStep(input) => {
+                    if s.in_flight().contains(pending_req_msg) {
+                        assert(s_prime.in_flight().contains(pending_req_msg));
+                    } else {
+                        assert(s_prime.in_flight().contains(resp));
+                    }
+                }
                 Step::ControllerStep(input) => {
                     let input_controller_id = input.0;
                     let input_cr_key = input.2->0;
@@ -175,6 +210,16 @@ pub proof fn lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_s
                         }
                     }
                 }
+                Step::ExternalFailureStep(input) => {
+                    if input.0 == pending_req_msg {
+                        let resp_msg = form_external_resp_msg(pending_req_msg, Err(input.1));
+                        assert(s_prime.in_flight().contains(resp_msg));
+                    } else {
+                        if !s.in_flight().contains(pending_req_msg) {
+                            assert(s_prime.in_flight().contains(resp));
+                        }
+                    }
+                }
                 _ => {
                     assert(invariant(s_prime));
                 }
@@ -400,6 +445,128 @@ proof fn lemma_xor_preserves_during_external_step(
     }
 }
 
+// Helper lemma for the ExternalFailureStep case
+proof fn lemma_xor_preserves_during_external_failure_step(
+    self, controller_id: int, key: ObjectRef, s: ClusterState, s_prime: ClusterState, input: (Message, ExternalError, bool),
+)
+    requires
+        self.controller_models.contains_key(controller_id),
+        s.ongoing_reconciles(controller_id).contains_key(key),
+        Self::pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg(controller_id, key)(s),
+        self.next_step(s, s_prime, Step::ExternalFailureStep(input)),
+        Self::every_in_flight_msg_has_no_replicas_and_has_unique_id()(s),
+    ensures Self::pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg(controller_id, key)(s_prime),
+{
+    if s_prime.ongoing_reconciles(controller_id).contains_key(key) && Self::has_pending_req_msg(controller_id, s_prime, key) {
+        let pending_req_msg = s.ongoing_reconciles(controller_id)[key].pending_req_msg->0;
+        let req_msg = input.0;
+        let err_resp_msg = form_external_resp_msg(req_msg, Err(input.1));
+        if req_msg == pending_req_msg {
+            assert(s_prime.in_flight().contains(err_resp_msg));
+            assert(!s_prime.in_flight().contains(pending_req_msg));
+        } else if s.in_flight().contains(pending_req_msg) {
+            assert(s_prime.in_flight().contains(pending_req_msg));
+            assert(req_msg.rpc_id != pending_req_msg.rpc_id);
+            assert(!resp_msg_matches_req_msg(err_resp_msg, pending_req_msg));
+            assert forall |resp_msg: Message| true implies {
+                ||| ! #[trigger] s_prime.in_flight().contains(resp_msg)
+                ||| !resp_msg_matches_req_msg(resp_msg, pending_req_msg)
+            } by {
+                if s.in_flight().contains(resp_msg) {}
+            }
+        } else {
+            let resp = choose |msg| {
+                #[trigger] s.in_flight().contains(msg)
+                && resp_msg_matches_req_msg(msg, pending_req_msg)
+            };
+            assert(s_prime.in_flight().contains(resp));
+            assert(!s_prime.in_flight().contains(pending_req_msg));
+        }
+    }
+}
+
+// Helper lemma for the DropRespStep case
+proof fn lemma_xor_preserves_during_drop_resp_step(
+    self, controller_id: int, key: ObjectRef, s: ClusterState, s_prime: ClusterState, input: Message,
+)
+    requires
+        self.controller_models.contains_key(controller_id),
+        s.ongoing_reconciles(controller_id).contains_key(key),
+        Self::pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg(controller_id, key)(s),
+        self.next_step(s, s_prime, Step::DropRespStep(input)),
+        Self::every_in_flight_msg_has_no_replicas_and_has_unique_id()(s),
+    ensures Self::pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg(controller_id, key)(s_prime),
+{
+    if s_prime.ongoing_reconciles(controller_id).contains_key(key) && Self::has_pending_req_msg(controller_id, s_prime, key) {
+        let pending_req_msg = s.ongoing_reconciles(controller_id)[key].pending_req_msg->0;
+        let err_resp_msg = form_err_resp_msg_replacing(input, APIError::Timeout);
+        // The error response matches the same request as the dropped response.
+        assert(resp_msg_matches_req_msg(err_resp_msg, pending_req_msg) == resp_msg_matches_req_msg(input, pending_req_msg));
+        if s.in_flight().contains(pending_req_msg) {
+            assert(s_prime.in_flight().contains(pending_req_msg));
+            assert(!resp_msg_matches_req_msg(input, pending_req_msg));
+            assert forall |resp_msg: Message| true implies {
+                ||| ! #[trigger] s_prime.in_flight().contains(resp_msg)
+                ||| !resp_msg_matches_req_msg(resp_msg, pending_req_msg)
+            } by {
+                if s.in_flight().contains(resp_msg) {}
+            }
+        } else {
+            let resp = choose |msg| {
+                #[trigger] s.in_flight().contains(msg)
+                && resp_msg_matches_req_msg(msg, pending_req_msg)
+            };
+            if input == resp {
+                assert(s_prime.in_flight().contains(err_resp_msg));
+            } else {
+                assert(s_prime.in_flight().contains(resp));
+            }
+            assert(!s_prime.in_flight().contains(pending_req_msg));
+        }
+    }
+}
+
+// Helper lemma for the DuplicateReqStep case
+proof fn lemma_xor_preserves_during_duplicate_req_step(
+    self, controller_id: int, key: ObjectRef, s: ClusterState, s_prime: ClusterState, input: Message,
+)
+    requires
+        self.controller_models.contains_key(controller_id),
+        s.ongoing_reconciles(controller_id).contains_key(key),
+        Self::pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg(controller_id, key)(s),
+        self.next_step(s, s_prime, Step::DuplicateReqStep(input)),
+        Self::every_in_flight_msg_has_lower_id_than_allocator()(s),
+    ensures Self::pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg(controller_id, key)(s_prime),
+{
+    if s_prime.ongoing_reconciles(controller_id).contains_key(key) && Self::has_pending_req_msg(controller_id, s_prime, key) {
+        let pending_req_msg = s.ongoing_reconciles(controller_id)[key].pending_req_msg->0;
+        let dup_req_msg = form_msg(input.src, input.dst, s.rpc_id_allocator.rpc_id_counter, input.content);
+        // The copy has a new rpc_id, so it is neither the pending request nor a response to it,
+        // whose rpc_id is the same as some message in flight.
+        if s.in_flight().contains(pending_req_msg) {
+            assert(pending_req_msg.rpc_id < s.rpc_id_allocator.rpc_id_counter);
+            assert(s_prime.in_flight().contains(pending_req_msg));
+            assert forall |resp_msg: Message| true implies {
+                ||| ! #[trigger] s_prime.in_flight().contains(resp_msg)
+                ||| !resp_msg_matches_req_msg(resp_msg, pending_req_msg)
+            } by {
+                if s.in_flight().contains(resp_msg) {} else {
+                    assert(resp_msg == dup_req_msg);
+                }
+            }
+        } else {
+            let resp = choose |msg| {
+                #[trigger] s.in_flight().contains(msg)
+                && resp_msg_matches_req_msg(msg, pending_req_msg)
+            };
+            assert(resp.rpc_id == pending_req_msg.rpc_id);
+            assert(pending_req_msg.rpc_id < s.rpc_id_allocator.rpc_id_counter);
+            assert(pending_req_msg != dup_req_msg);
+            assert(s_prime.in_flight().contains(resp));
+            assert(!s_prime.in_flight().contains(pending_req_msg));
+        }
+    }
+}
 
 // see https://github.com/verus-lang/verus/issues/2038
 // currently every branch in match is proved, but the combination of them fails
@@ -471,6 +638,15 @@ pub proof fn lemma_true_leads_to_always_pending_req_in_flight_xor_resp_in_flight
                     Step::ExternalStep(input) => {
                         self.lemma_xor_preserves_during_external_step(controller_id, key, s, s_prime, input);
                     }
+                    Step::ExternalFailureStep(input) => {
+                        self.lemma_xor_preserves_during_external_failure_step(controller_id, key, s, s_prime, input);
+                    }
+                    Step::DropRespStep(input) => {
+                        self.lemma_xor_preserves_during_drop_resp_step(controller_id, key, s, s_prime, input);
+                    }
+                    Step::DuplicateReqStep(input) => {
+                        self.lemma_xor_preserves_during_duplicate_req_step(controller_id, key, s, s_prime, input);
+                    }
                     _ => {}
                 }
             }
@@ -505,6 +681,8 @@ pub proof fn lemma_always_no_pending_req_msg_at_reconcile_state(self, spec: Temp
         spec.entails(lift_state(self.init())),
         spec.entails(always(lift_action(self.next()))),
         forall |cr, resp_o, pre_state| #[trigger] state((self.controller_models[controller_id].reconcile_model.transition)(cr, resp_o, pre_state).0) ==> (self.controller_models[controller_id].reconcile_model.transition)(cr, resp_o, pre_state).1 is None,
+        self.controller_models[controller_id].reconcile_model.finalizer is Some ==>
+            forall |cr, resp_o, pre_state| #[trigger] state((self.controller_models[controller_id].reconcile_model.delete_transition)(cr, resp_o, pre_state).0) ==> (self.controller_models[controller_id].reconcile_model.delete_transition)(cr, resp_o, pre_state).1 is None,
     ensures spec.entails(always(lift_state(Self::no_pending_req_msg_at_reconcile_state(controller_id, key, state)))),
 {
     let invariant = Self::no_pending_req_msg_at_reconcile_state(controller_id, key, state);
@@ -908,6 +1086,13 @@ pub proof fn lemma_always_every_ongoing_reconcile_has_unique_id(
     init_invariant::<ClusterState>(spec, self.init(), stronger_next, invariant);
 }
 
+// The finalizer updates sent by run_scheduled_reconcile and end_reconcile are not waited for,
+// so they are never the pending request of the reconcile.
+pub open spec fn is_finalizer_update_request_msg_of(key: ObjectRef, msg: Message) -> bool {
+    &&& msg.content.is_update_request()
+    &&& msg.content.get_update_request().key() == key
+}
+
 pub open spec fn every_msg_from_key_is_pending_req_msg_of(
     controller_id: int, key: ObjectRef
 ) -> StatePred<ClusterState> {
@@ -917,6 +1102,7 @@ pub open spec fn every_msg_from_key_is_pending_req_msg_of(
             &&& msg.content is APIRequest
             &&& msg.dst is APIServer
             &&& s.in_flight().contains(msg)
+            &&& !Self::is_finalizer_update_request_msg_of(key, msg)
         } ==> {
             &&& s.ongoing_reconciles(controller_id).contains_key(key)
             &&& Cluster::pending_req_msg_is(controller_id, s, key, msg)
@@ -932,6 +1118,7 @@ pub proof fn lemma_true_leads_to_always_every_msg_from_key_is_pending_req_msg_of
         spec.entails(always(lift_state(Self::there_is_the_controller_state(controller_id)))),
         spec.entails(always(lift_state(Self::crash_disabled(controller_id)))),
         spec.entails(always(lift_state(Self::req_drop_disabled()))),
+        spec.entails(always(lift_state(Self::req_dup_disabled()))),
         spec.entails(always(lift_state(Self::pod_monkey_disabled()))),
         spec.entails(always(lift_state(Self::pending_req_of_key_is_unique_with_unique_id(controller_id, key)))),
         spec.entails(always(lift_state(Self::every_in_flight_msg_has_lower_id_than_allocator()))),
@@ -954,6 +1141,7 @@ pub proof fn lemma_true_leads_to_always_every_msg_from_key_is_pending_req_msg_of
             &&& msg.content is APIRequest
             &&& msg.dst is APIServer
             &&& s.in_flight().contains(msg)
+            &&& !Self::is_finalizer_update_request_msg_of(key, msg)
         }) ==> ({
             &&& s.ongoing_reconciles(controller_id).contains_key(key)
             &&& Self::pending_req_msg_is(controller_id, s, key, msg)
@@ -964,6 +1152,7 @@ pub proof fn lemma_true_leads_to_always_every_msg_from_key_is_pending_req_msg_of
         &&& msg.content is APIRequest
         &&& msg.dst is APIServer
         &&& s.in_flight().contains(msg)
+        &&& !Self::is_finalizer_update_request_msg_of(key, msg)
     };
 
     let stronger_next = |s, s_prime| {
@@ -971,6 +1160,7 @@ pub proof fn lemma_true_leads_to_always_every_msg_from_key_is_pending_req_msg_of
         &&& Self::there_is_the_controller_state(controller_id)(s)
         &&& Self::crash_disabled(controller_id)(s)
         &&& Self::req_drop_disabled()(s)
+        &&& Self::req_dup_disabled()(s)
         &&& Self::pod_monkey_disabled()(s)
         &&& Self::pending_req_of_key_is_unique_with_unique_id(controller_id, key)(s)
         &&& Self::pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg(controller_id, key)(s)
@@ -1004,6 +1194,8 @@ pub proof fn lemma_true_leads_to_always_every_msg_from_key_is_pending_req_msg_of
                             // `pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg`
                             // if there's an incoming message, the `no_pending_req_msg_at_reconcile_state`
                             // family if there's not one (meaning we're done).
+                            // The messages sent by RunScheduledReconcile and EndReconcile are finalizer
+                            // updates of key, which are excluded by is_finalizer_update_request_msg_of.
                             //
                             // (comment left to provide a hint of the reasoning needed).
                         }
@@ -1021,6 +1213,7 @@ pub proof fn lemma_true_leads_to_always_every_msg_from_key_is_pending_req_msg_of
         lift_state(Self::there_is_the_controller_state(controller_id)),
         lift_state(Self::crash_disabled(controller_id)),
         lift_state(Self::req_drop_disabled()),
+        lift_state(Self::req_dup_disabled()),
         lift_state(Self::pod_monkey_disabled()),
         lift_state(Self::pending_req_of_key_is_unique_with_unique_id(controller_id, key)),
         lift_state(Self::pending_req_in_flight_xor_resp_in_flight_if_has_pending_req_msg(controller_id, key)),
@@ -1115,9 +1308,12 @@ pub open spec fn there_is_no_request_msg_to_external_from_controller(controller_
 }
 
 pub open spec fn reconcile_model_sends_no_external_request(model: ReconcileModel) -> bool {
-    forall |cr: DynamicObjectView, resp_o: Option<ResponseContent>, local: ReconcileLocalState|
+    &&& forall |cr: DynamicObjectView, resp_o: Option<ResponseContent>, local: ReconcileLocalState|
         #[trigger] (model.transition)(cr, resp_o, local).1 is Some
         ==> (model.transition)(cr, resp_o, local).1->0 is KubernetesRequest
+    &&& model.finalizer is Some ==> forall |cr: DynamicObjectView, resp_o: Option<ResponseContent>, local: ReconcileLocalState|
+        #[trigger] (model.delete_transition)(cr, resp_o, local).1 is Some
+        ==> (model.delete_transition)(cr, resp_o, local).1->0 is KubernetesRequest
 }
 
 pub proof fn lemma_always_there_is_no_request_msg_to_external_from_controller(self, spec: TempPred<ClusterState>, controller_id: int)
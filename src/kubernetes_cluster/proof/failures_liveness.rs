@@ -46,6 +46,36 @@ pub proof fn lemma_true_leads_to_req_drop_always_disabled(self, spec: TempPred<C
     leads_to_stable(spec, lift_action(self.next()), true_pred(), lift_state(Self::req_drop_disabled()));
 }
 
+pub open spec fn resp_drop_disabled() -> StatePred<ClusterState> {
+    |s: ClusterState| !s.resp_drop_enabled
+}
+
+pub proof fn lemma_true_leads_to_resp_drop_always_disabled(self, spec: TempPred<ClusterState>)
+    requires
+        spec.entails(always(lift_action(self.next()))),
+        spec.entails(self.disable_resp_drop().weak_fairness(())),
+    ensures spec.entails(true_pred().leads_to(always(lift_state(Self::resp_drop_disabled())))),
+{
+    let true_state = |s: ClusterState| true;
+    self.disable_resp_drop().wf1((), spec, self.next(), true_state, Self::resp_drop_disabled());
+    leads_to_stable(spec, lift_action(self.next()), true_pred(), lift_state(Self::resp_drop_disabled()));
+}
+
+pub open spec fn req_dup_disabled() -> StatePred<ClusterState> {
+    |s: ClusterState| !s.req_dup_enabled
+}
+
+pub proof fn lemma_true_leads_to_req_dup_always_disabled(self, spec: TempPred<ClusterState>)
+    requires
+        spec.entails(always(lift_action(self.next()))),
+        spec.entails(self.disable_req_dup().weak_fairness(())),
+    ensures spec.entails(true_pred().leads_to(always(lift_state(Self::req_dup_disabled())))),
+{
+    let true_state = |s: ClusterState| true;
+    self.disable_req_dup().wf1((), spec, self.next(), true_state, Self::req_dup_disabled());
+    leads_to_stable(spec, lift_action(self.next()), true_pred(), lift_state(Self::req_dup_disabled()));
+}
+
 pub open spec fn pod_monkey_disabled() -> StatePred<ClusterState> {
     |s: ClusterState| !s.pod_monkey_enabled
 }
@@ -61,6 +91,36 @@ pub proof fn lemma_true_leads_to_pod_monkey_always_disabled(self, spec: TempPred
     leads_to_stable(spec, lift_action(self.next()), true_pred(), lift_state(Self::pod_monkey_disabled()));
 }
 
+pub open spec fn node_failure_disabled() -> StatePred<ClusterState> {
+    |s: ClusterState| !s.node_failure_enabled
+}
+
+pub proof fn lemma_true_leads_to_node_failure_always_disabled(self, spec: TempPred<ClusterState>)
+    requires
+        spec.entails(always(lift_action(self.next()))),
+        spec.entails(self.disable_node_failure().weak_fairness(())),
+    ensures spec.entails(true_pred().leads_to(always(lift_state(Self::node_failure_disabled()))))
+{
+    let true_state = |s: ClusterState| true;
+    self.disable_node_failure().wf1((), spec, self.next(), true_state, Self::node_failure_disabled());
+    leads_to_stable(spec, lift_action(self.next()), true_pred(), lift_state(Self::node_failure_disabled()));
+}
+
+pub open spec fn external_failure_disabled() -> StatePred<ClusterState> {
+    |s: ClusterState| !s.external_failure_enabled
+}
+
+pub proof fn lemma_true_leads_to_external_failure_always_disabled(self, spec: TempPred<ClusterState>)
+    requires
+        spec.entails(always(lift_action(self.next()))),
+        spec.entails(self.disable_external_failure().weak_fairness(())),
+    ensures spec.entails(true_pred().leads_to(always(lift_state(Self::external_failure_disabled()))))
+{
+    let true_state = |s: ClusterState| true;
+    self.disable_external_failure().wf1((), spec, self.next(), true_state, Self::external_failure_disabled());
+    leads_to_stable(spec, lift_action(self.next()), true_pred(), lift_state(Self::external_failure_disabled()));
+}
+
 }
 
 }
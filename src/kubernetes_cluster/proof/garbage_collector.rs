@@ -4,7 +4,7 @@ use crate::kubernetes_cluster::spec::{
     builtin_controllers::types::*, cluster::*, message::*,
 };
 use verus_temporal_logic::{defs::*, rules::*};
-use crate::vstd_ext::string_view::StringView;
+use crate::vstd_ext::{seq_lib::*, string_view::StringView};
 use vstd::prelude::*;
 
 verus! {
@@ -106,7 +106,7 @@ spec fn exists_effective_delete_request_msg_for_key(key: ObjectRef) -> StatePred
     }
 }
 
-pub open spec fn garbage_collector_deletion_enabled(key: ObjectRef) -> StatePred<ClusterState> {
+pub open spec fn garbage_collector_deletion_enabled(self, key: ObjectRef) -> StatePred<ClusterState> {
     |s: ClusterState| {
         let input = BuiltinControllersActionInput {
             choice: BuiltinControllerChoice::GarbageCollector,
@@ -114,7 +114,7 @@ pub open spec fn garbage_collector_deletion_enabled(key: ObjectRef) -> StatePred
             rpc_id_allocator: s.rpc_id_allocator,
             resources: s.api_server.resources
         };
-        (run_garbage_collector().precondition)(input, ())
+        (run_garbage_collector(self.installed_types).precondition)(input, ())
     }
 }
 
@@ -160,7 +160,7 @@ pub proof fn lemma_eventually_objects_owner_references_satisfies(
         spec.entails(always(lift_state(Self::every_create_msg_with_generate_name_matching_key_set_owner_references_as(key, eventual_owner_ref)))),
         spec.entails(always(lift_state(Self::object_has_no_finalizers(key)))),
         // If the current owner_references does not satisfy the eventual requirement, the gc action is enabled.
-        spec.entails(always(lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(Self::garbage_collector_deletion_enabled(key))))),
+        spec.entails(always(lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(self.garbage_collector_deletion_enabled(key))))),
         spec.entails(always(lift_state(Self::each_object_in_etcd_is_weakly_well_formed()))),
     ensures spec.entails(true_pred().leads_to(always(lift_state(Self::objects_owner_references_satisfies(key, eventual_owner_ref))))),
 {
@@ -172,7 +172,7 @@ pub proof fn lemma_eventually_objects_owner_references_satisfies(
 
     let pre = |s: ClusterState| {
         &&& Self::objects_owner_references_violates(key, eventual_owner_ref)(s)
-        &&& Self::garbage_collector_deletion_enabled(key)(s)
+        &&& self.garbage_collector_deletion_enabled(key)(s)
     };
 
     let delete_msg_in_flight = |s: ClusterState| {
@@ -186,18 +186,18 @@ pub proof fn lemma_eventually_objects_owner_references_satisfies(
         &&& Self::every_create_msg_sets_owner_references_as(key, eventual_owner_ref)(s)
         &&& Self::every_valid_update_msg_sets_owner_references_as(self.installed_types, key, eventual_owner_ref)(s)
         &&& Self::every_create_msg_with_generate_name_matching_key_set_owner_references_as(key, eventual_owner_ref)(s)
-        &&& Self::objects_owner_references_violates(key, eventual_owner_ref)(s) ==> Self::garbage_collector_deletion_enabled(key)(s)
-        &&& Self::objects_owner_references_violates(key, eventual_owner_ref)(s_prime) ==> Self::garbage_collector_deletion_enabled(key)(s_prime)
+        &&& Self::objects_owner_references_violates(key, eventual_owner_ref)(s) ==> self.garbage_collector_deletion_enabled(key)(s)
+        &&& Self::objects_owner_references_violates(key, eventual_owner_ref)(s_prime) ==> self.garbage_collector_deletion_enabled(key)(s_prime)
     };
-    always_to_always_later(spec, lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(Self::garbage_collector_deletion_enabled(key))));
+    always_to_always_later(spec, lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(self.garbage_collector_deletion_enabled(key))));
     combine_spec_entails_always_n!(
         spec, lift_action(stronger_next),
         lift_action(self.next()),
         lift_state(Self::every_create_msg_sets_owner_references_as(key, eventual_owner_ref)),
         lift_state(Self::every_valid_update_msg_sets_owner_references_as(self.installed_types, key, eventual_owner_ref)),
         lift_state(Self::every_create_msg_with_generate_name_matching_key_set_owner_references_as(key, eventual_owner_ref)),
-        lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(Self::garbage_collector_deletion_enabled(key))),
-        later(lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(Self::garbage_collector_deletion_enabled(key))))
+        lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(self.garbage_collector_deletion_enabled(key))),
+        later(lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(self.garbage_collector_deletion_enabled(key))))
     );
 
     assert forall |s, s_prime| pre(s) && #[trigger] stronger_next(s, s_prime) && self.builtin_controllers_next().forward(input)(s, s_prime) implies delete_msg_in_flight(s_prime) by {
@@ -214,7 +214,7 @@ pub proof fn lemma_eventually_objects_owner_references_satisfies(
         match step {
             Step::BuiltinControllersStep(i) => {
                 if i == input {
-                    assert(Self::garbage_collector_deletion_enabled(key)(s));
+                    assert(self.garbage_collector_deletion_enabled(key)(s));
                     let preconditions = PreconditionsView {
                         uid: s.api_server.resources[key].metadata.uid,
                         resource_version: None,
@@ -246,7 +246,7 @@ pub proof fn lemma_eventually_objects_owner_references_satisfies(
             or_leads_to_combine_and_equality!(spec, lift_state(delete_msg_in_flight), lift_state(post), lift_state(Self::exists_effective_delete_request_msg_for_key(key)); lift_state(post));
             leads_to_trans_n!(spec, lift_state(pre), lift_state(delete_msg_in_flight), lift_state(post));
 
-            temp_pred_equality(lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(Self::garbage_collector_deletion_enabled(key))), lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(pre)));
+            temp_pred_equality(lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(self.garbage_collector_deletion_enabled(key))), lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)).implies(lift_state(pre)));
             leads_to_weaken(spec, lift_state(pre), lift_state(post), lift_state(Self::objects_owner_references_violates(key, eventual_owner_ref)), lift_state(post));
         }
     );
@@ -333,6 +333,51 @@ proof fn lemma_delete_msg_in_flight_leads_to_owner_references_satisfies(
 }
 
 
+// The builtin controllers update the object at the chosen key in etcd: the garbage collector removes some of its
+// finalizers or owner references, or stops it from blocking the deletion of its owners, and the scheduler sets the node
+// of the pod. So the update keeps the uid of the object, and an owner reference in the update that blocks the deletion
+// of its owner comes from the object in etcd.
+pub proof fn lemma_builtin_controllers_update_comes_from_etcd(
+    self, s: ClusterState, s_prime: ClusterState, input: (BuiltinControllerChoice, ObjectRef), msg: Message
+)
+    requires
+        self.next_step(s, s_prime, Step::BuiltinControllersStep(input)),
+        Self::each_object_in_etcd_is_weakly_well_formed()(s),
+        !s.in_flight().contains(msg),
+        s_prime.in_flight().contains(msg),
+        msg.content.is_update_request(),
+    ensures
+        s.resources().contains_key(input.1),
+        msg.content.get_update_request().key() == input.1,
+        msg.content.get_update_request().obj.metadata.uid == s.resources()[input.1].metadata.uid,
+        forall |owner_reference: OwnerReferenceView| {
+            &&& #[trigger] msg.content.get_update_request().obj.metadata.owner_references_contains(owner_reference)
+            &&& owner_reference.block_owner_deletion == Some(true)
+        } ==> s.resources()[input.1].metadata.owner_references_contains(owner_reference),
+{
+    let key = input.1;
+    let obj = s.resources()[key];
+    let owner_references = obj.metadata.owner_references->0;
+    let req_obj = msg.content.get_update_request().obj;
+    assert(obj.object_ref() == key);
+    let orphan_pred = |o: OwnerReferenceView| !owner_is_deleting_with(self.installed_types, s.resources(), o, key, orphan_finalizer());
+    let prune_pred = |o: OwnerReferenceView| !owner_is_dangling(self.installed_types, s.resources(), o, key);
+    seq_filter_is_a_subset_of_original_seq(owner_references, orphan_pred);
+    seq_filter_is_a_subset_of_original_seq(owner_references, prune_pred);
+    assert forall |owner_reference: OwnerReferenceView| {
+        &&& #[trigger] req_obj.metadata.owner_references_contains(owner_reference)
+        &&& owner_reference.block_owner_deletion == Some(true)
+    } implies obj.metadata.owner_references_contains(owner_reference) by {
+        if req_obj.metadata.owner_references == Some(pruned_owner_references(self.installed_types, s.resources(), owner_references, key)) {
+            // The owner references that no longer block the deletion have blockOwnerDeletion set to false,
+            // so owner_reference is one of the remaining owner references.
+            let pruned = req_obj.metadata.owner_references->0;
+            let i = choose |i| 0 <= i < pruned.len() && pruned[i] == owner_reference;
+            assert(owner_references.contains(owner_references.filter(prune_pred)[i]));
+        }
+    }
+}
+
 // Universally quantified versions of spec fns for reasoning about all keys satisfying cond.
 
 pub open spec fn every_valid_update_msg_sets_owner_references_as_for_all(
@@ -361,11 +406,11 @@ pub open spec fn every_create_msg_with_generate_name_matching_key_set_owner_refe
 }
 
 pub open spec fn gc_is_enabled_for_all_keys_violating_owner_ref_requirements(
-    cond: spec_fn(ObjectRef) -> bool, requirements: spec_fn(Option<Seq<OwnerReferenceView>>) -> bool
+    self, cond: spec_fn(ObjectRef) -> bool, requirements: spec_fn(Option<Seq<OwnerReferenceView>>) -> bool
 ) -> StatePred<ClusterState> {
     |s: ClusterState| {
         forall |key: ObjectRef| #[trigger] cond(key) && Self::objects_owner_references_violates(key, requirements)(s)
-            ==> Self::garbage_collector_deletion_enabled(key)(s)
+            ==> self.garbage_collector_deletion_enabled(key)(s)
     }
 }
 
@@ -405,7 +450,7 @@ pub proof fn lemma_eventually_objects_owner_references_satisfies_for_all(
         spec.entails(always(lift_state(Self::every_create_msg_with_generate_name_matching_key_set_owner_references_as_for_all(cond, eventual_owner_ref)))),
         spec.entails(always(lift_state(Self::object_has_no_finalizers_for_all(cond)))),
         // If any key satisfying cond violates the requirement, gc deletion is enabled for that key.
-        spec.entails(always(lift_state(Self::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)))),
+        spec.entails(always(lift_state(self.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)))),
         spec.entails(always(lift_state(Self::each_object_in_etcd_is_weakly_well_formed()))),
     ensures spec.entails(true_pred().leads_to(always(lift_state(Self::objects_owner_references_satisfies_for_all(cond, eventual_owner_ref))))),
 {
@@ -416,21 +461,21 @@ pub proof fn lemma_eventually_objects_owner_references_satisfies_for_all(
         &&& Self::every_create_msg_sets_owner_references_as_for_all(cond, eventual_owner_ref)(s)
         &&& Self::every_valid_update_msg_sets_owner_references_as_for_all(self.installed_types, cond, eventual_owner_ref)(s)
         &&& Self::every_create_msg_with_generate_name_matching_key_set_owner_references_as_for_all(cond, eventual_owner_ref)(s)
-        &&& Self::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)(s)
-        &&& Self::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)(s_prime)
+        &&& self.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)(s)
+        &&& self.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)(s_prime)
         &&& Self::object_has_no_finalizers_for_all(cond)(s)
         &&& Self::req_drop_disabled()(s)
         &&& Self::each_object_in_etcd_is_weakly_well_formed()(s)
     };
-    always_to_always_later(spec, lift_state(Self::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)));
+    always_to_always_later(spec, lift_state(self.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)));
     combine_spec_entails_always_n!(
         spec, lift_action(stronger_next),
         lift_action(self.next()),
         lift_state(Self::every_create_msg_sets_owner_references_as_for_all(cond, eventual_owner_ref)),
         lift_state(Self::every_valid_update_msg_sets_owner_references_as_for_all(self.installed_types, cond, eventual_owner_ref)),
         lift_state(Self::every_create_msg_with_generate_name_matching_key_set_owner_references_as_for_all(cond, eventual_owner_ref)),
-        lift_state(Self::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)),
-        later(lift_state(Self::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref))),
+        lift_state(self.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)),
+        later(lift_state(self.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref))),
         lift_state(Self::object_has_no_finalizers_for_all(cond)),
         lift_state(Self::req_drop_disabled()),
         lift_state(Self::each_object_in_etcd_is_weakly_well_formed())
@@ -480,12 +525,12 @@ pub proof fn lemma_eventually_objects_owner_references_satisfies_for_all(
                         always(lift_state(Self::object_has_no_finalizers(k)))
                     );
                     entails_preserved_by_always(
-                        lift_state(Self::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)),
-                        lift_state(Self::objects_owner_references_violates(k, eventual_owner_ref)).implies(lift_state(Self::garbage_collector_deletion_enabled(k)))
+                        lift_state(self.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref)),
+                        lift_state(Self::objects_owner_references_violates(k, eventual_owner_ref)).implies(lift_state(self.garbage_collector_deletion_enabled(k)))
                     );
                     entails_trans(spec,
-                        always(lift_state(Self::gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref))),
-                        always(lift_state(Self::objects_owner_references_violates(k, eventual_owner_ref)).implies(lift_state(Self::garbage_collector_deletion_enabled(k))))
+                        always(lift_state(self.gc_is_enabled_for_all_keys_violating_owner_ref_requirements(cond, eventual_owner_ref))),
+                        always(lift_state(Self::objects_owner_references_violates(k, eventual_owner_ref)).implies(lift_state(self.garbage_collector_deletion_enabled(k))))
                     );
                     self.lemma_eventually_objects_owner_references_satisfies(spec, k, eventual_owner_ref);
                     true_leads_to_eventually_always_equality(
@@ -64,7 +64,22 @@ pub proof fn lemma_always_every_in_flight_msg_has_lower_id_than_allocator(self,
                         assert(s.in_flight().contains(input.0));
                         assert(msg.rpc_id == input.0.rpc_id);
                     },
+                    Step::CachedReadStep(input) => {
+                        assert(s.in_flight().contains(input));
+                        assert(msg.rpc_id == input.rpc_id);
+                    },
+                    Step::DropRespStep(input) => {
+                        assert(s.in_flight().contains(input));
+                        assert(msg.rpc_id == input.rpc_id);
+                    },
+                    Step::DuplicateReqStep(input) => {
+                        assert(msg.rpc_id == s.rpc_id_allocator.rpc_id_counter);
+                    },
                     Step::ExternalStep(_) => {},
+                    Step::ExternalFailureStep(input) => {
+                        assert(s.in_flight().contains(input.0));
+                        assert(msg.rpc_id == input.0.rpc_id);
+                    },
                     _ => {},
                 }
             }
@@ -362,6 +377,19 @@ pub proof fn lemma_always_every_in_flight_req_msg_from_controller_has_valid_cont
                     }
                 }
             },
+            Step::DuplicateReqStep(input) => {
+                assert forall |msg|
+                    #[trigger] s_prime.in_flight().contains(msg)
+                    && msg.content is APIRequest
+                    && msg.src is Controller
+                    implies self.controller_models.contains_key(msg.src->Controller_0) by {
+                    if !s.in_flight().contains(msg) {
+                        // The copy is sent by the same controller as the request in flight.
+                        assert(msg.src == input.src);
+                        assert(s.in_flight().contains(input));
+                    }
+                }
+            },
             _ => {
                 assert forall |msg| 
                     #[trigger] s_prime.in_flight().contains(msg) 
@@ -445,6 +473,37 @@ pub proof fn lemma_always_every_in_flight_msg_has_no_replicas_and_has_unique_id(
                                 assert(s_prime.in_flight().count(msg) == 1);
                             }
                         },
+                        Step::CachedReadStep(input) => {
+                            let req = input;
+                            assert(s.in_flight().contains(req));
+                            if s.in_flight().contains(msg) {
+                                assert(s.in_flight().count(msg) == 1);
+                                assert(s_prime.in_flight().count(msg) == 1);
+                            } else {
+                                assert(s_prime.in_flight().count(msg) == 1);
+                            }
+                        },
+                        Step::DropRespStep(input) => {
+                            let resp = input;
+                            assert(s.in_flight().contains(resp));
+                            if s.in_flight().contains(msg) {
+                                assert(s.in_flight().count(msg) == 1);
+                                assert(s_prime.in_flight().count(msg) == 1);
+                            } else {
+                                assert(s_prime.in_flight().count(msg) == 1);
+                            }
+                        },
+                        Step::DuplicateReqStep(input) => {
+                            // The copy has a new rpc_id, so it is different from all the messages in flight.
+                            if s.in_flight().contains(msg) {
+                                assert(s.in_flight().count(msg) == 1);
+                                assert(msg.rpc_id < s.rpc_id_allocator.rpc_id_counter);
+                                assert(s_prime.in_flight().count(msg) == 1);
+                            } else {
+                                assert(msg.rpc_id == s.rpc_id_allocator.rpc_id_counter);
+                                assert(s_prime.in_flight().count(msg) == 1);
+                            }
+                        },
                         _ => {
                             if s.in_flight().contains(msg) {
                                 assert(s.in_flight().count(msg) == 1);
@@ -496,8 +555,27 @@ proof fn newly_added_msg_have_different_id_from_existing_ones(self, s: ClusterSt
                 assert(s.network.in_flight.count(req_msg) <= 1);
                 assert(msg_1.rpc_id != msg_2.rpc_id);
             }
+            Step::CachedReadStep(input) => {
+                let req_msg = input;
+                assert(s.network.in_flight.count(req_msg) <= 1);
+                assert(msg_1.rpc_id != msg_2.rpc_id);
+            }
+            Step::DropRespStep(input) => {
+                let resp_msg = input;
+                assert(s.network.in_flight.count(resp_msg) <= 1);
+                assert(msg_1.rpc_id != msg_2.rpc_id);
+            }
             _ => assert(false),
         }
+    } else if msg_2.content is APIRequest {
+        let next_step = choose |step| self.next_step(s, s_prime, step);
+        match next_step {
+            Step::DuplicateReqStep(input) => {
+                assert(msg_2.rpc_id == s.rpc_id_allocator.rpc_id_counter);
+                assert(msg_1.rpc_id < s.rpc_id_allocator.rpc_id_counter);
+            }
+            _ => {}
+        }
     } else if msg_2.content is ExternalResponse {
         let next_step = choose |step| self.next_step(s, s_prime, step);
         match next_step {
@@ -506,6 +584,11 @@ proof fn newly_added_msg_have_different_id_from_existing_ones(self, s: ClusterSt
                 assert(s.network.in_flight.count(req_msg) <= 1);
                 assert(msg_1.rpc_id != msg_2.rpc_id);
             }
+            Step::ExternalFailureStep(input) => {
+                let req_msg = input.0;
+                assert(s.network.in_flight.count(req_msg) <= 1);
+                assert(msg_1.rpc_id != msg_2.rpc_id);
+            }
             _ => assert(false),
         }
     }
@@ -561,7 +644,13 @@ pub proof fn lemma_always_no_pending_request_to_api_server_from_api_server_or_ex
             &&& (msg.src is APIServer || msg.src is External)
             &&& msg.dst is APIServer
             &&& msg.content is APIRequest} by {
-            if s.in_flight().contains(msg) {} else {}
+            if s.in_flight().contains(msg) {} else {
+                let step = choose |step| self.next_step(s, s_prime, step);
+                if step is DuplicateReqStep {
+                    // The copy has the same src and content as the request in flight.
+                    assert(s.in_flight().contains(step->DuplicateReqStep_0));
+                }
+            }
         }
     };
     init_invariant(spec, self.init(), self.next(), inv);
@@ -608,41 +697,99 @@ pub proof fn lemma_always_all_requests_from_pod_monkey_are_api_pod_requests(self
                 _ => false,
             }
         } by {
-            if s.in_flight().contains(msg) {} else {}
+            if s.in_flight().contains(msg) {} else {
+                let step = choose |step| self.next_step(s, s_prime, step);
+                if step is DuplicateReqStep {
+                    // The copy has the same src and content as the request in flight.
+                    assert(s.in_flight().contains(step->DuplicateReqStep_0));
+                }
+            }
         }
     };
     init_invariant(spec, self.init(), self.next(), inv);
 }
 
-// this is obvious but Verus still needs it
-pub open spec fn all_requests_from_builtin_controllers_are_api_delete_requests() -> StatePred<ClusterState> {
+pub open spec fn all_requests_from_node_monkey_are_api_node_delete_requests() -> StatePred<ClusterState> {
     |s: ClusterState| {
         forall |msg: Message| {
             &&& #[trigger] s.in_flight().contains(msg)
-            &&& msg.src is BuiltinController
+            &&& msg.src is NodeMonkey
         } ==> {
             &&& msg.dst is APIServer
             &&& msg.content.is_delete_request()
+            &&& msg.content.get_delete_request().key.kind == Kind::NodeKind
+            &&& msg.content.get_delete_request().propagation_policy is None
         }
     }
 }
 
-pub proof fn lemma_always_all_requests_from_builtin_controllers_are_api_delete_requests(self, spec: TempPred<ClusterState>)
+pub proof fn lemma_always_all_requests_from_node_monkey_are_api_node_delete_requests(self, spec: TempPred<ClusterState>)
     requires
         spec.entails(lift_state(self.init())),
         spec.entails(always(lift_action(self.next()))),
-    ensures spec.entails(always(lift_state(Self::all_requests_from_builtin_controllers_are_api_delete_requests()))),
+    ensures spec.entails(always(lift_state(Self::all_requests_from_node_monkey_are_api_node_delete_requests()))),
 {
-    let inv = Self::all_requests_from_builtin_controllers_are_api_delete_requests();
+    let inv = Self::all_requests_from_node_monkey_are_api_node_delete_requests();
     assert forall |s, s_prime| inv(s) && #[trigger] self.next()(s, s_prime) implies inv(s_prime) by {
         assert forall |msg: Message| {
             &&& #[trigger] s_prime.in_flight().contains(msg)
-            &&& msg.src is BuiltinController
+            &&& msg.src is NodeMonkey
         } implies {
             &&& msg.dst is APIServer
             &&& msg.content.is_delete_request()
+            &&& msg.content.get_delete_request().key.kind == Kind::NodeKind
+            &&& msg.content.get_delete_request().propagation_policy is None
+        } by {
+            if s.in_flight().contains(msg) {} else {
+                let step = choose |step| self.next_step(s, s_prime, step);
+                if step is DuplicateReqStep {
+                    // The copy has the same src and content as the request in flight.
+                    assert(s.in_flight().contains(step->DuplicateReqStep_0));
+                }
+            }
+        }
+    };
+    init_invariant(spec, self.init(), self.next(), inv);
+}
+
+// this is obvious but Verus still needs it:
+// the garbage collector deletes objects, and updates objects to remove their finalizers (foregroundDeletion and orphan)
+// or owner references (see builtin_controllers::garbage_collector); the scheduler updates pods to bind them to nodes,
+// the kubelet updates the status of pods, and the node lifecycle controller deletes pods (see builtin_controllers::pod_lifecycle).
+pub open spec fn all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests() -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        forall |msg: Message| {
+            &&& #[trigger] s.in_flight().contains(msg)
+            &&& msg.src is BuiltinController
+        } ==> {
+            &&& msg.dst is APIServer
+            &&& msg.content.is_delete_request() || msg.content.is_update_request() || msg.content.is_update_status_request()
+        }
+    }
+}
+
+pub proof fn lemma_always_all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests(self, spec: TempPred<ClusterState>)
+    requires
+        spec.entails(lift_state(self.init())),
+        spec.entails(always(lift_action(self.next()))),
+    ensures spec.entails(always(lift_state(Self::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests()))),
+{
+    let inv = Self::all_requests_from_builtin_controllers_are_api_delete_or_update_or_update_status_requests();
+    assert forall |s, s_prime| inv(s) && #[trigger] self.next()(s, s_prime) implies inv(s_prime) by {
+        assert forall |msg: Message| {
+            &&& #[trigger] s_prime.in_flight().contains(msg)
+            &&& msg.src is BuiltinController
+        } implies {
+            &&& msg.dst is APIServer
+            &&& msg.content.is_delete_request() || msg.content.is_update_request() || msg.content.is_update_status_request()
         } by {
-            if s.in_flight().contains(msg) {} else {}
+            if s.in_flight().contains(msg) {} else {
+                let step = choose |step| self.next_step(s, s_prime, step);
+                if step is DuplicateReqStep {
+                    // The copy has the same src and content as the request in flight.
+                    assert(s.in_flight().contains(step->DuplicateReqStep_0));
+                }
+            }
         }
     };
     init_invariant(spec, self.init(), self.next(), inv);
@@ -16,7 +16,7 @@ pub open spec fn no_req_before_rpc_id_is_in_flight(rpc_id: RPCId) -> StatePred<C
 }
 
 // similar to no_pending_request_to_api_server_from_api_server_or_external,
-// but forbids messages from PodMonkey
+// but forbids messages from PodMonkey and NodeMonkey
 pub open spec fn no_pending_request_to_api_server_from_non_controllers() -> StatePred<ClusterState> {
     |s: ClusterState| {
         forall |msg: Message| !{
@@ -35,7 +35,9 @@ pub proof fn lemma_true_leads_to_always_no_pending_request_to_api_server_from_no
         spec.entails(always(lift_action(self.next()))),
         spec.entails(tla_forall(|i| self.api_server_next().weak_fairness(i))),
         spec.entails(always(lift_state(Cluster::req_drop_disabled()))),
+        spec.entails(always(lift_state(Cluster::req_dup_disabled()))),
         spec.entails(always(lift_state(Cluster::pod_monkey_disabled()))),
+        spec.entails(always(lift_state(Cluster::node_failure_disabled()))),
         spec.entails(always(lift_state(Cluster::every_in_flight_msg_has_lower_id_than_allocator()))),
     ensures spec.entails(true_pred().leads_to(always(lift_state(Self::no_pending_request_to_api_server_from_non_controllers())))),
 {
@@ -47,14 +49,18 @@ pub proof fn lemma_true_leads_to_always_no_pending_request_to_api_server_from_no
     let stronger_next = |s: ClusterState, s_prime: ClusterState| {
         &&& self.next()(s, s_prime)
         &&& Cluster::req_drop_disabled()(s)
+        &&& Cluster::req_dup_disabled()(s)
         &&& Cluster::pod_monkey_disabled()(s)
+        &&& Cluster::node_failure_disabled()(s)
     };
     invariant_n!(
         spec, lift_action(stronger_next),
         lift_action(Cluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(self.next()),
         lift_state(Cluster::req_drop_disabled()),
-        lift_state(Cluster::pod_monkey_disabled())
+        lift_state(Cluster::req_dup_disabled()),
+        lift_state(Cluster::pod_monkey_disabled()),
+        lift_state(Cluster::node_failure_disabled())
     );
     self.lemma_true_leads_to_always_every_in_flight_req_msg_satisfies(spec, requirements);
     temp_pred_equality(
@@ -442,6 +448,20 @@ proof fn pending_requests_num_decreases(self, spec: TempPred<ClusterState>, rpc_
                     assert(pending_req_multiset =~= pending_req_multiset_prime);
                 }
             },
+            Step::CachedReadStep(input) => {
+                if pending_req_multiset.count(input) > 0 {
+                    assert(pending_req_multiset.remove(input) =~= pending_req_multiset_prime);
+                } else {
+                    assert(pending_req_multiset =~= pending_req_multiset_prime);
+                }
+            },
+            Step::DropRespStep(input) => {
+                assert(pending_req_multiset =~= pending_req_multiset_prime);
+            },
+            Step::DuplicateReqStep(input) => {
+                // The copy has a new rpc_id that is no smaller than rpc_id.
+                assert(pending_req_multiset =~= pending_req_multiset_prime);
+            },
             Step::BuiltinControllersStep(input) => {
                 assert(pending_req_multiset =~= pending_req_multiset_prime);
             },
@@ -454,6 +474,9 @@ proof fn pending_requests_num_decreases(self, spec: TempPred<ClusterState>, rpc_
             Step::ExternalStep(input) => {
                 assert(pending_req_multiset =~= pending_req_multiset_prime);
             },
+            Step::ExternalFailureStep(input) => {
+                assert(pending_req_multiset =~= pending_req_multiset_prime);
+            },
             _ => {}
         }
     }
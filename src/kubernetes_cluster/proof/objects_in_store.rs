@@ -142,6 +142,7 @@ pub proof fn lemma_always_each_builtin_object_in_etcd_is_well_formed(self, spec:
                             APIRequest::GetThenDeleteRequest(_) => {}
                             APIRequest::GetThenUpdateRequest(_) => {}
                             APIRequest::GetThenUpdateStatusRequest(_) => {}
+                            APIRequest::PatchRequest(_) => {}
                         }
                     }
                     _ => {}
@@ -171,6 +172,19 @@ pub proof fn lemma_always_each_builtin_object_in_etcd_is_well_formed(self, spec:
                             APIRequest::GetThenDeleteRequest(_) => {}
                             APIRequest::GetThenUpdateRequest(_) => {}
                             APIRequest::GetThenUpdateStatusRequest(_) => {}
+                            APIRequest::PatchRequest(_) => {
+                                // A server-side apply of an absent object creates it
+                                ConfigMapView::marshal_status_preserves_integrity();
+                                DaemonSetView::marshal_status_preserves_integrity();
+                                PersistentVolumeClaimView::marshal_status_preserves_integrity();
+                                PodView::marshal_status_preserves_integrity();
+                                RoleBindingView::marshal_status_preserves_integrity();
+                                RoleView::marshal_status_preserves_integrity();
+                                SecretView::marshal_status_preserves_integrity();
+                                ServiceView::marshal_status_preserves_integrity();
+                                StatefulSetView::marshal_status_preserves_integrity();
+                                ServiceAccountView::marshal_status_preserves_integrity();
+                            }
                         }
                     }
                     _ => {}
@@ -233,6 +247,7 @@ pub proof fn lemma_always_each_custom_object_in_etcd_is_well_formed<T: CustomRes
                             }
                             APIRequest::GetThenUpdateRequest(_) => {}
                             APIRequest::GetThenUpdateStatusRequest(_) => {}
+                            APIRequest::PatchRequest(_) => {}
                         }
                     }
                     _ => {}
@@ -253,6 +268,10 @@ pub proof fn lemma_always_each_custom_object_in_etcd_is_well_formed<T: CustomRes
                             APIRequest::GetThenDeleteRequest(_) => {}
                             APIRequest::GetThenUpdateRequest(_) => {}
                             APIRequest::GetThenUpdateStatusRequest(_) => {}
+                            APIRequest::PatchRequest(_) => {
+                                // A server-side apply of an absent object creates it
+                                T::marshal_status_preserves_integrity();
+                            }
                         }
                     }
                     _ => {}
@@ -323,6 +342,88 @@ pub proof fn lemma_always_each_object_in_etcd_has_at_most_one_controller_owner(s
     );
 }
 
+
+// Each object in the cache of a controller was stored in etcd before (see Cluster::sync_cache):
+// it is weakly well formed, and if etcd still has an object of the same key with the same
+// resource version, the two objects are the same.
+pub open spec fn each_object_in_cache_was_in_etcd(self) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        forall |controller_id: int, key: ObjectRef|
+            #[trigger] self.controller_models.contains_key(controller_id)
+            && s.cache(controller_id) is Some
+            && #[trigger] s.cache(controller_id)->0.contains_key(key)
+                ==> {
+                    let obj = s.cache(controller_id)->0[key];
+                    &&& obj.metadata.well_formed_for_namespaced()
+                    &&& obj.object_ref() == key
+                    &&& obj.metadata.resource_version->0 < s.api_server.resource_version_counter
+                    &&& obj.metadata.uid->0 < s.api_server.uid_counter
+                    &&& s.resources().contains_key(key) && s.resources()[key].metadata.resource_version == obj.metadata.resource_version
+                        ==> s.resources()[key] == obj
+                }
+    }
+}
+
+pub proof fn lemma_always_each_object_in_cache_was_in_etcd(self, spec: TempPred<ClusterState>)
+    requires
+        spec.entails(lift_state(self.init())),
+        spec.entails(always(lift_action(self.next()))),
+    ensures spec.entails(always(lift_state(self.each_object_in_cache_was_in_etcd()))),
+{
+    let invariant = self.each_object_in_cache_was_in_etcd();
+    let stronger_next = |s: ClusterState, s_prime: ClusterState| {
+        &&& self.next()(s, s_prime)
+        &&& Self::each_object_in_etcd_is_weakly_well_formed()(s)
+    };
+    self.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
+    combine_spec_entails_always_n!(
+        spec,
+        lift_action(stronger_next),
+        lift_action(self.next()),
+        lift_state(Self::each_object_in_etcd_is_weakly_well_formed())
+    );
+    assert forall |s, s_prime| invariant(s) && #[trigger] stronger_next(s, s_prime) implies invariant(s_prime) by {
+        assert forall |controller_id: int, key: ObjectRef|
+            #[trigger] self.controller_models.contains_key(controller_id)
+            && s_prime.cache(controller_id) is Some
+            && #[trigger] s_prime.cache(controller_id)->0.contains_key(key)
+        implies {
+            let obj = s_prime.cache(controller_id)->0[key];
+            &&& obj.metadata.well_formed_for_namespaced()
+            &&& obj.object_ref() == key
+            &&& obj.metadata.resource_version->0 < s_prime.api_server.resource_version_counter
+            &&& obj.metadata.uid->0 < s_prime.api_server.uid_counter
+            &&& s_prime.resources().contains_key(key) && s_prime.resources()[key].metadata.resource_version == obj.metadata.resource_version
+                ==> s_prime.resources()[key] == obj
+        } by {
+            let step = choose |step| self.next_step(s, s_prime, step);
+            match step {
+                Step::SyncCacheStep(input) => {
+                    if input == controller_id {
+                        assert(s_prime.cache(controller_id)->0 == s.resources());
+                        assert(Self::etcd_object_is_weakly_well_formed(key)(s));
+                    } else {
+                        assert(s_prime.cache(controller_id) == s.cache(controller_id));
+                    }
+                },
+                Step::APIServerStep(input) => {
+                    let obj = s.cache(controller_id)->0[key];
+                    assert(s_prime.cache(controller_id) == s.cache(controller_id));
+                    if s_prime.resources().contains_key(key) && (!s.resources().contains_key(key) || s.resources()[key] != s_prime.resources()[key]) {
+                        // The object written to etcd gets a new resource version, which no object in the cache has
+                        assert(s_prime.resources()[key].metadata.resource_version->0 == s.api_server.resource_version_counter);
+                        assert(s_prime.resources()[key].metadata.resource_version != obj.metadata.resource_version);
+                    }
+                },
+                _ => {
+                    assert(s_prime.cache(controller_id) == s.cache(controller_id));
+                }
+            }
+        }
+    }
+    init_invariant(spec, self.init(), stronger_next, invariant);
+}
+
 }
 
 }
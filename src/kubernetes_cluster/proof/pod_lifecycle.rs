@@ -0,0 +1,110 @@
+use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::kubernetes_cluster::spec::{
+    builtin_controllers::pod_lifecycle::*, builtin_controllers::types::*, cluster::*, message::*,
+};
+use verus_temporal_logic::{defs::*, rules::*};
+use vstd::prelude::*;
+
+verus! {
+
+impl Cluster {
+
+// pod_is_running_and_not_ready says that the pod at key is running on an existing node,
+// but the kubelet has not reported it as ready yet (see mark_pod_ready).
+pub open spec fn pod_is_running_and_not_ready(key: ObjectRef) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        let pod = PodView::unmarshal(s.resources()[key])->Ok_0;
+        &&& pod_exists_at(s.resources(), key)
+        &&& pod_is_on_existing_node(s.resources(), pod)
+        &&& pod.phase() == pod_running_phase()
+        &&& !pod.is_ready()
+        &&& pod.metadata.deletion_timestamp is None
+    }
+}
+
+// pod_containers_are_ready says that the kubelet reports every container of pod as started and ready.
+pub open spec fn pod_containers_are_ready(pod: PodView) -> bool {
+    let container_statuses = pod.status->0.container_statuses->0;
+    &&& pod.status is Some
+    &&& pod.status->0.container_statuses is Some
+    &&& container_statuses.len() == pod.spec->0.containers.len()
+    &&& forall |i| #![trigger container_statuses[i]] 0 <= i < container_statuses.len()
+        ==> container_statuses[i].ready && container_statuses[i].started == Some(true)
+}
+
+// kubelet_reports_pod_ready says that the kubelet has sent the request that updates the status of
+// the pod at key to Ready, with the Ready condition set and all the containers ready.
+pub open spec fn kubelet_reports_pod_ready(key: ObjectRef) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        exists |msg: Message| {
+            let pod = PodView::unmarshal(msg.content.get_update_status_request().obj)->Ok_0;
+            &&& #[trigger] s.in_flight().contains(msg)
+            &&& update_status_msg_from_bc_for(key)(msg)
+            &&& PodView::unmarshal(msg.content.get_update_status_request().obj) is Ok
+            &&& pod.is_ready()
+            &&& Self::pod_containers_are_ready(pod)
+        }
+    }
+}
+
+// A running pod is eventually reported as ready by the kubelet, unless the pod stops running on an existing node
+// (e.g., it fails, is deleted, or its node is gone) or is reported as ready by someone else in the meantime.
+// This follows from the weak fairness of the builtin controllers action (specifically, the kubelet).
+pub proof fn lemma_pod_is_running_and_not_ready_leads_to_kubelet_reports_pod_ready(
+    self, spec: TempPred<ClusterState>, key: ObjectRef
+)
+    requires
+        spec.entails(always(lift_action(self.next()))),
+        spec.entails(tla_forall(|i| self.builtin_controllers_next().weak_fairness(i))),
+    ensures
+        spec.entails(lift_state(Self::pod_is_running_and_not_ready(key)).leads_to(
+            lift_state(|s: ClusterState| !Self::pod_is_running_and_not_ready(key)(s) || Self::kubelet_reports_pod_ready(key)(s))
+        )),
+{
+    let pre = Self::pod_is_running_and_not_ready(key);
+    let post = |s: ClusterState| !Self::pod_is_running_and_not_ready(key)(s) || Self::kubelet_reports_pod_ready(key)(s);
+    let input = (BuiltinControllerChoice::Kubelet, key);
+
+    assert forall |s, s_prime| pre(s) && #[trigger] self.next()(s, s_prime) && self.builtin_controllers_next().forward(input)(s, s_prime) implies post(s_prime) by {
+        let pod = PodView::unmarshal(s.resources()[key])->Ok_0;
+        let action_input = BuiltinControllersActionInput {
+            choice: input.0,
+            key: key,
+            rpc_id_allocator: s.rpc_id_allocator,
+            resources: s.api_server.resources,
+        };
+        // start_pod is disabled as the pod is not pending, so the kubelet marks the pod as ready
+        assert(!(start_pod().precondition)(action_input, ()));
+        assert((mark_pod_ready().precondition)(action_input, ()));
+        let ready_pod = pod.with_status(pod_status_with(pod, pod_running_phase(), true, true));
+        let update_status_req_msg = built_in_controller_req_msg(
+            s.rpc_id_allocator.allocate().1,
+            pod_status_updated_req_msg_content(key, pod, pod_status_with(pod, pod_running_phase(), true, true))
+        );
+        assert(s_prime.in_flight().contains(update_status_req_msg));
+        PodView::marshal_preserves_integrity();
+        assert(PodView::unmarshal(update_status_req_msg.content.get_update_status_request().obj)->Ok_0 == ready_pod);
+        assert(ready_pod.status->0.conditions->0[0].type_ == pod_ready_condition());
+        assert(ready_pod.is_ready());
+        assert(Self::pod_containers_are_ready(ready_pod));
+        assert(Self::kubelet_reports_pod_ready(key)(s_prime));
+    }
+
+    assert forall |s| #[trigger] pre(s) implies self.builtin_controllers_action_pre(BuiltinControllersStep::MarkPodReady, input)(s) by {
+        let action_input = BuiltinControllersActionInput {
+            choice: input.0,
+            key: key,
+            rpc_id_allocator: s.rpc_id_allocator,
+            resources: s.api_server.resources,
+        };
+        assert((mark_pod_ready().precondition)(action_input, ()));
+    }
+
+    self.lemma_pre_leads_to_post_by_builtin_controllers(
+        spec, input, self.next(), BuiltinControllersStep::MarkPodReady, pre, post
+    );
+}
+
+}
+
+}
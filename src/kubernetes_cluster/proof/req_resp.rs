@@ -32,11 +32,14 @@ pub proof fn lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(sel
     let next = |s, s_prime| {
         &&& self.next()(s, s_prime)
         &&& Self::each_object_in_etcd_is_weakly_well_formed()(s)
+        &&& self.each_object_in_cache_was_in_etcd()(s)
     };
     self.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
+    self.lemma_always_each_object_in_cache_was_in_etcd(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(self.next()),
-        lift_state(Self::each_object_in_etcd_is_weakly_well_formed())
+        lift_state(Self::each_object_in_etcd_is_weakly_well_formed()),
+        lift_state(self.each_object_in_cache_was_in_etcd())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
         assert forall |msg| s_prime.in_flight().contains(msg) && #[trigger] is_ok_get_response_msg()(msg)
@@ -47,6 +50,15 @@ pub proof fn lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(sel
             let step = choose |step| self.next_step(s, s_prime, step);
             if s.in_flight().contains(msg) {
                 assert(s.api_server.resource_version_counter <= s_prime.api_server.resource_version_counter);
+            } else if step is CachedReadStep {
+                let input = step->CachedReadStep_0;
+                let cache = s.cache(input.src->Controller_0)->0;
+                let req_key = input.content.get_get_request().key;
+                assert(cache.contains_key(req_key));
+                assert(msg.content.get_get_response().res->Ok_0 == cache[req_key]);
+            } else if step is DropRespStep || step is DuplicateReqStep {
+                // The only new message is an error response or a request
+                assert(!is_ok_get_response_msg()(msg));
             } else {
                 let input = step->APIServerStep_0->0;
                 match input.content->APIRequest_0 {
@@ -88,12 +100,15 @@ pub proof fn lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sel
         &&& self.next()(s, s_prime)
         &&& Self::each_object_in_etcd_is_weakly_well_formed()(s)
         &&& Self::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
+        &&& self.each_object_in_cache_was_in_etcd()(s)
     };
     self.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
     self.lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(spec);
+    self.lemma_always_each_object_in_cache_was_in_etcd(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(self.next()), lift_state(Self::each_object_in_etcd_is_weakly_well_formed()),
-        lift_state(Self::object_in_ok_get_response_has_smaller_rv_than_etcd())
+        lift_state(Self::object_in_ok_get_response_has_smaller_rv_than_etcd()),
+        lift_state(self.each_object_in_cache_was_in_etcd())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
         assert forall |msg| #[trigger] s_prime.in_flight().contains(msg)
@@ -102,12 +117,22 @@ pub proof fn lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sel
             && s_prime.resources()[key].metadata.resource_version->0 == msg.content.get_get_response().res->Ok_0.metadata.resource_version->0
         implies s_prime.resources()[key] == msg.content.get_get_response().res->Ok_0 by {
             assert(is_ok_get_response_msg()(msg));
+            let step = choose |step| self.next_step(s, s_prime, step);
             if s.in_flight().contains(msg) {
                 if !s.resources().contains_key(key) || s.resources()[key] != s_prime.resources()[key] {
                     assert(s_prime.resources()[key].metadata.resource_version->0 != msg.content.get_get_response().res->Ok_0.metadata.resource_version->0)
                 }
+            } else if step is CachedReadStep {
+                // The object is read from the cache, so it is the same as the object in etcd if it has the same rv
+                let req = step->CachedReadStep_0;
+                let cache = s.cache(req.src->Controller_0)->0;
+                assert(cache.contains_key(req.content.get_get_request().key));
+                assert(msg.content.get_get_response().res->Ok_0 == cache[req.content.get_get_request().key]);
+                assert(s.api_server == s_prime.api_server);
+            } else if step is DropRespStep || step is DuplicateReqStep {
+                // The only new message is an error response or a request
+                assert(!is_ok_get_response_msg()(msg));
             } else {
-                let step = choose |step| self.next_step(s, s_prime, step);
                 assert(step is APIServerStep);
                 let req = step->APIServerStep_0->0;
                 match req.content->APIRequest_0 {
@@ -120,6 +145,7 @@ pub proof fn lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sel
                     APIRequest::GetThenDeleteRequest(_) => {}
                     APIRequest::GetThenUpdateRequest(_) => {}
                     APIRequest::GetThenUpdateStatusRequest(_) => {}
+                    APIRequest::PatchRequest(_) => {}
                 }
                 assert(msg == handle_get_request_msg(req, s.api_server).1);
                 assert(s.resources().contains_key(req.content.get_get_request().key));
@@ -164,18 +190,21 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_get_resp_message_is_same_a
         &&& Self::every_in_flight_req_msg_has_different_id_from_pending_req_msg_of(controller_id, key)(s)
         &&& Self::every_in_flight_msg_has_unique_id()(s)
         &&& Self::there_is_the_controller_state(controller_id)(s)
+        &&& self.each_object_in_cache_was_in_etcd()(s)
     };
     self.lemma_always_each_object_in_etcd_is_weakly_well_formed(spec);
     self.lemma_always_every_in_flight_msg_has_lower_id_than_allocator(spec);
     self.lemma_always_every_in_flight_req_msg_has_different_id_from_pending_req_msg_of(spec, controller_id, key);
     self.lemma_always_every_in_flight_msg_has_unique_id(spec);
     self.lemma_always_there_is_the_controller_state(spec, controller_id);
+    self.lemma_always_each_object_in_cache_was_in_etcd(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(self.next()), lift_state(Self::each_object_in_etcd_is_weakly_well_formed()),
         lift_state(Self::every_in_flight_msg_has_lower_id_than_allocator()),
         lift_state(Self::every_in_flight_req_msg_has_different_id_from_pending_req_msg_of(controller_id, key)),
         lift_state(Self::every_in_flight_msg_has_unique_id()),
-        lift_state(Self::there_is_the_controller_state(controller_id))
+        lift_state(Self::there_is_the_controller_state(controller_id)),
+        lift_state(self.each_object_in_cache_was_in_etcd())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
         assert forall |msg| #[trigger] s_prime.in_flight().contains(msg)
@@ -214,6 +243,19 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_get_resp_message_is_same_a
                         assert(is_ok_get_response_msg_and_matches_key(req_key)(msg));
                     }
                 },
+                Step::CachedReadStep(input) => {
+                    assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
+                    if !s.in_flight().contains(msg) {
+                        let cache = s.cache(input.src->Controller_0)->0;
+                        assert(msg.content.is_get_response());
+                        assert(input == s.ongoing_reconciles(controller_id)[key].pending_req_msg->0);
+                        if msg.content.get_get_response().res is Ok {
+                            assert(cache.contains_key(req_key));
+                            assert(cache[req_key].object_ref() == req_key);
+                        }
+                        assert(is_ok_get_response_msg_and_matches_key(req_key)(msg));
+                    }
+                },
                 Step::DropReqStep(input) => {
                     assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
                     if !s.in_flight().contains(msg) {
@@ -223,10 +265,23 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_get_resp_message_is_same_a
                     }
                     assert(is_ok_get_response_msg_and_matches_key(req_key)(msg));
                 },
+                Step::DropRespStep(input) => {
+                    assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
+                    if !s.in_flight().contains(msg) {
+                        assert(msg.src is APIServer);
+                        assert(msg.content.is_get_response());
+                        assert(msg.content.get_get_response().res is Err);
+                    }
+                    assert(is_ok_get_response_msg_and_matches_key(req_key)(msg));
+                },
                 Step::ExternalStep(input) => {
                     assert(input.1->0 != msg);
                     assert(s.in_flight().contains(msg));
                 },
+                Step::ExternalFailureStep(input) => {
+                    assert(input.0 != msg);
+                    assert(s.in_flight().contains(msg));
+                },
                 _ => {
                     assert(s.in_flight().contains(msg));
                     assert(is_ok_get_response_msg_and_matches_key(req_key)(msg));
@@ -319,6 +374,14 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_update_resp_message_is_sam
                         assert(is_ok_update_response_msg_and_matches_key(req_key)(msg));
                     }
                 },
+                Step::CachedReadStep(input) => {
+                    assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
+                    if !s.in_flight().contains(msg) {
+                        // The only new message is a get or list response
+                        assert(msg.content.is_get_response() || msg.content.is_list_response());
+                    }
+                    assert(s.in_flight().contains(msg));
+                },
                 Step::DropReqStep(input) => {
                     assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
                     if !s.in_flight().contains(msg) {
@@ -328,10 +391,23 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_update_resp_message_is_sam
                     }
                     assert(is_ok_update_response_msg_and_matches_key(req_key)(msg));
                 },
+                Step::DropRespStep(input) => {
+                    assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
+                    if !s.in_flight().contains(msg) {
+                        assert(msg.src is APIServer);
+                        assert(msg.content.is_update_response());
+                        assert(msg.content.get_update_response().res is Err);
+                    }
+                    assert(is_ok_update_response_msg_and_matches_key(req_key)(msg));
+                },
                 Step::ExternalStep(input) => {
                     assert(input.1->0 != msg);
                     assert(s.in_flight().contains(msg));
                 },
+                Step::ExternalFailureStep(input) => {
+                    assert(input.0 != msg);
+                    assert(s.in_flight().contains(msg));
+                },
                 _ => {
                     assert(s.in_flight().contains(msg));
                     assert(is_ok_update_response_msg_and_matches_key(req_key)(msg));
@@ -426,6 +502,14 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_create_resp_message_is_sam
                         assert(is_ok_create_response_msg_and_matches_key(req_key)(msg));
                     }
                 },
+                Step::CachedReadStep(input) => {
+                    assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
+                    if !s.in_flight().contains(msg) {
+                        // The only new message is a get or list response
+                        assert(msg.content.is_get_response() || msg.content.is_list_response());
+                    }
+                    assert(s.in_flight().contains(msg));
+                },
                 Step::DropReqStep(input) => {
                     assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
                     if !s.in_flight().contains(msg) {
@@ -435,11 +519,25 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_create_resp_message_is_sam
                     }
                     assert(is_ok_create_response_msg_and_matches_key(req_key)(msg));
                 },
+                Step::DropRespStep(input) => {
+                    assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
+                    if !s.in_flight().contains(msg) {
+                        assert(msg.src is APIServer);
+                        assert(msg.content.is_create_response());
+                        assert(msg.content.get_create_response().res is Err);
+                    }
+                    assert(is_ok_create_response_msg_and_matches_key(req_key)(msg));
+                },
                 Step::ExternalStep(input) => {
                     assert(input.1->0 != msg);
                     assert(s.in_flight().contains(msg));
                     assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
                 },
+                Step::ExternalFailureStep(input) => {
+                    assert(input.0 != msg);
+                    assert(s.in_flight().contains(msg));
+                    assert(s.ongoing_reconciles(controller_id)[key] == s_prime.ongoing_reconciles(controller_id)[key]);
+                },
                 _ => {
                     assert(s.in_flight().contains(msg));
                     assert(is_ok_create_response_msg_and_matches_key(req_key)(msg));
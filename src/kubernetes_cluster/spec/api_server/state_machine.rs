@@ -15,8 +15,11 @@ verus! {
 //
 // The datastore provides a key-value store interface to store all the state objects. For each
 // state object, the key is a tuple of its name, namespace and kind, and the value is the entire
-// object. Besides name, namespace and kind, each object has other metadata fields including:
+// object. A cluster-scoped object (e.g., a custom resource whose CRD has the Cluster scope) does not
+// belong to any namespace, so its namespace is None and its key has the empty namespace (see cluster_scope_namespace).
+// Besides name, namespace and kind, each object has other metadata fields including:
 // * a resource_version that tracks the revision of this object
+// * a generation that tracks the revision of the spec of this object
 // * a uid that uniquely distinguishes an object from its historical occurrences
 // * so on...
 //
@@ -52,9 +55,9 @@ verus! {
 // list to gradually improve the model.
 //
 // The TODO list:
-// + Support more expressive list operation
+// + Support more expressive list operation (e.g., resourceVersion and resourceVersionMatch)
 //
-// + Model Patch (if needed)
+// + Model deletion of fields by null values in merge patches, and field ownership conflicts of server-side apply
 //
 // + Model uniqueness of generated name using spec ensures (when supported)
 //
@@ -64,10 +67,6 @@ verus! {
 //
 // + Model graceful deletion
 //
-// + Model foreground and orphan deletion options
-//
-// + Support cluster-wide state objects (the ones that don't belong to a namespace)
-//
 // + Keep the error code consistent with the real API Server
 //
 // + Document intended mismatch between the model and the real API server
@@ -77,6 +76,8 @@ pub open spec fn unmarshallable_spec(obj: DynamicObjectView, installed_types: In
     match obj.kind {
         Kind::ConfigMapKind => ConfigMapView::unmarshal_spec(obj.spec) is Ok,
         Kind::DaemonSetKind => DaemonSetView::unmarshal_spec(obj.spec) is Ok,
+        Kind::EventKind => EventView::unmarshal_spec(obj.spec) is Ok,
+        Kind::NodeKind => NodeView::unmarshal_spec(obj.spec) is Ok,
         Kind::PersistentVolumeClaimKind => PersistentVolumeClaimView::unmarshal_spec(obj.spec) is Ok,
         Kind::PodKind => PodView::unmarshal_spec(obj.spec) is Ok,
         Kind::RoleBindingKind => RoleBindingView::unmarshal_spec(obj.spec) is Ok,
@@ -94,6 +95,8 @@ pub open spec fn unmarshallable_status(obj: DynamicObjectView, installed_types:
     match obj.kind {
         Kind::ConfigMapKind => ConfigMapView::unmarshal_status(obj.status) is Ok,
         Kind::DaemonSetKind => DaemonSetView::unmarshal_status(obj.status) is Ok,
+        Kind::EventKind => EventView::unmarshal_status(obj.status) is Ok,
+        Kind::NodeKind => NodeView::unmarshal_status(obj.status) is Ok,
         Kind::PersistentVolumeClaimKind => PersistentVolumeClaimView::unmarshal_status(obj.status) is Ok,
         Kind::PodKind => PodView::unmarshal_status(obj.status) is Ok,
         Kind::RoleBindingKind => RoleBindingView::unmarshal_status(obj.status) is Ok,
@@ -134,6 +137,8 @@ pub open spec fn valid_object(obj: DynamicObjectView, installed_types: Installed
     match obj.kind {
         Kind::ConfigMapKind => ConfigMapView::unmarshal(obj)->Ok_0.state_validation(),
         Kind::DaemonSetKind => DaemonSetView::unmarshal(obj)->Ok_0.state_validation(),
+        Kind::EventKind => EventView::unmarshal(obj)->Ok_0.state_validation(),
+        Kind::NodeKind => NodeView::unmarshal(obj)->Ok_0.state_validation(),
         Kind::PersistentVolumeClaimKind => PersistentVolumeClaimView::unmarshal(obj)->Ok_0.state_validation(),
         Kind::PodKind => PodView::unmarshal(obj)->Ok_0.state_validation(),
         Kind::RoleBindingKind => RoleBindingView::unmarshal(obj)->Ok_0.state_validation(),
@@ -158,6 +163,8 @@ pub open spec fn valid_transition(obj: DynamicObjectView, old_obj: DynamicObject
     match obj.kind {
         Kind::ConfigMapKind => ConfigMapView::unmarshal(obj)->Ok_0.transition_validation(ConfigMapView::unmarshal(old_obj)->Ok_0),
         Kind::DaemonSetKind => DaemonSetView::unmarshal(obj)->Ok_0.transition_validation(DaemonSetView::unmarshal(old_obj)->Ok_0),
+        Kind::EventKind => EventView::unmarshal(obj)->Ok_0.transition_validation(EventView::unmarshal(old_obj)->Ok_0),
+        Kind::NodeKind => NodeView::unmarshal(obj)->Ok_0.transition_validation(NodeView::unmarshal(old_obj)->Ok_0),
         Kind::PersistentVolumeClaimKind => PersistentVolumeClaimView::unmarshal(obj)->Ok_0.transition_validation(PersistentVolumeClaimView::unmarshal(old_obj)->Ok_0),
         Kind::PodKind => PodView::unmarshal(obj)->Ok_0.transition_validation(PodView::unmarshal(old_obj)->Ok_0),
         Kind::RoleBindingKind => RoleBindingView::unmarshal(obj)->Ok_0.transition_validation(RoleBindingView::unmarshal(old_obj)->Ok_0),
@@ -182,6 +189,8 @@ pub open spec fn marshalled_default_status(kind: Kind, installed_types: Installe
     match kind {
         Kind::ConfigMapKind => ConfigMapView::marshal_status(ConfigMapView::default().status()),
         Kind::DaemonSetKind => DaemonSetView::marshal_status(DaemonSetView::default().status()),
+        Kind::EventKind => EventView::marshal_status(EventView::default().status()),
+        Kind::NodeKind => NodeView::marshal_status(NodeView::default().status()),
         Kind::PersistentVolumeClaimKind => PersistentVolumeClaimView::marshal_status(PersistentVolumeClaimView::default().status()),
         Kind::PodKind => PodView::marshal_status(PodView::default().status()),
         Kind::RoleBindingKind => RoleBindingView::marshal_status(RoleBindingView::default().status()),
@@ -205,15 +214,100 @@ pub open spec fn handle_get_request(req: GetRequest, s: APIServerState) -> GetRe
     }
 }
 
-#[verifier(inline)]
-pub open spec fn handle_list_request(req: ListRequest, s: APIServerState) -> ListResponse {
-    // s.resources.values() returns the set of objects in s.resources
+// listed_objects returns the objects in resources that are selected by req.
+pub open spec fn listed_objects(req: ListRequest, resources: StoredState) -> Seq<DynamicObjectView> {
+    // resources.values() returns the set of objects in resources
     // This will not make list return fewer number of objects because
     // each object is unique in terms of {name, namespace, kind}
-    ListResponse{res: Ok(s.resources.values().filter(|o: DynamicObjectView| {
+    resources.values().filter(|o: DynamicObjectView| {
         &&& o.object_ref().namespace == req.namespace
         &&& o.object_ref().kind == req.kind
-    }).to_seq())}
+        &&& req.selects(o)
+    }).to_seq()
+}
+
+// The continue token of a paginated list encodes the resource version of the snapshot that the list is served from
+// and the offset where the next page starts. The encoding is opaque to the controllers; the model only requires that
+// the API server can parse the tokens it has issued.
+pub uninterp spec fn continue_token(resource_version: ResourceVersion, offset: nat) -> StringView;
+
+pub uninterp spec fn parse_continue_token(token: StringView) -> Option<(ResourceVersion, nat)>;
+
+#[verifier(external_body)] // TRUSTED
+pub proof fn continue_token_spec(resource_version: ResourceVersion, offset: nat)
+    ensures parse_continue_token(continue_token(resource_version, offset)) == Some((resource_version, offset)),
+{}
+
+// NOTE: etcd compacts the history of the resources from time to time, after which the snapshots at the compacted
+// resource versions are gone and the lists continuing from them fail with Expired (410 Gone).
+// When the compaction happens is out of the control of the controllers, so it is left uninterpreted.
+pub uninterp spec fn list_snapshot_compacted(s: APIServerState, resource_version: ResourceVersion) -> bool;
+
+// list_page returns the page of objs starting at offset with at most limit objects, and the continue token
+// for the next page if there are objects left after the page.
+pub open spec fn list_page(req: ListRequest, objs: Seq<DynamicObjectView>, resource_version: ResourceVersion, offset: nat) -> ListResponse {
+    let start = if offset <= objs.len() { offset } else { objs.len() };
+    let end = if req.limit is Some && req.limit->0 > 0 && start + req.limit->0 < objs.len() {
+        (start + req.limit->0) as int
+    } else {
+        objs.len() as int
+    };
+    ListResponse{
+        res: Ok(objs.subrange(start as int, end)),
+        continue_token: if end < objs.len() { Some(continue_token(resource_version, end as nat)) } else { None },
+    }
+}
+
+// A list without limit and continue_token returns all the selected objects in one response.
+//
+// A paginated list is served in pages: the first page is listed from the latest resources, and the API server keeps
+// the resources at the current resource version as a snapshot if there are more pages. Each later page is listed
+// from the snapshot that the continue_token refers to, so all the pages together are consistent with the resources
+// at the time of the first page, even if the resources have been changed in between. If the snapshot has been
+// compacted, the list fails with Expired and the controller has to list again from the first page, which is then
+// served from the latest resources.
+//
+// A Cached list is always served in one page, ignoring limit, like a list with resourceVersion=0 that is
+// served from the watch cache of the API server. A Cached list cannot continue a paginated list.
+#[verifier(inline)]
+pub open spec fn handle_list_request(req: ListRequest, s: APIServerState) -> (APIServerState, ListResponse) {
+    if !req.field_selector_is_supported() {
+        // List fails because the field selector uses a field path that is not supported
+        (s, ListResponse{res: Err(APIError::BadRequest), continue_token: None})
+    } else if req.read_mode is Cached && req.continue_token is Some {
+        // List fails because a Cached list cannot continue a paginated list
+        (s, ListResponse{res: Err(APIError::BadRequest), continue_token: None})
+    } else if req.read_mode is Cached || !req.is_paginated() {
+        // List returns all the selected objects
+        (s, ListResponse{res: Ok(listed_objects(req, s.resources)), continue_token: None})
+    } else if req.continue_token is None {
+        // List returns the first page from the latest resources
+        let resp = list_page(req, listed_objects(req, s.resources), s.resource_version_counter, 0);
+        if resp.continue_token is Some {
+            // Keep the snapshot for the later pages
+            (APIServerState {
+                list_snapshots: s.list_snapshots.insert(s.resource_version_counter, s.resources),
+                ..s
+            }, resp)
+        } else {
+            (s, resp)
+        }
+    } else {
+        let position = parse_continue_token(req.continue_token->0);
+        if position is None {
+            // List fails because the continue token is malformed
+            (s, ListResponse{res: Err(APIError::BadRequest), continue_token: None})
+        } else {
+            let (resource_version, offset) = position->0;
+            if !s.list_snapshots.contains_key(resource_version) || list_snapshot_compacted(s, resource_version) {
+                // List fails because the snapshot is gone
+                (s, ListResponse{res: Err(APIError::Expired), continue_token: None})
+            } else {
+                // List returns the next page from the snapshot
+                (s, list_page(req, listed_objects(req, s.list_snapshots[resource_version]), resource_version, offset))
+            }
+        }
+    }
 }
 
 pub open spec fn create_request_admission_check(installed_types: InstalledTypes, req: CreateRequest, s: APIServerState) -> Option<APIError> {
@@ -223,6 +317,9 @@ pub open spec fn create_request_admission_check(installed_types: InstalledTypes,
     } else if req.obj.metadata.namespace is Some && req.namespace != req.obj.metadata.namespace->0 {
         // Creation fails because the namespace of the provided object does not match the namespace sent on the request
         Some(APIError::BadRequest)
+    } else if !kind_is_namespaced(installed_types, req.obj.kind) && req.namespace != cluster_scope_namespace() {
+        // Creation fails because a cluster-scoped object cannot be created in a namespace
+        Some(APIError::BadRequest)
     } else if !unmarshallable_object(req.obj, installed_types) {
         // Creation fails because the provided object is not well formed
         Some(APIError::BadRequest) // TODO: should the error be BadRequest?
@@ -285,9 +382,11 @@ pub open spec fn handle_create_request(installed_types: InstalledTypes, req: Cre
                 } else {
                     Some(generated_name(s, req.obj.metadata.generate_name.unwrap()))
                 },
-                namespace: Some(req.namespace), // Set namespace for new object
+                // Set namespace for new object, unless it is cluster-scoped
+                namespace: if kind_is_namespaced(installed_types, req.obj.kind) { Some(req.namespace) } else { None },
                 resource_version: Some(s.resource_version_counter), // Set rv for new object
                 uid: Some(s.uid_counter), // Set uid for new object
+                generation: Some(1), // Set generation for new object
                 deletion_timestamp: None, // Unset deletion timestamp for new object
                 ..req.obj.metadata
             },
@@ -353,10 +452,9 @@ pub open spec fn delete_request_admission_check(req: DeleteRequest, s: APIServer
 pub uninterp spec fn deletion_timestamp() -> StringView;
 
 // NOTE: Deletion has three modes including background (default), foreground, orphan.
-// For now, we only model background deletion and only allow our controllers to perform background deletion
-// (by hiding the mode option).
-// This is acceptable for this project because background deletion is enough for all the reconciliation tasks
-// performed by our controllers.
+// The API server only records the mode as a finalizer (foregroundDeletion or orphan) of the object
+// (see finalizers_for_propagation_policy), so that the object is kept until the garbage collector
+// handles its dependents and removes the finalizer (see builtin_controllers::garbage_collector).
 pub open spec fn handle_delete_request(req: DeleteRequest, s: APIServerState) -> (APIServerState, DeleteResponse) {
     if delete_request_admission_check(req, s) is Some {
         // Deletion fails.
@@ -364,7 +462,8 @@ pub open spec fn handle_delete_request(req: DeleteRequest, s: APIServerState) ->
     } else {
         // Deletion succeeds.
         let obj = s.resources[req.key];
-        if obj.metadata.finalizers is Some && obj.metadata.finalizers->0.len() > 0 {
+        let finalizers = finalizers_for_propagation_policy(obj, req.propagation_policy);
+        if finalizers is Some && finalizers->0.len() > 0 {
             // With the finalizer(s) in the object, we cannot immediately delete it from the key-value store.
             // Instead, we set the deletion timestamp of this object.
             // If the object already has a deletion timestamp, then skip.
@@ -377,12 +476,22 @@ pub open spec fn handle_delete_request(req: DeleteRequest, s: APIServerState) ->
             // because Pod implements CheckGracefulDelete (see https://github.com/kubernetes/kubernetes/blob/v1.30.0/pkg/registry/core/pod/strategy.go#L168).
             // This is irrelevant to application controllers that do not manage pods, and acceptable for verifying
             // low-level built-in controllers because they are supposed to treat terminating pods as non-existing pods.
-            if obj.metadata.deletion_timestamp is Some {
-                // A deletion timestamp is already set so no need to bother it.
+            if obj.metadata.deletion_timestamp is Some && finalizers == obj.metadata.finalizers {
+                // A deletion timestamp is already set and the propagation policy does not change,
+                // so no need to bother it.
                 (s, DeleteResponse{res: Ok(())})
             } else {
-                let stamped_obj_with_new_rv = obj.with_deletion_timestamp(deletion_timestamp())
-                                                 .with_resource_version(s.resource_version_counter);
+                // If the object already has a deletion timestamp, the API server only updates the finalizers
+                // for the new propagation policy.
+                let stamped_obj = if obj.metadata.deletion_timestamp is Some {
+                    obj
+                } else {
+                    obj.with_deletion_timestamp(deletion_timestamp())
+                };
+                let stamped_obj_with_new_rv = stamped_obj.with_metadata(ObjectMetaView {
+                    finalizers: finalizers,
+                    ..stamped_obj.metadata
+                }).with_resource_version(s.resource_version_counter);
                 (APIServerState {
                     // Here we use req.key, instead of stamped_obj.object_ref(), to insert to the map.
                     // This is intended because using stamped_obj.object_ref() will require us to use
@@ -399,8 +508,8 @@ pub open spec fn handle_delete_request(req: DeleteRequest, s: APIServerState) ->
             //
             // NOTE: In some very corner case, the API server *seems* to first updates the object (to update its finalizers)
             // and then deletes the object immediately, which makes the entire Delete operation not atomic.
-            // However, this only happens in the orphan or foreground deletion mode, so we do not model this
-            // seemingly non-atomic behavior for now.
+            // However, this only happens when a background deletion removes the orphan or foregroundDeletion finalizer
+            // of a terminating object, so we do not model this seemingly non-atomic behavior for now.
             // For more details, see how the API server updates the object in the middle of handling deletion requests:
             // https://github.com/kubernetes/kubernetes/blob/v1.30.0/staging/src/k8s.io/apiserver/pkg/registry/generic/registry/store.go#L1009
             (APIServerState {
@@ -412,6 +521,16 @@ pub open spec fn handle_delete_request(req: DeleteRequest, s: APIServerState) ->
     }
 }
 
+// kind_is_namespaced says whether the objects of kind belong to a namespace.
+// All the builtin kinds modeled so far except Node are namespaced, and the scope of a custom resource kind is given by its CRD.
+pub open spec fn kind_is_namespaced(installed_types: InstalledTypes, kind: Kind) -> bool {
+    match kind {
+        Kind::CustomResourceKind(string) => installed_types[string].scope is Namespaced,
+        Kind::NodeKind => false,
+        _ => true,
+    }
+}
+
 // Unconditional update means one can update the object without providing a resource version.
 // For all the supported kinds, unconditional update is disallowed for CustomResource only.
 // Note that if the resource version is provided, it has to be the correct one.
@@ -469,13 +588,28 @@ pub open spec fn update_request_admission_check(installed_types: InstalledTypes,
     update_request_admission_check_helper(installed_types, req.name, req.namespace, req.obj, s)
 }
 
+// The generation of an object is incremented by each update that changes its spec, and is left unchanged
+// by updates to its metadata or status. Controllers record the generation they have acted on in the
+// observed_generation of the status, so that clients can tell whether the status reflects the latest spec.
+//
+// NOTE: the actual API server also increments the generation when it sets the deletion timestamp
+// of an object during graceful deletion, which is not modeled since graceful deletion is not supported.
+pub open spec fn updated_generation(spec: Value, old_obj: DynamicObjectView) -> Option<int> {
+    if spec != old_obj.spec {
+        Some(old_obj.metadata.generation.unwrap_or(0) + 1)
+    } else {
+        old_obj.metadata.generation
+    }
+}
+
 pub open spec fn updated_object(req: UpdateRequest, old_obj: DynamicObjectView) -> DynamicObjectView {
     let updated_obj = DynamicObjectView {
         kind: old_obj.kind,
         metadata: ObjectMetaView {
-            namespace: Some(req.namespace), // Overwrite namespace since it might not be provided
+            namespace: old_obj.metadata.namespace, // Keep the stored namespace (None for a cluster-scoped object) since the request might not provide it
             resource_version: old_obj.metadata.resource_version, // Overwrite rv since it might not be provided
             uid: old_obj.metadata.uid, // Overwrite uid since it might not be provided
+            generation: updated_generation(req.obj.spec, old_obj), // Ignore any change to generation
             deletion_timestamp: old_obj.metadata.deletion_timestamp, // Ignore any change to deletion_timestamp
             ..req.obj.metadata
         },
@@ -613,6 +747,120 @@ pub open spec fn handle_update_status_request(installed_types: InstalledTypes, r
     }
 }
 
+// merge_patch returns the result of merging the patch into value following JSON merge patch (RFC 7386).
+// Since spec is stored in its marshalled form, the merge is left uninterpreted and merge_patch_spec
+// gives the properties that proofs can use.
+pub uninterp spec fn merge_patch(value: Value, patch: Value) -> Value;
+
+// value_has_null says that value (in its marshalled form) contains a null at any depth.
+pub uninterp spec fn value_has_null(value: Value) -> bool;
+
+// NOTE: merging the same patch twice is the same as merging it once, and merging a value into itself
+// changes nothing. The latter only holds for values without null, since merging a null deletes the field.
+#[verifier(external_body)] // TRUSTED
+pub proof fn merge_patch_spec(value: Value, patch: Value)
+    ensures
+        merge_patch(merge_patch(value, patch), patch) == merge_patch(value, patch),
+        !value_has_null(value) ==> merge_patch(value, value) == value,
+{}
+
+pub open spec fn patch_request_admission_check(installed_types: InstalledTypes, req: PatchRequest, s: APIServerState) -> Option<APIError> {
+    if req.obj.metadata.name is Some && req.name != req.obj.metadata.name->0 {
+        // Patch fails because the name in the patch does not match the name sent on the request
+        Some(APIError::BadRequest)
+    } else if req.obj.metadata.namespace is Some && req.namespace != req.obj.metadata.namespace->0 {
+        // Patch fails because the namespace in the patch does not match the namespace sent on the request
+        Some(APIError::BadRequest)
+    } else if req.field_manager is Some && req.obj.metadata.name is None {
+        // Server-side apply requires the patch to carry the name of the object
+        Some(APIError::BadRequest)
+    } else if !s.resources.contains_key(req.key()) && !apply_creates_object(req, s) {
+        // Patch fails because the object does not exist (and the patch is not a server-side apply that creates it)
+        Some(APIError::ObjectNotFound)
+    } else if req.obj.metadata.resource_version is Some
+        && req.obj.metadata.resource_version != s.resources[req.key()].metadata.resource_version {
+        // Patch fails because the patch has a wrong rv
+        // Unlike update, a patch without rv is always allowed, even for custom resources
+        Some(APIError::Conflict)
+    } else if req.obj.metadata.uid is Some
+        && req.obj.metadata.uid != s.resources[req.key()].metadata.uid {
+        // Patch fails because the patch has a wrong uid
+        Some(APIError::Conflict)
+    } else {
+        None
+    }
+}
+
+// apply_creates_object says that req is a server-side apply of an object that does not exist, which creates the object
+// like the real API server does. An apply that carries a resource_version or a uid is a precondition on an existing
+// object, so it fails with ObjectNotFound instead.
+pub open spec fn apply_creates_object(req: PatchRequest, s: APIServerState) -> bool {
+    &&& req.is_apply()
+    &&& !s.resources.contains_key(req.key())
+    &&& req.obj.metadata.resource_version is None
+    &&& req.obj.metadata.uid is None
+}
+
+// patched_object merges the patch in req into old_obj:
+// * labels and annotations in the patch are merged into the existing ones (entries in the patch win);
+// * finalizers and owner_references in the patch replace the existing ones (lists are replaced as a whole);
+// * spec is merged by merge_patch;
+// * all the other fields, including status, come from old_obj.
+// A field that is None in the patch is left untouched. Server-side apply is modeled in the same way
+// as a merge patch since each field of the object is expected to be managed by a single field manager.
+pub open spec fn patched_object(req: PatchRequest, old_obj: DynamicObjectView) -> DynamicObjectView {
+    let patch = req.obj.metadata;
+    let patched_obj = DynamicObjectView {
+        kind: old_obj.kind,
+        metadata: ObjectMetaView {
+            labels: if patch.labels is Some {
+                Some(old_obj.metadata.labels.unwrap_or(Map::empty()).union_prefer_right(patch.labels->0))
+            } else {
+                old_obj.metadata.labels
+            },
+            annotations: if patch.annotations is Some {
+                Some(old_obj.metadata.annotations.unwrap_or(Map::empty()).union_prefer_right(patch.annotations->0))
+            } else {
+                old_obj.metadata.annotations
+            },
+            finalizers: if patch.finalizers is Some { patch.finalizers } else { old_obj.metadata.finalizers },
+            owner_references: if patch.owner_references is Some { patch.owner_references } else { old_obj.metadata.owner_references },
+            ..old_obj.metadata
+        },
+        spec: merge_patch(old_obj.spec, req.obj.spec),
+        status: old_obj.status, // Ignore any change to status
+    };
+    patched_obj
+}
+
+// A patch is handled as an update of the patched object to the current object. The update uses the current
+// resource_version and uid so it never fails because of the version check, which means a patch only conflicts
+// with other writes when the patch itself carries a resource_version.
+// A server-side apply of an object that does not exist is handled as a create of the applied object
+// (see apply_creates_object).
+#[verifier(inline)]
+pub open spec fn handle_patch_request(installed_types: InstalledTypes, req: PatchRequest, s: APIServerState) -> (APIServerState, PatchResponse) {
+    if patch_request_admission_check(installed_types, req, s) is Some {
+        // Patch fails.
+        (s, PatchResponse{res: Err(patch_request_admission_check(installed_types, req, s)->0)})
+    } else if apply_creates_object(req, s) {
+        let create_req = CreateRequest {
+            namespace: req.namespace,
+            obj: req.obj,
+        };
+        let (s_prime, create_resp) = handle_create_request(installed_types, create_req, s);
+        (s_prime, PatchResponse{res: create_resp.res})
+    } else {
+        let update_req = UpdateRequest {
+            name: req.name,
+            namespace: req.namespace,
+            obj: patched_object(req, s.resources[req.key()]),
+        };
+        let (s_prime, update_resp) = handle_update_request(installed_types, update_req, s);
+        (s_prime, PatchResponse{res: update_resp.res})
+    }
+}
+
 pub open spec fn handle_get_request_msg(msg: Message, s: APIServerState) -> (APIServerState, Message)
     recommends
         msg.content.is_get_request(),
@@ -626,7 +874,8 @@ pub open spec fn handle_list_request_msg(msg: Message, s: APIServerState) -> (AP
         msg.content.is_list_request(),
 {
     let req = msg.content.get_list_request();
-    (s, form_list_resp_msg(msg, handle_list_request(req, s)))
+    let (s_prime, resp) = handle_list_request(req, s);
+    (s_prime, form_list_resp_msg(msg, resp))
 }
 
 pub open spec fn handle_create_request_msg(installed_types: InstalledTypes, msg: Message, s: APIServerState) -> (APIServerState, Message)
@@ -665,6 +914,15 @@ pub open spec fn handle_update_status_request_msg(installed_types: InstalledType
     (s_prime, form_update_status_resp_msg(msg, resp))
 }
 
+pub open spec fn handle_patch_request_msg(installed_types: InstalledTypes, msg: Message, s: APIServerState) -> (APIServerState, Message)
+    recommends
+        msg.content.is_patch_request(),
+{
+    let req = msg.content.get_patch_request();
+    let (s_prime, resp) = handle_patch_request(installed_types, req, s);
+    (s_prime, form_patch_resp_msg(msg, resp))
+}
+
 // get_then_delete performs transactional operations by first getting the object, and then apply an delete when some
 // condition holds. A get_then_delete request will never fail due to conflicting operations from other controllers.
 //
@@ -680,20 +938,21 @@ pub open spec fn handle_get_then_delete_request_msg(msg: Message, s: APIServerSt
     } else {
         // Step 1: get the object
         let get_req = GetRequest {
-            key: req.key
+            key: req.key,
+            read_mode: ReadMode::Quorum,
         };
         let get_resp = handle_get_request(get_req, s);
         match get_resp.res {
             Ok(_) => {
                 let current_obj = s.resources[req.key()];
                 // Step 2: if the object exists, perform a check using a predicate on object
-                // The predicate: Is the current object owned by req.owner_ref?
-                // TODO: the predicate should be provided by clients instead of the hardcoded one
-                if current_obj.metadata.owner_references_contains(req.owner_ref) {
+                // The predicate: Is the current object owned by req.owner_ref, and does req.predicate hold on it?
+                if current_obj.metadata.owner_references_contains(req.owner_ref) && req.predicate.holds_on(current_obj) {
                     // Step 3: if the check passes, delete the object
                     let delete_req = DeleteRequest {
                         key: req.key,
                         preconditions: None,
+                        propagation_policy: None,
                     };
                     let (s_prime, delete_resp) = handle_delete_request(delete_req, s);
                     (s_prime, form_get_then_delete_resp_msg(msg, GetThenDeleteResponse {res: delete_resp.res}))
@@ -721,16 +980,16 @@ pub open spec fn handle_get_then_update_request_msg(installed_types: InstalledTy
     } else {
         // Step 1: get the object
         let get_req = GetRequest {
-            key: req.key()
+            key: req.key(),
+            read_mode: ReadMode::Quorum,
         };
         let get_resp = handle_get_request(get_req, s);
         match get_resp.res {
             Ok(_) => {
                 let current_obj = s.resources[req.key()];
                 // Step 2: if the object exists, perform a check using a predicate on object
-                // The predicate: Is the current object owned by req.owner_ref?
-                // TODO: the predicate should be provided by clients instead of the hardcoded one
-                if current_obj.metadata.owner_references_contains(req.owner_ref) {
+                // The predicate: Is the current object owned by req.owner_ref, and does req.predicate hold on it?
+                if current_obj.metadata.owner_references_contains(req.owner_ref) && req.predicate.holds_on(current_obj) {
                     // Step 3: if the check passes, overwrite the object with the new one
                     // Note that resource_version and uid comes from the current object to avoid conflict error
                     let new_obj = DynamicObjectView {
@@ -767,16 +1026,16 @@ pub open spec fn handle_get_then_update_status_request_msg(installed_types: Inst
     } else {
         // Step 1: get the object
         let get_req = GetRequest {
-            key: req.key()
+            key: req.key(),
+            read_mode: ReadMode::Quorum,
         };
         let get_resp = handle_get_request(get_req, s);
         match get_resp.res {
             Ok(_) => {
                 let current_obj = s.resources[req.key()];
                 // Step 2: if the object exists, perform a check using a predicate on object
-                // The predicate: Is the current object owned by req.owner_ref?
-                // TODO: the predicate should be provided by clients instead of the hardcoded one
-                if current_obj.metadata.owner_references_contains(req.owner_ref) {
+                // The predicate: Is the current object owned by req.owner_ref, and does req.predicate hold on it?
+                if current_obj.metadata.owner_references_contains(req.owner_ref) && req.predicate.holds_on(current_obj) {
                     // Step 3: if the check passes, overwrite the status of the object with the new one
                     // Note that resource_version and uid comes from the current object to avoid conflict error
                     let new_obj = DynamicObjectView {
@@ -815,6 +1074,7 @@ pub open spec fn transition_by_etcd(installed_types: InstalledTypes, msg: Messag
         APIRequest::GetThenDeleteRequest(_) => handle_get_then_delete_request_msg(msg, s),
         APIRequest::GetThenUpdateRequest(_) => handle_get_then_update_request_msg(installed_types, msg, s),
         APIRequest::GetThenUpdateStatusRequest(_) => handle_get_then_update_status_request_msg(installed_types, msg, s),
+        APIRequest::PatchRequest(_) => handle_patch_request_msg(installed_types, msg, s),
     }
 }
 
@@ -836,7 +1096,8 @@ pub open spec fn handle_request(installed_types: InstalledTypes) -> APIServerAct
 pub open spec fn api_server(installed_types: InstalledTypes) -> APIServerStateMachine {
     StateMachine {
         init: |s: APIServerState| {
-            s.resources == Map::<ObjectRef, DynamicObjectView>::empty()
+            &&& s.resources == Map::<ObjectRef, DynamicObjectView>::empty()
+            &&& s.list_snapshots == Map::<ResourceVersion, StoredState>::empty()
         },
         actions: set![handle_request(installed_types)],
         step_to_action: |step: APIServerStep| {
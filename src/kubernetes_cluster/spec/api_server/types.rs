@@ -7,15 +7,21 @@ use vstd::{multiset::*, prelude::*};
 
 verus! {
 
+// list_snapshots keeps the resources at the resource versions where paginated lists started,
+// so that the later pages of a list are served from the same snapshot as the first page.
+
 pub struct APIServerState {
     pub resources: StoredState,
     pub uid_counter: Uid,
     pub resource_version_counter: ResourceVersion,
+    pub list_snapshots: Map<ResourceVersion, StoredState>,
 }
 
 pub type InstalledTypes = Map<StringView, InstalledType>;
 
+// InstalledType describes a custom resource type installed by its CRD.
 pub struct InstalledType {
+    pub scope: ResourceScope,
     pub unmarshallable_spec: spec_fn(Value) -> bool,
     pub unmarshallable_status: spec_fn(Value) -> bool,
     pub valid_object: spec_fn(DynamicObjectView) -> bool,
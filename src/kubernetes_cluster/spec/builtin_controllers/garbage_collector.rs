@@ -1,18 +1,53 @@
 use crate::kubernetes_api_objects::spec::prelude::*;
-use crate::kubernetes_cluster::spec::{builtin_controllers::types::*, message::*};
+use crate::kubernetes_cluster::spec::{
+    api_server::{state_machine::kind_is_namespaced, types::InstalledTypes},
+    builtin_controllers::types::*, message::*,
+};
 use crate::state_machine::action::*;
+use crate::vstd_ext::string_view::*;
 use vstd::{multiset::*, prelude::*};
 
 verus! {
 
-// TODO:
-// + Specify foreground deletion
+// The garbage collector deletes the dependents of an object according to how the object is deleted
+// (see PropagationPolicy):
+// + Background: run_garbage_collector deletes the dependents whose owners are all gone.
 //
-// + Specify orphan dependents
+// + Foreground: delete_dependent_in_foreground deletes the dependents of an owner that has the foregroundDeletion
+// finalizer, and remove_foreground_deletion_finalizer removes the finalizer once the owner has no dependents
+// that block its deletion (i.e., whose owner reference to it has blockOwnerDeletion set).
 //
-// + Specify how GC removes owner references from the object
+// + Orphan: orphan_dependent removes the owner references to an owner that has the orphan finalizer from the dependents,
+// and remove_orphan_finalizer removes the finalizer once the owner has no dependents.
+//
+// An object with mixed ownership, i.e., with at least one solid owner (see owner_is_solid), is never deleted.
+// Instead, prune_owner_references removes its owner references to the dangling owners (see owner_is_dangling),
+// and stops it from blocking the deletion of the owners that are being deleted in foreground.
+// See https://github.com/kubernetes/kubernetes/blob/v1.30.0/pkg/controller/garbagecollector/garbagecollector.go#L560
+//
+// Owner references do not carry namespaces. The owner of a namespaced object is looked up in the namespace of the object
+// if the owner is namespaced, or in the cluster scope if the owner is cluster-scoped (see owner_reference_key).
+// A cluster-scoped object can only be owned by cluster-scoped owners, and its owner references to namespaced owners
+// are invalid (see owner_reference_is_invalid).
+
+// owner_reference_key returns the key of the owner referred by owner_reference from the object at key.
+pub open spec fn owner_reference_key(installed_types: InstalledTypes, owner_reference: OwnerReferenceView, key: ObjectRef) -> ObjectRef {
+    if kind_is_namespaced(installed_types, owner_reference.kind) {
+        owner_reference_to_object_reference(owner_reference, key.namespace)
+    } else {
+        owner_reference_to_object_reference(owner_reference, cluster_scope_namespace())
+    }
+}
+
+// owner_reference_is_invalid says that owner_reference from the cluster-scoped object at key refers to a namespaced owner,
+// which can never be resolved. Like the real garbage collector, an invalid owner reference is treated as a solid owner,
+// i.e., the object is never deleted and the owner reference is never pruned.
+pub open spec fn owner_reference_is_invalid(installed_types: InstalledTypes, owner_reference: OwnerReferenceView, key: ObjectRef) -> bool {
+    &&& !kind_is_namespaced(installed_types, key.kind)
+    &&& kind_is_namespaced(installed_types, owner_reference.kind)
+}
 
-pub open spec fn run_garbage_collector() -> BuiltinControllersAction {
+pub open spec fn run_garbage_collector(installed_types: InstalledTypes) -> BuiltinControllersAction {
     Action {
         precondition: |input: BuiltinControllersActionInput, s: ()| {
             let resources = input.resources;
@@ -26,14 +61,9 @@ pub open spec fn run_garbage_collector() -> BuiltinControllersAction {
             &&& resources[key].metadata.owner_references is Some
             &&& resources[key].metadata.owner_references->0.len() > 0
             // The garbage collector decides whether to delete an object by checking its owner references,
-            // it deletes the object if for each owner reference...
-            &&& forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len() ==> {
-                // the referred owner object does not exist in the cluster state
-                ||| !resources.contains_key(owner_reference_to_object_reference(owner_references[i], key.namespace))
-                // or it exists but has a different uid
-                // (which means the actual owner was deleted and another object with the same name gets created again)
-                ||| resources[owner_reference_to_object_reference(owner_references[i], key.namespace)].metadata.uid != Some(owner_references[i].uid)
-            }
+            // it deletes the object if each referred owner is dangling
+            &&& forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                ==> owner_is_dangling(installed_types, resources, owner_references[i], key)
         },
         transition: |input: BuiltinControllersActionInput, s: ()| {
             // GC set the preconditions to the object's uid in its delete request
@@ -54,4 +84,249 @@ pub open spec fn run_garbage_collector() -> BuiltinControllersAction {
     }
 }
 
+// is_deleting_with says that obj is being deleted and the deletion waits for the garbage collector
+// to remove the finalizer (foreground_deletion_finalizer or orphan_finalizer).
+pub open spec fn is_deleting_with(obj: DynamicObjectView, finalizer: StringView) -> bool {
+    &&& obj.metadata.deletion_timestamp is Some
+    &&& obj.metadata.finalizers_as_set().contains(finalizer)
+}
+
+// owner_is_deleting_with says that the owner referred by owner_reference (from the object at key)
+// exists and is being deleted with finalizer.
+pub open spec fn owner_is_deleting_with(installed_types: InstalledTypes, resources: StoredState, owner_reference: OwnerReferenceView, key: ObjectRef, finalizer: StringView) -> bool {
+    let owner_key = owner_reference_key(installed_types, owner_reference, key);
+    &&& !owner_reference_is_invalid(installed_types, owner_reference, key)
+    &&& resources.contains_key(owner_key)
+    &&& resources[owner_key].metadata.uid == Some(owner_reference.uid)
+    &&& is_deleting_with(resources[owner_key], finalizer)
+}
+
+// owner_is_dangling says that the owner referred by owner_reference (from the object at key) is gone:
+// it does not exist, or it exists but has a different uid
+// (which means the actual owner was deleted and another object with the same name gets created again).
+pub open spec fn owner_is_dangling(installed_types: InstalledTypes, resources: StoredState, owner_reference: OwnerReferenceView, key: ObjectRef) -> bool {
+    let owner_key = owner_reference_key(installed_types, owner_reference, key);
+    &&& !owner_reference_is_invalid(installed_types, owner_reference, key)
+    &&& {
+        ||| !resources.contains_key(owner_key)
+        ||| resources[owner_key].metadata.uid != Some(owner_reference.uid)
+    }
+}
+
+// owner_is_solid says that the owner referred by owner_reference exists and is not waiting for its dependents
+// to be deleted, so the garbage collector keeps the dependent.
+pub open spec fn owner_is_solid(installed_types: InstalledTypes, resources: StoredState, owner_reference: OwnerReferenceView, key: ObjectRef) -> bool {
+    &&& !owner_is_dangling(installed_types, resources, owner_reference, key)
+    &&& !owner_is_deleting_with(installed_types, resources, owner_reference, key, foreground_deletion_finalizer())
+}
+
+// has_dependents says that some object in resources has an owner reference to the object at owner_key.
+// If blocking_only, only the owner references with blockOwnerDeletion set are considered.
+pub open spec fn has_dependents(installed_types: InstalledTypes, resources: StoredState, owner_key: ObjectRef, blocking_only: bool) -> bool {
+    exists |key: ObjectRef| {
+        let owner_references = resources[key].metadata.owner_references->0;
+        &&& #[trigger] resources.contains_key(key)
+        &&& resources[key].metadata.owner_references is Some
+        &&& exists |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+            && !owner_reference_is_invalid(installed_types, owner_references[i], key)
+            && owner_reference_key(installed_types, owner_references[i], key) == owner_key
+            && resources[owner_key].metadata.uid == Some(owner_references[i].uid)
+            && (!blocking_only || owner_references[i].block_owner_deletion == Some(true))
+    }
+}
+
+// pruned_owner_references returns the owner references of the object at key with mixed ownership
+// after prune_owner_references: the references to dangling owners are removed, and the references to owners
+// being deleted in foreground no longer block the deletion.
+pub open spec fn pruned_owner_references(installed_types: InstalledTypes, resources: StoredState, owner_references: Seq<OwnerReferenceView>, key: ObjectRef) -> Seq<OwnerReferenceView> {
+    owner_references.filter(|o: OwnerReferenceView| !owner_is_dangling(installed_types, resources, o, key))
+        .map_values(|o: OwnerReferenceView| {
+            if owner_is_deleting_with(installed_types, resources, o, key, foreground_deletion_finalizer()) && o.block_owner_deletion == Some(true) {
+                OwnerReferenceView { block_owner_deletion: Some(false), ..o }
+            } else {
+                o
+            }
+        })
+}
+
+// finalizer_removed_req_msg_content is the update request that removes finalizer from obj.
+pub open spec fn finalizer_removed_req_msg_content(key: ObjectRef, obj: DynamicObjectView, finalizer: StringView) -> MessageContent {
+    update_req_msg_content(
+        key.namespace, key.name,
+        obj.with_metadata(obj.metadata.with_finalizers(obj.metadata.finalizers->0.filter(|f: StringView| f != finalizer)))
+    )
+}
+
+pub open spec fn delete_dependent_in_foreground(installed_types: InstalledTypes) -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let resources = input.resources;
+            let key = input.key;
+            let owner_references = resources[key].metadata.owner_references->0;
+            &&& input.choice is GarbageCollector
+            &&& resources.contains_key(key)
+            // The dependent is not being deleted yet
+            &&& resources[key].metadata.deletion_timestamp is None
+            &&& resources[key].metadata.owner_references is Some
+            // and one of its owners is being deleted in foreground
+            &&& exists |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                && owner_is_deleting_with(installed_types, resources, owner_references[i], key, foreground_deletion_finalizer())
+            // and none of its owners is solid
+            &&& forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                ==> !owner_is_solid(installed_types, resources, owner_references[i], key)
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            // If the dependent blocks the deletion of an owner, it is also deleted in foreground
+            // so that the owner waits for the dependents of the dependent
+            // See https://github.com/kubernetes/kubernetes/blob/v1.30.0/pkg/controller/garbagecollector/garbagecollector.go#L640
+            let key = input.key;
+            let owner_references = input.resources[key].metadata.owner_references->0;
+            let propagation_policy = if exists |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                && owner_references[i].block_owner_deletion == Some(true)
+                && owner_is_deleting_with(installed_types, input.resources, owner_references[i], key, foreground_deletion_finalizer()) {
+                PropagationPolicy::Foreground
+            } else {
+                PropagationPolicy::Background
+            };
+            let preconditions = PreconditionsView {
+                uid: input.resources[input.key].metadata.uid,
+                resource_version: None,
+            };
+            let delete_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                delete_req_msg_content_with_policy(input.key, Some(preconditions), Some(propagation_policy))
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(delete_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+pub open spec fn remove_foreground_deletion_finalizer(installed_types: InstalledTypes) -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let resources = input.resources;
+            let key = input.key;
+            &&& input.choice is GarbageCollector
+            // The owner is being deleted in foreground
+            &&& resources.contains_key(key)
+            &&& is_deleting_with(resources[key], foreground_deletion_finalizer())
+            // and all the dependents that block its deletion are gone
+            &&& !has_dependents(installed_types, resources, key, true)
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            let update_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                finalizer_removed_req_msg_content(input.key, input.resources[input.key], foreground_deletion_finalizer())
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+pub open spec fn orphan_dependent(installed_types: InstalledTypes) -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let resources = input.resources;
+            let key = input.key;
+            let owner_references = resources[key].metadata.owner_references->0;
+            &&& input.choice is GarbageCollector
+            &&& resources.contains_key(key)
+            &&& resources[key].metadata.owner_references is Some
+            // One of the owners of the dependent is being deleted with the orphan policy
+            &&& exists |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                && owner_is_deleting_with(installed_types, resources, owner_references[i], key, orphan_finalizer())
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            // GC removes the owner references to all the orphaning owners from the dependent
+            // See https://github.com/kubernetes/kubernetes/blob/v1.30.0/pkg/controller/garbagecollector/garbagecollector.go#L769
+            let key = input.key;
+            let obj = input.resources[key];
+            let owner_references = obj.metadata.owner_references->0.filter(|o: OwnerReferenceView|
+                !owner_is_deleting_with(installed_types, input.resources, o, key, orphan_finalizer())
+            );
+            let update_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                update_req_msg_content(key.namespace, key.name, obj.with_metadata(obj.metadata.with_owner_references(owner_references)))
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+pub open spec fn remove_orphan_finalizer(installed_types: InstalledTypes) -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let resources = input.resources;
+            let key = input.key;
+            &&& input.choice is GarbageCollector
+            // The owner is being deleted with the orphan policy
+            &&& resources.contains_key(key)
+            &&& is_deleting_with(resources[key], orphan_finalizer())
+            // and none of its dependents refers to it any more
+            &&& !has_dependents(installed_types, resources, key, false)
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            let update_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                finalizer_removed_req_msg_content(input.key, input.resources[input.key], orphan_finalizer())
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+pub open spec fn prune_owner_references(installed_types: InstalledTypes) -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let resources = input.resources;
+            let key = input.key;
+            let owner_references = resources[key].metadata.owner_references->0;
+            &&& input.choice is GarbageCollector
+            &&& resources.contains_key(key)
+            &&& resources[key].metadata.owner_references is Some
+            // The object has at least one solid owner so it is not deleted...
+            &&& exists |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                && owner_is_solid(installed_types, resources, owner_references[i], key)
+            // but it refers to a dangling owner, or blocks the deletion of an owner
+            &&& exists |i| #![trigger owner_references[i]] 0 <= i < owner_references.len() && {
+                ||| owner_is_dangling(installed_types, resources, owner_references[i], key)
+                ||| {
+                    &&& owner_references[i].block_owner_deletion == Some(true)
+                    &&& owner_is_deleting_with(installed_types, resources, owner_references[i], key, foreground_deletion_finalizer())
+                }
+            }
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            let key = input.key;
+            let obj = input.resources[key];
+            let owner_references = pruned_owner_references(installed_types, input.resources, obj.metadata.owner_references->0, key);
+            let update_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                update_req_msg_content(key.namespace, key.name, obj.with_metadata(obj.metadata.with_owner_references(owner_references)))
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
 }
@@ -1,5 +1,6 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 pub mod garbage_collector;
+pub mod pod_lifecycle;
 pub mod state_machine;
 pub mod types;
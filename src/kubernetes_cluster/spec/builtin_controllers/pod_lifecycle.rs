@@ -0,0 +1,222 @@
+use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::kubernetes_cluster::spec::{builtin_controllers::types::*, message::*};
+use crate::state_machine::action::*;
+use crate::vstd_ext::string_view::*;
+use vstd::{multiset::*, prelude::*};
+
+verus! {
+
+// The scheduler and the kubelet move each pod through its lifecycle:
+// + schedule_pod (scheduler) binds a pod that has no node to the node chosen by the scheduler,
+// which must be an existing and schedulable node. The real scheduler binds the pod through the binding subresource,
+// which is modeled as an update request that sets spec.node_name. Binding changes the pod spec, which the liveness
+// properties of the controllers that manage pods compare against their templates, so like the pod monkey,
+// schedule_pod is only enabled while the pod monkey is enabled (see builtin_controllers_next).
+//
+// + start_pod (kubelet) starts the containers of a pending pod that has been scheduled,
+// and the pod becomes Running but not ready yet. Each node runs its own kubelet,
+// so start_pod is only enabled if the node that the pod is bound to exists.
+//
+// + mark_pod_ready (kubelet) marks a running pod as ready, which abstracts away the readiness probes.
+// Like start_pod, it is only enabled if the node that the pod is bound to exists.
+//
+// + fail_pod (kubelet) fails a pending or running pod, e.g., when its containers crash or its node runs out of resources.
+// The failed pod stays Failed until it is deleted. Unlike the other steps, fail_pod is a failure rather than progress,
+// so it is only enabled while the pod monkey is enabled (see builtin_controllers_next).
+//
+// + evict_pod (node lifecycle controller) deletes a pod bound to a node that is gone, e.g., after a node failure
+// (see node_failure_next). Like the garbage collector, it sets the uid of the pod as the precondition,
+// and a pod with finalizers is marked as terminating instead of being removed.
+// Like fail_pod, evict_pod is a failure, so it is only enabled while node failures are enabled (see builtin_controllers_next).
+// The local volumes on the gone node are not modeled, so evicting a pod does not lose the data of its PVCs.
+// See https://github.com/kubernetes/kubernetes/blob/v1.30.0/pkg/controller/podgc/gc_controller.go
+//
+// The kubelet reports the status through update status requests. It does not restart containers,
+// and does not model the pods that run to completion (i.e., Succeeded).
+// Nodes are registered out of band (e.g., by the cluster administrator), so none of these controllers creates nodes.
+
+// pod_exists_at says that the object at key is a pod with a spec.
+pub open spec fn pod_exists_at(resources: StoredState, key: ObjectRef) -> bool {
+    &&& key.kind == Kind::PodKind
+    &&& resources.contains_key(key)
+    &&& PodView::unmarshal(resources[key]) is Ok
+    &&& PodView::unmarshal(resources[key])->Ok_0.spec is Some
+}
+
+// node_is_schedulable says that the node with the name exists and the scheduler can bind new pods to it.
+pub open spec fn node_is_schedulable(resources: StoredState, name: StringView) -> bool {
+    &&& resources.contains_key(node_key(name))
+    &&& NodeView::unmarshal(resources[node_key(name)]) is Ok
+    &&& NodeView::unmarshal(resources[node_key(name)])->Ok_0.is_schedulable()
+}
+
+// pod_is_on_existing_node says that the node that pod is bound to exists, so the kubelet on the node runs pod.
+pub open spec fn pod_is_on_existing_node(resources: StoredState, pod: PodView) -> bool {
+    &&& pod.is_scheduled()
+    &&& resources.contains_key(node_key(pod.spec->0.node_name->0))
+}
+
+// container_statuses returns the status of each container of pod, which is started and ready as given.
+pub open spec fn container_statuses(pod: PodView, started: bool, ready: bool) -> Seq<ContainerStatusView> {
+    pod.spec->0.containers.map_values(|c: ContainerView| ContainerStatusView {
+        name: c.name,
+        ready: ready,
+        started: Some(started),
+        restart_count: 0,
+    })
+}
+
+// pod_status_with returns the status reported by the kubelet for pod in phase.
+pub open spec fn pod_status_with(pod: PodView, phase: StringView, started: bool, ready: bool) -> PodStatusView {
+    PodStatusView::default()
+        .with_phase(phase)
+        .with_conditions(seq![PodConditionView {
+            type_: pod_ready_condition(),
+            status: if ready { "True"@ } else { "False"@ },
+        }])
+        .with_container_statuses(container_statuses(pod, started, ready))
+}
+
+// pod_status_updated_req_msg_content is the update status request that sets the status of pod at key to status.
+pub open spec fn pod_status_updated_req_msg_content(key: ObjectRef, pod: PodView, status: PodStatusView) -> MessageContent {
+    update_status_req_msg_content(key.namespace, key.name, pod.with_status(status).marshal())
+}
+
+pub open spec fn schedule_pod() -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            // The scheduler is chosen by the top level state machine, together with the node to bind the pod to
+            &&& input.choice is Scheduler
+            &&& pod_exists_at(input.resources, input.key)
+            // The pod has not been bound to any node
+            &&& !pod.is_scheduled()
+            &&& pod.metadata.deletion_timestamp is None
+            // and the chosen node can run new pods
+            &&& node_is_schedulable(input.resources, input.choice->Scheduler_0)
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            let node_name = input.choice->Scheduler_0;
+            let update_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                update_req_msg_content(
+                    input.key.namespace, input.key.name, pod.with_spec(pod.spec->0.with_node_name(node_name)).marshal()
+                )
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+pub open spec fn start_pod() -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            &&& input.choice is Kubelet
+            &&& pod_exists_at(input.resources, input.key)
+            // The pod has been bound to an existing node and its containers are not started yet
+            &&& pod_is_on_existing_node(input.resources, pod)
+            &&& pod.phase() == pod_pending_phase()
+            &&& pod.metadata.deletion_timestamp is None
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            let update_status_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                pod_status_updated_req_msg_content(input.key, pod, pod_status_with(pod, pod_running_phase(), true, false))
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_status_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+pub open spec fn mark_pod_ready() -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            &&& input.choice is Kubelet
+            &&& pod_exists_at(input.resources, input.key)
+            // The pod is running on an existing node but not ready yet
+            &&& pod_is_on_existing_node(input.resources, pod)
+            &&& pod.phase() == pod_running_phase()
+            &&& !pod.is_ready()
+            &&& pod.metadata.deletion_timestamp is None
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            let update_status_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                pod_status_updated_req_msg_content(input.key, pod, pod_status_with(pod, pod_running_phase(), true, true))
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_status_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+pub open spec fn fail_pod() -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            &&& input.choice is KubeletFailure
+            &&& pod_exists_at(input.resources, input.key)
+            // The pod has been bound to a node and has not failed yet
+            &&& pod.is_scheduled()
+            &&& pod.phase() == pod_pending_phase() || pod.phase() == pod_running_phase()
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            let update_status_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1,
+                pod_status_updated_req_msg_content(input.key, pod, pod_status_with(pod, pod_failed_phase(), false, false))
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_status_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+pub open spec fn evict_pod() -> BuiltinControllersAction {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ()| {
+            let pod = PodView::unmarshal(input.resources[input.key])->Ok_0;
+            &&& input.choice is NodeLifecycle
+            &&& pod_exists_at(input.resources, input.key)
+            // The pod is bound to a node that no longer exists
+            &&& pod.is_scheduled()
+            &&& !input.resources.contains_key(node_key(pod.spec->0.node_name->0))
+            &&& pod.metadata.deletion_timestamp is None
+        },
+        transition: |input: BuiltinControllersActionInput, s: ()| {
+            let preconditions = PreconditionsView {
+                uid: input.resources[input.key].metadata.uid,
+                resource_version: None,
+            };
+            let delete_req_msg = built_in_controller_req_msg(
+                input.rpc_id_allocator.allocate().1, delete_req_msg_content(input.key, Some(preconditions))
+            );
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(delete_req_msg),
+                rpc_id_allocator: input.rpc_id_allocator.allocate().0,
+            };
+            ((), output)
+        },
+    }
+}
+
+}
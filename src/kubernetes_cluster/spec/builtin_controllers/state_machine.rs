@@ -1,20 +1,42 @@
-use crate::kubernetes_cluster::spec::builtin_controllers::{garbage_collector::*, types::*};
+use crate::kubernetes_cluster::spec::{
+    api_server::types::InstalledTypes, builtin_controllers::{garbage_collector::*, pod_lifecycle::*, types::*},
+};
 use crate::state_machine::state_machine::*;
 use vstd::prelude::*;
 
 verus! {
 
-pub open spec fn builtin_controllers() -> BuiltinControllersStateMachine {
+pub open spec fn builtin_controllers(installed_types: InstalledTypes) -> BuiltinControllersStateMachine {
     StateMachine {
         init: |s: ()| {
             true
         },
         actions: set![
-            run_garbage_collector(),
+            run_garbage_collector(installed_types),
+            delete_dependent_in_foreground(installed_types),
+            remove_foreground_deletion_finalizer(installed_types),
+            orphan_dependent(installed_types),
+            remove_orphan_finalizer(installed_types),
+            prune_owner_references(installed_types),
+            schedule_pod(),
+            start_pod(),
+            mark_pod_ready(),
+            fail_pod(),
+            evict_pod(),
         ],
         step_to_action: |step: BuiltinControllersStep| {
             match step {
-                BuiltinControllersStep::RunGarbageCollector => run_garbage_collector(),
+                BuiltinControllersStep::RunGarbageCollector => run_garbage_collector(installed_types),
+                BuiltinControllersStep::DeleteDependentInForeground => delete_dependent_in_foreground(installed_types),
+                BuiltinControllersStep::RemoveForegroundDeletionFinalizer => remove_foreground_deletion_finalizer(installed_types),
+                BuiltinControllersStep::OrphanDependent => orphan_dependent(installed_types),
+                BuiltinControllersStep::RemoveOrphanFinalizer => remove_orphan_finalizer(installed_types),
+                BuiltinControllersStep::PruneOwnerReferences => prune_owner_references(installed_types),
+                BuiltinControllersStep::SchedulePod => schedule_pod(),
+                BuiltinControllersStep::StartPod => start_pod(),
+                BuiltinControllersStep::MarkPodReady => mark_pod_ready(),
+                BuiltinControllersStep::FailPod => fail_pod(),
+                BuiltinControllersStep::EvictPod => evict_pod(),
             }
         },
         action_input: |step: BuiltinControllersStep, input: BuiltinControllersActionInput| {
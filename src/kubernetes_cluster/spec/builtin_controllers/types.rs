@@ -2,16 +2,35 @@ use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::message::*;
 use crate::state_machine::action::*;
 use crate::state_machine::state_machine::*;
+use crate::vstd_ext::string_view::*;
 use vstd::{multiset::*, prelude::*};
 
 verus! {
 
 pub enum BuiltinControllersStep {
     RunGarbageCollector,
+    DeleteDependentInForeground,
+    RemoveForegroundDeletionFinalizer,
+    OrphanDependent,
+    RemoveOrphanFinalizer,
+    PruneOwnerReferences,
+    SchedulePod,
+    StartPod,
+    MarkPodReady,
+    FailPod,
+    EvictPod,
 }
 
 pub enum BuiltinControllerChoice {
     GarbageCollector,
+    // The scheduler binds pods to the node
+    Scheduler(StringView),
+    Kubelet,
+    // The kubelet fails pods, which is only enabled while the pod monkey is enabled
+    KubeletFailure,
+    // The node lifecycle controller evicts the pods from the nodes that are gone,
+    // which is only enabled while node failures are enabled
+    NodeLifecycle,
 }
 
 pub struct BuiltinControllersActionInput {
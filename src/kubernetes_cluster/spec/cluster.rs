@@ -8,6 +8,7 @@ use crate::kubernetes_cluster::spec::{
     pod_monkey::state_machine::*, pod_monkey::types::*,
 };
 use crate::state_machine::action::*;
+use crate::vstd_ext::string_view::*;
 use verus_temporal_logic::defs::*;
 use vstd::{multiset::*, prelude::*};
 
@@ -17,23 +18,33 @@ verus! {
 // the states of each controller running in the cluster (and the associated external system if exists),
 // the state of the network (the pending messages).
 // It also has a global rpc_id_allocator that assign a unique id to each RPC call,
-// and a req_drop_enabled to enable/disable network message drop.
+// and req_drop_enabled, resp_drop_enabled and req_dup_enabled to enable/disable
+// network request drop, response drop and request duplication,
+// pod_monkey_enabled and node_failure_enabled to enable/disable pod and node failures,
+// and external_failure_enabled to enable/disable failed requests to the external systems.
 pub struct ClusterState {
     pub api_server: APIServerState,
     pub controller_and_externals: Map<int, ControllerAndExternalState>,
     pub network: NetworkState,
     pub rpc_id_allocator: RPCIdAllocator,
     pub req_drop_enabled: bool,
+    pub resp_drop_enabled: bool,
+    pub req_dup_enabled: bool,
     pub pod_monkey_enabled: bool,
+    pub node_failure_enabled: bool,
+    pub external_failure_enabled: bool,
 }
 
 // The ControllerAndExternalState includes the controller's internal state,
 // the associated external system's state (if exists).
-// It also has a crash_enabled that to enable/disable controller crash.
+// It also has a crash_enabled that to enable/disable controller crash,
+// and the cache kept by the controller's watches (if the controller has one),
+// which lags behind the resources in the API server (see sync_cache and cached_read).
 pub struct ControllerAndExternalState {
     pub controller: ControllerState,
     pub external: Option<ExternalState>,
     pub crash_enabled: bool,
+    pub cache: Option<StoredState>,
 }
 
 impl ClusterState {
@@ -62,6 +73,11 @@ impl ClusterState {
         self.controller_and_externals[controller_id].controller.reconcile_id_allocator
     }
 
+    #[verifier(inline)]
+    pub open spec fn cache(self, controller_id: int) -> Option<StoredState> {
+        self.controller_and_externals[controller_id].cache
+    }
+
     pub open spec fn has_rpc_id_counter_no_smaller_than(self, rpc_id: nat) -> bool {
         self.rpc_id_allocator.rpc_id_counter >= rpc_id
     }
@@ -81,9 +97,19 @@ pub enum Step {
     DisableCrashStep(int),
     DropReqStep((Message, APIError)),
     DisableReqDropStep,
+    DropRespStep(Message),
+    DisableRespDropStep,
+    DuplicateReqStep(Message),
+    DisableReqDupStep,
     PodMonkeyStep(PodView),
     DisablePodMonkeyStep,
+    NodeFailureStep(StringView),
+    DisableNodeFailureStep,
     ExternalStep((int, Option<Message>)),
+    ExternalFailureStep((Message, ExternalError, bool)),
+    DisableExternalFailureStep,
+    SyncCacheStep(int),
+    CachedReadStep(Message),
     StutterStep,
 }
 
@@ -112,13 +138,20 @@ impl Cluster {
             // The API server is initialized...
             &&& (api_server(self.installed_types).init)(s.api_server)
             // and the built-in controllers are initialized...
-            &&& (builtin_controllers().init)(())
+            &&& (builtin_controllers(self.installed_types).init)(())
             // and the network is initialized...
             &&& (network().init)(s.network)
             // and message drop is enabled...
             &&& s.req_drop_enabled
+            &&& s.resp_drop_enabled
+            // and request duplication is enabled...
+            &&& s.req_dup_enabled
             // and pod monkey is enabled...
             &&& s.pod_monkey_enabled
+            // and node failure is enabled...
+            &&& s.node_failure_enabled
+            // and external failure is enabled...
+            &&& s.external_failure_enabled
             // and for each controller...
             &&& forall |key| #[trigger] self.controller_models.contains_key(key)
                 ==> {
@@ -129,6 +162,9 @@ impl Cluster {
                     &&& (controller(model.reconcile_model, key).init)(s.controller_and_externals[key].controller)
                     // and crash for this controller is enabled...
                     &&& s.controller_and_externals[key].crash_enabled
+                    // and if the controller has a cache, the cache is in sync with the API server...
+                    &&& s.controller_and_externals[key].cache is Some
+                        ==> s.controller_and_externals[key].cache->0 == s.api_server.resources
                     // and if the controller has an associated external system...
                     &&& model.external_model is Some
                         ==> {
@@ -160,9 +196,19 @@ impl Cluster {
             Step::DisableCrashStep(input) => self.disable_crash().forward(input)(s, s_prime),
             Step::DropReqStep(input) => self.drop_req().forward(input)(s, s_prime),
             Step::DisableReqDropStep => self.disable_req_drop().forward(())(s, s_prime),
+            Step::DropRespStep(input) => self.drop_resp().forward(input)(s, s_prime),
+            Step::DisableRespDropStep => self.disable_resp_drop().forward(())(s, s_prime),
+            Step::DuplicateReqStep(input) => self.duplicate_req().forward(input)(s, s_prime),
+            Step::DisableReqDupStep => self.disable_req_dup().forward(())(s, s_prime),
             Step::PodMonkeyStep(input) => self.pod_monkey_next().forward(input)(s, s_prime),
             Step::DisablePodMonkeyStep => self.disable_pod_monkey().forward(())(s, s_prime),
+            Step::NodeFailureStep(input) => self.node_failure_next().forward(input)(s, s_prime),
+            Step::DisableNodeFailureStep => self.disable_node_failure().forward(())(s, s_prime),
             Step::ExternalStep(input) => self.external_next().forward(input)(s, s_prime),
+            Step::ExternalFailureStep(input) => self.external_failure().forward(input)(s, s_prime),
+            Step::DisableExternalFailureStep => self.disable_external_failure().forward(())(s, s_prime),
+            Step::SyncCacheStep(input) => self.sync_cache().forward(input)(s, s_prime),
+            Step::CachedReadStep(input) => self.cached_read().forward(input)(s, s_prime),
             Step::StutterStep => self.stutter().forward(())(s, s_prime),
         }
     }
@@ -201,8 +247,8 @@ impl Cluster {
         }
     }
 
-    // The builtin_controllers_next models the built-in controllers that come with Kubernetes.
-    // For now, it only models the garbage collector.
+    // The builtin_controllers_next models the built-in controllers that come with Kubernetes,
+    // i.e., the garbage collector, the scheduler, the kubelet and the node lifecycle controller (see builtin_controllers).
     // To keep things simple, instead of modeling how the built-in controllers sends get/list
     // requests to read the cluster state, the Kubernetes cluster state (i.e., resources) is
     // directly passed to the built-in controller.
@@ -227,6 +273,12 @@ impl Cluster {
         };
         Action {
             precondition: |input: (BuiltinControllerChoice, ObjectRef), s: ClusterState| {
+                // Like the pod monkey, the kubelet only fails pods before the failures are disabled
+                &&& input.0 is KubeletFailure ==> s.pod_monkey_enabled
+                // and the scheduler only binds pods before the pod monkey is disabled, since binding changes the pod spec
+                &&& input.0 is Scheduler ==> s.pod_monkey_enabled
+                // Like the node failures, the node lifecycle controller only evicts pods before the failures are disabled
+                &&& input.0 is NodeLifecycle ==> s.node_failure_enabled
                 &&& result(input, s).0 is Enabled
                 &&& result(input, s).1 is Enabled
             },
@@ -483,6 +535,121 @@ impl Cluster {
         }
     }
 
+    // The drop_resp intercepts a response sent by the API server and replaces it with
+    // a Timeout error to the receiving controller. Different from drop_req, the request
+    // has already been handled by the API server, so the controller sees an error even
+    // though the request took effect. This is used to model failures including:
+    // * Network failures that drop the response, or the connection gets closed before
+    //   the response arrives, where the client times out.
+    // * The response arrives after the client gives up waiting for it.
+    //
+    // The error response has the same rpc_id as the dropped response, so it still matches
+    // the request and the controller can tell the request has been answered.
+    pub open spec fn drop_resp(self) -> Action<ClusterState, Message, ()> {
+        let result = |input: Message, s: ClusterState| {
+            let resp_msg = input;
+            let err_resp = form_err_resp_msg_replacing(resp_msg, APIError::Timeout);
+            let msg_ops = MessageOps {
+                recv: Some(resp_msg),
+                send: Multiset::singleton(err_resp),
+            };
+            let result = network().next_result(msg_ops, s.network);
+            result
+        };
+        Action {
+            precondition: |input: Message, s: ClusterState| {
+                let resp_msg = input;
+                &&& s.resp_drop_enabled
+                &&& resp_msg.src is APIServer
+                &&& resp_msg.content is APIResponse
+                &&& result(input, s) is Enabled
+            },
+            transition: |input: Message, s: ClusterState| {
+                (ClusterState {
+                    network: result(input, s)->Enabled_0,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
+    // The disable_resp_drop disables the network from dropping the response messages.
+    // This is used to constrain the network failures for proving liveness:
+    // the network eventually stops dropping response messages.
+    pub open spec fn disable_resp_drop(self) -> Action<ClusterState, (), ()> {
+        Action {
+            precondition: |input:(), s: ClusterState| {
+                true
+            },
+            transition: |input: (), s: ClusterState| {
+                (ClusterState {
+                    resp_drop_enabled: false,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
+    // The duplicate_req sends another copy of a request that is in flight to the API server.
+    // This is used to model the retries by the client transport, for example, HTTP/2 retries
+    // a request on a connection that has been closed, and client-go retries requests that fail
+    // with some errors (see https://github.com/kubernetes/client-go/blob/v0.30.0/rest/with_retry.go#L68).
+    // The API server might then handle the same request twice, and in any order with the other
+    // requests in flight (a delayed duplicate can take effect after the sender has moved on).
+    //
+    // The copy gets a new rpc_id from the rpc_id_allocator. The client only returns one response
+    // to the sender for each request, so the response to the copy never matches the request
+    // the sender is waiting for, and it is dropped by the sender like any other stale response.
+    // This also keeps the rpc_id of each message in flight unique.
+    pub open spec fn duplicate_req(self) -> Action<ClusterState, Message, ()> {
+        let result = |input: Message, s: ClusterState| {
+            let req_msg = input;
+            let (rpc_id_allocator_prime, rpc_id) = s.rpc_id_allocator.allocate();
+            let dup_req_msg = form_msg(req_msg.src, req_msg.dst, rpc_id, req_msg.content);
+            let msg_ops = MessageOps {
+                recv: None,
+                send: Multiset::singleton(dup_req_msg),
+            };
+            let network_result = network().next_result(msg_ops, s.network);
+            (rpc_id_allocator_prime, network_result)
+        };
+        Action {
+            precondition: |input: Message, s: ClusterState| {
+                let req_msg = input;
+                &&& s.req_dup_enabled
+                &&& req_msg.dst is APIServer
+                &&& req_msg.content is APIRequest
+                &&& s.in_flight().contains(req_msg)
+                &&& result(input, s).1 is Enabled
+            },
+            transition: |input: Message, s: ClusterState| {
+                let (rpc_id_allocator_prime, network_result) = result(input, s);
+                (ClusterState {
+                    network: network_result->Enabled_0,
+                    rpc_id_allocator: rpc_id_allocator_prime,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
+    // The disable_req_dup disables the client transport from duplicating the request messages.
+    // This is used to constrain the network failures for proving liveness:
+    // the network eventually stops duplicating request messages.
+    pub open spec fn disable_req_dup(self) -> Action<ClusterState, (), ()> {
+        Action {
+            precondition: |input:(), s: ClusterState| {
+                true
+            },
+            transition: |input: (), s: ClusterState| {
+                (ClusterState {
+                    req_dup_enabled: false,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
     // The pod_monkey_next randomly creates, updates, or deletes a pod. This is used
     // to model any event that affects pods but not issued by controllers, including:
     // app container failures that cause pod termination (deletion), node failures or
@@ -520,7 +687,8 @@ impl Cluster {
         }
     }
 
-    // The disable_pod_monkey disables the pod monkey from touching any pod.
+    // The disable_pod_monkey disables the pod monkey from touching any pod,
+    // the kubelet from failing pods (see fail_pod) and the scheduler from binding pods (see schedule_pod).
     // This is used to constrain the pod monkey events for proving liveness of
     // the controllers that directly manage pods.
     pub open spec fn disable_pod_monkey(self) -> Action<ClusterState, (), ()> {
@@ -537,6 +705,61 @@ impl Cluster {
         }
     }
 
+    // The node_failure_next fails the node with the given name, which is removed from the cluster
+    // (as the node object is deleted when the machine is gone, e.g., by the cloud node controller).
+    // The pods bound to the failed node are then evicted by the node lifecycle controller (see evict_pod),
+    // and the controllers that manage these pods need to recreate them on other nodes.
+    // Eviction is a consequence of node failures, so it is disabled together with them (see disable_node_failure).
+    //
+    // Note that the data on the failed node is lost together with the node, which is NOT modeled:
+    // a PVC bound to a local volume on the failed node stays in etcd and still looks usable,
+    // so the model does not capture that a stateful pod recreated on another node cannot get its data back.
+    pub open spec fn node_failure_next(self) -> Action<ClusterState, StringView, ()> {
+        let result = |input: StringView, s: ClusterState| {
+            let (rpc_id_allocator_prime, rpc_id) = s.rpc_id_allocator.allocate();
+            let delete_req_msg = node_monkey_req_msg(rpc_id, delete_req_msg_content(node_key(input), None));
+            let msg_ops = MessageOps {
+                recv: None,
+                send: Multiset::singleton(delete_req_msg),
+            };
+            let network_result = network().next_result(msg_ops, s.network);
+            (rpc_id_allocator_prime, network_result)
+        };
+        Action {
+            precondition: |input: StringView, s: ClusterState| {
+                &&& s.node_failure_enabled
+                &&& s.resources().contains_key(node_key(input))
+                &&& result(input, s).1 is Enabled
+            },
+            transition: |input: StringView, s: ClusterState| {
+                let (rpc_id_allocator_prime, network_result) = result(input, s);
+                (ClusterState {
+                    network: network_result->Enabled_0,
+                    rpc_id_allocator: rpc_id_allocator_prime,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
+    // The disable_node_failure disables the nodes from failing,
+    // and the node lifecycle controller from evicting pods (see evict_pod).
+    // This is used to constrain the node failures for proving liveness:
+    // the cluster eventually stops losing nodes.
+    pub open spec fn disable_node_failure(self) -> Action<ClusterState, (), ()> {
+        Action {
+            precondition: |input:(), s: ClusterState| {
+                true
+            },
+            transition: |input: (), s: ClusterState| {
+                (ClusterState {
+                    node_failure_enabled: false,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
     // The external_next models the external system that a controller interacts with.
     // The modelling assumes that the interaction is based on RPC.
     // It chooses one external system from controller_models and run it.
@@ -594,6 +817,145 @@ impl Cluster {
         }
     }
 
+    // The external_failure intercepts a request sent to an external system and returns the error
+    // chosen by the step (Timeout or TransportError) to the controller instead of the response.
+    // Whether the request has taken effect is also chosen by the step: the external system may
+    // have handled the request before the call fails (e.g., the shim layer gives up waiting for it),
+    // so the state of the external system may or may not change as the transition of its model does.
+    pub open spec fn external_failure(self) -> Action<ClusterState, (Message, ExternalError, bool), ()> {
+        let result = |input: (Message, ExternalError, bool), s: ClusterState| {
+            let req_msg = input.0;
+            let controller_id = req_msg.dst->External_0;
+            let external_state = s.controller_and_externals[controller_id].external->0;
+            let external_state_prime = if input.2 {
+                transition_by_external(self.controller_models[controller_id].external_model->0, req_msg, s.api_server.resources, external_state).0
+            } else {
+                external_state
+            };
+            let resp = form_external_resp_msg(req_msg, Err(input.1));
+            let msg_ops = MessageOps {
+                recv: Some(req_msg),
+                send: Multiset::singleton(resp),
+            };
+            (external_state_prime, network().next_result(msg_ops, s.network))
+        };
+        Action {
+            precondition: |input: (Message, ExternalError, bool), s: ClusterState| {
+                let req_msg = input.0;
+                let controller_id = req_msg.dst->External_0;
+                &&& s.external_failure_enabled
+                &&& req_msg.dst is External
+                &&& req_msg.content is ExternalRequest
+                &&& self.controller_models.contains_key(controller_id)
+                &&& self.controller_models[controller_id].external_model is Some
+                &&& result(input, s).1 is Enabled
+            },
+            transition: |input: (Message, ExternalError, bool), s: ClusterState| {
+                let controller_id = input.0.dst->External_0;
+                let (external_state_prime, network_result) = result(input, s);
+                let controller_and_external_state_prime = ControllerAndExternalState {
+                    external: Some(external_state_prime),
+                    ..s.controller_and_externals[controller_id]
+                };
+                (ClusterState {
+                    controller_and_externals: s.controller_and_externals.insert(controller_id, controller_and_external_state_prime),
+                    network: network_result->Enabled_0,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
+    // The disable_external_failure disables the requests to the external systems from failing.
+    // This is used to constrain the external failures for proving liveness:
+    // the external systems eventually stop failing the requests.
+    pub open spec fn disable_external_failure(self) -> Action<ClusterState, (), ()> {
+        Action {
+            precondition: |input:(), s: ClusterState| {
+                true
+            },
+            transition: |input: (), s: ClusterState| {
+                (ClusterState {
+                    external_failure_enabled: false,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
+    // The sync_cache brings the cache of one controller up to date with the resources in the API server.
+    // It models the watches of the controller, which receive the events of the API server and apply
+    // them to the cache in order. Between two sync_cache steps, the cache keeps the resources at the
+    // time of the last sync_cache, and since sync_cache can happen at any time, a read from the cache
+    // can return any past state of the resources that is not older than the state the controller
+    // has read before from the cache.
+    //
+    // Note that to keep things simple, all the kinds in the cache are synced together, so the controller
+    // never observes two kinds at two different points of time. In a real cluster each watch (one for
+    // each kind and namespace) lags by a different amount, which is not modeled here.
+    pub open spec fn sync_cache(self) -> Action<ClusterState, int, ()> {
+        Action {
+            precondition: |input: int, s: ClusterState| {
+                let controller_id = input;
+                &&& self.controller_models.contains_key(controller_id)
+                &&& s.controller_and_externals[controller_id].cache is Some
+            },
+            transition: |input: int, s: ClusterState| {
+                let controller_id = input;
+                let controller_and_external_state_prime = ControllerAndExternalState {
+                    cache: Some(s.resources()),
+                    ..s.controller_and_externals[controller_id]
+                };
+                (ClusterState {
+                    controller_and_externals: s.controller_and_externals.insert(controller_id, controller_and_external_state_prime),
+                    ..s
+                }, ())
+            },
+        }
+    }
+
+    // The cached_read serves a Get or List request with the Cached read mode from the cache of the
+    // controller that sends the request, instead of from the resources in the API server.
+    // A Cached read can still be served by the API server (api_server_next), which is the same as
+    // reading from a cache that is up to date, so the controller cannot tell whether the response
+    // is stale or not. Quorum reads, and all the reads of a controller without a cache, are only
+    // served by the API server.
+    pub open spec fn cached_read(self) -> Action<ClusterState, Message, ()> {
+        let result = |input: Message, s: ClusterState| {
+            let controller_id = input.src->Controller_0;
+            let cached_api_server_state = APIServerState {
+                resources: s.controller_and_externals[controller_id].cache->0,
+                ..s.api_server
+            };
+            let resp = if input.content.is_get_request() {
+                handle_get_request_msg(input, cached_api_server_state).1
+            } else {
+                handle_list_request_msg(input, cached_api_server_state).1
+            };
+            let msg_ops = MessageOps {
+                recv: Some(input),
+                send: Multiset::singleton(resp),
+            };
+            let result = network().next_result(msg_ops, s.network);
+            result
+        };
+        Action {
+            precondition: |input: Message, s: ClusterState| {
+                &&& input.src is Controller
+                &&& self.controller_models.contains_key(input.src->Controller_0)
+                &&& s.controller_and_externals[input.src->Controller_0].cache is Some
+                &&& cached_read_request_msg(input)
+                &&& result(input, s) is Enabled
+            },
+            transition: |input: Message, s: ClusterState| {
+                (ClusterState {
+                    network: result(input, s)->Enabled_0,
+                    ..s
+                }, ())
+            }
+        }
+    }
+
     // The stutter step does nothing.
     // It's used to ensure that always(next) holds.
     pub open spec fn stutter(self) -> Action<ClusterState, (), ()> {
@@ -659,6 +1021,9 @@ impl Cluster {
             };
             let network_result = network().next_result(msg_ops, s.network);
 
+            &&& input.0 is KubeletFailure ==> s.pod_monkey_enabled
+            &&& input.0 is Scheduler ==> s.pod_monkey_enabled
+            &&& input.0 is NodeLifecycle ==> s.node_failure_enabled
             &&& host_result is Enabled
             &&& network_result is Enabled
         }
@@ -713,7 +1078,7 @@ impl Cluster {
     }
 
     pub open spec fn builtin_controllers(self) -> BuiltinControllersStateMachine {
-        builtin_controllers()
+        builtin_controllers(self.installed_types)
     }
 
     pub open spec fn controller(self, controller_id: int) -> ControllerStateMachine {
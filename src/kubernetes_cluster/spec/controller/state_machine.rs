@@ -2,11 +2,67 @@ use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::{controller::types::*, message::*};
 use crate::state_machine::action::*;
 use crate::state_machine::state_machine::*;
+use crate::vstd_ext::string_view::*;
 use vstd::{multiset::*, prelude::*};
 
 verus! {
 
-pub open spec fn run_scheduled_reconcile(model: ReconcileModel) -> ControllerAction {
+// finalizer_update_req returns the request that adds (if add) or removes (if !add) the finalizer of model
+// to or from cr, or None if cr already carries (or does not carry) the finalizer.
+// The finalizer is only added to a custom resource without a deletion timestamp,
+// as the API server forbids adding finalizers to a terminating object.
+pub open spec fn finalizer_update_req(model: ReconcileModel, cr: DynamicObjectView, add: bool) -> Option<UpdateRequest> {
+    if model.finalizer is None {
+        None
+    } else {
+        let finalizer = model.finalizer->0;
+        let finalizers = if cr.metadata.finalizers is Some { cr.metadata.finalizers->0 } else { Seq::empty() };
+        if add && cr.metadata.deletion_timestamp is None && !cr.metadata.finalizers_as_set().contains(finalizer) {
+            Some(UpdateRequest {
+                namespace: cr.metadata.namespace->0,
+                name: cr.metadata.name->0,
+                obj: cr.with_metadata(cr.metadata.with_finalizers(finalizers.push(finalizer))),
+            })
+        } else if !add && cr.metadata.deletion_timestamp is Some && cr.metadata.finalizers_as_set().contains(finalizer) {
+            Some(UpdateRequest {
+                namespace: cr.metadata.namespace->0,
+                name: cr.metadata.name->0,
+                obj: cr.with_metadata(cr.metadata.with_finalizers(finalizers.filter(|f: StringView| f != finalizer))),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+// finalizer_update_output sends the request returned by finalizer_update_req (if any) without waiting for its response.
+// If the request fails (e.g., because of a conflict), the next reconcile of cr sends it again.
+pub open spec fn finalizer_update_output(model: ReconcileModel, controller_id: int, cr_key: ObjectRef, cr: DynamicObjectView, add: bool, rpc_id_allocator: RPCIdAllocator) -> ControllerActionOutput {
+    let req_o = finalizer_update_req(model, cr, add);
+    if req_o is Some {
+        let req_msg = controller_req_msg(controller_id, cr_key, rpc_id_allocator.allocate().1, APIRequest::UpdateRequest(req_o->0));
+        ControllerActionOutput {
+            send: Multiset::singleton(req_msg),
+            rpc_id_allocator: rpc_id_allocator.allocate().0,
+        }
+    } else {
+        ControllerActionOutput {
+            send: Multiset::empty(),
+            rpc_id_allocator: rpc_id_allocator,
+        }
+    }
+}
+
+// reconcile_transition applies delete_transition if cr is being deleted and model has a finalizer, or transition otherwise.
+pub open spec fn reconcile_transition(model: ReconcileModel, cr: DynamicObjectView, resp_o: Option<ResponseContent>, local_state: ReconcileLocalState) -> (ReconcileLocalState, Option<RequestContent>) {
+    if model.finalizer is Some && cr.metadata.deletion_timestamp is Some {
+        (model.delete_transition)(cr, resp_o, local_state)
+    } else {
+        (model.transition)(cr, resp_o, local_state)
+    }
+}
+
+pub open spec fn run_scheduled_reconcile(model: ReconcileModel, controller_id: int) -> ControllerAction {
     Action {
         precondition: |input: ControllerActionInput, s: ControllerState| {
             &&& input.scheduled_cr_key is Some
@@ -30,10 +86,9 @@ pub open spec fn run_scheduled_reconcile(model: ReconcileModel) -> ControllerAct
                 reconcile_id_allocator: new_allocator,
                 ..s
             };
-            let output = ControllerActionOutput {
-                send: Multiset::empty(),
-                rpc_id_allocator: input.rpc_id_allocator,
-            };
+            let output = finalizer_update_output(
+                model, controller_id, cr_key, s.scheduled_reconciles[cr_key], true, input.rpc_id_allocator
+            );
             (s_prime, output)
         },
     }
@@ -72,7 +127,7 @@ pub open spec fn continue_reconcile(model: ReconcileModel, controller_id: int) -
             } else {
                 None
             };
-            let (local_state_prime, req_o) = (model.transition)(reconcile_state.triggering_cr, resp_o, reconcile_state.local_state);
+            let (local_state_prime, req_o) = reconcile_transition(model, reconcile_state.triggering_cr, resp_o, reconcile_state.local_state);
             let (pending_req_msg, send, rpc_id_allocator_prime) = if req_o is Some {
                 let pending_req_msg = match req_o->0 {
                     RequestContent::KubernetesRequest(req) => {
@@ -105,7 +160,7 @@ pub open spec fn continue_reconcile(model: ReconcileModel, controller_id: int) -
     }
 }
 
-pub open spec fn end_reconcile(model: ReconcileModel) -> ControllerAction {
+pub open spec fn end_reconcile(model: ReconcileModel, controller_id: int) -> ControllerAction {
     Action {
         precondition: |input: ControllerActionInput, s: ControllerState| {
             if input.scheduled_cr_key is Some {
@@ -121,13 +176,19 @@ pub open spec fn end_reconcile(model: ReconcileModel) -> ControllerAction {
         },
         transition: |input: ControllerActionInput, s: ControllerState| {
             let cr_key = input.scheduled_cr_key->0;
+            let reconcile_state = s.ongoing_reconciles[cr_key];
             let s_prime = ControllerState {
                 ongoing_reconciles: s.ongoing_reconciles.remove(cr_key),
                 ..s
             };
-            let output = ControllerActionOutput {
-                send: Multiset::empty(),
-                rpc_id_allocator: input.rpc_id_allocator,
+            // The finalizer is removed only after the clean up by delete_transition is done
+            let output = if (model.done)(reconcile_state.local_state) {
+                finalizer_update_output(model, controller_id, cr_key, reconcile_state.triggering_cr, false, input.rpc_id_allocator)
+            } else {
+                ControllerActionOutput {
+                    send: Multiset::empty(),
+                    rpc_id_allocator: input.rpc_id_allocator,
+                }
             };
             (s_prime, output)
         }
@@ -146,15 +207,15 @@ pub open spec fn controller(model: ReconcileModel, controller_id: int) -> Contro
             }
         },
         actions: set![
-            run_scheduled_reconcile(model),
+            run_scheduled_reconcile(model, controller_id),
             continue_reconcile(model, controller_id),
-            end_reconcile(model)
+            end_reconcile(model, controller_id)
         ],
         step_to_action: |step: ControllerStep| {
             match step {
-                ControllerStep::RunScheduledReconcile => run_scheduled_reconcile(model),
+                ControllerStep::RunScheduledReconcile => run_scheduled_reconcile(model, controller_id),
                 ControllerStep::ContinueReconcile => continue_reconcile(model, controller_id),
-                ControllerStep::EndReconcile => end_reconcile(model),
+                ControllerStep::EndReconcile => end_reconcile(model, controller_id),
             }
         },
         action_input: |step: ControllerStep, input: ControllerActionInput| {
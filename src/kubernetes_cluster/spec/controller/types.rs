@@ -2,6 +2,7 @@ use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::message::*;
 use crate::state_machine::action::*;
 use crate::state_machine::state_machine::*;
+use crate::vstd_ext::string_view::*;
 use vstd::{multiset::*, prelude::*};
 
 verus! {
@@ -51,12 +52,18 @@ impl ReconcileIdAllocator {
     }
 }
 
+// ReconcileModel is the model of the reconciler (see installed_reconcile_model).
+// If finalizer is not None, the controller adds it to the custom resource,
+// runs delete_transition instead of transition once the custom resource has a deletion timestamp,
+// and removes the finalizer after delete_transition is done.
 pub struct ReconcileModel {
     pub kind: Kind,
     pub init: spec_fn() -> ReconcileLocalState,
     pub transition: spec_fn(DynamicObjectView, Option<ResponseContent>, ReconcileLocalState) -> (ReconcileLocalState, Option<RequestContent>),
     pub done: spec_fn(ReconcileLocalState) -> bool,
     pub error: spec_fn(ReconcileLocalState) -> bool,
+    pub finalizer: Option<StringView>,
+    pub delete_transition: spec_fn(DynamicObjectView, Option<ResponseContent>, ReconcileLocalState) -> (ReconcileLocalState, Option<RequestContent>),
 }
 
 pub struct OngoingReconcile {
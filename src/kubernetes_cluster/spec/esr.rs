@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MIT
 use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::cluster::*;
+use crate::vstd_ext::string_view::*;
 use verus_temporal_logic::defs::*;
 use vstd::prelude::*;
 
@@ -45,6 +46,40 @@ pub open spec fn eventually_stable_reconciliation_per_cr<T: CustomResourceView>(
     always(lift_state(Self::desired_state_is(cr))).leads_to(always(lift_state(current_state_matches(cr))))
 }
 
+// deletion_is_requested says that the object that has the same key and uid with cr exists in etcd,
+// has a deletion timestamp and still carries the finalizer, i.e., the controller has not finished cleaning up for cr.
+pub open spec fn deletion_is_requested<T: CustomResourceView>(cr: T, finalizer: StringView) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        &&& s.resources().contains_key(cr.object_ref())
+        &&& s.resources()[cr.object_ref()].metadata.uid == cr.metadata().uid
+        &&& s.resources()[cr.object_ref()].metadata.deletion_timestamp is Some
+        &&& s.resources()[cr.object_ref()].metadata.finalizers_as_set().contains(finalizer)
+    }
+}
+
+// finalizer_is_removed says that cr no longer carries the finalizer: either it is gone from etcd
+// (or replaced by another object with a different uid), or its finalizer has been removed.
+pub open spec fn finalizer_is_removed<T: CustomResourceView>(cr: T, finalizer: StringView) -> StatePred<ClusterState> {
+    |s: ClusterState| {
+        s.resources().contains_key(cr.object_ref()) && s.resources()[cr.object_ref()].metadata.uid == cr.metadata().uid
+        ==> !s.resources()[cr.object_ref()].metadata.finalizers_as_set().contains(finalizer)
+    }
+}
+
+// eventually_finalized is the liveness property expected of the deletion path: once the deletion of cr is requested,
+// the controller eventually runs reconcile_delete to the end and removes its finalizer,
+// so that the API server can delete cr.
+// Similar to eventually_stable_reconciliation, a controller usually needs some assumption on the environment
+// (e.g., other controllers do not keep touching the objects it cleans up) to establish this property,
+// which is conjoined to deletion_is_requested by the controller's own theorem.
+pub open spec fn eventually_finalized<T: CustomResourceView>(finalizer: StringView) -> TempPred<ClusterState> {
+    tla_forall(|cr: T| lift_state(Self::deletion_is_requested(cr, finalizer)).leads_to(lift_state(Self::finalizer_is_removed(cr, finalizer))))
+}
+
+pub open spec fn eventually_finalized_per_cr<T: CustomResourceView>(cr: T, finalizer: StringView) -> TempPred<ClusterState> {
+    lift_state(Self::deletion_is_requested(cr, finalizer)).leads_to(lift_state(Self::finalizer_is_removed(cr, finalizer)))
+}
+
 }
 
 }
@@ -12,6 +12,11 @@ pub struct ExternalState {
     pub state: ExternalLocalState,
 }
 
+// ExternalModel models the external system that a controller talks to.
+// The transition models how the external system handles a request that goes through.
+// The requests that fail with an ExternalError are modeled by external_failure in the cluster,
+// which may or may not apply the transition: the shim layer gives up on a call that takes too long
+// and returns Timeout even if the external system has already handled it.
 pub struct ExternalModel {
     pub init: spec_fn() -> ExternalLocalState,
     pub transition: spec_fn(ExternalRequest, ExternalLocalState, StoredState) -> (ExternalLocalState, ExternalResponse),
@@ -13,6 +13,7 @@ impl Cluster {
 
 pub open spec fn installed_type<T: CustomResourceView>() -> InstalledType {
     InstalledType {
+        scope: T::scope(),
         unmarshallable_spec: |v: Value| T::unmarshal_spec(v) is Ok,
         unmarshallable_status: |v: Value| T::unmarshal_status(v) is Ok,
         valid_object: |obj: DynamicObjectView| T::unmarshal(obj)->Ok_0.state_validation(),
@@ -39,26 +40,43 @@ pub open spec fn installed_reconcile_model<R, S, K, EReq, EResp>() -> ReconcileM
         kind: K::kind(),
         init: || R::reconcile_init_state().marshal(),
         transition: |obj, resp_o, s| {
-            let obj_um = K::unmarshal(obj)->Ok_0;
-            let resp_o_um = match resp_o {
-                None => None,
-                Some(resp) => Some(match resp {
-                    ResponseContent::KubernetesResponse(api_resp) => ResponseView::<EResp>::KResponse(api_resp),
-                    ResponseContent::ExternalResponse(ext_resp) => ResponseView::<EResp>::ExternalResponse(EResp::unmarshal(ext_resp)->Ok_0),
-                })
-            };
-            let s_um = S::unmarshal(s)->Ok_0;
-            let (s_prime_um, req_o_um) = R::reconcile_core(obj_um, resp_o_um, s_um);
-            (s_prime_um.marshal(), match req_o_um {
-                None => None,
-                Some(req) => Some(match req {
-                    RequestView::<EReq>::KRequest(api_req) => RequestContent::KubernetesRequest(api_req),
-                    RequestView::<EReq>::ExternalRequest(ext_req) => RequestContent::ExternalRequest(ext_req.marshal()),
-                })
-            })
+            let (s_prime_um, req_o_um) = R::reconcile_core(
+                K::unmarshal(obj)->Ok_0, Self::unmarshal_resp_o::<EResp>(resp_o), S::unmarshal(s)->Ok_0
+            );
+            (s_prime_um.marshal(), Self::marshal_req_o::<EReq>(req_o_um))
         },
         done: |s| R::reconcile_done(S::unmarshal(s)->Ok_0),
         error: |s| R::reconcile_error(S::unmarshal(s)->Ok_0),
+        finalizer: R::finalizer(),
+        delete_transition: |obj, resp_o, s| {
+            let (s_prime_um, req_o_um) = R::reconcile_delete(
+                K::unmarshal(obj)->Ok_0, Self::unmarshal_resp_o::<EResp>(resp_o), S::unmarshal(s)->Ok_0
+            );
+            (s_prime_um.marshal(), Self::marshal_req_o::<EReq>(req_o_um))
+        },
+    }
+}
+
+pub open spec fn unmarshal_resp_o<EResp: Marshallable>(resp_o: Option<ResponseContent>) -> Option<ResponseView<EResp>> {
+    match resp_o {
+        None => None,
+        Some(resp) => Some(match resp {
+            ResponseContent::KubernetesResponse(api_resp) => ResponseView::<EResp>::KResponse(api_resp),
+            ResponseContent::ExternalResponse(ext_resp) => ResponseView::<EResp>::ExternalResponse(match ext_resp {
+                Ok(v) => Ok(EResp::unmarshal(v)->Ok_0),
+                Err(err) => Err(err),
+            }),
+        })
+    }
+}
+
+pub open spec fn marshal_req_o<EReq: Marshallable>(req_o: Option<RequestView<EReq>>) -> Option<RequestContent> {
+    match req_o {
+        None => None,
+        Some(req) => Some(match req {
+            RequestView::<EReq>::KRequest(api_req) => RequestContent::KubernetesRequest(api_req),
+            RequestView::<EReq>::ExternalRequest(ext_req) => RequestContent::ExternalRequest(ext_req.marshal()),
+        })
     }
 }
 
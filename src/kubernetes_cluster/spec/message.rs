@@ -1,5 +1,6 @@
 use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::kubernetes_api_objects::spec::label_selector::*;
 use crate::vstd_ext::string_view::*;
 use vstd::{multiset::*, prelude::*};
 
@@ -9,7 +10,9 @@ pub type RPCId = nat;
 
 pub type ExternalRequest = Value;
 
-pub type ExternalResponse = Value;
+// ExternalResponse is either the (marshalled) response from the external system,
+// or the error if the request fails before the external system responds (e.g., it times out).
+pub type ExternalResponse = Result<Value, ExternalError>;
 
 pub struct MessageOps {
     pub recv: Option<Message>,
@@ -29,6 +32,7 @@ pub enum HostId {
     Controller(int, ObjectRef),
     External(int),
     PodMonkey,
+    NodeMonkey,
 }
 
 // RPCIdAllocator allocates unique RPCId for each request sent by the controller.
@@ -76,6 +80,7 @@ pub open spec fn is_ok_resp(resp: APIResponse) -> bool {
         APIResponse::GetThenDeleteResponse(resp) => resp.res is Ok,
         APIResponse::GetThenUpdateResponse(resp) => resp.res is Ok,
         APIResponse::GetThenUpdateStatusResponse(resp) => resp.res is Ok,
+        APIResponse::PatchResponse(resp) => resp.res is Ok,
     }
 }
 
@@ -95,6 +100,10 @@ pub open spec fn pod_monkey_req_msg(rpc_id: RPCId, msg_content: MessageContent)
     form_msg(HostId::PodMonkey, HostId::APIServer, rpc_id, msg_content)
 }
 
+pub open spec fn node_monkey_req_msg(rpc_id: RPCId, msg_content: MessageContent) -> Message {
+    form_msg(HostId::NodeMonkey, HostId::APIServer, rpc_id, msg_content)
+}
+
 pub open spec fn resp_msg_matches_req_msg(resp_msg: Message, req_msg: Message) -> bool {
     ||| {
         &&& resp_msg.content is APIResponse
@@ -112,6 +121,7 @@ pub open spec fn resp_msg_matches_req_msg(resp_msg: Message, req_msg: Message) -
             APIResponse::GetThenDeleteResponse(_) => req_msg.content->APIRequest_0 is GetThenDeleteRequest,
             APIResponse::GetThenUpdateResponse(_) => req_msg.content->APIRequest_0 is GetThenUpdateRequest,
             APIResponse::GetThenUpdateStatusResponse(_) => req_msg.content->APIRequest_0 is GetThenUpdateStatusRequest,
+            APIResponse::PatchResponse(_) => req_msg.content->APIRequest_0 is PatchRequest,
         }
     }
     ||| {
@@ -128,7 +138,7 @@ pub open spec fn form_matched_err_resp_msg(req_msg: Message, err: APIError) -> M
 {
     match req_msg.content->APIRequest_0 {
         APIRequest::GetRequest(_) => form_get_resp_msg(req_msg, GetResponse{res: Err(err)}),
-        APIRequest::ListRequest(_) => form_list_resp_msg(req_msg, ListResponse{res: Err(err)}),
+        APIRequest::ListRequest(_) => form_list_resp_msg(req_msg, ListResponse{res: Err(err), continue_token: None}),
         APIRequest::CreateRequest(_) => form_create_resp_msg(req_msg, CreateResponse{res: Err(err)}),
         APIRequest::DeleteRequest(_) => form_delete_resp_msg(req_msg, DeleteResponse{res: Err(err)}),
         APIRequest::UpdateRequest(_) => form_update_resp_msg(req_msg, UpdateResponse{res: Err(err)}),
@@ -136,9 +146,30 @@ pub open spec fn form_matched_err_resp_msg(req_msg: Message, err: APIError) -> M
         APIRequest::GetThenDeleteRequest(_) => form_get_then_delete_resp_msg(req_msg, GetThenDeleteResponse{res: Err(err)}),
         APIRequest::GetThenUpdateRequest(_) => form_get_then_update_resp_msg(req_msg, GetThenUpdateResponse{res: Err(err)}),
         APIRequest::GetThenUpdateStatusRequest(_) => form_get_then_update_status_resp_msg(req_msg, GetThenUpdateStatusResponse{res: Err(err)}),
+        APIRequest::PatchRequest(_) => form_patch_resp_msg(req_msg, PatchResponse{res: Err(err)}),
     }
 }
 
+// Similar to form_matched_err_resp_msg, but forms the error response from a response,
+// so the error response matches the same request as the given response.
+pub open spec fn form_err_resp_msg_replacing(resp_msg: Message, err: APIError) -> Message
+    recommends resp_msg.content is APIResponse,
+{
+    let resp = match resp_msg.content->APIResponse_0 {
+        APIResponse::GetResponse(_) => APIResponse::GetResponse(GetResponse{res: Err(err)}),
+        APIResponse::ListResponse(_) => APIResponse::ListResponse(ListResponse{res: Err(err), continue_token: None}),
+        APIResponse::CreateResponse(_) => APIResponse::CreateResponse(CreateResponse{res: Err(err)}),
+        APIResponse::DeleteResponse(_) => APIResponse::DeleteResponse(DeleteResponse{res: Err(err)}),
+        APIResponse::UpdateResponse(_) => APIResponse::UpdateResponse(UpdateResponse{res: Err(err)}),
+        APIResponse::UpdateStatusResponse(_) => APIResponse::UpdateStatusResponse(UpdateStatusResponse{res: Err(err)}),
+        APIResponse::GetThenDeleteResponse(_) => APIResponse::GetThenDeleteResponse(GetThenDeleteResponse{res: Err(err)}),
+        APIResponse::GetThenUpdateResponse(_) => APIResponse::GetThenUpdateResponse(GetThenUpdateResponse{res: Err(err)}),
+        APIResponse::GetThenUpdateStatusResponse(_) => APIResponse::GetThenUpdateStatusResponse(GetThenUpdateStatusResponse{res: Err(err)}),
+        APIResponse::PatchResponse(_) => APIResponse::PatchResponse(PatchResponse{res: Err(err)}),
+    };
+    form_msg(resp_msg.src, resp_msg.dst, resp_msg.rpc_id, MessageContent::APIResponse(resp))
+}
+
 pub open spec fn form_msg(src: HostId, dst: HostId, rpc_id: RPCId, msg_content: MessageContent) -> Message {
     Message {
         src: src,
@@ -157,13 +188,19 @@ pub open spec fn form_external_resp_msg(req_msg: Message, resp: ExternalResponse
 pub open spec fn get_req_msg_content(key: ObjectRef) -> MessageContent {
     MessageContent::APIRequest(APIRequest::GetRequest(GetRequest{
         key: key,
+        read_mode: ReadMode::Quorum,
     }))
 }
 
-pub open spec fn list_req_msg_content(kind: Kind, namespace: StringView) -> MessageContent {
+pub open spec fn list_req_msg_content(kind: Kind, namespace: StringView, label_selector: Option<LabelSelectorView>, field_selector: Option<Map<StringView, StringView>>) -> MessageContent {
     MessageContent::APIRequest(APIRequest::ListRequest(ListRequest{
         kind: kind,
         namespace: namespace,
+        label_selector: label_selector,
+        field_selector: field_selector,
+        read_mode: ReadMode::Quorum,
+        limit: None,
+        continue_token: None,
     }))
 }
 
@@ -175,9 +212,14 @@ pub open spec fn create_req_msg_content(namespace: StringView, obj: DynamicObjec
 }
 
 pub open spec fn delete_req_msg_content(key: ObjectRef, preconditions: Option<PreconditionsView>) -> MessageContent {
+    delete_req_msg_content_with_policy(key, preconditions, None)
+}
+
+pub open spec fn delete_req_msg_content_with_policy(key: ObjectRef, preconditions: Option<PreconditionsView>, propagation_policy: Option<PropagationPolicy>) -> MessageContent {
     MessageContent::APIRequest(APIRequest::DeleteRequest(DeleteRequest{
         key: key,
         preconditions: preconditions,
+        propagation_policy: propagation_policy,
     }))
 }
 
@@ -197,32 +239,44 @@ pub open spec fn update_status_req_msg_content(namespace: StringView, name: Stri
     }))
 }
 
-pub open spec fn get_then_delete_req_msg_content(key: ObjectRef, owner_ref: OwnerReferenceView) -> MessageContent {
+pub open spec fn get_then_delete_req_msg_content(key: ObjectRef, owner_ref: OwnerReferenceView, predicate: GetThenPredicate) -> MessageContent {
     MessageContent::APIRequest(APIRequest::GetThenDeleteRequest(GetThenDeleteRequest{
         key: key,
         owner_ref: owner_ref,
+        predicate: predicate,
     }))
 }
 
 
-pub open spec fn get_then_update_req_msg_content(namespace: StringView, name: StringView, owner_ref: OwnerReferenceView, obj: DynamicObjectView) -> MessageContent {
+pub open spec fn get_then_update_req_msg_content(namespace: StringView, name: StringView, owner_ref: OwnerReferenceView, predicate: GetThenPredicate, obj: DynamicObjectView) -> MessageContent {
     MessageContent::APIRequest(APIRequest::GetThenUpdateRequest(GetThenUpdateRequest{
         namespace: namespace,
         name: name,
         owner_ref: owner_ref,
+        predicate: predicate,
         obj: obj,
     }))
 }
 
-pub open spec fn get_then_update_status_req_msg_content(namespace: StringView, name: StringView, owner_ref: OwnerReferenceView, obj: DynamicObjectView) -> MessageContent {
+pub open spec fn get_then_update_status_req_msg_content(namespace: StringView, name: StringView, owner_ref: OwnerReferenceView, predicate: GetThenPredicate, obj: DynamicObjectView) -> MessageContent {
     MessageContent::APIRequest(APIRequest::GetThenUpdateStatusRequest(GetThenUpdateStatusRequest{
         namespace: namespace,
         name: name,
         owner_ref: owner_ref,
+        predicate: predicate,
         obj: obj,
     }))
 }
 
+pub open spec fn patch_req_msg_content(namespace: StringView, name: StringView, obj: DynamicObjectView, field_manager: Option<StringView>) -> MessageContent {
+    MessageContent::APIRequest(APIRequest::PatchRequest(PatchRequest{
+        namespace: namespace,
+        name: name,
+        obj: obj,
+        field_manager: field_manager,
+    }))
+}
+
 pub open spec fn api_request_msg_before(rpc_id: RPCId) -> spec_fn(Message) -> bool {
     |msg: Message| {
         &&& msg.rpc_id < rpc_id
@@ -230,6 +284,15 @@ pub open spec fn api_request_msg_before(rpc_id: RPCId) -> spec_fn(Message) -> bo
     }
 }
 
+// cached_read_request_msg is a Get or List request sent to the API server with the Cached read mode.
+pub open spec fn cached_read_request_msg(msg: Message) -> bool {
+    &&& msg.dst is APIServer
+    &&& {
+        ||| msg.content.is_get_request() && msg.content.get_get_request().read_mode is Cached
+        ||| msg.content.is_list_request() && msg.content.get_list_request().read_mode is Cached
+    }
+}
+
 pub open spec fn received_msg_destined_for(recv: Option<Message>, host_id: HostId) -> bool {
     if recv is Some {
         recv->0.dst == host_id
@@ -360,6 +423,13 @@ declare_message_content_req_helper_methods!(
     arrow_GetThenUpdateStatusRequest_0
 );
 
+declare_message_content_req_helper_methods!(
+    is_patch_request,
+    get_patch_request,
+    PatchRequest,
+    arrow_PatchRequest_0
+);
+
 declare_message_content_req_helper_methods_with_key!(
     is_delete_request_with_key,
     DeleteRequest,
@@ -396,6 +466,12 @@ declare_message_content_req_helper_methods_with_key!(
     arrow_GetThenUpdateStatusRequest_0
 );
 
+declare_message_content_req_helper_methods_with_key!(
+    is_patch_request_with_key,
+    PatchRequest,
+    arrow_PatchRequest_0
+);
+
 declare_message_content_resp_helper_methods!(
     is_get_response,
     get_get_response,
@@ -459,6 +535,13 @@ declare_message_content_resp_helper_methods!(
     arrow_GetThenUpdateStatusResponse_0
 );
 
+declare_message_content_resp_helper_methods!(
+    is_patch_response,
+    get_patch_response,
+    PatchResponse,
+    arrow_PatchResponse_0
+);
+
 macro_rules! declare_form_resp_msg_functions {
     ($fun:ident, $resp_type:ty) => {
         verus! {
@@ -487,6 +570,8 @@ declare_form_resp_msg_functions!(form_get_then_update_resp_msg, GetThenUpdateRes
 
 declare_form_resp_msg_functions!(form_get_then_update_status_resp_msg, GetThenUpdateStatusResponse);
 
+declare_form_resp_msg_functions!(form_patch_resp_msg, PatchResponse);
+
 macro_rules! declare_is_req_msg_functions {
     ($is_fun:ident, $is_req:ident, $get_req:ident) => {
         verus! {
@@ -535,6 +620,12 @@ declare_is_req_msg_functions!(
     get_get_then_update_status_request
 );
 
+declare_is_req_msg_functions!(
+    resource_patch_request_msg,
+    is_patch_request,
+    get_patch_request
+);
+
 verus! {
 
 pub open spec fn update_status_msg_from_bc_for(key: ObjectRef) -> spec_fn(Message) -> bool {
@@ -1,6 +1,7 @@
 #![allow(unused_imports)]
 
 pub mod crds;
+pub mod executable_model;
 
 pub mod external_shim_layer;
 pub mod kubernetes_api_objects;
@@ -1,3 +1,4 @@
+use crate::kubernetes_api_objects::error::ExternalError;
 use crate::kubernetes_api_objects::exec::api_method::*;
 use crate::reconciler::spec::io::*;
 use vstd::prelude::*;
@@ -45,9 +46,11 @@ impl<T: View> DeepView for Request<T> {
 // Anyway, the process method in the ExternalAPI, the input type in Request, output type in Response and the handling
 // of external response in reconcile_core are correlative.
 // Developers have the freedom to define them in their own preferred way as long as they make them work well.
+// If the request to the third-party library fails before the library responds (e.g., it times out),
+// the shim layer delivers an ExternalError instead.
 pub enum Response<T: View> {
     KResponse(KubeAPIResponse),
-    ExternalResponse(T),
+    ExternalResponse(Result<T, ExternalError>),
 }
 
 impl<T: View> View for Response<T> {
@@ -56,7 +59,10 @@ impl<T: View> View for Response<T> {
     open spec fn view(&self) -> ResponseView<T::V> {
         match self {
             Response::KResponse(resp) => ResponseView::KResponse(resp@),
-            Response::ExternalResponse(resp) => ResponseView::ExternalResponse(resp@),
+            Response::ExternalResponse(resp) => ResponseView::ExternalResponse(match resp {
+                Ok(resp) => Ok(resp@),
+                Err(err) => Err(*err),
+            }),
         }
     }
 }
@@ -80,7 +86,7 @@ impl <T: View> Response<T> {
         }
     }
 
-    pub fn as_external_response_ref(&self) -> (resp: &T)
+    pub fn as_external_response_ref(&self) -> (resp: &Result<T, ExternalError>)
         requires self is ExternalResponse,
         ensures resp == self->ExternalResponse_0,
     {
@@ -90,7 +96,7 @@ impl <T: View> Response<T> {
         }
     }
 
-    pub fn into_external_response(self) -> (resp: T)
+    pub fn into_external_response(self) -> (resp: Result<T, ExternalError>)
         requires self is ExternalResponse,
         ensures resp == self->ExternalResponse_0,
     {
@@ -172,6 +178,14 @@ macro_rules! is_some_k_update_resp {
     };
 }
 
+#[macro_export]
+macro_rules! is_some_k_update_status_resp {
+    ($r:expr) => {
+        $r.is_some() && $r.as_ref().unwrap().is_k_response()
+        && $r.as_ref().unwrap().as_k_response_ref().is_update_status_response()
+    };
+}
+
 #[macro_export]
 macro_rules! is_some_k_list_resp {
     ($r:expr) => {
@@ -233,6 +247,13 @@ macro_rules! extract_some_k_update_resp {
     };
 }
 
+#[macro_export]
+macro_rules! extract_some_k_update_status_resp {
+    ($r:expr) => {
+        $r.unwrap().into_k_response().into_update_status_response().res
+    };
+}
+
 #[macro_export]
 macro_rules! extract_some_k_list_resp {
     ($r:expr) => {
@@ -289,6 +310,13 @@ macro_rules! extract_some_k_update_resp_as_ref {
     };
 }
 
+#[macro_export]
+macro_rules! extract_some_k_update_status_resp_as_ref {
+    ($r:expr) => {
+        $r.as_ref().unwrap().as_k_response_ref().as_update_status_response_ref().res
+    };
+}
+
 #[macro_export]
 macro_rules! extract_some_k_list_resp_as_ref {
     ($r:expr) => {
@@ -327,6 +355,7 @@ macro_rules! extract_some_k_get_then_update_status_resp_as_ref {
 pub use is_some_k_get_resp;
 pub use is_some_k_create_resp;
 pub use is_some_k_update_resp;
+pub use is_some_k_update_status_resp;
 pub use is_some_k_list_resp;
 pub use is_some_k_delete_resp;
 pub use is_some_k_get_then_update_resp;
@@ -335,6 +364,7 @@ pub use is_some_k_get_then_update_status_resp;
 pub use extract_some_k_get_resp;
 pub use extract_some_k_create_resp;
 pub use extract_some_k_update_resp;
+pub use extract_some_k_update_status_resp;
 pub use extract_some_k_list_resp;
 pub use extract_some_k_delete_resp;
 pub use extract_some_k_get_then_update_resp;
@@ -343,6 +373,7 @@ pub use extract_some_k_get_then_update_status_resp;
 pub use extract_some_k_get_resp_as_ref;
 pub use extract_some_k_create_resp_as_ref;
 pub use extract_some_k_update_resp_as_ref;
+pub use extract_some_k_update_status_resp_as_ref;
 pub use extract_some_k_list_resp_as_ref;
 pub use extract_some_k_delete_resp_as_ref;
 pub use extract_some_k_get_then_update_resp_as_ref;
@@ -54,6 +54,19 @@ where
     // It conforms to the model's reconciler_error.
     fn reconcile_error(state: &Self::S) -> (res: bool)
         ensures res == Self::M::reconcile_error(state@);
+
+    // finalizer is the finalizer that controller_runtime adds to the custom resource
+    // so that reconcile_delete gets to run before the custom resource is deleted.
+    // It conforms to the model's finalizer.
+    fn finalizer() -> (finalizer: Option<String>)
+        ensures finalizer.deep_view() == Self::M::finalizer();
+
+    // reconcile_delete describes the cleanup logic, which controller_runtime invokes instead of reconcile_core
+    // when the custom resource has a deletion timestamp (and finalizer is not None).
+    // It conforms to the model's reconcile_delete.
+    fn reconcile_delete(cr: &Self::K, resp_o: Option<Response<Self::EResp>>, state: Self::S) -> (res: (Self::S, Option<Request<Self::EReq>>))
+        requires cr@.metadata().well_formed_for_namespaced() && cr@.state_validation(),
+        ensures (res.0@, res.1.deep_view()) == Self::M::reconcile_delete(cr@, resp_o.deep_view(), state@);
 }
 
 }
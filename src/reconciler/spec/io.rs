@@ -1,5 +1,5 @@
 use crate::kubernetes_api_objects::{
-    error::{UnmarshalError, APIError},
+    error::{UnmarshalError, APIError, ExternalError},
     spec::{api_method::*, resource::*, prelude::*},
 };
 use vstd::prelude::*;
@@ -37,9 +37,11 @@ pub enum RequestView<T> {
     ExternalRequest(T),
 }
 
+// An external response carries an ExternalError if the request to the external system fails
+// before the external system responds (see ExternalModel).
 pub enum ResponseView<T> {
     KResponse(APIResponse),
-    ExternalResponse(T),
+    ExternalResponse(Result<T, ExternalError>),
 }
 
 pub type DefaultResp = Option<ResponseView<VoidERespView>>;
@@ -92,6 +94,11 @@ pub open spec fn is_some_k_get_then_update_status_resp_view(resp_o: DefaultResp)
     resp_o is Some && resp_o->0 is KResponse && resp_o->0->KResponse_0 is GetThenUpdateStatusResponse
 }
 
+#[verifier(inline)]
+pub open spec fn is_some_k_patch_resp_view(resp_o: DefaultResp) -> bool {
+    resp_o is Some && resp_o->0 is KResponse && resp_o->0->KResponse_0 is PatchResponse
+}
+
 // should be called only when is_some_k_get_resp_view holds
 #[verifier(inline)]
 pub open spec fn extract_some_k_get_resp_view(resp_o: DefaultResp) -> Result<DynamicObjectView, APIError> {
@@ -145,4 +152,10 @@ pub open spec fn extract_some_k_get_then_update_status_resp_view(resp_o: Default
     resp_o->0->KResponse_0->GetThenUpdateStatusResponse_0.res
 }
 
+// should be called only when is_some_k_patch_resp_view holds
+#[verifier(inline)]
+pub open spec fn extract_some_k_patch_resp_view(resp_o: DefaultResp) -> Result<DynamicObjectView, APIError> {
+    resp_o->0->KResponse_0->PatchResponse_0.res
+}
+
 }
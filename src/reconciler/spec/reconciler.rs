@@ -1,5 +1,6 @@
 use crate::kubernetes_api_objects::spec::resource::*;
 use crate::reconciler::spec::io::*;
+use crate::vstd_ext::string_view::*;
 use vstd::prelude::*;
 
 verus! {
@@ -36,6 +37,18 @@ pub trait Reconciler<S, K: CustomResourceView, EReq, EResp> {
     // reconcile_error is used to tell the controller_runtime whether this reconcile round returns with error.
     // If it is true, controller_runtime will requeue the reconcile with a typically shorter waiting time.
     spec fn reconcile_error(state: S) -> bool;
+
+    // finalizer is the finalizer that controller_runtime adds to the custom resource so that the reconciler
+    // gets to clean up before the custom resource is deleted. None means the reconciler needs no cleanup
+    // (e.g., the objects it creates are owned by the custom resource and removed by the garbage collector).
+    spec fn finalizer() -> Option<StringView>;
+
+    // reconcile_delete describes the cleanup logic, which replaces reconcile_core when the custom resource
+    // has a deletion timestamp (and finalizer is not None).
+    // It starts from reconcile_init_state and ends with reconcile_done or reconcile_error as reconcile_core does.
+    // When it ends with reconcile_done, controller_runtime removes the finalizer so the deletion can complete.
+    // See vdeployment_controller::proof::liveness::finalize for a proof that this eventually happens.
+    spec fn reconcile_delete(cr: K, resp_o: Option<ResponseView<EResp>>, state: S) -> (S, Option<RequestView<EReq>>);
 }
 
 }
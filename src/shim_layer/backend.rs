@@ -0,0 +1,511 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::*, dynamic::*, resource::*};
+use crate::shim_layer::controller_runtime::{
+    api_in, dynamic_api_in, is_namespaced, kube_error_to_api_error, transactional_get_then_delete_by_retry,
+    transactional_get_then_update_by_retry, transactional_get_then_update_status_by_retry, ApiScope,
+};
+use core::fmt::Debug;
+use k8s_openapi::api::core::v1::{Namespace, Node, PersistentVolume};
+use k8s_openapi::api::rbac::v1::{ClusterRole, ClusterRoleBinding};
+use k8s_openapi::api::storage::v1::StorageClass;
+use kube::{
+    api::{Api, DeleteParams, ListParams, Patch, PatchParams, PostParams, Resource},
+    Client,
+};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use tracing::info;
+
+// APIServerBackend is the API server that reconcile_with talks to.
+// reconcile_with turns every KubeAPIRequest from reconcile_core into one handle_request call
+// and feeds the returned KubeAPIResponse back to reconcile_core.
+//
+// There are two implementations:
+// * KubeAPIServerBackend sends the requests to a real Kubernetes API server via kube-rs, and
+// * InMemoryAPIServerBackend (in shim_layer::in_memory_backend) handles the requests on an in-process
+//   object store following the semantics of crate::kubernetes_cluster::spec::api_server,
+//   so that reconcilers can be driven without a cluster.
+//
+// NamespaceScopedBackend wraps either of them to restrict the requests to a set of namespaces.
+pub trait APIServerBackend: Send + Sync {
+    // get_cr reads the custom resource that triggers the reconcile.
+    fn get_cr<K>(&self, namespace: &str, name: &str) -> impl Future<Output = Result<K, APIError>> + Send
+    where
+        K: Clone + Resource + DeserializeOwned + Debug + Send,
+        K::Scope: ApiScope,
+        K::DynamicType: Default;
+
+    // handle_request handles one request issued by reconcile_core and returns the corresponding response.
+    fn handle_request(&self, req: KubeAPIRequest, log_header: &str) -> impl Future<Output = KubeAPIResponse> + Send;
+}
+
+// KubeAPIServerBackend forwards each request to the Kubernetes API server through a kube-rs client.
+// If dry_run is set, the mutating requests are sent with server-side dry run,
+// so the API server validates and answers them without persisting any change.
+// cr_kind is the kind of the custom resource whose reconcile sends the requests (empty if there is none),
+// which labels the conflict retries of the Get-then-* requests (see inc_conflict_retry).
+pub struct KubeAPIServerBackend {
+    pub client: Client,
+    pub dry_run: bool,
+    pub cr_kind: String,
+}
+
+impl KubeAPIServerBackend {
+    fn post_params(&self) -> PostParams {
+        PostParams {
+            dry_run: self.dry_run,
+            ..PostParams::default()
+        }
+    }
+}
+
+impl APIServerBackend for KubeAPIServerBackend {
+    async fn get_cr<K>(&self, namespace: &str, name: &str) -> Result<K, APIError>
+    where
+        K: Clone + Resource + DeserializeOwned + Debug + Send,
+        K::Scope: ApiScope,
+        K::DynamicType: Default,
+    {
+        let cr_api = api_in::<K>(&self.client, &Some(namespace.to_string()));
+        // Get the custom resource by a quorum read to Kubernetes' storage (etcd) to get the most updated custom resource
+        cr_api.get(name).await.map_err(|err| kube_error_to_api_error(&err))
+    }
+
+    async fn handle_request(&self, req: KubeAPIRequest, log_header: &str) -> KubeAPIResponse {
+        let client = &self.client;
+        let log_header = log_header.to_string();
+        match req {
+            KubeAPIRequest::GetRequest(get_req) => {
+                let api = dynamic_api_in(client, &get_req.namespace, get_req.api_resource.as_kube_ref());
+                let key = get_req.key();
+                match api.get(&get_req.name).await {
+                    Err(err) => {
+                        info!("{} Get {} failed with error: {}", log_header, key, err);
+                        KubeAPIResponse::GetResponse(KubeGetResponse {
+                            res: Err(kube_error_to_api_error(&err)),
+                        })
+                    }
+                    Ok(obj) => {
+                        info!("{} Get {} done", log_header, key);
+                        KubeAPIResponse::GetResponse(KubeGetResponse {
+                            res: Ok(DynamicObject::from_kube(obj)),
+                        })
+                    }
+                }
+            }
+            KubeAPIRequest::ListRequest(list_req) => {
+                let api = dynamic_api_in(client, &list_req.namespace, list_req.api_resource.as_kube_ref());
+                let key = list_req.key();
+                let (limit, continue_token) = match list_pagination(&list_req) {
+                    Ok(pagination) => pagination,
+                    Err(err) => {
+                        info!("{} List {} failed with error: {:?}", log_header, key, err);
+                        return KubeAPIResponse::ListResponse(KubeListResponse {
+                            res: Err(err),
+                            continue_token: None,
+                        });
+                    }
+                };
+                let mut lp = ListParams::default();
+                if let Some(label_selector) = list_req.label_selector_string() {
+                    lp = lp.labels(&label_selector);
+                }
+                if let Some(field_selector) = list_req.field_selector_string() {
+                    lp = lp.fields(&field_selector);
+                }
+                let res = match limit {
+                    Some(limit) => list_page(&api, &lp, Some(limit), continue_token).await,
+                    None => list_all(&api, &lp, continue_token).await,
+                };
+                match res {
+                    Err(err) => {
+                        info!("{} List {} failed with error: {}", log_header, key, err);
+                        KubeAPIResponse::ListResponse(KubeListResponse {
+                            res: Err(kube_error_to_api_error(&err)),
+                            continue_token: None,
+                        })
+                    }
+                    Ok((objs, continue_token)) => {
+                        info!("{} List {} done", log_header, key);
+                        KubeAPIResponse::ListResponse(KubeListResponse {
+                            res: Ok(objs
+                                .into_iter()
+                                .map(|obj| DynamicObject::from_kube(obj))
+                                .collect()),
+                            continue_token: continue_token,
+                        })
+                    }
+                }
+            }
+            KubeAPIRequest::CreateRequest(create_req) => {
+                let api = dynamic_api_in(client, &create_req.namespace, create_req.api_resource.as_kube_ref());
+                let pp = self.post_params();
+                let key = create_req.key();
+                let obj_to_create = create_req.obj.into_kube();
+                match api.create(&pp, &obj_to_create).await {
+                    Err(err) => {
+                        info!("{} Create {} failed with error: {}", log_header, key, err);
+                        KubeAPIResponse::CreateResponse(KubeCreateResponse {
+                            res: Err(kube_error_to_api_error(&err)),
+                        })
+                    }
+                    Ok(obj) => {
+                        info!("{} Create {} done", log_header, key);
+                        KubeAPIResponse::CreateResponse(KubeCreateResponse {
+                            res: Ok(DynamicObject::from_kube(obj)),
+                        })
+                    }
+                }
+            }
+            KubeAPIRequest::DeleteRequest(delete_req) => {
+                let api = dynamic_api_in(client, &delete_req.namespace, delete_req.api_resource.as_kube_ref());
+                let mut dp = DeleteParams::default();
+                dp.dry_run = self.dry_run;
+                if delete_req.preconditions.is_some() {
+                    dp = dp.preconditions(delete_req.preconditions.clone().unwrap().into_kube());
+                }
+                dp.propagation_policy = delete_req.propagation_policy.map(|p| p.into_kube());
+                let key = delete_req.key();
+                match api.delete(&delete_req.name, &dp).await {
+                    Err(err) => {
+                        info!("{} Delete {} failed with error: {}", log_header, key, err);
+                        KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
+                            res: Err(kube_error_to_api_error(&err)),
+                        })
+                    }
+                    Ok(_) => {
+                        info!("{} Delete {} done", log_header, key);
+                        KubeAPIResponse::DeleteResponse(KubeDeleteResponse { res: Ok(()) })
+                    }
+                }
+            }
+            KubeAPIRequest::UpdateRequest(update_req) => {
+                let api = dynamic_api_in(client, &update_req.namespace, update_req.api_resource.as_kube_ref());
+                let pp = self.post_params();
+                let key = update_req.key();
+                let obj_to_update = update_req.obj.into_kube();
+                match api.replace(&update_req.name, &pp, &obj_to_update).await {
+                    Err(err) => {
+                        info!("{} Update {} failed with error: {}", log_header, key, err);
+                        KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
+                            res: Err(kube_error_to_api_error(&err)),
+                        })
+                    }
+                    Ok(obj) => {
+                        info!("{} Update {} done", log_header, key);
+                        KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
+                            res: Ok(DynamicObject::from_kube(obj)),
+                        })
+                    }
+                }
+            }
+            KubeAPIRequest::UpdateStatusRequest(update_status_req) => {
+                let api = dynamic_api_in(client, &update_status_req.namespace, update_status_req.api_resource.as_kube_ref());
+                let pp = self.post_params();
+                let key = update_status_req.key();
+                let obj_to_update = update_status_req.obj.into_kube();
+                // Here we assume serde_json always succeed
+                match api
+                    .replace_status(
+                        &update_status_req.name,
+                        &pp,
+                        k8s_openapi::serde_json::to_vec(&obj_to_update).unwrap(),
+                    )
+                    .await
+                {
+                    Err(err) => {
+                        info!("{} UpdateStatus {} failed with error: {}", log_header, key, err);
+                        KubeAPIResponse::UpdateStatusResponse(KubeUpdateStatusResponse {
+                            res: Err(kube_error_to_api_error(&err)),
+                        })
+                    }
+                    Ok(obj) => {
+                        info!("{} UpdateStatus {} done", log_header, key);
+                        KubeAPIResponse::UpdateStatusResponse(KubeUpdateStatusResponse {
+                            res: Ok(DynamicObject::from_kube(obj)),
+                        })
+                    }
+                }
+            }
+            KubeAPIRequest::GetThenDeleteRequest(req) => KubeAPIResponse::GetThenDeleteResponse(
+                transactional_get_then_delete_by_retry(client, &self.cr_kind, req, log_header, self.dry_run).await,
+            ),
+            KubeAPIRequest::GetThenUpdateRequest(req) => KubeAPIResponse::GetThenUpdateResponse(
+                transactional_get_then_update_by_retry(client, &self.cr_kind, req, log_header, self.dry_run).await,
+            ),
+            KubeAPIRequest::GetThenUpdateStatusRequest(req) => {
+                KubeAPIResponse::GetThenUpdateStatusResponse(
+                    transactional_get_then_update_status_by_retry(client, &self.cr_kind, req, log_header, self.dry_run).await,
+                )
+            }
+            KubeAPIRequest::PatchRequest(patch_req) => {
+                let api = dynamic_api_in(client, &patch_req.namespace, patch_req.api_resource.as_kube_ref());
+                let key = patch_req.key();
+                let patch_obj = patch_req.obj.into_kube();
+                let res = match &patch_req.field_manager {
+                    // Server-side apply forces the ownership of the applied fields, the same as a controller
+                    // that always overwrites the fields it manages
+                    Some(field_manager) => {
+                        let mut pp = PatchParams::apply(field_manager).force();
+                        pp.dry_run = self.dry_run;
+                        api.patch(&patch_req.name, &pp, &Patch::Apply(&patch_obj)).await
+                    }
+                    None => {
+                        let mut pp = PatchParams::default();
+                        pp.dry_run = self.dry_run;
+                        api.patch(&patch_req.name, &pp, &Patch::Merge(&patch_obj)).await
+                    }
+                };
+                match res {
+                    Err(err) => {
+                        info!("{} Patch {} failed with error: {}", log_header, key, err);
+                        KubeAPIResponse::PatchResponse(KubePatchResponse {
+                            res: Err(kube_error_to_api_error(&err)),
+                        })
+                    }
+                    Ok(obj) => {
+                        info!("{} Patch {} done", log_header, key);
+                        KubeAPIResponse::PatchResponse(KubePatchResponse {
+                            res: Ok(DynamicObject::from_kube(obj)),
+                        })
+                    }
+                }
+            }
+        }
+    }
+}
+
+// DEFAULT_LIST_PAGE_SIZE is the page size that KubeAPIServerBackend uses to page through a list on behalf of
+// a reconciler that does not ask for pages, the same as the default page size of client-go's pager.
+pub const DEFAULT_LIST_PAGE_SIZE: u32 = 500;
+
+// list_pagination returns the limit and the continue token that the list request is served with.
+// Like handle_list_request in the spec, a limit of zero means no limit, and a Cached list is served
+// in one page (so its limit is ignored) and cannot continue a paginated list.
+pub fn list_pagination(req: &KubeListRequest) -> Result<(Option<u32>, Option<String>), APIError> {
+    match req.read_mode {
+        KubeReadMode::Quorum => Ok((req.limit.filter(|limit| *limit > 0), req.continue_token.clone())),
+        KubeReadMode::Cached if req.continue_token.is_some() => Err(APIError::BadRequest),
+        KubeReadMode::Cached => Ok((None, None)),
+    }
+}
+
+// list_page lists one page of at most limit objects (or all the remaining objects if limit is None)
+// starting from continue_token, and returns the objects with the continue token of the next page.
+async fn list_page(
+    api: &Api<kube::api::DynamicObject>,
+    lp: &ListParams,
+    limit: Option<u32>,
+    continue_token: Option<String>,
+) -> Result<(Vec<kube::api::DynamicObject>, Option<String>), kube::Error> {
+    let mut lp = lp.clone();
+    if let Some(limit) = limit {
+        lp = lp.limit(limit);
+    }
+    if let Some(continue_token) = continue_token {
+        lp = lp.continue_token(&continue_token);
+    }
+    let obj_list = api.list(&lp).await?;
+    // The API server returns an empty continue token for the last page
+    let continue_token = obj_list.metadata.continue_.filter(|token| !token.is_empty());
+    Ok((obj_list.items, continue_token))
+}
+
+// list_all pages through the list with DEFAULT_LIST_PAGE_SIZE objects per page, starting from continue_token,
+// and returns all the objects at once so that the reconciler never sees the pages.
+// Like client-go's pager, if the snapshot of the list is compacted before the last page (so a page fails with Expired),
+// list_all falls back to one unpaginated list, which is served from the latest state.
+async fn list_all(
+    api: &Api<kube::api::DynamicObject>,
+    lp: &ListParams,
+    continue_token: Option<String>,
+) -> Result<(Vec<kube::api::DynamicObject>, Option<String>), kube::Error> {
+    let mut objs = Vec::new();
+    let mut next = continue_token.clone();
+    loop {
+        match list_page(api, lp, Some(DEFAULT_LIST_PAGE_SIZE), next.clone()).await {
+            Ok((page, next_token)) => {
+                objs.extend(page);
+                if next_token.is_none() {
+                    return Ok((objs, None));
+                }
+                next = next_token;
+            }
+            // Only fall back if the list is started by list_all; if it continues the reconciler's own
+            // continue token, a list from the latest state would return objects the reconciler has already seen
+            Err(err)
+                if continue_token.is_none()
+                    && matches!(kube_error_to_api_error(&err), APIError::Expired) =>
+            {
+                return list_page(api, lp, None, None).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+// NamespaceScopedBackend forwards the requests to the inner backend only if they target one of the allowed namespaces
+// or a cluster-scoped kind (in the empty namespace), so that the reconciler can still work on cluster-scoped objects.
+// Any other request is rejected with APIError::Forbidden without reaching the inner backend,
+// so a controller running with namespace-scoped RBAC fails fast and clearly instead of hitting the RBAC denial.
+//
+// A request in the empty namespace to a namespaced kind would reach all the namespaces (e.g., a list of pods
+// in all the namespaces), so whether it is allowed is decided by the scope of the kind before it is sent
+// (see is_cluster_scoped_kind). A kind whose scope is unknown to the shim layer is treated as namespaced.
+pub struct NamespaceScopedBackend<'a, B: APIServerBackend> {
+    pub inner: &'a B,
+    pub namespaces: &'a [String],
+}
+
+impl<'a, B: APIServerBackend> NamespaceScopedBackend<'a, B> {
+    fn allows(&self, namespace: &str, cluster_scoped: bool) -> bool {
+        if namespace.is_empty() {
+            cluster_scoped
+        } else {
+            self.namespaces.iter().any(|ns| ns == namespace)
+        }
+    }
+}
+
+impl<'a, B: APIServerBackend> APIServerBackend for NamespaceScopedBackend<'a, B> {
+    async fn get_cr<K>(&self, namespace: &str, name: &str) -> Result<K, APIError>
+    where
+        K: Clone + Resource + DeserializeOwned + Debug + Send,
+        K::Scope: ApiScope,
+        K::DynamicType: Default,
+    {
+        if !self.allows(namespace, !is_namespaced::<K>()) {
+            return Err(APIError::Forbidden);
+        }
+        self.inner.get_cr::<K>(namespace, name).await
+    }
+
+    async fn handle_request(&self, req: KubeAPIRequest, log_header: &str) -> KubeAPIResponse {
+        let namespace = request_namespace(&req);
+        if !self.allows(namespace, is_cluster_scoped_kind(request_api_resource(&req).as_kube_ref())) {
+            info!(
+                "{} Reject request to {} in namespace \"{}\" which is outside the allowed namespaces {:?}",
+                log_header, request_kind(&req), namespace, self.namespaces
+            );
+            return error_response(&req, APIError::Forbidden);
+        }
+        self.inner.handle_request(req, log_header).await
+    }
+}
+
+// is_cluster_scoped_kind tells whether the objects of api_resource do not belong to any namespace.
+// Like kind_is_namespaced in the API server model, the scope of a builtin kind is fixed,
+// and it is looked up from the kube-rs type of the kind.
+// The custom resource kinds are not listed, so a cluster-scoped custom resource other than the reconciled one
+// cannot be accessed through NamespaceScopedBackend (the reconciled one is read by get_cr).
+pub fn is_cluster_scoped_kind(api_resource: &kube::api::ApiResource) -> bool {
+    fn is_kind<T>(api_resource: &kube::api::ApiResource) -> bool
+    where
+        T: Resource<DynamicType = ()>,
+        T::Scope: ApiScope,
+    {
+        !is_namespaced::<T>() && api_resource.group == T::group(&()) && api_resource.kind == T::kind(&())
+    }
+    is_kind::<Namespace>(api_resource)
+        || is_kind::<Node>(api_resource)
+        || is_kind::<PersistentVolume>(api_resource)
+        || is_kind::<ClusterRole>(api_resource)
+        || is_kind::<ClusterRoleBinding>(api_resource)
+        || is_kind::<StorageClass>(api_resource)
+}
+
+// request_namespace returns the namespace that the request targets.
+pub fn request_namespace(req: &KubeAPIRequest) -> &str {
+    match req {
+        KubeAPIRequest::GetRequest(req) => &req.namespace,
+        KubeAPIRequest::ListRequest(req) => &req.namespace,
+        KubeAPIRequest::CreateRequest(req) => &req.namespace,
+        KubeAPIRequest::DeleteRequest(req) => &req.namespace,
+        KubeAPIRequest::UpdateRequest(req) => &req.namespace,
+        KubeAPIRequest::UpdateStatusRequest(req) => &req.namespace,
+        KubeAPIRequest::GetThenDeleteRequest(req) => &req.namespace,
+        KubeAPIRequest::GetThenUpdateRequest(req) => &req.namespace,
+        KubeAPIRequest::GetThenUpdateStatusRequest(req) => &req.namespace,
+        KubeAPIRequest::PatchRequest(req) => &req.namespace,
+    }
+}
+
+// request_name returns the name of the object that the request targets,
+// which is empty for a list request and for a create request of an object with generate_name.
+pub fn request_name(req: &KubeAPIRequest) -> &str {
+    match req {
+        KubeAPIRequest::GetRequest(req) => &req.name,
+        KubeAPIRequest::ListRequest(_) => "",
+        KubeAPIRequest::CreateRequest(req) => req.obj.as_kube_ref().metadata.name.as_deref().unwrap_or(""),
+        KubeAPIRequest::DeleteRequest(req) => &req.name,
+        KubeAPIRequest::UpdateRequest(req) => &req.name,
+        KubeAPIRequest::UpdateStatusRequest(req) => &req.name,
+        KubeAPIRequest::GetThenDeleteRequest(req) => &req.name,
+        KubeAPIRequest::GetThenUpdateRequest(req) => &req.name,
+        KubeAPIRequest::GetThenUpdateStatusRequest(req) => &req.name,
+        KubeAPIRequest::PatchRequest(req) => &req.name,
+    }
+}
+
+// request_api_resource returns the api resource of the object that the request targets.
+pub fn request_api_resource(req: &KubeAPIRequest) -> &ApiResource {
+    match req {
+        KubeAPIRequest::GetRequest(req) => &req.api_resource,
+        KubeAPIRequest::ListRequest(req) => &req.api_resource,
+        KubeAPIRequest::CreateRequest(req) => &req.api_resource,
+        KubeAPIRequest::DeleteRequest(req) => &req.api_resource,
+        KubeAPIRequest::UpdateRequest(req) => &req.api_resource,
+        KubeAPIRequest::UpdateStatusRequest(req) => &req.api_resource,
+        KubeAPIRequest::GetThenDeleteRequest(req) => &req.api_resource,
+        KubeAPIRequest::GetThenUpdateRequest(req) => &req.api_resource,
+        KubeAPIRequest::GetThenUpdateStatusRequest(req) => &req.api_resource,
+        KubeAPIRequest::PatchRequest(req) => &req.api_resource,
+    }
+}
+
+// request_kind returns the kind of the object that the request targets.
+pub fn request_kind(req: &KubeAPIRequest) -> String {
+    request_api_resource(req).as_kube_ref().kind.clone()
+}
+
+// request_key returns the key of the object (or the objects) that the request targets, in the form of kind/namespace/name.
+pub fn request_key(req: &KubeAPIRequest) -> String {
+    match req {
+        KubeAPIRequest::GetRequest(req) => req.key(),
+        KubeAPIRequest::ListRequest(req) => req.key(),
+        KubeAPIRequest::CreateRequest(req) => req.key(),
+        KubeAPIRequest::DeleteRequest(req) => req.key(),
+        KubeAPIRequest::UpdateRequest(req) => req.key(),
+        KubeAPIRequest::UpdateStatusRequest(req) => req.key(),
+        KubeAPIRequest::GetThenDeleteRequest(req) => req.key(),
+        KubeAPIRequest::GetThenUpdateRequest(req) => req.key(),
+        KubeAPIRequest::GetThenUpdateStatusRequest(req) => req.key(),
+        KubeAPIRequest::PatchRequest(req) => req.key(),
+    }
+}
+
+// error_response returns the response to req that carries err.
+pub fn error_response(req: &KubeAPIRequest, err: APIError) -> KubeAPIResponse {
+    match req {
+        KubeAPIRequest::GetRequest(_) => KubeAPIResponse::GetResponse(KubeGetResponse { res: Err(err) }),
+        KubeAPIRequest::ListRequest(_) => KubeAPIResponse::ListResponse(KubeListResponse { res: Err(err), continue_token: None }),
+        KubeAPIRequest::CreateRequest(_) => KubeAPIResponse::CreateResponse(KubeCreateResponse { res: Err(err) }),
+        KubeAPIRequest::DeleteRequest(_) => KubeAPIResponse::DeleteResponse(KubeDeleteResponse { res: Err(err) }),
+        KubeAPIRequest::UpdateRequest(_) => KubeAPIResponse::UpdateResponse(KubeUpdateResponse { res: Err(err) }),
+        KubeAPIRequest::UpdateStatusRequest(_) => {
+            KubeAPIResponse::UpdateStatusResponse(KubeUpdateStatusResponse { res: Err(err) })
+        }
+        KubeAPIRequest::GetThenDeleteRequest(_) => {
+            KubeAPIResponse::GetThenDeleteResponse(KubeGetThenDeleteResponse { res: Err(err) })
+        }
+        KubeAPIRequest::GetThenUpdateRequest(_) => {
+            KubeAPIResponse::GetThenUpdateResponse(KubeGetThenUpdateResponse { res: Err(err) })
+        }
+        KubeAPIRequest::GetThenUpdateStatusRequest(_) => {
+            KubeAPIResponse::GetThenUpdateStatusResponse(KubeGetThenUpdateStatusResponse { res: Err(err) })
+        }
+        KubeAPIRequest::PatchRequest(_) => KubeAPIResponse::PatchResponse(KubePatchResponse { res: Err(err) }),
+    }
+}
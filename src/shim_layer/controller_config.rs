@@ -0,0 +1,195 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::fault_injection::FaultPlanSource;
+use crate::shim_layer::leader_election::LeaderElectionConfig;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// MAX_BACKOFF_CAP is the largest backoff_cap accepted by from_args.
+// A larger cap does not make sense for a controller and lets the delay overflow when the jitter is added.
+pub const MAX_BACKOFF_CAP: Duration = Duration::from_secs(24 * 60 * 60);
+
+// ControllerConfig is passed to run_controller and configures how the controller runs.
+#[derive(Clone, Debug)]
+pub struct ControllerConfig {
+    // fault_injection enables crash testing (see shim_layer::fault_injection)
+    pub fault_injection: bool,
+    // fault_plan is where to load the fault plan from (see shim_layer::fault_injection::FaultPlan).
+    // If it is set, it takes precedence over fault_injection.
+    pub fault_plan: Option<FaultPlanSource>,
+    // leader_election enables lease-based leader election (see shim_layer::leader_election)
+    pub leader_election: Option<LeaderElectionConfig>,
+    // metrics_addr is where the /metrics endpoint is served (see shim_layer::metrics)
+    pub metrics_addr: Option<SocketAddr>,
+    // requeue_interval is how long to wait before reconciling a custom resource again after a successful reconcile
+    pub requeue_interval: Duration,
+    // After the n-th consecutive failed reconcile of a custom resource, the controller waits for
+    // min(backoff_base * 2^(n-1), backoff_cap) plus a random jitter of up to backoff_jitter (between 0 and 1) of that delay
+    pub backoff_base: Duration,
+    pub backoff_cap: Duration,
+    pub backoff_jitter: f64,
+    // external_call_timeout is how long to wait for the external system before failing an external request
+    // with ExternalError::Timeout (see external_shim_layer::call_with_timeout)
+    pub external_call_timeout: Duration,
+    // trace_file is where the trace of each reconcile is appended to (see shim_layer::trace)
+    pub trace_file: Option<PathBuf>,
+    // namespaces restricts the controller to the listed namespaces: it only watches the custom resources
+    // and the owned objects there, and rejects requests to other namespaces (see NamespaceScopedBackend).
+    // If it is None, the controller works cluster-wide.
+    pub namespaces: Option<Vec<String>>,
+}
+
+impl Default for ControllerConfig {
+    fn default() -> ControllerConfig {
+        ControllerConfig {
+            fault_injection: false,
+            fault_plan: None,
+            leader_election: None,
+            metrics_addr: None,
+            requeue_interval: Duration::from_secs(60),
+            backoff_base: Duration::from_secs(10),
+            backoff_cap: Duration::from_secs(300),
+            backoff_jitter: 0.1,
+            external_call_timeout: Duration::from_secs(30),
+            trace_file: None,
+            namespaces: None,
+        }
+    }
+}
+
+impl ControllerConfig {
+    // from_args builds the config from the command line options of the controller binaries:
+    // --leader-elect, --metrics-addr <addr>, --requeue-interval-secs <n>,
+    // --backoff-base-secs <n>, --backoff-cap-secs <n>, --backoff-jitter <fraction>, --external-call-timeout-secs <n>,
+    // --trace-file <path>, --namespaces <ns1,ns2,...>, --fault-plan <path>
+    // and --fault-plan-config-map <namespace>/<name>.
+    // controller_name is used as the name of the lease for leader election.
+    pub fn from_args(controller_name: &str, args: &[String]) -> Result<ControllerConfig> {
+        let default = ControllerConfig::default();
+        let value_of = |option: &str| -> Option<&String> {
+            args.iter().position(|arg| arg == option).and_then(|i| args.get(i + 1))
+        };
+        let secs_of = |option: &str, default: Duration| -> Result<Duration> {
+            match value_of(option) {
+                Some(val) => val
+                    .parse::<u64>()
+                    .map(Duration::from_secs)
+                    .map_err(|_e| anyhow!("{} expects a number of seconds, got {}", option, val)),
+                None => Ok(default),
+            }
+        };
+        let backoff_cap = secs_of("--backoff-cap-secs", default.backoff_cap)?;
+        if backoff_cap > MAX_BACKOFF_CAP {
+            return Err(anyhow!(
+                "--backoff-cap-secs expects at most {} seconds, got {}",
+                MAX_BACKOFF_CAP.as_secs(),
+                backoff_cap.as_secs()
+            ));
+        }
+        let backoff_jitter = match value_of("--backoff-jitter") {
+            Some(val) => val
+                .parse::<f64>()
+                .ok()
+                .filter(|jitter| (0.0..=1.0).contains(jitter))
+                .ok_or_else(|| anyhow!("--backoff-jitter expects a fraction between 0 and 1, got {}", val))?,
+            None => default.backoff_jitter,
+        };
+        let namespaces = match value_of("--namespaces") {
+            Some(val) => {
+                let namespaces: Vec<String> = val.split(',').map(|ns| ns.trim().to_string()).collect();
+                if namespaces.iter().any(|ns| ns.is_empty()) {
+                    return Err(anyhow!("--namespaces expects a comma-separated list of namespaces, got {}", val));
+                }
+                Some(namespaces)
+            }
+            None => None,
+        };
+        let fault_plan = match (value_of("--fault-plan"), value_of("--fault-plan-config-map")) {
+            (Some(_), Some(_)) => {
+                return Err(anyhow!("--fault-plan and --fault-plan-config-map cannot be used together"));
+            }
+            (Some(path), None) => Some(FaultPlanSource::File(PathBuf::from(path))),
+            (None, Some(val)) => match val.split_once('/') {
+                Some((namespace, name)) if !namespace.is_empty() && !name.is_empty() => Some(FaultPlanSource::ConfigMap {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                }),
+                _ => return Err(anyhow!("--fault-plan-config-map expects <namespace>/<name>, got {}", val)),
+            },
+            (None, None) => None,
+        };
+        Ok(ControllerConfig {
+            fault_injection: false,
+            fault_plan: fault_plan,
+            leader_election: if args.iter().any(|arg| arg == "--leader-elect") {
+                Some(LeaderElectionConfig::from_env(controller_name))
+            } else {
+                None
+            },
+            metrics_addr: match value_of("--metrics-addr") {
+                Some(val) => Some(val.parse().map_err(|_e| anyhow!("--metrics-addr expects an address, got {}", val))?),
+                None => None,
+            },
+            requeue_interval: secs_of("--requeue-interval-secs", default.requeue_interval)?,
+            backoff_base: secs_of("--backoff-base-secs", default.backoff_base)?,
+            backoff_cap: backoff_cap,
+            backoff_jitter: backoff_jitter,
+            external_call_timeout: secs_of("--external-call-timeout-secs", default.external_call_timeout)?,
+            trace_file: value_of("--trace-file").map(PathBuf::from),
+            namespaces: namespaces,
+        })
+    }
+}
+
+// FailureBackoff tracks the number of consecutive failed reconciles of each custom resource (by its key)
+// and computes the exponential backoff delay before the next reconcile.
+pub struct FailureBackoff {
+    base: Duration,
+    cap: Duration,
+    jitter: f64,
+    failures: Mutex<HashMap<String, u32>>,
+}
+
+impl FailureBackoff {
+    pub fn new(config: &ControllerConfig) -> FailureBackoff {
+        FailureBackoff {
+            base: config.backoff_base,
+            // Same as the jitter below, a config built in code may skip the check on the cap in from_args
+            cap: config.backoff_cap.min(MAX_BACKOFF_CAP),
+            // A config built in code may skip the check in from_args, and Duration::mul_f64 panics on
+            // a negative, infinite or overflowing factor
+            jitter: if config.backoff_jitter.is_finite() { config.backoff_jitter.clamp(0.0, 1.0) } else { 0.0 },
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // on_failure records one more failed reconcile of cr_key and returns how long to wait before retrying.
+    pub fn on_failure(&self, cr_key: &str) -> Duration {
+        let mut failures = self.failures.lock().unwrap();
+        let count = failures.entry(cr_key.to_string()).or_insert(0);
+        *count = count.saturating_add(1);
+        let delay = self.delay_without_jitter(*count);
+        // Neither the multiplication nor the addition can overflow and panic, even if the delay is close to Duration::MAX
+        let jitter = Duration::try_from_secs_f64(delay.as_secs_f64() * self.jitter * rand::random::<f64>()).unwrap_or(Duration::ZERO);
+        delay.saturating_add(jitter)
+    }
+
+    // on_success resets the backoff of cr_key.
+    pub fn on_success(&self, cr_key: &str) {
+        self.failures.lock().unwrap().remove(cr_key);
+    }
+
+    pub fn failures(&self, cr_key: &str) -> u32 {
+        self.failures.lock().unwrap().get(cr_key).cloned().unwrap_or(0)
+    }
+
+    // delay_without_jitter returns min(base * 2^(failures-1), cap).
+    pub fn delay_without_jitter(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(31);
+        self.base.saturating_mul(1u32 << exponent).min(self.cap)
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::external_shim_layer::*;
+use crate::kubernetes_api_objects::exec::resource::*;
+use crate::kubernetes_api_objects::spec::resource::*;
+use crate::reconciler::exec::reconciler::*;
+use crate::shim_layer::controller_config::*;
+use crate::shim_layer::controller_runtime::*;
+use crate::shim_layer::events::*;
+use crate::shim_layer::read_cache::*;
+use crate::shim_layer::trace::*;
+use anyhow::Result;
+use core::fmt::Debug;
+use core::hash::Hash;
+use futures::{future::join_all, future::BoxFuture, FutureExt, StreamExt};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    api::Resource,
+    runtime::{
+        controller::Controller,
+        reflector::{self, store::Writer, ReflectHandle, Store},
+        watcher, WatchStreamExt,
+    },
+    Client, CustomResourceExt,
+};
+use kube_core::NamespaceResourceScope;
+use serde::{de::DeserializeOwned, Serialize};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tracing::{error, info};
+use vstd::prelude::*;
+
+// ControllerManager runs several controllers in one process, for example:
+//
+//   let mut manager = ControllerManager::new(config).await?;
+//   manager.register_watching_owned::<VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, Pod>(()).await?;
+//   manager.register_watching_owned::<VDeployment, VDeploymentReconciler, VoidExternalShimLayer, VReplicaSet>(()).await?;
+//   manager.run().await
+//
+// All the controllers share one client, and each resource type is watched only once (per namespace):
+// the watch feeds a shared cache, which is read by all the controllers that watch the type,
+// either as their custom resource or as an owned type.
+// The manager handles the termination signals, leader election (with one lease for all the controllers)
+// and the metrics endpoint once, while each controller keeps its own error policy and backoff state (see Data).
+// The metrics are labeled by the kind of the custom resource, so they are still reported per controller.
+// Each controller serves its Get and List requests with the Cached read mode from the shared caches
+// of the types that it watches (see CachedBackend); the Quorum reads still go to the API server.
+//
+// Note that running the controllers together is only safe if they do not interfere with each other,
+// which is what the composition proofs establish for the verified controllers.
+pub struct ControllerManager {
+    client: Client,
+    config: ControllerConfig,
+    // watches maps each (type, namespace) to its shared cache and the handle to subscribe to its changes
+    watches: HashMap<(TypeId, Option<String>), Box<dyn Any + Send>>,
+    // watch_streams keep the shared caches up to date
+    watch_streams: Vec<BoxFuture<'static, ()>>,
+    controllers: Vec<BoxFuture<'static, ()>>,
+    shutdown: watch::Sender<bool>,
+}
+
+// The number of changes that a shared watch buffers for the slowest controller
+const SHARED_WATCH_BUFFER_SIZE: usize = 1024;
+
+impl ControllerManager {
+    pub async fn new(config: ControllerConfig) -> Result<ControllerManager> {
+        Ok(ControllerManager {
+            client: Client::try_default().await?,
+            config: config,
+            watches: HashMap::new(),
+            watch_streams: Vec::new(),
+            controllers: Vec::new(),
+            shutdown: watch::channel(false).0,
+        })
+    }
+
+    // register adds a controller of R that reconciles K (see run_controller).
+    pub async fn register<K, R, E>(&mut self, external_config: E::Config) -> Result<()>
+    where
+        K: Clone
+            + Resource
+            + CustomResourceExt
+            + DeserializeOwned
+            + Debug
+            + Send
+            + Serialize
+            + Sync
+            + 'static,
+        K::Scope: ApiScope,
+        K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin + Send + Sync,
+        R: Reconciler + ReconcileEvents + Send + Sync,
+        R::K: ResourceWrapper<K> + Send,
+        <R::K as View>::V: CustomResourceView,
+        R::S: Send + ReconcileStepName,
+        R::EReq: Send + TraceExternal,
+        R::EResp: Send + TraceExternal,
+        E: ExternalShimLayer<R::EReq, R::EResp>,
+    {
+        self.register_with::<K, R, E>(external_config, |_manager, controller, _namespace, _read_cache| controller)
+            .await
+    }
+
+    // register_watching_owned adds a controller of R that reconciles K,
+    // and is also triggered by the Pods and the objects of O owned by K (see run_controller_watching_owned).
+    pub async fn register_watching_owned<K, R, E, O>(&mut self, external_config: E::Config) -> Result<()>
+    where
+        K: Clone
+            + Resource
+            + CustomResourceExt
+            + DeserializeOwned
+            + Debug
+            + Send
+            + Serialize
+            + Sync
+            + 'static,
+        K::Scope: ApiScope,
+        K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin + Send + Sync,
+        R: Reconciler + ReconcileEvents + Send + Sync,
+        R::K: ResourceWrapper<K> + Send,
+        <R::K as View>::V: CustomResourceView,
+        R::S: Send + ReconcileStepName,
+        R::EReq: Send + TraceExternal,
+        R::EResp: Send + TraceExternal,
+        E: ExternalShimLayer<R::EReq, R::EResp>,
+        O: Clone
+            + Resource<Scope = NamespaceResourceScope, DynamicType = ()>
+            + DeserializeOwned
+            + Serialize
+            + Debug
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.register_with::<K, R, E>(external_config, |manager, controller, namespace, read_cache| {
+            let (pods, pod_trigger) = manager.shared_watch::<Pod>(namespace);
+            let (owned, owned_trigger) = manager.shared_watch::<O>(namespace);
+            read_cache.insert(namespace, pods);
+            read_cache.insert(namespace, owned);
+            controller
+                .owns_shared_stream(pod_trigger) // Watch owned Pods
+                .owns_shared_stream(owned_trigger) // Watch owned CRs of type O
+        })
+        .await
+    }
+
+    async fn register_with<K, R, E>(
+        &mut self,
+        external_config: E::Config,
+        owns: impl Fn(&mut ControllerManager, Controller<K>, &Option<String>, &mut ReadCache) -> Controller<K>,
+    ) -> Result<()>
+    where
+        K: Clone
+            + Resource
+            + CustomResourceExt
+            + DeserializeOwned
+            + Debug
+            + Send
+            + Serialize
+            + Sync
+            + 'static,
+        K::Scope: ApiScope,
+        K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin + Send + Sync,
+        R: Reconciler + ReconcileEvents + Send + Sync,
+        R::K: ResourceWrapper<K> + Send,
+        <R::K as View>::V: CustomResourceView,
+        R::S: Send + ReconcileStepName,
+        R::EReq: Send + TraceExternal,
+        R::EResp: Send + TraceExternal,
+        E: ExternalShimLayer<R::EReq, R::EResp>,
+    {
+        let kind = K::kind(&K::DynamicType::default()).to_string();
+        // A cluster-scoped custom resource does not belong to any namespace,
+        // so it cannot be watched in the namespaces that the config restricts to
+        if !is_namespaced::<K>() && self.config.namespaces.is_some() {
+            return Err(anyhow::anyhow!("cannot register cluster-scoped {} with restricted namespaces", kind));
+        }
+        let external = Arc::new(E::init(external_config).await?);
+
+        // Build the async closure on top of reconcile_with
+        let reconcile = move |cr: Arc<K>, ctx: Arc<Data>| {
+            let external = external.clone();
+            async move {
+                return reconcile_with::<K, R, E>(cr, ctx, external.as_ref()).await;
+            }
+        };
+        // Each controller has its own Data, so the backoff of one controller does not affect the others
+        let mut data = Data::new(self.client.clone(), self.config.clone()).await?;
+        // read_cache collects the caches of all the watches of this controller
+        let mut read_cache = ReadCache::new();
+        let mut controllers = Vec::new();
+        for namespace in watched_namespaces(&self.config) {
+            let (store, trigger) = self.shared_watch::<K>(&namespace);
+            read_cache.insert(&namespace, store.clone());
+            let controller = Controller::for_shared_stream(trigger, store); // The controller's reconcile is triggered when a CR is created/updated
+            controllers.push(owns(self, controller, &namespace, &mut read_cache));
+        }
+        data.read_cache = Some(read_cache);
+        let data = Arc::new(data);
+
+        for controller in controllers {
+            let kind = kind.clone();
+            let backoff_data = data.clone();
+            let controller = controller
+                .graceful_shutdown_on(self.shutdown_signal())
+                .run(reconcile.clone(), error_policy, data.clone()) // The reconcile function is registered
+                .for_each(move |res| {
+                    let kind = kind.clone();
+                    let data = backoff_data.clone();
+                    async move {
+                        match res {
+                            Ok(o) => info!("{} controller reconciled {:?}", kind, o),
+                            // A requeued custom resource that has been deleted is dropped without calling reconcile,
+                            // so its backoff state is reset here, otherwise the key of every deleted custom resource
+                            // whose last reconcile failed stays in the backoff state forever
+                            Err(kube::runtime::controller::Error::ObjectNotFound(obj_ref)) => {
+                                data.backoff.on_success(&cr_key(&kind, &obj_ref.namespace.unwrap_or_default(), &obj_ref.name));
+                                info!("{} controller skipped deleted {}", kind, obj_ref.name);
+                            }
+                            Err(e) => info!("{} controller reconcile failed: {}", kind, e),
+                        }
+                    }
+                });
+            self.controllers.push(controller.boxed());
+        }
+        info!("registered {} controller", kind);
+        Ok(())
+    }
+
+    // shared_watch returns the shared cache of T in the namespace and a handle to subscribe to its changes.
+    // The watch is started on the first call for T and the namespace, and shared by the later calls.
+    fn shared_watch<T>(&mut self, namespace: &Option<String>) -> (Store<T>, ReflectHandle<T>)
+    where
+        T: Clone + Resource + DeserializeOwned + Serialize + Debug + Send + Sync + 'static,
+        T::Scope: ApiScope,
+        T::DynamicType: Default + Eq + Hash + Clone + Send + Sync,
+    {
+        let key = (TypeId::of::<T>(), namespace.clone());
+        if let Some(watch) = self.watches.get(&key) {
+            return watch.downcast_ref::<(Store<T>, ReflectHandle<T>)>().unwrap().clone();
+        }
+        let (store, writer): (Store<T>, Writer<T>) = reflector::store_shared(SHARED_WATCH_BUFFER_SIZE);
+        let handle = writer.subscribe().unwrap();
+        let stream = watcher(api_in::<T>(&self.client, namespace), watcher::Config::default())
+            .default_backoff()
+            .reflect_shared(writer)
+            .for_each(|res| async move {
+                if let Err(e) = res {
+                    error!("shared watch failed: {}", e);
+                }
+            });
+        self.watch_streams.push(stream.boxed());
+        self.watches.insert(key, Box::new((store.clone(), handle.clone())));
+        (store, handle)
+    }
+
+    fn shutdown_signal(&self) -> BoxFuture<'static, ()> {
+        let mut shutdown = self.shutdown.subscribe();
+        async move {
+            let _ = shutdown.wait_for(|shutdown| *shutdown).await;
+        }
+        .boxed()
+    }
+
+    // run drives all the registered controllers until a termination signal is received.
+    pub async fn run(self) -> Result<()> {
+        let shutdown = self.shutdown;
+        tokio::spawn(async move {
+            wait_for_termination().await;
+            info!("received termination signal, shutting down all controllers");
+            let _ = shutdown.send(true);
+        });
+        let controllers = self.controllers;
+        let watch_streams = self.watch_streams;
+        let controller = async move {
+            // The shared watches only end with the process, so the manager ends when all the controllers end
+            futures::future::select(join_all(controllers), join_all(watch_streams)).await;
+        };
+        run_with_config(&self.client, &self.config, controller).await
+    }
+}
+
+// wait_for_termination resolves when the process receives SIGINT or SIGTERM.
+async fn wait_for_termination() {
+    let ctrl_c = tokio::signal::ctrl_c();
+    #[cfg(unix)]
+    {
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).unwrap();
+        tokio::select! {
+            _ = ctrl_c => {},
+            _ = sigterm.recv() => {},
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = ctrl_c.await;
+    }
+}
@@ -1,29 +1,37 @@
 use crate::external_shim_layer::*;
 use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::exec::prelude::Preconditions;
-use crate::kubernetes_api_objects::exec::{api_method::*, dynamic::*, resource::*};
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::*, dynamic::*, resource::*};
 use crate::kubernetes_api_objects::spec::resource::*;
 use crate::reconciler::exec::{io::*, reconciler::*};
+use crate::shim_layer::backend::*;
+use crate::shim_layer::controller_config::*;
+use crate::shim_layer::controller_manager::*;
+use crate::shim_layer::events::*;
 use crate::shim_layer::fault_injection::*;
+use crate::shim_layer::leader_election::*;
+use crate::shim_layer::metrics::*;
+use crate::shim_layer::read_cache::*;
+use crate::shim_layer::trace::*;
 use core::fmt::Debug;
 use core::hash::Hash;
 use anyhow::Result;
-use futures::StreamExt;
+use futures::{future::join_all, Future, StreamExt};
 use k8s_openapi::api::core::v1::Pod;
 use kube::{
-    api::{Api, DeleteParams, ListParams, PostParams, Resource},
+    api::{Api, DeleteParams, PostParams, Resource},
     runtime::{
         controller::{Action, Controller},
         watcher,
     },
     Client, CustomResourceExt,
 };
-use kube_core::{ErrorResponse, NamespaceResourceScope};
+use kube_core::{ClusterResourceScope, NamespaceResourceScope};
 use serde::{de::DeserializeOwned, Serialize};
 use tracing::{error, info, warn};
 use crate::crds::Error;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use vstd::string::*;
 
 // The shim layer connects the verified reconciler to the trusted kube-rs APIs.
@@ -34,10 +42,18 @@ use vstd::string::*;
 // run_controller prepares and runs the controller. It requires:
 // K: the custom resource type
 // R: the reconciler type
-pub async fn run_controller<K, R, E>(fault_injection: bool) -> Result<()>
+//
+// E: the external shim layer type
+//
+// config decides the requeue and backoff policy, the namespaces to watch, and whether to enable
+// fault injection, leader election and the metrics endpoint (see ControllerConfig).
+// If config restricts the namespaces, the controller runs one watch per namespace.
+// The controller runs as the only controller of a ControllerManager.
+// external_config is used to initialize the external shim layer, which is shared by all the reconciles.
+pub async fn run_controller<K, R, E>(config: ControllerConfig, external_config: E::Config) -> Result<()>
 where
     K: Clone
-        + Resource<Scope = NamespaceResourceScope>
+        + Resource
         + CustomResourceExt
         + DeserializeOwned
         + Debug
@@ -45,42 +61,25 @@ where
         + Serialize
         + Sync
         + 'static,
-    K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin,
-    R: Reconciler + Send + Sync,
+    K::Scope: ApiScope,
+    K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin + Send + Sync,
+    R: Reconciler + ReconcileEvents + Send + Sync,
     R::K: ResourceWrapper<K> + Send,
     <R::K as View>::V: CustomResourceView,
-    R::S: Send,
-    R::EReq: Send,
-    R::EResp: Send,
+    R::S: Send + ReconcileStepName,
+    R::EReq: Send + TraceExternal,
+    R::EResp: Send + TraceExternal,
     E: ExternalShimLayer<R::EReq, R::EResp>,
 {
-    let client = Client::try_default().await?;
-    let crs = Api::<K>::all(client.clone());
-
-    // Build the async closure on top of reconcile_with
-    let reconcile = |cr: Arc<K>, ctx: Arc<Data>| async move {
-        return reconcile_with::<K, R, E>(cr, ctx, fault_injection).await;
-    };
-
-    info!("starting controller");
-    Controller::new(crs, watcher::Config::default()) // The controller's reconcile is triggered when a CR is created/updated
-        .shutdown_on_signal()
-        .run(reconcile, error_policy, Arc::new(Data { client })) // The reconcile function is registered
-        .for_each(|res| async move {
-            match res {
-                Ok(o) => info!("reconciled {:?}", o),
-                Err(e) => info!("reconcile failed: {}", e),
-            }
-        })
-        .await;
-    info!("controller terminated");
-    Ok(())
+    let mut manager = ControllerManager::new(config).await?;
+    manager.register::<K, R, E>(external_config).await?;
+    manager.run().await
 }
 
-pub async fn run_controller_watching_owned<K, R, E, O>(fault_injection: bool) -> Result<()>
+pub async fn run_controller_watching_owned<K, R, E, O>(config: ControllerConfig, external_config: E::Config) -> Result<()>
 where
     K: Clone
-        + Resource<Scope = NamespaceResourceScope>
+        + Resource
         + CustomResourceExt
         + DeserializeOwned
         + Debug
@@ -88,105 +87,339 @@ where
         + Serialize
         + Sync
         + 'static,
-    K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin,
-    R: Reconciler + Send + Sync,
+    K::Scope: ApiScope,
+    K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin + Send + Sync,
+    R: Reconciler + ReconcileEvents + Send + Sync,
     R::K: ResourceWrapper<K> + Send,
     <R::K as View>::V: CustomResourceView,
-    R::S: Send,
-    R::EReq: Send,
-    R::EResp: Send,
+    R::S: Send + ReconcileStepName,
+    R::EReq: Send + TraceExternal,
+    R::EResp: Send + TraceExternal,
     E: ExternalShimLayer<R::EReq, R::EResp>,
     O: Clone
         + Resource<Scope = NamespaceResourceScope, DynamicType = ()>
         + DeserializeOwned
+        + Serialize
         + Debug
         + Send
         + Sync
         + 'static,
 {
-    let client = Client::try_default().await?;
-    let crs = Api::<K>::all(client.clone());
+    let mut manager = ControllerManager::new(config).await?;
+    manager.register_watching_owned::<K, R, E, O>(external_config).await?;
+    manager.run().await
+}
 
-    // Build the async closure on top of reconcile_with
-    let reconcile = |cr: Arc<K>, ctx: Arc<Data>| async move {
-        return reconcile_with::<K, R, E>(cr, ctx, fault_injection).await;
-    };
+// watched_namespaces returns the namespaces to run a watch in, where None stands for all namespaces.
+pub fn watched_namespaces(config: &ControllerConfig) -> Vec<Option<String>> {
+    match &config.namespaces {
+        Some(namespaces) => namespaces.iter().map(|ns| Some(ns.clone())).collect(),
+        None => vec![None],
+    }
+}
+
+// ApiScope is implemented by the scopes of the resources that the shim layer can reconcile and watch,
+// i.e., namespaced and cluster-scoped resources.
+pub trait ApiScope {
+    // NAMESPACED tells whether the resources of the scope belong to a namespace.
+    const NAMESPACED: bool;
+
+    fn api_in<T>(client: &Client, namespace: &Option<String>) -> Api<T>
+    where
+        T: Resource<Scope = Self>,
+        T::DynamicType: Default;
+}
+
+impl ApiScope for NamespaceResourceScope {
+    const NAMESPACED: bool = true;
+
+    fn api_in<T>(client: &Client, namespace: &Option<String>) -> Api<T>
+    where
+        T: Resource<Scope = Self>,
+        T::DynamicType: Default,
+    {
+        match namespace {
+            Some(namespace) => Api::<T>::namespaced(client.clone(), namespace),
+            None => Api::<T>::all(client.clone()),
+        }
+    }
+}
+
+impl ApiScope for ClusterResourceScope {
+    const NAMESPACED: bool = false;
+
+    fn api_in<T>(client: &Client, _namespace: &Option<String>) -> Api<T>
+    where
+        T: Resource<Scope = Self>,
+        T::DynamicType: Default,
+    {
+        Api::<T>::all(client.clone())
+    }
+}
+
+// api_in returns the Api of T in the namespace, or in all namespaces if namespace is None.
+// A cluster-scoped T does not belong to any namespace, so its Api is always for the whole cluster.
+pub fn api_in<T>(client: &Client, namespace: &Option<String>) -> Api<T>
+where
+    T: Resource,
+    T::Scope: ApiScope,
+    T::DynamicType: Default,
+{
+    <T::Scope as ApiScope>::api_in::<T>(client, namespace)
+}
+
+// is_namespaced tells whether the resources of T belong to a namespace.
+pub fn is_namespaced<T>() -> bool
+where
+    T: Resource,
+    T::Scope: ApiScope,
+{
+    <T::Scope as ApiScope>::NAMESPACED
+}
+
+// dynamic_api_in returns the Api of the dynamic objects of api_resource in the namespace,
+// where the empty namespace stands for the cluster scope (the same as cluster_scope_namespace in the spec).
+pub fn dynamic_api_in(client: &Client, namespace: &str, api_resource: &kube::api::ApiResource) -> Api<kube::api::DynamicObject> {
+    if namespace.is_empty() {
+        Api::<kube::api::DynamicObject>::all_with(client.clone(), api_resource)
+    } else {
+        Api::<kube::api::DynamicObject>::namespaced_with(client.clone(), namespace, api_resource)
+    }
+}
 
-    info!("starting controller");
-    Controller::new(crs, watcher::Config::default()) // The controller's reconcile is triggered when a CR is created/updated
-        .owns(Api::<Pod>::all(client.clone()), watcher::Config::default()) // Watch owned Pods
-        .owns(Api::<O>::all(client.clone()), watcher::Config::default()) // Watch owned CRs of type O
-        .shutdown_on_signal()
-        .run(reconcile, error_policy, Arc::new(Data { client })) // The reconcile function is registered
-        .for_each(|res| async move {
-            match res {
-                Ok(o) => info!("reconciled {:?}", o),
-                Err(e) => info!("reconcile failed: {}", e),
+// run_with_config drives the controller stream, and if leader election is enabled,
+// only does so while holding the lease.
+// Losing the lease is reported as an error so that the process exits and gets restarted as a candidate.
+pub async fn run_with_config<F>(client: &Client, config: &ControllerConfig, controller: F) -> Result<()>
+where
+    F: Future<Output = ()>,
+{
+    if let Some(metrics_addr) = config.metrics_addr {
+        tokio::spawn(serve_metrics(metrics_addr));
+    }
+    match config.leader_election.clone() {
+        None => {
+            info!("starting controller");
+            controller.await;
+        }
+        Some(config) => {
+            let backend = KubeAPIServerBackend { client: client.clone(), dry_run: false, cr_kind: String::new() };
+            info!("waiting for lease {}/{} as {}", config.lease_namespace, config.lease_name, config.identity);
+            let elector = LeaderElector::new(&backend, config);
+            let controller = async {
+                info!("starting controller");
+                controller.await
+            };
+            if elector.run(controller).await.is_none() {
+                return Err(anyhow::anyhow!("controller stepped down after losing the lease"));
             }
-        })
-        .await;
+        }
+    }
     info!("controller terminated");
     Ok(())
 }
 
 // reconcile_with implements the reconcile function by repeatedly invoking R::reconcile_core.
 // reconcile_with will be invoked by kube-rs whenever kube-rs's watcher receives any relevant event to the controller.
-// In each invocation, reconcile_with invokes R::reconcile_core in a loop:
+// It sends the requests from R::reconcile_core to the Kubernetes API server via KubeAPIServerBackend
+// (wrapped in CachedBackend, which serves the Cached reads from the watch caches,
+// in NamespaceScopedBackend if the config restricts the namespaces,
+// and in EventBackend, which records the failed and the notable requests as events of the custom resource);
+// see reconcile_with_backend for the reconcile loop itself.
+// If the reconcile ends with an error, the error is also recorded as an event of the custom resource.
+pub async fn reconcile_with<K, R, E>(cr: Arc<K>, ctx: Arc<Data>, external: &E) -> Result<Action, Error>
+where
+    K: Clone
+        + Resource
+        + CustomResourceExt
+        + DeserializeOwned
+        + Debug
+        + Send
+        + Serialize,
+    K::Scope: ApiScope,
+    K::DynamicType: Default + Clone + Debug,
+    R: Reconciler + ReconcileEvents,
+    R::K: ResourceWrapper<K>,
+    R::S: ReconcileStepName,
+    R::EReq: TraceExternal,
+    R::EResp: TraceExternal,
+    <R::K as View>::V: CustomResourceView,
+    E: ExternalShimLayer<R::EReq, R::EResp>,
+{
+    let backend = KubeAPIServerBackend {
+        client: ctx.client.clone(),
+        dry_run: false,
+        cr_kind: K::kind(&K::DynamicType::default()).to_string(),
+    };
+    // Get and List requests with the Cached read mode are served from the watch caches (if there are any)
+    let backend = CachedBackend {
+        inner: &backend,
+        cache: ctx.read_cache.as_ref(),
+    };
+    match &ctx.config.namespaces {
+        Some(namespaces) => {
+            // Requests to namespaces other than the watched ones are rejected in the shim layer,
+            // and so are the events, which are written through the same backend
+            let backend = NamespaceScopedBackend {
+                inner: &backend,
+                namespaces: namespaces,
+            };
+            reconcile_recording_events::<K, R, E, _>(cr, &ctx, &backend, external).await
+        }
+        None => reconcile_recording_events::<K, R, E, _>(cr, &ctx, &backend, external).await,
+    }
+}
+
+// reconcile_recording_events runs reconcile_with_backend with the requests sent to backend through an EventBackend,
+// and records the error of the reconcile (if any) as an event of the custom resource.
+// All the events are written through backend.
+async fn reconcile_recording_events<K, R, E, B>(cr: Arc<K>, ctx: &Data, backend: &B, external: &E) -> Result<Action, Error>
+where
+    K: Clone
+        + Resource
+        + CustomResourceExt
+        + DeserializeOwned
+        + Debug
+        + Send
+        + Serialize,
+    K::Scope: ApiScope,
+    K::DynamicType: Default + Clone + Debug,
+    R: Reconciler + ReconcileEvents,
+    R::K: ResourceWrapper<K>,
+    R::S: ReconcileStepName,
+    R::EReq: TraceExternal,
+    R::EResp: TraceExternal,
+    <R::K as View>::V: CustomResourceView,
+    E: ExternalShimLayer<R::EReq, R::EResp>,
+    B: APIServerBackend,
+{
+    let cr_key = cr_key_of(cr.as_ref());
+    let recorder = EventRecorder::new(cr.as_ref(), R::notable_event, &ctx.event_correlator);
+    let event_backend = EventBackend {
+        inner: backend,
+        recorder: &recorder,
+    };
+    let res = reconcile_with_backend::<K, R, E, _>(
+        cr,
+        &event_backend,
+        ctx.config.requeue_interval,
+        ctx.fault_injection.as_ref(),
+        ctx.trace_recorder.as_ref(),
+        external,
+        ctx.config.external_call_timeout,
+    )
+    .await;
+    match &res {
+        // A successful reconcile resets the backoff of this custom resource
+        Ok(_) => ctx.backoff.on_success(&cr_key),
+        Err(err) => {
+            let log_header = format!("Reconciling {}:", cr_key);
+            recorder
+                .record(backend, WARNING_EVENT, "ReconcileFailed", &err.to_string(), &log_header)
+                .await
+        }
+    }
+    res
+}
+
+// reconcile_with_backend invokes R::reconcile_core in a loop:
 // it starts with R::reconcile_init_state, and in each iteration it invokes R::reconcile_core
 // with the new state returned by the previous invocation.
-// For each request from R::reconcile_core, it invokes kube-rs APIs to send the request to the Kubernetes API.
+// For each request from R::reconcile_core, it asks the backend to handle the request.
 // It ends the loop when the R reports the reconcile is done (R::reconcile_done)
 // or encounters error (R::reconcile_error).
-pub async fn reconcile_with<K, R, E>(
+//
+// When the reconcile is done, the custom resource is requeued after requeue_interval.
+//
+// If R::finalizer returns a finalizer, the finalizer is added to the custom resource before the loop
+// (unless the custom resource is being deleted) and the loop starts from the updated custom resource, and once the custom resource has a deletion timestamp,
+// the loop invokes R::reconcile_delete instead of R::reconcile_core. When R::reconcile_delete is done,
+// the finalizer is removed so that the API server can delete the custom resource.
+// The finalizer updates follow the controller model (see finalizer_update_req): they are sent without
+// affecting the reconcile, and a failed update is retried by the next reconcile.
+//
+// If fault_injection is provided, the controller either checks whether to crash after each
+// create, update, delete or external request, or injects the faults of the fault plan
+// into the requests (see shim_layer::fault_injection).
+//
+// If trace_recorder is provided, the requests and responses of this reconcile are recorded
+// as one trace (see shim_layer::trace).
+//
+// External requests are sent to external, and fail with ExternalError::Timeout
+// if external does not respond within external_call_timeout.
+pub async fn reconcile_with_backend<K, R, E, B>(
     cr: Arc<K>,
-    ctx: Arc<Data>,
-    fault_injection: bool,
+    backend: &B,
+    requeue_interval: Duration,
+    fault_injection: Option<&FaultInjection>,
+    trace_recorder: Option<&TraceRecorder>,
+    external: &E,
+    external_call_timeout: Duration,
 ) -> Result<Action, Error>
 where
     K: Clone
-        + Resource<Scope = NamespaceResourceScope>
+        + Resource
         + CustomResourceExt
         + DeserializeOwned
         + Debug
+        + Send
         + Serialize,
+    K::Scope: ApiScope,
     K::DynamicType: Default + Clone + Debug,
     R: Reconciler,
     R::K: ResourceWrapper<K>,
+    R::S: ReconcileStepName,
+    R::EReq: TraceExternal,
+    R::EResp: TraceExternal,
     <R::K as View>::V: CustomResourceView,
     E: ExternalShimLayer<R::EReq, R::EResp>,
+    B: APIServerBackend,
 {
-    let client = &ctx.client;
-
-    let cr_name = cr.meta().name.as_ref().ok_or_else(|| {
-        Error::ShimLayerError("Custom resource misses \".metadata.name\"".to_string())
-    })?;
-    let cr_namespace = cr.meta().namespace.as_ref().ok_or_else(|| {
-        Error::ShimLayerError("Custom resources misses \".metadata.namespace\"".to_string())
-    })?;
     let cr_kind = K::kind(&K::DynamicType::default()).to_string();
+    // Every exit path below records the reconcile with observe_reconcile, including the ones before reconcile_core runs
+    let cr_name = match cr.meta().name.as_ref() {
+        Some(name) => name,
+        None => {
+            let res = Err(Error::ShimLayerError("Custom resource misses \".metadata.name\"".to_string()));
+            observe_reconcile(&cr_kind, 0, &res);
+            return res;
+        }
+    };
+    // A cluster-scoped custom resource has no namespace and is keyed by the empty namespace
+    let cr_namespace = match cr.meta().namespace.as_ref() {
+        Some(namespace) => namespace.clone(),
+        None if !is_namespaced::<K>() => String::new(),
+        None => {
+            let res = Err(Error::ShimLayerError("Custom resources misses \".metadata.namespace\"".to_string()));
+            observe_reconcile(&cr_kind, 0, &res);
+            return res;
+        }
+    };
 
     let cr_key = format!("{}/{}/{}", cr_kind, cr_namespace, cr_name);
     let log_header = format!("Reconciling {}:", cr_key);
 
-    let cr_api = Api::<K>::namespaced(client.clone(), &cr_namespace);
-    // Get the custom resource by a quorum read to Kubernetes' storage (etcd) to get the most updated custom resource
-    let get_cr_resp = cr_api.get(&cr_name).await;
+    // Get the custom resource from the backend to get the most updated custom resource
+    let get_cr_resp = backend.get_cr::<K>(&cr_namespace, &cr_name).await;
     match get_cr_resp {
-        Err(kube_client::error::Error::Api(ErrorResponse { reason, .. }))
-            if &reason == "NotFound" =>
-        {
+        Err(APIError::ObjectNotFound) => {
             warn!(
                 "{} Custom resource {} not found, end reconcile",
                 log_header, cr_name
             );
-            return Ok(Action::await_change());
+            let res = Ok(Action::await_change());
+            observe_reconcile(&cr_kind, 0, &res);
+            return res;
         }
         Err(err) => {
             warn!(
-                "{} Get custom resource {} failed with error: {}, will retry reconcile",
+                "{} Get custom resource {} failed with error: {:?}, will retry reconcile",
                 log_header, cr_name, err
             );
-            return Ok(Action::requeue(Duration::from_secs(60)));
+            // The failure is returned as an error so that error_policy retries the reconcile with the backoff
+            let res = Err(Error::ShimLayerError(format!("Get custom resource {} failed with error: {:?}", cr_name, err)));
+            observe_reconcile(&cr_kind, 0, &res);
+            return res;
         }
         _ => {}
     }
@@ -198,12 +431,25 @@ where
         k8s_openapi::serde_json::to_string(&cr).unwrap()
     );
 
+    let finalizer = R::finalizer();
+    let terminating = finalizer.is_some() && cr.meta().deletion_timestamp.is_some();
+    // The reconcile starts from the custom resource returned by the finalizer update (if any),
+    // so that reconcile_core sees the added finalizer and the new resource version
+    let cr = match finalizer.as_ref().and_then(|f| finalizer_update_request(&cr, f, true)) {
+        Some(req) => update_finalizers::<K, B>(backend, req, &log_header).await.unwrap_or(cr),
+        None => cr,
+    };
+
+    // The trace is only built when it is going to be recorded
+    let mut trace = trace_recorder.map(|_| ReconcileTrace::new(cr_key.clone(), &cr));
     let cr_wrapper = R::K::from_kube(cr);
     let mut state = R::reconcile_init_state();
     let mut resp_option: Option<Response<R::EResp>> = None;
     // check_fault_timing is only set to true right after the controller issues any create, update or delete request,
     // or external request
     let mut check_fault_timing: bool;
+    // iterations is the number of reconcile_core invocations, which is exported as a metric
+    let mut iterations: usize = 0;
 
     // Call reconcile_core in a loop
     loop {
@@ -215,245 +461,74 @@ where
         }
         if R::reconcile_error(&state) {
             warn!("{} error", log_header);
-            return Err(Error::ReconcileCoreError);
+            record_trace(trace_recorder, trace, TraceOutcome::Error, &log_header);
+            let res = Err(Error::ReconcileCoreError);
+            observe_reconcile(&cr_kind, iterations, &res);
+            return res;
         }
+        let mut trace_step = trace.as_ref().map(|_| TraceStep::new(&state));
+        // The fault plan selects the requests by the reconcile step that issues them
+        let reconcile_step = state.reconcile_step_name();
         // Feed the current reconcile state and get the new state and the pending request
-        let (state_prime, request_option) = R::reconcile_core(&cr_wrapper, resp_option, state);
-        // Pattern match the request and send requests to the Kubernetes API via kube-rs methods
+        let (state_prime, request_option) = if terminating {
+            R::reconcile_delete(&cr_wrapper, resp_option, state)
+        } else {
+            R::reconcile_core(&cr_wrapper, resp_option, state)
+        };
+        iterations += 1;
+        if let Some(trace_step) = trace_step.as_mut() {
+            trace_step.request = request_option.as_ref().map(request_json);
+        }
+        // Pattern match the request and send requests to the backend
         match request_option {
             Some(request) => match request {
                 Request::KRequest(req) => {
-                    let kube_resp: KubeAPIResponse;
-                    match req {
-                        KubeAPIRequest::GetRequest(get_req) => {
-                            let api = Api::<kube::api::DynamicObject>::namespaced_with(
-                                client.clone(),
-                                &get_req.namespace,
-                                get_req.api_resource.as_kube_ref(),
-                            );
-                            let key = get_req.key();
-                            match api.get(&get_req.name).await {
-                                Err(err) => {
-                                    kube_resp = KubeAPIResponse::GetResponse(KubeGetResponse {
-                                        res: Err(kube_error_to_api_error(&err)),
-                                    });
-                                    info!("{} Get {} failed with error: {}", log_header, key, err);
-                                }
-                                Ok(obj) => {
-                                    kube_resp = KubeAPIResponse::GetResponse(KubeGetResponse {
-                                        res: Ok(DynamicObject::from_kube(obj)),
-                                    });
-                                    info!("{} Get {} done", log_header, key);
-                                }
-                            }
-                        }
-                        KubeAPIRequest::ListRequest(list_req) => {
-                            let api = Api::<kube::api::DynamicObject>::namespaced_with(
-                                client.clone(),
-                                &list_req.namespace,
-                                list_req.api_resource.as_kube_ref(),
-                            );
-                            let key = list_req.key();
-                            let lp = ListParams::default();
-                            match api.list(&lp).await {
-                                Err(err) => {
-                                    kube_resp = KubeAPIResponse::ListResponse(KubeListResponse {
-                                        res: Err(kube_error_to_api_error(&err)),
-                                    });
-                                    info!("{} List {} failed with error: {}", log_header, key, err);
-                                }
-                                Ok(obj_list) => {
-                                    kube_resp = KubeAPIResponse::ListResponse(KubeListResponse {
-                                        res: Ok(obj_list
-                                            .items
-                                            .into_iter()
-                                            .map(|obj| DynamicObject::from_kube(obj))
-                                            .collect()),
-                                    });
-                                    info!("{} List {} done", log_header, key);
-                                }
-                            }
-                        }
-                        KubeAPIRequest::CreateRequest(create_req) => {
-                            check_fault_timing = true;
-                            let api = Api::<kube::api::DynamicObject>::namespaced_with(
-                                client.clone(),
-                                &create_req.namespace,
-                                create_req.api_resource.as_kube_ref(),
-                            );
-                            let pp = PostParams::default();
-                            let key = create_req.key();
-                            let obj_to_create = create_req.obj.into_kube();
-                            match api.create(&pp, &obj_to_create).await {
-                                Err(err) => {
-                                    kube_resp =
-                                        KubeAPIResponse::CreateResponse(KubeCreateResponse {
-                                            res: Err(kube_error_to_api_error(&err)),
-                                        });
-                                    info!(
-                                        "{} Create {} failed with error: {}",
-                                        log_header, key, err
-                                    );
-                                }
-                                Ok(obj) => {
-                                    kube_resp =
-                                        KubeAPIResponse::CreateResponse(KubeCreateResponse {
-                                            res: Ok(DynamicObject::from_kube(obj)),
-                                        });
-                                    info!("{} Create {} done", log_header, key);
-                                }
-                            }
-                        }
-                        KubeAPIRequest::DeleteRequest(delete_req) => {
-                            check_fault_timing = true;
-                            let api = Api::<kube::api::DynamicObject>::namespaced_with(
-                                client.clone(),
-                                &delete_req.namespace,
-                                delete_req.api_resource.as_kube_ref(),
-                            );
-                            let mut dp = DeleteParams::default();
-                            if delete_req.preconditions.is_some() {
-                                dp = dp.preconditions(
-                                    delete_req.preconditions.clone().unwrap().into_kube(),
-                                );
-                            }
-                            let key = delete_req.key();
-                            match api.delete(&delete_req.name, &dp).await {
-                                Err(err) => {
-                                    kube_resp =
-                                        KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
-                                            res: Err(kube_error_to_api_error(&err)),
-                                        });
-                                    info!(
-                                        "{} Delete {} failed with error: {}",
-                                        log_header, key, err
-                                    );
-                                }
-                                Ok(_) => {
-                                    kube_resp =
-                                        KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
-                                            res: Ok(()),
-                                        });
-                                    info!("{} Delete {} done", log_header, key);
-                                }
-                            }
-                        }
-                        KubeAPIRequest::UpdateRequest(update_req) => {
-                            check_fault_timing = true;
-                            let api = Api::<kube::api::DynamicObject>::namespaced_with(
-                                client.clone(),
-                                &update_req.namespace,
-                                update_req.api_resource.as_kube_ref(),
-                            );
-                            let pp = PostParams::default();
-                            let key = update_req.key();
-                            let obj_to_update = update_req.obj.into_kube();
-                            match api.replace(&update_req.name, &pp, &obj_to_update).await {
-                                Err(err) => {
-                                    kube_resp =
-                                        KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
-                                            res: Err(kube_error_to_api_error(&err)),
-                                        });
-                                    info!(
-                                        "{} Update {} failed with error: {}",
-                                        log_header, key, err
-                                    );
-                                }
-                                Ok(obj) => {
-                                    kube_resp =
-                                        KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
-                                            res: Ok(DynamicObject::from_kube(obj)),
-                                        });
-                                    info!("{} Update {} done", log_header, key);
-                                }
-                            }
-                        }
-                        KubeAPIRequest::UpdateStatusRequest(update_status_req) => {
-                            check_fault_timing = true;
-                            let api = Api::<kube::api::DynamicObject>::namespaced_with(
-                                client.clone(),
-                                &update_status_req.namespace,
-                                update_status_req.api_resource.as_kube_ref(),
-                            );
-                            let pp = PostParams::default();
-                            let key = update_status_req.key();
-                            let obj_to_update = update_status_req.obj.into_kube();
-                            // Here we assume serde_json always succeed
-                            match api
-                                .replace_status(
-                                    &update_status_req.name,
-                                    &pp,
-                                    k8s_openapi::serde_json::to_vec(&obj_to_update)
-                                        .unwrap(),
-                                )
+                    check_fault_timing = match req {
+                        KubeAPIRequest::GetRequest(_) | KubeAPIRequest::ListRequest(_) => false,
+                        _ => true,
+                    };
+                    let verb = request_verb(&req);
+                    let start = Instant::now();
+                    let kube_resp = match fault_injection {
+                        Some(FaultInjection::Plan(injector)) => {
+                            injector
+                                .handle_request(backend, req, &cr_key, &reconcile_step, &log_header)
                                 .await
-                            {
-                                Err(err) => {
-                                    kube_resp = KubeAPIResponse::UpdateStatusResponse(
-                                        KubeUpdateStatusResponse {
-                                            res: Err(kube_error_to_api_error(&err)),
-                                        },
-                                    );
-                                    info!(
-                                        "{} UpdateStatus {} failed with error: {}",
-                                        log_header, key, err
-                                    );
-                                }
-                                Ok(obj) => {
-                                    kube_resp = KubeAPIResponse::UpdateStatusResponse(
-                                        KubeUpdateStatusResponse {
-                                            res: Ok(DynamicObject::from_kube(obj)),
-                                        },
-                                    );
-                                    info!("{} UpdateStatus {} done", log_header, key);
-                                }
-                            }
-                        }
-                        KubeAPIRequest::GetThenDeleteRequest(req) => {
-                            check_fault_timing = true;
-                            kube_resp = KubeAPIResponse::GetThenDeleteResponse(
-                                transactional_get_then_delete_by_retry(
-                                    client,
-                                    req,
-                                    log_header.clone(),
-                                )
-                                .await,
-                            );
-                        }
-                        KubeAPIRequest::GetThenUpdateRequest(req) => {
-                            check_fault_timing = true;
-                            kube_resp = KubeAPIResponse::GetThenUpdateResponse(
-                                transactional_get_then_update_by_retry(
-                                    client,
-                                    req,
-                                    log_header.clone(),
-                                )
-                                .await,
-                            );
-                        }
-                        KubeAPIRequest::GetThenUpdateStatusRequest(req) => {
-                            check_fault_timing = true;
-                            kube_resp = KubeAPIResponse::GetThenUpdateStatusResponse(
-                                transactional_get_then_update_status_by_retry(
-                                    client,
-                                    req,
-                                    log_header.clone(),
-                                )
-                                .await,
-                            );
                         }
+                        _ => backend.handle_request(req, &log_header).await,
+                    };
+                    observe_api_request(&cr_kind, verb, response_error(&kube_resp), start.elapsed());
+                    if let Some(trace_step) = trace_step.as_mut() {
+                        trace_step.response = Some(response_to_json(&kube_resp));
                     }
                     resp_option = Some(Response::KResponse(kube_resp));
                 }
                 Request::ExternalRequest(external_req) => {
                     check_fault_timing = true;
-                    let external_resp = E::external_call(external_req);
+                    let call = call_with_timeout(external, external_req, external_call_timeout);
+                    let external_resp = match fault_injection {
+                        Some(FaultInjection::Plan(injector)) => {
+                            injector
+                                .handle_external_request(call, &cr_key, &reconcile_step, &log_header)
+                                .await
+                        }
+                        _ => call.await,
+                    };
+                    if let Err(err) = &external_resp {
+                        warn!("{} External request failed with error: {:?}", log_header, err);
+                    }
+                    if let Some(trace_step) = trace_step.as_mut() {
+                        trace_step.response = Some(external_response_to_json(&external_resp));
+                    }
                     resp_option = Some(Response::ExternalResponse(external_resp));
                 }
             },
             _ => resp_option = None,
         }
-        if check_fault_timing && fault_injection {
+        if let (Some(trace), Some(trace_step)) = (trace.as_mut(), trace_step) {
+            trace.steps.push(trace_step);
+        }
+        if let (true, Some(FaultInjection::CrashOrContinue(client))) = (check_fault_timing, fault_injection) {
             // If the controller just issues create, update, delete or external request,
             // and fault injection option is on, then check whether to crash at this point
             let result = crash_or_continue(client, &cr_key, &log_header).await;
@@ -468,23 +543,90 @@ where
         state = state_prime;
     }
 
-    return Ok(Action::requeue(Duration::from_secs(60)));
+    if terminating {
+        // The custom resource is read again before removing the finalizer, since the cleanup may have updated it
+        // (e.g., its status) and an update with the resource version read at the beginning would conflict
+        match backend.get_cr::<K>(&cr_namespace, &cr_name).await {
+            Ok(cr) => {
+                if let Some(req) = finalizer.as_ref().and_then(|f| finalizer_update_request(&cr, f, false)) {
+                    update_finalizers::<K, B>(backend, req, &log_header).await;
+                }
+            }
+            Err(err) => {
+                warn!("{} Get custom resource {} failed with error: {:?}, will retry removing the finalizer", log_header, cr_name, err);
+            }
+        }
+    }
+    record_trace(trace_recorder, trace, TraceOutcome::Done, &log_header);
+    let res = Ok(Action::requeue(requeue_interval));
+    observe_reconcile(&cr_kind, iterations, &res);
+    return res;
+}
+
+// finalizer_update_request returns the request that adds (if add) or removes (if !add) finalizer to or from cr,
+// or None if there is nothing to update. Same as finalizer_update_req in the controller model,
+// the finalizer is only added to a custom resource without a deletion timestamp,
+// and only removed from a custom resource with a deletion timestamp.
+// The update carries the resource version of cr, so cr should be freshly read.
+pub fn finalizer_update_request<K>(cr: &K, finalizer: &str, add: bool) -> Option<KubeAPIRequest>
+where
+    K: Resource + Serialize,
+    K::DynamicType: Default,
+{
+    let meta = cr.meta();
+    let terminating = meta.deletion_timestamp.is_some();
+    let mut finalizers = meta.finalizers.clone().unwrap_or_default();
+    let has_finalizer = finalizers.iter().any(|f| f == finalizer);
+    if add && !terminating && !has_finalizer {
+        finalizers.push(finalizer.to_string());
+    } else if !add && terminating && has_finalizer {
+        finalizers.retain(|f| f != finalizer);
+    } else {
+        return None;
+    }
+    let mut obj: kube::api::DynamicObject = serde_json::from_value(serde_json::to_value(cr).ok()?).ok()?;
+    obj.metadata.finalizers = Some(finalizers);
+    Some(KubeAPIRequest::UpdateRequest(KubeUpdateRequest {
+        api_resource: ApiResource::from_kube(kube::api::ApiResource::erase::<K>(&K::DynamicType::default())),
+        name: meta.name.clone().unwrap_or_default(),
+        namespace: meta.namespace.clone().unwrap_or_default(),
+        obj: DynamicObject::from_kube(obj),
+    }))
+}
+
+// update_finalizers sends the finalizer update to the backend and returns the updated custom resource.
+// A failed update is only logged.
+async fn update_finalizers<K, B>(backend: &B, req: KubeAPIRequest, log_header: &str) -> Option<K>
+where
+    K: DeserializeOwned,
+    B: APIServerBackend,
+{
+    match backend.handle_request(req, log_header).await {
+        KubeAPIResponse::UpdateResponse(KubeUpdateResponse { res: Ok(obj) }) => {
+            serde_json::to_value(obj.as_kube_ref()).ok().and_then(|val| serde_json::from_value(val).ok())
+        }
+        resp => {
+            if let Some(err) = response_error(&resp) {
+                warn!("{} Update finalizers failed with error: {:?}, will retry in the next reconcile", log_header, err);
+            }
+            None
+        }
+    }
 }
 
 // transactional_get_then_delete_by_retry retries get and then delete upon conflict errors to simulate atomic operations.
 // This guarantees that the entire get_then_delete operation will not fail due to conflicts between concurrent
 // controllers. Note that transactional_get_then_delete_by_retry's termination depends on fairness assumptions.
+// If dry_run is set, the delete is sent with server-side dry run.
 pub async fn transactional_get_then_delete_by_retry(
     client: &Client,
+    cr_kind: &str,
     req: KubeGetThenDeleteRequest,
     log_header: String,
+    dry_run: bool,
 ) -> KubeGetThenDeleteResponse {
     // sanity check, can be removed if type invariant is supported by Verus
-    let api = Api::<kube::api::DynamicObject>::namespaced_with(
-        client.clone(),
-        &req.namespace,
-        req.api_resource.as_kube_ref(),
-    );
+    let api = dynamic_api_in(client, &req.namespace, req.api_resource.as_kube_ref());
     let key = req.key();
 
     loop {
@@ -500,12 +642,12 @@ pub async fn transactional_get_then_delete_by_retry(
             };
         }
         // Step 2: if the object exists, perform a check using a predicate on object
-        // The predicate: Is the current object owned by req.owner_ref?
-        // TODO: the predicate should be provided by clients instead of the hardcoded one
+        // The predicate: Is the current object owned by req.owner_ref, and does req.predicate hold on it?
         let current_obj = DynamicObject::from_kube(get_result.unwrap());
         if !current_obj
             .metadata()
             .owner_references_contains(&req.owner_ref)
+            || !req.predicate.holds_on(current_obj.as_kube_ref())
         {
             return KubeGetThenDeleteResponse {
                 res: Err(APIError::TransactionAbort),
@@ -513,10 +655,11 @@ pub async fn transactional_get_then_delete_by_retry(
         }
         // Step 3: if the check passes, delete the object with a precondition
         // Note that resource_version and uid comes from the current object to avoid conflict error
-        let dp = DeleteParams::default().preconditions(kube::api::Preconditions {
+        let mut dp = DeleteParams::default().preconditions(kube::api::Preconditions {
             resource_version: current_obj.as_kube_ref().metadata.resource_version.clone(),
             uid: current_obj.as_kube_ref().metadata.uid.clone(),
         });
+        dp.dry_run = dry_run;
         match api.delete(&req.name, &dp).await {
             Err(err) => {
                 let api_err = kube_error_to_api_error(&err);
@@ -527,6 +670,7 @@ pub async fn transactional_get_then_delete_by_retry(
                             "{} Delete of Get-then-Delete {} failed with Conflict; retry...",
                             log_header, key
                         );
+                        inc_conflict_retry(cr_kind, &req.api_resource.as_kube_ref().kind, "GetThenDelete");
                         continue;
                     }
                     _ => {
@@ -549,18 +693,20 @@ pub async fn transactional_get_then_delete_by_retry(
 // transactional_get_then_update_by_retry retries get and then update upon conflict errors to simulate atomic operations.
 // This guarantees that the entire get_then_update operation will not fail due to conflicts between concurrent
 // controllers. Note that transactional_get_then_update_by_retry's termination depends on fairness assumptions.
+// If dry_run is set, the update is sent with server-side dry run.
 pub async fn transactional_get_then_update_by_retry(
     client: &Client,
+    cr_kind: &str,
     req: KubeGetThenUpdateRequest,
     log_header: String,
+    dry_run: bool,
 ) -> KubeGetThenUpdateResponse {
     // sanity check, can be removed if type invariant is supported by Verus
-    let api = Api::<kube::api::DynamicObject>::namespaced_with(
-        client.clone(),
-        &req.namespace,
-        req.api_resource.as_kube_ref(),
-    );
-    let pp = PostParams::default();
+    let api = dynamic_api_in(client, &req.namespace, req.api_resource.as_kube_ref());
+    let pp = PostParams {
+        dry_run: dry_run,
+        ..PostParams::default()
+    };
     let key = req.key();
     let mut obj_to_update = req.obj.into_kube();
 
@@ -577,12 +723,12 @@ pub async fn transactional_get_then_update_by_retry(
             };
         }
         // Step 2: if the object exists, perform a check using a predicate on object
-        // The predicate: Is the current object owned by req.owner_ref?
-        // TODO: the predicate should be provided by clients instead of the hardcoded one
+        // The predicate: Is the current object owned by req.owner_ref, and does req.predicate hold on it?
         let current_obj = DynamicObject::from_kube(get_result.unwrap());
         if !current_obj
             .metadata()
             .owner_references_contains(&req.owner_ref)
+            || !req.predicate.holds_on(current_obj.as_kube_ref())
         {
             return KubeGetThenUpdateResponse {
                 res: Err(APIError::TransactionAbort),
@@ -603,6 +749,7 @@ pub async fn transactional_get_then_update_by_retry(
                             "{} Update of Get-then-Update {} failed with Conflict; retry...",
                             log_header, key
                         );
+                        inc_conflict_retry(cr_kind, &req.api_resource.as_kube_ref().kind, "GetThenUpdate");
                         continue;
                     }
                     _ => {
@@ -627,18 +774,20 @@ pub async fn transactional_get_then_update_by_retry(
 // transactional_get_then_update_status_by_retry retries get and then update status upon conflict errors to simulate atomic operations.
 // This guarantees that the entire get_then_update_status operation will not fail due to conflicts between concurrent
 // controllers. Note that transactional_get_then_update_status_by_retry's termination depends on fairness assumptions.
+// If dry_run is set, the update is sent with server-side dry run.
 pub async fn transactional_get_then_update_status_by_retry(
     client: &Client,
+    cr_kind: &str,
     req: KubeGetThenUpdateStatusRequest,
     log_header: String,
+    dry_run: bool,
 ) -> KubeGetThenUpdateStatusResponse {
     // sanity check, can be removed if type invariant is supported by Verus
-    let api = Api::<kube::api::DynamicObject>::namespaced_with(
-        client.clone(),
-        &req.namespace,
-        req.api_resource.as_kube_ref(),
-    );
-    let pp = PostParams::default();
+    let api = dynamic_api_in(client, &req.namespace, req.api_resource.as_kube_ref());
+    let pp = PostParams {
+        dry_run: dry_run,
+        ..PostParams::default()
+    };
     let key = req.key();
     let mut obj_to_update = req.obj.into_kube();
     
@@ -655,11 +804,12 @@ pub async fn transactional_get_then_update_status_by_retry(
             };
         }
         // Step 2: if the object exists, perform a check using a predicate on object
-        // The predicate: Is the current object owned by req.owner_ref?
+        // The predicate: Is the current object owned by req.owner_ref, and does req.predicate hold on it?
         let current_obj = DynamicObject::from_kube(get_result.unwrap());
         if !current_obj
             .metadata()
             .owner_references_contains(&req.owner_ref)
+            || !req.predicate.holds_on(current_obj.as_kube_ref())
         {
             return KubeGetThenUpdateStatusResponse {
                 res: Err(APIError::TransactionAbort),
@@ -687,6 +837,7 @@ pub async fn transactional_get_then_update_status_by_retry(
                             "{} UpdateStatus of Get-then-Update-Status {} failed with Conflict; retry...",
                             log_header, key
                         );
+                        inc_conflict_retry(cr_kind, &req.api_resource.as_kube_ref().kind, "GetThenUpdateStatus");
                         continue;
                     }
                     _ => {
@@ -709,18 +860,98 @@ pub async fn transactional_get_then_update_status_by_retry(
 }
 
 // error_policy defines the controller's behavior when the reconcile ends with an error.
-pub fn error_policy<K>(_object: Arc<K>, _error: &Error, _ctx: Arc<Data>) -> Action
+// The custom resource is requeued with an exponential backoff on its consecutive failures.
+pub fn error_policy<K>(object: Arc<K>, error: &Error, ctx: Arc<Data>) -> Action
 where
     K: Clone + Resource + DeserializeOwned + Debug + Send + Sync + 'static,
-    K::DynamicType: Eq + Hash + Clone + Debug + Unpin,
+    K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin,
 {
-    Action::requeue(Duration::from_secs(10))
+    let cr_key = cr_key_of(object.as_ref());
+    let delay = ctx.backoff.on_failure(&cr_key);
+    warn!(
+        "Reconciling {}: failed {} time(s) in a row with error: {}, will retry after {:?}",
+        cr_key,
+        ctx.backoff.failures(&cr_key),
+        error,
+        delay
+    );
+    Action::requeue(delay)
+}
+
+// record_trace writes the trace of a reconcile that ends with outcome.
+// Failing to record the trace does not fail the reconcile.
+fn record_trace(
+    trace_recorder: Option<&TraceRecorder>,
+    trace: Option<ReconcileTrace>,
+    outcome: TraceOutcome,
+    log_header: &str,
+) {
+    if let (Some(trace_recorder), Some(mut trace)) = (trace_recorder, trace) {
+        trace.outcome = outcome;
+        if let Err(err) = trace_recorder.record(&trace) {
+            error!("{} failed to record trace: {}", log_header, err);
+        }
+    }
 }
 
 // Data is passed to reconcile_with.
-// It carries the client that communicates with Kubernetes API.
+// It carries the client that communicates with Kubernetes API,
+// the controller config, the per custom resource backoff state,
+// the trace recorder (if the config has a trace file),
+// the fault injection (if the config has a fault plan or enables fault injection),
+// the caches of the watches to serve the Cached reads from
+// and the event correlator that aggregates the events of the custom resources.
+// The caches are set by the ControllerManager when it starts the watches.
 pub struct Data {
     pub client: Client,
+    pub config: ControllerConfig,
+    pub backoff: FailureBackoff,
+    pub trace_recorder: Option<TraceRecorder>,
+    pub fault_injection: Option<FaultInjection>,
+    pub read_cache: Option<ReadCache>,
+    pub event_correlator: EventCorrelator,
+}
+
+impl Data {
+    pub async fn new(client: Client, config: ControllerConfig) -> Result<Data> {
+        let trace_recorder = match &config.trace_file {
+            Some(path) => Some(TraceRecorder::create(path)?),
+            None => None,
+        };
+        let fault_injection = match &config.fault_plan {
+            Some(source) => Some(FaultInjection::Plan(FaultInjector::load(&client, source).await?)),
+            None if config.fault_injection => Some(FaultInjection::CrashOrContinue(client.clone())),
+            None => None,
+        };
+        Ok(Data {
+            client: client,
+            backoff: FailureBackoff::new(&config),
+            config: config,
+            trace_recorder: trace_recorder,
+            fault_injection: fault_injection,
+            read_cache: None,
+            event_correlator: EventCorrelator::new(),
+        })
+    }
+}
+
+// cr_key_of returns the key of the custom resource in the form of kind/namespace/name,
+// which is the same as the key used in the reconcile logs.
+fn cr_key_of<K>(cr: &K) -> String
+where
+    K: Resource,
+    K::DynamicType: Default,
+{
+    cr_key(
+        &K::kind(&K::DynamicType::default()),
+        &cr.meta().namespace.clone().unwrap_or_default(),
+        &cr.meta().name.clone().unwrap_or_default(),
+    )
+}
+
+// cr_key is the key of a custom resource in the backoff state and the logs, in the form of "kind/namespace/name".
+pub fn cr_key(kind: &str, namespace: &str, name: &str) -> String {
+    format!("{}/{}/{}", kind, namespace, name)
 }
 
 // kube_error_to_api_error translates the API error from kube-rs APIs
@@ -746,6 +977,8 @@ pub fn kube_error_to_api_error(error: &kube::Error) -> APIError {
                 APIError::Timeout
             } else if &error_resp.reason == "ServerTimeout" {
                 APIError::ServerTimeout
+            } else if &error_resp.reason == "Expired" {
+                APIError::Expired
             } else {
                 APIError::Other
             }
@@ -0,0 +1,331 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, dynamic::DynamicObject, event::Event, resource::*};
+use crate::shim_layer::backend::*;
+use crate::shim_layer::controller_runtime::ApiScope;
+use crate::shim_layer::metrics::{request_verb, response_error};
+use core::fmt::Debug;
+use k8s_openapi::api::core::v1::{EventSource, ObjectReference};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
+use kube::api::Resource;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+// The shim layer records Kubernetes Events on the custom resource under reconcile,
+// so that `kubectl describe` shows what the controller did to the custom resource and why it failed:
+// * a Warning event when a create, update or delete request fails or the reconcile ends with an error, and
+// * a Normal event when a request that the reconciler deems notable (see ReconcileEvents) succeeds,
+//   e.g., creating or condemning a pod.
+//
+// The events are written by the shim layer, not by reconcile_core, so they never show up in the requests of
+// reconcile_core (nor in its traces or plans). The rely/guarantee conditions of the controllers only constrain the
+// requests sent by reconcile_core and the objects of the kinds that the controllers manage, so writing events
+// does not affect the proofs. Failing to record an event is logged and does not fail the reconcile.
+//
+// Like client-go's EventCorrelator, the events are aggregated and rate limited (see EventCorrelator),
+// so a reconcile that keeps failing the same way bumps the count of one event instead of creating a new event
+// every time. The events of a cluster-scoped custom resource are written to the default namespace.
+
+pub const NORMAL_EVENT: &str = "Normal";
+pub const WARNING_EVENT: &str = "Warning";
+
+// The component reported as the source of the events
+const EVENT_SOURCE_COMPONENT: &str = "anvil-controller";
+
+// The namespace of the events of a cluster-scoped custom resource
+const CLUSTER_SCOPED_EVENT_NAMESPACE: &str = "default";
+
+// The token bucket of each custom resource holds up to EVENT_BURST tokens and gets one token back every
+// EVENT_REFILL_INTERVAL, the same as the defaults of client-go's EventCorrelator.
+pub const EVENT_BURST: u32 = 25;
+pub const EVENT_REFILL_INTERVAL: Duration = Duration::from_secs(300);
+
+// The number of custom resources whose events are tracked; the one seen least recently is forgotten first.
+const MAX_TRACKED_OBJECTS: usize = 4096;
+
+// NotableEvent is the reason and message of the Normal event recorded for a notable request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotableEvent {
+    pub reason: String,
+    pub message: String,
+}
+
+// ReconcileEvents decides which requests of the reconciler are notable enough to be recorded as events.
+// notable_event is called with the verb (see request_verb), kind and name of each create, update or delete request
+// that succeeds. For a create request, name is the one allocated by the API server if the object has generate_name.
+// By default no request is notable. The controllers implement it next to their exec reconcilers (outside the verified code).
+pub trait ReconcileEvents {
+    fn notable_event(_verb: &str, _kind: &str, _name: &str) -> Option<NotableEvent> {
+        None
+    }
+}
+
+// successful_event is the Normal event of a notable request, worded in the same way for all the controllers
+// (following the built-in ReplicaSet controller): the reason is Successful<action> (e.g., SuccessfulCreate)
+// and the message is "<done> <kind>: <name>" (e.g., "Created Pod: name").
+pub fn successful_event(action: &str, done: &str, kind: &str, name: &str) -> Option<NotableEvent> {
+    Some(NotableEvent {
+        reason: format!("Successful{}", action),
+        message: format!("{} {}: {}", done, kind, name),
+    })
+}
+
+// EventCorrelator keeps, across reconciles, the event recorded for each custom resource and reason
+// and the token bucket of each custom resource. It is shared by all the EventRecorders of a controller (see Data).
+pub struct EventCorrelator {
+    burst: u32,
+    refill_interval: Duration,
+    objects: Mutex<HashMap<String, ObjectEvents>>,
+}
+
+// ObjectEvents is what the EventCorrelator keeps for one custom resource.
+struct ObjectEvents {
+    tokens: u32,
+    last_refill: Instant,
+    last_seen: Instant,
+    // recorded maps the type and reason of an event to the name and count of the event recorded for them
+    recorded: HashMap<(String, String), (String, i32)>,
+}
+
+// Correlation is what EventCorrelator::correlate decides to do with a new event.
+enum Correlation {
+    // the event is dropped by the rate limit
+    Drop,
+    // a new event is created
+    Create,
+    // the existing event with the name is bumped to the count
+    Bump(String, i32),
+}
+
+impl EventCorrelator {
+    pub fn new() -> EventCorrelator {
+        EventCorrelator::with_rate_limit(EVENT_BURST, EVENT_REFILL_INTERVAL)
+    }
+
+    pub fn with_rate_limit(burst: u32, refill_interval: Duration) -> EventCorrelator {
+        EventCorrelator {
+            burst: burst,
+            refill_interval: refill_interval,
+            objects: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // correlate takes a token of the custom resource with object_key and decides how to record the event.
+    fn correlate(&self, object_key: &str, type_: &str, reason: &str) -> Correlation {
+        let now = Instant::now();
+        let mut objects = self.objects.lock().unwrap();
+        if !objects.contains_key(object_key) && objects.len() >= MAX_TRACKED_OBJECTS {
+            let least_recent = objects.iter().min_by_key(|(_, events)| events.last_seen).map(|(key, _)| key.clone());
+            if let Some(key) = least_recent {
+                objects.remove(&key);
+            }
+        }
+        let events = objects.entry(object_key.to_string()).or_insert_with(|| ObjectEvents {
+            tokens: self.burst,
+            last_refill: now,
+            last_seen: now,
+            recorded: HashMap::new(),
+        });
+        events.last_seen = now;
+        if !self.refill_interval.is_zero() {
+            let refills = (now.duration_since(events.last_refill).as_nanos() / self.refill_interval.as_nanos()) as u32;
+            if refills > 0 {
+                events.tokens = events.tokens.saturating_add(refills).min(self.burst);
+                events.last_refill += self.refill_interval * refills;
+            }
+        }
+        if events.tokens == 0 {
+            return Correlation::Drop;
+        }
+        events.tokens -= 1;
+        match events.recorded.get(&(type_.to_string(), reason.to_string())) {
+            Some((name, count)) => Correlation::Bump(name.clone(), count.saturating_add(1)),
+            None => Correlation::Create,
+        }
+    }
+
+    // observe remembers that the event of the custom resource with object_key, type_ and reason
+    // is now the one with the name and count.
+    fn observe(&self, object_key: &str, type_: &str, reason: &str, name: String, count: i32) {
+        if let Some(events) = self.objects.lock().unwrap().get_mut(object_key) {
+            events.recorded.insert((type_.to_string(), reason.to_string()), (name, count));
+        }
+    }
+}
+
+// EventRecorder records the events of one custom resource.
+pub struct EventRecorder<'a> {
+    involved_object: ObjectReference,
+    object_key: String,
+    namespace: String,
+    notable_event: fn(&str, &str, &str) -> Option<NotableEvent>,
+    correlator: &'a EventCorrelator,
+}
+
+impl<'a> EventRecorder<'a> {
+    // new returns the recorder of the events of cr, where notable_event is usually R::notable_event
+    // and correlator is shared by the recorders of the controller.
+    pub fn new<K>(
+        cr: &K, notable_event: fn(&str, &str, &str) -> Option<NotableEvent>, correlator: &'a EventCorrelator,
+    ) -> EventRecorder<'a>
+    where
+        K: Resource,
+        K::DynamicType: Default,
+    {
+        let dt = K::DynamicType::default();
+        let meta = cr.meta();
+        EventRecorder {
+            involved_object: ObjectReference {
+                api_version: Some(K::api_version(&dt).to_string()),
+                kind: Some(K::kind(&dt).to_string()),
+                name: meta.name.clone(),
+                namespace: meta.namespace.clone(),
+                uid: meta.uid.clone(),
+                ..ObjectReference::default()
+            },
+            object_key: format!(
+                "{}/{}/{}/{}",
+                K::kind(&dt),
+                meta.namespace.as_deref().unwrap_or_default(),
+                meta.name.as_deref().unwrap_or_default(),
+                meta.uid.as_deref().unwrap_or_default()
+            ),
+            namespace: meta.namespace.clone().unwrap_or_else(|| CLUSTER_SCOPED_EVENT_NAMESPACE.to_string()),
+            notable_event: notable_event,
+            correlator: correlator,
+        }
+    }
+
+    // record records an event of type_ (NORMAL_EVENT or WARNING_EVENT) on the custom resource through backend.
+    // If an event with the same type and reason was recorded before, its count and lastTimestamp are bumped
+    // and its message is replaced; otherwise a new event is created. Events over the rate limit are dropped.
+    pub async fn record<B: APIServerBackend>(&self, backend: &B, type_: &str, reason: &str, message: &str, log_header: &str) {
+        match self.correlator.correlate(&self.object_key, type_, reason) {
+            Correlation::Drop => {
+                warn!("{} Dropped {} event {} over the rate limit", log_header, type_, reason);
+            }
+            Correlation::Bump(name, count) => {
+                let resp = self.bump(backend, &name, count, message, log_header).await;
+                match response_error(&resp) {
+                    None => self.correlator.observe(&self.object_key, type_, reason, name, count),
+                    // The event is gone (e.g., it expired), so a new one is created
+                    Some(err) if err.is_object_not_found() => self.create(backend, type_, reason, message, log_header).await,
+                    Some(err) => warn!("{} Failed to record {} event {} with error: {:?}", log_header, type_, reason, err),
+                }
+            }
+            Correlation::Create => self.create(backend, type_, reason, message, log_header).await,
+        }
+    }
+
+    // bump patches the count, lastTimestamp and message of the existing event with the name.
+    async fn bump<B: APIServerBackend>(&self, backend: &B, name: &str, count: i32, message: &str, log_header: &str) -> KubeAPIResponse {
+        let patch: kube::api::DynamicObject = serde_json::from_value(json!({
+            "apiVersion": "v1",
+            "kind": "Event",
+            "metadata": {"name": name},
+            "count": count,
+            "lastTimestamp": Time(chrono::Utc::now()),
+            "message": message,
+        }))
+        .unwrap();
+        let req = KubeAPIRequest::PatchRequest(KubePatchRequest {
+            api_resource: Event::api_resource(),
+            name: name.to_string(),
+            namespace: self.namespace.clone(),
+            obj: DynamicObject::from_kube(patch),
+            field_manager: None,
+        });
+        backend.handle_request(req, log_header).await
+    }
+
+    // create creates a new event and remembers its name for the later events with the same type and reason.
+    async fn create<B: APIServerBackend>(&self, backend: &B, type_: &str, reason: &str, message: &str, log_header: &str) {
+        let now = Time(chrono::Utc::now());
+        let event = k8s_openapi::api::core::v1::Event {
+            metadata: ObjectMeta {
+                generate_name: Some(format!("{}.", self.involved_object.name.as_deref().unwrap_or_default())),
+                namespace: Some(self.namespace.clone()),
+                ..ObjectMeta::default()
+            },
+            involved_object: self.involved_object.clone(),
+            reason: Some(reason.to_string()),
+            message: Some(message.to_string()),
+            type_: Some(type_.to_string()),
+            count: Some(1),
+            first_timestamp: Some(now.clone()),
+            last_timestamp: Some(now),
+            source: Some(EventSource {
+                component: Some(EVENT_SOURCE_COMPONENT.to_string()),
+                ..EventSource::default()
+            }),
+            ..k8s_openapi::api::core::v1::Event::default()
+        };
+        let req = KubeAPIRequest::CreateRequest(KubeCreateRequest {
+            api_resource: Event::api_resource(),
+            namespace: self.namespace.clone(),
+            obj: Event::from_kube(event).marshal(),
+        });
+        match backend.handle_request(req, log_header).await {
+            KubeAPIResponse::CreateResponse(KubeCreateResponse { res: Ok(obj) }) => {
+                let name = obj.as_kube_ref().metadata.name.clone().unwrap_or_default();
+                self.correlator.observe(&self.object_key, type_, reason, name, 1);
+            }
+            resp => {
+                if let Some(err) = response_error(&resp) {
+                    warn!("{} Failed to record {} event {} with error: {:?}", log_header, type_, reason, err);
+                }
+            }
+        }
+    }
+}
+
+// EventBackend forwards the requests to the inner backend, and for each create, update or delete request
+// records a Warning event if the request fails, or a Normal event if the request succeeds and is notable.
+// The events are written through the inner backend.
+pub struct EventBackend<'a, B: APIServerBackend> {
+    pub inner: &'a B,
+    pub recorder: &'a EventRecorder<'a>,
+}
+
+impl<'a, B: APIServerBackend> APIServerBackend for EventBackend<'a, B> {
+    async fn get_cr<K>(&self, namespace: &str, name: &str) -> Result<K, APIError>
+    where
+        K: Clone + Resource + DeserializeOwned + Debug + Send,
+        K::Scope: ApiScope,
+        K::DynamicType: Default,
+    {
+        self.inner.get_cr::<K>(namespace, name).await
+    }
+
+    async fn handle_request(&self, req: KubeAPIRequest, log_header: &str) -> KubeAPIResponse {
+        if let KubeAPIRequest::GetRequest(_) | KubeAPIRequest::ListRequest(_) = req {
+            return self.inner.handle_request(req, log_header).await;
+        }
+        let verb = request_verb(&req);
+        let kind = request_kind(&req);
+        let key = request_key(&req);
+        let mut name = request_name(&req).to_string();
+        let resp = self.inner.handle_request(req, log_header).await;
+        if let KubeAPIResponse::CreateResponse(KubeCreateResponse { res: Ok(obj) }) = &resp {
+            // The name of an object created with generate_name is only known from the response
+            name = obj.as_kube_ref().metadata.name.clone().unwrap_or_default();
+        }
+        match response_error(&resp) {
+            Some(err) => {
+                let message = format!("{} {} failed with error: {:?}", verb, key, err);
+                self.recorder.record(self.inner, WARNING_EVENT, "FailedRequest", &message, log_header).await;
+            }
+            None => {
+                if let Some(event) = (self.recorder.notable_event)(verb, &kind, &name) {
+                    self.recorder.record(self.inner, NORMAL_EVENT, &event.reason, &event.message, log_header).await;
+                }
+            }
+        }
+        resp
+    }
+}
@@ -1,10 +1,21 @@
-use anyhow::Result;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::*, resource::*};
+use crate::shim_layer::backend::{error_response, request_kind, APIServerBackend};
+use crate::shim_layer::metrics::request_verb;
+use anyhow::{anyhow, Result};
 use k8s_openapi::api::core::v1::ConfigMap;
 use kube::{
     api::{Api, PostParams},
     Client,
 };
-use tracing::info;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{error, info};
 
 pub async fn crash_or_continue(
     client: &Client,
@@ -69,3 +80,451 @@ pub async fn crash_or_continue(
     }
     return Ok(());
 }
+
+// FaultInjection decides which faults to inject into the requests issued by reconcile_core.
+pub enum FaultInjection {
+    // Crash after the n-th mutating request as configured by fault-injection-config (see crash_or_continue)
+    CrashOrContinue(Client),
+    // Inject the faults listed by a fault plan (see FaultPlan)
+    Plan(FaultInjector),
+}
+
+// FaultPlan lists the faults to inject, for example:
+//
+//   seed: 42
+//   faults:
+//   - verb: Create
+//     kind: Pod
+//     reconcileStep: AfterListPods
+//     nth: 2
+//     action: {type: CrashAfterSend}
+//   - verb: Update
+//     probability: 0.1
+//     action: {type: ReturnError, error: Conflict}
+//
+// A fault selects the requests by verb (see request_verb, or "External" for external requests),
+// by the kind of the target object, by the reconcile step that issues the request and by cr_key;
+// a missing selector selects all the requests.
+// The fault is injected at the nth selected request if nth is given, or at each selected request with
+// the given probability drawn from a random generator seeded by seed, or otherwise at every selected request.
+// Note that with concurrent reconciles, the order of the draws (and the counting of nth) depends on scheduling.
+//
+// The faults exercise the failure paths that the cluster spec models: crashes correspond to RestartControllerStep,
+// synthetic errors to DropReqStep, dropped responses to DropRespStep and duplicates to DuplicateReqStep.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaultPlan {
+    #[serde(default)]
+    pub seed: u64,
+    pub faults: Vec<Fault>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fault {
+    pub verb: Option<String>,
+    pub kind: Option<String>,
+    pub reconcile_step: Option<String>,
+    pub cr_key: Option<String>,
+    pub nth: Option<u64>,
+    pub probability: Option<f64>,
+    pub action: FaultAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum FaultAction {
+    // Crash the controller before the request is sent
+    CrashBeforeSend,
+    // Crash the controller after the request is sent, before reconcile_core receives the response
+    CrashAfterSend,
+    // Do not send the request and respond with the error instead
+    ReturnError { error: InjectedError },
+    // Delay the response by millis milliseconds
+    DelayResponse { millis: u64 },
+    // Send the mutating request twice; reconcile_core receives the response to the first one
+    Duplicate,
+    // Send the request but drop the response; reconcile_core receives a Timeout error instead
+    DropResponse,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum InjectedError {
+    Conflict,
+    Timeout,
+    InternalError,
+}
+
+impl InjectedError {
+    pub fn api_error(self) -> APIError {
+        match self {
+            InjectedError::Conflict => APIError::Conflict,
+            InjectedError::Timeout => APIError::Timeout,
+            InjectedError::InternalError => APIError::InternalError,
+        }
+    }
+
+    // external_error is the error that an external request fails with
+    pub fn external_error(self) -> ExternalError {
+        match self {
+            InjectedError::Timeout => ExternalError::Timeout,
+            _ => ExternalError::TransportError,
+        }
+    }
+}
+
+// FaultPlanSource is where to load the fault plan from: a YAML (or JSON) file,
+// or the "plan" entry of a ConfigMap.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FaultPlanSource {
+    File(PathBuf),
+    ConfigMap { namespace: String, name: String },
+}
+
+// FaultInjector injects the faults of a fault plan.
+//
+// The counters of nth are kept in memory, so they restart from zero when the controller restarts.
+// To avoid crashing at the same point forever, a crash fault loaded from a ConfigMap is recorded
+// in the "fired" entry of the ConfigMap before the controller crashes, and is skipped afterwards.
+// Crash faults loaded from a file do not have this protection and should be used with probability or cr_key.
+pub struct FaultInjector {
+    plan: FaultPlan,
+    config_map: Option<(Api<ConfigMap>, String)>,
+    state: Mutex<FaultInjectorState>,
+}
+
+struct FaultInjectorState {
+    // selected[i] is the number of requests selected by the i-th fault so far
+    selected: Vec<u64>,
+    // fired is the set of the crash faults that have fired
+    fired: BTreeSet<usize>,
+    rng: StdRng,
+}
+
+// RequestInfo is what a fault selects the requests by.
+pub struct RequestInfo<'a> {
+    pub cr_key: &'a str,
+    pub verb: &'a str,
+    pub kind: Option<&'a str>,
+    pub reconcile_step: &'a str,
+}
+
+impl FaultInjector {
+    pub fn new(plan: FaultPlan) -> FaultInjector {
+        FaultInjector {
+            state: Mutex::new(FaultInjectorState {
+                selected: vec![0; plan.faults.len()],
+                fired: BTreeSet::new(),
+                rng: StdRng::seed_from_u64(plan.seed),
+            }),
+            plan: plan,
+            config_map: None,
+        }
+    }
+
+    pub async fn load(client: &Client, source: &FaultPlanSource) -> Result<FaultInjector> {
+        match source {
+            FaultPlanSource::File(path) => {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow!("failed to read fault plan {}: {}", path.display(), e))?;
+                let plan = serde_yaml::from_str::<FaultPlan>(&content)
+                    .map_err(|e| anyhow!("malformed fault plan {}: {}", path.display(), e))?;
+                Ok(FaultInjector::new(plan))
+            }
+            FaultPlanSource::ConfigMap { namespace, name } => {
+                let api = Api::<ConfigMap>::namespaced(client.clone(), namespace);
+                let data = api.get(name).await?.data.unwrap_or_default();
+                let plan = serde_yaml::from_str::<FaultPlan>(
+                    data.get("plan").map(String::as_str).unwrap_or_default(),
+                )
+                .map_err(|e| {
+                    anyhow!(
+                        "malformed fault plan in ConfigMap {}/{}: {}",
+                        namespace,
+                        name,
+                        e
+                    )
+                })?;
+                let mut injector = FaultInjector::new(plan);
+                injector.state.get_mut().unwrap().fired = parse_fired(data.get("fired"));
+                injector.config_map = Some((api, name.clone()));
+                Ok(injector)
+            }
+        }
+    }
+
+    // select returns the faults (with their indices in the plan) to inject into the request.
+    pub fn select(&self, req: &RequestInfo) -> Vec<(usize, FaultAction)> {
+        let matches = |selector: &Option<String>, v: Option<&str>| match selector {
+            Some(selector) => v == Some(selector.as_str()),
+            None => true,
+        };
+        let mut state = self.state.lock().unwrap();
+        let mut selected = Vec::new();
+        for (i, fault) in self.plan.faults.iter().enumerate() {
+            if !matches(&fault.verb, Some(req.verb))
+                || !matches(&fault.kind, req.kind)
+                || !matches(&fault.reconcile_step, Some(req.reconcile_step))
+                || !matches(&fault.cr_key, Some(req.cr_key))
+                || state.fired.contains(&i)
+            {
+                continue;
+            }
+            state.selected[i] += 1;
+            let inject = match (fault.nth, fault.probability) {
+                (Some(nth), _) => state.selected[i] == nth,
+                (None, Some(probability)) => state.rng.gen::<f64>() < probability,
+                (None, None) => true,
+            };
+            if inject {
+                selected.push((i, fault.action));
+            }
+        }
+        selected
+    }
+
+    // handle_request sends req to the backend with the faults selected for it injected.
+    pub async fn handle_request<B: APIServerBackend>(
+        &self,
+        backend: &B,
+        req: KubeAPIRequest,
+        cr_key: &str,
+        reconcile_step: &str,
+        log_header: &str,
+    ) -> KubeAPIResponse {
+        let kind = request_kind(&req);
+        let faults = self.select(&RequestInfo {
+            cr_key: cr_key,
+            verb: request_verb(&req),
+            kind: Some(&kind),
+            reconcile_step: reconcile_step,
+        });
+        self.crash(&faults, FaultAction::CrashBeforeSend, log_header)
+            .await;
+        let resp = match injected_error(&faults) {
+            Some(err) => {
+                info!("{} Inject {:?} to {}", log_header, err, request_verb(&req));
+                error_response(&req, err.api_error())
+            }
+            None => {
+                let duplicate = if faults
+                    .iter()
+                    .any(|(_, action)| *action == FaultAction::Duplicate)
+                {
+                    duplicate_request(&req)
+                } else {
+                    None
+                };
+                // The response to a dropped request is replaced by a Timeout error, like in DropRespStep
+                let dropped = if faults
+                    .iter()
+                    .any(|(_, action)| *action == FaultAction::DropResponse)
+                {
+                    info!(
+                        "{} Inject a dropped response to {}",
+                        log_header,
+                        request_verb(&req)
+                    );
+                    Some(error_response(&req, APIError::Timeout))
+                } else {
+                    None
+                };
+                let resp = backend.handle_request(req, log_header).await;
+                if let Some(duplicate) = duplicate {
+                    info!(
+                        "{} Inject a duplicate of {}",
+                        log_header,
+                        request_verb(&duplicate)
+                    );
+                    backend.handle_request(duplicate, log_header).await;
+                }
+                match dropped {
+                    Some(dropped) => dropped,
+                    None => resp,
+                }
+            }
+        };
+        self.delay(&faults, log_header).await;
+        self.crash(&faults, FaultAction::CrashAfterSend, log_header)
+            .await;
+        resp
+    }
+
+    // handle_external_request awaits call (which sends the external request) with the faults selected for it injected.
+    pub async fn handle_external_request<T, F>(
+        &self,
+        call: F,
+        cr_key: &str,
+        reconcile_step: &str,
+        log_header: &str,
+    ) -> Result<T, ExternalError>
+    where
+        F: Future<Output = Result<T, ExternalError>>,
+    {
+        let faults = self.select(&RequestInfo {
+            cr_key: cr_key,
+            verb: "External",
+            kind: None,
+            reconcile_step: reconcile_step,
+        });
+        self.crash(&faults, FaultAction::CrashBeforeSend, log_header)
+            .await;
+        let resp = match injected_error(&faults) {
+            Some(err) => {
+                info!("{} Inject {:?} to External", log_header, err);
+                Err(err.external_error())
+            }
+            None => call.await,
+        };
+        self.delay(&faults, log_header).await;
+        self.crash(&faults, FaultAction::CrashAfterSend, log_header)
+            .await;
+        resp
+    }
+
+    async fn delay(&self, faults: &[(usize, FaultAction)], log_header: &str) {
+        for (_, action) in faults {
+            if let FaultAction::DelayResponse { millis } = action {
+                info!("{} Inject a delay of {}ms", log_header, millis);
+                tokio::time::sleep(Duration::from_millis(*millis)).await;
+            }
+        }
+    }
+
+    // crash panics if any of the faults is timing, after recording the fault as fired in the ConfigMap.
+    async fn crash(&self, faults: &[(usize, FaultAction)], timing: FaultAction, log_header: &str) {
+        if let Some((i, _)) = faults.iter().find(|(_, action)| *action == timing) {
+            info!("{} Inject {:?} by fault {}", log_header, timing, i);
+            if let Some((api, name)) = &self.config_map {
+                if let Err(e) = record_fired(api, name, *i).await {
+                    error!("{} Fail to record fault {} as fired: {}", log_header, i, e);
+                }
+            }
+            panic!("fault {} injected: {:?}", i, timing);
+        }
+    }
+}
+
+fn injected_error(faults: &[(usize, FaultAction)]) -> Option<InjectedError> {
+    faults.iter().find_map(|(_, action)| match action {
+        FaultAction::ReturnError { error } => Some(*error),
+        _ => None,
+    })
+}
+
+fn parse_fired(fired: Option<&String>) -> BTreeSet<usize> {
+    match fired {
+        Some(fired) => fired
+            .split(',')
+            .filter_map(|i| i.trim().parse::<usize>().ok())
+            .collect(),
+        None => BTreeSet::new(),
+    }
+}
+
+async fn record_fired(api: &Api<ConfigMap>, name: &str, i: usize) -> Result<()> {
+    let mut config_map = api.get(name).await?;
+    let data = config_map.data.get_or_insert_with(Default::default);
+    let mut fired = parse_fired(data.get("fired"));
+    fired.insert(i);
+    data.insert(
+        "fired".to_string(),
+        fired
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+    api.replace(name, &PostParams::default(), &config_map)
+        .await?;
+    Ok(())
+}
+
+// duplicate_request returns a copy of req if req is a mutating request, or None otherwise.
+fn duplicate_request(req: &KubeAPIRequest) -> Option<KubeAPIRequest> {
+    let api_resource =
+        |api_resource: &ApiResource| ApiResource::from_kube(api_resource.as_kube_ref().clone());
+    let predicate = |predicate: &KubeGetThenPredicate| match predicate {
+        KubeGetThenPredicate::OwnerRef => KubeGetThenPredicate::OwnerRef,
+        KubeGetThenPredicate::LabelsMatch(labels) => {
+            KubeGetThenPredicate::LabelsMatch(labels.clone())
+        }
+        KubeGetThenPredicate::ResourceVersionMatch(rv) => {
+            KubeGetThenPredicate::ResourceVersionMatch(rv.clone())
+        }
+        KubeGetThenPredicate::SpecHashMatch(hash) => {
+            KubeGetThenPredicate::SpecHashMatch(hash.clone())
+        }
+    };
+    match req {
+        KubeAPIRequest::GetRequest(_) | KubeAPIRequest::ListRequest(_) => None,
+        KubeAPIRequest::CreateRequest(req) => {
+            Some(KubeAPIRequest::CreateRequest(KubeCreateRequest {
+                api_resource: api_resource(&req.api_resource),
+                namespace: req.namespace.clone(),
+                obj: req.obj.clone(),
+            }))
+        }
+        KubeAPIRequest::DeleteRequest(req) => {
+            Some(KubeAPIRequest::DeleteRequest(KubeDeleteRequest {
+                api_resource: api_resource(&req.api_resource),
+                name: req.name.clone(),
+                namespace: req.namespace.clone(),
+                preconditions: req.preconditions.clone(),
+                propagation_policy: req.propagation_policy,
+            }))
+        }
+        KubeAPIRequest::UpdateRequest(req) => {
+            Some(KubeAPIRequest::UpdateRequest(KubeUpdateRequest {
+                api_resource: api_resource(&req.api_resource),
+                name: req.name.clone(),
+                namespace: req.namespace.clone(),
+                obj: req.obj.clone(),
+            }))
+        }
+        KubeAPIRequest::UpdateStatusRequest(req) => Some(KubeAPIRequest::UpdateStatusRequest(
+            KubeUpdateStatusRequest {
+                api_resource: api_resource(&req.api_resource),
+                name: req.name.clone(),
+                namespace: req.namespace.clone(),
+                obj: req.obj.clone(),
+            },
+        )),
+        KubeAPIRequest::GetThenDeleteRequest(req) => Some(KubeAPIRequest::GetThenDeleteRequest(
+            KubeGetThenDeleteRequest {
+                api_resource: api_resource(&req.api_resource),
+                name: req.name.clone(),
+                namespace: req.namespace.clone(),
+                owner_ref: req.owner_ref.clone(),
+                predicate: predicate(&req.predicate),
+            },
+        )),
+        KubeAPIRequest::GetThenUpdateRequest(req) => Some(KubeAPIRequest::GetThenUpdateRequest(
+            KubeGetThenUpdateRequest {
+                api_resource: api_resource(&req.api_resource),
+                name: req.name.clone(),
+                namespace: req.namespace.clone(),
+                owner_ref: req.owner_ref.clone(),
+                predicate: predicate(&req.predicate),
+                obj: req.obj.clone(),
+            },
+        )),
+        KubeAPIRequest::GetThenUpdateStatusRequest(req) => Some(
+            KubeAPIRequest::GetThenUpdateStatusRequest(KubeGetThenUpdateStatusRequest {
+                api_resource: api_resource(&req.api_resource),
+                name: req.name.clone(),
+                namespace: req.namespace.clone(),
+                owner_ref: req.owner_ref.clone(),
+                predicate: predicate(&req.predicate),
+                obj: req.obj.clone(),
+            }),
+        ),
+        KubeAPIRequest::PatchRequest(req) => Some(KubeAPIRequest::PatchRequest(KubePatchRequest {
+            api_resource: api_resource(&req.api_resource),
+            name: req.name.clone(),
+            namespace: req.namespace.clone(),
+            obj: req.obj.clone(),
+            field_manager: req.field_manager.clone(),
+        })),
+    }
+}
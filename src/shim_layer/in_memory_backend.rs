@@ -0,0 +1,360 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds;
+use crate::executable_model::{api_server::ExecutableApiServerModel, api_server_state::ApiServerState, installed_types::ExecutableInstalledTypes};
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::ApiResource, dynamic::*, resource::*};
+use crate::rabbitmq_controller::trusted::exec_types::RabbitmqCluster;
+use crate::shim_layer::backend::APIServerBackend;
+use crate::shim_layer::controller_runtime::ApiScope;
+use crate::vdeployment_controller::trusted::exec_types::VDeployment;
+use crate::vreplicaset_controller::trusted::exec_types::VReplicaSet;
+use crate::vstatefulset_controller::trusted::exec_types::VStatefulSet;
+use core::fmt::Debug;
+use k8s_openapi::api::coordination::v1::Lease;
+use k8s_openapi::api::core::v1::Namespace;
+use kube::api::Resource;
+use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
+use std::sync::Mutex;
+use tracing::info;
+
+pub use crate::executable_model::common::{selects, FOREGROUND_DELETION_FINALIZER, ORPHAN_FINALIZER};
+
+// InMemoryAPIServerBackend is an APIServerBackend that keeps all the objects in process.
+// It handles each request in one atomic step with crate::executable_model::api_server::ExecutableApiServerModel,
+// whose handle_*_request are written to conform to their correspondences in
+// crate::kubernetes_cluster::spec::api_server::state_machine, with the custom resources of the controllers
+// installed like the cluster installs them (see new). So the reconcilers are driven by the same request handling
+// logic that they are verified against, including the state_validation and transition_validation of each kind.
+//
+// The backend itself only fills in the type meta of the objects in the requests (the model tells the kind of an
+// object from its type meta), and rejects with BadRequest the objects whose kinds are neither builtin nor installed
+// since the model only handles the installed kinds. Besides the trusted functions of the model
+// (marked with TRUSTED in crate::executable_model), the known divergences from the spec are:
+// * resource versions and uids are decimal strings, generated names are "<generate_name><counter>",
+//   and deletion timestamps are the current time,
+// * patches delete the fields set to null (JSON merge patch), which merge_patch in the spec leaves uninterpreted, and
+// * Namespace and Lease, which are not modeled, are installed without validation.
+// Like the spec, server-side apply is handled as a merge patch without field managers.
+//
+// It does not garbage collect dependents (so the foregroundDeletion and orphan finalizers stay until they are
+// removed by an update), and no other actor (e.g., kubelet) changes the objects. It is meant for driving
+// reconcilers in tests, not for checking conformance with a real API server.
+pub struct InMemoryAPIServerBackend {
+    state: Mutex<InMemoryAPIServerState>,
+}
+
+struct InMemoryAPIServerState {
+    api_server: ApiServerState,
+    installed_types: ExecutableInstalledTypes,
+}
+
+impl InMemoryAPIServerBackend {
+    pub fn new() -> InMemoryAPIServerBackend {
+        let mut installed_types = ExecutableInstalledTypes::new();
+        installed_types.install::<crds::VReplicaSet>(
+            "vreplicaset", true,
+            |vrs| VReplicaSet::from_kube(vrs.clone()).state_validation(),
+            |_vrs, _old_vrs| true,
+        );
+        installed_types.install::<crds::VDeployment>(
+            "vdeployment", true,
+            |vd| VDeployment::from_kube(vd.clone()).state_validation(),
+            |_vd, _old_vd| true,
+        );
+        installed_types.install::<crds::VStatefulSet>(
+            "vstatefulset", true,
+            |vsts| VStatefulSet::from_kube(vsts.clone()).state_validation(),
+            // Fields other than replicas, template and persistent_volume_claim_retention_policy are immutable
+            |vsts, old_vsts| old_vsts.spec == crds::VStatefulSetSpec {
+                replicas: old_vsts.spec.replicas,
+                template: old_vsts.spec.template.clone(),
+                persistent_volume_claim_retention_policy: old_vsts.spec.persistent_volume_claim_retention_policy.clone(),
+                ..vsts.spec.clone()
+            },
+        );
+        installed_types.install::<crds::RabbitmqCluster>(
+            "rabbitmq", true,
+            |rmq| RabbitmqCluster::from_kube(rmq.clone()).state_validation(),
+            |rmq, old_rmq| {
+                rmq.spec.replicas >= old_rmq.spec.replicas
+                    && rmq.spec.persistence.storage == old_rmq.spec.persistence.storage
+                    && rmq.spec.persistence.storage_class_name == old_rmq.spec.persistence.storage_class_name
+                    && rmq.spec.pod_management_policy == old_rmq.spec.pod_management_policy
+            },
+        );
+        installed_types.install_without_validation::<Namespace>("namespace", false);
+        installed_types.install_without_validation::<Lease>("lease", true);
+        InMemoryAPIServerBackend {
+            state: Mutex::new(InMemoryAPIServerState {
+                api_server: ApiServerState::new(),
+                installed_types: installed_types,
+            }),
+        }
+    }
+
+    // create inserts a typed object (e.g., the custom resource to reconcile) through a create request.
+    pub fn create<K>(&self, namespace: &str, obj: &K) -> Result<K, APIError>
+    where
+        K: Resource + Serialize + DeserializeOwned,
+        K::DynamicType: Default,
+    {
+        let obj = serde_json::from_value::<kube::api::DynamicObject>(
+            serde_json::to_value(obj).map_err(|_e| APIError::BadRequest)?,
+        )
+        .map_err(|_e| APIError::BadRequest)?;
+        let req = KubeCreateRequest {
+            api_resource: api_resource_of::<K>(),
+            namespace: namespace.to_string(),
+            obj: DynamicObject::from_kube(obj),
+        };
+        let created = self.state.lock().unwrap().handle_create(req).res?;
+        parse_object::<K>(created.into_kube())
+    }
+
+    // get returns the typed object with the name in the namespace.
+    pub fn get<K>(&self, namespace: &str, name: &str) -> Result<K, APIError>
+    where
+        K: Resource + DeserializeOwned,
+        K::DynamicType: Default,
+    {
+        let req = KubeGetRequest {
+            api_resource: api_resource_of::<K>(),
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+            read_mode: KubeReadMode::Quorum,
+        };
+        let obj = self.state.lock().unwrap().handle_get(&req).res?;
+        parse_object::<K>(obj.into_kube())
+    }
+
+    // list returns all the typed objects of kind K in the namespace.
+    pub fn list<K>(&self, namespace: &str) -> Vec<K>
+    where
+        K: Resource + DeserializeOwned,
+        K::DynamicType: Default,
+    {
+        let req = KubeListRequest {
+            api_resource: api_resource_of::<K>(),
+            namespace: namespace.to_string(),
+            label_selector: None,
+            field_selector: None,
+            read_mode: KubeReadMode::Quorum,
+            limit: None,
+            continue_token: None,
+        };
+        self.state
+            .lock()
+            .unwrap()
+            .handle_list(&req)
+            .res
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|obj| parse_object::<K>(obj.into_kube()).ok())
+            .collect()
+    }
+
+    // compact_list_snapshots drops the snapshots of all the paginated lists, like a compaction of etcd,
+    // so the lists continuing from them fail with Expired.
+    pub fn compact_list_snapshots(&self) {
+        self.state.lock().unwrap().api_server.list_snapshots.clear();
+    }
+
+    // delete deletes the object of kind K with the name in the namespace (without preconditions).
+    pub fn delete<K>(&self, namespace: &str, name: &str) -> Result<(), APIError>
+    where
+        K: Resource,
+        K::DynamicType: Default,
+    {
+        let req = KubeDeleteRequest {
+            api_resource: api_resource_of::<K>(),
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+            preconditions: None,
+            propagation_policy: None,
+        };
+        self.state.lock().unwrap().handle_delete(&req).res
+    }
+
+    fn handle_request_now(&self, req: KubeAPIRequest, log_header: &str) -> KubeAPIResponse {
+        let mut s = self.state.lock().unwrap();
+        match req {
+            KubeAPIRequest::GetRequest(req) => {
+                let resp = s.handle_get(&req);
+                info!("{} Get {} returns {}", log_header, req.key(), res_summary(&resp.res));
+                KubeAPIResponse::GetResponse(resp)
+            }
+            KubeAPIRequest::ListRequest(req) => {
+                let resp = s.handle_list(&req);
+                info!("{} List {} returns {}", log_header, req.key(), res_summary(&resp.res));
+                KubeAPIResponse::ListResponse(resp)
+            }
+            KubeAPIRequest::CreateRequest(req) => {
+                let key = req.key();
+                let resp = s.handle_create(req);
+                info!("{} Create {} returns {}", log_header, key, res_summary(&resp.res));
+                KubeAPIResponse::CreateResponse(resp)
+            }
+            KubeAPIRequest::DeleteRequest(req) => {
+                let resp = s.handle_delete(&req);
+                info!("{} Delete {} returns {}", log_header, req.key(), res_summary(&resp.res));
+                KubeAPIResponse::DeleteResponse(resp)
+            }
+            KubeAPIRequest::UpdateRequest(req) => {
+                let key = format!("{}/{}/{}", req.api_resource.as_kube_ref().kind, req.namespace, req.name);
+                let resp = s.handle_update(req);
+                info!("{} Update {} returns {}", log_header, key, res_summary(&resp.res));
+                KubeAPIResponse::UpdateResponse(resp)
+            }
+            KubeAPIRequest::UpdateStatusRequest(req) => {
+                let key = format!("{}/{}/{}", req.api_resource.as_kube_ref().kind, req.namespace, req.name);
+                let resp = s.handle_update_status(req);
+                info!("{} UpdateStatus {} returns {}", log_header, key, res_summary(&resp.res));
+                KubeAPIResponse::UpdateStatusResponse(resp)
+            }
+            KubeAPIRequest::GetThenDeleteRequest(req) => {
+                let resp = s.handle_get_then_delete(&req);
+                info!("{} GetThenDelete {} returns {}", log_header, req.key(), res_summary(&resp.res));
+                KubeAPIResponse::GetThenDeleteResponse(resp)
+            }
+            KubeAPIRequest::GetThenUpdateRequest(req) => {
+                let key = req.key();
+                let resp = s.handle_get_then_update(req);
+                info!("{} GetThenUpdate {} returns {}", log_header, key, res_summary(&resp.res));
+                KubeAPIResponse::GetThenUpdateResponse(resp)
+            }
+            KubeAPIRequest::GetThenUpdateStatusRequest(req) => {
+                let key = req.key();
+                let resp = s.handle_get_then_update_status(req);
+                info!("{} GetThenUpdateStatus {} returns {}", log_header, key, res_summary(&resp.res));
+                KubeAPIResponse::GetThenUpdateStatusResponse(resp)
+            }
+            KubeAPIRequest::PatchRequest(req) => {
+                let key = req.key();
+                let resp = s.handle_patch(req);
+                info!("{} Patch {} returns {}", log_header, key, res_summary(&resp.res));
+                KubeAPIResponse::PatchResponse(resp)
+            }
+        }
+    }
+}
+
+impl APIServerBackend for InMemoryAPIServerBackend {
+    fn get_cr<K>(&self, namespace: &str, name: &str) -> impl Future<Output = Result<K, APIError>> + Send
+    where
+        K: Clone + Resource + DeserializeOwned + Debug + Send,
+        K::Scope: ApiScope,
+        K::DynamicType: Default,
+    {
+        std::future::ready(self.get::<K>(namespace, name))
+    }
+
+    fn handle_request(&self, req: KubeAPIRequest, log_header: &str) -> impl Future<Output = KubeAPIResponse> + Send {
+        std::future::ready(self.handle_request_now(req, log_header))
+    }
+}
+
+// Each handle_* below fills in the type meta of the object in the request, if any, checks that the kind of
+// the object can be handled by the model (see installed_kind), and hands the request to the model.
+impl InMemoryAPIServerState {
+    fn handle_get(&self, req: &KubeGetRequest) -> KubeGetResponse {
+        ExecutableApiServerModel::handle_get_request(&self.installed_types, req, &self.api_server)
+    }
+
+    fn handle_list(&mut self, req: &KubeListRequest) -> KubeListResponse {
+        ExecutableApiServerModel::handle_list_request(&self.installed_types, req, &mut self.api_server)
+    }
+
+    fn handle_create(&mut self, mut req: KubeCreateRequest) -> KubeCreateResponse {
+        req.obj = with_type_meta(req.obj, &req.api_resource);
+        if !self.installed(&req.obj) {
+            return KubeCreateResponse { res: Err(APIError::BadRequest) };
+        }
+        ExecutableApiServerModel::handle_create_request(&self.installed_types, &req, &mut self.api_server)
+    }
+
+    fn handle_delete(&mut self, req: &KubeDeleteRequest) -> KubeDeleteResponse {
+        ExecutableApiServerModel::handle_delete_request(&self.installed_types, req, &mut self.api_server)
+    }
+
+    fn handle_update(&mut self, mut req: KubeUpdateRequest) -> KubeUpdateResponse {
+        req.obj = with_type_meta(req.obj, &req.api_resource);
+        if !self.installed(&req.obj) {
+            return KubeUpdateResponse { res: Err(APIError::BadRequest) };
+        }
+        ExecutableApiServerModel::handle_update_request(&self.installed_types, &req, &mut self.api_server)
+    }
+
+    fn handle_update_status(&mut self, mut req: KubeUpdateStatusRequest) -> KubeUpdateStatusResponse {
+        req.obj = with_type_meta(req.obj, &req.api_resource);
+        if !self.installed(&req.obj) {
+            return KubeUpdateStatusResponse { res: Err(APIError::BadRequest) };
+        }
+        ExecutableApiServerModel::handle_update_status_request(&self.installed_types, &req, &mut self.api_server)
+    }
+
+    fn handle_get_then_delete(&mut self, req: &KubeGetThenDeleteRequest) -> KubeGetThenDeleteResponse {
+        ExecutableApiServerModel::handle_get_then_delete_request(&self.installed_types, req, &mut self.api_server)
+    }
+
+    fn handle_get_then_update(&mut self, mut req: KubeGetThenUpdateRequest) -> KubeGetThenUpdateResponse {
+        req.obj = with_type_meta(req.obj, &req.api_resource);
+        if !self.installed(&req.obj) {
+            return KubeGetThenUpdateResponse { res: Err(APIError::BadRequest) };
+        }
+        ExecutableApiServerModel::handle_get_then_update_request(&self.installed_types, &req, &mut self.api_server)
+    }
+
+    fn handle_get_then_update_status(&mut self, mut req: KubeGetThenUpdateStatusRequest) -> KubeGetThenUpdateStatusResponse {
+        req.obj = with_type_meta(req.obj, &req.api_resource);
+        if !self.installed(&req.obj) {
+            return KubeGetThenUpdateStatusResponse { res: Err(APIError::BadRequest) };
+        }
+        ExecutableApiServerModel::handle_get_then_update_status_request(&self.installed_types, &req, &mut self.api_server)
+    }
+
+    fn handle_patch(&mut self, mut req: KubePatchRequest) -> KubePatchResponse {
+        req.obj = with_type_meta(req.obj, &req.api_resource);
+        if !self.installed(&req.obj) {
+            return KubePatchResponse { res: Err(APIError::BadRequest) };
+        }
+        ExecutableApiServerModel::handle_patch_request(&self.installed_types, &req, &mut self.api_server)
+    }
+
+    fn installed(&self, obj: &DynamicObject) -> bool {
+        self.installed_types.is_installed(&self.installed_types.kind(obj))
+    }
+}
+
+fn api_resource_of<K>() -> ApiResource
+where
+    K: Resource,
+    K::DynamicType: Default,
+{
+    ApiResource::from_kube(kube::api::ApiResource::erase::<K>(&K::DynamicType::default()))
+}
+
+// with_type_meta fills in the type meta of obj from the api resource of the request
+// since the objects sent by the controllers (e.g., patches) do not always carry one.
+fn with_type_meta(obj: DynamicObject, api_resource: &ApiResource) -> DynamicObject {
+    let mut obj = obj.into_kube();
+    if obj.types.is_none() {
+        obj.types = Some(kube::api::TypeMeta {
+            api_version: api_resource.as_kube_ref().api_version.clone(),
+            kind: api_resource.as_kube_ref().kind.clone(),
+        });
+    }
+    DynamicObject::from_kube(obj)
+}
+
+fn parse_object<K: Resource + DeserializeOwned>(obj: kube::api::DynamicObject) -> Result<K, APIError> {
+    obj.try_parse::<K>().map_err(|_e| APIError::BadRequest)
+}
+
+fn res_summary<T>(res: &Result<T, APIError>) -> String {
+    match res {
+        Ok(_) => "Ok".to_string(),
+        Err(err) => format!("{:?}", err),
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::*, dynamic::*, resource::*};
+use crate::shim_layer::backend::APIServerBackend;
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::coordination::v1::{Lease, LeaseSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta};
+use std::env;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+// LeaderElectionConfig configures the lease-based leader election used by run_controller.
+// The candidates of the same controller share one coordination.k8s.io/v1 Lease object,
+// and only the candidate holding the lease runs the controller.
+#[derive(Clone, Debug)]
+pub struct LeaderElectionConfig {
+    pub lease_name: String,
+    pub lease_namespace: String,
+    // identity is written to the lease's holderIdentity and must be unique among the candidates
+    pub identity: String,
+    // lease_duration is how long the other candidates wait before taking over a lease that is not renewed
+    pub lease_duration: Duration,
+    // renew_deadline is how long the leader keeps retrying to renew the lease before stepping down
+    pub renew_deadline: Duration,
+    // retry_period is the interval between two attempts to acquire or renew the lease
+    pub retry_period: Duration,
+}
+
+impl LeaderElectionConfig {
+    pub fn new(lease_name: &str, lease_namespace: &str, identity: &str) -> LeaderElectionConfig {
+        LeaderElectionConfig {
+            lease_name: lease_name.to_string(),
+            lease_namespace: lease_namespace.to_string(),
+            identity: identity.to_string(),
+            lease_duration: Duration::from_secs(15),
+            renew_deadline: Duration::from_secs(10),
+            retry_period: Duration::from_secs(2),
+        }
+    }
+
+    // from_env builds the config for a controller running in a pod:
+    // the lease lives in the pod's namespace (POD_NAMESPACE) and the identity is the pod's name (POD_NAME or HOSTNAME).
+    // If neither is set, a random identity is used so that two local replicas do not share one.
+    pub fn from_env(lease_name: &str) -> LeaderElectionConfig {
+        let lease_namespace = env::var("POD_NAMESPACE").unwrap_or("default".to_string());
+        let identity = env::var("POD_NAME")
+            .or_else(|_| env::var("HOSTNAME"))
+            .unwrap_or_else(|_| format!("{}-{}", lease_name, rand::random::<u32>()));
+        LeaderElectionConfig::new(lease_name, &lease_namespace, &identity)
+    }
+}
+
+// LeaderElector acquires and renews the lease through an APIServerBackend,
+// so it works with both the real API server and the in-memory one.
+pub struct LeaderElector<'a, B: APIServerBackend> {
+    backend: &'a B,
+    config: LeaderElectionConfig,
+    observed: Mutex<Option<ObservedLease>>,
+}
+
+// ObservedLease is the last lease record seen by this candidate and the local time when it was first seen.
+// Like client-go, a lease expires lease_duration after the local observation of its last renewal
+// rather than after its renewTime, which is written with the holder's clock and can be skewed.
+struct ObservedLease {
+    spec: LeaseSpec,
+    resource_version: Option<String>,
+    observed_at: Instant,
+}
+
+impl<'a, B: APIServerBackend> LeaderElector<'a, B> {
+    pub fn new(backend: &'a B, config: LeaderElectionConfig) -> LeaderElector<'a, B> {
+        LeaderElector {
+            backend,
+            config,
+            observed: Mutex::new(None),
+        }
+    }
+
+    // try_acquire_or_renew makes one attempt to acquire (or renew, if this candidate is the leader) the lease.
+    // It returns Ok(true) if this candidate holds the lease after the attempt,
+    // Ok(false) if the lease is held by another candidate,
+    // and Err if the attempt fails (e.g., the lease is concurrently updated by another candidate).
+    pub async fn try_acquire_or_renew(&self) -> Result<bool, APIError> {
+        let now = Utc::now();
+        let lease = match self.get_lease().await {
+            Err(APIError::ObjectNotFound) => {
+                let lease = Lease {
+                    metadata: ObjectMeta {
+                        name: Some(self.config.lease_name.clone()),
+                        namespace: Some(self.config.lease_namespace.clone()),
+                        ..ObjectMeta::default()
+                    },
+                    spec: Some(self.acquired_lease_spec(&LeaseSpec::default(), now)),
+                };
+                return self.create_lease(lease).await.map(|_| true);
+            }
+            Err(err) => return Err(err),
+            Ok(lease) => lease,
+        };
+        let spec = lease.spec.clone().unwrap_or_default();
+        let spec_prime = if self.holds(&spec) {
+            LeaseSpec {
+                lease_duration_seconds: Some(self.config.lease_duration.as_secs() as i32),
+                renew_time: Some(MicroTime(now)),
+                ..spec
+            }
+        } else if self.observe_and_check_expired(&spec, &lease.metadata.resource_version) {
+            self.acquired_lease_spec(&spec, now)
+        } else {
+            return Ok(false);
+        };
+        self.update_lease(Lease {
+            spec: Some(spec_prime),
+            ..lease
+        })
+        .await
+        .map(|_| true)
+    }
+
+    // release gives up the lease if this candidate holds it, so that another candidate can take over
+    // without waiting for the lease to expire.
+    pub async fn release(&self) -> Result<(), APIError> {
+        let lease = self.get_lease().await?;
+        let spec = lease.spec.clone().unwrap_or_default();
+        if !self.holds(&spec) {
+            return Ok(());
+        }
+        self.update_lease(Lease {
+            spec: Some(LeaseSpec {
+                holder_identity: None,
+                acquire_time: None,
+                renew_time: None,
+                ..spec
+            }),
+            ..lease
+        })
+        .await
+        .map(|_| ())
+    }
+
+    // run blocks until this candidate becomes the leader and then drives fut while renewing the lease.
+    // It returns Some(output) if fut completes, after releasing the lease,
+    // or None if the lease is lost, in which case fut is dropped (the controller steps down).
+    pub async fn run<F: Future>(&self, fut: F) -> Option<F::Output> {
+        let lease_key = format!("{}/{}", self.config.lease_namespace, self.config.lease_name);
+        loop {
+            match self.try_acquire_or_renew().await {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(err) => warn!("{} fails to acquire lease {}: {:?}", self.config.identity, lease_key, err),
+            }
+            tokio::time::sleep(self.config.retry_period).await;
+        }
+        info!("{} acquired lease {}", self.config.identity, lease_key);
+        tokio::pin!(fut);
+        let mut last_renew = Instant::now();
+        loop {
+            tokio::select! {
+                output = &mut fut => {
+                    if let Err(err) = self.release().await {
+                        warn!("{} fails to release lease {}: {:?}", self.config.identity, lease_key, err);
+                    }
+                    return Some(output);
+                }
+                _ = tokio::time::sleep(self.config.retry_period) => {
+                    // The attempt is bounded by what is left of renew_deadline,
+                    // so a hung request cannot keep the controller running after the lease expires
+                    let remaining = self.config.renew_deadline.saturating_sub(last_renew.elapsed());
+                    match tokio::time::timeout(remaining, self.try_acquire_or_renew()).await {
+                        Ok(Ok(true)) => last_renew = Instant::now(),
+                        Ok(Ok(false)) => {
+                            warn!("{} lost lease {} to another candidate", self.config.identity, lease_key);
+                            return None;
+                        }
+                        Ok(Err(err)) => {
+                            warn!("{} fails to renew lease {}: {:?}", self.config.identity, lease_key, err);
+                            if last_renew.elapsed() >= self.config.renew_deadline {
+                                warn!("{} fails to renew lease {} before the deadline", self.config.identity, lease_key);
+                                return None;
+                            }
+                        }
+                        Err(_) => {
+                            warn!("{} fails to renew lease {} before the deadline", self.config.identity, lease_key);
+                            return None;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // observe_and_check_expired records the lease if it differs from the last observed one
+    // and returns whether it has not been renewed for lease_duration since this candidate observed it.
+    fn observe_and_check_expired(&self, spec: &LeaseSpec, resource_version: &Option<String>) -> bool {
+        let mut observed = self.observed.lock().unwrap();
+        let changed = match observed.as_ref() {
+            Some(o) => o.spec != *spec || o.resource_version != *resource_version,
+            None => true,
+        };
+        if changed {
+            *observed = Some(ObservedLease {
+                spec: spec.clone(),
+                resource_version: resource_version.clone(),
+                observed_at: Instant::now(),
+            });
+        }
+        lease_expired(spec, observed.as_ref().unwrap().observed_at)
+    }
+
+    fn holds(&self, spec: &LeaseSpec) -> bool {
+        spec.holder_identity.as_ref() == Some(&self.config.identity)
+    }
+
+    fn acquired_lease_spec(&self, spec: &LeaseSpec, now: DateTime<Utc>) -> LeaseSpec {
+        LeaseSpec {
+            holder_identity: Some(self.config.identity.clone()),
+            lease_duration_seconds: Some(self.config.lease_duration.as_secs() as i32),
+            acquire_time: Some(MicroTime(now)),
+            renew_time: Some(MicroTime(now)),
+            lease_transitions: Some(spec.lease_transitions.unwrap_or(0) + if spec.holder_identity.is_some() { 1 } else { 0 }),
+            ..spec.clone()
+        }
+    }
+
+    async fn get_lease(&self) -> Result<Lease, APIError> {
+        let req = KubeAPIRequest::GetRequest(KubeGetRequest {
+            api_resource: lease_api_resource(),
+            name: self.config.lease_name.clone(),
+            namespace: self.config.lease_namespace.clone(),
+            read_mode: KubeReadMode::Quorum,
+        });
+        match self.backend.handle_request(req, &self.log_header()).await {
+            KubeAPIResponse::GetResponse(resp) => resp.res.and_then(dynamic_object_to_lease),
+            _ => Err(APIError::Other),
+        }
+    }
+
+    async fn create_lease(&self, lease: Lease) -> Result<Lease, APIError> {
+        let req = KubeAPIRequest::CreateRequest(KubeCreateRequest {
+            api_resource: lease_api_resource(),
+            namespace: self.config.lease_namespace.clone(),
+            obj: lease_to_dynamic_object(&lease),
+        });
+        match self.backend.handle_request(req, &self.log_header()).await {
+            KubeAPIResponse::CreateResponse(resp) => resp.res.and_then(dynamic_object_to_lease),
+            _ => Err(APIError::Other),
+        }
+    }
+
+    // update_lease relies on the resource version carried by the lease,
+    // so that two candidates cannot both win the same expired lease.
+    async fn update_lease(&self, lease: Lease) -> Result<Lease, APIError> {
+        let req = KubeAPIRequest::UpdateRequest(KubeUpdateRequest {
+            api_resource: lease_api_resource(),
+            name: self.config.lease_name.clone(),
+            namespace: self.config.lease_namespace.clone(),
+            obj: lease_to_dynamic_object(&lease),
+        });
+        match self.backend.handle_request(req, &self.log_header()).await {
+            KubeAPIResponse::UpdateResponse(resp) => resp.res.and_then(dynamic_object_to_lease),
+            _ => Err(APIError::Other),
+        }
+    }
+
+    fn log_header(&self) -> String {
+        format!("leader-election/{}:", self.config.identity)
+    }
+}
+
+// lease_expired only uses the local clock: observed_at is when this candidate observed the last renewal.
+fn lease_expired(spec: &LeaseSpec, observed_at: Instant) -> bool {
+    match (&spec.holder_identity, &spec.renew_time, spec.lease_duration_seconds) {
+        (Some(_), Some(_), Some(duration)) => observed_at.elapsed() > Duration::from_secs(duration.max(0) as u64),
+        // A lease without a holder (e.g., released) or without renew time is free to take
+        _ => true,
+    }
+}
+
+fn lease_api_resource() -> ApiResource {
+    ApiResource::from_kube(kube::api::ApiResource::erase::<Lease>(&()))
+}
+
+// Here we assume serde_json always succeeds in converting a Lease to a DynamicObject
+fn lease_to_dynamic_object(lease: &Lease) -> DynamicObject {
+    DynamicObject::from_kube(serde_json::from_value(serde_json::to_value(lease).unwrap()).unwrap())
+}
+
+fn dynamic_object_to_lease(obj: DynamicObject) -> Result<Lease, APIError> {
+    obj.into_kube().try_parse::<Lease>().map_err(|_e| APIError::Other)
+}
@@ -0,0 +1,261 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds::Error;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::api_method::*;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tracing::info;
+use warp::Filter;
+
+// The shim layer records the metrics below into a process-wide registry.
+// Recording is cheap and always on; the metrics are only exported when the
+// controller binary is started with an address for the /metrics endpoint (see serve_metrics).
+//
+// The metrics are exported in the Prometheus text exposition format.
+
+struct MetricDesc {
+    name: &'static str,
+    help: &'static str,
+    buckets: Option<&'static [f64]>,
+}
+
+const RECONCILES: MetricDesc = MetricDesc {
+    name: "anvil_reconciles_total",
+    help: "Number of reconciles, labelled by the custom resource kind.",
+    buckets: None,
+};
+
+const RECONCILE_FAILURES: MetricDesc = MetricDesc {
+    name: "anvil_reconcile_failures_total",
+    help: "Number of failed reconciles, labelled by the custom resource kind and the error (ReconcileCoreError or ShimLayerError).",
+    buckets: None,
+};
+
+const RECONCILE_CORE_ITERATIONS: MetricDesc = MetricDesc {
+    name: "anvil_reconcile_core_iterations",
+    help: "Number of reconcile_core invocations in one reconcile, labelled by the custom resource kind.",
+    buckets: Some(&[1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0]),
+};
+
+const API_REQUEST_DURATION: MetricDesc = MetricDesc {
+    name: "anvil_api_request_duration_seconds",
+    help: "Latency of the API requests issued by reconcile_core, labelled by the custom resource kind and the request verb.",
+    buckets: Some(&[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]),
+};
+
+const API_REQUEST_ERRORS: MetricDesc = MetricDesc {
+    name: "anvil_api_request_errors_total",
+    help: "Number of failed API requests issued by reconcile_core, labelled by the custom resource kind, the request verb and the APIError variant.",
+    buckets: None,
+};
+
+const API_CONFLICT_RETRIES: MetricDesc = MetricDesc {
+    name: "anvil_api_conflict_retries_total",
+    help: "Number of retries upon Conflict in the transactional Get-then-* requests, labelled by the custom resource kind, the kind of the object being written and the request verb.",
+    buckets: None,
+};
+
+const METRICS: [&MetricDesc; 6] = [
+    &RECONCILES,
+    &RECONCILE_FAILURES,
+    &RECONCILE_CORE_ITERATIONS,
+    &API_REQUEST_DURATION,
+    &API_REQUEST_ERRORS,
+    &API_CONFLICT_RETRIES,
+];
+
+type Labels = Vec<(&'static str, String)>;
+
+struct Histogram {
+    // bucket_counts[i] is the number of observations no larger than the i-th bucket
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+#[derive(Default)]
+struct Registry {
+    counters: BTreeMap<(&'static str, Labels), u64>,
+    histograms: BTreeMap<(&'static str, Labels), Histogram>,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(Registry::default()))
+}
+
+fn inc_counter(desc: &MetricDesc, labels: Labels) {
+    *registry()
+        .lock()
+        .unwrap()
+        .counters
+        .entry((desc.name, labels))
+        .or_insert(0) += 1;
+}
+
+fn observe_histogram(desc: &MetricDesc, labels: Labels, value: f64) {
+    let buckets = desc.buckets.unwrap();
+    let mut registry = registry().lock().unwrap();
+    let histogram = registry
+        .histograms
+        .entry((desc.name, labels))
+        .or_insert_with(|| Histogram {
+            bucket_counts: vec![0; buckets.len()],
+            sum: 0.0,
+            count: 0,
+        });
+    for (i, bound) in buckets.iter().enumerate() {
+        if value <= *bound {
+            histogram.bucket_counts[i] += 1;
+        }
+    }
+    histogram.sum += value;
+    histogram.count += 1;
+}
+
+// observe_reconcile records one reconcile of a custom resource of kind cr_kind,
+// which invoked reconcile_core for iterations times and ended with res.
+pub fn observe_reconcile<T>(cr_kind: &str, iterations: usize, res: &Result<T, Error>) {
+    inc_counter(&RECONCILES, vec![("kind", cr_kind.to_string())]);
+    observe_histogram(&RECONCILE_CORE_ITERATIONS, vec![("kind", cr_kind.to_string())], iterations as f64);
+    if let Err(err) = res {
+        let error = match err {
+            Error::ReconcileCoreError => "ReconcileCoreError",
+            Error::ShimLayerError(_) => "ShimLayerError",
+        };
+        inc_counter(
+            &RECONCILE_FAILURES,
+            vec![("kind", cr_kind.to_string()), ("error", error.to_string())],
+        );
+    }
+}
+
+// observe_api_request records one API request with the verb issued when reconciling a custom resource of kind cr_kind.
+pub fn observe_api_request(cr_kind: &str, verb: &str, error: Option<&APIError>, elapsed: Duration) {
+    observe_histogram(
+        &API_REQUEST_DURATION,
+        vec![("kind", cr_kind.to_string()), ("verb", verb.to_string())],
+        elapsed.as_secs_f64(),
+    );
+    if let Some(err) = error {
+        inc_counter(
+            &API_REQUEST_ERRORS,
+            vec![
+                ("kind", cr_kind.to_string()),
+                ("verb", verb.to_string()),
+                ("error", format!("{:?}", err)),
+            ],
+        );
+    }
+}
+
+// inc_conflict_retry records one retry upon Conflict when writing an object of kind obj_kind with the verb
+// while reconciling a custom resource of kind cr_kind.
+pub fn inc_conflict_retry(cr_kind: &str, obj_kind: &str, verb: &str) {
+    inc_counter(
+        &API_CONFLICT_RETRIES,
+        vec![
+            ("kind", cr_kind.to_string()),
+            ("object_kind", obj_kind.to_string()),
+            ("verb", verb.to_string()),
+        ],
+    );
+}
+
+pub fn request_verb(req: &KubeAPIRequest) -> &'static str {
+    match req {
+        KubeAPIRequest::GetRequest(_) => "Get",
+        KubeAPIRequest::ListRequest(_) => "List",
+        KubeAPIRequest::CreateRequest(_) => "Create",
+        KubeAPIRequest::DeleteRequest(_) => "Delete",
+        KubeAPIRequest::UpdateRequest(_) => "Update",
+        KubeAPIRequest::UpdateStatusRequest(_) => "UpdateStatus",
+        KubeAPIRequest::GetThenDeleteRequest(_) => "GetThenDelete",
+        KubeAPIRequest::GetThenUpdateRequest(_) => "GetThenUpdate",
+        KubeAPIRequest::GetThenUpdateStatusRequest(_) => "GetThenUpdateStatus",
+        KubeAPIRequest::PatchRequest(_) => "Patch",
+    }
+}
+
+pub fn response_error(resp: &KubeAPIResponse) -> Option<&APIError> {
+    match resp {
+        KubeAPIResponse::GetResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::ListResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::CreateResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::DeleteResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::UpdateResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::UpdateStatusResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::GetThenDeleteResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::GetThenUpdateResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::GetThenUpdateStatusResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::PatchResponse(resp) => resp.res.as_ref().err(),
+    }
+}
+
+fn format_labels(labels: &Labels, extra: Option<(&str, String)>) -> String {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, escape_label_value(v)))
+        .collect();
+    if let Some((k, v)) = extra {
+        pairs.push(format!("{}=\"{}\"", k, v));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn escape_label_value(v: &str) -> String {
+    v.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+// render returns all the recorded metrics in the Prometheus text exposition format.
+pub fn render() -> String {
+    let registry = registry().lock().unwrap();
+    let mut out = String::new();
+    for desc in METRICS.iter() {
+        let _ = writeln!(out, "# HELP {} {}", desc.name, desc.help);
+        match desc.buckets {
+            None => {
+                let _ = writeln!(out, "# TYPE {} counter", desc.name);
+                for ((name, labels), value) in registry.counters.iter() {
+                    if *name == desc.name {
+                        let _ = writeln!(out, "{}{} {}", name, format_labels(labels, None), value);
+                    }
+                }
+            }
+            Some(buckets) => {
+                let _ = writeln!(out, "# TYPE {} histogram", desc.name);
+                for ((name, labels), histogram) in registry.histograms.iter() {
+                    if *name != desc.name {
+                        continue;
+                    }
+                    for (bound, count) in buckets.iter().zip(histogram.bucket_counts.iter()) {
+                        let le = Some(("le", bound.to_string()));
+                        let _ = writeln!(out, "{}_bucket{} {}", name, format_labels(labels, le), count);
+                    }
+                    let le = Some(("le", "+Inf".to_string()));
+                    let _ = writeln!(out, "{}_bucket{} {}", name, format_labels(labels, le), histogram.count);
+                    let _ = writeln!(out, "{}_sum{} {}", name, format_labels(labels, None), histogram.sum);
+                    let _ = writeln!(out, "{}_count{} {}", name, format_labels(labels, None), histogram.count);
+                }
+            }
+        }
+    }
+    out
+}
+
+// serve_metrics serves the recorded metrics at http://<addr>/metrics until the process exits.
+pub async fn serve_metrics(addr: SocketAddr) {
+    let route = warp::path("metrics").and(warp::get()).map(|| {
+        warp::reply::with_header(render(), "content-type", "text/plain; version=0.0.4")
+    });
+    info!("serving metrics at http://{}/metrics", addr);
+    warp::serve(route).run(addr).await;
+}
@@ -1,4 +1,14 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
+pub mod backend;
+pub mod controller_config;
+pub mod controller_manager;
 pub mod controller_runtime;
+pub mod events;
 pub mod fault_injection;
+pub mod in_memory_backend;
+pub mod leader_election;
+pub mod metrics;
+pub mod plan;
+pub mod read_cache;
+pub mod trace;
@@ -0,0 +1,351 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::external_shim_layer::*;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::*, dynamic::*, owner_reference::*, resource::*};
+use crate::kubernetes_api_objects::spec::resource::*;
+use crate::reconciler::exec::reconciler::*;
+use crate::shim_layer::backend::*;
+use crate::shim_layer::controller_config::*;
+use crate::shim_layer::controller_runtime::*;
+use crate::shim_layer::metrics::request_verb;
+use crate::shim_layer::trace::*;
+use anyhow::Result;
+use core::fmt::Debug;
+use kube::{
+    api::Resource,
+    Client, CustomResourceExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+use vstd::prelude::*;
+
+// PlannedChange is one mutating request issued by reconcile_core during a plan (see plan),
+// with the error that the request fails with (if any) and the diff between the object before and after the request.
+#[derive(Debug)]
+pub struct PlannedChange {
+    pub verb: &'static str,
+    pub key: String,
+    pub error: Option<String>,
+    pub diff: Vec<String>,
+}
+
+// PlanBackend answers the Get and List requests with the live state from the inner backend,
+// and answers each mutating request without changing the cluster: it sends the request to the dry_run backend
+// (e.g., a KubeAPIServerBackend with dry_run set), or if dry_run is None, simulates the response
+// from the current object in the inner backend.
+// Every mutating request is recorded as a PlannedChange.
+pub struct PlanBackend<'a, B: APIServerBackend> {
+    pub inner: &'a B,
+    pub dry_run: Option<&'a B>,
+    changes: Mutex<Vec<PlannedChange>>,
+}
+
+impl<'a, B: APIServerBackend> PlanBackend<'a, B> {
+    pub fn new(inner: &'a B, dry_run: Option<&'a B>) -> PlanBackend<'a, B> {
+        PlanBackend {
+            inner: inner,
+            dry_run: dry_run,
+            changes: Mutex::new(Vec::new()),
+        }
+    }
+
+    // into_changes returns the recorded changes in the order of the requests.
+    pub fn into_changes(self) -> Vec<PlannedChange> {
+        self.changes.into_inner().unwrap()
+    }
+
+    // current_object reads the object that req targets from the inner backend, if req targets an existing object.
+    async fn current_object(&self, req: &KubeAPIRequest, log_header: &str) -> Option<DynamicObject> {
+        let (api_resource, namespace, name) = match req {
+            KubeAPIRequest::DeleteRequest(req) => (&req.api_resource, &req.namespace, &req.name),
+            KubeAPIRequest::UpdateRequest(req) => (&req.api_resource, &req.namespace, &req.name),
+            KubeAPIRequest::UpdateStatusRequest(req) => (&req.api_resource, &req.namespace, &req.name),
+            KubeAPIRequest::GetThenDeleteRequest(req) => (&req.api_resource, &req.namespace, &req.name),
+            KubeAPIRequest::GetThenUpdateRequest(req) => (&req.api_resource, &req.namespace, &req.name),
+            KubeAPIRequest::GetThenUpdateStatusRequest(req) => (&req.api_resource, &req.namespace, &req.name),
+            KubeAPIRequest::PatchRequest(req) => (&req.api_resource, &req.namespace, &req.name),
+            _ => return None,
+        };
+        let get_req = KubeAPIRequest::GetRequest(KubeGetRequest {
+            api_resource: ApiResource::from_kube(api_resource.as_kube_ref().clone()),
+            name: name.clone(),
+            namespace: namespace.clone(),
+            read_mode: KubeReadMode::Quorum,
+        });
+        match self.inner.handle_request(get_req, log_header).await {
+            KubeAPIResponse::GetResponse(KubeGetResponse { res: Ok(obj) }) => Some(obj),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, B: APIServerBackend> APIServerBackend for PlanBackend<'a, B> {
+    async fn get_cr<K>(&self, namespace: &str, name: &str) -> Result<K, APIError>
+    where
+        K: Clone + Resource + DeserializeOwned + Debug + Send,
+        K::Scope: ApiScope,
+        K::DynamicType: Default,
+    {
+        self.inner.get_cr::<K>(namespace, name).await
+    }
+
+    async fn handle_request(&self, req: KubeAPIRequest, log_header: &str) -> KubeAPIResponse {
+        if let KubeAPIRequest::GetRequest(_) | KubeAPIRequest::ListRequest(_) = req {
+            return self.inner.handle_request(req, log_header).await;
+        }
+        let verb = request_verb(&req);
+        let key = request_key(&req);
+        let before = self.current_object(&req, log_header).await;
+        let resp = match self.dry_run {
+            Some(dry_run) => dry_run.handle_request(req, log_header).await,
+            None => simulate(req, before.as_ref()),
+        };
+        let (error, after) = response_object(&resp);
+        let diff = match (&error, verb) {
+            (None, "Delete") | (None, "GetThenDelete") | (Some(_), _) => Vec::new(),
+            _ => diff(&object_json(before.as_ref()), &object_json(after.as_ref())),
+        };
+        info!("{} Plan {} {} with error {:?}", log_header, verb, key, error);
+        self.changes.lock().unwrap().push(PlannedChange {
+            verb: verb,
+            key: key,
+            error: error,
+            diff: diff,
+        });
+        resp
+    }
+}
+
+// plan runs one reconcile of the custom resource namespace/name against the live cluster state
+// without changing the cluster, and returns the changes that the reconcile would make, in order.
+// The mutating requests are answered by server-side dry run, or by simulated responses if simulate is set.
+// Since the changes are not persisted, the later requests of the reconcile still see the state before the changes,
+// so the plan is exact for the reconciles that only read the state at the beginning (which is the common case).
+// Note that external requests are still sent to external.
+pub async fn plan<K, R, E>(namespace: &str, name: &str, simulate: bool, external: &E) -> Result<Vec<PlannedChange>>
+where
+    K: Clone
+        + Resource
+        + CustomResourceExt
+        + DeserializeOwned
+        + Debug
+        + Send
+        + Serialize,
+    K::Scope: ApiScope,
+    K::DynamicType: Default + Clone + Debug,
+    R: Reconciler,
+    R::K: ResourceWrapper<K>,
+    R::S: ReconcileStepName,
+    R::EReq: TraceExternal,
+    R::EResp: TraceExternal,
+    <R::K as View>::V: CustomResourceView,
+    E: ExternalShimLayer<R::EReq, R::EResp>,
+{
+    let client = Client::try_default().await?;
+    let cr = api_in::<K>(&client, &Some(namespace.to_string())).get(name).await?;
+    let cr_kind = K::kind(&K::DynamicType::default()).to_string();
+    let inner = KubeAPIServerBackend { client: client.clone(), dry_run: false, cr_kind: cr_kind.clone() };
+    let dry_run = KubeAPIServerBackend { client: client, dry_run: true, cr_kind: cr_kind };
+    let backend = PlanBackend::new(&inner, if simulate { None } else { Some(&dry_run) });
+    let config = ControllerConfig::default();
+    let res = reconcile_with_backend::<K, R, E, _>(
+        Arc::new(cr),
+        &backend,
+        config.requeue_interval,
+        None,
+        None,
+        external,
+        config.external_call_timeout,
+    )
+    .await;
+    if let Err(err) = res {
+        // The changes before the error are still worth showing
+        warn!("reconcile of {}/{} failed with error: {}", namespace, name, err);
+    }
+    Ok(backend.into_changes())
+}
+
+// plan_key splits the argument of the plan subcommand into the namespace and the name of the custom resource.
+// A namespaced K is addressed by <namespace>/<name>, and a cluster-scoped K by a bare <name>
+// (the namespace is then empty and ignored by api_in).
+pub fn plan_key<K>(key: &str) -> Result<(String, String)>
+where
+    K: Resource,
+    K::Scope: ApiScope,
+{
+    if is_namespaced::<K>() {
+        match key.split_once('/') {
+            Some((namespace, name)) if !namespace.is_empty() && !name.is_empty() => {
+                Ok((namespace.to_string(), name.to_string()))
+            }
+            _ => Err(anyhow::anyhow!("\"plan\" expects <namespace>/<name> for a namespaced resource, got {}", key)),
+        }
+    } else if key.is_empty() || key.contains('/') {
+        Err(anyhow::anyhow!("\"plan\" expects <name> for a cluster-scoped resource, got {}", key))
+    } else {
+        Ok((String::new(), key.to_string()))
+    }
+}
+
+// format_plan renders the changes as a numbered list, each followed by its diff.
+pub fn format_plan(changes: &[PlannedChange]) -> String {
+    if changes.is_empty() {
+        return "No changes.\n".to_string();
+    }
+    let mut out = String::new();
+    for (i, change) in changes.iter().enumerate() {
+        out.push_str(&format!("{}. {} {}\n", i + 1, change.verb, change.key));
+        if let Some(err) = &change.error {
+            out.push_str(&format!("   (fails with {})\n", err));
+        }
+        for line in &change.diff {
+            out.push_str(&format!("   {}\n", line));
+        }
+    }
+    out
+}
+
+// The metadata fields that are maintained by the API server, which are left out of the diff
+const SERVER_MAINTAINED_FIELDS: [&str; 5] = [
+    "metadata.creationTimestamp",
+    "metadata.generation",
+    "metadata.managedFields",
+    "metadata.resourceVersion",
+    "metadata.uid",
+];
+
+// diff lists the differences between two objects, one line per changed field:
+// "+ path: value" for an added field, "- path: value" for a removed field and "~ path: old -> new" for a changed one.
+// Arrays are compared as a whole.
+pub fn diff(before: &Value, after: &Value) -> Vec<String> {
+    let mut lines = Vec::new();
+    diff_at("", before, after, &mut lines);
+    lines
+}
+
+fn diff_at(path: &str, before: &Value, after: &Value, lines: &mut Vec<String>) {
+    if SERVER_MAINTAINED_FIELDS.contains(&path) {
+        return;
+    }
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+            for k in keys {
+                let path = if path.is_empty() { k.clone() } else { format!("{}.{}", path, k) };
+                diff_at(&path, before.get(k).unwrap_or(&Value::Null), after.get(k).unwrap_or(&Value::Null), lines);
+            }
+        }
+        _ if before == after => {}
+        (Value::Null, _) => lines.push(format!("+ {}: {}", path, after)),
+        (_, Value::Null) => lines.push(format!("- {}: {}", path, before)),
+        _ => lines.push(format!("~ {}: {} -> {}", path, before, after)),
+    }
+}
+
+fn object_json(obj: Option<&DynamicObject>) -> Value {
+    match obj {
+        Some(obj) => serde_json::to_value(obj.as_kube_ref()).unwrap(),
+        None => Value::Object(Default::default()),
+    }
+}
+
+// response_object returns the error of resp (if any) and the object after the request (if any).
+fn response_object(resp: &KubeAPIResponse) -> (Option<String>, Option<DynamicObject>) {
+    let obj_res = match resp {
+        KubeAPIResponse::CreateResponse(resp) => &resp.res,
+        KubeAPIResponse::UpdateResponse(resp) => &resp.res,
+        KubeAPIResponse::UpdateStatusResponse(resp) => &resp.res,
+        KubeAPIResponse::GetThenUpdateResponse(resp) => &resp.res,
+        KubeAPIResponse::GetThenUpdateStatusResponse(resp) => &resp.res,
+        KubeAPIResponse::PatchResponse(resp) => &resp.res,
+        KubeAPIResponse::DeleteResponse(resp) => return (resp.res.as_ref().err().map(|err| format!("{:?}", err)), None),
+        KubeAPIResponse::GetThenDeleteResponse(resp) => {
+            return (resp.res.as_ref().err().map(|err| format!("{:?}", err)), None)
+        }
+        _ => return (None, None),
+    };
+    match obj_res {
+        Ok(obj) => (None, Some(obj.clone())),
+        Err(err) => (Some(format!("{:?}", err)), None),
+    }
+}
+
+// simulate answers the mutating request req as the API server would if the current object is current,
+// without checking the resource version or validating the object.
+fn simulate(req: KubeAPIRequest, current: Option<&DynamicObject>) -> KubeAPIResponse {
+    let existing = |owner_ref: Option<(&OwnerReference, &KubeGetThenPredicate)>| -> Result<DynamicObject, APIError> {
+        let current = current.ok_or(APIError::ObjectNotFound)?;
+        match owner_ref {
+            Some((owner_ref, predicate))
+                if !current.metadata().owner_references_contains(owner_ref)
+                    || !predicate.holds_on(current.as_kube_ref()) =>
+            {
+                Err(APIError::TransactionAbort)
+            }
+            _ => Ok(current.clone()),
+        }
+    };
+    let with_status_of = |current: DynamicObject, obj: &DynamicObject| {
+        let mut updated = current.into_kube();
+        if let (Some(data), Some(status)) = (updated.data.as_object_mut(), obj.as_kube_ref().data.get("status")) {
+            data.insert("status".to_string(), status.clone());
+        }
+        DynamicObject::from_kube(updated)
+    };
+    match req {
+        KubeAPIRequest::CreateRequest(req) => {
+            let mut obj = req.obj.into_kube();
+            obj.metadata.namespace = Some(req.namespace.clone());
+            KubeAPIResponse::CreateResponse(KubeCreateResponse {
+                res: Ok(DynamicObject::from_kube(obj)),
+            })
+        }
+        KubeAPIRequest::DeleteRequest(_) => KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
+            res: existing(None).map(|_obj| ()),
+        }),
+        KubeAPIRequest::UpdateRequest(req) => KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
+            res: existing(None).map(|_obj| req.obj),
+        }),
+        KubeAPIRequest::UpdateStatusRequest(req) => KubeAPIResponse::UpdateStatusResponse(KubeUpdateStatusResponse {
+            res: existing(None).map(|current| with_status_of(current, &req.obj)),
+        }),
+        KubeAPIRequest::GetThenDeleteRequest(req) => KubeAPIResponse::GetThenDeleteResponse(KubeGetThenDeleteResponse {
+            res: existing(Some((&req.owner_ref, &req.predicate))).map(|_obj| ()),
+        }),
+        KubeAPIRequest::GetThenUpdateRequest(req) => KubeAPIResponse::GetThenUpdateResponse(KubeGetThenUpdateResponse {
+            res: existing(Some((&req.owner_ref, &req.predicate))).map(|_obj| req.obj),
+        }),
+        KubeAPIRequest::GetThenUpdateStatusRequest(req) => {
+            KubeAPIResponse::GetThenUpdateStatusResponse(KubeGetThenUpdateStatusResponse {
+                res: existing(Some((&req.owner_ref, &req.predicate))).map(|current| with_status_of(current, &req.obj)),
+            })
+        }
+        // A server-side apply (a patch with a field manager) creates the object if it does not exist,
+        // unless it carries a resource version or uid (see apply_creates_object in the API server model)
+        KubeAPIRequest::PatchRequest(req)
+            if current.is_none()
+                && req.field_manager.is_some()
+                && req.obj.as_kube_ref().metadata.resource_version.is_none()
+                && req.obj.as_kube_ref().metadata.uid.is_none() =>
+        {
+            let mut obj = req.obj.into_kube();
+            obj.metadata.namespace = Some(req.namespace.clone());
+            KubeAPIResponse::PatchResponse(KubePatchResponse {
+                res: Ok(DynamicObject::from_kube(obj)),
+            })
+        }
+        KubeAPIRequest::PatchRequest(req) => KubeAPIResponse::PatchResponse(KubePatchResponse {
+            res: existing(None).map(|mut current| {
+                current.merge_patch_from(&req.obj);
+                current
+            }),
+        }),
+        KubeAPIRequest::GetRequest(_) | KubeAPIRequest::ListRequest(_) => {
+            panic!("simulate only handles mutating requests")
+        }
+    }
+}
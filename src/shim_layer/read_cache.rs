@@ -0,0 +1,183 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, dynamic::*, resource::*};
+use crate::shim_layer::backend::{error_response, list_pagination, APIServerBackend};
+use crate::shim_layer::controller_runtime::ApiScope;
+use crate::shim_layer::in_memory_backend::selects;
+use core::fmt::Debug;
+use core::hash::Hash;
+use futures::future::BoxFuture;
+use kube::api::Resource;
+use kube::runtime::reflector::{ObjectRef, Store};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+use tracing::{info, warn};
+
+// CachedStore is the reflector store of one resource type, viewed as dynamic objects.
+pub trait CachedStore: Send + Sync {
+    // wait_until_ready resolves after the store receives the initial list of its watch,
+    // and returns false if the watch has been dropped.
+    fn wait_until_ready(&self) -> BoxFuture<'_, bool>;
+
+    fn get(&self, namespace: &str, name: &str) -> Option<kube::api::DynamicObject>;
+
+    fn list(&self, namespace: &str) -> Vec<kube::api::DynamicObject>;
+}
+
+impl<T> CachedStore for Store<T>
+where
+    T: Resource + Clone + Serialize + Send + Sync + 'static,
+    T::DynamicType: Default + Eq + Hash + Clone + Send + Sync,
+{
+    fn wait_until_ready(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async move { Store::wait_until_ready(self).await.is_ok() })
+    }
+
+    fn get(&self, namespace: &str, name: &str) -> Option<kube::api::DynamicObject> {
+        // Cluster-scoped objects are keyed by the empty namespace, but have no namespace in the store
+        let obj_ref = if namespace.is_empty() { ObjectRef::new(name) } else { ObjectRef::new(name).within(namespace) };
+        Store::get(self, &obj_ref).map(|obj| to_dynamic_object(obj.as_ref()))
+    }
+
+    fn list(&self, namespace: &str) -> Vec<kube::api::DynamicObject> {
+        self.state()
+            .iter()
+            .filter(|obj| obj.meta().namespace.as_deref().unwrap_or_default() == namespace)
+            .map(|obj| to_dynamic_object(obj.as_ref()))
+            .collect()
+    }
+}
+
+fn to_dynamic_object<T: Serialize>(obj: &T) -> kube::api::DynamicObject {
+    serde_json::from_value(serde_json::to_value(obj).unwrap()).unwrap()
+}
+
+// ReadCache holds the reflector stores kept by the watches of a controller, by kind and namespace
+// (where None stands for all namespaces).
+pub struct ReadCache {
+    stores: HashMap<(String, Option<String>), Box<dyn CachedStore>>,
+    // ready_timeout is how long a read waits for the store to receive the initial list of its watch
+    // before falling back to the inner backend
+    pub ready_timeout: Duration,
+}
+
+impl ReadCache {
+    pub fn new() -> ReadCache {
+        ReadCache {
+            stores: HashMap::new(),
+            ready_timeout: Duration::from_secs(10),
+        }
+    }
+
+    // insert adds the store of T kept by the watch in the namespace.
+    pub fn insert<T>(&mut self, namespace: &Option<String>, store: Store<T>)
+    where
+        T: Resource + Clone + Serialize + Send + Sync + 'static,
+        T::DynamicType: Default + Eq + Hash + Clone + Send + Sync,
+    {
+        let kind = T::kind(&T::DynamicType::default()).to_string();
+        self.stores.insert((kind, namespace.clone()), Box::new(store));
+    }
+
+    // store returns the store that holds the objects of the kind in the namespace, if any.
+    pub fn store(&self, kind: &str, namespace: &str) -> Option<&dyn CachedStore> {
+        self.stores
+            .get(&(kind.to_string(), Some(namespace.to_string())))
+            .or_else(|| self.stores.get(&(kind.to_string(), None)))
+            .map(|store| store.as_ref())
+    }
+}
+
+// CachedBackend serves the Get and List requests with the Cached read mode from the reflector stores
+// in the cache, and forwards all the other requests (and the read of the custom resource) to the inner backend.
+// Requests on kinds that are not watched, Lists whose selectors cannot be evaluated locally,
+// and all requests when cache is None also go to the inner backend.
+//
+// Note that the reads from the cache can be stale: the cluster spec models them as reads from a cache that
+// lags behind etcd (see Cluster::cached_read in crate::kubernetes_cluster::spec::cluster), so a controller
+// should only issue Cached reads if its proof holds with stale reads. The read mode of the request is the opt-in:
+// Quorum reads always go to the inner backend.
+pub struct CachedBackend<'a, B: APIServerBackend> {
+    pub inner: &'a B,
+    pub cache: Option<&'a ReadCache>,
+}
+
+impl<'a, B: APIServerBackend> CachedBackend<'a, B> {
+    // ready_store returns the store of the kind in the namespace once it is ready, or None if there is no such store
+    // or it is not ready within the ready_timeout of the cache (e.g., the watch keeps failing to list).
+    async fn ready_store(&self, kind: &str, namespace: &str) -> Option<&'a dyn CachedStore> {
+        let cache = self.cache?;
+        let store = cache.store(kind, namespace)?;
+        match tokio::time::timeout(cache.ready_timeout, store.wait_until_ready()).await {
+            Ok(true) => Some(store),
+            Ok(false) => None,
+            Err(_elapsed) => {
+                warn!("The cache of {} is not ready after {:?}, reading from the API server", kind, cache.ready_timeout);
+                None
+            }
+        }
+    }
+}
+
+impl<'a, B: APIServerBackend> APIServerBackend for CachedBackend<'a, B> {
+    // The custom resource is always read from the inner backend, like the quorum read assumed by
+    // Cluster::schedule_controller_reconcile.
+    async fn get_cr<K>(&self, namespace: &str, name: &str) -> Result<K, APIError>
+    where
+        K: Clone + Resource + DeserializeOwned + Debug + Send,
+        K::Scope: ApiScope,
+        K::DynamicType: Default,
+    {
+        self.inner.get_cr::<K>(namespace, name).await
+    }
+
+    async fn handle_request(&self, req: KubeAPIRequest, log_header: &str) -> KubeAPIResponse {
+        match &req {
+            KubeAPIRequest::GetRequest(get_req) if matches!(get_req.read_mode, KubeReadMode::Cached) => {
+                if let Some(store) = self.ready_store(&get_req.api_resource.as_kube_ref().kind, &get_req.namespace).await {
+                    let res = store
+                        .get(&get_req.namespace, &get_req.name)
+                        .map(DynamicObject::from_kube)
+                        .ok_or(APIError::ObjectNotFound);
+                    info!("{} Get {} from cache", log_header, get_req.key());
+                    return KubeAPIResponse::GetResponse(KubeGetResponse { res });
+                }
+            }
+            KubeAPIRequest::ListRequest(list_req) if matches!(list_req.read_mode, KubeReadMode::Cached) => {
+                // A Cached list is served in one page (see list_pagination)
+                if let Err(err) = list_pagination(list_req) {
+                    return error_response(&req, err);
+                }
+                // Only the selectors that the cache can evaluate (see selects) are served from the cache
+                let label_selector = match &list_req.label_selector {
+                    Some(selector) if selector.as_kube_ref().match_expressions.is_some() => None,
+                    Some(selector) => Some(selector.as_kube_ref().match_labels.clone().unwrap_or_default()),
+                    None => Some(Default::default()),
+                };
+                let field_selector = list_req.field_selector.as_ref().map(|selector| selector.clone().into_rust_map());
+                let supported_fields = field_selector.as_ref().map_or(true, |selector| {
+                    selector.keys().all(|path| path == "metadata.name" || path == "metadata.namespace")
+                });
+                if let (Some(label_selector), true) = (label_selector, supported_fields) {
+                    if let Some(store) =
+                        self.ready_store(&list_req.api_resource.as_kube_ref().kind, &list_req.namespace).await
+                    {
+                        let objs = store
+                            .list(&list_req.namespace)
+                            .into_iter()
+                            .filter(|obj| selects(obj, Some(&label_selector), field_selector.as_ref()))
+                            .map(DynamicObject::from_kube)
+                            .collect();
+                        info!("{} List {} from cache", log_header, list_req.key());
+                        return KubeAPIResponse::ListResponse(KubeListResponse { res: Ok(objs), continue_token: None });
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.inner.handle_request(req, log_header).await
+    }
+}
@@ -0,0 +1,503 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::*, dynamic::*, resource::*};
+use crate::kubernetes_api_objects::spec::resource::*;
+use crate::reconciler::exec::{io::*, reconciler::*};
+use crate::shim_layer::metrics::request_verb;
+use anyhow::{anyhow, bail, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use vstd::prelude::View;
+
+// A trace records what happens in one reconcile: the custom resource that R::reconcile_core is invoked with,
+// and for each invocation of R::reconcile_core, the reconcile step it starts from, the request it issues
+// and the response it receives.
+//
+// When the controller runs with a trace file (see ControllerConfig), reconcile_with_backend appends
+// one line of JSON to the file for each reconcile (i.e., the JSON Lines format).
+// replay_file reads the trace file and replays the reconciles offline, without a Kubernetes cluster,
+// to reproduce a production incident or to detect nondeterminism in the exec reconcile_core.
+//
+// Requests to the external systems and their responses are recorded through TraceExternal;
+// when replaying, the external requests are checked against the recorded ones and answered with
+// the recorded responses, so the replay never reaches the external systems either.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReconcileTrace {
+    pub cr_key: String,
+    // cr is the custom resource that R::reconcile_core is invoked with
+    pub cr: Value,
+    pub steps: Vec<TraceStep>,
+    pub outcome: TraceOutcome,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TraceStep {
+    // reconcile_step is the name of the reconcile step before invoking R::reconcile_core
+    pub reconcile_step: String,
+    pub request: Option<Value>,
+    pub response: Option<Value>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraceOutcome {
+    // The reconcile has not ended yet
+    Pending,
+    // R::reconcile_done
+    Done,
+    // R::reconcile_error
+    Error,
+}
+
+// ReconcileStepName names the reconcile step of the reconcile state (R::S) in the trace.
+// The controllers implement it next to their exec reconcilers (outside the verified code),
+// usually as the Debug form of the step (e.g., "AfterCreatePod(0)").
+pub trait ReconcileStepName {
+    fn reconcile_step_name(&self) -> String;
+}
+
+// TraceExternal converts the requests to (R::EReq) and the responses from (R::EResp) the external systems
+// to and from JSON, so that they are recorded in the trace and replayed from it.
+pub trait TraceExternal: Sized {
+    fn to_trace_json(&self) -> Value;
+    fn from_trace_json(v: &Value) -> Result<Self>;
+}
+
+// VoidEReq and VoidEResp carry nothing
+impl TraceExternal for VoidEReq {
+    fn to_trace_json(&self) -> Value {
+        json!({})
+    }
+
+    fn from_trace_json(_v: &Value) -> Result<VoidEReq> {
+        Ok(VoidEReq {})
+    }
+}
+
+impl TraceExternal for VoidEResp {
+    fn to_trace_json(&self) -> Value {
+        json!({})
+    }
+
+    fn from_trace_json(_v: &Value) -> Result<VoidEResp> {
+        Ok(VoidEResp {})
+    }
+}
+
+impl ReconcileTrace {
+    pub fn new<K: Serialize>(cr_key: String, cr: &K) -> ReconcileTrace {
+        ReconcileTrace {
+            cr_key: cr_key,
+            cr: to_json(cr),
+            steps: Vec::new(),
+            outcome: TraceOutcome::Pending,
+        }
+    }
+}
+
+impl TraceStep {
+    pub fn new<S: ReconcileStepName>(state: &S) -> TraceStep {
+        TraceStep {
+            reconcile_step: state.reconcile_step_name(),
+            request: None,
+            response: None,
+        }
+    }
+}
+
+// TraceRecorder appends the traces to the trace file.
+// Reconciles of different custom resources run concurrently, so each trace is written as a whole line.
+pub struct TraceRecorder {
+    file: Mutex<File>,
+}
+
+impl TraceRecorder {
+    pub fn create(path: &Path) -> Result<TraceRecorder> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| anyhow!("failed to open trace file {}: {}", path.display(), e))?;
+        Ok(TraceRecorder {
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn record(&self, trace: &ReconcileTrace) -> Result<()> {
+        let line = serde_json::to_string(trace)?;
+        let mut file = self.file.lock().unwrap();
+        writeln!(file, "{}", line)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+// replay_file replays each reconcile recorded in the trace file at path (see replay),
+// and returns the number of replayed reconciles.
+pub fn replay_file<K, R>(path: &Path) -> Result<usize>
+where
+    K: DeserializeOwned,
+    R: Reconciler,
+    R::K: ResourceWrapper<K>,
+    R::S: ReconcileStepName,
+    R::EReq: TraceExternal,
+    R::EResp: TraceExternal,
+    <R::K as View>::V: CustomResourceView,
+{
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("failed to read trace file {}: {}", path.display(), e))?;
+    let mut replayed = 0;
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let trace: ReconcileTrace =
+            serde_json::from_str(line).map_err(|e| anyhow!("line {}: malformed trace: {}", i + 1, e))?;
+        replay::<K, R>(&trace).map_err(|e| anyhow!("line {}: {}", i + 1, e))?;
+        replayed += 1;
+    }
+    Ok(replayed)
+}
+
+// replay feeds the responses recorded in the trace (including the ones from the external systems)
+// back to R::reconcile_core, and checks that R::reconcile_core goes through the same reconcile steps,
+// issues exactly the same requests and ends with the same outcome as recorded.
+pub fn replay<K, R>(trace: &ReconcileTrace) -> Result<()>
+where
+    K: DeserializeOwned,
+    R: Reconciler,
+    R::K: ResourceWrapper<K>,
+    R::S: ReconcileStepName,
+    R::EReq: TraceExternal,
+    R::EResp: TraceExternal,
+    <R::K as View>::V: CustomResourceView,
+{
+    let cr = serde_json::from_value::<K>(trace.cr.clone())
+        .map_err(|e| anyhow!("{}: malformed custom resource: {}", trace.cr_key, e))?;
+    let cr_wrapper = R::K::from_kube(cr);
+    // Same as reconcile_with_backend, a terminating custom resource is reconciled by reconcile_delete
+    let terminating = R::finalizer().is_some() && !trace.cr["metadata"]["deletionTimestamp"].is_null();
+    let mut state = R::reconcile_init_state();
+    let mut resp_option: Option<Response<R::EResp>> = None;
+
+    for (i, step) in trace.steps.iter().enumerate() {
+        let reconcile_step = state.reconcile_step_name();
+        if R::reconcile_done(&state) || R::reconcile_error(&state) {
+            bail!(
+                "{} step {}: reconcile ends at {} but the trace has {} steps",
+                trace.cr_key, i, reconcile_step, trace.steps.len()
+            );
+        }
+        if reconcile_step != step.reconcile_step {
+            bail!(
+                "{} step {}: expected reconcile step {}, got {}",
+                trace.cr_key, i, step.reconcile_step, reconcile_step
+            );
+        }
+        let (state_prime, request_option) = if terminating {
+            R::reconcile_delete(&cr_wrapper, resp_option, state)
+        } else {
+            R::reconcile_core(&cr_wrapper, resp_option, state)
+        };
+        let request = request_option.as_ref().map(request_json);
+        if request != step.request {
+            bail!(
+                "{} step {} ({}): expected request {}, got {}",
+                trace.cr_key, i, reconcile_step, show(&step.request), show(&request)
+            );
+        }
+        resp_option = match request_option {
+            Some(request) => {
+                let response = step
+                    .response
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("{} step {}: the trace misses the response", trace.cr_key, i))?;
+                match request {
+                    Request::KRequest(_) => Some(Response::KResponse(response_from_json(response)?)),
+                    Request::ExternalRequest(_) => Some(Response::ExternalResponse(external_response_from_json(response)?)),
+                }
+            }
+            None => None,
+        };
+        state = state_prime;
+    }
+
+    let outcome = if R::reconcile_done(&state) {
+        TraceOutcome::Done
+    } else if R::reconcile_error(&state) {
+        TraceOutcome::Error
+    } else {
+        TraceOutcome::Pending
+    };
+    if outcome != trace.outcome {
+        bail!(
+            "{}: expected the reconcile to end with {:?}, got {:?} at {}",
+            trace.cr_key, trace.outcome, outcome, state.reconcile_step_name()
+        );
+    }
+    Ok(())
+}
+
+fn show(v: &Option<Value>) -> String {
+    match v {
+        Some(v) => v.to_string(),
+        None => "none".to_string(),
+    }
+}
+
+fn to_json<T: Serialize>(v: &T) -> Value {
+    serde_json::to_value(v).unwrap_or(Value::Null)
+}
+
+fn api_resource_json(api_resource: &ApiResource) -> Value {
+    let api_resource = api_resource.as_kube_ref();
+    json!({
+        "apiVersion": api_resource.api_version,
+        "kind": api_resource.kind,
+        "plural": api_resource.plural,
+    })
+}
+
+fn predicate_json(predicate: &KubeGetThenPredicate) -> Value {
+    match predicate {
+        KubeGetThenPredicate::OwnerRef => json!({ "ownerRef": {} }),
+        KubeGetThenPredicate::LabelsMatch(labels) => json!({ "labelsMatch": labels.clone().into_rust_map() }),
+        KubeGetThenPredicate::ResourceVersionMatch(rv) => json!({ "resourceVersionMatch": rv }),
+        KubeGetThenPredicate::SpecHashMatch(hash) => json!({ "specHashMatch": hash }),
+    }
+}
+
+pub fn request_json<T: View + TraceExternal>(req: &Request<T>) -> Value {
+    match req {
+        Request::KRequest(req) => request_to_json(req),
+        Request::ExternalRequest(req) => json!({ "verb": "External", "req": req.to_trace_json() }),
+    }
+}
+
+pub fn request_to_json(req: &KubeAPIRequest) -> Value {
+    let verb = request_verb(req);
+    match req {
+        KubeAPIRequest::GetRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "readMode": format!("{:?}", req.read_mode),
+        }),
+        KubeAPIRequest::ListRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "labelSelector": req.label_selector.as_ref().map(|s| to_json(s.as_kube_ref())),
+            "fieldSelector": req.field_selector.as_ref().map(|s| to_json(&s.clone().into_rust_map())),
+            "readMode": format!("{:?}", req.read_mode),
+            "limit": req.limit,
+            "continue": req.continue_token,
+        }),
+        KubeAPIRequest::CreateRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "obj": to_json(req.obj.as_kube_ref()),
+        }),
+        KubeAPIRequest::DeleteRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "preconditions": req.preconditions.as_ref().map(|p| to_json(p.as_kube_ref())),
+            "propagationPolicy": req.propagation_policy.map(|p| format!("{:?}", p)),
+        }),
+        KubeAPIRequest::UpdateRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "obj": to_json(req.obj.as_kube_ref()),
+        }),
+        KubeAPIRequest::UpdateStatusRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "obj": to_json(req.obj.as_kube_ref()),
+        }),
+        KubeAPIRequest::GetThenDeleteRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "ownerRef": to_json(req.owner_ref.as_kube_ref()),
+            "predicate": predicate_json(&req.predicate),
+        }),
+        KubeAPIRequest::GetThenUpdateRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "ownerRef": to_json(req.owner_ref.as_kube_ref()),
+            "predicate": predicate_json(&req.predicate),
+            "obj": to_json(req.obj.as_kube_ref()),
+        }),
+        KubeAPIRequest::GetThenUpdateStatusRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "ownerRef": to_json(req.owner_ref.as_kube_ref()),
+            "predicate": predicate_json(&req.predicate),
+            "obj": to_json(req.obj.as_kube_ref()),
+        }),
+        KubeAPIRequest::PatchRequest(req) => json!({
+            "verb": verb,
+            "resource": api_resource_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "obj": to_json(req.obj.as_kube_ref()),
+            "fieldManager": req.field_manager,
+        }),
+    }
+}
+
+fn result_json<T>(verb: &str, res: &Result<T, APIError>, ok: impl Fn(&T) -> Value) -> Value {
+    match res {
+        Ok(v) => json!({ "verb": verb, "ok": ok(v) }),
+        Err(err) => json!({ "verb": verb, "err": format!("{:?}", err) }),
+    }
+}
+
+fn object_json(obj: &DynamicObject) -> Value {
+    to_json(obj.as_kube_ref())
+}
+
+fn objects_json(objs: &Vec<DynamicObject>) -> Value {
+    Value::Array(objs.iter().map(object_json).collect())
+}
+
+pub fn response_to_json(resp: &KubeAPIResponse) -> Value {
+    match resp {
+        KubeAPIResponse::GetResponse(resp) => result_json("Get", &resp.res, object_json),
+        KubeAPIResponse::ListResponse(resp) => {
+            let mut json = result_json("List", &resp.res, objects_json);
+            if let Some(continue_token) = &resp.continue_token {
+                json["continue"] = json!(continue_token);
+            }
+            json
+        }
+        KubeAPIResponse::CreateResponse(resp) => result_json("Create", &resp.res, object_json),
+        KubeAPIResponse::DeleteResponse(resp) => result_json("Delete", &resp.res, |_| Value::Null),
+        KubeAPIResponse::UpdateResponse(resp) => result_json("Update", &resp.res, object_json),
+        KubeAPIResponse::UpdateStatusResponse(resp) => result_json("UpdateStatus", &resp.res, object_json),
+        KubeAPIResponse::GetThenDeleteResponse(resp) => result_json("GetThenDelete", &resp.res, |_| Value::Null),
+        KubeAPIResponse::GetThenUpdateResponse(resp) => result_json("GetThenUpdate", &resp.res, object_json),
+        KubeAPIResponse::GetThenUpdateStatusResponse(resp) => {
+            result_json("GetThenUpdateStatus", &resp.res, object_json)
+        }
+        KubeAPIResponse::PatchResponse(resp) => result_json("Patch", &resp.res, object_json),
+    }
+}
+
+pub fn external_response_to_json<T: TraceExternal>(resp: &Result<T, ExternalError>) -> Value {
+    match resp {
+        Ok(resp) => json!({ "verb": "External", "ok": resp.to_trace_json() }),
+        Err(err) => json!({ "verb": "External", "err": format!("{:?}", err) }),
+    }
+}
+
+pub fn external_response_from_json<T: TraceExternal>(resp: &Value) -> Result<Result<T, ExternalError>> {
+    if resp["verb"] != "External" {
+        bail!("expected an external response, got {}", resp);
+    }
+    match resp.get("err").map(|err| err.as_str().unwrap_or_default()) {
+        Some("Timeout") => Ok(Err(ExternalError::Timeout)),
+        Some("TransportError") => Ok(Err(ExternalError::TransportError)),
+        Some(err) => Err(anyhow!("unknown ExternalError {}", err)),
+        None => Ok(Ok(T::from_trace_json(&resp["ok"])?)),
+    }
+}
+
+fn api_error_from_str(err: &str) -> Result<APIError> {
+    match err {
+        "BadRequest" => Ok(APIError::BadRequest),
+        "Conflict" => Ok(APIError::Conflict),
+        "Forbidden" => Ok(APIError::Forbidden),
+        "Invalid" => Ok(APIError::Invalid),
+        "ObjectNotFound" => Ok(APIError::ObjectNotFound),
+        "ObjectAlreadyExists" => Ok(APIError::ObjectAlreadyExists),
+        "NotSupported" => Ok(APIError::NotSupported),
+        "InternalError" => Ok(APIError::InternalError),
+        "Timeout" => Ok(APIError::Timeout),
+        "ServerTimeout" => Ok(APIError::ServerTimeout),
+        "TransactionAbort" => Ok(APIError::TransactionAbort),
+        "Expired" => Ok(APIError::Expired),
+        "Other" => Ok(APIError::Other),
+        _ => Err(anyhow!("unknown APIError {}", err)),
+    }
+}
+
+fn result_from_json<T>(resp: &Value, ok: impl Fn(&Value) -> Result<T>) -> Result<Result<T, APIError>> {
+    match resp.get("err") {
+        Some(err) => Ok(Err(api_error_from_str(err.as_str().unwrap_or_default())?)),
+        None => Ok(Ok(ok(&resp["ok"])?)),
+    }
+}
+
+fn object_from_json(obj: &Value) -> Result<DynamicObject> {
+    let obj = serde_json::from_value::<kube::api::DynamicObject>(obj.clone())?;
+    Ok(DynamicObject::from_kube(obj))
+}
+
+fn objects_from_json(objs: &Value) -> Result<Vec<DynamicObject>> {
+    objs.as_array()
+        .ok_or_else(|| anyhow!("expected a list of objects, got {}", objs))?
+        .iter()
+        .map(object_from_json)
+        .collect()
+}
+
+pub fn response_from_json(resp: &Value) -> Result<KubeAPIResponse> {
+    let verb = resp["verb"]
+        .as_str()
+        .ok_or_else(|| anyhow!("response misses verb: {}", resp))?;
+    match verb {
+        "Get" => Ok(KubeAPIResponse::GetResponse(KubeGetResponse {
+            res: result_from_json(resp, object_from_json)?,
+        })),
+        "List" => Ok(KubeAPIResponse::ListResponse(KubeListResponse {
+            res: result_from_json(resp, objects_from_json)?,
+            continue_token: resp.get("continue").and_then(Value::as_str).map(str::to_string),
+        })),
+        "Create" => Ok(KubeAPIResponse::CreateResponse(KubeCreateResponse {
+            res: result_from_json(resp, object_from_json)?,
+        })),
+        "Delete" => Ok(KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
+            res: result_from_json(resp, |_| Ok(()))?,
+        })),
+        "Update" => Ok(KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
+            res: result_from_json(resp, object_from_json)?,
+        })),
+        "UpdateStatus" => Ok(KubeAPIResponse::UpdateStatusResponse(KubeUpdateStatusResponse {
+            res: result_from_json(resp, object_from_json)?,
+        })),
+        "GetThenDelete" => Ok(KubeAPIResponse::GetThenDeleteResponse(KubeGetThenDeleteResponse {
+            res: result_from_json(resp, |_| Ok(()))?,
+        })),
+        "GetThenUpdate" => Ok(KubeAPIResponse::GetThenUpdateResponse(KubeGetThenUpdateResponse {
+            res: result_from_json(resp, object_from_json)?,
+        })),
+        "GetThenUpdateStatus" => Ok(KubeAPIResponse::GetThenUpdateStatusResponse(
+            KubeGetThenUpdateStatusResponse {
+                res: result_from_json(resp, object_from_json)?,
+            },
+        )),
+        "Patch" => Ok(KubeAPIResponse::PatchResponse(KubePatchResponse {
+            res: result_from_json(resp, object_from_json)?,
+        })),
+        _ => Err(anyhow!("unknown response verb {}", verb)),
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::external_shim_layer::*;
+use crate::kubernetes_api_objects::error::*;
+use crate::reconciler::exec::io::{VoidEReq, VoidEResp};
+use std::time::Duration;
+
+// SlowExternalShimLayer responds after the delay given at startup.
+struct SlowExternalShimLayer {
+    delay: Duration,
+}
+
+impl ExternalShimLayer<VoidEReq, VoidEResp> for SlowExternalShimLayer {
+    type Config = Duration;
+
+    async fn init(delay: Duration) -> anyhow::Result<SlowExternalShimLayer> {
+        Ok(SlowExternalShimLayer { delay: delay })
+    }
+
+    async fn external_call(&self, _req: VoidEReq) -> Result<VoidEResp, ExternalError> {
+        tokio::time::sleep(self.delay).await;
+        Ok(VoidEResp {})
+    }
+}
+
+#[tokio::test]
+pub async fn test_call_with_timeout() {
+    let external = SlowExternalShimLayer::init(Duration::from_millis(200)).await.unwrap();
+    assert!(matches!(
+        call_with_timeout(&external, VoidEReq {}, Duration::from_millis(10)).await,
+        Err(ExternalError::Timeout)
+    ));
+    assert!(call_with_timeout(&external, VoidEReq {}, Duration::from_secs(10)).await.is_ok());
+}
+
+#[tokio::test]
+pub async fn test_void_external_shim_layer_fails_requests() {
+    let external = VoidExternalShimLayer::init(()).await.unwrap();
+    assert!(matches!(
+        call_with_timeout(&external, VoidEReq {}, Duration::from_secs(10)).await,
+        Err(ExternalError::TransportError)
+    ));
+}
@@ -3,6 +3,7 @@
 use crate::kubernetes_api_objects::exec::api_method::*;
 use crate::kubernetes_api_objects::exec::api_resource::*;
 use crate::kubernetes_api_objects::exec::dynamic::*;
+use crate::kubernetes_api_objects::exec::label_selector::*;
 use crate::kubernetes_api_objects::exec::object_meta::*;
 use crate::kubernetes_api_objects::exec::resource::*;
 use crate::vstd_ext::string_map::*;
@@ -23,6 +24,7 @@ pub fn test_getrequest_key() {
         }),
         name: "name".to_string(),
         namespace: "namespace".to_string(),
+        read_mode: KubeReadMode::Quorum,
     };
     assert_eq!(api_method.key(), "kind/namespace/name");
 }
@@ -38,10 +40,84 @@ pub fn test_listrequest_key() {
             plural: "plural".to_string(),
         }),
         namespace: "namespace".to_string(),
+        label_selector: None,
+        field_selector: None,
+        read_mode: KubeReadMode::Quorum,
+        limit: None,
+        continue_token: None,
     };
     assert_eq!(api_method.key(), "kind/namespace");
 }
 
+#[test]
+pub fn test_listrequest_selector_string() {
+    let mut match_labels = StringMap::empty();
+    match_labels.insert("app".to_string(), "nginx".to_string());
+    match_labels.insert("tier".to_string(), "web".to_string());
+    let mut label_selector = LabelSelector::default();
+    label_selector.set_match_labels(match_labels);
+    let mut field_selector = StringMap::empty();
+    field_selector.insert("metadata.name".to_string(), "name".to_string());
+    let api_method = KubeListRequest {
+        api_resource: ApiResource::from_kube(kube::api::ApiResource {
+            group: "group".to_string(),
+            version: "version".to_string(),
+            kind: "kind".to_string(),
+            api_version: "api_version".to_string(),
+            plural: "plural".to_string(),
+        }),
+        namespace: "namespace".to_string(),
+        label_selector: Some(label_selector),
+        field_selector: Some(field_selector),
+        read_mode: KubeReadMode::Quorum,
+        limit: None,
+        continue_token: None,
+    };
+    assert_eq!(api_method.label_selector_string(), Some("app=nginx,tier=web".to_string()));
+    assert_eq!(api_method.field_selector_string(), Some("metadata.name=name".to_string()));
+}
+
+#[test]
+pub fn test_field_selector_string_escapes_values() {
+    assert_eq!(escape_field_selector_value("name"), "name".to_string());
+    assert_eq!(escape_field_selector_value("a,b=c\\d"), "a\\,b\\=c\\\\d".to_string());
+    let mut field_selector = StringMap::empty();
+    field_selector.insert("metadata.name".to_string(), "a,metadata.namespace=b".to_string());
+    let api_method = KubeListRequest {
+        api_resource: ApiResource::from_kube(kube::api::ApiResource {
+            group: "group".to_string(),
+            version: "version".to_string(),
+            kind: "kind".to_string(),
+            api_version: "api_version".to_string(),
+            plural: "plural".to_string(),
+        }),
+        namespace: "namespace".to_string(),
+        label_selector: None,
+        field_selector: Some(field_selector),
+        read_mode: KubeReadMode::Quorum,
+        limit: None,
+        continue_token: None,
+    };
+    assert_eq!(api_method.field_selector_string(), Some("metadata.name=a\\,metadata.namespace\\=b".to_string()));
+}
+
+// The cases of TestEscapeValue in k8s.io/apimachinery/pkg/fields/selector_test.go (v1.30.0),
+// whose fields.EscapeValue prefixes each '\', ',' and '=' with a '\'
+#[test]
+pub fn test_escape_field_selector_value_matches_escape_value() {
+    let cases = [
+        (r"", r""),
+        (r"a", r"a"),
+        (r"=", r"\="),
+        (r",", r"\,"),
+        (r"\", r"\\"),
+        (r"\=\,\", r"\\\=\\\,\\"),
+    ];
+    for (unescaped, escaped) in cases {
+        assert_eq!(escape_field_selector_value(unescaped), escaped.to_string());
+    }
+}
+
 #[test]
 pub fn test_createquest_key() {
     let api_method = KubeCreateRequest {
@@ -84,6 +160,7 @@ pub fn test_deleterequest_key() {
         name: "name".to_string(),
         namespace: "namespace".to_string(),
         preconditions: None,
+        propagation_policy: None,
     };
     assert_eq!(api_method.key(), "kind/namespace/name");
 }
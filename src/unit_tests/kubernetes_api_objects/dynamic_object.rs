@@ -101,3 +101,29 @@ pub fn test_fmt() {
         format!("{:?}", dynamic_object.into_kube())
     );
 }
+
+#[test]
+pub fn test_spec_hash() {
+    let dynamic_object = DynamicObject::from_kube(kube::api::DynamicObject {
+        metadata: kube::api::ObjectMeta {
+            name: Some("name".to_string()),
+            namespace: Some("namespace".to_string()),
+            ..Default::default()
+        },
+        types: Some(kube::api::TypeMeta {
+            api_version: "api_version".to_string(),
+            kind: "kind".to_string(),
+        }),
+        data: serde_json::json!({
+            "spec": {"b": 2, "a": 1},
+            "key": "value",
+            "status": {"ready": true},
+        }),
+    });
+    // The hash is pinned so that a change of the hash function (which breaks the predicates
+    // built by an older controller) shows up here
+    assert_eq!(
+        dynamic_object.spec_hash(),
+        "ab5d51be5bb1c4b08b5d4bd373cecabb2ab7ebc18db571f7b71d73ccee2a06f4"
+    );
+}
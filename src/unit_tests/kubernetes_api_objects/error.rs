@@ -32,6 +32,8 @@ pub fn test_apierror_fmt() {
     assert_eq!(format!("{:?}", error), "Timeout");
     let error = APIError::ServerTimeout;
     assert_eq!(format!("{:?}", error), "ServerTimeout");
+    let error = APIError::Expired;
+    assert_eq!(format!("{:?}", error), "Expired");
     let error = APIError::Other;
     assert_eq!(format!("{:?}", error), "Other");
 }
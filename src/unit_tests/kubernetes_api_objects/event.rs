@@ -0,0 +1,89 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::exec::api_resource::*;
+use crate::kubernetes_api_objects::exec::event::*;
+use crate::kubernetes_api_objects::exec::object_meta::*;
+use crate::kubernetes_api_objects::exec::resource::*;
+use vstd::prelude::*;
+use vstd::string::*;
+
+#[test]
+pub fn test_set_metadata() {
+    let mut object_meta = ObjectMeta::default();
+    object_meta.set_name("name".to_string());
+
+    let mut event = Event::default();
+    event.set_metadata(object_meta.clone());
+    assert_eq!(object_meta.into_kube(), event.into_kube().metadata);
+}
+
+#[test]
+pub fn test_default() {
+    let event = Event::default();
+    assert_eq!(
+        event.into_kube(),
+        k8s_openapi::api::core::v1::Event::default()
+    );
+}
+
+#[test]
+pub fn test_set_involved_object() {
+    let mut involved_object = ObjectReference::default();
+    involved_object.set_api_version("anvil.dev/v1".to_string());
+    involved_object.set_kind("VReplicaSet".to_string());
+    involved_object.set_name("name".to_string());
+    involved_object.set_namespace("default".to_string());
+
+    let mut event = Event::default();
+    event.set_involved_object(involved_object.clone());
+    assert_eq!(involved_object.into_kube(), event.involved_object().into_kube());
+    assert_eq!(
+        k8s_openapi::api::core::v1::ObjectReference {
+            api_version: Some("anvil.dev/v1".to_string()),
+            kind: Some("VReplicaSet".to_string()),
+            name: Some("name".to_string()),
+            namespace: Some("default".to_string()),
+            ..Default::default()
+        },
+        event.into_kube().involved_object
+    );
+}
+
+#[test]
+pub fn test_set_reason_message_and_type() {
+    let mut event = Event::default();
+    event.set_reason("SuccessfulCreate".to_string());
+    event.set_message("Created pod: name".to_string());
+    event.set_type("Normal".to_string());
+    assert_eq!(Some("SuccessfulCreate".to_string()), event.reason());
+    assert_eq!(Some("Created pod: name".to_string()), event.message());
+    assert_eq!(Some("Normal".to_string()), event.type_());
+}
+
+#[test]
+pub fn test_api_resource() {
+    let api_resource = Event::api_resource();
+    assert_eq!(api_resource.into_kube().kind, "Event");
+}
+
+#[test]
+pub fn test_marshal() {
+    let kube_event = k8s_openapi::api::core::v1::Event {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some("name".to_string()),
+            ..Default::default()
+        },
+        reason: Some("SuccessfulCreate".to_string()),
+        type_: Some("Normal".to_string()),
+        ..Default::default()
+    };
+
+    let event = Event::from_kube(kube_event.clone());
+
+    assert_eq!(
+        kube_event,
+        Event::unmarshal(event.marshal())
+            .unwrap()
+            .into_kube()
+    );
+}
@@ -18,6 +18,7 @@ pub mod empty_dir_volume_source;
 pub mod env_var;
 pub mod env_var_source;
 pub mod error;
+pub mod event;
 pub mod exec_action;
 pub mod host_path_volume_source;
 pub mod key_to_path;
@@ -25,6 +26,7 @@ pub mod label_selector;
 pub mod lifecycle;
 pub mod lifecycle_handler;
 pub mod local_object_reference;
+pub mod node;
 pub mod object_field_selector;
 pub mod object_meta;
 pub mod owner_reference;
@@ -33,6 +35,7 @@ pub mod persistent_volume_claim_spec;
 pub mod pod;
 pub mod pod_security_context;
 pub mod pod_spec;
+pub mod pod_status;
 pub mod pod_template_spec;
 pub mod policy_rule;
 pub mod probe;
@@ -0,0 +1,77 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::exec::node::*;
+use crate::kubernetes_api_objects::exec::object_meta::*;
+use crate::kubernetes_api_objects::exec::resource::*;
+use vstd::prelude::*;
+
+#[test]
+pub fn test_default() {
+    let node = Node::default();
+    assert_eq!(node.into_kube(), k8s_openapi::api::core::v1::Node::default());
+}
+
+#[test]
+pub fn test_set_metadata() {
+    let mut node = Node::default();
+    let mut metadata = ObjectMeta::default();
+    metadata.set_name("name".to_string());
+    node.set_metadata(metadata.clone());
+    assert_eq!(metadata.into_kube(), node.into_kube().metadata);
+}
+
+#[test]
+pub fn test_set_spec() {
+    let mut node = Node::default();
+    assert_eq!(None, node.spec().map(|s| s.into_kube()));
+    let mut node_spec = NodeSpec::default();
+    node_spec.set_unschedulable(true);
+    node.set_spec(node_spec.clone());
+    assert_eq!(node_spec.into_kube(), node.spec().unwrap().into_kube());
+    assert_eq!(Some(true), node.into_kube().spec.unwrap().unschedulable);
+}
+
+#[test]
+pub fn test_api_resource() {
+    let api_resource = Node::api_resource();
+    assert_eq!(api_resource.into_kube().kind, "Node");
+}
+
+#[test]
+pub fn test_kube() {
+    let kube_node = k8s_openapi::api::core::v1::Node {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some("name".to_string()),
+            ..Default::default()
+        },
+        spec: Some(k8s_openapi::api::core::v1::NodeSpec {
+            unschedulable: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let node = Node::from_kube(kube_node.clone());
+
+    assert_eq!(node.into_kube(), kube_node);
+}
+
+#[test]
+pub fn test_marshal() {
+    let kube_node = k8s_openapi::api::core::v1::Node {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some("name".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let node = Node::from_kube(kube_node.clone());
+
+    assert_eq!(
+        kube_node,
+        Node::unmarshal(node.marshal())
+            .unwrap()
+            .into_kube()
+    );
+}
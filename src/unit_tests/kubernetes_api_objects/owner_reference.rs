@@ -22,3 +22,20 @@ pub fn test_kube() {
     let owner_reference = OwnerReference::from_kube(kube_owner_reference.clone());
     assert_eq!(owner_reference.into_kube(), kube_owner_reference);
 }
+
+#[test]
+pub fn test_controller_and_block_owner_deletion() {
+    let owner_reference = OwnerReference::from_kube(
+        k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference {
+            api_version: "api_version".to_string(),
+            kind: "kind".to_string(),
+            name: "name".to_string(),
+            uid: "uid".to_string(),
+            controller: Some(true),
+            block_owner_deletion: Some(false),
+        },
+    );
+    assert_eq!(owner_reference.controller(), Some(true));
+    assert_eq!(owner_reference.block_owner_deletion(), Some(false));
+    assert_eq!(OwnerReference::default().block_owner_deletion(), None);
+}
@@ -59,6 +59,22 @@ pub fn test_set_spec() {
     assert_eq!(pod_spec.into_kube(), pod.into_kube().spec.unwrap());
 }
 
+#[test]
+pub fn test_status() {
+    let pod = Pod::default();
+    assert!(pod.status().is_none());
+
+    let kube_status = k8s_openapi::api::core::v1::PodStatus {
+        phase: Some("Running".to_string()),
+        ..Default::default()
+    };
+    let pod = Pod::from_kube(k8s_openapi::api::core::v1::Pod {
+        status: Some(kube_status.clone()),
+        ..Default::default()
+    });
+    assert_eq!(kube_status, pod.status().unwrap().into_kube());
+}
+
 #[test]
 pub fn test_api_resource() {
     let api_resource = Pod::api_resource();
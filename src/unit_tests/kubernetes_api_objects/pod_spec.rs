@@ -218,6 +218,19 @@ pub fn test_set_termination_grace_period_seconds() {
     );
 }
 
+#[test]
+pub fn test_node_name() {
+    let mut pod_spec = PodSpec::default();
+    assert_eq!(None, pod_spec.node_name());
+    pod_spec = PodSpec::from_kube(k8s_openapi::api::core::v1::PodSpec {
+        node_name: Some("node".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(Some("node".to_string()), pod_spec.node_name());
+    pod_spec.unset_node_name();
+    assert_eq!(None, pod_spec.into_kube().node_name);
+}
+
 #[test]
 pub fn test_kube() {
     let kube_pod_spec =
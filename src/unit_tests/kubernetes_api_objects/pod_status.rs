@@ -0,0 +1,32 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::exec::pod::*;
+use crate::kubernetes_api_objects::exec::resource::*;
+use vstd::prelude::*;
+
+#[test]
+pub fn test_kube() {
+    let kube_pod_status = k8s_openapi::api::core::v1::PodStatus {
+        phase: Some("Running".to_string()),
+        conditions: Some(vec![k8s_openapi::api::core::v1::PodCondition {
+            type_: "Ready".to_string(),
+            status: "True".to_string(),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    };
+    let pod_status = PodStatus::from_kube(kube_pod_status.clone());
+    assert_eq!(pod_status.into_kube(), kube_pod_status);
+}
+
+#[test]
+pub fn test_phase() {
+    let pod_status = PodStatus::from_kube(k8s_openapi::api::core::v1::PodStatus::default());
+    assert_eq!(None, pod_status.phase());
+
+    let pod_status = PodStatus::from_kube(k8s_openapi::api::core::v1::PodStatus {
+        phase: Some("Failed".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(Some("Failed".to_string()), pod_status.phase());
+}
@@ -1,4 +1,6 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
+pub mod external_shim_layer;
 pub mod kubernetes_api_objects;
+pub mod shim_layer;
 pub mod vstd_ext;
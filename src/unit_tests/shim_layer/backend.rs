@@ -0,0 +1,185 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds;
+use crate::external_shim_layer::VoidExternalShimLayer;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, api_resource::*, config_map::ConfigMap as ConfigMapWrapper, resource::*,
+};
+use crate::shim_layer::backend::*;
+use crate::shim_layer::controller_runtime::reconcile_with_backend;
+use crate::shim_layer::in_memory_backend::*;
+use crate::vreplicaset_controller::exec::reconciler::VReplicaSetReconciler;
+use k8s_openapi::api::core::v1::{ConfigMap, Namespace, Pod};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn get_config_map(namespace: &str) -> KubeAPIRequest {
+    KubeAPIRequest::GetRequest(KubeGetRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        name: "cm".to_string(),
+        namespace: namespace.to_string(),
+        read_mode: KubeReadMode::Quorum,
+    })
+}
+
+#[tokio::test]
+pub async fn test_namespace_scoped_backend_rejects_other_namespaces() {
+    let backend = InMemoryAPIServerBackend::new();
+    let cm: ConfigMap = serde_json::from_value(json!({"metadata": {"name": "cm"}})).unwrap();
+    backend.create("team-a", &cm).unwrap();
+    backend.create("team-c", &cm).unwrap();
+    let namespaces = vec!["team-a".to_string(), "team-b".to_string()];
+    let scoped = NamespaceScopedBackend {
+        inner: &backend,
+        namespaces: &namespaces,
+    };
+    assert!(matches!(
+        scoped.handle_request(get_config_map("team-a"), "test").await,
+        KubeAPIResponse::GetResponse(KubeGetResponse { res: Ok(_) })
+    ));
+    assert!(matches!(
+        scoped.handle_request(get_config_map("team-b"), "test").await,
+        KubeAPIResponse::GetResponse(KubeGetResponse { res: Err(APIError::ObjectNotFound) })
+    ));
+    assert!(matches!(
+        scoped.handle_request(get_config_map("team-c"), "test").await,
+        KubeAPIResponse::GetResponse(KubeGetResponse { res: Err(APIError::Forbidden) })
+    ));
+    assert!(matches!(
+        scoped.get_cr::<ConfigMap>("team-c", "cm").await,
+        Err(APIError::Forbidden)
+    ));
+}
+
+#[tokio::test]
+pub async fn test_namespace_scoped_backend_allows_cluster_scope() {
+    let backend = InMemoryAPIServerBackend::new();
+    let ns: Namespace = serde_json::from_value(json!({"metadata": {"name": "team-a"}})).unwrap();
+    backend.create("", &ns).unwrap();
+    let cm: ConfigMap = serde_json::from_value(json!({"metadata": {"name": "cm"}})).unwrap();
+    backend.create("team-c", &cm).unwrap();
+    let namespaces = vec!["team-a".to_string()];
+    let scoped = NamespaceScopedBackend {
+        inner: &backend,
+        namespaces: &namespaces,
+    };
+    let list = |api_resource: ApiResource| KubeAPIRequest::ListRequest(KubeListRequest {
+        api_resource: api_resource,
+        namespace: "".to_string(),
+        label_selector: None,
+        field_selector: None,
+        read_mode: KubeReadMode::Quorum,
+        limit: None,
+        continue_token: None,
+    });
+    // Cluster-scoped objects are reachable through the empty namespace
+    match scoped.handle_request(list(ApiResource::from_kube(kube::api::ApiResource::erase::<Namespace>(&()))), "test").await {
+        KubeAPIResponse::ListResponse(KubeListResponse { res: Ok(objs), .. }) => assert_eq!(objs.len(), 1),
+        _ => panic!("unexpected response"),
+    }
+    assert!(matches!(
+        scoped.get_cr::<Namespace>("", "team-a").await,
+        Ok(_)
+    ));
+    // A namespaced kind in the empty namespace would reach all the namespaces (including the ones not allowed),
+    // so the request is rejected by the scope of the kind before it reaches the API server
+    let inner = CountingBackend { received: Mutex::new(0) };
+    let scoped = NamespaceScopedBackend {
+        inner: &inner,
+        namespaces: &namespaces,
+    };
+    assert!(matches!(
+        scoped.handle_request(list(ConfigMapWrapper::api_resource()), "test").await,
+        KubeAPIResponse::ListResponse(KubeListResponse { res: Err(APIError::Forbidden), .. })
+    ));
+    assert!(matches!(
+        scoped.handle_request(get_config_map(""), "test").await,
+        KubeAPIResponse::GetResponse(KubeGetResponse { res: Err(APIError::Forbidden) })
+    ));
+    assert!(matches!(
+        scoped.get_cr::<ConfigMap>("", "cm").await,
+        Err(APIError::Forbidden)
+    ));
+    assert_eq!(*inner.received.lock().unwrap(), 0);
+}
+
+#[test]
+pub fn test_is_cluster_scoped_kind() {
+    assert!(is_cluster_scoped_kind(&kube::api::ApiResource::erase::<Namespace>(&())));
+    assert!(!is_cluster_scoped_kind(ConfigMapWrapper::api_resource().as_kube_ref()));
+    assert!(!is_cluster_scoped_kind(&kube::api::ApiResource::erase::<Pod>(&())));
+    assert!(!is_cluster_scoped_kind(&kube::api::ApiResource::erase::<crds::VReplicaSet>(&())));
+}
+
+// CountingBackend counts the requests that reach it.
+struct CountingBackend {
+    received: Mutex<usize>,
+}
+
+impl APIServerBackend for CountingBackend {
+    async fn get_cr<K>(&self, _namespace: &str, _name: &str) -> Result<K, APIError>
+    where
+        K: Clone + kube::Resource + serde::de::DeserializeOwned + std::fmt::Debug + Send,
+        K::Scope: crate::shim_layer::controller_runtime::ApiScope,
+        K::DynamicType: Default,
+    {
+        *self.received.lock().unwrap() += 1;
+        Err(APIError::ObjectNotFound)
+    }
+
+    async fn handle_request(&self, req: KubeAPIRequest, _log_header: &str) -> KubeAPIResponse {
+        *self.received.lock().unwrap() += 1;
+        error_response(&req, APIError::Other)
+    }
+}
+
+#[tokio::test]
+pub async fn test_reconcile_outside_allowed_namespaces() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs: crds::VReplicaSet = serde_json::from_value(json!({
+        "metadata": {"name": "test"},
+        "spec": {
+            "replicas": 1,
+            "selector": {"matchLabels": {"app": "test"}},
+            "template": {
+                "metadata": {"labels": {"app": "test"}},
+                "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+            },
+        },
+    }))
+    .unwrap();
+    let vrs = backend.create("team-c", &vrs).unwrap();
+    let namespaces = vec!["team-a".to_string()];
+    let scoped = NamespaceScopedBackend {
+        inner: &backend,
+        namespaces: &namespaces,
+    };
+    reconcile_with_backend::<crds::VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vrs), &scoped, Duration::from_secs(60), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+    assert_eq!(backend.list::<Pod>("team-c").len(), 0);
+}
+
+#[test]
+pub fn test_list_pagination() {
+    let list_config_maps = |read_mode: KubeReadMode, limit: Option<u32>, continue_token: Option<&str>| KubeListRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        namespace: "default".to_string(),
+        label_selector: None,
+        field_selector: None,
+        read_mode: read_mode,
+        limit: limit,
+        continue_token: continue_token.map(|token| token.to_string()),
+    };
+    assert_eq!(list_pagination(&list_config_maps(KubeReadMode::Quorum, Some(10), Some("token"))).unwrap(), (Some(10), Some("token".to_string())));
+    // A limit of zero means no limit
+    assert_eq!(list_pagination(&list_config_maps(KubeReadMode::Quorum, Some(0), None)).unwrap(), (None, None));
+    // A Cached list is served in one page
+    assert_eq!(list_pagination(&list_config_maps(KubeReadMode::Cached, Some(10), None)).unwrap(), (None, None));
+    assert!(matches!(list_pagination(&list_config_maps(KubeReadMode::Cached, None, Some("token"))), Err(APIError::BadRequest)));
+}
@@ -0,0 +1,152 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::controller_config::*;
+use crate::shim_layer::fault_injection::FaultPlanSource;
+use std::time::Duration;
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+pub fn test_from_args() {
+    let config = ControllerConfig::from_args(
+        "test-controller",
+        &args(&[
+            "controller",
+            "run",
+            "--requeue-interval-secs",
+            "30",
+            "--backoff-base-secs",
+            "2",
+            "--backoff-cap-secs",
+            "60",
+            "--backoff-jitter",
+            "0.5",
+            "--external-call-timeout-secs",
+            "5",
+            "--metrics-addr",
+            "127.0.0.1:8080",
+            "--trace-file",
+            "/tmp/trace.jsonl",
+        ]),
+    )
+    .unwrap();
+    assert_eq!(config.fault_injection, false);
+    assert!(config.leader_election.is_none());
+    assert_eq!(config.metrics_addr, Some("127.0.0.1:8080".parse().unwrap()));
+    assert_eq!(config.requeue_interval, Duration::from_secs(30));
+    assert_eq!(config.backoff_base, Duration::from_secs(2));
+    assert_eq!(config.backoff_cap, Duration::from_secs(60));
+    assert_eq!(config.backoff_jitter, 0.5);
+    assert_eq!(config.external_call_timeout, Duration::from_secs(5));
+    assert_eq!(config.trace_file, Some(std::path::PathBuf::from("/tmp/trace.jsonl")));
+
+    let config = ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--leader-elect"])).unwrap();
+    assert_eq!(config.leader_election.unwrap().lease_name, "test-controller");
+    assert_eq!(config.requeue_interval, ControllerConfig::default().requeue_interval);
+    assert!(config.namespaces.is_none());
+
+    let config = ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--namespaces", "team-a, team-b"])).unwrap();
+    assert_eq!(config.namespaces, Some(vec!["team-a".to_string(), "team-b".to_string()]));
+    assert!(config.fault_plan.is_none());
+
+    let config = ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--fault-plan", "/tmp/plan.yaml"])).unwrap();
+    assert_eq!(config.fault_plan, Some(FaultPlanSource::File(std::path::PathBuf::from("/tmp/plan.yaml"))));
+
+    let config = ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--fault-plan-config-map", "default/fault-plan"])).unwrap();
+    assert_eq!(
+        config.fault_plan,
+        Some(FaultPlanSource::ConfigMap {
+            namespace: "default".to_string(),
+            name: "fault-plan".to_string(),
+        })
+    );
+}
+
+#[test]
+pub fn test_from_args_rejects_invalid_values() {
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--requeue-interval-secs", "soon"])).is_err());
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--backoff-jitter", "-1"])).is_err());
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--backoff-jitter", "inf"])).is_err());
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--backoff-jitter", "NaN"])).is_err());
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--backoff-jitter", "1e300"])).is_err());
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--backoff-cap-secs", "18446744073709551615"])).is_err());
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--metrics-addr", "nowhere"])).is_err());
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--namespaces", "team-a,,team-b"])).is_err());
+    assert!(ControllerConfig::from_args("test-controller", &args(&["controller", "run", "--fault-plan-config-map", "fault-plan"])).is_err());
+}
+
+#[test]
+pub fn test_backoff_doubles_up_to_cap() {
+    let backoff = FailureBackoff::new(&ControllerConfig {
+        backoff_base: Duration::from_secs(10),
+        backoff_cap: Duration::from_secs(60),
+        backoff_jitter: 0.0,
+        ..ControllerConfig::default()
+    });
+    assert_eq!(backoff.on_failure("a"), Duration::from_secs(10));
+    assert_eq!(backoff.on_failure("a"), Duration::from_secs(20));
+    assert_eq!(backoff.on_failure("a"), Duration::from_secs(40));
+    assert_eq!(backoff.on_failure("a"), Duration::from_secs(60));
+    assert_eq!(backoff.on_failure("a"), Duration::from_secs(60));
+    assert_eq!(backoff.failures("a"), 5);
+    // Each custom resource has its own backoff
+    assert_eq!(backoff.on_failure("b"), Duration::from_secs(10));
+    assert_eq!(backoff.delay_without_jitter(100), Duration::from_secs(60));
+}
+
+#[test]
+pub fn test_backoff_resets_on_success() {
+    let backoff = FailureBackoff::new(&ControllerConfig {
+        backoff_jitter: 0.0,
+        ..ControllerConfig::default()
+    });
+    backoff.on_failure("a");
+    backoff.on_failure("a");
+    backoff.on_success("a");
+    assert_eq!(backoff.failures("a"), 0);
+    assert_eq!(backoff.on_failure("a"), ControllerConfig::default().backoff_base);
+}
+
+#[test]
+pub fn test_backoff_jitter_is_bounded() {
+    let backoff = FailureBackoff::new(&ControllerConfig {
+        backoff_base: Duration::from_secs(10),
+        backoff_jitter: 0.5,
+        ..ControllerConfig::default()
+    });
+    for _ in 0..100 {
+        backoff.on_success("a");
+        let delay = backoff.on_failure("a");
+        assert!(delay >= Duration::from_secs(10));
+        assert!(delay <= Duration::from_secs(15));
+    }
+}
+
+#[test]
+pub fn test_backoff_jitter_out_of_range_is_clamped() {
+    for jitter in [f64::INFINITY, f64::NAN, 1e300, -1.0] {
+        let backoff = FailureBackoff::new(&ControllerConfig {
+            backoff_base: Duration::from_secs(10),
+            backoff_jitter: jitter,
+            ..ControllerConfig::default()
+        });
+        let delay = backoff.on_failure("a");
+        assert!(delay >= Duration::from_secs(10));
+        assert!(delay <= Duration::from_secs(20));
+    }
+}
+
+#[test]
+pub fn test_backoff_with_huge_cap_does_not_overflow() {
+    let backoff = FailureBackoff::new(&ControllerConfig {
+        backoff_base: Duration::MAX,
+        backoff_cap: Duration::MAX,
+        backoff_jitter: 1.0,
+        ..ControllerConfig::default()
+    });
+    for _ in 0..40 {
+        assert!(backoff.on_failure("a") <= MAX_BACKOFF_CAP * 2);
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::api_method::*;
+use crate::shim_layer::backend::APIServerBackend;
+use crate::shim_layer::controller_runtime::{finalizer_update_request, is_namespaced};
+use crate::shim_layer::in_memory_backend::*;
+use crate::shim_layer::metrics::response_error;
+use k8s_openapi::api::core::v1::{ConfigMap, Namespace};
+use serde_json::json;
+
+const FINALIZER: &str = "anvil.dev/cleanup";
+
+fn vreplicaset() -> crds::VReplicaSet {
+    serde_json::from_value(json!({
+        "metadata": {"name": "test"},
+        "spec": {
+            "replicas": 1,
+            "selector": {"matchLabels": {"app": "test"}},
+            "template": {
+                "metadata": {"labels": {"app": "test"}},
+                "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+            },
+        },
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+pub async fn test_finalizer_is_added_and_removed() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs = backend.create("default", &vreplicaset()).unwrap();
+    assert!(finalizer_update_request(&vrs, FINALIZER, false).is_none());
+
+    let req = finalizer_update_request(&vrs, FINALIZER, true).unwrap();
+    assert!(response_error(&backend.handle_request(req, "test").await).is_none());
+    let vrs = backend.get::<crds::VReplicaSet>("default", "test").unwrap();
+    assert_eq!(vrs.metadata.finalizers, Some(vec![FINALIZER.to_string()]));
+    assert!(finalizer_update_request(&vrs, FINALIZER, true).is_none());
+
+    // The finalizer keeps the custom resource until it is removed
+    backend.delete::<crds::VReplicaSet>("default", "test").unwrap();
+    let vrs = backend.get::<crds::VReplicaSet>("default", "test").unwrap();
+    assert!(vrs.metadata.deletion_timestamp.is_some());
+    assert!(finalizer_update_request(&vrs, FINALIZER, true).is_none());
+
+    let req = finalizer_update_request(&vrs, FINALIZER, false).unwrap();
+    assert!(response_error(&backend.handle_request(req, "test").await).is_none());
+    assert!(matches!(backend.get::<crds::VReplicaSet>("default", "test"), Err(APIError::ObjectNotFound)));
+}
+
+#[tokio::test]
+pub async fn test_finalizer_removal_needs_a_fresh_read() {
+    let backend = InMemoryAPIServerBackend::new();
+    let mut vrs = vreplicaset();
+    vrs.metadata.finalizers = Some(vec![FINALIZER.to_string(), "other".to_string()]);
+    backend.create("default", &vrs).unwrap();
+    backend.delete::<crds::VReplicaSet>("default", "test").unwrap();
+    let stale = backend.get::<crds::VReplicaSet>("default", "test").unwrap();
+
+    // The custom resource is updated during the cleanup, here by another controller removing its own finalizer
+    let req = finalizer_update_request(&stale, "other", false).unwrap();
+    assert!(response_error(&backend.handle_request(req, "test").await).is_none());
+
+    let req = finalizer_update_request(&stale, FINALIZER, false).unwrap();
+    assert!(matches!(response_error(&backend.handle_request(req, "test").await), Some(APIError::Conflict)));
+    let fresh = backend.get::<crds::VReplicaSet>("default", "test").unwrap();
+    let req = finalizer_update_request(&fresh, FINALIZER, false).unwrap();
+    assert!(response_error(&backend.handle_request(req, "test").await).is_none());
+    assert!(matches!(backend.get::<crds::VReplicaSet>("default", "test"), Err(APIError::ObjectNotFound)));
+}
+
+#[test]
+pub fn test_finalizer_update_keeps_other_finalizers() {
+    let mut vrs = vreplicaset();
+    vrs.metadata.namespace = Some("default".to_string());
+    vrs.metadata.finalizers = Some(vec!["other".to_string()]);
+    match finalizer_update_request(&vrs, FINALIZER, true).unwrap() {
+        KubeAPIRequest::UpdateRequest(req) => {
+            assert_eq!(req.name, "test");
+            assert_eq!(req.namespace, "default");
+            assert_eq!(
+                req.obj.into_kube().metadata.finalizers,
+                Some(vec!["other".to_string(), FINALIZER.to_string()])
+            );
+        }
+        _ => panic!("expected an update request"),
+    }
+}
+
+#[test]
+pub fn test_is_namespaced() {
+    assert!(is_namespaced::<crds::VReplicaSet>());
+    assert!(is_namespaced::<ConfigMap>());
+    assert!(!is_namespaced::<Namespace>());
+}
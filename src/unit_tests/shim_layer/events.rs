@@ -0,0 +1,179 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds;
+use crate::external_shim_layer::VoidExternalShimLayer;
+use crate::kubernetes_api_objects::exec::{api_method::*, config_map::ConfigMap as ConfigMapWrapper, resource::*};
+use crate::shim_layer::backend::{APIServerBackend, NamespaceScopedBackend};
+use crate::shim_layer::controller_runtime::reconcile_with_backend;
+use crate::shim_layer::events::*;
+use crate::shim_layer::in_memory_backend::*;
+use crate::vreplicaset_controller::exec::reconciler::VReplicaSetReconciler;
+use k8s_openapi::api::core::v1::{ConfigMap, Event, Namespace, Pod};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn vreplicaset(replicas: i32) -> crds::VReplicaSet {
+    serde_json::from_value(json!({
+        "metadata": {"name": "test"},
+        "spec": {
+            "replicas": replicas,
+            "selector": {"matchLabels": {"app": "test"}},
+            "template": {
+                "metadata": {"labels": {"app": "test"}},
+                "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+            },
+        },
+    }))
+    .unwrap()
+}
+
+fn no_notable_event(_verb: &str, _kind: &str, _name: &str) -> Option<NotableEvent> {
+    None
+}
+
+#[tokio::test]
+pub async fn test_notable_requests_are_recorded_as_normal_events() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs = backend.create("default", &vreplicaset(2)).unwrap();
+    let correlator = EventCorrelator::new();
+    let recorder = EventRecorder::new(&vrs, VReplicaSetReconciler::notable_event, &correlator);
+    let event_backend = EventBackend {
+        inner: &backend,
+        recorder: &recorder,
+    };
+    reconcile_with_backend::<crds::VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vrs.clone()), &event_backend, Duration::from_secs(60), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+
+    let pods: Vec<String> = backend.list::<Pod>("default").into_iter().map(|pod| pod.metadata.name.unwrap()).collect();
+    let events = backend.list::<Event>("default");
+    assert_eq!(pods.len(), 2);
+    assert_eq!(events.len(), 2);
+    for event in events {
+        assert_eq!(event.type_.as_deref(), Some(NORMAL_EVENT));
+        assert_eq!(event.reason.as_deref(), Some("SuccessfulCreate"));
+        assert_eq!(event.involved_object.kind.as_deref(), Some("VReplicaSet"));
+        assert_eq!(event.involved_object.name.as_deref(), Some("test"));
+        assert_eq!(event.involved_object.uid, vrs.metadata.uid);
+        let message = event.message.unwrap();
+        assert!(pods.iter().any(|pod| message == format!("Created Pod: {}", pod)));
+    }
+}
+
+#[tokio::test]
+pub async fn test_failed_requests_are_recorded_as_warning_events() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs = backend.create("default", &vreplicaset(1)).unwrap();
+    let correlator = EventCorrelator::new();
+    let recorder = EventRecorder::new(&vrs, no_notable_event, &correlator);
+    let event_backend = EventBackend {
+        inner: &backend,
+        recorder: &recorder,
+    };
+    // A config map without name or generate_name is rejected by the API server
+    let cm: ConfigMap = serde_json::from_value(json!({"metadata": {}})).unwrap();
+    let req = KubeAPIRequest::CreateRequest(KubeCreateRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        namespace: "default".to_string(),
+        obj: ConfigMapWrapper::from_kube(cm).marshal(),
+    });
+    event_backend.handle_request(req, "test").await;
+
+    let events = backend.list::<Event>("default");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].type_.as_deref(), Some(WARNING_EVENT));
+    assert_eq!(events[0].reason.as_deref(), Some("FailedRequest"));
+    assert_eq!(events[0].message.as_deref(), Some("Create ConfigMap/default/ failed with error: Invalid"));
+    assert!(events[0].metadata.name.as_ref().unwrap().starts_with("test."));
+}
+
+#[tokio::test]
+pub async fn test_reads_are_not_recorded() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs = backend.create("default", &vreplicaset(1)).unwrap();
+    let correlator = EventCorrelator::new();
+    let recorder = EventRecorder::new(&vrs, no_notable_event, &correlator);
+    let event_backend = EventBackend {
+        inner: &backend,
+        recorder: &recorder,
+    };
+    let req = KubeAPIRequest::GetRequest(KubeGetRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        name: "missing".to_string(),
+        namespace: "default".to_string(),
+        read_mode: KubeReadMode::Quorum,
+    });
+    event_backend.handle_request(req, "test").await;
+    assert_eq!(backend.list::<Event>("default").len(), 0);
+}
+
+#[tokio::test]
+pub async fn test_repeated_events_are_aggregated() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs = backend.create("default", &vreplicaset(1)).unwrap();
+    let correlator = EventCorrelator::new();
+    // The recorders of two reconciles of the same custom resource share the correlator
+    for message in ["first failure", "second failure"] {
+        let recorder = EventRecorder::new(&vrs, no_notable_event, &correlator);
+        recorder.record(&backend, WARNING_EVENT, "ReconcileFailed", message, "test").await;
+    }
+    let recorder = EventRecorder::new(&vrs, no_notable_event, &correlator);
+    recorder.record(&backend, WARNING_EVENT, "FailedRequest", "other failure", "test").await;
+
+    let events = backend.list::<Event>("default");
+    assert_eq!(events.len(), 2);
+    let aggregated = events.iter().find(|event| event.reason.as_deref() == Some("ReconcileFailed")).unwrap();
+    assert_eq!(aggregated.count, Some(2));
+    assert_eq!(aggregated.message.as_deref(), Some("second failure"));
+    assert!(aggregated.last_timestamp >= aggregated.first_timestamp);
+    let other = events.iter().find(|event| event.reason.as_deref() == Some("FailedRequest")).unwrap();
+    assert_eq!(other.count, Some(1));
+}
+
+#[tokio::test]
+pub async fn test_events_are_rate_limited() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs = backend.create("default", &vreplicaset(1)).unwrap();
+    let correlator = EventCorrelator::with_rate_limit(2, Duration::from_secs(3600));
+    let recorder = EventRecorder::new(&vrs, no_notable_event, &correlator);
+    for reason in ["First", "Second", "Third"] {
+        recorder.record(&backend, WARNING_EVENT, reason, "failure", "test").await;
+    }
+    let reasons: Vec<String> = backend.list::<Event>("default").into_iter().map(|event| event.reason.unwrap()).collect();
+    assert_eq!(reasons.len(), 2);
+    assert!(!reasons.contains(&"Third".to_string()));
+}
+
+#[tokio::test]
+pub async fn test_events_of_cluster_scoped_cr_are_in_default_namespace() {
+    let backend = InMemoryAPIServerBackend::new();
+    let ns: Namespace = serde_json::from_value(json!({"metadata": {"name": "team-a", "uid": "team-a-uid"}})).unwrap();
+    let correlator = EventCorrelator::new();
+    let recorder = EventRecorder::new(&ns, no_notable_event, &correlator);
+    recorder.record(&backend, NORMAL_EVENT, "Synced", "synced", "test").await;
+
+    let events = backend.list::<Event>("default");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].involved_object.kind.as_deref(), Some("Namespace"));
+    assert!(events[0].involved_object.namespace.is_none());
+}
+
+#[tokio::test]
+pub async fn test_events_through_namespace_scoped_backend_stay_in_allowed_namespaces() {
+    let backend = InMemoryAPIServerBackend::new();
+    let ns: Namespace = serde_json::from_value(json!({"metadata": {"name": "team-a", "uid": "team-a-uid"}})).unwrap();
+    let correlator = EventCorrelator::new();
+    let recorder = EventRecorder::new(&ns, no_notable_event, &correlator);
+    let namespaces = vec!["team-a".to_string()];
+    let scoped_backend = NamespaceScopedBackend {
+        inner: &backend,
+        namespaces: &namespaces,
+    };
+    // The events of a cluster-scoped custom resource go to the default namespace, which is not allowed
+    recorder.record(&scoped_backend, WARNING_EVENT, "ReconcileFailed", "failure", "test").await;
+    assert!(backend.list::<Event>("default").is_empty());
+}
@@ -0,0 +1,175 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, config_map::ConfigMap as ConfigMapWrapper, dynamic::DynamicObject, resource::*,
+};
+use crate::shim_layer::fault_injection::*;
+use crate::shim_layer::in_memory_backend::*;
+use k8s_openapi::api::core::v1::ConfigMap;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn fault(verb: Option<&str>, nth: Option<u64>, probability: Option<f64>, action: FaultAction) -> Fault {
+    Fault {
+        verb: verb.map(|verb| verb.to_string()),
+        kind: Some("ConfigMap".to_string()),
+        reconcile_step: None,
+        cr_key: None,
+        nth: nth,
+        probability: probability,
+        action: action,
+    }
+}
+
+fn injector(faults: Vec<Fault>) -> FaultInjector {
+    FaultInjector::new(FaultPlan { seed: 0, faults: faults })
+}
+
+async fn create_config_map(injector: &FaultInjector, backend: &InMemoryAPIServerBackend, name: &str) -> Result<(), APIError> {
+    let req = KubeAPIRequest::CreateRequest(KubeCreateRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        namespace: "default".to_string(),
+        obj: DynamicObject::from_kube(serde_json::from_value(json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": {"name": name},
+            "data": {"key": "value"},
+        })).unwrap()),
+    });
+    match injector.handle_request(backend, req, "VReplicaSet/default/test", "Init", "test").await {
+        KubeAPIResponse::CreateResponse(resp) => resp.res.map(|_obj| ()),
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[tokio::test]
+pub async fn test_return_error_does_not_send_request() {
+    let backend = InMemoryAPIServerBackend::new();
+    let injector = injector(vec![fault(Some("Create"), None, None, FaultAction::ReturnError { error: InjectedError::Conflict })]);
+    assert!(matches!(create_config_map(&injector, &backend, "cm").await, Err(APIError::Conflict)));
+    assert!(matches!(backend.get::<ConfigMap>("default", "cm"), Err(APIError::ObjectNotFound)));
+}
+
+#[tokio::test]
+pub async fn test_duplicate_returns_response_to_first_request() {
+    let backend = InMemoryAPIServerBackend::new();
+    let injector = injector(vec![fault(Some("Create"), None, None, FaultAction::Duplicate)]);
+    // The duplicate fails with ObjectAlreadyExists, which the controller never sees
+    assert!(create_config_map(&injector, &backend, "cm").await.is_ok());
+    assert!(backend.get::<ConfigMap>("default", "cm").is_ok());
+}
+
+#[tokio::test]
+pub async fn test_drop_response_sends_request() {
+    let backend = InMemoryAPIServerBackend::new();
+    let injector = injector(vec![fault(Some("Create"), None, None, FaultAction::DropResponse)]);
+    // The controller sees a Timeout even though the object has been created
+    assert!(matches!(create_config_map(&injector, &backend, "cm").await, Err(APIError::Timeout)));
+    assert!(backend.get::<ConfigMap>("default", "cm").is_ok());
+}
+
+#[tokio::test]
+pub async fn test_nth_fault_fires_at_nth_selected_request() {
+    let backend = InMemoryAPIServerBackend::new();
+    let injector = injector(vec![fault(Some("Create"), Some(2), None, FaultAction::ReturnError { error: InjectedError::Timeout })]);
+    assert!(create_config_map(&injector, &backend, "cm-1").await.is_ok());
+    assert!(matches!(create_config_map(&injector, &backend, "cm-2").await, Err(APIError::Timeout)));
+    assert!(create_config_map(&injector, &backend, "cm-3").await.is_ok());
+    assert_eq!(backend.list::<ConfigMap>("default").len(), 2);
+}
+
+#[test]
+pub fn test_faults_select_by_verb_kind_and_reconcile_step() {
+    let mut selective = fault(Some("Update"), None, None, FaultAction::CrashAfterSend);
+    selective.reconcile_step = Some("AfterListPods".to_string());
+    let injector = injector(vec![selective]);
+    let request = |verb: &'static str, kind: Option<&'static str>, reconcile_step: &'static str| RequestInfo {
+        cr_key: "VReplicaSet/default/test",
+        verb: verb,
+        kind: kind,
+        reconcile_step: reconcile_step,
+    };
+    assert_eq!(injector.select(&request("Update", Some("ConfigMap"), "AfterListPods")), vec![(0, FaultAction::CrashAfterSend)]);
+    assert!(injector.select(&request("Create", Some("ConfigMap"), "AfterListPods")).is_empty());
+    assert!(injector.select(&request("Update", Some("Pod"), "AfterListPods")).is_empty());
+    assert!(injector.select(&request("Update", Some("ConfigMap"), "Init")).is_empty());
+    assert!(injector.select(&request("External", None, "AfterListPods")).is_empty());
+}
+
+#[test]
+pub fn test_random_faults_are_deterministic_under_seed() {
+    let draws = |seed: u64| -> Vec<bool> {
+        let injector = FaultInjector::new(FaultPlan {
+            seed: seed,
+            faults: vec![fault(None, None, Some(0.5), FaultAction::Duplicate)],
+        });
+        (0..100)
+            .map(|_| {
+                !injector
+                    .select(&RequestInfo {
+                        cr_key: "VReplicaSet/default/test",
+                        verb: "Create",
+                        kind: Some("ConfigMap"),
+                        reconcile_step: "Init",
+                    })
+                    .is_empty()
+            })
+            .collect()
+    };
+    assert_eq!(draws(42), draws(42));
+    assert!(draws(42).contains(&true));
+    assert!(draws(42).contains(&false));
+}
+
+#[tokio::test]
+pub async fn test_return_error_to_external_request() {
+    let injector = FaultInjector::new(FaultPlan {
+        seed: 0,
+        faults: vec![Fault {
+            verb: Some("External".to_string()),
+            kind: None,
+            reconcile_step: None,
+            cr_key: None,
+            nth: None,
+            probability: None,
+            action: FaultAction::ReturnError { error: InjectedError::Timeout },
+        }],
+    });
+    let sent = AtomicBool::new(false);
+    let call = async {
+        sent.store(true, Ordering::SeqCst);
+        Ok(())
+    };
+    let resp = injector.handle_external_request(call, "VReplicaSet/default/test", "Init", "test").await;
+    assert!(matches!(resp, Err(ExternalError::Timeout)));
+    assert!(!sent.load(Ordering::SeqCst));
+}
+
+#[test]
+pub fn test_parse_fault_plan() {
+    let plan: FaultPlan = serde_yaml::from_str(
+        r#"
+seed: 7
+faults:
+- verb: Create
+  kind: Pod
+  reconcileStep: AfterListPods
+  nth: 2
+  action: {type: CrashAfterSend}
+- probability: 0.1
+  action: {type: ReturnError, error: InternalError}
+- action: {type: DelayResponse, millis: 100}
+"#,
+    )
+    .unwrap();
+    assert_eq!(plan.seed, 7);
+    assert_eq!(plan.faults.len(), 3);
+    assert_eq!(plan.faults[0].verb, Some("Create".to_string()));
+    assert_eq!(plan.faults[0].reconcile_step, Some("AfterListPods".to_string()));
+    assert_eq!(plan.faults[0].nth, Some(2));
+    assert_eq!(plan.faults[0].action, FaultAction::CrashAfterSend);
+    assert_eq!(plan.faults[1].action, FaultAction::ReturnError { error: InjectedError::InternalError });
+    assert_eq!(plan.faults[2].action, FaultAction::DelayResponse { millis: 100 });
+    assert!(plan.faults[2].verb.is_none());
+}
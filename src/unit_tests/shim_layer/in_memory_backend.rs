@@ -0,0 +1,666 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds;
+use crate::external_shim_layer::VoidExternalShimLayer;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, api_resource::ApiResource, config_map::ConfigMap as ConfigMapWrapper, dynamic::DynamicObject,
+    label_selector::*, owner_reference::OwnerReference, pod::Pod as PodWrapper, preconditions::Preconditions,
+    resource::*,
+};
+use crate::rabbitmq_controller::exec::reconciler::RabbitmqReconciler;
+use crate::shim_layer::backend::APIServerBackend;
+use crate::shim_layer::controller_runtime::reconcile_with_backend;
+use crate::shim_layer::in_memory_backend::*;
+use crate::vdeployment_controller::exec::reconciler::VDeploymentReconciler;
+use crate::vreplicaset_controller::exec::reconciler::VReplicaSetReconciler;
+use crate::vstatefulset_controller::exec::reconciler::VStatefulSetReconciler;
+use crate::vstd_ext::string_map::StringMap;
+use k8s_openapi::api::apps::v1::StatefulSet;
+use k8s_openapi::api::core::v1::{ConfigMap, Namespace, Pod};
+use serde_json::json;
+use kube::runtime::controller::Action;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn config_map(name: &str, finalizers: Vec<&str>) -> ConfigMap {
+    serde_json::from_value(json!({
+        "metadata": {"name": name, "finalizers": finalizers},
+        "data": {"key": "value"},
+    }))
+    .unwrap()
+}
+
+fn pod_template() -> serde_json::Value {
+    json!({
+        "metadata": {"labels": {"app": "test"}},
+        "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+    })
+}
+
+#[test]
+pub fn test_create_allocates_uid_and_resource_version() {
+    let backend = InMemoryAPIServerBackend::new();
+    let created = backend.create("default", &config_map("cm", vec![])).unwrap();
+    assert_eq!(created.metadata.namespace, Some("default".to_string()));
+    assert!(created.metadata.uid.is_some());
+    assert!(created.metadata.resource_version.is_some());
+    assert!(matches!(
+        backend.create("default", &config_map("cm", vec![])),
+        Err(APIError::ObjectAlreadyExists)
+    ));
+}
+
+#[test]
+pub fn test_create_with_generate_name() {
+    let backend = InMemoryAPIServerBackend::new();
+    let cm: ConfigMap = serde_json::from_value(json!({"metadata": {"generateName": "cm-"}})).unwrap();
+    let first = backend.create("default", &cm).unwrap();
+    let second = backend.create("default", &cm).unwrap();
+    assert!(first.metadata.name.as_ref().unwrap().starts_with("cm-"));
+    assert_ne!(first.metadata.name, second.metadata.name);
+    assert_eq!(backend.list::<ConfigMap>("default").len(), 2);
+    assert_eq!(backend.list::<ConfigMap>("other").len(), 0);
+}
+
+#[test]
+pub fn test_delete_with_finalizers_sets_deletion_timestamp() {
+    let backend = InMemoryAPIServerBackend::new();
+    backend.create("default", &config_map("cm", vec!["anvil.dev/finalizer"])).unwrap();
+    backend.delete::<ConfigMap>("default", "cm").unwrap();
+    let cm = backend.get::<ConfigMap>("default", "cm").unwrap();
+    assert!(cm.metadata.deletion_timestamp.is_some());
+    backend.create("default", &config_map("cm2", vec![])).unwrap();
+    backend.delete::<ConfigMap>("default", "cm2").unwrap();
+    assert!(matches!(
+        backend.get::<ConfigMap>("default", "cm2"),
+        Err(APIError::ObjectNotFound)
+    ));
+}
+
+async fn delete_config_map(backend: &InMemoryAPIServerBackend, name: &str, propagation_policy: Option<KubePropagationPolicy>) {
+    let req = KubeAPIRequest::DeleteRequest(KubeDeleteRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        name: name.to_string(),
+        namespace: "default".to_string(),
+        preconditions: None,
+        propagation_policy: propagation_policy,
+    });
+    match backend.handle_request(req, "test").await {
+        KubeAPIResponse::DeleteResponse(resp) => assert!(resp.res.is_ok()),
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[tokio::test]
+pub async fn test_delete_records_propagation_policy_as_finalizer() {
+    let backend = InMemoryAPIServerBackend::new();
+    backend.create("default", &config_map("cm", vec![])).unwrap();
+    delete_config_map(&backend, "cm", Some(KubePropagationPolicy::Foreground)).await;
+    let cm = backend.get::<ConfigMap>("default", "cm").unwrap();
+    assert!(cm.metadata.deletion_timestamp.is_some());
+    assert_eq!(cm.metadata.finalizers, Some(vec![FOREGROUND_DELETION_FINALIZER.to_string()]));
+
+    // Deleting again with another policy replaces the finalizer
+    delete_config_map(&backend, "cm", Some(KubePropagationPolicy::Orphan)).await;
+    let cm = backend.get::<ConfigMap>("default", "cm").unwrap();
+    assert_eq!(cm.metadata.finalizers, Some(vec![ORPHAN_FINALIZER.to_string()]));
+
+    // Without a policy the recorded one is kept
+    delete_config_map(&backend, "cm", None).await;
+    let cm2 = backend.get::<ConfigMap>("default", "cm").unwrap();
+    assert_eq!(cm2.metadata.resource_version, cm.metadata.resource_version);
+
+    // Background deletion removes the finalizer so the object is deleted right away
+    delete_config_map(&backend, "cm", Some(KubePropagationPolicy::Background)).await;
+    assert!(matches!(
+        backend.get::<ConfigMap>("default", "cm"),
+        Err(APIError::ObjectNotFound)
+    ));
+}
+
+fn pod(name: &str, app: &str) -> Pod {
+    serde_json::from_value(json!({
+        "metadata": {"name": name, "labels": {"app": app}},
+        "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+    }))
+    .unwrap()
+}
+
+async fn list_pods(backend: &InMemoryAPIServerBackend, label_selector: Option<LabelSelector>, field_selector: Option<StringMap>) -> Result<usize, APIError> {
+    let req = KubeAPIRequest::ListRequest(KubeListRequest {
+        api_resource: PodWrapper::api_resource(),
+        namespace: "default".to_string(),
+        label_selector: label_selector,
+        field_selector: field_selector,
+        read_mode: KubeReadMode::Quorum,
+        limit: None,
+        continue_token: None,
+    });
+    match backend.handle_request(req, "test").await {
+        KubeAPIResponse::ListResponse(resp) => resp.res.map(|objs| objs.len()),
+        _ => panic!("unexpected response"),
+    }
+}
+
+async fn list_pod_page(backend: &InMemoryAPIServerBackend, limit: Option<u32>, continue_token: Option<String>) -> Result<(Vec<String>, Option<String>), APIError> {
+    let req = KubeAPIRequest::ListRequest(KubeListRequest {
+        api_resource: PodWrapper::api_resource(),
+        namespace: "default".to_string(),
+        label_selector: None,
+        field_selector: None,
+        read_mode: KubeReadMode::Quorum,
+        limit: limit,
+        continue_token: continue_token,
+    });
+    match backend.handle_request(req, "test").await {
+        KubeAPIResponse::ListResponse(resp) => resp.res.map(|objs| {
+            (objs.into_iter().map(|obj| obj.into_kube().metadata.name.unwrap()).collect(), resp.continue_token)
+        }),
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[tokio::test]
+pub async fn test_paginated_list_is_served_from_snapshot() {
+    let backend = InMemoryAPIServerBackend::new();
+    backend.create("default", &pod("a", "web")).unwrap();
+    backend.create("default", &pod("b", "web")).unwrap();
+    backend.create("default", &pod("c", "web")).unwrap();
+    let (page, continue_token) = list_pod_page(&backend, Some(2), None).await.unwrap();
+    assert_eq!(page, vec!["a", "b"]);
+    assert!(continue_token.is_some());
+    // The changes after the first page are not visible to the later pages
+    backend.delete::<Pod>("default", "c").unwrap();
+    backend.create("default", &pod("d", "web")).unwrap();
+    let (page, continue_token) = list_pod_page(&backend, Some(2), continue_token).await.unwrap();
+    assert_eq!(page, vec!["c"]);
+    assert!(continue_token.is_none());
+    // An unpaginated list is served from the latest objects
+    let (page, continue_token) = list_pod_page(&backend, None, None).await.unwrap();
+    assert_eq!(page, vec!["a", "b", "d"]);
+    assert!(continue_token.is_none());
+}
+
+#[tokio::test]
+pub async fn test_paginated_list_expires_after_compaction() {
+    let backend = InMemoryAPIServerBackend::new();
+    backend.create("default", &pod("a", "web")).unwrap();
+    backend.create("default", &pod("b", "web")).unwrap();
+    let (_, continue_token) = list_pod_page(&backend, Some(1), None).await.unwrap();
+    backend.compact_list_snapshots();
+    assert!(matches!(list_pod_page(&backend, Some(1), continue_token).await, Err(APIError::Expired)));
+    assert!(matches!(list_pod_page(&backend, Some(1), Some("malformed".to_string())).await, Err(APIError::BadRequest)));
+}
+
+#[tokio::test]
+pub async fn test_list_with_selectors() {
+    let backend = InMemoryAPIServerBackend::new();
+    backend.create("default", &pod("a", "web")).unwrap();
+    backend.create("default", &pod("b", "web")).unwrap();
+    backend.create("default", &pod("c", "db")).unwrap();
+    assert_eq!(list_pods(&backend, None, None).await.unwrap(), 3);
+
+    let mut match_labels = StringMap::empty();
+    match_labels.insert("app".to_string(), "web".to_string());
+    let mut label_selector = LabelSelector::default();
+    label_selector.set_match_labels(match_labels);
+    assert_eq!(list_pods(&backend, Some(label_selector.clone()), None).await.unwrap(), 2);
+
+    let mut field_selector = StringMap::empty();
+    field_selector.insert("metadata.name".to_string(), "b".to_string());
+    assert_eq!(list_pods(&backend, Some(label_selector), Some(field_selector)).await.unwrap(), 1);
+
+    let mut unsupported_field_selector = StringMap::empty();
+    unsupported_field_selector.insert("spec.nodeName".to_string(), "node".to_string());
+    assert!(matches!(
+        list_pods(&backend, None, Some(unsupported_field_selector)).await,
+        Err(APIError::BadRequest)
+    ));
+}
+
+async fn patch_config_map(
+    backend: &InMemoryAPIServerBackend, name: &str, patch: serde_json::Value, field_manager: Option<&str>,
+) -> Result<ConfigMap, APIError> {
+    let req = KubeAPIRequest::PatchRequest(KubePatchRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        name: name.to_string(),
+        namespace: "default".to_string(),
+        obj: DynamicObject::from_kube(serde_json::from_value(patch).unwrap()),
+        field_manager: field_manager.map(|f| f.to_string()),
+    });
+    match backend.handle_request(req, "test").await {
+        KubeAPIResponse::PatchResponse(resp) => resp.res.map(|obj| obj.into_kube().try_parse::<ConfigMap>().unwrap()),
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[tokio::test]
+pub async fn test_patch_merges_into_current_object() {
+    let backend = InMemoryAPIServerBackend::new();
+    let cm: ConfigMap = serde_json::from_value(json!({
+        "metadata": {"name": "cm", "labels": {"a": "1"}, "finalizers": ["anvil.dev/finalizer"]},
+        "data": {"key": "value", "other": "value"},
+    }))
+    .unwrap();
+    let created = backend.create("default", &cm).unwrap();
+
+    let patched = patch_config_map(&backend, "cm", json!({
+        "metadata": {"labels": {"b": "2"}, "finalizers": []},
+        "data": {"key": "new-value", "other": null},
+    }), None).await.unwrap();
+    let labels = patched.metadata.labels.clone().unwrap();
+    assert_eq!(labels.get("a"), Some(&"1".to_string()));
+    assert_eq!(labels.get("b"), Some(&"2".to_string()));
+    assert_eq!(patched.metadata.finalizers, Some(vec![]));
+    assert_eq!(patched.data.clone().unwrap().get("key"), Some(&"new-value".to_string()));
+    assert!(patched.data.clone().unwrap().get("other").is_none());
+    assert_eq!(patched.metadata.uid, created.metadata.uid);
+    assert_ne!(patched.metadata.resource_version, created.metadata.resource_version);
+
+    // Patching the same content again is a noop
+    let repatched = patch_config_map(&backend, "cm", json!({"metadata": {"name": "cm"}, "data": {"key": "new-value"}}), Some("test-manager")).await.unwrap();
+    assert_eq!(repatched.metadata.resource_version, patched.metadata.resource_version);
+
+    // A patch with a stale resource version conflicts
+    assert!(matches!(
+        patch_config_map(&backend, "cm", json!({"metadata": {"resourceVersion": created.metadata.resource_version}}), None).await,
+        Err(APIError::Conflict)
+    ));
+    assert!(matches!(
+        patch_config_map(&backend, "missing", json!({"metadata": {}}), None).await,
+        Err(APIError::ObjectNotFound)
+    ));
+    // A server-side apply creates the missing object, unless it carries a resource version
+    assert!(matches!(
+        patch_config_map(&backend, "missing", json!({"metadata": {"name": "missing", "resourceVersion": "1"}}), Some("test-manager")).await,
+        Err(APIError::ObjectNotFound)
+    ));
+    let applied = patch_config_map(&backend, "missing", json!({"metadata": {"name": "missing"}, "data": {"key": "value"}}), Some("test-manager")).await.unwrap();
+    assert_eq!(applied.metadata.generation, Some(1));
+    assert_eq!(applied.data.unwrap().get("key"), Some(&"value".to_string()));
+    assert!(matches!(
+        patch_config_map(&backend, "cm", json!({"metadata": {"name": "other"}}), None).await,
+        Err(APIError::BadRequest)
+    ));
+}
+
+#[tokio::test]
+pub async fn test_generation_only_changes_with_spec() {
+    let backend = InMemoryAPIServerBackend::new();
+    let created = backend.create("default", &config_map("cm", vec![])).unwrap();
+    assert_eq!(created.metadata.generation, Some(1));
+
+    let relabeled = patch_config_map(&backend, "cm", json!({"metadata": {"labels": {"a": "1"}}}), None).await.unwrap();
+    assert_ne!(relabeled.metadata.resource_version, created.metadata.resource_version);
+    assert_eq!(relabeled.metadata.generation, Some(1));
+
+    let modified = patch_config_map(&backend, "cm", json!({"data": {"key": "new-value"}}), None).await.unwrap();
+    assert_eq!(modified.metadata.generation, Some(2));
+
+    // The generation in the request is ignored
+    let modified = patch_config_map(&backend, "cm", json!({"metadata": {"generation": 10}}), None).await.unwrap();
+    assert_eq!(modified.metadata.generation, Some(2));
+}
+
+#[test]
+pub fn test_cluster_scoped_object_has_no_namespace() {
+    let backend = InMemoryAPIServerBackend::new();
+    let ns: Namespace = serde_json::from_value(json!({"metadata": {"name": "ns"}})).unwrap();
+    let created = backend.create("", &ns).unwrap();
+    assert_eq!(created.metadata.namespace, None);
+    assert_eq!(backend.get::<Namespace>("", "ns").unwrap().metadata.namespace, None);
+    assert_eq!(backend.list::<Namespace>("").len(), 1);
+    assert!(matches!(backend.get::<Namespace>("default", "ns"), Err(APIError::ObjectNotFound)));
+}
+
+fn owner_ref() -> k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference {
+    serde_json::from_value(json!({
+        "apiVersion": "anvil.dev/v1", "kind": "Owner", "name": "owner", "uid": "owner-uid", "controller": true,
+    }))
+    .unwrap()
+}
+
+async fn get_then_update_config_map(
+    backend: &InMemoryAPIServerBackend, cm: ConfigMap, predicate: KubeGetThenPredicate,
+) -> Result<ConfigMap, APIError> {
+    let req = KubeAPIRequest::GetThenUpdateRequest(KubeGetThenUpdateRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        name: cm.metadata.name.clone().unwrap(),
+        namespace: "default".to_string(),
+        owner_ref: OwnerReference::from_kube(owner_ref()),
+        predicate: predicate,
+        obj: DynamicObject::from_kube(serde_json::from_value(serde_json::to_value(cm).unwrap()).unwrap()),
+    });
+    match backend.handle_request(req, "test").await {
+        KubeAPIResponse::GetThenUpdateResponse(resp) => resp.res.map(|obj| obj.into_kube().try_parse::<ConfigMap>().unwrap()),
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[tokio::test]
+pub async fn test_get_then_update_checks_predicate() {
+    let backend = InMemoryAPIServerBackend::new();
+    let mut cm: ConfigMap = serde_json::from_value(json!({
+        "metadata": {"name": "cm", "labels": {"app": "web"}},
+        "data": {"key": "value"},
+    }))
+    .unwrap();
+    cm.metadata.owner_references = Some(vec![owner_ref()]);
+    let created = backend.create("default", &cm).unwrap();
+    let hash = DynamicObject::from_kube(serde_json::from_value(serde_json::to_value(&created).unwrap()).unwrap()).spec_hash();
+
+    let mut new_cm = created.clone();
+    new_cm.data = Some([("key".to_string(), "new-value".to_string())].into());
+    let mut web_labels = StringMap::empty();
+    web_labels.insert("app".to_string(), "web".to_string());
+    let mut db_labels = StringMap::empty();
+    db_labels.insert("app".to_string(), "db".to_string());
+
+    // The update is aborted if the predicate does not hold on the current object
+    for predicate in [
+        KubeGetThenPredicate::LabelsMatch(db_labels),
+        KubeGetThenPredicate::ResourceVersionMatch("0".to_string()),
+        KubeGetThenPredicate::SpecHashMatch("0".to_string()),
+    ] {
+        assert!(matches!(
+            get_then_update_config_map(&backend, new_cm.clone(), predicate).await,
+            Err(APIError::TransactionAbort)
+        ));
+    }
+
+    let updated = get_then_update_config_map(&backend, new_cm.clone(), KubeGetThenPredicate::LabelsMatch(web_labels)).await.unwrap();
+    assert_eq!(updated.data.clone().unwrap().get("key"), Some(&"new-value".to_string()));
+    // The spec has changed since the hash was taken, and so has the resource version
+    assert!(matches!(
+        get_then_update_config_map(&backend, new_cm.clone(), KubeGetThenPredicate::SpecHashMatch(hash)).await,
+        Err(APIError::TransactionAbort)
+    ));
+    assert!(matches!(
+        get_then_update_config_map(&backend, new_cm.clone(), KubeGetThenPredicate::ResourceVersionMatch(created.metadata.resource_version.unwrap())).await,
+        Err(APIError::TransactionAbort)
+    ));
+    assert!(get_then_update_config_map(&backend, new_cm, KubeGetThenPredicate::ResourceVersionMatch(updated.metadata.resource_version.unwrap())).await.is_ok());
+}
+
+#[tokio::test]
+pub async fn test_vreplicaset_reconcile_creates_pods() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs: crds::VReplicaSet = serde_json::from_value(json!({
+        "metadata": {
+            "name": "test",
+            "ownerReferences": [{
+                "apiVersion": "anvil.dev/v1",
+                "kind": "VDeployment",
+                "name": "owner",
+                "uid": "owner-uid",
+                "controller": true,
+            }],
+        },
+        "spec": {
+            "replicas": 3,
+            "selector": {"matchLabels": {"app": "test"}},
+            "template": pod_template(),
+        },
+    }))
+    .unwrap();
+    let vrs = backend.create("default", &vrs).unwrap();
+    let action = reconcile_with_backend::<crds::VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vrs), &backend, Duration::from_secs(30), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+    assert_eq!(action, Action::requeue(Duration::from_secs(30)));
+    assert_eq!(backend.list::<Pod>("default").len(), 3);
+    let vrs = backend.get::<crds::VReplicaSet>("default", "test").unwrap();
+    assert_eq!(vrs.status.unwrap().replicas, 3);
+
+    // A second reconcile observes the existing pods and creates nothing
+    let vrs = backend.get::<crds::VReplicaSet>("default", "test").unwrap();
+    reconcile_with_backend::<crds::VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vrs), &backend, Duration::from_secs(60), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+    assert_eq!(backend.list::<Pod>("default").len(), 3);
+}
+
+#[tokio::test]
+pub async fn test_vdeployment_and_vreplicaset_reconcile_create_pods() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vd: crds::VDeployment = serde_json::from_value(json!({
+        "metadata": {"name": "test"},
+        "spec": {
+            "replicas": 2,
+            "selector": {"matchLabels": {"app": "test"}},
+            "template": pod_template(),
+        },
+    }))
+    .unwrap();
+    let vd = backend.create("default", &vd).unwrap();
+    reconcile_with_backend::<crds::VDeployment, VDeploymentReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vd), &backend, Duration::from_secs(60), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+    let vrs_list = backend.list::<crds::VReplicaSet>("default");
+    assert_eq!(vrs_list.len(), 1);
+    reconcile_with_backend::<crds::VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vrs_list[0].clone()), &backend, Duration::from_secs(60), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+    assert_eq!(backend.list::<Pod>("default").len(), 2);
+}
+
+#[tokio::test]
+pub async fn test_vstatefulset_reconcile_creates_pods() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vsts: crds::VStatefulSet = serde_json::from_value(json!({
+        "metadata": {"name": "test"},
+        "spec": {
+            "serviceName": "test",
+            "replicas": 2,
+            "selector": {"matchLabels": {"app": "test"}},
+            "template": pod_template(),
+        },
+    }))
+    .unwrap();
+    let vsts = backend.create("default", &vsts).unwrap();
+    reconcile_with_backend::<crds::VStatefulSet, VStatefulSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vsts), &backend, Duration::from_secs(60), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+    assert_eq!(backend.list::<Pod>("default").len(), 2);
+}
+
+#[tokio::test]
+pub async fn test_rabbitmq_reconcile_creates_stateful_set() {
+    let backend = InMemoryAPIServerBackend::new();
+    let rabbitmq: crds::RabbitmqCluster = serde_json::from_value(json!({
+        "metadata": {"name": "test"},
+        "spec": {"replicas": 3, "image": "rabbitmq:3.11.10-management"},
+    }))
+    .unwrap();
+    let rabbitmq = backend.create("default", &rabbitmq).unwrap();
+    reconcile_with_backend::<crds::RabbitmqCluster, RabbitmqReconciler, VoidExternalShimLayer, _>(
+        Arc::new(rabbitmq), &backend, Duration::from_secs(60), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+    let sts_list = backend.list::<StatefulSet>("default");
+    assert_eq!(sts_list.len(), 1);
+    assert_eq!(sts_list[0].spec.as_ref().unwrap().replicas, Some(3));
+}
+
+// The tests below check that the backend follows the branches of handle_*_request in
+// crate::kubernetes_cluster::spec::api_server::state_machine (see the comments on InMemoryAPIServerBackend).
+
+async fn update(backend: &InMemoryAPIServerBackend, api_resource: ApiResource, name: &str, obj: serde_json::Value, status: bool) -> Result<kube::api::DynamicObject, APIError> {
+    let obj = DynamicObject::from_kube(serde_json::from_value(obj).unwrap());
+    let req = if status {
+        KubeAPIRequest::UpdateStatusRequest(KubeUpdateStatusRequest {
+            api_resource: api_resource, name: name.to_string(), namespace: "default".to_string(), obj: obj,
+        })
+    } else {
+        KubeAPIRequest::UpdateRequest(KubeUpdateRequest {
+            api_resource: api_resource, name: name.to_string(), namespace: "default".to_string(), obj: obj,
+        })
+    };
+    match backend.handle_request(req, "test").await {
+        KubeAPIResponse::UpdateResponse(resp) => resp.res.map(|obj| obj.into_kube()),
+        KubeAPIResponse::UpdateStatusResponse(resp) => resp.res.map(|obj| obj.into_kube()),
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[tokio::test]
+pub async fn test_update_conforms_to_update_request_admission_check() {
+    let backend = InMemoryAPIServerBackend::new();
+    let created = backend.create("default", &config_map("cm", vec![])).unwrap();
+    let rv = created.metadata.resource_version.clone().unwrap();
+    let uid = created.metadata.uid.clone().unwrap();
+
+    // The name in the object does not match the name sent on the request
+    assert!(matches!(
+        update(&backend, ConfigMapWrapper::api_resource(), "cm", json!({"metadata": {"name": "other"}}), false).await,
+        Err(APIError::BadRequest)
+    ));
+    // The namespace in the object does not match the namespace sent on the request
+    assert!(matches!(
+        update(&backend, ConfigMapWrapper::api_resource(), "cm", json!({"metadata": {"name": "cm", "namespace": "other"}}), false).await,
+        Err(APIError::BadRequest)
+    ));
+    // The object does not exist
+    assert!(matches!(
+        update(&backend, ConfigMapWrapper::api_resource(), "missing", json!({"metadata": {"name": "missing"}}), false).await,
+        Err(APIError::ObjectNotFound)
+    ));
+    // The object has a wrong resource version or uid
+    assert!(matches!(
+        update(&backend, ConfigMapWrapper::api_resource(), "cm", json!({"metadata": {"name": "cm", "resourceVersion": "100"}}), false).await,
+        Err(APIError::Conflict)
+    ));
+    assert!(matches!(
+        update(&backend, ConfigMapWrapper::api_resource(), "cm", json!({"metadata": {"name": "cm", "uid": "100"}}), false).await,
+        Err(APIError::Conflict)
+    ));
+    // Unconditional update is allowed for the builtin kinds, and a noop update keeps the resource version
+    let updated = update(&backend, ConfigMapWrapper::api_resource(), "cm", json!({
+        "metadata": {"name": "cm"}, "data": {"key": "value"},
+    }), false).await.unwrap();
+    assert_eq!(updated.metadata.resource_version, Some(rv.clone()));
+    let updated = update(&backend, ConfigMapWrapper::api_resource(), "cm", json!({
+        "metadata": {"name": "cm", "resourceVersion": rv, "uid": uid}, "data": {"key": "new-value"},
+    }), false).await.unwrap();
+    assert_ne!(updated.metadata.resource_version, Some(rv));
+}
+
+#[tokio::test]
+pub async fn test_unconditional_update_of_custom_resource_is_invalid() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs: crds::VReplicaSet = serde_json::from_value(json!({
+        "metadata": {"name": "test"},
+        "spec": {"replicas": 1, "selector": {"matchLabels": {"app": "test"}}, "template": pod_template()},
+    }))
+    .unwrap();
+    let created = backend.create("default", &vrs).unwrap();
+    let api_resource = ApiResource::from_kube(kube::api::ApiResource::erase::<crds::VReplicaSet>(&()));
+    let mut obj = serde_json::to_value(&created).unwrap();
+    obj["metadata"]["resourceVersion"] = serde_json::Value::Null;
+    assert!(matches!(
+        update(&backend, api_resource, "test", obj, false).await,
+        Err(APIError::Invalid)
+    ));
+}
+
+#[tokio::test]
+pub async fn test_update_and_update_status_touch_disjoint_fields() {
+    let backend = InMemoryAPIServerBackend::new();
+    let created = backend.create("default", &pod("pod", "test")).unwrap();
+
+    // handle_update_request ignores any change to status
+    let updated = update(&backend, PodWrapper::api_resource(), "pod", json!({
+        "metadata": {"name": "pod", "labels": {"app": "other"}},
+        "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+        "status": {"phase": "Running"},
+    }), false).await.unwrap();
+    assert_ne!(updated.metadata.resource_version, created.metadata.resource_version);
+    assert!(updated.data.get("status").is_none());
+
+    // handle_update_status_request ignores any change to metadata and spec
+    let status_updated = update(&backend, PodWrapper::api_resource(), "pod", json!({
+        "metadata": {"name": "pod", "labels": {"app": "test"}},
+        "status": {"phase": "Running"},
+    }), true).await.unwrap();
+    assert_eq!(status_updated.data["status"]["phase"], json!("Running"));
+    assert_eq!(status_updated.metadata.labels, updated.metadata.labels);
+    assert_eq!(status_updated.data["spec"], updated.data["spec"]);
+}
+
+#[tokio::test]
+pub async fn test_delete_conforms_to_handle_delete_request() {
+    let backend = InMemoryAPIServerBackend::new();
+    let created = backend.create("default", &config_map("cm", vec![])).unwrap();
+    let delete = |name: &str, uid: Option<String>| KubeAPIRequest::DeleteRequest(KubeDeleteRequest {
+        api_resource: ConfigMapWrapper::api_resource(),
+        name: name.to_string(),
+        namespace: "default".to_string(),
+        preconditions: uid.map(|uid| Preconditions::from_kube(kube::api::Preconditions { uid: Some(uid), resource_version: None })),
+        propagation_policy: None,
+    });
+    let res = |resp: KubeAPIResponse| match resp {
+        KubeAPIResponse::DeleteResponse(resp) => resp.res,
+        _ => panic!("unexpected response"),
+    };
+
+    // The object does not exist
+    assert!(matches!(res(backend.handle_request(delete("missing", None), "test").await), Err(APIError::ObjectNotFound)));
+    // The uid in the preconditions does not match
+    assert!(matches!(res(backend.handle_request(delete("cm", Some("100".to_string())), "test").await), Err(APIError::Conflict)));
+    assert!(backend.get::<ConfigMap>("default", "cm").is_ok());
+    // An object without finalizers is removed right away
+    assert!(res(backend.handle_request(delete("cm", created.metadata.uid.clone()), "test").await).is_ok());
+    assert!(matches!(backend.get::<ConfigMap>("default", "cm"), Err(APIError::ObjectNotFound)));
+}
+
+// UnavailableBackend fails to read the custom resource, as an API server that cannot be reached does.
+struct UnavailableBackend {}
+
+impl APIServerBackend for UnavailableBackend {
+    async fn get_cr<K>(&self, _namespace: &str, _name: &str) -> Result<K, APIError>
+    where
+        K: Clone + kube::Resource + serde::de::DeserializeOwned + std::fmt::Debug + Send,
+        K::Scope: crate::shim_layer::controller_runtime::ApiScope,
+        K::DynamicType: Default,
+    {
+        Err(APIError::Other)
+    }
+
+    async fn handle_request(&self, _req: KubeAPIRequest, _log_header: &str) -> KubeAPIResponse {
+        panic!("no request is sent after the custom resource cannot be read")
+    }
+}
+
+#[tokio::test]
+pub async fn test_failed_read_of_custom_resource_is_an_error() {
+    let vrs: crds::VReplicaSet = serde_json::from_value(json!({
+        "metadata": {"name": "test", "namespace": "default"},
+        "spec": {"replicas": 1, "selector": {"matchLabels": {"app": "test"}}, "template": pod_template()},
+    }))
+    .unwrap();
+    // The failure is returned as an error (rather than a requeue) so that error_policy backs off
+    let res = reconcile_with_backend::<crds::VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vrs), &UnavailableBackend {}, Duration::from_secs(30), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await;
+    assert!(matches!(res, Err(crate::crds::Error::ShimLayerError(_))));
+}
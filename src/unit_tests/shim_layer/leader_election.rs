@@ -0,0 +1,116 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::in_memory_backend::*;
+use crate::shim_layer::leader_election::*;
+use k8s_openapi::api::coordination::v1::{Lease, LeaseSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta};
+use std::time::Duration;
+
+fn config(identity: &str) -> LeaderElectionConfig {
+    LeaderElectionConfig {
+        lease_duration: Duration::from_secs(1),
+        renew_deadline: Duration::from_millis(500),
+        retry_period: Duration::from_millis(50),
+        ..LeaderElectionConfig::new("test-controller", "default", identity)
+    }
+}
+
+fn holder(backend: &InMemoryAPIServerBackend) -> Option<String> {
+    backend
+        .get::<Lease>("default", "test-controller")
+        .unwrap()
+        .spec
+        .unwrap()
+        .holder_identity
+}
+
+#[tokio::test]
+pub async fn test_only_one_candidate_acquires_lease() {
+    let backend = InMemoryAPIServerBackend::new();
+    let a = LeaderElector::new(&backend, config("a"));
+    let b = LeaderElector::new(&backend, config("b"));
+    assert_eq!(a.try_acquire_or_renew().await.unwrap(), true);
+    assert_eq!(b.try_acquire_or_renew().await.unwrap(), false);
+    assert_eq!(a.try_acquire_or_renew().await.unwrap(), true);
+    assert_eq!(holder(&backend), Some("a".to_string()));
+}
+
+#[tokio::test]
+pub async fn test_expired_lease_is_taken_over() {
+    let backend = InMemoryAPIServerBackend::new();
+    let a = LeaderElector::new(&backend, config("a"));
+    let b = LeaderElector::new(&backend, config("b"));
+    assert_eq!(a.try_acquire_or_renew().await.unwrap(), true);
+    // b starts the expiry clock when it first observes the lease held by a
+    assert_eq!(b.try_acquire_or_renew().await.unwrap(), false);
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert_eq!(b.try_acquire_or_renew().await.unwrap(), true);
+    assert_eq!(a.try_acquire_or_renew().await.unwrap(), false);
+    let lease = backend.get::<Lease>("default", "test-controller").unwrap();
+    assert_eq!(lease.spec.unwrap().lease_transitions, Some(1));
+}
+
+#[tokio::test]
+pub async fn test_lease_expiry_ignores_holder_clock_skew() {
+    let backend = InMemoryAPIServerBackend::new();
+    // The holder's clock is far behind, so the renew time it writes looks expired to the other candidates
+    let lease = Lease {
+        metadata: ObjectMeta {
+            name: Some("test-controller".to_string()),
+            ..ObjectMeta::default()
+        },
+        spec: Some(LeaseSpec {
+            holder_identity: Some("b".to_string()),
+            lease_duration_seconds: Some(1),
+            renew_time: Some(MicroTime(chrono::Utc::now() - chrono::Duration::hours(1))),
+            ..LeaseSpec::default()
+        }),
+    };
+    backend.create("default", &lease).unwrap();
+    let a = LeaderElector::new(&backend, config("a"));
+    assert_eq!(a.try_acquire_or_renew().await.unwrap(), false);
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(a.try_acquire_or_renew().await.unwrap(), false);
+    assert_eq!(holder(&backend), Some("b".to_string()));
+    // Without renewals, the lease expires one lease duration after a first observed it
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(a.try_acquire_or_renew().await.unwrap(), true);
+    assert_eq!(holder(&backend), Some("a".to_string()));
+}
+
+#[tokio::test]
+pub async fn test_run_releases_lease_when_done() {
+    let backend = InMemoryAPIServerBackend::new();
+    let a = LeaderElector::new(&backend, config("a"));
+    let b = LeaderElector::new(&backend, config("b"));
+    assert_eq!(a.run(async { 42 }).await, Some(42));
+    assert_eq!(holder(&backend), None);
+    assert_eq!(b.try_acquire_or_renew().await.unwrap(), true);
+}
+
+#[tokio::test]
+pub async fn test_run_steps_down_when_lease_is_lost() {
+    let backend = InMemoryAPIServerBackend::new();
+    let a = LeaderElector::new(&backend, config("a"));
+    let steal_lease = async {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        // Another candidate forcibly takes over the lease
+        backend.delete::<Lease>("default", "test-controller").unwrap();
+        let lease = Lease {
+            metadata: ObjectMeta {
+                name: Some("test-controller".to_string()),
+                ..ObjectMeta::default()
+            },
+            spec: Some(LeaseSpec {
+                holder_identity: Some("b".to_string()),
+                lease_duration_seconds: Some(60),
+                renew_time: Some(MicroTime(chrono::Utc::now())),
+                ..LeaseSpec::default()
+            }),
+        };
+        backend.create("default", &lease).unwrap();
+    };
+    let (res, _) = tokio::join!(a.run(std::future::pending::<()>()), steal_lease);
+    assert_eq!(res, None);
+    assert_eq!(holder(&backend), Some("b".to_string()));
+}
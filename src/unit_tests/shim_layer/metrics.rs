@@ -0,0 +1,42 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds::Error;
+use crate::kubernetes_api_objects::error::*;
+use crate::shim_layer::metrics::*;
+use std::time::Duration;
+
+// The registry is shared by the whole process, so each test uses its own kind label.
+
+#[test]
+pub fn test_render_reconcile_metrics() {
+    observe_reconcile::<()>("MetricsTestReconcile", 3, &Err(Error::ReconcileCoreError));
+    observe_reconcile("MetricsTestReconcile", 1, &Ok(()));
+    let out = render();
+    assert!(out.contains("# TYPE anvil_reconciles_total counter"));
+    assert!(out.contains("anvil_reconciles_total{kind=\"MetricsTestReconcile\"} 2"));
+    assert!(out.contains(
+        "anvil_reconcile_failures_total{kind=\"MetricsTestReconcile\",error=\"ReconcileCoreError\"} 1"
+    ));
+    assert!(out.contains("anvil_reconcile_core_iterations_bucket{kind=\"MetricsTestReconcile\",le=\"2\"} 1"));
+    assert!(out.contains("anvil_reconcile_core_iterations_bucket{kind=\"MetricsTestReconcile\",le=\"4\"} 2"));
+    assert!(out.contains("anvil_reconcile_core_iterations_bucket{kind=\"MetricsTestReconcile\",le=\"+Inf\"} 2"));
+    assert!(out.contains("anvil_reconcile_core_iterations_sum{kind=\"MetricsTestReconcile\"} 4"));
+}
+
+#[test]
+pub fn test_render_api_request_metrics() {
+    observe_api_request("MetricsTestRequest", "Update", None, Duration::from_millis(20));
+    observe_api_request("MetricsTestRequest", "Update", Some(&APIError::Conflict), Duration::from_millis(200));
+    inc_conflict_retry("MetricsTestRequest", "MetricsTestObject", "GetThenUpdate");
+    let out = render();
+    assert!(out.contains(
+        "anvil_api_request_duration_seconds_bucket{kind=\"MetricsTestRequest\",verb=\"Update\",le=\"0.025\"} 1"
+    ));
+    assert!(out.contains("anvil_api_request_duration_seconds_count{kind=\"MetricsTestRequest\",verb=\"Update\"} 2"));
+    assert!(out.contains(
+        "anvil_api_request_errors_total{kind=\"MetricsTestRequest\",verb=\"Update\",error=\"Conflict\"} 1"
+    ));
+    assert!(out.contains(
+        "anvil_api_conflict_retries_total{kind=\"MetricsTestRequest\",object_kind=\"MetricsTestObject\",verb=\"GetThenUpdate\"} 1"
+    ));
+}
@@ -0,0 +1,149 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds;
+use crate::external_shim_layer::VoidExternalShimLayer;
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, config_map::ConfigMap as ConfigMapWrapper, dynamic::DynamicObject, resource::*,
+};
+use crate::shim_layer::backend::APIServerBackend;
+use crate::shim_layer::controller_runtime::reconcile_with_backend;
+use crate::shim_layer::in_memory_backend::*;
+use crate::shim_layer::plan::*;
+use crate::vreplicaset_controller::exec::reconciler::VReplicaSetReconciler;
+use k8s_openapi::api::core::v1::{Node, Pod};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+pub async fn test_plan_does_not_change_cluster() {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs: crds::VReplicaSet = serde_json::from_value(json!({
+        "metadata": {
+            "name": "test",
+            "ownerReferences": [{
+                "apiVersion": "anvil.dev/v1",
+                "kind": "VDeployment",
+                "name": "owner",
+                "uid": "owner-uid",
+                "controller": true,
+            }],
+        },
+        "spec": {
+            "replicas": 2,
+            "selector": {"matchLabels": {"app": "test"}},
+            "template": {
+                "metadata": {"labels": {"app": "test"}},
+                "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+            },
+        },
+    }))
+    .unwrap();
+    let vrs = backend.create("default", &vrs).unwrap();
+    let plan_backend = PlanBackend::new(&backend, None);
+    reconcile_with_backend::<crds::VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vrs), &plan_backend, Duration::from_secs(30), None, None,
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+    let changes = plan_backend.into_changes();
+    assert_eq!(backend.list::<Pod>("default").len(), 0);
+
+    let creates: Vec<&PlannedChange> = changes.iter().filter(|change| change.verb == "Create").collect();
+    assert_eq!(creates.len(), 2);
+    assert!(creates.iter().all(|change| change.key.starts_with("Pod/default/") && change.error.is_none()));
+    assert!(creates[0].diff.iter().any(|line| line.starts_with("+ spec: ")));
+    assert!(format_plan(&changes).starts_with("1. Create Pod/default/"));
+}
+
+#[tokio::test]
+pub async fn test_plan_simulates_apply_of_absent_object_as_create() {
+    let backend = InMemoryAPIServerBackend::new();
+    let plan_backend = PlanBackend::new(&backend, None);
+    let patch = |field_manager: Option<&str>| {
+        KubeAPIRequest::PatchRequest(KubePatchRequest {
+            api_resource: ConfigMapWrapper::api_resource(),
+            name: "cm".to_string(),
+            namespace: "default".to_string(),
+            obj: DynamicObject::from_kube(
+                serde_json::from_value(json!({
+                    "apiVersion": "v1",
+                    "kind": "ConfigMap",
+                    "metadata": {"name": "cm"},
+                    "data": {"key": "value"},
+                }))
+                .unwrap(),
+            ),
+            field_manager: field_manager.map(|f| f.to_string()),
+        })
+    };
+    match plan_backend.handle_request(patch(Some("test")), "test").await {
+        KubeAPIResponse::PatchResponse(resp) => assert_eq!(resp.res.unwrap().into_kube().metadata.namespace, Some("default".to_string())),
+        _ => panic!("unexpected response"),
+    }
+    // a merge patch still needs the object to exist
+    match plan_backend.handle_request(patch(None), "test").await {
+        KubeAPIResponse::PatchResponse(resp) => assert!(resp.res.is_err()),
+        _ => panic!("unexpected response"),
+    }
+    let changes = plan_backend.into_changes();
+    assert!(changes[0].error.is_none());
+    assert!(changes[0].diff.contains(&"+ data: {\"key\":\"value\"}".to_string()));
+    assert_eq!(changes[1].error, Some("ObjectNotFound".to_string()));
+}
+
+#[test]
+pub fn test_diff_lists_changed_fields() {
+    let before = json!({
+        "metadata": {"name": "cm", "resourceVersion": "1", "labels": {"a": "1", "b": "2"}},
+        "data": {"key": "value"},
+    });
+    let after = json!({
+        "metadata": {"name": "cm", "resourceVersion": "2", "labels": {"a": "1", "c": "3"}},
+        "data": {"key": "new-value"},
+    });
+    assert_eq!(
+        diff(&before, &after),
+        vec![
+            "~ data.key: \"value\" -> \"new-value\"".to_string(),
+            "- metadata.labels.b: \"2\"".to_string(),
+            "+ metadata.labels.c: \"3\"".to_string(),
+        ]
+    );
+    assert!(diff(&before, &before).is_empty());
+}
+
+#[test]
+pub fn test_format_plan() {
+    let changes = vec![
+        PlannedChange {
+            verb: "Update",
+            key: "ConfigMap/default/cm".to_string(),
+            error: None,
+            diff: vec!["~ data.key: \"value\" -> \"new-value\"".to_string()],
+        },
+        PlannedChange {
+            verb: "Delete",
+            key: "Pod/default/pod".to_string(),
+            error: Some("Conflict".to_string()),
+            diff: Vec::new(),
+        },
+    ];
+    assert_eq!(
+        format_plan(&changes),
+        "1. Update ConfigMap/default/cm\n   ~ data.key: \"value\" -> \"new-value\"\n2. Delete Pod/default/pod\n   (fails with Conflict)\n"
+    );
+    assert_eq!(format_plan(&[]), "No changes.\n");
+}
+
+#[test]
+pub fn test_plan_key() {
+    assert_eq!(
+        plan_key::<crds::VReplicaSet>("default/test").unwrap(),
+        ("default".to_string(), "test".to_string())
+    );
+    assert!(plan_key::<crds::VReplicaSet>("test").is_err());
+    assert_eq!(plan_key::<Node>("node-1").unwrap(), (String::new(), "node-1".to_string()));
+    assert!(plan_key::<Node>("default/node-1").is_err());
+}
@@ -0,0 +1,136 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, api_resource::ApiResource, config_map::ConfigMap as ConfigMapWrapper, label_selector::*, pod::Pod as PodWrapper,
+    resource::*,
+};
+use crate::shim_layer::backend::APIServerBackend;
+use crate::shim_layer::in_memory_backend::*;
+use crate::shim_layer::read_cache::*;
+use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+use kube::runtime::{reflector::store::Writer, watcher};
+use serde_json::json;
+use std::time::Duration;
+
+fn pod(name: &str, app: &str) -> Pod {
+    serde_json::from_value(json!({
+        "apiVersion": "v1",
+        "kind": "Pod",
+        "metadata": {"name": name, "namespace": "default", "labels": {"app": app}},
+        "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+    }))
+    .unwrap()
+}
+
+// pod_writer returns a writer whose store has received the initial list of pods.
+fn pod_writer(pods: Vec<Pod>) -> Writer<Pod> {
+    let mut writer = Writer::<Pod>::default();
+    writer.apply_watcher_event(&watcher::Event::Restarted(pods));
+    writer
+}
+
+async fn get(
+    backend: &impl APIServerBackend,
+    api_resource: ApiResource,
+    name: &str,
+    read_mode: KubeReadMode,
+) -> Result<(), APIError> {
+    let req = KubeAPIRequest::GetRequest(KubeGetRequest {
+        api_resource: api_resource,
+        name: name.to_string(),
+        namespace: "default".to_string(),
+        read_mode: read_mode,
+    });
+    match backend.handle_request(req, "test").await {
+        KubeAPIResponse::GetResponse(resp) => resp.res.map(|_obj| ()),
+        _ => panic!("unexpected response"),
+    }
+}
+
+async fn list_pods(backend: &impl APIServerBackend, label_selector: Option<LabelSelector>) -> Result<usize, APIError> {
+    let req = KubeAPIRequest::ListRequest(KubeListRequest {
+        api_resource: PodWrapper::api_resource(),
+        namespace: "default".to_string(),
+        label_selector: label_selector,
+        field_selector: None,
+        read_mode: KubeReadMode::Cached,
+        limit: None,
+        continue_token: None,
+    });
+    match backend.handle_request(req, "test").await {
+        KubeAPIResponse::ListResponse(resp) => resp.res.map(|objs| objs.len()),
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[tokio::test]
+pub async fn test_get_is_served_from_cache() {
+    let inner = InMemoryAPIServerBackend::new();
+    inner.create("default", &pod("live", "web")).unwrap();
+    let writer = pod_writer(vec![pod("cached", "web")]);
+    let mut cache = ReadCache::new();
+    cache.insert(&None, writer.as_reader());
+    let backend = CachedBackend { inner: &inner, cache: Some(&cache) };
+
+    assert!(get(&backend, PodWrapper::api_resource(), "cached", KubeReadMode::Cached).await.is_ok());
+    // The pod that only exists in the inner backend has not reached the cache
+    assert!(matches!(
+        get(&backend, PodWrapper::api_resource(), "live", KubeReadMode::Cached).await,
+        Err(APIError::ObjectNotFound)
+    ));
+    // Quorum reads and the read of the custom resource always go to the inner backend
+    assert!(get(&backend, PodWrapper::api_resource(), "live", KubeReadMode::Quorum).await.is_ok());
+    assert!(matches!(
+        get(&backend, PodWrapper::api_resource(), "cached", KubeReadMode::Quorum).await,
+        Err(APIError::ObjectNotFound)
+    ));
+    assert!(backend.get_cr::<Pod>("default", "live").await.is_ok());
+
+    // ConfigMaps are not watched, so they are read from the inner backend
+    let cm: ConfigMap = serde_json::from_value(json!({"metadata": {"name": "cm"}})).unwrap();
+    inner.create("default", &cm).unwrap();
+    assert!(get(&backend, ConfigMapWrapper::api_resource(), "cm", KubeReadMode::Cached).await.is_ok());
+}
+
+#[tokio::test]
+pub async fn test_list_is_served_from_cache() {
+    let inner = InMemoryAPIServerBackend::new();
+    let writer = pod_writer(vec![pod("a", "web"), pod("b", "web"), pod("c", "db")]);
+    let mut cache = ReadCache::new();
+    cache.insert(&Some("default".to_string()), writer.as_reader());
+    let backend = CachedBackend { inner: &inner, cache: Some(&cache) };
+
+    assert_eq!(list_pods(&backend, None).await.unwrap(), 3);
+    let web = LabelSelector::from_kube(serde_json::from_value(json!({"matchLabels": {"app": "web"}})).unwrap());
+    assert_eq!(list_pods(&backend, Some(web)).await.unwrap(), 2);
+    // Match expressions are not evaluated by the cache, so the list goes to the inner backend
+    let expr = LabelSelector::from_kube(
+        serde_json::from_value(json!({"matchExpressions": [{"key": "app", "operator": "Exists"}]})).unwrap(),
+    );
+    assert_eq!(list_pods(&backend, Some(expr)).await.unwrap(), 0);
+}
+
+#[tokio::test]
+pub async fn test_reads_go_to_inner_backend_without_cache() {
+    let inner = InMemoryAPIServerBackend::new();
+    inner.create("default", &pod("live", "web")).unwrap();
+    let backend = CachedBackend { inner: &inner, cache: None };
+    assert!(get(&backend, PodWrapper::api_resource(), "live", KubeReadMode::Cached).await.is_ok());
+    assert_eq!(list_pods(&backend, None).await.unwrap(), 1);
+}
+
+#[tokio::test]
+pub async fn test_reads_go_to_inner_backend_if_cache_is_not_ready() {
+    let inner = InMemoryAPIServerBackend::new();
+    inner.create("default", &pod("live", "web")).unwrap();
+    // The writer is kept alive without the initial list, so the store never becomes ready
+    let writer = Writer::<Pod>::default();
+    let mut cache = ReadCache::new();
+    cache.insert(&None, writer.as_reader());
+    cache.ready_timeout = Duration::from_millis(10);
+    let backend = CachedBackend { inner: &inner, cache: Some(&cache) };
+    assert!(get(&backend, PodWrapper::api_resource(), "live", KubeReadMode::Cached).await.is_ok());
+    assert_eq!(list_pods(&backend, None).await.unwrap(), 1);
+    drop(writer);
+}
@@ -0,0 +1,128 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::crds;
+use crate::external_shim_layer::VoidExternalShimLayer;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::api_method::*;
+use crate::reconciler::exec::io::*;
+use crate::shim_layer::controller_runtime::reconcile_with_backend;
+use crate::shim_layer::in_memory_backend::*;
+use crate::shim_layer::trace::*;
+use crate::vreplicaset_controller::exec::reconciler::VReplicaSetReconciler;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn trace_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("anvil-{}-{}.jsonl", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+async fn record_vreplicaset_reconcile(path: &PathBuf) {
+    let backend = InMemoryAPIServerBackend::new();
+    let vrs: crds::VReplicaSet = serde_json::from_value(json!({
+        "metadata": {
+            "name": "test",
+            "ownerReferences": [{
+                "apiVersion": "anvil.dev/v1",
+                "kind": "VDeployment",
+                "name": "owner",
+                "uid": "owner-uid",
+                "controller": true,
+            }],
+        },
+        "spec": {
+            "replicas": 2,
+            "selector": {"matchLabels": {"app": "test"}},
+            "template": {
+                "metadata": {"labels": {"app": "test"}},
+                "spec": {"containers": [{"name": "nginx", "image": "nginx:1.25"}]},
+            },
+        },
+    }))
+    .unwrap();
+    let vrs = backend.create("default", &vrs).unwrap();
+    let recorder = TraceRecorder::create(path).unwrap();
+    reconcile_with_backend::<crds::VReplicaSet, VReplicaSetReconciler, VoidExternalShimLayer, _>(
+        Arc::new(vrs), &backend, Duration::from_secs(60), None, Some(&recorder),
+        &VoidExternalShimLayer {}, Duration::from_secs(30),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_record_and_replay() {
+    let path = trace_file("test-record-and-replay");
+    record_vreplicaset_reconcile(&path).await;
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let trace: ReconcileTrace = serde_json::from_str(content.lines().next().unwrap()).unwrap();
+    assert_eq!(trace.cr_key, "VReplicaSet/default/test");
+    assert_eq!(trace.outcome, TraceOutcome::Done);
+    assert_eq!(trace.steps[0].reconcile_step, "Init");
+    assert_eq!(trace.steps[0].request.as_ref().unwrap()["verb"], "List");
+    // One list, two creates and one status update
+    assert_eq!(trace.steps.len(), 4);
+
+    assert_eq!(
+        replay_file::<crds::VReplicaSet, VReplicaSetReconciler>(&path).unwrap(),
+        1
+    );
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+pub async fn test_replay_detects_divergence() {
+    let path = trace_file("test-replay-detects-divergence");
+    record_vreplicaset_reconcile(&path).await;
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let trace: ReconcileTrace = serde_json::from_str(content.lines().next().unwrap()).unwrap();
+    // With one fewer replica, reconcile_core updates the status where the trace has another create
+    let mut diverged = trace.clone();
+    diverged.cr["spec"]["replicas"] = json!(1);
+    assert!(replay::<crds::VReplicaSet, VReplicaSetReconciler>(&diverged).is_err());
+
+    // A truncated trace ends before reconcile_core is done
+    let mut truncated = trace.clone();
+    truncated.steps.pop();
+    assert!(replay::<crds::VReplicaSet, VReplicaSetReconciler>(&truncated).is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+pub fn test_response_json_round_trip() {
+    let resp = KubeAPIResponse::GetThenDeleteResponse(KubeGetThenDeleteResponse {
+        res: Err(APIError::TransactionAbort),
+    });
+    let json = response_to_json(&resp);
+    assert_eq!(json, json!({"verb": "GetThenDelete", "err": "TransactionAbort"}));
+    assert_eq!(response_to_json(&response_from_json(&json).unwrap()), json);
+
+    let resp = KubeAPIResponse::ListResponse(KubeListResponse { res: Ok(Vec::new()), continue_token: None });
+    let json = response_to_json(&resp);
+    assert_eq!(json, json!({"verb": "List", "ok": []}));
+    assert_eq!(response_to_json(&response_from_json(&json).unwrap()), json);
+
+    let resp = KubeAPIResponse::ListResponse(KubeListResponse { res: Ok(Vec::new()), continue_token: Some("token".to_string()) });
+    let json = response_to_json(&resp);
+    assert_eq!(json, json!({"verb": "List", "ok": [], "continue": "token"}));
+    assert_eq!(response_to_json(&response_from_json(&json).unwrap()), json);
+
+    assert!(response_from_json(&json!({"verb": "Get", "err": "Unknown"})).is_err());
+
+    let json = external_response_to_json(&Ok(VoidEResp {}));
+    assert_eq!(json, json!({"verb": "External", "ok": {}}));
+    assert!(matches!(external_response_from_json::<VoidEResp>(&json).unwrap(), Ok(_)));
+    let json = external_response_to_json::<VoidEResp>(&Err(ExternalError::Timeout));
+    assert_eq!(json, json!({"verb": "External", "err": "Timeout"}));
+    assert!(matches!(external_response_from_json::<VoidEResp>(&json).unwrap(), Err(ExternalError::Timeout)));
+    assert!(external_response_from_json::<VoidEResp>(&json!({"verb": "Get", "err": "Timeout"})).is_err());
+    assert_eq!(
+        request_json(&Request::ExternalRequest(VoidEReq {})),
+        json!({"verb": "External", "req": {}})
+    );
+}